@@ -65,8 +65,20 @@ pub enum Statement {
         /// The source span of the whole `throw` statement.
         range: SourceRange,
     },
+    /// `yield expr` — hand `expr` to the consumer of the enclosing generator
+    /// and suspend until the next value is asked for. Legal only inside a
+    /// block body, which includes a `@generator` method's body.
+    Yield {
+        /// The yielded expression.
+        expr: Expr,
+        /// The source span of the whole `yield` statement.
+        range: SourceRange,
+    },
     /// Local export declaration in the module body: `export Name, Other as Alias`.
     Export(ExportDecl),
+    /// `extend Name { members }` — methods added to an existing class, seen
+    /// only by sends compiled in this module and in modules that import it.
+    Extend(ExtendDef),
 }
 
 // ── Logical Import / Export / Preamble AST ────────────────────────────────
@@ -266,6 +278,20 @@ pub struct ClassDef {
     pub name_range: SourceRange,
 }
 
+/// A top-level `extend Name { members }` block: methods, getters and setters
+/// added to the class `target` names without reopening it.
+///
+/// The block declares no binding of its own. Its members are active in the
+/// declaring module and in every module that imports it directly; a class
+/// member of the same selector always wins over an extension.
+#[derive(Debug, Clone)]
+pub struct ExtendDef {
+    /// The extended class, resolved like a superclass reference.
+    pub target: StaticSymbolRef,
+    pub members: Vec<ClassMember>,
+    pub range: SourceRange,
+}
+
 /// A `@name(args…)` attribute attached to a class or class member.
 ///
 /// Attributes bind to the *following* class member by default (the parser's
@@ -297,6 +323,17 @@ pub enum BuiltinAttr {
     Ignore,
     Private,
     Protected,
+    Memoize,
+    Retry,
+    Lazy,
+    Traced,
+    Synchronized,
+    Mixin,
+    Compose,
+    Protocol,
+    Multimethod,
+    Generator,
+    Async,
 }
 
 impl BuiltinAttr {
@@ -318,6 +355,17 @@ impl BuiltinAttr {
             BuiltinAttr::Ignore => "ignore",
             BuiltinAttr::Private => "private",
             BuiltinAttr::Protected => "protected",
+            BuiltinAttr::Memoize => "memoize",
+            BuiltinAttr::Retry => "retry",
+            BuiltinAttr::Lazy => "lazy",
+            BuiltinAttr::Traced => "traced",
+            BuiltinAttr::Synchronized => "synchronized",
+            BuiltinAttr::Mixin => "mixin",
+            BuiltinAttr::Compose => "compose",
+            BuiltinAttr::Protocol => "protocol",
+            BuiltinAttr::Multimethod => "multimethod",
+            BuiltinAttr::Generator => "generator",
+            BuiltinAttr::Async => "async",
         }
     }
 
@@ -339,6 +387,31 @@ impl BuiltinAttr {
             "ignore" => Some(BuiltinAttr::Ignore),
             "private" => Some(BuiltinAttr::Private),
             "protected" => Some(BuiltinAttr::Protected),
+            "memoize" => Some(BuiltinAttr::Memoize),
+            "retry" => Some(BuiltinAttr::Retry),
+            "lazy" => Some(BuiltinAttr::Lazy),
+            "traced" => Some(BuiltinAttr::Traced),
+            "synchronized" => Some(BuiltinAttr::Synchronized),
+            "mixin" => Some(BuiltinAttr::Mixin),
+            "compose" => Some(BuiltinAttr::Compose),
+            "protocol" => Some(BuiltinAttr::Protocol),
+            "multimethod" => Some(BuiltinAttr::Multimethod),
+            "generator" => Some(BuiltinAttr::Generator),
+            "async" => Some(BuiltinAttr::Async),
+            _ => None,
+        }
+    }
+
+    /// The core `Attribute` subclass a behavioral decorator instantiates at
+    /// runtime — `@memoize(...)` is `Memoize.new(...)` — or `None` for a
+    /// compiler-only builtin.
+    pub fn runtime_class(&self) -> Option<&'static str> {
+        match self {
+            BuiltinAttr::Memoize => Some("Memoize"),
+            BuiltinAttr::Retry => Some("Retry"),
+            BuiltinAttr::Lazy => Some("Lazy"),
+            BuiltinAttr::Traced => Some("Traced"),
+            BuiltinAttr::Synchronized => Some("Synchronized"),
             _ => None,
         }
     }
@@ -364,6 +437,9 @@ pub struct Attribute {
     pub kind: AttrKind,
     pub name: String,
     pub args: Vec<Expr>,
+    /// `label: expr` arguments following the positional ones, in source
+    /// order — only `@cfg(feature: "x")` reads them (see `phalcom_modules::cfg`).
+    pub labeled_args: Vec<(String, Expr)>,
     pub range: SourceRange,
 }
 
@@ -1664,6 +1740,8 @@ pub enum UnaryOp {
     Not,
     /// Prefix `~x` — lowers to the `~` getter send.
     BitNot,
+    /// Prefix `await x` — lowers to the `await` getter send (`Future#await`).
+    Await,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
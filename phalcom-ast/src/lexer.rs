@@ -466,6 +466,8 @@ impl<'input> Lexer<'input> {
                 "construct" => Token::Construct,
                 "throw" => Token::Throw,
                 "try" => Token::Try,
+                "yield" => Token::Yield,
+                "await" => Token::Await,
                 _ => Token::Identifier(slice.to_string()),
             });
         }
@@ -51,6 +51,7 @@ use std::ops::Range;
 /// (DEC-ANNOT-B).
 type ClassBodyParts = (Vec<ClassMember>, Vec<Attribute>, Vec<(Expr, SourceRange)>);
 type SelectorSpecSlots = (Vec<SelectorSlotSyntax>, Vec<SelectorSlotSyntax>, Option<SourceRange>, usize);
+type AttributeArgs = (Vec<Expr>, Vec<(String, Expr)>);
 
 enum ProductLabelStart {
     Computed,
@@ -1055,6 +1056,11 @@ impl<'source> Parser<'source> {
             // A compound statement requires a NEWLINE terminator, not EOF.
             self.expect(&Token::Newline, &["newline"])?;
             Ok(())
+        } else if self.at_extend_header() {
+            let stmt = self.parse_extend()?;
+            out.push(stmt);
+            self.expect(&Token::Newline, &["newline"])?;
+            Ok(())
         } else {
             loop {
                 let stmt = self.parse_small_statement()?;
@@ -1114,6 +1120,7 @@ impl<'source> Parser<'source> {
             Token::For => self.parse_for(),
             Token::Throw => self.parse_throw(),
             Token::Try => self.parse_try(),
+            Token::Yield => self.parse_yield(),
             Token::Export => {
                 let export_decl = self.parse_export_decl()?;
                 Ok(Statement::Export(export_decl))
@@ -1220,6 +1227,15 @@ impl<'source> Parser<'source> {
         Ok(Statement::Throw { expr, range })
     }
 
+    /// Parses `yield expr` — hands `expr` out of the enclosing generator body.
+    fn parse_yield(&mut self) -> ParserResult<Statement> {
+        let start = self.cur_start();
+        self.advance(); // 'yield'
+        let expr = self.parse_expr()?;
+        let range = (start..self.prev_end).into();
+        Ok(Statement::Yield { expr, range })
+    }
+
     /// Parses `try { P } (on T e { … })* (catch e { … })? (ensure { … })?`
     /// straight into the ADR-0031 §3 **nested re-wrapping** desugar over the
     /// `Block` catch protocol (error-handling.md §2):
@@ -1736,6 +1752,7 @@ impl<'source> Parser<'source> {
                 | Token::LParen
                 | Token::LBracket
                 | Token::Not
+                | Token::Await
                 | Token::Minus
                 | Token::LBrace
                 | Token::Pipe
@@ -1809,10 +1826,72 @@ impl<'source> Parser<'source> {
             Token::Construct => "construct",
             Token::Throw => "throw",
             Token::Try => "try",
+            Token::Yield => "yield",
+            Token::Await => "await",
             _ => return None,
         })
     }
 
+    /// Returns `true` at `extend IDENT`. `extend` is contextual: it opens an
+    /// extension block only in top-level position, and stays an ordinary
+    /// identifier everywhere else (a method or local may still be named
+    /// `extend`).
+    fn at_extend_header(&self) -> bool {
+        matches!(self.peek(), Token::Identifier(word) if word == "extend") && matches!(self.peek_next(), Token::Identifier(_))
+    }
+
+    /// Parses `extend Name { members }`, where `Name` is a bare or
+    /// module-qualified class reference and the members use the class-body
+    /// grammar. Which members an extension may declare is the compiler's
+    /// call; only the standalone `@invariant` carve-out is refused here, since
+    /// an extension has no instances of its own to hold one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target, braces or any member is malformed, or
+    /// for an `@invariant` in the body.
+    fn parse_extend(&mut self) -> ParserResult<Statement> {
+        let start = self.cur_start();
+        self.advance(); // contextual 'extend'
+        let target_start = self.cur_start();
+        let root = self.expect_identifier(&["class name"])?;
+        let root_range = (target_start..self.prev_end).into();
+        let mut members = Vec::new();
+        while self.eat(&Token::Dot) {
+            let member_start = self.cur_start();
+            let name = self.expect_identifier(&["qualified class name"])?;
+            members.push(PathSegment {
+                name,
+                range: (member_start..self.prev_end).into(),
+            });
+        }
+        let target = StaticSymbolRef {
+            root,
+            root_range,
+            members,
+            range: (target_start..self.prev_end).into(),
+        };
+
+        self.expect(&Token::LBrace, &["\"{\""])?;
+        let previous_class_context = self.in_class_body;
+        self.in_class_body = true;
+        let body = self.parse_class_body();
+        self.in_class_body = previous_class_context;
+        let (members, _, invariants) = body?;
+        if let Some((_, range)) = invariants.first() {
+            return Err(SyntaxError {
+                kind: SyntaxErrorKind::Message("extend.invariant: an `extend` block cannot declare an `@invariant`".to_string()),
+                range: range.start..range.end,
+            });
+        }
+        self.expect(&Token::RBrace, &["\"}\""])?;
+        Ok(Statement::Extend(ExtendDef {
+            target,
+            members,
+            range: (start..self.prev_end).into(),
+        }))
+    }
+
     // ── Class declarations ───────────────────────────────────────────────────
 
     /// Parses an optional run of `@name(args…) class Name { members }`
@@ -2008,39 +2087,58 @@ impl<'source> Parser<'source> {
         } else {
             self.expect_identifier(&["attribute name"])?
         };
-        let args = if self.eat(&Token::LParen) {
+        let (args, labeled_args) = if self.eat(&Token::LParen) {
             let args = self.parse_attribute_arg_list()?;
             self.expect(&Token::RParen, &["\")\""])?;
             args
         } else {
-            Vec::new()
+            (Vec::new(), Vec::new())
         };
         let range = (start..self.prev_end).into();
         let kind = match BuiltinAttr::parse(&name) {
             Some(b) => AttrKind::Builtin(b),
             None => AttrKind::User(name.clone()),
         };
-        Ok(Attribute { kind, name, args, range })
+        Ok(Attribute {
+            kind,
+            name,
+            args,
+            labeled_args,
+            range,
+        })
     }
 
     /// Parses a parenthesized, comma-separated list of attribute argument
-    /// expressions (no labels — attribute arguments are always positional).
+    /// expressions: positional ones first, then `name: expr` labeled ones
+    /// (`@cfg(feature: "json")`). Labels are bare identifiers only.
     ///
     /// # Errors
     ///
-    /// Returns an error if any argument expression is malformed.
-    fn parse_attribute_arg_list(&mut self) -> ParserResult<Vec<Expr>> {
+    /// Returns an error if any argument expression is malformed, or a
+    /// positional argument follows a labeled one.
+    fn parse_attribute_arg_list(&mut self) -> ParserResult<AttributeArgs> {
         if matches!(self.peek(), Token::RParen) {
-            return Ok(Vec::new());
+            return Ok((Vec::new(), Vec::new()));
         }
         let mut args = Vec::new();
+        let mut labeled_args = Vec::new();
         loop {
-            args.push(self.parse_expr()?);
+            if let Token::Identifier(label) = self.peek().clone()
+                && matches!(self.peek_next(), Token::Colon)
+            {
+                self.advance();
+                self.advance(); // ':'
+                labeled_args.push((label, self.parse_expr()?));
+            } else if !labeled_args.is_empty() {
+                return Err(self.error_message_here("positional attribute argument cannot follow a labeled argument"));
+            } else {
+                args.push(self.parse_expr()?);
+            }
             if !self.eat(&Token::Comma) {
                 break;
             }
         }
-        Ok(args)
+        Ok((args, labeled_args))
     }
 
     /// Attaches `attrs` to `member`'s `attributes` field.
@@ -2425,6 +2523,7 @@ impl<'source> Parser<'source> {
             Token::And => "and".to_string(),
             Token::Or => "or".to_string(),
             Token::Not => "not".to_string(),
+            Token::Await => "await".to_string(),
             Token::Is => "is".to_string(),
             // `from` is a module-syntax keyword, but remains valid as a
             // selector for existing APIs such as `Map.from(...)`.
@@ -2928,6 +3027,7 @@ impl<'source> Parser<'source> {
             | Token::Plus
             | Token::Minus
             | Token::Not
+            | Token::Await
             | Token::Tilde
             | Token::DotDotDot => true,
             Token::LBrace => {
@@ -3338,6 +3438,7 @@ impl<'source> Parser<'source> {
             Token::Plus => UnaryOp::Plus,
             Token::Minus => UnaryOp::Minus,
             Token::Not => UnaryOp::Not,
+            Token::Await => UnaryOp::Await,
             Token::Tilde => UnaryOp::BitNot,
             _ => return self.parse_power(),
         };
@@ -3753,6 +3854,9 @@ impl<'source> Parser<'source> {
             // position — `fiber.try(...)`/`fiber.try` (`Fiber#try`, ADR-0030)
             // predates this unit and must keep parsing.
             Token::Try => "try".to_string(),
+            // `yield` likewise: `Fiber.yield(...)` is the fiber-level send a
+            // generator's `yield` statement lowers to.
+            Token::Yield => "yield".to_string(),
             // `from` is reserved in module preambles but remains valid in
             // message-send position (`Map.from(...)`).
             Token::From => "from".to_string(),
@@ -3781,6 +3885,7 @@ impl<'source> Parser<'source> {
             Token::And => "and".to_string(),
             Token::Or => "or".to_string(),
             Token::Not => "not".to_string(),
+            Token::Await => "await".to_string(),
             Token::Is => "is".to_string(),
             _ => return Err(self.error_here(strs(&["identifier", "\"class\"", "operator"]))),
         };
@@ -4230,6 +4335,7 @@ impl<'source> Parser<'source> {
             Token::And => "and",
             Token::Or => "or",
             Token::Not => "not",
+            Token::Await => "await",
             Token::Is => "is",
             Token::Question => "?",
             _ => return None,
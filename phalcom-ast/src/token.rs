@@ -96,6 +96,14 @@ pub enum Token {
     /// parse because [`crate::parser`]'s property-name production also accepts
     /// this token.
     Try,
+    /// The `yield` keyword — hands a value out of a generator body
+    /// (`@generator` methods and generator blocks). `Fiber.yield(...)` sends
+    /// still parse because the property-name production also accepts it.
+    Yield,
+    /// The `await` keyword — prefix `await x` parks the current fiber until
+    /// the `Future` `x` settles, lowering to the `await` getter send exactly
+    /// as `not x` lowers to `not`.
+    Await,
 
     /// An identifier lexeme, e.g. `foo` or a field name like `_bar`.
     Identifier(String),
@@ -631,6 +631,25 @@ fn multiple_attributes_attach_to_same_member_in_order() {
     ));
 }
 
+#[test]
+fn attribute_labeled_arguments_follow_positional_ones() {
+    let program = parse_source("@cfg(feature: \"json\", mode: \"release\")\nclass Codec {}\n", 0).unwrap();
+    let Statement::Class(class) = &program.statements[0] else {
+        panic!("expected a class");
+    };
+    let attr = &class.attributes[0];
+    assert!(attr.args.is_empty());
+    let labels: Vec<_> = attr.labeled_args.iter().map(|(label, _)| label.as_str()).collect();
+    assert_eq!(labels, ["feature", "mode"]);
+    assert!(matches!(&attr.labeled_args[0].1, Expr::String { value, .. } if value == "json"));
+
+    let err = parse_source("class Point {\n  @tag(kind: 1, 2)\n  x() { 1 }\n}\n", 0).unwrap_err();
+    assert!(
+        format!("{err:?}").contains("positional attribute argument cannot follow a labeled argument"),
+        "{err:?}"
+    );
+}
+
 #[test]
 fn standalone_invariant_diverts_to_class_invariants() {
     // DEC-ANNOT-B: `@invariant` alone has no following member — it lands in
@@ -4,8 +4,10 @@ use clap::{Args, Parser, Subcommand, ValueHint};
 use phalcom_core::compiler::attributes::CompileMode;
 use phalcom_core::diagnostics::style::{ColorMode, RenderConfig};
 use phalcom_core::vm::VM;
+use phalcom_modules::{CfgOptions, LockMode, LockOptions, Lockfile, ProjectLoadOptions, ProjectUniverse, Refresh};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::{fs, path::PathBuf};
 
 /// Run, tokenize, parse, or disassemble phalcom source.
 #[derive(Parser)]
@@ -24,15 +26,16 @@ pub struct Cli {
     /// `@ensures`/`@invariant`'s guards are stripped (no-op weave), and
     /// reflectable contract metadata (`MethodObject::contracts`) is retained
     /// by default — pass `--strip-contract-metadata` alongside this flag to
-    /// also strip it. Conflicts with `--unchecked`. Only affects the default
-    /// (no-subcommand) run path.
+    /// also strip it. Conflicts with `--unchecked`. Only affects the run
+    /// paths (no subcommand, and `phalcom run`).
     #[arg(long, conflicts_with = "unchecked")]
     pub(crate) release: bool,
 
     /// Compile contracts in unchecked mode (plan §3.6): every
     /// `@requires`/`@ensures`/`@invariant` guard is stripped (no-op weave)
     /// and reflectable contract metadata is stripped by default. Conflicts
-    /// with `--release`. Only affects the default (no-subcommand) run path.
+    /// with `--release`. Only affects the run paths (no subcommand, and
+    /// `phalcom run`).
     #[arg(long, conflicts_with = "release")]
     pub(crate) unchecked: bool,
 
@@ -92,6 +95,98 @@ impl Cli {
     pub(crate) fn render_config(&self) -> RenderConfig {
         RenderConfig::from_env(self.color, self.plain)
     }
+
+    /// The [`ProjectLoadOptions`] of the command: its [`ProjectArgs`] under
+    /// [`Self::compile_mode`], or the defaults when it takes none.
+    pub(crate) fn project_options(&self) -> ProjectLoadOptions {
+        let defaults = ProjectArgs::default();
+        let project = self.command.as_ref().and_then(Commands::project_args).unwrap_or(&defaults);
+        project.load_options(self.compile_mode())
+    }
+}
+
+/// Dependency resolution flags shared by the project commands.
+#[derive(Args, Default)]
+pub struct ProjectArgs {
+    /// Fail instead of rewriting `phalcom.lock` when the resolved dependency
+    /// graph no longer matches it
+    #[arg(long, conflicts_with = "frozen")]
+    pub(crate) locked: bool,
+
+    /// Resolve only the versions `phalcom.lock` records, and fail when it is
+    /// missing or out of date
+    #[arg(long, conflicts_with = "locked")]
+    pub(crate) frozen: bool,
+
+    /// Resolve dependencies only from the project's `vendor/` directory, and
+    /// fail on any that `phalcom vendor` did not copy there
+    #[arg(long)]
+    pub(crate) offline: bool,
+
+    /// Comma-separated `[features]` to enable on the root project, on top of
+    /// its `default` ones
+    #[arg(long, value_delimiter = ',')]
+    pub(crate) features: Vec<String>,
+
+    /// Do not enable the root project's `default` features
+    #[arg(long)]
+    pub(crate) no_default_features: bool,
+}
+
+impl ProjectArgs {
+    /// Resolves `mode` and `--features`/`--no-default-features` into the
+    /// [`CfgOptions`] project loads filter modules under, so
+    /// `@cfg(mode: "release")` follows `--release`.
+    fn cfg_options(&self, mode: CompileMode) -> CfgOptions {
+        let mode = match mode {
+            CompileMode::Debug => "debug",
+            CompileMode::Release => "release",
+            CompileMode::Unchecked => "unchecked",
+        };
+        CfgOptions {
+            mode: mode.to_string(),
+            features: self.features.clone(),
+            default_features: !self.no_default_features,
+        }
+    }
+
+    /// Resolves `--locked`/`--frozen`/`--offline` into the [`LockOptions`]
+    /// project commands load under; without `--locked`/`--frozen` they keep
+    /// `phalcom.lock` up to date.
+    fn lock_options(&self) -> LockOptions {
+        let mode = if self.frozen {
+            LockMode::Frozen
+        } else if self.locked {
+            LockMode::Locked
+        } else {
+            LockMode::Update
+        };
+        LockOptions {
+            mode,
+            offline: self.offline,
+            ..LockOptions::default()
+        }
+    }
+
+    /// The [`ProjectLoadOptions`] of a project command compiled under `mode`:
+    /// [`Self::lock_options`] and [`Self::cfg_options`].
+    pub(crate) fn load_options(&self, mode: CompileMode) -> ProjectLoadOptions {
+        ProjectLoadOptions {
+            lock: self.lock_options(),
+            cfg: self.cfg_options(mode),
+        }
+    }
+}
+
+/// `options` for running or checking a single file, inline source or
+/// package: the lock of a project the file belongs to is honored, but only
+/// project commands write it.
+fn script_options(options: &ProjectLoadOptions) -> ProjectLoadOptions {
+    let mut options = options.clone();
+    if options.lock.mode == LockMode::Update {
+        options.lock.mode = LockMode::ReadOnly;
+    }
+    options
 }
 
 #[derive(Subcommand)]
@@ -105,13 +200,54 @@ pub enum Commands {
     /// Print disassembled phalcom bytecode
     Disasm(DisasmArgs),
 
-    /// Lex and parse phalcom source, reporting syntax diagnostics without compiling or running
+    /// Resolve and type-check a file, package, project or workspace, reporting its diagnostics without running it
     Check(CheckArgs),
 
+    /// Generate a project's Phaldoc documentation as an HTML site and a JSON model
+    Doc(DocArgs),
+
+    /// Re-resolve a project's dependencies and rewrite `phalcom.lock`
+    Update(UpdateArgs),
+
+    /// Create a new project directory with `project.toml` and `src/package.ph`
+    New(NewArgs),
+
+    /// Resolve, link, and type-check every module of a project, reporting all diagnostics
+    Build(BuildArgs),
+
+    /// Run a project's entry module
+    Run(RunArgs),
+
+    /// Pack a project into a checksummed archive with its interfaces and semantic metadata
+    Package(PackageArgs),
+
+    /// Print a project's module graph, runtime cycles, initialization order and unused imports
+    Graph(GraphArgs),
+
+    /// Copy every locked dependency into `vendor/` and record its checksum in `phalcom.lock`
+    Vendor(VendorArgs),
+
     /// Print version
     Version,
 }
 
+impl Commands {
+    /// The dependency resolution flags of a project command.
+    fn project_args(&self) -> Option<&ProjectArgs> {
+        match self {
+            Commands::Check(args) => Some(&args.dependencies),
+            Commands::Doc(args) => Some(&args.dependencies),
+            Commands::Update(args) => Some(&args.dependencies),
+            Commands::Build(args) => Some(&args.dependencies),
+            Commands::Run(args) => Some(&args.dependencies),
+            Commands::Package(args) => Some(&args.dependencies),
+            Commands::Graph(args) => Some(&args.dependencies),
+            Commands::Vendor(args) => Some(&args.dependencies),
+            Commands::Tokenize(_) | Commands::Parse(_) | Commands::Disasm(_) | Commands::New(_) | Commands::Version => None,
+        }
+    }
+}
+
 /// Tokenize phalcom code
 #[derive(Args)]
 pub struct TokenizeArgs {
@@ -136,20 +272,154 @@ pub struct ParseArgs {
     source: Option<String>,
 }
 
-/// Lex and parse phalcom code, reporting syntax diagnostics only
+/// Check phalcom code without running it
 #[derive(Args)]
 pub struct CheckArgs {
-    /// Path to a `.ph` file
-    #[arg(value_name = "path", value_hint = clap::ValueHint::FilePath, conflicts_with = "source")]
+    /// A `.ph` file, a package directory, or a project or workspace directory
+    /// containing `project.toml`; every member of a workspace is checked
+    #[arg(value_name = "path", value_hint = clap::ValueHint::AnyPath, conflicts_with = "source")]
     path: Option<PathBuf>,
 
     /// Provide source inline instead of a file
     #[arg(short, long, value_name = "source", conflicts_with = "path")]
     source: Option<String>,
 
-    /// Output format: `text` (human-readable, default) or `json` (machine-readable, one diagnostic per syntax error)
+    /// Output format: `text` (human-readable, default) or `json` (machine-readable, one diagnostic per line)
+    #[arg(long, value_name = "format", default_value = "text")]
+    format: String,
+
+    #[command(flatten)]
+    dependencies: ProjectArgs,
+}
+
+/// Document a phalcom project
+#[derive(Args)]
+pub struct DocArgs {
+    /// Project directory containing `project.toml`
+    #[arg(value_name = "path", value_hint = ValueHint::DirPath, default_value = ".")]
+    path: PathBuf,
+
+    /// Output directory for the site and `phaldoc.json` (default: `<project>/target/doc`)
+    #[arg(short, long, value_name = "dir", value_hint = ValueHint::DirPath)]
+    out: Option<PathBuf>,
+
+    /// Print the JSON model to stdout instead of writing the site
+    #[arg(long)]
+    json: bool,
+
+    /// Run every `@example` block as a doctest; exits `1` if any fails
+    #[arg(long)]
+    test: bool,
+
+    #[command(flatten)]
+    dependencies: ProjectArgs,
+}
+
+/// Update locked dependency versions
+#[derive(Args)]
+pub struct UpdateArgs {
+    /// Registry packages to update; every package when none are given
+    #[arg(value_name = "package")]
+    packages: Vec<String>,
+
+    /// Project directory containing `project.toml`
+    #[arg(long, value_name = "dir", value_hint = ValueHint::DirPath, default_value = ".")]
+    project: PathBuf,
+
+    #[command(flatten)]
+    dependencies: ProjectArgs,
+}
+
+/// Create a phalcom project
+#[derive(Args)]
+pub struct NewArgs {
+    /// Directory to create; its name becomes the project name
+    #[arg(value_name = "name", value_hint = ValueHint::DirPath)]
+    name: PathBuf,
+
+    /// Create a library, which declares no `entry`
+    #[arg(long)]
+    lib: bool,
+}
+
+/// Build a phalcom project
+#[derive(Args)]
+pub struct BuildArgs {
+    /// Project or workspace directory containing `project.toml`
+    #[arg(value_name = "path", value_hint = ValueHint::DirPath, default_value = ".")]
+    path: PathBuf,
+
+    /// Output format: `text` (human-readable, default) or `json` (one diagnostic per line)
     #[arg(long, value_name = "format", default_value = "text")]
     format: String,
+
+    #[command(flatten)]
+    dependencies: ProjectArgs,
+}
+
+/// Run a phalcom project
+#[derive(Args)]
+pub struct RunArgs {
+    /// Entry module as a dotted path, e.g. `app.tools.migrate` or `tools.migrate`
+    /// (default: the manifest's `entry`, then its `default_entry`)
+    #[arg(value_name = "entry")]
+    entry: Option<String>,
+
+    /// Project directory containing `project.toml`
+    #[arg(long, value_name = "dir", value_hint = ValueHint::DirPath, default_value = ".")]
+    project: PathBuf,
+
+    #[command(flatten)]
+    dependencies: ProjectArgs,
+}
+
+/// Package a phalcom project
+#[derive(Args)]
+pub struct PackageArgs {
+    /// Project directory containing `project.toml`
+    #[arg(value_name = "path", value_hint = ValueHint::DirPath, default_value = ".")]
+    path: PathBuf,
+
+    /// Output directory for the archive (default: `<project>/target/package`)
+    #[arg(short, long, value_name = "dir", value_hint = ValueHint::DirPath)]
+    out: Option<PathBuf>,
+
+    #[command(flatten)]
+    dependencies: ProjectArgs,
+}
+
+/// Graph a phalcom project's modules
+#[derive(Args)]
+pub struct GraphArgs {
+    /// Project directory containing `project.toml`
+    #[arg(value_name = "path", value_hint = ValueHint::DirPath, default_value = ".")]
+    path: PathBuf,
+
+    /// Output format: `text` (human-readable, default), `dot` (Graphviz) or `json`
+    #[arg(long, value_name = "format", default_value = "text")]
+    format: String,
+
+    /// Graph layer: `runtime` (initialization dependencies, default) or `semantic` (interface dependencies)
+    #[arg(long, value_name = "layer", default_value = "runtime")]
+    layer: String,
+
+    /// Explain how the first module comes to import the second, e.g. `--why app.main app.util`
+    #[arg(long, num_args = 2, value_names = ["from", "to"])]
+    why: Option<Vec<String>>,
+
+    #[command(flatten)]
+    dependencies: ProjectArgs,
+}
+
+/// Vendor a phalcom project's dependencies
+#[derive(Args)]
+pub struct VendorArgs {
+    /// Project or workspace directory containing `project.toml`
+    #[arg(value_name = "path", value_hint = ValueHint::DirPath, default_value = ".")]
+    path: PathBuf,
+
+    #[command(flatten)]
+    dependencies: ProjectArgs,
 }
 
 /// Disassemble phalcom code
@@ -171,9 +441,7 @@ pub fn cmd_run(cli: Cli) -> Result<()> {
             std::process::exit(66);
         }
     }
-    let compile_mode = cli.compile_mode();
-    let strip_contract_metadata = cli.strip_contract_metadata;
-
+    let options = cli.project_options();
     let selection = if let Some(src) = &cli.source {
         phalcom_core::modules::compile::EntrySelection::Inline(src.as_str().into())
     } else if let Some(path) = &cli.path {
@@ -202,10 +470,36 @@ pub fn cmd_run(cli: Cli) -> Result<()> {
         };
         phalcom_core::modules::compile::EntrySelection::Inline(source.as_str().into())
     };
+    let options = match selection {
+        phalcom_core::modules::compile::EntrySelection::Project(_) => options,
+        _ => script_options(&options),
+    };
+    run_selection(&cli, selection, &options)
+}
 
+/// Runs a project's entry module for `phalcom run`: the named `entry`, else
+/// the manifest's `entry`, else its `default_entry`. Exit codes match the
+/// default run path.
+pub fn cmd_run_project(cli: &Cli, args: &RunArgs) -> Result<()> {
+    if !args.project.join("project.toml").is_file() {
+        eprintln!("Error: {} is not a project directory (no project.toml)", args.project.display());
+        std::process::exit(66);
+    }
+    let root = fs::canonicalize(&args.project).with_context(|| format!("Failed to resolve path {}", args.project.display()))?;
+    let selection = phalcom_core::modules::compile::EntrySelection::ProjectEntry {
+        root,
+        entry: args.entry.clone(),
+    };
+    run_selection(cli, selection, &cli.project_options())
+}
+
+/// Compiles and runs `selection`, loading projects under `options`, on a VM
+/// configured from the global flags, exiting with `65` for compile errors,
+/// `70` for runtime errors and `74` for I/O errors.
+fn run_selection(cli: &Cli, selection: phalcom_core::modules::compile::EntrySelection, options: &ProjectLoadOptions) -> Result<()> {
     let mut vm = VM::new();
-    vm.compile_mode = compile_mode;
-    vm.strip_contract_metadata = strip_contract_metadata;
+    vm.compile_mode = cli.compile_mode();
+    vm.strip_contract_metadata = cli.strip_contract_metadata;
     vm.trace_core = cli.trace_core;
     vm.trace_format_json = cli.trace_format == "json";
     vm.trace_fibers = cli.trace.iter().any(|t| t == "fibers");
@@ -213,7 +507,7 @@ pub fn cmd_run(cli: Cli) -> Result<()> {
         eprintln!("warning: --trace=dispatch requested but the 'vm-trace' cargo feature is not enabled");
     }
 
-    let program_res = phalcom_core::modules::compile::ProgramCompiler::compile_entry_selection(selection);
+    let program_res = phalcom_core::modules::compile::ProgramCompiler::compile_entry_selection_with_options(selection, options);
     let run_res = match program_res {
         Ok(program) => vm.run_compiled(&program),
         Err(err) => {
@@ -315,12 +609,23 @@ pub fn cmd_parse(args: ParseArgs) -> Result<()> {
 /// intended for editor tooling (e.g. an LSP-less VS Code extension shelling
 /// out per save); `--format text` (default) reuses the existing span-aware
 /// renderer.
-pub fn cmd_check(args: CheckArgs) -> Result<()> {
+pub fn cmd_check(args: CheckArgs, options: &ProjectLoadOptions) -> Result<()> {
     let selection = if let Some(p) = args.path.clone() {
         if !p.exists() {
             eprintln!("Error: File {} does not exist", p.display());
             std::process::exit(66);
         }
+        if let Some(projects) = workspace_projects(&p)? {
+            // Check every member, so one run reports all of them.
+            let mut clean = true;
+            for project in projects {
+                clean &= check_project(&args, &project, options);
+            }
+            if !clean {
+                std::process::exit(65);
+            }
+            return Ok(());
+        }
         if p.is_dir() {
             if p.join("project.toml").exists() {
                 phalcom_core::modules::compile::EntrySelection::Project(p)
@@ -337,122 +642,249 @@ pub fn cmd_check(args: CheckArgs) -> Result<()> {
         std::process::exit(64);
     };
 
-    match phalcom_core::modules::compile::ProgramAnalyzer::analyze_entry_selection(selection) {
-        Ok(analyzed) => {
-            if analyzed.semantic.has_errors() {
-                for (module, diags) in analyzed.semantic.diagnostics.iter() {
-                    let source_text = analyzed.sources.get(module).map(|u| u.text.clone()).unwrap_or_else(|| Arc::from(""));
-                    let path_str = analyzed
-                        .sources
-                        .get(module)
-                        .and_then(|u| u.source.as_ref())
-                        .map(|s| s.display_path.display().to_string());
-
-                    if args.format == "json" {
-                        for diag in diags.iter() {
-                            let (start_line, start_col) = byte_offset_to_line_col(&source_text, diag.primary_range.start);
-                            let (end_line, end_col) = byte_offset_to_line_col(&source_text, diag.primary_range.end);
-                            println!(
-                                "{{\"severity\":\"error\",\"code\":{},\"message\":{},\"module\":{},\"range\":{{\"start\":{{\"line\":{},\"column\":{}}},\"end\":{{\"line\":{},\"column\":{}}}}}}}",
-                                json_escape(diag.code.as_str()),
-                                json_escape(&diag.message),
-                                json_escape(&module.to_string()),
-                                start_line,
-                                start_col,
-                                end_line,
-                                end_col
-                            );
-                        }
-                    } else {
-                        for diag in diags.iter() {
-                            let range = diag.primary_range.start..diag.primary_range.end;
-                            phalcom_core::diagnostics::print_parse(&source_text, path_str.as_deref(), &format!("{}: {}", diag.code, diag.message), range);
-                        }
-                    }
-                }
-                std::process::exit(65);
-            }
-            Ok(())
-        }
-        Err(err) => match err {
-            phalcom_core::modules::compile::ProgramCompileError::Semantic(diags) => {
-                if args.format == "json" {
-                    for (module, module_diags) in diags.iter() {
-                        for diag in module_diags {
-                            println!(
-                                "{{\"severity\":\"error\",\"code\":{},\"message\":{},\"module\":{}}}",
-                                json_escape(diag.code.as_str()),
-                                json_escape(&diag.message),
-                                json_escape(&module.to_string()),
-                            );
-                        }
-                    }
-                } else {
-                    for (module, module_diags) in diags.iter() {
-                        for diag in module_diags {
-                            eprintln!("Semantic error in {module} [{}]: {}", diag.code, diag.message);
-                        }
-                    }
-                }
-                std::process::exit(65);
-            }
-            phalcom_core::modules::compile::ProgramCompileError::ModuleLoad(phalcom_modules::ModuleLoadError::Parse { source, error, .. }) => {
-                let message = error.kind.to_string();
-                let source_text = fs::read_to_string(&source).unwrap_or_default();
-                let path_display = source.display().to_string();
-                if args.format == "json" {
-                    let (start_line, start_col) = byte_offset_to_line_col(&source_text, error.range.start);
-                    let (end_line, end_col) = byte_offset_to_line_col(&source_text, error.range.end);
+    let options = match selection {
+        phalcom_core::modules::compile::EntrySelection::Project(_) => options.clone(),
+        _ => script_options(options),
+    };
+    if !check_selection(&args, selection, &options) {
+        std::process::exit(65);
+    }
+    Ok(())
+}
+
+/// Analyzes one entry selection for `phalcom check`, printing its
+/// diagnostics; returns whether it was clean.
+fn check_selection(args: &CheckArgs, selection: phalcom_core::modules::compile::EntrySelection, options: &ProjectLoadOptions) -> bool {
+    match phalcom_core::modules::compile::ProgramAnalyzer::analyze_entry_selection_with_options(selection, options) {
+        Ok(analyzed) => report_checked_program(args, &analyzed),
+        Err(err) => report_check_error(args, err),
+    }
+}
+
+/// Checks every module of the project in `dir`. Workspace members are
+/// checked this way, since a library member declares no entry.
+fn check_project(args: &CheckArgs, dir: &Path, options: &ProjectLoadOptions) -> bool {
+    match phalcom_core::modules::ProgramAnalyzer::analyze_project_modules_with_options(dir, options) {
+        // Reports every program rather than stopping at the first unclean one.
+        Ok(project) => project.programs.iter().filter(|program| !report_checked_program(args, program)).count() == 0,
+        Err(err) => report_check_error(args, err),
+    }
+}
+
+/// Prints an analyzed program's diagnostics; returns whether there were none.
+fn report_checked_program(args: &CheckArgs, analyzed: &phalcom_core::modules::compile::AnalyzedProgram) -> bool {
+    if analyzed.semantic.has_errors() {
+        for (module, diags) in analyzed.semantic.diagnostics.iter() {
+            let source_text = analyzed.sources.get(module).map(|u| u.text.clone()).unwrap_or_else(|| Arc::from(""));
+            let path_str = analyzed
+                .sources
+                .get(module)
+                .and_then(|u| u.source.as_ref())
+                .map(|s| s.display_path.display().to_string());
+
+            if args.format == "json" {
+                for diag in diags.iter() {
+                    let (start_line, start_col) = byte_offset_to_line_col(&source_text, diag.primary_range.start);
+                    let (end_line, end_col) = byte_offset_to_line_col(&source_text, diag.primary_range.end);
                     println!(
-                        "{{\"severity\":\"error\",\"code\":\"SyntaxError\",\"message\":{},\"range\":{{\"start\":{{\"line\":{},\"column\":{}}},\"end\":{{\"line\":{},\"column\":{}}}}}}}",
-                        json_escape(&message),
+                        "{{\"severity\":\"error\",\"code\":{},\"message\":{},\"module\":{},\"range\":{{\"start\":{{\"line\":{},\"column\":{}}},\"end\":{{\"line\":{},\"column\":{}}}}}}}",
+                        json_escape(diag.code.as_str()),
+                        json_escape(&diag.message),
+                        json_escape(&module.to_string()),
                         start_line,
                         start_col,
                         end_line,
                         end_col
                     );
-                } else {
-                    phalcom_core::diagnostics::print_parse(&source_text, Some(&path_display), &message, error.range.clone());
                 }
-                std::process::exit(65);
+            } else {
+                for diag in diags.iter() {
+                    let range = diag.primary_range.start..diag.primary_range.end;
+                    phalcom_core::diagnostics::print_parse(&source_text, path_str.as_deref(), &format!("{}: {}", diag.code, diag.message), range);
+                }
             }
-            phalcom_core::modules::compile::ProgramCompileError::Parse(parse_err) => {
-                let message = parse_err.kind.to_string();
-                let source_text = if let Some(ref p) = args.path {
-                    fs::read_to_string(p).unwrap_or_default()
-                } else {
-                    args.source.clone().unwrap_or_default()
-                };
-                let path_display = args
-                    .path
-                    .as_ref()
-                    .and_then(|p| fs::canonicalize(p).ok())
-                    .map(|p| p.display().to_string())
-                    .or_else(|| args.path.as_ref().map(|p| p.display().to_string()));
-                if args.format == "json" {
-                    let (start_line, start_col) = byte_offset_to_line_col(&source_text, parse_err.range.start);
-                    let (end_line, end_col) = byte_offset_to_line_col(&source_text, parse_err.range.end);
-                    println!(
-                        "{{\"severity\":\"error\",\"code\":\"SyntaxError\",\"message\":{},\"range\":{{\"start\":{{\"line\":{},\"column\":{}}},\"end\":{{\"line\":{},\"column\":{}}}}}}}",
-                        json_escape(&message),
-                        start_line,
-                        start_col,
-                        end_line,
-                        end_col
-                    );
-                } else {
-                    phalcom_core::diagnostics::print_parse(&source_text, path_display.as_deref(), &message, parse_err.range.clone());
+        }
+        return false;
+    }
+    true
+}
+
+/// Prints why analysis failed; always returns `false`.
+fn report_check_error(args: &CheckArgs, err: phalcom_core::modules::compile::ProgramCompileError) -> bool {
+    match err {
+        phalcom_core::modules::compile::ProgramCompileError::Semantic(diags) => {
+            if args.format == "json" {
+                for (module, module_diags) in diags.iter() {
+                    for diag in module_diags {
+                        println!(
+                            "{{\"severity\":\"error\",\"code\":{},\"message\":{},\"module\":{}}}",
+                            json_escape(diag.code.as_str()),
+                            json_escape(&diag.message),
+                            json_escape(&module.to_string()),
+                        );
+                    }
+                }
+            } else {
+                for (module, module_diags) in diags.iter() {
+                    for diag in module_diags {
+                        eprintln!("Semantic error in {module} [{}]: {}", diag.code, diag.message);
+                    }
                 }
-                std::process::exit(65);
             }
-            _ => {
-                eprintln!("Check error: {err}");
-                std::process::exit(65);
+            false
+        }
+        phalcom_core::modules::compile::ProgramCompileError::ModuleLoad(phalcom_modules::ModuleLoadError::Parse { source, error, .. }) => {
+            let message = error.kind.to_string();
+            let source_text = fs::read_to_string(&source).unwrap_or_default();
+            let path_display = source.display().to_string();
+            if args.format == "json" {
+                let (start_line, start_col) = byte_offset_to_line_col(&source_text, error.range.start);
+                let (end_line, end_col) = byte_offset_to_line_col(&source_text, error.range.end);
+                println!(
+                    "{{\"severity\":\"error\",\"code\":\"SyntaxError\",\"message\":{},\"range\":{{\"start\":{{\"line\":{},\"column\":{}}},\"end\":{{\"line\":{},\"column\":{}}}}}}}",
+                    json_escape(&message),
+                    start_line,
+                    start_col,
+                    end_line,
+                    end_col
+                );
+            } else {
+                phalcom_core::diagnostics::print_parse(&source_text, Some(&path_display), &message, error.range.clone());
+            }
+            false
+        }
+        phalcom_core::modules::compile::ProgramCompileError::Parse(parse_err) => {
+            let message = parse_err.kind.to_string();
+            let source_text = if let Some(ref p) = args.path {
+                fs::read_to_string(p).unwrap_or_default()
+            } else {
+                args.source.clone().unwrap_or_default()
+            };
+            let path_display = args
+                .path
+                .as_ref()
+                .and_then(|p| fs::canonicalize(p).ok())
+                .map(|p| p.display().to_string())
+                .or_else(|| args.path.as_ref().map(|p| p.display().to_string()));
+            if args.format == "json" {
+                let (start_line, start_col) = byte_offset_to_line_col(&source_text, parse_err.range.start);
+                let (end_line, end_col) = byte_offset_to_line_col(&source_text, parse_err.range.end);
+                println!(
+                    "{{\"severity\":\"error\",\"code\":\"SyntaxError\",\"message\":{},\"range\":{{\"start\":{{\"line\":{},\"column\":{}}},\"end\":{{\"line\":{},\"column\":{}}}}}}}",
+                    json_escape(&message),
+                    start_line,
+                    start_col,
+                    end_line,
+                    end_col
+                );
+            } else {
+                phalcom_core::diagnostics::print_parse(&source_text, path_display.as_deref(), &message, parse_err.range.clone());
             }
-        },
+            false
+        }
+        _ => {
+            eprintln!("Check error: {err}");
+            false
+        }
     }
 }
 
+/// Analyzes every module of a project, harvests its Phaldoc into the
+/// documentation model, and writes the HTML site plus `phaldoc.json` (or, with
+/// `--json`, prints the model). Harvest warnings go to stderr.
+///
+/// `--test` then runs each `@example` as a doctest. Analysis failures exit
+/// `65` like `check`; a failed doctest exits `1` after every example has run.
+pub fn cmd_doc(args: DocArgs, options: &ProjectLoadOptions) -> Result<()> {
+    if !args.path.join("project.toml").is_file() {
+        eprintln!("Error: {} is not a project directory (no project.toml)", args.path.display());
+        std::process::exit(66);
+    }
+    let root = fs::canonicalize(&args.path).with_context(|| format!("Failed to resolve path {}", args.path.display()))?;
+    let out = args.out.clone().unwrap_or_else(|| root.join("target").join("doc"));
+    let doctests_failed = match workspace_projects(&root)? {
+        // Each workspace project documents into its own subdirectory.
+        Some(projects) => projects.iter().try_fold(false, |failed, project| {
+            let name = project.file_name().map(PathBuf::from).unwrap_or_default();
+            Ok::<_, anyhow::Error>(doc_project(&args, project, &out.join(name), options)? || failed)
+        })?,
+        None => doc_project(&args, &root, &out, options)?,
+    };
+    if doctests_failed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Documents the project at `root` into `out` for `phalcom doc`, running its
+/// doctests under `--test`; returns whether any doctest failed.
+fn doc_project(args: &DocArgs, root: &Path, out: &Path, options: &ProjectLoadOptions) -> Result<bool> {
+    let analysis = match phalcom_core::modules::ProgramAnalyzer::analyze_project_modules_with_options(root, options) {
+        Ok(analysis) => analysis,
+        Err(phalcom_core::modules::compile::ProgramCompileError::Semantic(diags)) => {
+            for (module, module_diags) in diags.iter() {
+                for diag in module_diags {
+                    eprintln!("Semantic error in {module} [{}]: {}", diag.code, diag.message);
+                }
+            }
+            std::process::exit(65);
+        }
+        Err(err) => {
+            eprintln!("Doc error: {err}");
+            std::process::exit(65);
+        }
+    };
+
+    let docs = phalcom_core::doc::document_project(&analysis);
+    for warning in &docs.warnings {
+        eprintln!("warning: {}:{}: {}", warning.path, warning.warning.line, warning.warning.message);
+    }
+    if args.json {
+        println!("{}", phalcom_core::doc::to_json(&docs));
+    } else {
+        phalcom_core::doc::write_site(&docs, out).with_context(|| format!("Failed to write documentation to {}", out.display()))?;
+        eprintln!("Documented {} module(s) into {}", docs.modules.len(), out.display());
+    }
+
+    if !args.test {
+        return Ok(false);
+    }
+    let doctests = phalcom_core::doc::collect_doctests(&docs);
+    let outcomes = phalcom_core::doc::run_doctests(&analysis, &doctests);
+    let mut failed = 0;
+    for outcome in &outcomes {
+        let location = format!("{} ({}:{})", outcome.doctest.target, outcome.doctest.path, outcome.doctest.line);
+        match &outcome.failure {
+            None => eprintln!("doctest {location} ... ok"),
+            Some(failure) => {
+                failed += 1;
+                eprintln!("doctest {location} ... FAILED\n    {}", failure.replace('\n', "\n    "));
+            }
+        }
+    }
+    eprintln!("doctest result: {} passed; {failed} failed", outcomes.len() - failed);
+    Ok(failed > 0)
+}
+
+/// The projects a command pointed at `dir` covers when `dir` is a workspace
+/// root: the root project unless the workspace is virtual, then every
+/// member. `None` for any other path.
+fn workspace_projects(dir: &Path) -> Result<Option<Vec<PathBuf>>> {
+    if !dir.is_dir() {
+        return Ok(None);
+    }
+    let dir = fs::canonicalize(dir).with_context(|| format!("Failed to resolve path {}", dir.display()))?;
+    let Some(workspace) = phalcom_modules::Workspace::enclosing(&dir)?.filter(|workspace| workspace.root == dir) else {
+        return Ok(None);
+    };
+    let projects = workspace
+        .project_manifests()
+        .iter()
+        .filter_map(|manifest| manifest.parent().map(Path::to_path_buf))
+        .collect();
+    Ok(Some(projects))
+}
+
 /// Converts a 0-based UTF-8 byte offset into a 1-based `(line, column)` pair.
 ///
 /// Both line and column count from `1`, matching editor conventions (LSP
@@ -511,3 +943,272 @@ fn read_source(path: Option<PathBuf>, source: Option<String>) -> Result<String>
         _ => bail!("Must provide either a path or --source/-s"),
     }
 }
+
+pub fn cmd_update(args: UpdateArgs, options: &ProjectLoadOptions) -> Result<()> {
+    let manifest = args.project.join("project.toml");
+    if !manifest.is_file() {
+        eprintln!("Error: {} is not a project directory (no project.toml)", args.project.display());
+        std::process::exit(66);
+    }
+    if options.lock.mode != LockMode::Update {
+        bail!("`phalcom update` rewrites phalcom.lock, which --locked and --frozen forbid");
+    }
+    if options.lock.offline {
+        bail!("`phalcom update` resolves against the registry, which --offline forbids");
+    }
+    let lock_path = phalcom_modules::lockfile::lockfile_path(&args.project)?;
+    let before = Lockfile::load(&lock_path)?.unwrap_or_default();
+    if let Some(unknown) = args.packages.iter().find(|name| !before.packages.iter().any(|package| &package.name == *name)) {
+        bail!("package '{unknown}' is not in {}", lock_path.display());
+    }
+    let refresh = if args.packages.is_empty() {
+        Refresh::Everything
+    } else {
+        Refresh::Packages(args.packages.into_iter().collect())
+    };
+    let lock_options = LockOptions {
+        mode: LockMode::Update,
+        refresh,
+        offline: false,
+    };
+    let mut universe = ProjectUniverse::with_cfg_options(options.cfg.clone());
+    if workspace_projects(&args.project)?.is_some() {
+        universe.load_workspace_with_options(&manifest, &lock_options)?;
+    } else {
+        universe.load_root_with_options(&manifest, &lock_options)?;
+    }
+
+    let after = Lockfile::load(&lock_path)?.unwrap_or_default();
+    let changes = before.changes(&after);
+    if changes.is_empty() {
+        eprintln!("{} is up to date", lock_path.display());
+    }
+    for change in changes {
+        println!("{change}");
+    }
+    Ok(())
+}
+
+/// Scaffolds a project for `phalcom new`: `<name>/project.toml` and
+/// `<name>/src/package.ph`. The namespace is the name in `snake_case`; an
+/// executable project's `entry` is its root package, a `--lib` declares none.
+/// Refuses to touch an existing path.
+pub fn cmd_new(args: NewArgs) -> Result<()> {
+    if args.name.exists() {
+        eprintln!("Error: {} already exists", args.name.display());
+        std::process::exit(73);
+    }
+    let Some(name) = args.name.file_name().and_then(|name| name.to_str()) else {
+        bail!("'{}' does not end in a project name", args.name.display());
+    };
+    let namespace = name.replace('-', "_");
+    let namespace =
+        phalcom_modules::ModuleComponent::from_identifier(&namespace).map_err(|e| anyhow::anyhow!("'{name}' does not make a valid project namespace: {e}"))?;
+    if matches!(namespace.as_str(), "core" | "universe" | "std") {
+        bail!("'{name}' collides with the builtin '{}' root", namespace.as_str());
+    }
+
+    let mut manifest = format!(
+        "[project]\nname = \"{name}\"\nnamespace = \"{namespace}\"\nversion = \"0.1.0\"\n",
+        namespace = namespace.as_str()
+    );
+    let package = if args.lib {
+        format!(
+            "/// The `{name}` library.\nclass {class} {{}}\n\nexport {class}\n",
+            class = type_name(namespace.as_str())
+        )
+    } else {
+        manifest.push_str(&format!("entry = \"{}\"\n", namespace.as_str()));
+        format!("System.print(\"Hello from {name}!\")\n")
+    };
+    fs::create_dir_all(args.name.join("src")).with_context(|| format!("Failed to create {}", args.name.display()))?;
+    fs::write(args.name.join("project.toml"), manifest).with_context(|| format!("Failed to write {}", args.name.join("project.toml").display()))?;
+    fs::write(args.name.join("src").join("package.ph"), package).with_context(|| format!("Failed to write {}", args.name.join("src/package.ph").display()))?;
+    let kind = if args.lib { "library" } else { "executable" };
+    eprintln!("Created {kind} project '{name}' in {}", args.name.display());
+    Ok(())
+}
+
+/// `snake_case` to `UpperCamelCase`, for the class a new library exports.
+fn type_name(namespace: &str) -> String {
+    namespace
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Resolves, links, and type-checks every module of a project (or of every
+/// project of a workspace root), printing all diagnostics before exiting
+/// `65` if there were any. Unlike `check`, a library without an entry
+/// builds, and one broken module does not hide the others' diagnostics.
+pub fn cmd_build(args: BuildArgs, options: &ProjectLoadOptions) -> Result<()> {
+    if !args.path.join("project.toml").is_file() {
+        eprintln!("Error: {} is not a project directory (no project.toml)", args.path.display());
+        std::process::exit(66);
+    }
+    let root = fs::canonicalize(&args.path).with_context(|| format!("Failed to resolve path {}", args.path.display()))?;
+    let projects = workspace_projects(&root)?.unwrap_or_else(|| vec![root]);
+    let report = CheckArgs {
+        path: None,
+        source: None,
+        format: args.format,
+        dependencies: args.dependencies,
+    };
+    let mut modules = 0;
+    let mut failures = 0;
+    for project in &projects {
+        match phalcom_core::modules::ProgramAnalyzer::build_project_modules_with_options(project, options) {
+            Ok(build) => {
+                modules += build.project.modules.len();
+                failures += build
+                    .project
+                    .programs
+                    .iter()
+                    .filter(|program| !report_checked_program(&report, program))
+                    .count();
+                failures += build.errors.len();
+                for err in build.errors {
+                    report_check_error(&report, err);
+                }
+            }
+            Err(err) => {
+                failures += 1;
+                report_check_error(&report, err);
+            }
+        }
+    }
+    if failures > 0 {
+        std::process::exit(65);
+    }
+    if report.format != "json" {
+        eprintln!("Built {} module(s) in {} project(s)", modules, projects.len());
+    }
+    Ok(())
+}
+
+/// Packs a project into `<name>-<version>.tar` for publication, printing the
+/// archive path and its checksum. Analysis failures and unpublishable
+/// manifests (no license or version, path dependencies) exit `65`.
+pub fn cmd_package(args: PackageArgs, options: &ProjectLoadOptions) -> Result<()> {
+    if !args.path.join("project.toml").is_file() {
+        eprintln!("Error: {} is not a project directory (no project.toml)", args.path.display());
+        std::process::exit(66);
+    }
+    let root = fs::canonicalize(&args.path).with_context(|| format!("Failed to resolve path {}", args.path.display()))?;
+    let out = args.out.clone().unwrap_or_else(|| root.join("target").join("package"));
+    match phalcom_core::modules::package_project(&root, &out, options) {
+        Ok(packaged) => {
+            eprintln!(
+                "Packaged {} source(s) and {} module interface(s) into {}",
+                packaged.sources,
+                packaged.modules,
+                packaged.archive.display()
+            );
+            println!("{}", packaged.checksum);
+            Ok(())
+        }
+        Err(phalcom_core::modules::compile::ProgramCompileError::Semantic(diags)) => {
+            for (module, module_diags) in diags.iter() {
+                for diag in module_diags {
+                    eprintln!("Semantic error in {module} [{}]: {}", diag.code, diag.message);
+                }
+            }
+            std::process::exit(65);
+        }
+        Err(err) => {
+            eprintln!("Package error: {err}");
+            std::process::exit(65);
+        }
+    }
+}
+
+/// Prints a project's module graph as text, DOT or JSON. Runtime cycles are
+/// reported rather than fatal, so the graph of a project `build` rejects can
+/// still be inspected. With `--why`, prints the import chain between two
+/// modules instead, exiting `1` when there is none.
+pub fn cmd_graph(args: GraphArgs, options: &ProjectLoadOptions) -> Result<()> {
+    if !args.path.join("project.toml").is_file() {
+        eprintln!("Error: {} is not a project directory (no project.toml)", args.path.display());
+        std::process::exit(66);
+    }
+    let layer = match args.layer.as_str() {
+        "runtime" => phalcom_core::modules::GraphLayer::Runtime,
+        "semantic" => phalcom_core::modules::GraphLayer::Semantic,
+        other => bail!("unknown graph layer '{other}'; expected `runtime` or `semantic`"),
+    };
+    if !["text", "dot", "json"].contains(&args.format.as_str()) {
+        bail!("unknown graph format '{}'; expected `text`, `dot` or `json`", args.format);
+    }
+    let root = fs::canonicalize(&args.path).with_context(|| format!("Failed to resolve path {}", args.path.display()))?;
+    let graph = match phalcom_core::modules::project_graph(&root, options) {
+        Ok(graph) => graph,
+        Err(err) => {
+            eprintln!("Graph error: {err}");
+            std::process::exit(65);
+        }
+    };
+
+    if let Some(why) = &args.why {
+        let [from, to] = [&why[0], &why[1]].map(|name| {
+            graph.find(name).unwrap_or_else(|| {
+                eprintln!("Error: no module '{name}' in the graph");
+                std::process::exit(64);
+            })
+        });
+        let chain = graph.why(&from, &to);
+        if args.format == "json" {
+            let chain = chain.as_deref().map(|chain| graph.chain_json(chain));
+            println!("{}", serde_json::json!({ "from": graph.label(&from), "to": graph.label(&to), "chain": chain }));
+        } else if let Some(chain) = &chain {
+            println!("{} reaches {}:", graph.label(&from), graph.label(&to));
+            for edge in chain {
+                let location = graph.location(&edge.from, edge.range).unwrap_or_default();
+                let line = graph.source_line(&edge.from, edge.range).unwrap_or_default();
+                println!("  {} -> {}  `{line}` ({location})", graph.label(&edge.from), graph.label(&edge.to));
+            }
+        } else {
+            println!("{} does not import {}", graph.label(&from), graph.label(&to));
+        }
+        if chain.is_none() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    match args.format.as_str() {
+        "dot" => print!("{}", graph.to_dot(layer)),
+        "json" => println!("{}", graph.to_json(layer)),
+        _ => print!("{}", graph.to_text(layer)),
+    }
+    Ok(())
+}
+
+/// Copies every dependency `phalcom.lock` records into the project's
+/// `vendor/` directory and records each copy's checksum in the lock, printing
+/// one `<id> <checksum>` line per package. Later loads resolve those packages
+/// from `vendor/` only, and `--offline` forbids anything else. Under
+/// `--locked`/`--frozen` a lock that no longer matches the graph fails
+/// instead of being rewritten.
+pub fn cmd_vendor(args: VendorArgs, options: &ProjectLoadOptions) -> Result<()> {
+    if !args.path.join("project.toml").is_file() {
+        eprintln!("Error: {} is not a project directory (no project.toml)", args.path.display());
+        std::process::exit(66);
+    }
+    if options.lock.offline {
+        bail!("`phalcom vendor` reads dependencies from their sources, which --offline forbids");
+    }
+    let root = fs::canonicalize(&args.path).with_context(|| format!("Failed to resolve path {}", args.path.display()))?;
+    let packages = phalcom_modules::vendor::vendor_dependencies(&root, options)?;
+    for package in &packages {
+        println!("{} {}", package.id, package.checksum.as_deref().unwrap_or_default());
+    }
+    eprintln!("Vendored {} package(s)", packages.len());
+    Ok(())
+}
@@ -3,7 +3,9 @@
 pub mod cli;
 pub mod disasm;
 
-use crate::cli::{Cli, Commands, cmd_check, cmd_disasm, cmd_parse, cmd_run, cmd_tokenize, cmd_version};
+use crate::cli::{
+    Cli, Commands, cmd_build, cmd_check, cmd_disasm, cmd_doc, cmd_graph, cmd_new, cmd_package, cmd_parse, cmd_run, cmd_run_project, cmd_tokenize, cmd_update, cmd_vendor, cmd_version,
+};
 use anyhow::Result;
 use clap::Parser;
 use tracing_subscriber::filter::Targets;
@@ -34,13 +36,24 @@ fn main() -> Result<()> {
     // `phalcom_core::diagnostics::RENDER_CONFIG`'s docs for why this is a `OnceLock` bridge
     // rather than an explicitly threaded parameter today.
     phalcom_core::diagnostics::install_render_config(cli.render_config());
+    // The compile mode and a project command's `--locked`/`--frozen`/
+    // `--offline`/`--features`, passed to every command that loads a project.
+    let options = cli.project_options();
 
     let result = match cli.command {
         None => cmd_run(cli),
         Some(Commands::Tokenize(args)) => cmd_tokenize(args),
         Some(Commands::Parse(args)) => cmd_parse(args),
         Some(Commands::Disasm(args)) => cmd_disasm(args),
-        Some(Commands::Check(args)) => cmd_check(args),
+        Some(Commands::Check(args)) => cmd_check(args, &options),
+        Some(Commands::Doc(args)) => cmd_doc(args, &options),
+        Some(Commands::Update(args)) => cmd_update(args, &options),
+        Some(Commands::New(args)) => cmd_new(args),
+        Some(Commands::Build(args)) => cmd_build(args, &options),
+        Some(Commands::Run(ref args)) => cmd_run_project(&cli, args),
+        Some(Commands::Package(args)) => cmd_package(args, &options),
+        Some(Commands::Graph(args)) => cmd_graph(args, &options),
+        Some(Commands::Vendor(args)) => cmd_vendor(args, &options),
         Some(Commands::Version) => cmd_version(),
     };
 
@@ -445,4 +445,35 @@ mod tests {
             "a heavily-warmed cache must still be busted, not just a lightly-touched one"
         );
     }
+
+    #[test]
+    fn ic_entry_carries_the_interception_chain() {
+        std::thread::Builder::new()
+            .stack_size(32 * 1024 * 1024)
+            .spawn(ic_entry_carries_the_interception_chain_impl)
+            .expect("spawn inline-cache test thread")
+            .join()
+            .expect("join inline-cache test thread");
+    }
+
+    // Runtime-tier interception (`vm/intercept.rs`) keeps its chain on the
+    // method object, so a warmed cache entry needs no invalidation to keep
+    // intercepting: the handle it cached is the decorated method itself.
+    fn ic_entry_carries_the_interception_chain_impl() {
+        let mut vm = crate::vm::VM::new();
+        let module = vm.create_module("main", "ic_entry_carries_the_interception_chain");
+        let source = "@On(Method, Runtime)\nclass Skip is Attribute {\n  aroundSend(_ inv) { 7 }\n}\n\
+                      class A {\n  @Skip\n  val() { 1 }\n}\nconst a = A.new()\n\
+                      const first = a.val()\nconst second = a.val()\nconst third = a.val()\n";
+        let closure = vm.compile_closure(module, source).expect("compiles");
+        vm.run_in_module(module, closure).expect("runs");
+
+        let val_sym = vm.get_or_intern("val()");
+        let chunk = &vm.heap.closure(closure).callable.chunk;
+        let ip = warmed_invoke_index(chunk, val_sym);
+        let entry = chunk.caches[ip].get().unwrap();
+        assert_eq!(vm.heap.method(entry.method).interceptors.len(), 1, "the cached handle is the decorated method");
+        let third = vm.get_or_intern("third");
+        assert_eq!(vm.heap.module(module).get(third), Some(Value::int(7)), "a cached send still runs the chain");
+    }
 }
@@ -736,6 +736,70 @@ impl AttributeExpander for SealedExpander {
     }
 }
 
+/// Registry entry for `@mixin`, `@compose` and `@protocol`, all class-target
+/// only. Same deliberate-no-op shape as [`SealedExpander`]: the argument
+/// checks and the `_$mixin(_)`/`_$compose(_)`/`_$protocol(_)` declaration
+/// send are emitted by `compiler::lib::class_decl::Compiler::compile_class`,
+/// and mixin conflicts are resolved when the class finalizes
+/// (`vm/mixin.rs`). The row exists so `attr.unknown`/`attr.illegal_target`
+/// fire correctly.
+pub struct MixinExpander;
+impl AttributeExpander for MixinExpander {
+    fn legal_targets(&self) -> &'static [Target] {
+        &[Target::Class]
+    }
+
+    fn expand(&self, _ctx: &mut ExpandCtx, _member: &mut ClassMember, _args: &[Expr]) -> Result<(), CompilerError> {
+        Ok(())
+    }
+}
+
+/// Registry entry for `@multimethod`, method-target only. Same
+/// deliberate-no-op shape as [`MixinExpander`]: the dispatch-class checks and
+/// the `_$multimethod(_,_,_)` case install are emitted by
+/// `compiler::lib::class_decl::Compiler::compile_class`, and ambiguity is
+/// checked when the class finalizes (`vm/multimethod.rs`).
+pub struct MultimethodExpander;
+impl AttributeExpander for MultimethodExpander {
+    fn legal_targets(&self) -> &'static [Target] {
+        &[Target::Method]
+    }
+
+    fn expand(&self, _ctx: &mut ExpandCtx, _member: &mut ClassMember, _args: &[Expr]) -> Result<(), CompilerError> {
+        Ok(())
+    }
+}
+
+/// Registry entry for `@generator`, method- and getter-target. Same
+/// deliberate-no-op shape as [`MultimethodExpander`]: the body is compiled
+/// into a `Generator` over a generator block by
+/// `compiler::lib::class_decl::Compiler::compile_class`
+/// (`compiler/lib/fiber_body.rs`).
+pub struct GeneratorExpander;
+impl AttributeExpander for GeneratorExpander {
+    fn legal_targets(&self) -> &'static [Target] {
+        &[Target::Method, Target::Getter]
+    }
+
+    fn expand(&self, _ctx: &mut ExpandCtx, _member: &mut ClassMember, _args: &[Expr]) -> Result<(), CompilerError> {
+        Ok(())
+    }
+}
+
+/// Registry entry for `@async`, method- and getter-target. Same no-op shape
+/// as [`GeneratorExpander`]: the body is compiled into a `Future.async`
+/// block (`compiler/lib/fiber_body.rs`).
+pub struct AsyncExpander;
+impl AttributeExpander for AsyncExpander {
+    fn legal_targets(&self) -> &'static [Target] {
+        &[Target::Method, Target::Getter]
+    }
+
+    fn expand(&self, _ctx: &mut ExpandCtx, _member: &mut ClassMember, _args: &[Expr]) -> Result<(), CompilerError> {
+        Ok(())
+    }
+}
+
 /// Registry entry for `@variant` (U-ANNOT-LAYOUT §3.4, `annotations-data.md`
 /// §"`@variant`"). Same deliberate-no-op shape as [`GetExpander`]: `@variant`'s
 /// real derive — stripping the [`phalcom_ast::ast::ClassMember::Variant`] arm
@@ -877,13 +941,29 @@ impl AttributeExpander for ProtectedExpander {
     }
 }
 
+/// Registry entry for a behavioral decorator — `@memoize`, `@retry`, `@lazy`,
+/// `@traced`, `@synchronized`. Nothing is expanded at compile time: the
+/// attach codegen instantiates the core `Attribute` subclass the name stands
+/// for ([`BuiltinAttr::runtime_class`]), a Runtime-tier interceptor
+/// (`vm/intercept.rs`). The row carries each name's legal targets.
+pub struct DecoratorExpander(&'static [Target]);
+impl AttributeExpander for DecoratorExpander {
+    fn legal_targets(&self) -> &'static [Target] {
+        self.0
+    }
+
+    fn expand(&self, _ctx: &mut ExpandCtx, _member: &mut ClassMember, _args: &[Expr]) -> Result<(), CompilerError> {
+        Ok(())
+    }
+}
+
 pub struct AttributeRegistry {
-    expanders: [Option<Box<dyn AttributeExpander + Send + Sync>>; 16],
+    expanders: [Option<Box<dyn AttributeExpander + Send + Sync>>; 27],
 }
 
 impl Default for AttributeRegistry {
     fn default() -> Self {
-        let mut expanders: [Option<Box<dyn AttributeExpander + Send + Sync>>; 16] = Default::default();
+        let mut expanders: [Option<Box<dyn AttributeExpander + Send + Sync>>; 27] = Default::default();
         expanders[BuiltinAttr::Requires as usize] = Some(Box::new(RequiresExpander));
         expanders[BuiltinAttr::Ensures as usize] = Some(Box::new(EnsuresExpander));
         expanders[BuiltinAttr::Invariant as usize] = Some(Box::new(InvariantExpander));
@@ -900,6 +980,17 @@ impl Default for AttributeRegistry {
         expanders[BuiltinAttr::Ignore as usize] = Some(Box::new(IgnoreExpander));
         expanders[BuiltinAttr::Private as usize] = Some(Box::new(PrivateExpander));
         expanders[BuiltinAttr::Protected as usize] = Some(Box::new(ProtectedExpander));
+        expanders[BuiltinAttr::Memoize as usize] = Some(Box::new(DecoratorExpander(&[Target::Method])));
+        expanders[BuiltinAttr::Retry as usize] = Some(Box::new(DecoratorExpander(&[Target::Method])));
+        expanders[BuiltinAttr::Lazy as usize] = Some(Box::new(DecoratorExpander(&[Target::Getter])));
+        expanders[BuiltinAttr::Traced as usize] = Some(Box::new(DecoratorExpander(&[Target::Method, Target::Getter])));
+        expanders[BuiltinAttr::Synchronized as usize] = Some(Box::new(DecoratorExpander(&[Target::Method])));
+        expanders[BuiltinAttr::Mixin as usize] = Some(Box::new(MixinExpander));
+        expanders[BuiltinAttr::Compose as usize] = Some(Box::new(MixinExpander));
+        expanders[BuiltinAttr::Protocol as usize] = Some(Box::new(MixinExpander));
+        expanders[BuiltinAttr::Multimethod as usize] = Some(Box::new(MultimethodExpander));
+        expanders[BuiltinAttr::Generator as usize] = Some(Box::new(GeneratorExpander));
+        expanders[BuiltinAttr::Async as usize] = Some(Box::new(AsyncExpander));
         Self { expanders }
     }
 }
@@ -1855,6 +1946,7 @@ fn expand_variants(class: &mut ClassDef, has_sealed: bool) -> Result<Vec<Stateme
                 kind: AttrKind::Builtin(BuiltinAttr::Data),
                 name: "data".to_string(),
                 args: Vec::new(),
+                labeled_args: Vec::new(),
                 range: v.range,
             }],
             invariants: Vec::new(),
@@ -1903,6 +1995,7 @@ fn expand_variants(class: &mut ClassDef, has_sealed: bool) -> Result<Vec<Stateme
         name: "__synthetic".to_string(),
         kind: AttrKind::User("__synthetic".to_string()),
         args: Vec::new(),
+        labeled_args: Vec::new(),
         range: match_range,
     };
     class.members.push(ClassMember::Method(MethodDef {
@@ -2103,6 +2196,19 @@ fn member_has_attr(member: &ClassMember, name: &str) -> bool {
     attrs.iter().any(|a| a.name == name)
 }
 
+/// Rejects `label: expr` arguments. Only `@cfg` reads them, and
+/// `phalcom_modules::cfg` removes every `@cfg` before compilation, so a
+/// labeled argument reaching an expander would otherwise be dropped silently.
+fn reject_labeled_args(attr: &Attribute) -> Result<(), CompilerError> {
+    match attr.labeled_args.first() {
+        Some((label, _)) => Err(CompilerError::Message(format!(
+            "attr.labeled_arg: attribute `@{}` takes no labeled argument `{label}:`",
+            attr.name
+        ))),
+        None => Ok(()),
+    }
+}
+
 /// Expands every `@name(args…)` attribute attached to `class` or one of its
 /// members (U-ANNOT-CONTRACTS's core pass, grown by U-ANNOT-LAYOUT's `@get`/
 /// `@set`/`@construct`/`@data`/`@sealed`/`@variant` rows) into ordinary AST,
@@ -2215,7 +2321,18 @@ pub fn expand_class_attributes(
                     | BuiltinAttr::Native
                     | BuiltinAttr::Ignore
                     | BuiltinAttr::Private
-                    | BuiltinAttr::Protected => {}
+                    | BuiltinAttr::Protected
+                    | BuiltinAttr::Memoize
+                    | BuiltinAttr::Retry
+                    | BuiltinAttr::Lazy
+                    | BuiltinAttr::Traced
+                    | BuiltinAttr::Synchronized
+                    | BuiltinAttr::Mixin
+                    | BuiltinAttr::Compose
+                    | BuiltinAttr::Protocol
+                    | BuiltinAttr::Multimethod
+                    | BuiltinAttr::Generator
+                    | BuiltinAttr::Async => {}
                 },
                 AttrKind::User(_) => {}
             }
@@ -2227,6 +2344,7 @@ pub fn expand_class_attributes(
         } else {
             return Err(CompilerError::Message(format!("attr.unknown: unknown attribute `@{}`", attr.name)));
         }
+        reject_labeled_args(attr)?;
     }
 
     if is_attribute_class {
@@ -2317,6 +2435,7 @@ pub fn expand_class_attributes(
             } else {
                 return Err(CompilerError::Message(format!("attr.unknown: unknown attribute `@{}`", attr.name)));
             }
+            reject_labeled_args(attr)?;
         }
 
         let visibility_count = attrs
@@ -2369,6 +2488,12 @@ pub fn expand_class_attributes(
                     "attr.illegal_target: `@constructor` cannot combine with `@class`".to_string(),
                 ));
             }
+            if let Some(wrapper) = method.attributes.iter().find(|a| a.name == "generator" || a.name == "async") {
+                return Err(CompilerError::Message(format!(
+                    "attr.illegal_target: `@constructor` cannot combine with `@{}`",
+                    wrapper.name
+                )));
+            }
             method.is_constructor = true;
             method.attributes.retain(|a| a.name != "constructor");
         }
@@ -2502,6 +2627,7 @@ fn lower_constructors(members: &mut Vec<ClassMember>) {
                 kind: AttrKind::Builtin(BuiltinAttr::Constructor),
                 name: "constructor".to_string(),
                 args: Vec::new(),
+                labeled_args: Vec::new(),
                 range,
             }],
             range,
@@ -2590,8 +2716,9 @@ fn statement_range(stmt: &Statement) -> SourceRange {
         Statement::Expr { range, .. } => *range,
         Statement::For(f) => f.range,
         Statement::Break { range } | Statement::Continue { range } => *range,
-        Statement::Throw { range, .. } => *range,
+        Statement::Throw { range, .. } | Statement::Yield { range, .. } => *range,
         Statement::Export(e) => e.range,
+        Statement::Extend(e) => e.range,
     }
 }
 
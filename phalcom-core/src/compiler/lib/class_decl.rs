@@ -9,13 +9,15 @@ use crate::value::Value;
 use crate::vm::ClassKey;
 use indexmap::IndexMap;
 use phalcom_ast::ast::{
-    AttrKind, Attribute, BuiltinAttr, ClassDef, ClassMember, ClosureParameters, Expr, IndexAccessor, ListLiteralElement, MapLiteralEntry, MapLiteralKey,
-    MethodCallExpr, PackItem, PackLabel, RestMode, SetLiteralEntry, Statement,
+    AttrKind, Attribute, BuiltinAttr, ClassDef, ClassMember, ClosureParameters, Expr, GetPropertyExpr, IndexAccessor, ListLiteralElement, ListLiteralExpr,
+    MapLiteralEntry, MapLiteralKey, MethodCallExpr, MethodDef, PackItem, PackLabel, ParameterDef, RestMode, SetLiteralEntry, Statement, StaticSymbolRef,
+    TypeAnnotationExpr,
 };
 use phalcom_common::range::SourceRange;
 
 use super::checked_send_arity;
 use super::error::{CompilerError, RestDeclarationErrorKind};
+use super::state::FiberBody;
 use super::{Compiler, UnitKind};
 
 /// Attribute names handled entirely by the compiler itself — the guard weave
@@ -46,9 +48,263 @@ const COMPILER_ONLY_ATTRS: &[&str] = &[
     "on",
     "native",
     "ignore",
+    "mixin",
+    "compose",
+    "protocol",
+    "multimethod",
+    "generator",
+    "async",
 ];
 
-fn member_visibility(name: Option<&str>, attributes: &[Attribute]) -> MemberVisibility {
+/// Whether `attr` is one of the two mixin declarations, `@mixin` or
+/// `@compose` (`vm/mixin.rs`).
+fn is_mixin_attr(attr: &Attribute) -> bool {
+    matches!(attr.kind, AttrKind::Builtin(BuiltinAttr::Mixin | BuiltinAttr::Compose))
+}
+
+/// Checks the compile-time shape of a `@mixin` or `@compose` class: not both
+/// at once, a non-empty `@compose` list, arguments that are mixin names (or,
+/// for `@mixin`, selector symbols and protocol names), and — for a mixin —
+/// no superclass, no instance fields and no constructor. Conflicts and
+/// requirements need the mixins' method tables and are checked when the
+/// class finalizes.
+///
+/// # Errors
+///
+/// A `mixin.*` [`CompilerError::Message`] naming the violated rule.
+fn validate_mixin_declaration(
+    class_def: &ClassDef,
+    attrs: &[Attribute],
+    own_instance_fields: &[Symbol],
+    interner: &crate::interner::Interner,
+) -> Result<(), CompilerError> {
+    let is_mixin = attrs.iter().any(|a| matches!(a.kind, AttrKind::Builtin(BuiltinAttr::Mixin)));
+    let compose = attrs.iter().find(|a| matches!(a.kind, AttrKind::Builtin(BuiltinAttr::Compose)));
+    let name = &class_def.name;
+    if is_mixin && compose.is_some() {
+        return Err(CompilerError::Message(format!(
+            "mixin.compose: mixin `{name}` lists the mixins it composes in `@mixin(...)`, not `@compose`"
+        )));
+    }
+    if compose.is_some_and(|attr| attr.args.is_empty()) {
+        return Err(CompilerError::Message(format!(
+            "mixin.compose: `@compose` on `{name}` needs at least one mixin"
+        )));
+    }
+    for attr in attrs.iter().filter(|a| is_mixin_attr(a)) {
+        for arg in &attr.args {
+            let legal = match arg {
+                Expr::Var { .. } | Expr::GetProperty(_) => true,
+                Expr::Symbol(_) => is_mixin,
+                _ => false,
+            };
+            if !legal {
+                let expected = if is_mixin {
+                    "selector symbols, protocol names or mixin names"
+                } else {
+                    "mixin names"
+                };
+                return Err(CompilerError::Message(format!("mixin.argument: `@{}` arguments are {expected}", attr.name)));
+            }
+        }
+    }
+    if !is_mixin {
+        return Ok(());
+    }
+    if class_def.superclass.is_some() {
+        return Err(CompilerError::Message(format!(
+            "mixin.superclass: mixin `{name}` cannot declare a superclass; it applies to whatever class composes it"
+        )));
+    }
+    if let Some(field) = own_instance_fields.first() {
+        return Err(CompilerError::Message(format!(
+            "mixin.field: mixin `{name}` cannot have instance fields (`{}`); it has no layout of its own",
+            interner.lookup(*field)
+        )));
+    }
+    if class_def
+        .members
+        .iter()
+        .any(|m| matches!(m, ClassMember::Method(method) if method.is_constructor))
+    {
+        return Err(CompilerError::Message(format!(
+            "mixin.constructor: mixin `{name}` cannot declare a constructor; its hosts construct their instances"
+        )));
+    }
+    Ok(())
+}
+
+/// Whether `attr` is `@protocol` (`vm/protocol.rs`).
+fn is_protocol_attr(attr: &Attribute) -> bool {
+    matches!(attr.kind, AttrKind::Builtin(BuiltinAttr::Protocol))
+}
+
+/// Checks the compile-time shape of a `@protocol` class: no mixin
+/// attributes, arguments that are protocol names, no superclass, no fields,
+/// no constructor, and only instance-side requirements written as signatures
+/// with an empty body (`size -> Int {}`).
+///
+/// # Errors
+///
+/// A `protocol.*` [`CompilerError::Message`] naming the violated rule.
+fn validate_protocol_declaration(class_def: &ClassDef, attrs: &[Attribute]) -> Result<(), CompilerError> {
+    let name = &class_def.name;
+    if attrs.iter().any(is_mixin_attr) {
+        return Err(CompilerError::Message(format!(
+            "protocol.combination: protocol `{name}` cannot also be a mixin or compose mixins"
+        )));
+    }
+    for attr in attrs.iter().filter(|a| is_protocol_attr(a)) {
+        if attr.args.iter().any(|arg| !matches!(arg, Expr::Var { .. } | Expr::GetProperty(_))) {
+            return Err(CompilerError::Message(
+                "protocol.argument: `@protocol` arguments are the protocols it includes".to_string(),
+            ));
+        }
+    }
+    if class_def.superclass.is_some() {
+        return Err(CompilerError::Message(format!(
+            "protocol.superclass: protocol `{name}` cannot declare a superclass; include other protocols with `@protocol(...)`"
+        )));
+    }
+    if class_def
+        .members
+        .iter()
+        .any(|m| matches!(m, ClassMember::Method(method) if method.is_constructor))
+    {
+        return Err(CompilerError::Message(format!(
+            "protocol.constructor: protocol `{name}` cannot declare a constructor; it has no instances"
+        )));
+    }
+    for member in &class_def.members {
+        let (label, body, is_static) = match member {
+            ClassMember::Field(field) => {
+                return Err(CompilerError::Message(format!(
+                    "protocol.field: protocol `{name}` cannot have fields (`{}`); it describes behavior only",
+                    field.name
+                )));
+            }
+            ClassMember::Method(method) => (method.name.as_str(), &method.body, method.is_static),
+            ClassMember::Getter(getter) => (getter.name.as_str(), &getter.body, getter.is_static),
+            ClassMember::Setter(setter) => (setter.name.as_str(), &setter.body, setter.is_static),
+            ClassMember::Index(index) => ("[]", &index.body, false),
+            ClassMember::Variant(_) => continue,
+        };
+        if is_static {
+            return Err(CompilerError::Message(format!(
+                "protocol.member: protocol `{name}` requirement `{label}` must be an instance member"
+            )));
+        }
+        if !body.is_empty() {
+            return Err(CompilerError::Message(format!(
+                "protocol.body: protocol `{name}` requirement `{label}` has a body; write its signature with `{{}}`"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Whether `attrs` marks a method as a multimethod case
+/// (`vm/multimethod.rs`).
+fn is_multimethod_case(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|a| matches!(a.kind, AttrKind::Builtin(BuiltinAttr::Multimethod)))
+}
+
+/// The fiber a method or getter's body runs on when it carries `@generator`
+/// or `@async` (`compiler/lib/fiber_body.rs`).
+pub(super) fn fiber_body(attrs: &[Attribute]) -> Option<FiberBody> {
+    attrs.iter().find_map(|a| match a.kind {
+        AttrKind::Builtin(BuiltinAttr::Generator) => Some(FiberBody::Generator),
+        AttrKind::Builtin(BuiltinAttr::Async) => Some(FiberBody::Async),
+        _ => None,
+    })
+}
+
+/// The class a multimethod case dispatches on for `param`: its annotation,
+/// or `None` for an unannotated parameter, which accepts any `Object`.
+fn dispatch_class(param: &ParameterDef) -> Option<&StaticSymbolRef> {
+    match param.annotation.as_ref().map(|annotation| &annotation.expr) {
+        Some(TypeAnnotationExpr::Reference(reference)) => Some(reference),
+        _ => None,
+    }
+}
+
+/// Checks the compile-time shape of a `@multimethod` case of class `name`
+/// and returns its dispatch classes as written, `Object` for an unannotated
+/// parameter: an instance or static method with fixed parameters, each
+/// annotated with at most a class name, in a class that is neither a mixin
+/// nor a protocol. Ambiguity between cases needs the classes themselves and
+/// is checked when the class finalizes.
+///
+/// # Errors
+///
+/// A `multimethod.*` [`CompilerError::Message`] naming the violated rule.
+fn validate_multimethod_case(name: &str, method: &MethodDef, class_attrs: &[Attribute]) -> Result<Vec<String>, CompilerError> {
+    let selector = &method.name;
+    if class_attrs.iter().any(|a| is_mixin_attr(a) || is_protocol_attr(a)) {
+        return Err(CompilerError::Message(format!(
+            "multimethod.target: `{selector}` in `{name}` cannot be a multimethod; mixins and protocols declare plain methods"
+        )));
+    }
+    if method
+        .attributes
+        .iter()
+        .any(|a| matches!(a.kind, AttrKind::Builtin(BuiltinAttr::Multimethod)) && !a.args.is_empty())
+    {
+        return Err(CompilerError::Message("multimethod.argument: `@multimethod` takes no arguments".to_string()));
+    }
+    if method.is_constructor {
+        return Err(CompilerError::Message(format!(
+            "multimethod.target: constructor `{selector}` of `{name}` cannot be a multimethod"
+        )));
+    }
+    if method.params.iter().any(ParameterDef::is_rest) {
+        return Err(CompilerError::Message(format!(
+            "multimethod.rest: multimethod `{selector}` of `{name}` cannot take rest parameters; it dispatches on each argument"
+        )));
+    }
+    let mut classes = Vec::with_capacity(method.params.len());
+    for param in &method.params {
+        match (&param.annotation, dispatch_class(param)) {
+            (None, _) => classes.push("Object".to_string()),
+            (Some(_), Some(reference)) => {
+                let mut path = reference.root.clone();
+                for segment in &reference.members {
+                    path.push('.');
+                    path.push_str(&segment.name);
+                }
+                classes.push(path);
+            }
+            (Some(_), None) => {
+                return Err(CompilerError::Message(format!(
+                    "multimethod.dispatch_type: parameter `{}` of multimethod `{selector}` must be annotated with a class name",
+                    param.name
+                )));
+            }
+        }
+    }
+    Ok(classes)
+}
+
+/// The expression that reads the class `reference` names at runtime: a
+/// global, or a property chain off an imported module alias.
+pub(super) fn static_ref_expr(reference: &StaticSymbolRef) -> Expr {
+    reference.members.iter().fold(
+        Expr::Var {
+            value: reference.root.clone(),
+            range: reference.root_range,
+        },
+        |object, segment| {
+            Expr::GetProperty(Box::new(GetPropertyExpr {
+                object,
+                property: segment.name.clone(),
+                property_range: Some(segment.range),
+                range: reference.range,
+            }))
+        },
+    )
+}
+
+pub(super) fn member_visibility(name: Option<&str>, attributes: &[Attribute]) -> MemberVisibility {
     if name.is_some_and(|name| name.starts_with("_$")) {
         MemberVisibility::Internal
     } else if attributes.iter().any(|attr| matches!(attr.kind, AttrKind::Builtin(BuiltinAttr::Private))) {
@@ -160,7 +416,7 @@ fn rest_selector(name: &str, params: &[phalcom_ast::ast::ParameterDef]) -> Strin
 
 /// Source parsing catches this first; compiler passes can synthesize members,
 /// so preserve the declaration-side selector invariant after expansion too.
-fn validate_declaration_labels(params: &[phalcom_ast::ast::ParameterDef]) -> Result<(), CompilerError> {
+pub(super) fn validate_declaration_labels(params: &[phalcom_ast::ast::ParameterDef]) -> Result<(), CompilerError> {
     let mut seen = std::collections::HashMap::<String, SourceRange>::new();
     for param in params {
         let Some(label) = &param.label else {
@@ -293,14 +549,22 @@ impl<'vm> Compiler<'vm> {
         // using the member's encoded selector identity (`encode_selector`/`SignatureKind`).
         // `Variant` never appears here (`expand_variants` already stripped it).
         {
-            #[derive(PartialEq, Eq, Hash)]
+            #[derive(Clone, PartialEq, Eq, Hash)]
             enum MemberKey {
                 Field(String),
                 Selector(bool, String),
             }
             let mut seen: std::collections::HashMap<MemberKey, (String, SourceRange)> = std::collections::HashMap::new();
             let mut rest_families: std::collections::HashMap<(bool, String), SourceRange> = std::collections::HashMap::new();
+            // `@multimethod` cases share their selector with each other —
+            // never with a plain member — and differ in dispatch classes.
+            let mut case_families: std::collections::HashSet<MemberKey> = std::collections::HashSet::new();
+            let mut case_signatures: std::collections::HashSet<(MemberKey, Vec<String>)> = std::collections::HashSet::new();
             for member in &class_def.members {
+                let case_classes = match member {
+                    ClassMember::Method(m) if is_multimethod_case(&m.attributes) => Some(validate_multimethod_case(&class_def.name, m, &class_level_attrs)?),
+                    _ => None,
+                };
                 let (key, display, member_name_range) = match member {
                     ClassMember::Field(f) => (MemberKey::Field(f.name.clone()), f.name.clone(), f.range),
                     ClassMember::Method(m) => {
@@ -340,7 +604,9 @@ impl<'vm> Compiler<'vm> {
                 {
                     return Err(CompilerError::Message(format!("reserved semantic selector `{selector}` cannot be declared",)));
                 }
-                if let Some((_, first_range)) = seen.get(&key) {
+                if let Some((_, first_range)) = seen.get(&key)
+                    && !(case_classes.is_some() && case_families.contains(&key))
+                {
                     let first_range = *first_range;
                     let source = self.source_text();
                     let (line, col) = crate::diagnostics::line_col(&source, first_range.start);
@@ -367,6 +633,17 @@ impl<'vm> Compiler<'vm> {
                         first_col,
                     });
                 }
+                if let Some(classes) = case_classes {
+                    if case_signatures.contains(&(key.clone(), classes.clone())) {
+                        return Err(CompilerError::Message(format!(
+                            "multimethod.duplicate: `{}` declares two `{display}` cases for ({})",
+                            class_def.name,
+                            classes.join(", ")
+                        )));
+                    }
+                    case_families.insert(key.clone());
+                    case_signatures.insert((key.clone(), classes));
+                }
                 seen.insert(key, (display, member_name_range));
             }
         }
@@ -512,6 +789,19 @@ impl<'vm> Compiler<'vm> {
             }
         }
 
+        // Mixins (`vm/mixin.rs`): a `@mixin` is a bundle of instance methods
+        // with no layout, constructor or superclass of its own; its
+        // arguments are required selectors and the mixins it composes. A
+        // host names its mixins with `@compose(...)`.
+        if class_level_attrs.iter().any(is_mixin_attr) {
+            validate_mixin_declaration(&class_def, &class_level_attrs, &own_instance_fields, &self.vm.interner)?;
+        }
+        // Protocols (`vm/protocol.rs`): a `@protocol` lists the selectors a
+        // conforming object answers, as bodiless signatures.
+        if class_level_attrs.iter().any(is_protocol_attr) {
+            validate_protocol_declaration(&class_def, &class_level_attrs)?;
+        }
+
         // 1b. Classes are closed (PDR-0001, U-CLASSCLOSE §2.1/§4). A
         // class is defined exactly once, by exactly one module; there is no
         // reopening. "The class being declared" is looked up by an
@@ -840,7 +1130,11 @@ impl<'vm> Compiler<'vm> {
                         .attributes
                         .iter()
                         .any(|attr| matches!(attr.kind, AttrKind::Builtin(BuiltinAttr::Constructor)) || attr.name == "__synthetic");
-                    let closure_result = self.compile_block(method_def.body, selector_sym, ClosureParameters::fixed(param_names), true, false, None);
+                    let closure_result = if let Some(kind) = fiber_body(&method_def.attributes) {
+                        self.compile_fiber_member(kind, method_def.body, selector_sym, ClosureParameters::fixed(param_names))
+                    } else {
+                        self.compile_block(method_def.body, selector_sym, ClosureParameters::fixed(param_names), true, false, None)
+                    };
                     self.compiler_internal = prior_compiler_internal;
                     let closure = closure_result?;
 
@@ -867,10 +1161,14 @@ impl<'vm> Compiler<'vm> {
                     }
 
                     let method_obj_idx = self.add_constant(Value::obj(method_obj));
-                    self.emit(Bytecode::Constant(method_obj_idx), range);
+                    if is_multimethod_case(&method_def.attributes) {
+                        self.emit_multimethod_case(&method_def.params, method_obj_idx, method_def.is_static, range)?;
+                    } else {
+                        self.emit(Bytecode::Constant(method_obj_idx), range);
 
-                    let selector_idx = self.add_constant(Value::symbol(selector_sym));
-                    self.emit(Bytecode::Method(selector_idx, method_def.is_static), range);
+                        let selector_idx = self.add_constant(Value::symbol(selector_sym));
+                        self.emit(Bytecode::Method(selector_idx, method_def.is_static), range);
+                    }
 
                     self.emit_member_attribute_attaches(&method_def.attributes, method_obj_idx, range)?;
                 }
@@ -881,7 +1179,11 @@ impl<'vm> Compiler<'vm> {
                     let selector_sym = self.vm.interner.intern(&selector);
 
                     self.is_static_context = getter_def.is_static;
-                    let closure = self.compile_block(getter_def.body, selector_sym, ClosureParameters::default(), true, false, None)?;
+                    let closure = if let Some(kind) = fiber_body(&getter_def.attributes) {
+                        self.compile_fiber_member(kind, getter_def.body, selector_sym, ClosureParameters::default())?
+                    } else {
+                        self.compile_block(getter_def.body, selector_sym, ClosureParameters::default(), true, false, None)?
+                    };
 
                     tracing::debug!("[Compiler] Compiling getter: {} (static: {})", selector, getter_def.is_static);
 
@@ -1072,6 +1374,13 @@ impl<'vm> Compiler<'vm> {
 
         self.current_class = None;
 
+        // The mixin or protocol declaration send (`vm/mixin.rs`,
+        // `vm/protocol.rs`): after every member is installed, before
+        // `FinalizeClass` applies the mixins it names.
+        if let Some(attr) = class_level_attrs.iter().find(|a| is_mixin_attr(a) || is_protocol_attr(a)) {
+            self.emit_mixin_declaration(attr)?;
+        }
+
         // Rebuild the class's (and its metaclass's) base-name index
         // (selectors.md §3.1, U16-Open) now that every member of this
         // body has been attached — peeks, does not pop, the class
@@ -1169,7 +1478,7 @@ impl<'vm> Compiler<'vm> {
     ///
     /// Propagates any error compiling a retained attribute's constructor
     /// arguments.
-    fn emit_member_attribute_attaches(&mut self, attrs: &[Attribute], method_obj_idx: u16, range: SourceRange) -> Result<(), CompilerError> {
+    pub(super) fn emit_member_attribute_attaches(&mut self, attrs: &[Attribute], method_obj_idx: u16, range: SourceRange) -> Result<(), CompilerError> {
         let mut any = false;
         for attr in attrs {
             if COMPILER_ONLY_ATTRS.contains(&attr.name.as_str()) {
@@ -1189,7 +1498,9 @@ impl<'vm> Compiler<'vm> {
     /// Compiles `AttrName.new(args…)` (`@Name(args)` desugars to a normal,
     /// fully positional constructor send — attribute arg lists are
     /// positional-only, `parser.rs`'s `parse_attribute_arg_list`, filed to
-    /// `docs/forge/DEFERRED.md`) and immediately sends `__attach(_)` with it
+    /// `docs/forge/DEFERRED.md`; a behavioral decorator's lowercase name
+    /// stands for its core class, [`BuiltinAttr::runtime_class`]) and
+    /// immediately sends `__attach(_)` with it
     /// as the sole argument, against whatever receiver is already on top of
     /// the stack (a class or method object the caller pushed). Leaves the
     /// stack exactly as found: the `__attach` call's `None` result is
@@ -1203,7 +1514,10 @@ impl<'vm> Compiler<'vm> {
         let range = attr.range;
         let ctor_call = Expr::MethodCall(Box::new(MethodCallExpr {
             object: Expr::Var {
-                value: attr.name.clone(),
+                value: match attr.kind {
+                    AttrKind::Builtin(builtin) => builtin.runtime_class().map_or_else(|| attr.name.clone(), str::to_string),
+                    AttrKind::User(_) => attr.name.clone(),
+                },
                 range,
             },
             method: "new".to_string(),
@@ -1221,6 +1535,68 @@ impl<'vm> Compiler<'vm> {
         Ok(())
     }
 
+    /// Installs the multimethod case `method_obj_idx` on the class on top of
+    /// the stack: sends it `_$multimethod(_,_,_)` with the case, a `List` of
+    /// the classes its `params` dispatch on (`Object` where unannotated) and
+    /// whether it is class-side, then pops the result. Stands in for the
+    /// case's [`Bytecode::Method`]; the shape is already checked by
+    /// [`validate_multimethod_case`].
+    ///
+    /// # Errors
+    ///
+    /// Propagates any error compiling a class reference.
+    fn emit_multimethod_case(&mut self, params: &[ParameterDef], method_obj_idx: u16, is_static: bool, range: SourceRange) -> Result<(), CompilerError> {
+        self.emit(Bytecode::Dup, range);
+        self.emit(Bytecode::Constant(method_obj_idx), range);
+        let elements = params
+            .iter()
+            .map(|param| {
+                let expr = match dispatch_class(param) {
+                    Some(reference) => static_ref_expr(reference),
+                    None => Expr::Var {
+                        value: "Object".to_string(),
+                        range: param.range,
+                    },
+                };
+                ListLiteralElement::Element { expr, range: param.range }
+            })
+            .collect();
+        self.compile_expr(Expr::ListLiteral(Box::new(ListLiteralExpr { elements, range })))?;
+        self.emit(if is_static { Bytecode::True } else { Bytecode::False }, range);
+        let selector = make_signature("_$multimethod", SignatureKind::Method(3));
+        let selector_sym = self.vm.interner.intern(&selector);
+        let selector_idx = self.add_constant(Value::symbol(selector_sym));
+        self.emit(Bytecode::InvokeCompilerInternal(3, selector_idx), range);
+        self.emit(Bytecode::Pop, range);
+        Ok(())
+    }
+
+    /// Sends `_$mixin(_)`, `_$compose(_)` or `_$protocol(_)` — by `attr`'s
+    /// name — to the class on top of the stack, with a `List` of the
+    /// attribute's arguments, and pops the result. Arguments are already
+    /// checked by [`validate_mixin_declaration`] or
+    /// [`validate_protocol_declaration`]: selector symbols evaluate to
+    /// themselves, mixin and protocol names to their classes.
+    ///
+    /// # Errors
+    ///
+    /// Propagates any error compiling an argument (e.g. an unknown global).
+    fn emit_mixin_declaration(&mut self, attr: &Attribute) -> Result<(), CompilerError> {
+        let range = attr.range;
+        self.emit(Bytecode::Dup, range);
+        let list = Expr::ListLiteral(Box::new(ListLiteralExpr {
+            elements: attr.args.iter().map(|expr| ListLiteralElement::Element { expr: expr.clone(), range }).collect(),
+            range,
+        }));
+        self.compile_expr(list)?;
+        let selector = make_signature(&format!("_${}", attr.name), SignatureKind::Method(1));
+        let selector_sym = self.vm.interner.intern(&selector);
+        let selector_idx = self.add_constant(Value::symbol(selector_sym));
+        self.emit(Bytecode::InvokeCompilerInternal(1, selector_idx), range);
+        self.emit(Bytecode::Pop, range);
+        Ok(())
+    }
+
     /// Sends `__freezeAttributes()` against whatever receiver is already on
     /// top of the stack, then pops its `None` result — see
     /// [`Self::emit_attribute_attach`] for the receiver-already-pushed
@@ -1247,7 +1623,7 @@ impl<'vm> Compiler<'vm> {
     /// # Errors
     ///
     /// Propagates any error compiling a predicate expression.
-    fn build_contracts_metadata(&mut self, attrs: &[Attribute]) -> Result<Vec<(Symbol, Value)>, CompilerError> {
+    pub(super) fn build_contracts_metadata(&mut self, attrs: &[Attribute]) -> Result<Vec<(Symbol, Value)>, CompilerError> {
         let mut contracts = Vec::new();
         let mut requires_n = 0usize;
         let mut ensures_n = 0usize;
@@ -1395,7 +1771,7 @@ fn collect_assigned_fields_stmt(stmt: &Statement, fields: &mut Vec<Symbol>, inte
                 collect_assigned_fields_stmt(body_stmt, fields, interner);
             }
         }
-        Statement::Throw { expr, .. } => {
+        Statement::Throw { expr, .. } | Statement::Yield { expr, .. } => {
             collect_assigned_fields(expr, fields, interner);
         }
         _ => {}
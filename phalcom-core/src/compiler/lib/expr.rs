@@ -1262,9 +1262,9 @@ impl<'vm> Compiler<'vm> {
                 }
             }
             Expr::Unary(unary_expr) => {
-                // All four unary operators lower to bare getter sends so user-defined
+                // Every unary operator lowers to a bare getter send so user-defined
                 // classes can implement them as zero-arg getters with bare selector
-                // names: `+`, `-`, `not`, `~`.
+                // names: `+`, `-`, `not`, `~`, `await`.
                 self.compile_expr(unary_expr.expr)?;
                 let range = unary_expr.range;
                 match unary_expr.op {
@@ -1272,6 +1272,7 @@ impl<'vm> Compiler<'vm> {
                     UnaryOp::Minus => self.emit_getter_send("-", range),
                     UnaryOp::Not => self.emit_getter_send("not", range),
                     UnaryOp::BitNot => self.emit_getter_send("~", range),
+                    UnaryOp::Await => self.emit_getter_send("await", range),
                 }
             }
             Expr::Membership(m) => {
@@ -0,0 +1,212 @@
+//! `extend Name { members }`: methods, getters and setters added to an
+//! existing class from the declaring module (`vm/extension.rs`).
+//!
+//! An extension never touches the class's method dictionary. Each member
+//! compiles to an ordinary method object and is handed to the class with
+//! `_$extend(_,_)`, which files it in the declaring module's extension table;
+//! only sends compiled in that module, or in a module importing it directly,
+//! see it.
+
+use crate::bytecode::Bytecode;
+use crate::compiler::attributes::{AttributeRegistry, CompileMode, ExpandCtx, expand_class_attributes};
+use crate::heap::{ObjRef, Object};
+use crate::method::{MethodKind, MethodObject, SignatureKind, encode_selector, make_signature};
+use crate::value::Value;
+use phalcom_ast::ast::{AttrKind, Attribute, BuiltinAttr, ClassDef, ClassMember, ClosureParameters, ExtendDef, ParameterDef};
+use phalcom_common::range::SourceRange;
+
+use super::Compiler;
+use super::checked_send_arity;
+use super::class_decl::{fiber_body, member_visibility, static_ref_expr, validate_declaration_labels};
+use super::error::CompilerError;
+
+/// What an `extend` block refuses for `member`, if anything: every member
+/// that needs the class's own layout or method dictionary.
+fn refused_member(member: &ClassMember) -> Option<&'static str> {
+    let attrs = match member {
+        ClassMember::Field(_) => return Some("fields"),
+        ClassMember::Index(_) => return Some("subscript methods"),
+        ClassMember::Variant(_) => return Some("`@variant` arms"),
+        ClassMember::Method(method) if method.is_constructor => return Some("constructors"),
+        ClassMember::Method(method) => &method.attributes,
+        ClassMember::Getter(getter) => &getter.attributes,
+        ClassMember::Setter(setter) => &setter.attributes,
+    };
+    attrs.iter().find_map(|attr| match attr.kind {
+        AttrKind::Builtin(BuiltinAttr::Constructor) => Some("constructors"),
+        AttrKind::Builtin(BuiltinAttr::Multimethod) => Some("`@multimethod` cases"),
+        AttrKind::Builtin(BuiltinAttr::Private | BuiltinAttr::Protected) => Some("private or protected members"),
+        _ => None,
+    })
+}
+
+impl Compiler<'_> {
+    /// Compiles an `extend` block: evaluates the target class, then installs
+    /// each member with `_$extend(_,_)` against it and pops the class.
+    ///
+    /// Members run through the same attribute expansion as a class body, so
+    /// `@class`, `@async`, `@generator`, `@requires` and `@ensures` mean what
+    /// they mean there.
+    ///
+    /// # Errors
+    ///
+    /// `extend.member` for a field, constructor, subscript, `@variant` arm,
+    /// multimethod case or private member; `extend.rest` for a rest
+    /// parameter; any attribute-expansion error or error compiling a member
+    /// body.
+    pub(super) fn compile_extend(&mut self, extend_def: ExtendDef) -> Result<(), CompilerError> {
+        let ExtendDef { target, members, range } = extend_def;
+        let class_name = target.leaf_name().to_string();
+        for member in &members {
+            if let Some(what) = refused_member(member) {
+                return Err(CompilerError::Message(format!(
+                    "extend.member: an `extend` block adds methods, getters and setters to `{class_name}`; {what} belong in the class itself"
+                )));
+            }
+            if let ClassMember::Method(method) = member
+                && method.params.iter().any(ParameterDef::is_rest)
+            {
+                return Err(CompilerError::Message(format!(
+                    "extend.rest: extension method `{}` of `{class_name}` cannot take rest parameters",
+                    method.name
+                )));
+            }
+            let reserved = match member {
+                ClassMember::Method(m) => Some((&m.name, m.name_range)),
+                ClassMember::Getter(g) => Some((&g.name, g.name_range)),
+                ClassMember::Setter(s) => Some((&s.name, s.name_range)),
+                _ => None,
+            };
+            if let Some((name, name_range)) = reserved
+                && name.starts_with("_$")
+                && !self.compiling_privileged_core()
+            {
+                return Err(CompilerError::InternalNamespaceReserved(name.clone(), name_range));
+            }
+        }
+
+        let strip_metadata = match self.vm.compile_mode {
+            CompileMode::Debug => false,
+            CompileMode::Release => self.vm.strip_contract_metadata,
+            CompileMode::Unchecked => true,
+        };
+        let core_module = self.vm.core_module();
+        let mut ctx = ExpandCtx {
+            interner: &mut self.vm.interner,
+            compile_mode: self.vm.compile_mode,
+            strip_metadata,
+            class_parents: &self.vm.class_parents,
+            sealed_classes: &self.vm.sealed_classes,
+            module: self.module,
+            core_module,
+        };
+        let shell = ClassDef {
+            name: class_name,
+            superclass: None,
+            members,
+            attributes: Vec::new(),
+            invariants: Vec::new(),
+            range,
+            name_range: target.range,
+        };
+        let (shell, _) = expand_class_attributes(shell, &mut ctx, &AttributeRegistry::new(), false)?;
+
+        self.compile_expr(static_ref_expr(&target))?;
+        for member in shell.members {
+            let (method_obj, attributes, is_static, member_range) = self.compile_extension_member(member, strip_metadata)?;
+            let method_obj_idx = self.add_constant(Value::obj(method_obj));
+            self.emit(Bytecode::Dup, member_range);
+            self.emit(Bytecode::Constant(method_obj_idx), member_range);
+            self.emit(if is_static { Bytecode::True } else { Bytecode::False }, member_range);
+            let selector = make_signature("_$extend", SignatureKind::Method(2));
+            let selector_sym = self.vm.interner.intern(&selector);
+            let selector_idx = self.add_constant(Value::symbol(selector_sym));
+            self.emit(Bytecode::InvokeCompilerInternal(2, selector_idx), member_range);
+            self.emit(Bytecode::Pop, member_range);
+            self.emit_member_attribute_attaches(&attributes, method_obj_idx, member_range)?;
+        }
+        self.is_static_context = false;
+        self.emit(Bytecode::Pop, range);
+        Ok(())
+    }
+
+    /// Compiles one expanded extension member into a method object, returning
+    /// it with the member's attributes, side and span. The body compiles
+    /// outside any class: it reaches the receiver only through `self` and its
+    /// public selectors, never its fields or `super`.
+    fn compile_extension_member(&mut self, member: ClassMember, strip_metadata: bool) -> Result<(ObjRef, Vec<Attribute>, bool, SourceRange), CompilerError> {
+        let (name, kind, params, body, attributes, is_static, range, fiber) = match member {
+            ClassMember::Method(method) => {
+                validate_declaration_labels(&method.params)?;
+                let arity = checked_send_arity("method declaration", method.params.len(), method.range)?;
+                let fiber = fiber_body(&method.attributes);
+                (
+                    method.name,
+                    SignatureKind::Method(arity),
+                    method.params,
+                    method.body,
+                    method.attributes,
+                    method.is_static,
+                    method.range,
+                    fiber,
+                )
+            }
+            ClassMember::Getter(getter) => {
+                let fiber = fiber_body(&getter.attributes);
+                (
+                    getter.name,
+                    SignatureKind::Getter,
+                    Vec::new(),
+                    getter.body,
+                    getter.attributes,
+                    getter.is_static,
+                    getter.range,
+                    fiber,
+                )
+            }
+            ClassMember::Setter(setter) => (
+                setter.name,
+                SignatureKind::Setter,
+                vec![setter.param],
+                setter.body,
+                setter.attributes,
+                setter.is_static,
+                setter.range,
+                None,
+            ),
+            // `compile_extend` refused every other member before expansion,
+            // and expansion only ever lowers into these three.
+            _ => unreachable!("extension members are methods, getters and setters"),
+        };
+        let selector = match kind {
+            SignatureKind::Method(_) => {
+                let labels: Vec<Option<String>> = params.iter().map(|p| p.label.clone()).collect();
+                encode_selector(&name, &labels, kind)
+            }
+            _ => make_signature(&name, kind),
+        };
+        let selector_sym = self.vm.interner.intern(&selector);
+        let param_names = ClosureParameters::fixed(params.iter().map(|p| p.name.clone()).collect());
+
+        self.is_static_context = is_static;
+        let closure = match fiber {
+            Some(fiber) => self.compile_fiber_member(fiber, body, selector_sym, param_names)?,
+            None => self.compile_block(body, selector_sym, param_names, true, false, None)?,
+        };
+        tracing::debug!("[Compiler] Compiling extension member: {} (static: {})", selector, is_static);
+
+        let method_obj = self.vm.heap.alloc(Object::Method(Box::new(MethodObject::new_single(
+            selector_sym,
+            kind,
+            MethodKind::Closure(closure),
+        ))));
+        self.vm.heap.method_mut(method_obj).visibility = member_visibility(Some(&name), &attributes);
+        if !strip_metadata {
+            let contracts = self.build_contracts_metadata(&attributes)?;
+            if !contracts.is_empty() {
+                self.vm.heap.method_mut(method_obj).contracts = Some(contracts);
+            }
+        }
+        Ok((method_obj, attributes, is_static, range))
+    }
+}
@@ -0,0 +1,100 @@
+use crate::bytecode::Bytecode;
+use crate::interner::Symbol;
+use crate::value::Value;
+use phalcom_ast::ast::{ClosureParameters, Expr, MethodCallExpr, PackItem, Statement};
+use phalcom_common::range::{EmptySourceRange, SourceRange};
+
+use super::Compiler;
+use super::error::CompilerError;
+use super::state::{FiberBody, FunctionState};
+
+// ── Members whose body runs on its own fiber ───────────────────────────────
+//
+// A `@generator` or `@async` member's body does not run when the member is
+// sent. A generator member (semantics/05-generator-recorded-decisions.md)
+// answers `Generator.new(<block>)`, and the `Generator`
+// (`concurrency/fiber.ph`) runs that block on its own `Fiber` one `yield` at
+// a time as it is iterated; `yield expr` is `Fiber.yield(expr)` for effect.
+// An async member (semantics/04-async-recorded-decisions.md) answers
+// `Future.async(<block>)`, which runs the block on its own fiber once the
+// scheduler reaches it and settles the future with the block's result or
+// error; `await f` inside it parks only that fiber. A block literal handed to
+// `Generator.new`/`Future.async` directly works the same way. The compiler's
+// part is only the wrapping and the rules the fiber cannot check: `yield`
+// must sit in a block body, and a `return` in the wrapped block ends its
+// fiber — without a value, for a generator.
+
+impl<'vm> Compiler<'vm> {
+    /// Compiles a `@generator` or `@async` member's `body` into the member's
+    /// closure: a method whose receiver and `params` the wrapped block
+    /// captures, and which answers the `Generator` or `Future` over it.
+    ///
+    /// # Errors
+    ///
+    /// Propagates any error compiling `body`, including `generator.return`
+    /// for a generator `return` with a value.
+    pub(super) fn compile_fiber_member(
+        &mut self,
+        kind: FiberBody,
+        body: Vec<Statement>,
+        name_sym: Symbol,
+        params: ClosureParameters,
+    ) -> Result<crate::heap::ObjRef, CompilerError> {
+        let mut state = FunctionState::new(false, false, true, None);
+        state.fiber_body = Some(kind);
+        self.compile_function(body, name_sym, params, state)
+    }
+
+    /// Emits `Generator.new(<block>)` or `Future.async(<block>)` for a
+    /// generator or async method's `statements`, compiled as a parameterless
+    /// block named after the method.
+    pub(super) fn emit_fiber_body(&mut self, kind: FiberBody, statements: Vec<Statement>, name_sym: Symbol) -> Result<(), CompilerError> {
+        let (class, constructor) = match kind {
+            FiberBody::Generator => ("Generator", "new"),
+            FiberBody::Async => ("Future", "async"),
+        };
+        self.compile_expr(Expr::Var {
+            value: class.to_string(),
+            range: EmptySourceRange,
+        })?;
+        let mut state = FunctionState::new(false, true, true, None);
+        state.fiber_body = Some(kind);
+        let block = self.compile_function(statements, name_sym, ClosureParameters::default(), state)?;
+        let idx = self.add_constant(Value::obj(block));
+        self.emit(Bytecode::Closure(idx), EmptySourceRange);
+        self.emit_operator_send(constructor, 1, EmptySourceRange);
+        Ok(())
+    }
+
+    /// Lowers `yield expr` to `Fiber.yield(expr)` for effect: the value goes to
+    /// whoever resumed the generator's fiber, and the statement leaves nothing.
+    /// Any block may hold one, since a block can reach `Generator.new` after
+    /// it is made; one run on the root fiber raises there instead.
+    ///
+    /// # Errors
+    ///
+    /// `generator.yield` when the innermost body is a method or the module
+    /// rather than a block, or is an `@async` member's body: only a generator
+    /// block's fiber hands values to the one iterating it.
+    pub(super) fn compile_yield(&mut self, expr: Expr, range: SourceRange) -> Result<(), CompilerError> {
+        let function = self.functions.last().unwrap();
+        if !function.is_block || function.fiber_body == Some(FiberBody::Async) {
+            return Err(CompilerError::Message(
+                "generator.yield: `yield` belongs in a `@generator` member or a block run by `Generator.new`".to_string(),
+            ));
+        }
+        let call = Expr::MethodCall(Box::new(MethodCallExpr {
+            object: Expr::Var {
+                value: "Fiber".to_string(),
+                range,
+            },
+            method: "yield".to_string(),
+            method_range: None,
+            args: vec![PackItem::Positional { expr, range }],
+            range,
+        }));
+        self.compile_expr_want(call, false)?;
+        self.emit(Bytecode::Pop, range);
+        Ok(())
+    }
+}
@@ -11,6 +11,8 @@
 mod class_decl;
 mod error;
 mod expr;
+mod extend_decl;
+mod fiber_body;
 mod jumps;
 mod loops;
 mod patterns;
@@ -46,7 +48,7 @@ use crate::modules::CompileBindings;
 use crate::vm::{ClassKey, VM};
 use phalcom_ast::ast::{BindingKind, ClosureParameters, Expr, MethodCallExpr, Pattern, Program, Statement};
 use phalcom_common::range::{EmptySourceRange, SourceRange};
-use state::FunctionState;
+use state::{FiberBody, FunctionState};
 use state::LoopContext;
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -308,6 +310,22 @@ impl<'vm> Compiler<'vm> {
         is_constructor: bool,
         constructor_name: Option<String>,
     ) -> Result<ObjRef, CompilerError> {
+        let has_self = is_method || self.functions.last().is_some_and(|function| function.has_self);
+        let state = FunctionState::new(is_constructor, !is_method, has_self, constructor_name);
+        self.compile_function(statements, name_sym, params, state)
+    }
+
+    /// [`Self::compile_block`]'s body, over a caller-built [`FunctionState`]:
+    /// a method body when `state.is_block` is unset, a block body otherwise.
+    /// A `@generator`/`@async` method body (`state.fiber_body` on a method)
+    /// compiles to the object wrapping it instead ([`Self::emit_fiber_body`]).
+    ///
+    /// # Errors
+    ///
+    /// Propagates any error compiling the body's statements.
+    fn compile_function(&mut self, statements: Vec<Statement>, name_sym: Symbol, params: ClosureParameters, state: FunctionState) -> Result<ObjRef, CompilerError> {
+        let is_method = !state.is_block;
+        let fiber_body = state.fiber_body.filter(|_| is_method);
         // Intern parameter and receiver names before pushing the function state.
         let mut param_names = params.fixed.iter().map(|param| param.name.clone()).collect::<Vec<_>>();
         if let Some(rest) = &params.positional_rest {
@@ -320,9 +338,8 @@ impl<'vm> Compiler<'vm> {
         let self_sym = self.vm.interner.intern("self");
         let dummy_sym = self.vm.interner.intern("<block-receiver>");
 
-        // Push a fresh function-compilation state for this body.
-        let has_self = is_method || self.functions.last().is_some_and(|function| function.has_self);
-        self.functions.push(FunctionState::new(is_constructor, !is_method, has_self, constructor_name));
+        // Push the function-compilation state for this body.
+        self.functions.push(state);
         self.begin_scope();
 
         if is_method {
@@ -347,7 +364,6 @@ impl<'vm> Compiler<'vm> {
             self.add_local(param_sym, false)?;
         }
 
-        let len = statements.len();
         let mut last_is_return = false;
         // Track whether the last statement leaves a value on the operand stack.
         // `Expr`, `Let`, and `Return` do; an empty body or a trailing `Class`
@@ -355,6 +371,16 @@ impl<'vm> Compiler<'vm> {
         // `compile_inline_block_body` so the fast (inlined) and fallback paths
         // agree on the fall-off-end result.
         let mut leaves_value = false;
+        // A generator or async method's statements run in a block on its own
+        // fiber; the method itself only answers the `Generator`/`Future`.
+        let statements = if let Some(kind) = fiber_body {
+            self.emit_fiber_body(kind, statements, name_sym)?;
+            leaves_value = true;
+            Vec::new()
+        } else {
+            statements
+        };
+        let len = statements.len();
         for (i, statement) in statements.into_iter().enumerate() {
             let is_last = i == len - 1;
             if is_last {
@@ -590,6 +616,11 @@ impl<'vm> Compiler<'vm> {
             }
             Statement::Return(return_stmt) => {
                 let range = return_stmt.range;
+                if self.functions.last().unwrap().fiber_body == Some(FiberBody::Generator) && return_stmt.value.is_some() {
+                    return Err(CompilerError::Message(
+                        "generator.return: a generator body cannot return a value; `return` alone ends it".to_string(),
+                    ));
+                }
                 if self.functions.last().unwrap().is_constructor {
                     if return_stmt.value.is_some() {
                         return Err(CompilerError::ReturnValueFromInitializer);
@@ -607,8 +638,11 @@ impl<'vm> Compiler<'vm> {
                 // the block's own frame (blocks.md §5, ADR-0013). A method or
                 // constructor body keeps the ordinary single-frame `Return`.
                 // Constructors are never block bodies (`is_constructor` always
-                // accompanies `is_method`), so `is_block` is the sole gate.
-                if self.functions.last().unwrap().is_block {
+                // accompanies `is_method`), so `is_block` is the sole gate —
+                // except in a generator or async block, whose `return` ends
+                // the block's own fiber rather than its long-gone home method.
+                let function = self.functions.last().unwrap();
+                if function.is_block && function.fiber_body.is_none() {
                     self.emit(Bytecode::ReturnNonLocal, range);
                 } else {
                     self.emit(Bytecode::Return, range);
@@ -617,6 +651,9 @@ impl<'vm> Compiler<'vm> {
             Statement::Class(class_def) => {
                 self.compile_class(class_def)?;
             }
+            Statement::Extend(extend_def) => {
+                self.compile_extend(extend_def)?;
+            }
             Statement::For(for_stmt) => {
                 // A `for` is a statement consumed for effect (U-ITER spec
                 // §1.2): it leaves no value, so `emit_pop` is irrelevant.
@@ -658,6 +695,9 @@ impl<'vm> Compiler<'vm> {
                     self.emit(Bytecode::Pop, range);
                 }
             }
+            Statement::Yield { expr, range } => {
+                self.compile_yield(expr, range)?;
+            }
         }
         Ok(())
     }
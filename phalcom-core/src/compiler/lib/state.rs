@@ -21,6 +21,18 @@ pub(super) struct Local {
     pub(super) is_mutable: bool,
 }
 
+/// A member body that runs on a fiber of its own instead of in the member's
+/// activation (`compiler/lib/fiber_body.rs`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FiberBody {
+    /// `@generator`: the member answers `Generator.new(<block>)`, and the
+    /// block hands values out with `yield`.
+    Generator,
+    /// `@async`: the member answers `Future.async(<block>)`, settled with the
+    /// block's result.
+    Async,
+}
+
 /// The mutable per-function compilation state for one closure body.
 ///
 /// Every method, getter, setter, block literal and the top-level module gets
@@ -59,6 +71,11 @@ pub(crate) struct FunctionState {
     /// `return` in a method body keeps [`crate::bytecode::Bytecode::Return`] (blocks.md §5,
     /// [ADR-0013](../../../docs/adr/accepted/0013-block-closure-upvalues.md)).
     pub(super) is_block: bool,
+    /// Set when this body belongs to a `@generator` or `@async` member: on the
+    /// method it makes the body compile to the block its [`FiberBody`] wraps,
+    /// and on that block it makes `return` end the block's own fiber (a plain
+    /// [`crate::bytecode::Bytecode::Return`]). Set by `compiler/lib/fiber_body.rs`.
+    pub(super) fiber_body: Option<FiberBody>,
     /// Whether this lexical body can reach a member receiver. Blocks inherit
     /// this from their enclosing body and access it through the usual upvalue.
     pub(super) has_self: bool,
@@ -114,6 +131,7 @@ impl FunctionState {
             is_constructor,
             constructor_name,
             is_block,
+            fiber_body: None,
             has_self,
             local_names: Vec::new(),
         }
@@ -387,10 +387,29 @@ fn emit_source_echo(out: &mut String, view: &FrameView) {
 
 /// Builds the caret block for the innermost live frame (IS §5.1).
 ///
+/// An error that crossed a fiber boundary was raised on frames that are gone;
+/// its caret marks the innermost *user* frame instead, the send that resumed or
+/// awaited the failed fiber, rather than core's machinery (`Future#await`)
+/// between that send and the raise.
+///
 /// Returns `None` when the innermost frame has no source text or there are no
 /// frames at all.
 fn innermost_caret_block(vm: &VM, err: &PhError, config: &RenderConfig) -> Option<String> {
-    let frame = vm.frames.last()?;
+    let crossed_fiber = matches!(
+        err,
+        PhError::Runtime(RuntimeError::Raise { traceback: Some(tb), .. })
+            if tb.iter().any(|r| matches!(r, FrameRecord::FiberBoundary { .. }))
+    );
+    let frame = if crossed_fiber {
+        let core = vm.core_module();
+        vm.frames
+            .iter()
+            .rev()
+            .find(|f| Some(vm.heap.closure(f.closure).module) != core)
+            .or(vm.frames.last())?
+    } else {
+        vm.frames.last()?
+    };
     let closure = vm.heap.closure(frame.closure);
     let module = vm.heap.module(closure.module);
     let source_id = closure.callable.chunk.source_id;
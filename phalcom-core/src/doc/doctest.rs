@@ -0,0 +1,107 @@
+//! Runs `@example` blocks as doctests (doc-comments-phaldoc.md §3, §8.6).
+//!
+//! Each example is analyzed as a synthetic module at the project's root —
+//! served from memory through an [`OverlaySourceProvider`], never written to
+//! disk — so it imports project modules exactly as a sibling file would
+//! (`from .geometry import Circle`). It is then compiled and run on a fresh
+//! VM under [`VmLimits::sandbox`]; an example passes when it analyzes,
+//! compiles, and runs without error, so one that spins or allocates past the
+//! sandbox limits fails instead of hanging the run.
+
+use super::model::ProjectDocs;
+use super::phaldoc::DocComment;
+use crate::modules::{AnalyzedProject, ProgramAnalyzer, ProgramCompileError, ProgramCompiler};
+use crate::vm::{VM, VmLimits};
+use phalcom_modules::{FilesystemSourceProvider, ModuleComponent, ModuleId, ModulePath, OverlaySourceProvider};
+use serde::Serialize;
+
+/// Logical module path examples are analyzed under. The leading underscores
+/// keep it from colliding with any physical (kebab-case) module name.
+const DOCTEST_MODULE: &str = "__doctest";
+
+/// One runnable example and where it was written.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Doctest {
+    /// What the example documents: `module`, `module::Class`, or
+    /// `module::Class#selector`.
+    pub target: String,
+    /// Source path relative to the project root.
+    pub path: String,
+    /// 1-based source line the example's code starts on.
+    pub line: usize,
+    pub code: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct DoctestOutcome {
+    pub doctest: Doctest,
+    /// `None` when the example ran cleanly.
+    pub failure: Option<String>,
+}
+
+impl DoctestOutcome {
+    pub fn passed(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// Collects every runnable example in the model, in page order.
+pub fn collect_doctests(docs: &ProjectDocs) -> Vec<Doctest> {
+    let mut doctests = Vec::new();
+    let mut push = |target: String, path: &str, doc: &Option<DocComment>| {
+        for example in doc.iter().flat_map(|doc| doc.examples.iter()).filter(|example| example.doctest) {
+            doctests.push(Doctest {
+                target: target.clone(),
+                path: path.to_string(),
+                line: example.line,
+                code: example.code.clone(),
+            });
+        }
+    };
+    for module in &docs.modules {
+        push(module.name.clone(), &module.path, &module.doc);
+        for class in &module.classes {
+            push(format!("{}::{}", module.name, class.name), &module.path, &class.doc);
+            for member in &class.members {
+                push(format!("{}::{}#{}", module.name, class.name, member.selector), &module.path, &member.doc);
+            }
+        }
+    }
+    doctests
+}
+
+/// Runs every doctest against the analyzed project.
+pub fn run_doctests(analysis: &AnalyzedProject, doctests: &[Doctest]) -> Vec<DoctestOutcome> {
+    doctests
+        .iter()
+        .map(|doctest| DoctestOutcome {
+            doctest: doctest.clone(),
+            failure: run_one(analysis, doctest).err(),
+        })
+        .collect()
+}
+
+fn run_one(analysis: &AnalyzedProject, doctest: &Doctest) -> Result<(), String> {
+    let component = ModuleComponent::from_identifier(DOCTEST_MODULE).expect("doctest module name is a valid identifier");
+    let entry = ModuleId::resolved(analysis.project, ModulePath::from_components(vec![component]));
+    let mut provider = OverlaySourceProvider::new(FilesystemSourceProvider::new());
+    provider.insert(entry.clone(), format!("{}:{}", doctest.path, doctest.line), doctest.code.as_str());
+
+    let analyzed = ProgramAnalyzer::analyze_in_universe(analysis.project_universe.clone(), provider, entry).map_err(describe)?;
+    let program = ProgramCompiler::compile_analyzed(&analyzed).map_err(describe)?;
+    let mut vm = VM::new();
+    vm.set_limits(VmLimits::sandbox());
+    vm.run_compiled(&program).map_err(|error| error.to_string())
+}
+
+fn describe(error: ProgramCompileError) -> String {
+    match error {
+        ProgramCompileError::Semantic(diagnostics) => diagnostics
+            .iter()
+            .flat_map(|(_, diagnostics)| diagnostics.iter())
+            .map(|diagnostic| format!("[{}] {}", diagnostic.code, diagnostic.message))
+            .collect::<Vec<_>>()
+            .join("; "),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,504 @@
+//! Static HTML rendering of a [`ProjectDocs`] model.
+//!
+//! One page per module plus an index, all sharing `style.css`. Classes are
+//! anchored by name and members by `Class.selector` (class-side members by
+//! `Class.class.selector`), so a selector symbol from `@see #sel` or a
+//! `#sel` prose reference lands on the member it names (§4). Class names in
+//! signatures, superclasses, and `@see` payloads link to their page whenever
+//! they resolve to exactly one documented class.
+
+use super::model::{ClassDocs, MemberDocs, MemberKind, ModuleDocs, ProjectDocs, TypeRef};
+use super::phaldoc::DocComment;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+const STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 0; display: flex; color: #1d1f21; }
+nav { width: 16rem; padding: 1rem; background: #f4f4f5; min-height: 100vh; box-sizing: border-box; }
+nav ul { list-style: none; padding-left: 0.5rem; }
+main { flex: 1; padding: 1rem 2rem; max-width: 60rem; }
+code, pre { font-family: ui-monospace, monospace; }
+pre { background: #f4f4f5; padding: 0.75rem; overflow-x: auto; }
+.signature { font-weight: 600; }
+.member { border-top: 1px solid #e4e4e7; padding: 0.5rem 0; }
+.kind { color: #71717a; font-size: 0.85em; margin-right: 0.5em; }
+.attribute { color: #7c3aed; }
+.deprecated { background: #fef3c7; padding: 0.25rem 0.5rem; }
+.summary { color: #3f3f46; }
+";
+
+/// Renders every page of the site as `(file name, contents)` pairs.
+pub fn render_site(docs: &ProjectDocs) -> Vec<(String, String)> {
+    let links = Links::new(docs);
+    let mut pages = vec![("style.css".to_string(), STYLE.to_string()), ("index.html".to_string(), render_index(docs))];
+    for module in &docs.modules {
+        pages.push((module_file(&module.name), render_module(docs, module, &links)));
+    }
+    pages
+}
+
+/// The page a module renders to.
+pub fn module_file(module: &str) -> String {
+    format!("{module}.html")
+}
+
+/// The anchor a member renders under.
+pub fn member_anchor(class: &str, member: &MemberDocs) -> String {
+    let side = if member.class_side { "class." } else { "" };
+    format!("{class}.{side}{}", slug(&member.selector))
+}
+
+/// Keeps anchor-safe characters and hex-escapes the rest, so operator and
+/// subscript selectors (`+(_)`, `[_]=(put)`) still get stable ids.
+fn slug(selector: &str) -> String {
+    let mut out = String::with_capacity(selector.len());
+    for ch in selector.chars() {
+        if ch.is_ascii_alphanumeric() || matches!(ch, '_' | '(' | ')' | ',' | ':' | '.') {
+            out.push(ch);
+        } else {
+            let _ = write!(out, "-{:x}", ch as u32);
+        }
+    }
+    out
+}
+
+/// Class-name resolution shared by every page.
+struct Links<'a> {
+    by_name: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl<'a> Links<'a> {
+    fn new(docs: &'a ProjectDocs) -> Self {
+        let mut by_name: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (module, class) in docs.classes() {
+            by_name.entry(class.name.as_str()).or_default().push(module.name.as_str());
+        }
+        Self { by_name }
+    }
+
+    /// The page-relative URL of class `name`, preferring `module` when it is
+    /// given and otherwise requiring the name to be unique in the project.
+    fn class_url(&self, name: &str, module: Option<&str>) -> Option<String> {
+        let modules = self.by_name.get(name)?;
+        let module = match module {
+            Some(module) => modules.iter().find(|candidate| **candidate == module)?,
+            None if modules.len() == 1 => &modules[0],
+            None => return None,
+        };
+        Some(format!("{}#{name}", module_file(module)))
+    }
+
+    fn type_ref(&self, ty: &TypeRef) -> String {
+        match self.class_url(&ty.name, ty.module.as_deref()) {
+            Some(url) => format!("<a href=\"{}\">{}</a>", escape(&url), escape(&ty.name)),
+            None => escape(&ty.name),
+        }
+    }
+
+    /// Escapes `text` and links every capitalized identifier that names a
+    /// documented class.
+    fn linkify_types(&self, text: &str) -> String {
+        let mut out = String::new();
+        let mut rest = text;
+        while let Some(start) = rest.find(|ch: char| ch.is_ascii_alphabetic() || ch == '_') {
+            out.push_str(&escape(&rest[..start]));
+            let tail = &rest[start..];
+            let end = tail.find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_')).unwrap_or(tail.len());
+            let word = &tail[..end];
+            match word.starts_with(char::is_uppercase).then(|| self.class_url(word, None)).flatten() {
+                Some(url) => {
+                    let _ = write!(out, "<a href=\"{}\">{}</a>", escape(&url), escape(word));
+                }
+                None => out.push_str(&escape(word)),
+            }
+            rest = &tail[end..];
+        }
+        out.push_str(&escape(rest));
+        out
+    }
+}
+
+fn page(docs: &ProjectDocs, title: &str, current: Option<&ModuleDocs>, body: &str) -> String {
+    let mut nav = format!("<h2><a href=\"index.html\">{}</a></h2>\n<ul>\n", escape(&docs.name));
+    for module in &docs.modules {
+        let _ = writeln!(nav, "<li><a href=\"{}\">{}</a></li>", escape(&module_file(&module.name)), escape(&module.name));
+        if current.is_some_and(|current| current.name == module.name) && !module.classes.is_empty() {
+            nav.push_str("<ul>\n");
+            for class in &module.classes {
+                let _ = writeln!(nav, "<li><a href=\"#{0}\">{0}</a></li>", escape(&class.name));
+            }
+            nav.push_str("</ul>\n");
+        }
+    }
+    nav.push_str("</ul>\n");
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<link rel=\"stylesheet\" href=\"style.css\">\n</head>\n<body>\n<nav>\n{nav}</nav>\n<main>\n{body}</main>\n</body>\n</html>\n",
+        escape(title)
+    )
+}
+
+fn render_index(docs: &ProjectDocs) -> String {
+    let mut body = format!("<h1>{}", escape(&docs.name));
+    if let Some(version) = &docs.version {
+        let _ = write!(body, " <small>{}</small>", escape(version));
+    }
+    body.push_str("</h1>\n");
+    if let Some(description) = &docs.description {
+        let _ = writeln!(body, "<p>{}</p>", escape(description));
+    }
+    body.push_str("<h2>Modules</h2>\n<table>\n");
+    for module in &docs.modules {
+        let summary = module.doc.as_ref().map(|doc| doc.summary.as_str()).unwrap_or_default();
+        let _ = writeln!(
+            body,
+            "<tr><td><a href=\"{}\">{}</a></td><td class=\"summary\">{}</td></tr>",
+            escape(&module_file(&module.name)),
+            escape(&module.name),
+            inline(summary, None)
+        );
+    }
+    body.push_str("</table>\n");
+    let mut classes: Vec<_> = docs.classes().collect();
+    if !classes.is_empty() {
+        classes.sort_by(|(_, a), (_, b)| a.name.cmp(&b.name));
+        body.push_str("<h2>Classes</h2>\n<table>\n");
+        for (module, class) in classes {
+            let summary = class.doc.as_ref().map(|doc| doc.summary.as_str()).unwrap_or_default();
+            let _ = writeln!(
+                body,
+                "<tr><td><a href=\"{}#{}\">{}</a></td><td>{}</td><td class=\"summary\">{}</td></tr>",
+                escape(&module_file(&module.name)),
+                escape(&class.name),
+                escape(&class.name),
+                escape(&module.name),
+                inline(summary, Some(class))
+            );
+        }
+        body.push_str("</table>\n");
+    }
+    page(docs, &docs.name, None, &body)
+}
+
+fn render_module(docs: &ProjectDocs, module: &ModuleDocs, links: &Links<'_>) -> String {
+    let kind = if module.package { "Package" } else { "Module" };
+    let mut body = format!(
+        "<h1><span class=\"kind\">{kind}</span>{}</h1>\n<p class=\"kind\">{}</p>\n",
+        escape(&module.name),
+        escape(&module.path)
+    );
+    if let Some(doc) = &module.doc {
+        body.push_str(&render_doc(doc, None, links));
+    }
+    if !module.classes.is_empty() {
+        body.push_str("<h2>Classes</h2>\n<table>\n");
+        for class in &module.classes {
+            let summary = class.doc.as_ref().map(|doc| doc.summary.as_str()).unwrap_or_default();
+            let _ = writeln!(
+                body,
+                "<tr><td><a href=\"#{0}\">{0}</a></td><td class=\"summary\">{1}</td></tr>",
+                escape(&class.name),
+                inline(summary, Some(class))
+            );
+        }
+        body.push_str("</table>\n");
+    }
+    for class in &module.classes {
+        body.push_str(&render_class(class, links));
+    }
+    page(docs, &module.name, Some(module), &body)
+}
+
+fn render_class(class: &ClassDocs, links: &Links<'_>) -> String {
+    let kind = match class.kind {
+        super::model::ClassKind::Class => "class",
+        super::model::ClassKind::Protocol => "protocol",
+        super::model::ClassKind::Mixin => "mixin",
+    };
+    let mut out = format!("<section id=\"{0}\">\n<h2><span class=\"kind\">{kind}</span>{0}", escape(&class.name));
+    if let Some(superclass) = &class.superclass {
+        let _ = write!(out, " <small>is {}</small>", links.type_ref(superclass));
+    }
+    out.push_str("</h2>\n");
+    for attribute in &class.attributes {
+        let _ = writeln!(out, "<code class=\"attribute\">{}</code>", escape(attribute));
+    }
+    if let Some(doc) = &class.doc {
+        out.push_str(&render_doc(doc, Some(class), links));
+    }
+    for member in &class.members {
+        out.push_str(&render_member(class, member, links));
+    }
+    out.push_str("</section>\n");
+    out
+}
+
+fn render_member(class: &ClassDocs, member: &MemberDocs, links: &Links<'_>) -> String {
+    let kind = match member.kind {
+        MemberKind::Constructor => "constructor",
+        MemberKind::Method => "method",
+        MemberKind::Getter => "getter",
+        MemberKind::Setter => "setter",
+        MemberKind::Subscript => "subscript",
+        MemberKind::Field => "field",
+        MemberKind::Variant => "variant",
+    };
+    let side = if member.class_side && member.kind != MemberKind::Constructor {
+        "static "
+    } else {
+        ""
+    };
+    let anchor = member_anchor(&class.name, member);
+    let mut out = format!(
+        "<div class=\"member\" id=\"{0}\">\n<p><span class=\"kind\">{side}{kind}</span><a href=\"#{0}\"><code class=\"signature\">{1}</code></a> <code class=\"kind\">{2}</code></p>\n",
+        escape(&anchor),
+        links.linkify_types(&member.signature),
+        escape(&member.selector)
+    );
+    for attribute in &member.attributes {
+        let _ = writeln!(out, "<p><code class=\"attribute\">{}</code></p>", escape(attribute));
+    }
+    if let Some(doc) = &member.doc {
+        out.push_str(&render_doc(doc, Some(class), links));
+    }
+    out.push_str("</div>\n");
+    out
+}
+
+fn render_doc(doc: &DocComment, class: Option<&ClassDocs>, links: &Links<'_>) -> String {
+    let mut out = String::new();
+    for reason in doc.tag_payloads("deprecated") {
+        let _ = writeln!(out, "<p class=\"deprecated\"><strong>Deprecated.</strong> {}</p>", inline(reason, class));
+    }
+    if !doc.summary.is_empty() {
+        let _ = writeln!(out, "<p class=\"summary\">{}</p>", inline(&doc.summary, class));
+    }
+    out.push_str(&markdown(&doc.details, class));
+
+    let params: Vec<&str> = doc.tag_payloads("param").collect();
+    if !params.is_empty() {
+        out.push_str("<h4>Parameters</h4>\n<dl>\n");
+        for param in params {
+            let (name, description) = split_tag_subject(param);
+            let _ = writeln!(out, "<dt><code>{}</code></dt><dd>{}</dd>", escape(name), inline(description, class));
+        }
+        out.push_str("</dl>\n");
+    }
+    for returns in doc.tag_payloads("returns") {
+        let _ = writeln!(out, "<h4>Returns</h4>\n<p>{}</p>", inline(returns, class));
+    }
+    let throws: Vec<&str> = doc.tag_payloads("throws").collect();
+    if !throws.is_empty() {
+        out.push_str("<h4>Throws</h4>\n<dl>\n");
+        for payload in throws {
+            let (error, when) = split_tag_subject(payload);
+            let _ = writeln!(out, "<dt>{}</dt><dd>{}</dd>", links.linkify_types(error), inline(when, class));
+        }
+        out.push_str("</dl>\n");
+    }
+    if !doc.examples.is_empty() {
+        out.push_str("<h4>Examples</h4>\n");
+        for example in &doc.examples {
+            let _ = writeln!(out, "<pre class=\"example\"><code>{}</code></pre>", escape(&example.code));
+        }
+    }
+    let see: Vec<&str> = doc.tag_payloads("see").collect();
+    if !see.is_empty() {
+        out.push_str("<h4>See also</h4>\n<ul>\n");
+        for target in see {
+            let _ = writeln!(out, "<li>{}</li>", see_link(target, class, links));
+        }
+        out.push_str("</ul>\n");
+    }
+    for since in doc.tag_payloads("since") {
+        let _ = writeln!(out, "<p class=\"kind\">Since {}</p>", escape(since));
+    }
+    for author in doc.tag_payloads("author") {
+        let _ = writeln!(out, "<p class=\"kind\">Author: {}</p>", escape(author));
+    }
+    out
+}
+
+/// Splits `name — description` (or `name - description`, or `name desc`).
+fn split_tag_subject(payload: &str) -> (&str, &str) {
+    let payload = payload.trim();
+    let (subject, rest) = payload.split_once(char::is_whitespace).unwrap_or((payload, ""));
+    let rest = rest.trim_start();
+    let rest = rest.strip_prefix('—').or_else(|| rest.strip_prefix("- ")).unwrap_or(rest);
+    (subject, rest.trim())
+}
+
+/// Links an `@see` payload: `#sel` in the current class, `Class#sel`,
+/// `Class`, or markdown prose.
+fn see_link(target: &str, class: Option<&ClassDocs>, links: &Links<'_>) -> String {
+    let target = target.trim();
+    if target.starts_with('#') {
+        return inline(target, class);
+    }
+    if let Some((class_name, selector)) = target.split_once('#') {
+        if let Some(url) = links.class_url(class_name, None) {
+            let page = url.split_once('#').map_or(url.as_str(), |(page, _)| page);
+            return format!(
+                "<a href=\"{}#{}.{}\"><code>{}</code></a>",
+                escape(page),
+                escape(class_name),
+                escape(&slug(selector)),
+                escape(target)
+            );
+        }
+    }
+    match links.class_url(target, None) {
+        Some(url) => format!("<a href=\"{}\"><code>{}</code></a>", escape(&url), escape(target)),
+        None => inline(target, class),
+    }
+}
+
+/// Block-level markdown: paragraphs, `-` lists, and fenced code.
+fn markdown(text: &str, class: Option<&ClassDocs>) -> String {
+    let mut out = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut list = false;
+    let mut fence: Option<Vec<&str>> = None;
+    let flush = |out: &mut String, paragraph: &mut Vec<&str>| {
+        if !paragraph.is_empty() {
+            let _ = writeln!(out, "<p>{}</p>", inline(&paragraph.join(" "), class));
+            paragraph.clear();
+        }
+    };
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if let Some(code) = fence.as_mut() {
+            if trimmed.starts_with("```") {
+                let _ = writeln!(out, "<pre><code>{}</code></pre>", escape(&code.join("\n")));
+                fence = None;
+            } else {
+                code.push(line);
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") {
+            flush(&mut out, &mut paragraph);
+            fence = Some(Vec::new());
+        } else if let Some(item) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")) {
+            flush(&mut out, &mut paragraph);
+            if !list {
+                out.push_str("<ul>\n");
+                list = true;
+            }
+            let _ = writeln!(out, "<li>{}</li>", inline(item, class));
+            continue;
+        } else if trimmed.is_empty() {
+            flush(&mut out, &mut paragraph);
+        } else {
+            paragraph.push(trimmed);
+        }
+        if list {
+            out.push_str("</ul>\n");
+            list = false;
+        }
+    }
+    if let Some(code) = fence {
+        let _ = writeln!(out, "<pre><code>{}</code></pre>", escape(&code.join("\n")));
+    }
+    flush(&mut out, &mut paragraph);
+    if list {
+        out.push_str("</ul>\n");
+    }
+    out
+}
+
+/// Inline markdown: `code`, `[text](url)`, and `#selector` references to
+/// members of the enclosing class.
+fn inline(text: &str, class: Option<&ClassDocs>) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(['`', '[', '#']) {
+        out.push_str(&escape(&rest[..start]));
+        let tail = &rest[start..];
+        let consumed = match tail.as_bytes()[0] {
+            b'`' => tail[1..].find('`').map(|end| {
+                let _ = write!(out, "<code>{}</code>", escape(&tail[1..=end]));
+                end + 2
+            }),
+            b'[' => markdown_link(tail).map(|(label, url, len)| {
+                let _ = write!(out, "<a href=\"{}\">{}</a>", escape(url), escape(label));
+                len
+            }),
+            _ => selector_ref(tail, class).map(|(selector, anchor)| {
+                let _ = write!(out, "<a href=\"#{}\"><code>{}</code></a>", escape(&anchor), escape(selector));
+                selector.len() + 1
+            }),
+        };
+        match consumed {
+            Some(len) => rest = &tail[len..],
+            None => {
+                out.push_str(&escape(&tail[..1]));
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(&escape(rest));
+    out
+}
+
+fn markdown_link(tail: &str) -> Option<(&str, &str, usize)> {
+    let label_end = tail.find("](")?;
+    let url_end = label_end + 2 + tail[label_end + 2..].find(')')?;
+    Some((&tail[1..label_end], &tail[label_end + 2..url_end], url_end + 1))
+}
+
+/// Resolves `#selector` against the enclosing class's members.
+fn selector_ref<'t>(tail: &'t str, class: Option<&ClassDocs>) -> Option<(&'t str, String)> {
+    let class = class?;
+    let body = &tail[1..];
+    let end = match body.find('(') {
+        Some(open) if body[..open].chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '_') => open + body[open..].find(')')? + 1,
+        _ => body.find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_')).unwrap_or(body.len()),
+    };
+    let selector = &body[..end];
+    let member = class.members.iter().find(|member| member.selector == selector)?;
+    Some((selector, member_anchor(&class.name, member)))
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inline_markdown_renders_code_links_and_escapes() {
+        assert_eq!(
+            inline("use `a < b` and [docs](x.html) & more", None),
+            "use <code>a &lt; b</code> and <a href=\"x.html\">docs</a> &amp; more"
+        );
+        assert_eq!(inline("unclosed `tick", None), "unclosed `tick");
+    }
+
+    #[test]
+    fn anchors_escape_operator_selectors() {
+        assert_eq!(slug("move(_,to)"), "move(_,to)");
+        assert_eq!(slug("+(_)"), "-2b(_)");
+        assert_eq!(slug("[_]=(put)"), "-5b_-5d-3d(put)");
+    }
+
+    #[test]
+    fn markdown_blocks() {
+        let html = markdown("First\nline.\n\n- one\n- two\n\n```\nx < 1\n```", None);
+        assert_eq!(
+            html,
+            "<p>First line.</p>\n<ul>\n<li>one</li>\n<li>two</li>\n</ul>\n<pre><code>x &lt; 1</code></pre>\n"
+        );
+    }
+}
@@ -0,0 +1,41 @@
+//! `phalcom doc`: Phaldoc extraction and rendering for a whole project.
+//!
+//! The pipeline is analysis → model → output. [`ProgramAnalyzer::analyze_project_modules`]
+//! resolves and analyzes every module under the project's source root;
+//! [`document_project`] walks each module's classes and members, keying them
+//! by selector and attaching `phalcom-semantic` signatures and the harvested
+//! `///`/`//!` docs; [`write_site`] renders the model as a static HTML site
+//! next to its JSON form. [`doctest`] optionally runs the `@example` blocks.
+//!
+//! See `docs/spec/design/experimental/doc-comments-phaldoc.md` for the
+//! comment convention itself.
+//!
+//! [`ProgramAnalyzer::analyze_project_modules`]: crate::modules::ProgramAnalyzer::analyze_project_modules
+
+pub mod doctest;
+pub mod html;
+pub mod model;
+pub mod phaldoc;
+
+pub use doctest::{Doctest, DoctestOutcome, collect_doctests, run_doctests};
+pub use model::{ClassDocs, ClassKind, MemberDocs, MemberKind, ModuleDocs, ModuleWarning, ParameterDocs, ProjectDocs, TypeRef, document_project};
+pub use phaldoc::{DocComment, DocExample, DocTag, DocWarning};
+
+use std::path::Path;
+
+/// File name of the JSON model within the output directory.
+pub const MODEL_FILE: &str = "phaldoc.json";
+
+/// Serializes the model as pretty-printed JSON.
+pub fn to_json(docs: &ProjectDocs) -> String {
+    serde_json::to_string_pretty(docs).expect("doc model serializes")
+}
+
+/// Writes the HTML site and the JSON model into `out_dir`, creating it.
+pub fn write_site(docs: &ProjectDocs, out_dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(out_dir)?;
+    for (file, contents) in html::render_site(docs) {
+        std::fs::write(out_dir.join(file), contents)?;
+    }
+    std::fs::write(out_dir.join(MODEL_FILE), to_json(docs))
+}
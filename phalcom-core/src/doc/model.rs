@@ -0,0 +1,502 @@
+//! The documentation model: one project's modules, classes, and members with
+//! their selectors, `phalcom-semantic` signatures, and harvested Phaldoc.
+//!
+//! This is what `phalcom doc` serializes as JSON and what the HTML renderer
+//! walks; it carries no reference back into the analysis.
+
+use super::phaldoc::{DocComment, DocWarning, SourceDocs};
+use crate::modules::AnalyzedProject;
+use phalcom_ast::ast::{ClassDef, ClassMember, IndexAccessor, ParameterDef, Statement};
+use phalcom_common::selector::{Selector, SelectorSlot};
+use phalcom_modules::{DeclarationId, ModuleId, ResolvedProject};
+use phalcom_semantic::surface::MemberSurface;
+use phalcom_semantic::{CallableSignature, SemanticSnapshot};
+use serde::Serialize;
+
+/// Version of the JSON layout below; bumped on incompatible changes.
+pub const DOC_MODEL_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ProjectDocs {
+    pub format_version: u32,
+    pub name: String,
+    pub namespace: String,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub modules: Vec<ModuleDocs>,
+    pub warnings: Vec<ModuleWarning>,
+}
+
+impl ProjectDocs {
+    pub fn module(&self, name: &str) -> Option<&ModuleDocs> {
+        self.modules.iter().find(|module| module.name == name)
+    }
+
+    /// Every `(module, class)` pair, in module then declaration order.
+    pub fn classes(&self) -> impl Iterator<Item = (&ModuleDocs, &ClassDocs)> {
+        self.modules.iter().flat_map(|module| module.classes.iter().map(move |class| (module, class)))
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ModuleDocs {
+    /// Dotted logical name, rooted at the project namespace (`shapes.geometry`).
+    pub name: String,
+    /// Source path relative to the project root.
+    pub path: String,
+    pub package: bool,
+    pub doc: Option<DocComment>,
+    pub classes: Vec<ClassDocs>,
+}
+
+impl ModuleDocs {
+    pub fn class(&self, name: &str) -> Option<&ClassDocs> {
+        self.classes.iter().find(|class| class.name == name)
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ClassDocs {
+    pub name: String,
+    pub kind: ClassKind,
+    /// Whether the module exports the class.
+    pub exported: bool,
+    pub superclass: Option<TypeRef>,
+    /// Source text of each attribute on the class header (`@data`, `@sealed`).
+    pub attributes: Vec<String>,
+    pub doc: Option<DocComment>,
+    pub members: Vec<MemberDocs>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClassKind {
+    Class,
+    Protocol,
+    Mixin,
+}
+
+/// A class named from a page: its module is `None` outside this project.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct TypeRef {
+    pub name: String,
+    pub module: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MemberDocs {
+    /// Canonical comma-form selector (ADR-0012) — the doc key (§4). Fields
+    /// use their declared name.
+    pub selector: String,
+    pub kind: MemberKind,
+    /// `true` for class-side members (`static`, `@class`).
+    pub class_side: bool,
+    /// The declaration head as a reader would write it, with
+    /// `phalcom-semantic`'s resolved types: `move(_ x: Int, to) -> Point`.
+    pub signature: String,
+    pub parameters: Vec<ParameterDocs>,
+    /// Resolved return (or field) type; `None` when unannotated.
+    pub returns: Option<String>,
+    /// Source text of each attached attribute (`@requires(x > 0)`).
+    pub attributes: Vec<String>,
+    pub doc: Option<DocComment>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberKind {
+    Constructor,
+    Method,
+    Getter,
+    Setter,
+    Subscript,
+    Field,
+    Variant,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ParameterDocs {
+    /// Keyword label, or `None` for a positional slot.
+    pub label: Option<String>,
+    pub name: String,
+    pub ty: Option<String>,
+    pub rest: bool,
+}
+
+/// A harvesting warning, located by module.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ModuleWarning {
+    pub module: String,
+    pub path: String,
+    #[serde(flatten)]
+    pub warning: DocWarning,
+}
+
+/// Builds the documentation model for every module of an analyzed project.
+pub fn document_project(analysis: &AnalyzedProject) -> ProjectDocs {
+    let project = analysis
+        .project_universe
+        .get_project(analysis.project)
+        .expect("analyzed project is in its universe");
+    let manifest = project.manifest.as_ref();
+    let mut docs = ProjectDocs {
+        format_version: DOC_MODEL_VERSION,
+        name: project.name.clone(),
+        namespace: project.namespace.to_string(),
+        version: manifest.and_then(|manifest| manifest.version.clone()),
+        description: manifest.and_then(|manifest| manifest.description.clone()),
+        modules: Vec::new(),
+        warnings: Vec::new(),
+    };
+    for module in &analysis.modules {
+        let Some(program) = analysis.program_for(module) else {
+            continue;
+        };
+        let unit = &program.sources[module];
+        let path = unit
+            .source
+            .as_ref()
+            .map(|source| {
+                let path = source.display_path.strip_prefix(&project.root_dir).unwrap_or(&source.display_path);
+                path.display().to_string()
+            })
+            .unwrap_or_default();
+        let name = module_name(project, module);
+        let mut warnings = Vec::new();
+        let builder = ModuleBuilder {
+            project,
+            module,
+            snapshot: &program.semantic,
+            source: SourceDocs::new(&unit.text),
+            exports: program.linked.modules.get(module).map(|linked| &linked.interface.exports),
+        };
+        let module_docs = builder.build(name.clone(), path.clone(), unit.kind.is_package(), &unit.program.statements, &mut warnings);
+        docs.modules.push(module_docs);
+        docs.warnings.extend(warnings.into_iter().map(|warning| ModuleWarning {
+            module: name.clone(),
+            path: path.clone(),
+            warning,
+        }));
+    }
+    docs.modules.sort_by(|a, b| a.name.cmp(&b.name));
+    docs.warnings.sort_by(|a, b| (&a.module, a.warning.line).cmp(&(&b.module, b.warning.line)));
+    docs
+}
+
+/// Dotted logical name of `module` within `project` (the root package is
+/// the bare namespace).
+fn module_name(project: &ResolvedProject, module: &ModuleId) -> String {
+    std::iter::once(project.namespace.as_str())
+        .chain(module.path.components().iter().map(|component| component.as_str()))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+struct ModuleBuilder<'a> {
+    project: &'a ResolvedProject,
+    module: &'a ModuleId,
+    snapshot: &'a SemanticSnapshot,
+    source: SourceDocs<'a>,
+    exports: Option<&'a std::collections::BTreeMap<Box<str>, phalcom_modules::LinkedExport>>,
+}
+
+impl ModuleBuilder<'_> {
+    fn build(&self, name: String, path: String, package: bool, statements: &[Statement], warnings: &mut Vec<DocWarning>) -> ModuleDocs {
+        let mut pinned = self.source.pinned_docs(warnings);
+        let classes = statements
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::Class(class_def) => Some(self.class(class_def, &mut pinned, warnings)),
+                _ => None,
+            })
+            .collect();
+        ModuleDocs {
+            name,
+            path,
+            package,
+            doc: self.source.module_doc(warnings),
+            classes,
+        }
+    }
+
+    fn class(&self, class_def: &ClassDef, pinned: &mut Vec<(String, DocComment)>, warnings: &mut Vec<DocWarning>) -> ClassDocs {
+        let declaration = DeclarationId::new(self.module.clone(), class_def.name.clone().into());
+        let start = class_def
+            .attributes
+            .iter()
+            .map(|attribute| attribute.range.start)
+            .chain([class_def.range.start])
+            .min()
+            .unwrap_or(class_def.range.start);
+        let outer = self.source.outer_doc(start, warnings);
+        let inner = self.source.inner_doc_after(class_def.name_range.end, warnings);
+        let kind = if class_def.attributes.iter().any(|attribute| attribute.name == "protocol") {
+            ClassKind::Protocol
+        } else if class_def.attributes.iter().any(|attribute| attribute.name == "mixin") {
+            ClassKind::Mixin
+        } else {
+            ClassKind::Class
+        };
+        let superclass = class_def
+            .superclass
+            .as_ref()
+            .map(|written| match self.snapshot.hierarchy().superclasses.get(&declaration) {
+                Some(resolved) => self.type_ref(resolved),
+                None => TypeRef {
+                    name: written.leaf_name().to_string(),
+                    module: None,
+                },
+            });
+        let surface = self.snapshot.surfaces().get(&declaration);
+        let members = class_def
+            .members
+            .iter()
+            .map(|member| self.member(member, surface.map(|s| (&s.instance, &s.class)), pinned, warnings))
+            .collect();
+        ClassDocs {
+            name: class_def.name.clone(),
+            kind,
+            exported: self.exports.is_some_and(|exports| exports.contains_key(class_def.name.as_str())),
+            superclass,
+            attributes: class_def
+                .attributes
+                .iter()
+                .filter_map(|a| self.source.slice(a.range.start, a.range.end))
+                .map(str::to_string)
+                .collect(),
+            doc: merge_docs(outer, inner),
+            members,
+        }
+    }
+
+    fn type_ref(&self, declaration: &DeclarationId) -> TypeRef {
+        let in_project = declaration.module.project.as_resolved() == Some(self.project.id);
+        TypeRef {
+            name: declaration.name.to_string(),
+            module: in_project.then(|| module_name(self.project, &declaration.module)),
+        }
+    }
+
+    fn member(
+        &self,
+        member: &ClassMember,
+        surface: Option<(&MemberSurface, &MemberSurface)>,
+        pinned: &mut Vec<(String, DocComment)>,
+        warnings: &mut Vec<DocWarning>,
+    ) -> MemberDocs {
+        // `@constructor` is still an attribute here; lowering to
+        // `is_constructor` happens later, in the compiler.
+        let is_constructor = matches!(member, ClassMember::Method(method) if method.is_constructor)
+            || member.attributes().iter().any(|attribute| attribute.name == "constructor");
+        let class_side = is_constructor || member.is_static() || member.attributes().iter().any(|attribute| attribute.name == "class");
+        let side = surface.map(|(instance, class)| if class_side { class } else { instance });
+        let store = self.snapshot.store();
+        let lookup = |selector: &Selector| -> Option<&CallableSignature> {
+            let (instance, class) = surface?;
+            side.and_then(|side| side.callable_signatures.get(selector))
+                .or_else(|| instance.callable_signatures.get(selector))
+                .or_else(|| class.callable_signatures.get(selector))
+        };
+
+        let mut parameters = Vec::new();
+        let (kind, selector, head, returns) = match member {
+            ClassMember::Method(method) => {
+                let selector = Selector::method(&method.name, slots(&method.params)).ok();
+                let signature = selector.as_ref().and_then(lookup);
+                parameters = self.parameters(&method.params, signature);
+                let kind = if is_constructor { MemberKind::Constructor } else { MemberKind::Method };
+                let returns = signature.and_then(|s| store.display_knowledge(&s.return_type));
+                (
+                    kind,
+                    encode(selector, &method.name),
+                    format!("{}({})", method.name, heads(&parameters)),
+                    returns,
+                )
+            }
+            ClassMember::Getter(getter) => {
+                let selector = Selector::getter(&getter.name).ok();
+                let returns = selector.as_ref().and_then(lookup).and_then(|s| store.display_knowledge(&s.return_type));
+                (MemberKind::Getter, encode(selector, &getter.name), getter.name.clone(), returns)
+            }
+            ClassMember::Setter(setter) => {
+                let selector = Selector::setter(&setter.name).ok();
+                let signature = selector.as_ref().and_then(lookup);
+                parameters = self.parameters(std::slice::from_ref(&setter.param), signature);
+                (
+                    MemberKind::Setter,
+                    encode(selector, &setter.name),
+                    format!("{}=({})", setter.name, heads(&parameters)),
+                    None,
+                )
+            }
+            ClassMember::Index(index) => {
+                let mut params = index.params.clone();
+                let selector = match &index.accessor {
+                    IndexAccessor::Get => Selector::subscript_get(slots(&index.params)).ok(),
+                    IndexAccessor::Set { put } => {
+                        params.push(put.clone());
+                        Selector::subscript_set(slots(&index.params)).ok()
+                    }
+                };
+                let signature = selector.as_ref().and_then(lookup);
+                parameters = self.parameters(&params, signature);
+                let (head, returns) = match &index.accessor {
+                    IndexAccessor::Get => (
+                        format!("[{}]", heads(&parameters)),
+                        signature.and_then(|s| store.display_knowledge(&s.return_type)),
+                    ),
+                    IndexAccessor::Set { .. } => {
+                        let (put, keys) = parameters.split_last().expect("setter has a put parameter");
+                        (format!("[{}]=({})", heads(keys), heads(std::slice::from_ref(put))), None)
+                    }
+                };
+                (MemberKind::Subscript, encode(selector, "[]"), head, returns)
+            }
+            ClassMember::Field(field) => {
+                let ty = side.and_then(|side| side.fields.get(&field.name)).and_then(|k| store.display_knowledge(k));
+                let head = if field.mutable { field.name.clone() } else { format!("const {}", field.name) };
+                (MemberKind::Field, field.name.clone(), head, ty)
+            }
+            ClassMember::Variant(variant) => {
+                parameters = variant
+                    .labels
+                    .iter()
+                    .map(|label| ParameterDocs {
+                        label: Some(label.clone()),
+                        name: label.clone(),
+                        ty: None,
+                        rest: false,
+                    })
+                    .collect();
+                let labels = variant.labels.iter().map(|label| format!("{label}:")).collect::<Vec<_>>().join(", ");
+                let selector = format!("{}({})", variant.name, variant.labels.iter().map(|l| l.as_str()).collect::<Vec<_>>().join(","));
+                (MemberKind::Variant, selector, format!("{}({labels})", variant.name), None)
+            }
+        };
+
+        let start = member
+            .attributes()
+            .iter()
+            .map(|attribute| attribute.range.start)
+            .chain([member_range_start(member)])
+            .min()
+            .unwrap_or_default();
+        let doc = self.source.outer_doc(start, warnings).or_else(|| take_pinned(pinned, &selector));
+        let signature = match &returns {
+            Some(ty) if kind == MemberKind::Field => format!("{head}: {ty}"),
+            Some(ty) => format!("{head} -> {ty}"),
+            None => head,
+        };
+        MemberDocs {
+            selector,
+            kind,
+            class_side,
+            signature,
+            parameters,
+            returns,
+            attributes: member
+                .attributes()
+                .iter()
+                .filter(|attribute| attribute.name != "variant")
+                .filter_map(|attribute| self.source.slice(attribute.range.start, attribute.range.end))
+                .map(str::to_string)
+                .collect(),
+            doc,
+        }
+    }
+
+    fn parameters(&self, params: &[ParameterDef], signature: Option<&CallableSignature>) -> Vec<ParameterDocs> {
+        params
+            .iter()
+            .enumerate()
+            .map(|(index, param)| ParameterDocs {
+                label: param.label.clone(),
+                name: param.name.clone(),
+                ty: signature
+                    .and_then(|signature| signature.parameters.get(index))
+                    .and_then(|parameter| self.snapshot.store().display_knowledge(&parameter.ty)),
+                rest: param.is_rest(),
+            })
+            .collect()
+    }
+}
+
+fn slots(params: &[ParameterDef]) -> Vec<SelectorSlot> {
+    params
+        .iter()
+        .map(|param| match &param.label {
+            Some(label) => SelectorSlot::Label(label.clone()),
+            None => SelectorSlot::Positional,
+        })
+        .collect()
+}
+
+fn encode(selector: Option<Selector>, fallback: &str) -> String {
+    selector.map(|selector| selector.encode()).unwrap_or_else(|| fallback.to_string())
+}
+
+/// Spells parameters the way a declaration writes them: `_ x: Int`,
+/// `to`, `label name`, `...rest`.
+fn heads(parameters: &[ParameterDocs]) -> String {
+    parameters
+        .iter()
+        .map(|parameter| {
+            let mut head = match &parameter.label {
+                _ if parameter.rest => format!("...{}", parameter.name),
+                Some(label) if *label == parameter.name => parameter.name.clone(),
+                Some(label) => format!("{label} {}", parameter.name),
+                None => format!("_ {}", parameter.name),
+            };
+            if let Some(ty) = &parameter.ty {
+                head.push_str(": ");
+                head.push_str(ty);
+            }
+            head
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn member_range_start(member: &ClassMember) -> usize {
+    match member {
+        ClassMember::Method(item) => item.range.start,
+        ClassMember::Getter(item) => item.range.start,
+        ClassMember::Setter(item) => item.range.start,
+        ClassMember::Field(item) => item.range.start,
+        ClassMember::Variant(item) => item.range.start,
+        ClassMember::Index(item) => item.range.start,
+    }
+}
+
+/// Takes the pinned block for `selector` when exactly one was written.
+fn take_pinned(pinned: &mut Vec<(String, DocComment)>, selector: &str) -> Option<DocComment> {
+    let mut matching = pinned.iter().enumerate().filter(|(_, (pin, _))| pin == selector);
+    let (index, _) = matching.next()?;
+    if matching.next().is_some() {
+        return None;
+    }
+    Some(pinned.remove(index).1)
+}
+
+/// A class documented both above (`///`) and from inside (`//!`) keeps the
+/// outer summary and appends the inner block.
+fn merge_docs(outer: Option<DocComment>, inner: Option<DocComment>) -> Option<DocComment> {
+    match (outer, inner) {
+        (Some(mut outer), Some(inner)) => {
+            let inner_text = [inner.summary, inner.details]
+                .into_iter()
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            outer.details = [std::mem::take(&mut outer.details), inner_text]
+                .into_iter()
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            outer.tags.extend(inner.tags);
+            outer.examples.extend(inner.examples);
+            Some(outer)
+        }
+        (outer, inner) => outer.or(inner),
+    }
+}
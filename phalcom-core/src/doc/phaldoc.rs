@@ -0,0 +1,432 @@
+//! Phaldoc comment harvesting (doc-comments-phaldoc.md §1-5).
+//!
+//! Phaldoc lives in `//`-trivia the lexer never tokenizes, so this re-reads
+//! the raw module text. Association is by source position: a `///` run
+//! documents the declaration that starts on the very next line (attributes
+//! included), `//!` lines at the top of the file document the module, and
+//! `//!` lines opening a class body document the class. A detached block
+//! whose first line is `selector: <sel>` is kept aside and attached to the
+//! one member with that selector.
+
+use serde::Serialize;
+
+/// One harvested doc block.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct DocComment {
+    /// The first paragraph — the one-liner an index shows (§2).
+    pub summary: String,
+    /// Markdown after the summary, up to the first `@tag` line.
+    pub details: String,
+    /// Every `@tag payload` except `@example`, in source order, with
+    /// indented continuation lines folded into the payload and `@return`/
+    /// `@raises` canonicalized to `returns`/`throws` (§3).
+    pub tags: Vec<DocTag>,
+    /// The `@example` blocks, in source order.
+    pub examples: Vec<DocExample>,
+}
+
+impl DocComment {
+    /// Payloads of every tag named `name`.
+    pub fn tag_payloads<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.tags.iter().filter(move |tag| tag.name == name).map(|tag| tag.payload.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DocTag {
+    pub name: String,
+    pub payload: String,
+}
+
+/// An `@example` block: the fenced source, dedented to the fence.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DocExample {
+    pub code: String,
+    /// 1-based source line the example's code starts on.
+    pub line: usize,
+    /// Whether the block is Phalcom source a doctest runner should execute.
+    /// A fence tagged with another language (```` ```text ````) or
+    /// `ignore` is rendered but never run.
+    pub doctest: bool,
+}
+
+/// Advisory finding raised while harvesting; Phaldoc never errors (§3).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DocWarning {
+    /// 1-based source line.
+    pub line: usize,
+    pub message: String,
+}
+
+/// The v1 tag vocabulary, after alias canonicalization (§3).
+const KNOWN_TAGS: &[&str] = &["param", "returns", "throws", "example", "see", "since", "deprecated", "author"];
+
+/// Reserved for later versions; rejected rather than silently dropped (§3).
+const RESERVED_TAGS: &[&str] = &["typeparam", "invariant-doc", "group", "internal"];
+
+/// Raw module text indexed by line, for position-based doc lookup.
+pub(crate) struct SourceDocs<'a> {
+    text: &'a str,
+    lines: Vec<&'a str>,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> SourceDocs<'a> {
+    pub(crate) fn new(text: &'a str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(text.match_indices('\n').map(|(i, _)| i + 1));
+        Self {
+            text,
+            lines: text.lines().collect(),
+            line_starts,
+        }
+    }
+
+    /// 0-based line containing byte `offset`.
+    pub(crate) fn line_of(&self, offset: usize) -> usize {
+        match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next) => next - 1,
+        }
+    }
+
+    /// Source text of a byte range, if it is in bounds.
+    pub(crate) fn slice(&self, start: usize, end: usize) -> Option<&'a str> {
+        self.text.get(start..end)
+    }
+
+    /// The `//!` block at the top of the file: every inner-doc line before
+    /// the first line that is neither blank nor a comment.
+    pub(crate) fn module_doc(&self, warnings: &mut Vec<DocWarning>) -> Option<DocComment> {
+        let mut body = Vec::new();
+        for (index, line) in self.lines.iter().enumerate() {
+            let trimmed = line.trim();
+            if let Some(rest) = trimmed.strip_prefix("//!") {
+                body.push((index, strip_one_space(rest)));
+            } else if !trimmed.is_empty() && !trimmed.starts_with("//") {
+                break;
+            }
+        }
+        (!body.is_empty()).then(|| parse_block(&body, warnings))
+    }
+
+    /// The `///` run ending on the line directly above `declaration_start`,
+    /// or `None` when that line is not a doc line. A `selector:` pin line is
+    /// skipped: adjacency already names the target.
+    pub(crate) fn outer_doc(&self, declaration_start: usize, warnings: &mut Vec<DocWarning>) -> Option<DocComment> {
+        let declaration_line = self.line_of(declaration_start);
+        if declaration_line == 0 || !is_outer_doc(self.lines.get(declaration_line - 1)?) {
+            return None;
+        }
+        let mut start = declaration_line - 1;
+        while start > 0 && is_outer_doc(self.lines[start - 1]) {
+            start -= 1;
+        }
+        let mut body = self.outer_body(start, declaration_line);
+        if body.first().is_some_and(|(_, line)| line.trim_start().starts_with("selector:")) {
+            body.remove(0);
+        }
+        Some(parse_block(&body, warnings))
+    }
+
+    /// The `//!` lines opening the body whose `{` is the first one at or
+    /// after `from` — a class documenting itself from inside (§1, §5).
+    pub(crate) fn inner_doc_after(&self, from: usize, warnings: &mut Vec<DocWarning>) -> Option<DocComment> {
+        let brace = from + self.text.get(from..)?.find('{')?;
+        let mut body = Vec::new();
+        for index in self.line_of(brace) + 1..self.lines.len() {
+            let trimmed = self.lines[index].trim();
+            match trimmed.strip_prefix("//!") {
+                Some(rest) => body.push((index, strip_one_space(rest))),
+                None if trimmed.is_empty() && body.is_empty() => {}
+                None => break,
+            }
+        }
+        (!body.is_empty()).then(|| parse_block(&body, warnings))
+    }
+
+    /// Every detached `/// selector: <sel>` block, keyed by its pinned
+    /// selector (§4.4).
+    pub(crate) fn pinned_docs(&self, warnings: &mut Vec<DocWarning>) -> Vec<(String, DocComment)> {
+        let mut pinned = Vec::new();
+        let mut index = 0;
+        while index < self.lines.len() {
+            if !is_outer_doc(self.lines[index]) {
+                index += 1;
+                continue;
+            }
+            let start = index;
+            while index < self.lines.len() && is_outer_doc(self.lines[index]) {
+                index += 1;
+            }
+            let body = self.outer_body(start, index);
+            if let Some(selector) = body.first().and_then(|(_, line)| line.trim().strip_prefix("selector:")) {
+                pinned.push((selector.trim().to_string(), parse_block(&body[1..], warnings)));
+            }
+        }
+        pinned
+    }
+
+    fn outer_body(&self, start: usize, end: usize) -> Vec<(usize, String)> {
+        (start..end)
+            .map(|index| (index, strip_one_space(self.lines[index].trim().strip_prefix("///").unwrap_or_default())))
+            .collect()
+    }
+}
+
+/// A `///` line that is not a `////` rule.
+fn is_outer_doc(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("///") && !trimmed.starts_with("////")
+}
+
+fn strip_one_space(rest: &str) -> String {
+    rest.strip_prefix(' ').unwrap_or(rest).to_string()
+}
+
+fn indent_width(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn canonical_tag_name(name: &str) -> &str {
+    match name {
+        "return" => "returns",
+        "raises" => "throws",
+        other => other,
+    }
+}
+
+/// Parses marker-stripped doc lines (each paired with its 0-based source
+/// line) into a [`DocComment`].
+fn parse_block(lines: &[(usize, String)], warnings: &mut Vec<DocWarning>) -> DocComment {
+    let mut doc = DocComment::default();
+    let mut index = 0;
+
+    let mut summary = Vec::new();
+    while index < lines.len() {
+        let trimmed = lines[index].1.trim();
+        if trimmed.is_empty() || trimmed.starts_with('@') || trimmed.starts_with("```") {
+            break;
+        }
+        summary.push(trimmed);
+        index += 1;
+    }
+    doc.summary = summary.join(" ");
+
+    let mut details = Vec::new();
+    let mut in_fence = false;
+    while index < lines.len() {
+        let line = &lines[index].1;
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        } else if !in_fence && line.trim_start().starts_with('@') {
+            break;
+        }
+        details.push(line.clone());
+        index += 1;
+    }
+
+    while index < lines.len() {
+        let (source_line, line) = &lines[index];
+        let tag_indent = indent_width(line);
+        let Some(rest) = line.trim_start().strip_prefix('@') else {
+            index += 1;
+            continue;
+        };
+        let mut parts = rest.splitn(2, char::is_whitespace);
+        let raw_name = parts.next().unwrap_or_default();
+        let name = canonical_tag_name(raw_name);
+        let mut payload = parts.next().unwrap_or_default().trim().to_string();
+        index += 1;
+
+        if name == "example" {
+            index = parse_example(lines, index, *source_line, payload, &mut doc.examples, warnings);
+            continue;
+        }
+        while index < lines.len() {
+            let next = &lines[index].1;
+            if next.trim().is_empty() || next.trim_start().starts_with('@') || indent_width(next) <= tag_indent {
+                break;
+            }
+            if !payload.is_empty() {
+                payload.push(' ');
+            }
+            payload.push_str(next.trim());
+            index += 1;
+        }
+
+        if KNOWN_TAGS.contains(&name) {
+            doc.tags.push(DocTag {
+                name: name.to_string(),
+                payload,
+            });
+        } else {
+            let kind = if RESERVED_TAGS.contains(&name) { "reserved" } else { "unknown" };
+            warnings.push(DocWarning {
+                line: source_line + 1,
+                message: format!("{kind} Phaldoc tag `@{raw_name}` is treated as prose"),
+            });
+            if details.last().is_some_and(|line: &String| !line.trim().is_empty()) {
+                details.push(String::new());
+            }
+            details.push(format!("@{raw_name} {payload}").trim_end().to_string());
+        }
+    }
+
+    doc.details = trim_blank_lines(&details);
+    doc
+}
+
+/// Reads the fenced block (or inline payload) following an `@example` tag
+/// starting at `index`; returns the index after it.
+fn parse_example(
+    lines: &[(usize, String)],
+    mut index: usize,
+    tag_line: usize,
+    payload: String,
+    examples: &mut Vec<DocExample>,
+    warnings: &mut Vec<DocWarning>,
+) -> usize {
+    while index < lines.len() && lines[index].1.trim().is_empty() {
+        index += 1;
+    }
+    let fence = lines
+        .get(index)
+        .and_then(|(_, line)| line.trim_start().strip_prefix("```").map(|lang| (line, lang)));
+    let Some((fence_line, lang)) = fence else {
+        if payload.is_empty() {
+            warnings.push(DocWarning {
+                line: tag_line + 1,
+                message: "`@example` has no fenced code block".to_string(),
+            });
+        } else {
+            examples.push(DocExample {
+                code: payload,
+                line: tag_line + 1,
+                doctest: true,
+            });
+        }
+        return index;
+    };
+
+    let lang = lang.trim();
+    let doctest = matches!(lang, "" | "phalcom" | "ph");
+    let fence_indent = indent_width(fence_line);
+    let start = index + 1;
+    let mut end = start;
+    while end < lines.len() && !lines[end].1.trim_start().starts_with("```") {
+        end += 1;
+    }
+    if end == lines.len() {
+        warnings.push(DocWarning {
+            line: lines[index].0 + 1,
+            message: "unterminated `@example` code fence".to_string(),
+        });
+    }
+    let code = lines[start..end]
+        .iter()
+        .map(|(_, line)| {
+            let strip = indent_width(line).min(fence_indent);
+            &line[strip..]
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    examples.push(DocExample {
+        code,
+        line: lines.get(start).map_or(tag_line, |(line, _)| *line) + 1,
+        doctest,
+    });
+    (end + 1).min(lines.len())
+}
+
+fn trim_blank_lines(lines: &[String]) -> String {
+    let start = lines.iter().position(|line| !line.trim().is_empty()).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|line| !line.trim().is_empty()).map_or(start, |end| end + 1);
+    lines[start..end].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_summary_details_tags_and_examples() {
+        let text = "\
+/// Moves the point.
+/// Keeps the original.
+///
+/// Details with `code`.
+///
+/// @param to the target
+///   point, absolute
+/// @return the moved copy
+/// @example
+/// ```phalcom
+/// Point.origin.move(to: 1)
+/// ```
+/// @frobnicate later
+move(to) {}
+";
+        let docs = SourceDocs::new(text);
+        let mut warnings = Vec::new();
+        let doc = docs.outer_doc(text.find("move(to)").unwrap(), &mut warnings).unwrap();
+        assert_eq!(doc.summary, "Moves the point. Keeps the original.");
+        assert_eq!(doc.details, "Details with `code`.\n\n@frobnicate later");
+        assert_eq!(
+            doc.tags,
+            vec![
+                DocTag {
+                    name: "param".into(),
+                    payload: "to the target point, absolute".into()
+                },
+                DocTag {
+                    name: "returns".into(),
+                    payload: "the moved copy".into()
+                },
+            ]
+        );
+        assert_eq!(doc.examples.len(), 1);
+        assert_eq!(doc.examples[0].code, "Point.origin.move(to: 1)");
+        assert_eq!(doc.examples[0].line, 11);
+        assert!(doc.examples[0].doctest);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].line, 13);
+    }
+
+    #[test]
+    fn module_class_inner_and_pinned_docs() {
+        let text = "\
+//! The geometry module.
+
+class Shape {
+  //! A closed figure.
+  area { 0 }
+}
+
+/// selector: area
+/// Always zero.
+";
+        let docs = SourceDocs::new(text);
+        let mut warnings = Vec::new();
+        assert_eq!(docs.module_doc(&mut warnings).unwrap().summary, "The geometry module.");
+        assert_eq!(
+            docs.inner_doc_after(text.find("Shape").unwrap(), &mut warnings).unwrap().summary,
+            "A closed figure."
+        );
+        assert!(docs.outer_doc(text.find("area {").unwrap(), &mut warnings).is_none());
+        let pinned = docs.pinned_docs(&mut warnings);
+        assert_eq!(pinned.len(), 1);
+        assert_eq!(pinned[0].0, "area");
+        assert_eq!(pinned[0].1.summary, "Always zero.");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn non_phalcom_fences_are_not_doctests() {
+        let text = "/// Shows output.\n/// @example\n/// ```text\n/// 42\n/// ```\nclass Answer {}\n";
+        let docs = SourceDocs::new(text);
+        let mut warnings = Vec::new();
+        let doc = docs.outer_doc(text.find("class").unwrap(), &mut warnings).unwrap();
+        assert!(!doc.examples[0].doctest);
+    }
+}
@@ -0,0 +1,397 @@
+//! Typed conversion between Rust values and Phalcom [`Value`]s.
+//!
+//! [`IntoPhalcom`] builds a value on the VM's heap; [`FromPhalcom`] reads one
+//! back. Both are strict about shape: an integer type is read from an `Int` or
+//! a `LargeInt` only (so whatever a `u64` or `i128` builds reads back), a
+//! `Vec<T>` from a `List` or positional `Tuple`, an `Option<T>` from `None` or
+//! `Some(_)` and never from a bare value. A mismatch is a
+//! [`RuntimeError::TypeConversion`] naming what was expected and what was
+//! found, and an integer outside the target's range a
+//! [`RuntimeError::NumericOverflow`].
+//!
+//! **Rooting.** A conversion that builds a container holds the values it has
+//! already built in Rust while it converts the rest, and converting a `Map`
+//! key sends `hash` — a re-entrant call that can collect. Every such
+//! intermediate is held as a temp root for the span of the conversion
+//! ([`VM::push_temp_root`]). The finished value is unrooted: convert it into
+//! a [`Handle`] before anything else runs if it must outlive the next call.
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+use super::handle::Handle;
+use crate::error::{PhError, PhResult, RuntimeError};
+use crate::heap::{Object, TupleObject};
+use crate::value::{OptionCase, Value};
+use crate::vm::VM;
+
+/// A Rust value that can be built as a Phalcom [`Value`].
+pub trait IntoPhalcom {
+    /// Builds `self` on `vm`'s heap.
+    ///
+    /// # Errors
+    ///
+    /// Propagates a failing `hash`/`==` send while building a `Map`, and
+    /// [`RuntimeError::OptionNestingLimit`] for an unrepresentable `Option`.
+    fn into_phalcom(self, vm: &mut VM) -> PhResult<Value>;
+}
+
+/// A Rust value that can be read from a Phalcom [`Value`].
+pub trait FromPhalcom: Sized {
+    /// Reads `value` as `Self`.
+    ///
+    /// # Errors
+    ///
+    /// [`RuntimeError::TypeConversion`] when `value` has the wrong shape, and
+    /// [`RuntimeError::NumericOverflow`] when a number does not fit.
+    fn from_phalcom(vm: &mut VM, value: Value) -> PhResult<Self>;
+}
+
+/// An argument list for [`Engine::call`](super::Engine::call): `()` or a tuple
+/// of up to eight [`IntoPhalcom`] values, or a prebuilt `Vec<Value>`.
+pub trait IntoArgs {
+    /// Converts every argument, in order.
+    ///
+    /// # Errors
+    ///
+    /// The first failing argument conversion.
+    fn into_args(self, vm: &mut VM) -> PhResult<Vec<Value>>;
+}
+
+/// Runs `build`, releasing every temp root it pushes on the way out.
+fn rooted<T>(vm: &mut VM, build: impl FnOnce(&mut VM) -> PhResult<T>) -> PhResult<T> {
+    let depth = vm.temp_root_depth();
+    let result = build(vm);
+    vm.truncate_temp_roots(depth);
+    result
+}
+
+/// Converts `item` and roots the result until the enclosing [`rooted`] ends.
+fn push_rooted(vm: &mut VM, item: impl IntoPhalcom) -> PhResult<Value> {
+    let value = item.into_phalcom(vm)?;
+    vm.push_temp_root(value);
+    Ok(value)
+}
+
+/// A diagnostic name for `value`'s shape — finer than
+/// [`Value::type_name`], which reports every heap value as `"object"`.
+fn describe(vm: &VM, value: Value) -> &'static str {
+    let Some(id) = value.as_obj() else {
+        return value.type_name();
+    };
+    match vm.heap.get(id) {
+        Object::Str(_) => "String",
+        Object::List(_) => "List",
+        Object::Map(_) => "Map",
+        Object::Set(_) => "Set",
+        Object::Tuple(_) => "Tuple",
+        Object::Record(_) => "Record",
+        Object::LargeInt(_) => "LargeInt",
+        Object::Instance(_) => "instance",
+        Object::Class(_) => "class",
+        Object::Module(_) => "module",
+        _ => "object",
+    }
+}
+
+fn mismatch(vm: &VM, expected: &'static str, value: Value) -> PhError {
+    RuntimeError::TypeConversion {
+        expected,
+        found: describe(vm, value),
+    }
+    .into()
+}
+
+fn overflow(target_type: &'static str) -> PhError {
+    RuntimeError::NumericOverflow {
+        operation: "host conversion",
+        target_type,
+    }
+    .into()
+}
+
+impl IntoPhalcom for Value {
+    fn into_phalcom(self, _vm: &mut VM) -> PhResult<Value> {
+        Ok(self)
+    }
+}
+
+impl FromPhalcom for Value {
+    fn from_phalcom(_vm: &mut VM, value: Value) -> PhResult<Self> {
+        Ok(value)
+    }
+}
+
+impl IntoPhalcom for &Handle {
+    fn into_phalcom(self, _vm: &mut VM) -> PhResult<Value> {
+        Ok(self.value())
+    }
+}
+
+impl IntoPhalcom for Handle {
+    fn into_phalcom(self, _vm: &mut VM) -> PhResult<Value> {
+        Ok(self.value())
+    }
+}
+
+impl FromPhalcom for Handle {
+    fn from_phalcom(vm: &mut VM, value: Value) -> PhResult<Self> {
+        Ok(Handle::new(vm, value))
+    }
+}
+
+/// `()` builds `None`, what a `.ph` body with no result produces, and reads
+/// any value by discarding it.
+impl IntoPhalcom for () {
+    fn into_phalcom(self, _vm: &mut VM) -> PhResult<Value> {
+        Ok(Value::none())
+    }
+}
+
+impl FromPhalcom for () {
+    fn from_phalcom(_vm: &mut VM, _value: Value) -> PhResult<Self> {
+        Ok(())
+    }
+}
+
+impl IntoPhalcom for bool {
+    fn into_phalcom(self, _vm: &mut VM) -> PhResult<Value> {
+        Ok(Value::bool(self))
+    }
+}
+
+impl FromPhalcom for bool {
+    fn from_phalcom(vm: &mut VM, value: Value) -> PhResult<Self> {
+        value.as_bool().ok_or_else(|| mismatch(vm, "Bool", value))
+    }
+}
+
+/// Integers that always fit an `Int`.
+macro_rules! small_int {
+    ($($ty:ty),*) => {$(
+        impl IntoPhalcom for $ty {
+            fn into_phalcom(self, _vm: &mut VM) -> PhResult<Value> {
+                Ok(Value::int(i64::from(self)))
+            }
+        }
+    )*};
+}
+
+/// Integers that may exceed an `Int` and are built as a `LargeInt` then.
+macro_rules! wide_int {
+    ($($ty:ty),*) => {$(
+        impl IntoPhalcom for $ty {
+            fn into_phalcom(self, vm: &mut VM) -> PhResult<Value> {
+                Ok(match i64::try_from(self) {
+                    Ok(int) => Value::int(int),
+                    Err(_) => crate::value::normalize_bigint(num_bigint::BigInt::from(self), &mut vm.heap),
+                })
+            }
+        }
+    )*};
+}
+
+/// Every integer type reads from an `Int` or a `LargeInt` in its range.
+macro_rules! read_int {
+    ($($ty:ty),*) => {$(
+        impl FromPhalcom for $ty {
+            fn from_phalcom(vm: &mut VM, value: Value) -> PhResult<Self> {
+                if let Some(int) = value.as_int() {
+                    return <$ty>::try_from(int).map_err(|_| overflow(stringify!($ty)));
+                }
+                let large = value
+                    .as_obj()
+                    .and_then(|id| vm.heap.as_large_int(id))
+                    .ok_or_else(|| mismatch(vm, "Int", value))?;
+                <$ty>::try_from(large).map_err(|_| overflow(stringify!($ty)))
+            }
+        }
+    )*};
+}
+
+small_int!(i8, i16, i32, i64, u8, u16, u32);
+wide_int!(u64, usize, isize, i128, u128);
+read_int!(i8, i16, i32, i64, isize, i128, u8, u16, u32, u64, usize, u128);
+
+impl IntoPhalcom for f64 {
+    fn into_phalcom(self, _vm: &mut VM) -> PhResult<Value> {
+        Ok(Value::float(self))
+    }
+}
+
+impl IntoPhalcom for f32 {
+    fn into_phalcom(self, _vm: &mut VM) -> PhResult<Value> {
+        Ok(Value::float(f64::from(self)))
+    }
+}
+
+/// Reads a `Float`, or an `Int` widened the way `Int#toFloat` widens it.
+impl FromPhalcom for f64 {
+    fn from_phalcom(vm: &mut VM, value: Value) -> PhResult<Self> {
+        value
+            .as_float()
+            .or_else(|| value.as_int().map(|int| int as f64))
+            .ok_or_else(|| mismatch(vm, "Float", value))
+    }
+}
+
+impl FromPhalcom for f32 {
+    fn from_phalcom(vm: &mut VM, value: Value) -> PhResult<Self> {
+        f64::from_phalcom(vm, value).map(|float| float as f32)
+    }
+}
+
+impl IntoPhalcom for String {
+    fn into_phalcom(self, vm: &mut VM) -> PhResult<Value> {
+        Ok(vm.alloc_string_value(self))
+    }
+}
+
+impl IntoPhalcom for &str {
+    fn into_phalcom(self, vm: &mut VM) -> PhResult<Value> {
+        Ok(vm.alloc_string_value(self.to_owned()))
+    }
+}
+
+impl FromPhalcom for String {
+    fn from_phalcom(vm: &mut VM, value: Value) -> PhResult<Self> {
+        value
+            .as_obj()
+            .and_then(|id| vm.heap.as_string(id))
+            .map(|string| string.as_str().to_owned())
+            .ok_or_else(|| mismatch(vm, "String", value))
+    }
+}
+
+impl<T: IntoPhalcom> IntoPhalcom for Option<T> {
+    fn into_phalcom(self, vm: &mut VM) -> PhResult<Value> {
+        match self {
+            Some(inner) => Ok(inner.into_phalcom(vm)?.wrap_some()?),
+            None => Ok(Value::none()),
+        }
+    }
+}
+
+impl<T: FromPhalcom> FromPhalcom for Option<T> {
+    fn from_phalcom(vm: &mut VM, value: Value) -> PhResult<Self> {
+        match value.option_case() {
+            OptionCase::None => Ok(None),
+            OptionCase::Some(inner) => T::from_phalcom(vm, inner).map(Some),
+            OptionCase::NotOption => Err(mismatch(vm, "Option", value)),
+        }
+    }
+}
+
+/// A `PhResult` builds its `Ok` value and passes its error through, so a host
+/// function can fail by returning `Err`.
+impl<T: IntoPhalcom> IntoPhalcom for PhResult<T> {
+    fn into_phalcom(self, vm: &mut VM) -> PhResult<Value> {
+        self?.into_phalcom(vm)
+    }
+}
+
+impl<T: IntoPhalcom> IntoPhalcom for Vec<T> {
+    fn into_phalcom(self, vm: &mut VM) -> PhResult<Value> {
+        rooted(vm, |vm| {
+            let elements = self.into_iter().map(|item| push_rooted(vm, item)).collect::<PhResult<Vec<_>>>()?;
+            Ok(Value::obj(vm.heap.alloc_list(elements)))
+        })
+    }
+}
+
+impl<T: FromPhalcom> FromPhalcom for Vec<T> {
+    fn from_phalcom(vm: &mut VM, value: Value) -> PhResult<Self> {
+        let elements = value
+            .as_obj()
+            .and_then(|id| match vm.heap.get(id) {
+                Object::List(list) => Some(list.elements().to_vec()),
+                Object::Tuple(tuple) if tuple.labeled_len() == 0 => Some(tuple.values().to_vec()),
+                _ => None,
+            })
+            .ok_or_else(|| mismatch(vm, "List", value))?;
+        elements.into_iter().map(|element| T::from_phalcom(vm, element)).collect()
+    }
+}
+
+impl<K: IntoPhalcom, V: IntoPhalcom, S> IntoPhalcom for HashMap<K, V, S> {
+    fn into_phalcom(self, vm: &mut VM) -> PhResult<Value> {
+        rooted(vm, |vm| {
+            let map = vm.heap.alloc_map();
+            vm.push_temp_root(Value::obj(map));
+            for (key, value) in self {
+                let key = push_rooted(vm, key)?;
+                let value = push_rooted(vm, value)?;
+                crate::primitive::map::map_literal_insert_unique(vm, map, key, value)?;
+            }
+            Ok(Value::obj(map))
+        })
+    }
+}
+
+impl<K: FromPhalcom + Eq + Hash, V: FromPhalcom, S: BuildHasher + Default> FromPhalcom for HashMap<K, V, S> {
+    fn from_phalcom(vm: &mut VM, value: Value) -> PhResult<Self> {
+        let entries: Vec<(Value, Value)> = value
+            .as_obj()
+            .and_then(|id| vm.heap.as_map(id))
+            .map(|map| map.entries().collect())
+            .ok_or_else(|| mismatch(vm, "Map", value))?;
+        entries
+            .into_iter()
+            .map(|(key, value)| Ok((K::from_phalcom(vm, key)?, V::from_phalcom(vm, value)?)))
+            .collect()
+    }
+}
+
+impl IntoArgs for Vec<Value> {
+    fn into_args(self, _vm: &mut VM) -> PhResult<Vec<Value>> {
+        Ok(self)
+    }
+}
+
+impl IntoArgs for () {
+    fn into_args(self, _vm: &mut VM) -> PhResult<Vec<Value>> {
+        Ok(Vec::new())
+    }
+}
+
+/// Positional tuples, both as `Tuple` values and as argument lists.
+macro_rules! tuple {
+    ($len:literal; $($name:ident $index:tt),+) => {
+        impl<$($name: IntoPhalcom),+> IntoPhalcom for ($($name,)+) {
+            fn into_phalcom(self, vm: &mut VM) -> PhResult<Value> {
+                let values = self.into_args(vm)?;
+                Ok(Value::obj(vm.heap.alloc(Object::Tuple(TupleObject::positional(values)))))
+            }
+        }
+
+        impl<$($name: FromPhalcom),+> FromPhalcom for ($($name,)+) {
+            fn from_phalcom(vm: &mut VM, value: Value) -> PhResult<Self> {
+                let values = value
+                    .as_obj()
+                    .and_then(|id| vm.heap.as_tuple(id))
+                    .filter(|tuple| tuple.labeled_len() == 0)
+                    .map(|tuple| tuple.values().to_vec())
+                    .ok_or_else(|| mismatch(vm, "Tuple", value))?;
+                if values.len() != $len {
+                    return Err(RuntimeError::Arity { signature: "Tuple", expected: $len, found: values.len() }.into());
+                }
+                Ok(($($name::from_phalcom(vm, values[$index])?,)+))
+            }
+        }
+
+        impl<$($name: IntoPhalcom),+> IntoArgs for ($($name,)+) {
+            fn into_args(self, vm: &mut VM) -> PhResult<Vec<Value>> {
+                rooted(vm, |vm| Ok(vec![$(push_rooted(vm, self.$index)?),+]))
+            }
+        }
+    };
+}
+
+tuple!(1; A 0);
+tuple!(2; A 0, B 1);
+tuple!(3; A 0, B 1, C 2);
+tuple!(4; A 0, B 1, C 2, D 3);
+tuple!(5; A 0, B 1, C 2, D 3, E 4);
+tuple!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+tuple!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
@@ -0,0 +1,124 @@
+//! GC-rooted host handles.
+//!
+//! A [`Handle`] is the host's counterpart of
+//! [`VM::push_temp_root`](crate::vm::VM): it keeps one value alive for as long
+//! as the Rust owner holds it. Temp roots are a depth-and-truncate stack, which
+//! suits a primitive that roots for the span of one re-entrant call; host
+//! values outlive any single call and are dropped in whatever order the
+//! embedder likes, so handles live in a slot table instead. Dropping a handle
+//! frees its slot, and the collector reads the table as part of the root set.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use super::convert::FromPhalcom;
+use crate::error::PhResult;
+use crate::heap::ObjRef;
+use crate::value::Value;
+use crate::vm::VM;
+
+/// The slot table behind every [`Handle`] of one VM.
+///
+/// Shared (`Rc`) between the VM — which traces it — and each handle — which
+/// releases its slot on drop without needing the VM.
+#[derive(Clone, Default)]
+pub(crate) struct HostRoots(Rc<RefCell<RootTable>>);
+
+#[derive(Default)]
+struct RootTable {
+    slots: Vec<Option<ObjRef>>,
+    free: Vec<usize>,
+}
+
+impl HostRoots {
+    fn root(&self, id: ObjRef) -> usize {
+        let mut table = self.0.borrow_mut();
+        match table.free.pop() {
+            Some(slot) => {
+                table.slots[slot] = Some(id);
+                slot
+            }
+            None => {
+                table.slots.push(Some(id));
+                table.slots.len() - 1
+            }
+        }
+    }
+
+    fn release(&self, slot: usize) {
+        let mut table = self.0.borrow_mut();
+        table.slots[slot] = None;
+        table.free.push(slot);
+    }
+
+    /// Visits every rooted handle — the collector's half.
+    pub(crate) fn each_handle(&self, f: &mut dyn FnMut(ObjRef)) {
+        self.0.borrow().slots.iter().flatten().for_each(|&id| f(id));
+    }
+
+    /// Number of live handles.
+    pub(crate) fn len(&self) -> usize {
+        let table = self.0.borrow();
+        table.slots.len() - table.free.len()
+    }
+}
+
+/// A Phalcom value held by the host, rooted for as long as the handle lives.
+///
+/// Immediates (numbers, booleans, symbols, `None`) take no slot; heap values
+/// are kept alive across every collection until the handle is dropped.
+/// Cloning roots the value again under a second slot.
+///
+/// A handle belongs to the VM that produced it. Passing it to another VM is
+/// not detected and hands that VM a meaningless object reference.
+pub struct Handle {
+    value: Value,
+    slot: Option<(HostRoots, usize)>,
+}
+
+impl Handle {
+    /// Roots `value` in `vm`.
+    pub fn new(vm: &VM, value: Value) -> Self {
+        let slot = value.gc_obj_ref().map(|id| (vm.host.roots.clone(), vm.host.roots.root(id)));
+        Self { value, slot }
+    }
+
+    /// The held value. It stays valid while `self` does.
+    pub fn value(&self) -> Value {
+        self.value
+    }
+
+    /// Converts the held value to `T`.
+    ///
+    /// # Errors
+    ///
+    /// Whatever `T`'s [`FromPhalcom`] conversion reports.
+    pub fn get<T: FromPhalcom>(&self, vm: &mut VM) -> PhResult<T> {
+        T::from_phalcom(vm, self.value)
+    }
+}
+
+impl Clone for Handle {
+    fn clone(&self) -> Self {
+        let slot = self.slot.as_ref().map(|(roots, _)| {
+            let id = self.value.gc_obj_ref().expect("a rooted handle holds a heap value");
+            (roots.clone(), roots.root(id))
+        });
+        Self { value: self.value, slot }
+    }
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        if let Some((roots, slot)) = self.slot.take() {
+            roots.release(slot);
+        }
+    }
+}
+
+impl fmt::Debug for Handle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Handle").field("value", &self.value).finish()
+    }
+}
@@ -0,0 +1,164 @@
+//! Host functions: Rust closures callable from `.ph` code.
+//!
+//! A native primitive is a plain `fn` pointer registered at link time
+//! (`#[primitive]`); a host function is a closure registered at run time, so
+//! it cannot be a method of its own. Every host function is instead an
+//! instance of one runtime class, `HostFunction`, whose single slot indexes
+//! [`HostState::functions`]. The class answers `call`, `call(_)`, … up to
+//! [`MAX_HOST_ARITY`] arguments through one primitive that looks the closure
+//! up and runs it, so `square(3)` and `square.call(3)` reach it like any
+//! other callable.
+
+use std::rc::Rc;
+
+use super::convert::{FromPhalcom, IntoPhalcom};
+use super::handle::HostRoots;
+use crate::error::{PhResult, RuntimeError};
+use crate::heap::{ClassId, InstanceObject, ObjRef, Object};
+use crate::method::{MethodObject, SignatureKind, encode_selector};
+use crate::value::Value;
+use crate::vm::VM;
+
+/// The most arguments a host function can be called with.
+pub const MAX_HOST_ARITY: usize = 8;
+
+/// The untyped host-function signature every [`HostFn`] is lowered to.
+pub type RawHostFn = dyn Fn(&mut VM, &[Value]) -> PhResult<Value>;
+
+/// Embedding state the VM carries for [`super::Engine`]: handle roots and
+/// registered host functions.
+#[derive(Default)]
+pub(crate) struct HostState {
+    pub(crate) roots: HostRoots,
+    functions: Vec<HostFunction>,
+    /// `HostFunction`, created with the first registration.
+    class: Option<ClassId>,
+}
+
+struct HostFunction {
+    /// Declared arity, checked before the closure runs; `None` for a raw
+    /// function that checks its own arguments.
+    arity: Option<usize>,
+    call: Rc<RawHostFn>,
+}
+
+impl HostState {
+    /// Visits the handles this state holds — the collector's half.
+    pub(crate) fn each_handle(&self, f: &mut dyn FnMut(ObjRef)) {
+        self.roots.each_handle(f);
+        if let Some(class) = self.class {
+            f(class);
+        }
+    }
+}
+
+/// A Rust closure usable as a host function, typed by its argument tuple.
+///
+/// Implemented for every `Fn(A, B, …) -> R` of up to [`MAX_HOST_ARITY`]
+/// arguments whose arguments are [`FromPhalcom`] and whose result is
+/// [`IntoPhalcom`] — including `PhResult<T>`, so a closure fails by returning
+/// `Err`.
+pub trait HostFn<Args>: 'static {
+    /// The number of arguments the closure takes.
+    const ARITY: usize;
+
+    /// Converts `args`, runs the closure and converts its result.
+    ///
+    /// # Errors
+    ///
+    /// A failing argument or result conversion, or the closure's own error.
+    fn call_host(&self, vm: &mut VM, args: &[Value]) -> PhResult<Value>;
+}
+
+macro_rules! host_fn {
+    ($len:literal; $($name:ident $index:tt),*) => {
+        impl<Func, Ret, $($name),*> HostFn<($($name,)*)> for Func
+        where
+            Func: Fn($($name),*) -> Ret + 'static,
+            Ret: IntoPhalcom,
+            $($name: FromPhalcom,)*
+        {
+            const ARITY: usize = $len;
+
+            #[allow(unused_variables)]
+            fn call_host(&self, vm: &mut VM, args: &[Value]) -> PhResult<Value> {
+                (self)($($name::from_phalcom(vm, args[$index])?),*).into_phalcom(vm)
+            }
+        }
+    };
+}
+
+host_fn!(0;);
+host_fn!(1; A 0);
+host_fn!(2; A 0, B 1);
+host_fn!(3; A 0, B 1, C 2);
+host_fn!(4; A 0, B 1, C 2, D 3);
+host_fn!(5; A 0, B 1, C 2, D 3, E 4);
+host_fn!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+host_fn!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+host_fn!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+impl VM {
+    /// Wraps `call` as a `HostFunction` value. `arity` is checked on every
+    /// call when present.
+    pub(crate) fn alloc_host_function(&mut self, arity: Option<usize>, call: Rc<RawHostFn>) -> Value {
+        let class = self.host_function_class();
+        let index = self.host.functions.len();
+        self.host.functions.push(HostFunction { arity, call });
+        let mut instance = InstanceObject::new(class, 1);
+        instance.slots[0] = Value::int(index as i64);
+        Value::obj(self.heap.alloc(Object::Instance(instance)))
+    }
+
+    fn host_function_class(&mut self) -> ClassId {
+        if let Some(class) = self.host.class {
+            return class;
+        }
+        let object = self.universe.classes.object_class;
+        let class = self.create_single_class("HostFunction", Some(object));
+        let metaclass = self.create_single_class("HostFunction.class", Some(self.heap.class(object).class));
+        self.heap.class_mut(metaclass).set_class(self.universe.classes.metaclass_class);
+        self.heap.class_mut(class).set_class(metaclass);
+        self.heap.class_mut(class).field_count = 1;
+
+        for arity in 0..=MAX_HOST_ARITY {
+            let kind = SignatureKind::Method(arity as u8);
+            let selector = self.interner.intern(&encode_selector("call", &vec![None; arity], kind));
+            let method = MethodObject::new_primitive(selector, kind, host_function_call, class);
+            let method = self.heap.alloc(Object::Method(Box::new(method)));
+            self.heap.class_mut(class).add_method(selector, method);
+        }
+        self.finalize_class_base_names(class);
+        self.world_version += 1;
+        self.host.class = Some(class);
+        class
+    }
+}
+
+/// Signature: `HostFunction::call(…)` at every arity up to
+/// [`MAX_HOST_ARITY`] — runs the registered closure.
+///
+/// # Errors
+///
+/// [`RuntimeError::Arity`] when the call's argument count is not the
+/// function's declared arity; otherwise whatever the closure returns.
+fn host_function_call(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let index = receiver
+        .as_obj()
+        .and_then(|id| vm.heap.as_instance(id))
+        .and_then(|instance| instance.slots[0].as_int())
+        .ok_or_else(|| RuntimeError::Internal("HostFunction without a function index".into()))?;
+    let function = &vm.host.functions[index as usize];
+    if let Some(arity) = function.arity
+        && arity != args.len()
+    {
+        return Err(RuntimeError::Arity {
+            signature: "call",
+            expected: arity,
+            found: args.len(),
+        }
+        .into());
+    }
+    let call = Rc::clone(&function.call);
+    call(vm, args)
+}
@@ -0,0 +1,287 @@
+//! A stable facade for embedding Phalcom in a Rust host.
+//!
+//! [`Engine`] owns a [`VM`] and the module its [`Engine::eval`] snippets run
+//! in, and exposes the handful of operations a host needs without touching
+//! VM internals: evaluate source, load a module, look up a global or export,
+//! call a Phalcom callable with Rust arguments, and register Rust closures as
+//! functions `.ph` code can call.
+//!
+//! ```ignore
+//! let mut engine = Engine::new();
+//! engine.register_fn("square", |x: f64| x * x)?;
+//! let area: f64 = engine.eval("square(1.5) * 4")?;
+//! let add = engine.eval::<Handle>("|a, b| a + b")?;
+//! let sum: i64 = engine.call(&add, (2, 3))?;
+//! ```
+//!
+//! **Values.** Arguments and results cross the boundary through
+//! [`IntoPhalcom`]/[`FromPhalcom`] (`convert.rs`). A value the host keeps past
+//! the call that produced it must be held as a [`Handle`], which roots it for
+//! the collector until dropped (`handle.rs`); a bare [`Value`] is only valid
+//! until the VM next runs.
+//!
+//! **Errors** come back as the [`PhError`] the VM raised; nothing is printed.
+//! Execution state is unwound after every failed entry, so the engine stays
+//! usable.
+//!
+//! [`Engine::vm_mut`] is the escape hatch to the full VM API. Anything done
+//! through it — including collecting — must respect the same rule: only
+//! handles survive.
+
+mod convert;
+mod handle;
+mod host;
+
+pub use convert::{FromPhalcom, IntoArgs, IntoPhalcom};
+pub use handle::Handle;
+pub(crate) use host::HostState;
+pub use host::{HostFn, MAX_HOST_ARITY, RawHostFn};
+
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use crate::compiler::lib::{CompilerError, UnitKind};
+use crate::error::{PhError, PhResult, RuntimeError};
+use crate::heap::{ModuleKind, ObjRef, Object, RuntimeExportRef};
+use crate::method::{SignatureKind, encode_selector};
+use crate::modules::ModuleExecutionContext;
+use crate::modules::compile::{EntrySelection, ProgramCompiler};
+use crate::value::Value;
+use crate::vm::{VM, VmLimits};
+
+/// An embedded Phalcom runtime.
+pub struct Engine {
+    vm: VM,
+    /// The module [`Self::eval`] runs in and host functions are bound in.
+    module: ObjRef,
+    context: ModuleExecutionContext,
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Engine {
+    /// An engine whose imports resolve relative to the current directory.
+    pub fn new() -> Self {
+        Self::with_root(std::env::current_dir().unwrap_or_default())
+    }
+
+    /// An engine whose imports resolve relative to `root`, inside the project
+    /// that owns it if there is one.
+    pub fn with_root(root: PathBuf) -> Self {
+        let mut vm = VM::new();
+        let context = ModuleExecutionContext::new(root.clone(), "main");
+        let module = vm.create_module_with_id(context.session_id.clone(), ModuleKind::Module, "main", &root.display().to_string());
+        Self { vm, module, context }
+    }
+
+    /// The underlying VM.
+    pub fn vm(&self) -> &VM {
+        &self.vm
+    }
+
+    /// The underlying VM, for anything this facade does not cover.
+    pub fn vm_mut(&mut self) -> &mut VM {
+        &mut self.vm
+    }
+
+    /// Installs sandbox limits ([`VM::set_limits`]).
+    pub fn set_limits(&mut self, limits: VmLimits) {
+        self.vm.set_limits(limits);
+    }
+
+    /// Evaluates `source` in the engine's module and converts its value.
+    ///
+    /// Snippets share one scope, as REPL cells do: a top-level `let` is a
+    /// global later snippets and [`Self::global`] can read. A snippet ending
+    /// in a statement evaluates to `None`.
+    ///
+    /// # Errors
+    ///
+    /// A parse or compile error, a failed import, the raised runtime error,
+    /// or a failing conversion to `T`.
+    pub fn eval<T: FromPhalcom>(&mut self, source: &str) -> PhResult<T> {
+        let source = if source.ends_with('\n') { source.to_owned() } else { format!("{source}\n") };
+        let program = phalcom_ast::parse_source(&source, 0).map_err(|err| PhError::Compile(CompilerError::Parse(err)))?;
+        self.context.process_cell_dependencies(&mut self.vm, self.module, &program)?;
+        let closure = self
+            .vm
+            .compile_closure_as_with_bindings(self.module, &source, UnitKind::Repl, Some(self.context.bindings.clone()))?;
+
+        let frame = self
+            .vm
+            .new_call_frame(closure, crate::frame::CallContext::Module { module: self.module }, 0, 0, None);
+        let result = self.vm.push_frame(frame).and_then(|()| self.vm.run());
+        self.vm.unwind_cell();
+        T::from_phalcom(&mut self.vm, result?)
+    }
+
+    /// Compiles, links and initializes `entry` with its imports, returning a
+    /// handle to the entry module for [`Self::export`].
+    ///
+    /// The module is compiled on its own: globals of the engine's module,
+    /// host functions included, are not in its scope. Pass them in as
+    /// arguments.
+    ///
+    /// # Errors
+    ///
+    /// A compile or link error, or an initializer's runtime error.
+    pub fn load(&mut self, entry: EntrySelection) -> PhResult<Handle> {
+        let program = ProgramCompiler::compile_entry_selection(entry)?;
+        let result = self.vm.run_compiled(&program);
+        if result.is_err() {
+            self.vm.unwind_cell();
+        }
+        result?;
+        let module = self
+            .vm
+            .module_registry
+            .get(&program.entry)
+            .ok_or_else(|| RuntimeError::Internal(format!("loaded module {} missing from registry", program.entry)))?
+            .object;
+        Ok(Handle::new(&self.vm, Value::obj(module)))
+    }
+
+    /// [`Self::load`] for inline module source.
+    ///
+    /// # Errors
+    ///
+    /// As for [`Self::load`].
+    pub fn load_source(&mut self, source: &str) -> PhResult<Handle> {
+        self.load(EntrySelection::Inline(source.into()))
+    }
+
+    /// Reads global `name` of the engine's module.
+    ///
+    /// # Errors
+    ///
+    /// [`RuntimeError::UndefinedVariable`] if it is not bound, or a failing
+    /// conversion to `T`.
+    pub fn global<T: FromPhalcom>(&mut self, name: &str) -> PhResult<T> {
+        let symbol = self.vm.get_or_intern(name);
+        let value = self
+            .vm
+            .heap
+            .module(self.module)
+            .get(symbol)
+            .ok_or_else(|| RuntimeError::UndefinedVariable { name: name.to_owned() })?;
+        T::from_phalcom(&mut self.vm, value)
+    }
+
+    /// Binds global `name` of the engine's module to `value`, replacing any
+    /// earlier binding.
+    ///
+    /// # Errors
+    ///
+    /// A failing conversion, or the module's global table being full.
+    pub fn set_global(&mut self, name: &str, value: impl IntoPhalcom) -> PhResult<()> {
+        let value = value.into_phalcom(&mut self.vm)?;
+        let symbol = self.vm.get_or_intern(name);
+        self.vm.define_global(self.module, symbol, value)?;
+        Ok(())
+    }
+
+    /// Reads export `name` of a module returned by [`Self::load`].
+    ///
+    /// # Errors
+    ///
+    /// [`RuntimeError::Type`] if `module` is not a module,
+    /// [`RuntimeError::UndefinedVariable`] if it exports no `name`, or a
+    /// failing conversion to `T`.
+    pub fn export<T: FromPhalcom>(&mut self, module: &Handle, name: &str) -> PhResult<T> {
+        let module = module
+            .value()
+            .as_obj()
+            .filter(|&id| matches!(self.vm.heap.get(id), Object::Module(_)))
+            .ok_or(RuntimeError::Type {
+                expected: "Module",
+                found: module.value().type_name(),
+            })?;
+        let symbol = self.vm.get_or_intern(name);
+        let value = match self.vm.heap.module(module).export(symbol) {
+            Some(RuntimeExportRef::Binding(binding)) => self.vm.heap.module(binding.module).get_by_slot(binding.slot as usize),
+            Some(RuntimeExportRef::Module(module)) => Some(Value::obj(module)),
+            None => None,
+        }
+        .ok_or_else(|| RuntimeError::UndefinedVariable { name: name.to_owned() })?;
+        T::from_phalcom(&mut self.vm, value)
+    }
+
+    /// Calls `function` — anything answering `call(…)`: a closure, a bound
+    /// method, a host function — with `args`, and converts its result.
+    ///
+    /// # Errors
+    ///
+    /// A failing argument conversion, the raised runtime error, or a failing
+    /// conversion to `R`.
+    pub fn call<R: FromPhalcom>(&mut self, function: &Handle, args: impl IntoArgs) -> PhResult<R> {
+        let args = args.into_args(&mut self.vm)?;
+        let selector = encode_selector("call", &vec![None; args.len()], SignatureKind::Method(args.len() as u8));
+        self.send(function, &selector, args)
+    }
+
+    /// Sends the message `selector` — in encoded form, `"at(_)"`,
+    /// `"insert(_,at:)"` — to `receiver` with `args`, and converts its result.
+    ///
+    /// # Errors
+    ///
+    /// As for [`Self::call`].
+    pub fn send<R: FromPhalcom>(&mut self, receiver: &Handle, selector: &str, args: impl IntoArgs) -> PhResult<R> {
+        let args = args.into_args(&mut self.vm)?;
+        let selector = self.vm.get_or_intern(selector);
+        let (stack_len, frames_len) = (self.vm.stack.len(), self.vm.frames.len());
+        let result = self.vm.send_dynamic(receiver.value(), selector, &args);
+        self.vm.unwind_to(stack_len, frames_len);
+        R::from_phalcom(&mut self.vm, result?)
+    }
+
+    /// Roots `value` as a [`Handle`].
+    ///
+    /// # Errors
+    ///
+    /// A failing conversion.
+    pub fn handle(&mut self, value: impl IntoPhalcom) -> PhResult<Handle> {
+        let value = value.into_phalcom(&mut self.vm)?;
+        Ok(Handle::new(&self.vm, value))
+    }
+
+    /// Binds global `name` to a host function running `function`, callable
+    /// from every later [`Self::eval`] as `name(…)`.
+    ///
+    /// Arguments are converted to the closure's parameter types and its
+    /// result back; a call with the wrong number of arguments raises
+    /// [`RuntimeError::Arity`] without running it.
+    ///
+    /// # Errors
+    ///
+    /// The module's global table being full.
+    pub fn register_fn<Args, F: HostFn<Args>>(&mut self, name: &str, function: F) -> PhResult<Handle> {
+        let call: Rc<RawHostFn> = Rc::new(move |vm: &mut VM, args: &[Value]| function.call_host(vm, args));
+        self.bind_host_function(name, Some(F::ARITY), call)
+    }
+
+    /// [`Self::register_fn`] for a closure over raw [`Value`]s, which takes
+    /// any number of arguments up to [`MAX_HOST_ARITY`] and checks them itself.
+    ///
+    /// # Errors
+    ///
+    /// As for [`Self::register_fn`].
+    pub fn register_raw_fn(&mut self, name: &str, function: impl Fn(&mut VM, &[Value]) -> PhResult<Value> + 'static) -> PhResult<Handle> {
+        self.bind_host_function(name, None, Rc::new(function))
+    }
+
+    fn bind_host_function(&mut self, name: &str, arity: Option<usize>, call: Rc<RawHostFn>) -> PhResult<Handle> {
+        let function = self.vm.alloc_host_function(arity, call);
+        let symbol = self.vm.get_or_intern(name);
+        self.vm.define_global(self.module, symbol, function)?;
+        Ok(Handle::new(&self.vm, function))
+    }
+
+    /// Number of live [`Handle`]s rooted in this engine's VM.
+    pub fn handle_count(&self) -> usize {
+        self.vm.host.roots.len()
+    }
+}
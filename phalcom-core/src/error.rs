@@ -35,6 +35,14 @@ pub enum PhError {
     StrError(&'static str),
 }
 
+impl PhError {
+    /// Whether this is a [`RuntimeError::LimitExceeded`], which no `.ph`
+    /// handler may intercept.
+    pub fn is_limit_exceeded(&self) -> bool {
+        matches!(self, PhError::Runtime(RuntimeError::LimitExceeded { .. }))
+    }
+}
+
 impl From<ModuleInitializationError> for PhError {
     fn from(err: ModuleInitializationError) -> Self {
         PhError::ModuleInitialization(Box::new(err))
@@ -235,6 +243,42 @@ pub enum RuntimeError {
         limit: usize,
     },
 
+    /// A sandbox limit from [`VmLimits`](crate::vm::VmLimits) was hit: the
+    /// fuel budget, the heap cap or the deadline.
+    ///
+    /// Unlike [`Self::DepthExceeded`] this is **uncatchable** — `on`/`catch`
+    /// and a `Fiber#try` boundary let it pass — so untrusted code cannot
+    /// swallow the limit that is stopping it and carry on. `ensure` cleanups
+    /// are skipped for the same reason.
+    #[error("{limit} limit exceeded ({budget} {}); the sandbox stopped the computation", limit.unit())]
+    LimitExceeded {
+        /// Which limit tripped.
+        limit: crate::vm::ResourceLimit,
+        /// The configured budget, in [`ResourceLimit::unit`](crate::vm::ResourceLimit::unit)s.
+        budget: u64,
+    },
+
+    /// A sandboxed VM ([`VmLimits::allow_host_modules`](crate::vm::VmLimits::allow_host_modules)
+    /// off) was asked to load a host-access module.
+    #[error("import of `{module}` is not permitted in this sandbox")]
+    CapabilityDenied {
+        /// The refused module's identity.
+        module: String,
+    },
+
+    /// A hot reload ([`VM::reload_module`](crate::vm::VM::reload_module))
+    /// would change a live class's shape in a way its existing instances or
+    /// subclasses cannot follow. The running module is left untouched.
+    #[error("cannot reload `{module}`: class `{class}` {reason}")]
+    IncompatibleReload {
+        /// The reloaded module's identity.
+        module: String,
+        /// The class whose new definition was refused.
+        class: String,
+        /// What changed, e.g. ``field `x` was removed or moved``.
+        reason: String,
+    },
+
     /// The compact Value metadata cannot represent another Option wrapper.
     /// This is a physical representation overflow, not an ordinary semantic
     /// nesting limit.
@@ -104,6 +104,33 @@ pub struct CallFrame {
     pub home_frame_token: Option<FrameToken>,
     /// Optional layout check required by a transplanted bytecode Method.
     pub(crate) foreign_receiver_guard: Option<ForeignReceiverGuard>,
+    /// The part this activation plays in a Runtime-tier interception chain
+    /// (`vm/intercept.rs`), or `None` for an ordinary activation.
+    pub(crate) interception: Option<InterceptRole>,
+}
+
+/// Marks a [`CallFrame`] that an intercepted send entered.
+///
+/// `attempt` is set on a step entered by `Invocation#attempt()`: its result —
+/// or an error unwinding through it — reaches the caller as an `Ok`/`Err`
+/// instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum InterceptRole {
+    /// An interceptor's `aroundSend(_)` body. Sends made above it bypass
+    /// interception.
+    Hook { attempt: bool },
+    /// The intercepted method itself, entered at the end of the chain. Sends
+    /// made above it are intercepted again.
+    Method { attempt: bool },
+}
+
+impl InterceptRole {
+    /// Whether the step settles into a `Result` when it ends.
+    pub(crate) fn attempt(self) -> bool {
+        match self {
+            InterceptRole::Hook { attempt } | InterceptRole::Method { attempt } => attempt,
+        }
+    }
 }
 
 impl CallFrame {
@@ -119,6 +146,7 @@ impl CallFrame {
             generation: 0,
             home_frame_token: None,
             foreign_receiver_guard: None,
+            interception: None,
         }
     }
 
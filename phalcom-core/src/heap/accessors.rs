@@ -13,6 +13,7 @@ use crate::heap::RecordObject;
 use crate::heap::StringObject;
 use crate::heap::TupleObject;
 use crate::heap::Upvalue;
+use crate::heap::{FinalizationRegistryObject, WeakMapObject, WeakRefObject};
 use crate::method::MethodObject;
 
 use super::object::{BoundMethodFamilyObject, BoundMethodObject, FamilyObject, MethodFamilyObject, SelectorObject, SelectorPatternObject};
@@ -122,6 +123,24 @@ impl Heap {
         }
     }
 
+    /// Handles of every instance whose class is one of `classes`.
+    pub(crate) fn instances_of(&self, classes: &[ClassId]) -> Vec<ObjRef> {
+        self.objects
+            .iter()
+            .filter(|(_, object)| matches!(object, Object::Instance(instance) if classes.contains(&instance.class)))
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// Every class row whose superclass is one of `classes`.
+    pub(crate) fn subclasses_of(&self, classes: &[ClassId]) -> Vec<ClassId> {
+        self.objects
+            .iter()
+            .filter(|(_, object)| matches!(object, Object::Class(class) if class.superclass.is_some_and(|parent| classes.contains(&parent))))
+            .map(|(id, _)| id)
+            .collect()
+    }
+
     /// Borrows the [`MethodObject`] behind `id`.
     ///
     /// # Panics
@@ -274,6 +293,65 @@ impl Heap {
         }
     }
 
+    /// Returns the [`WeakRefObject`] behind `id`, or `None` if it is not an
+    /// [`Object::WeakRef`].
+    pub fn as_weak_ref(&self, id: ObjRef) -> Option<&WeakRefObject> {
+        match self.objects.get(id) {
+            Some(Object::WeakRef(weak_ref)) => Some(weak_ref),
+            _ => None,
+        }
+    }
+
+    /// Borrows the [`WeakMapObject`] behind `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is stale or does not refer to an [`Object::WeakMap`].
+    pub fn weak_map(&self, id: ObjRef) -> &WeakMapObject {
+        match self.get(id) {
+            Object::WeakMap(map) => map,
+            _ => panic!("ObjRef {id:?} is not an Object::WeakMap"),
+        }
+    }
+
+    /// Mutably borrows the [`WeakMapObject`] behind `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is stale or does not refer to an [`Object::WeakMap`].
+    pub fn weak_map_mut(&mut self, id: ObjRef) -> &mut WeakMapObject {
+        match self.get_mut(id) {
+            Object::WeakMap(map) => map,
+            _ => panic!("ObjRef {id:?} is not an Object::WeakMap"),
+        }
+    }
+
+    /// Borrows the [`FinalizationRegistryObject`] behind `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is stale or does not refer to an
+    /// [`Object::FinalizationRegistry`].
+    pub fn finalization_registry(&self, id: ObjRef) -> &FinalizationRegistryObject {
+        match self.get(id) {
+            Object::FinalizationRegistry(registry) => registry,
+            _ => panic!("ObjRef {id:?} is not an Object::FinalizationRegistry"),
+        }
+    }
+
+    /// Mutably borrows the [`FinalizationRegistryObject`] behind `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is stale or does not refer to an
+    /// [`Object::FinalizationRegistry`].
+    pub fn finalization_registry_mut(&mut self, id: ObjRef) -> &mut FinalizationRegistryObject {
+        match self.get_mut(id) {
+            Object::FinalizationRegistry(registry) => registry,
+            _ => panic!("ObjRef {id:?} is not an Object::FinalizationRegistry"),
+        }
+    }
+
     /// Borrows the [`MapObject`] behind `id`.
     ///
     /// # Panics
@@ -727,6 +805,7 @@ impl Heap {
     }
 
     pub fn as_typing_object_mut(&mut self, id: ObjRef) -> Option<&mut crate::heap::TypingObject> {
+        self.write_barrier(id);
         match self.objects.get_mut(id) {
             Some(Object::Typing(t)) => Some(t),
             _ => None,
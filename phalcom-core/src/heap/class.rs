@@ -72,6 +72,23 @@ pub struct ClassObject {
     /// Refusal flag for abstract classes (e.g. Number, Bool, Function, Option, Behavior)
     /// that cannot be allocated via generic InstanceObject::new (new_).
     pub is_abstract: bool,
+    /// Set by a `@mixin` declaration (`vm/mixin.rs`): the row is a bundle of
+    /// instance methods applied to hosts at their finalization — never a
+    /// superclass, never instantiated (`is_abstract` is set alongside).
+    pub is_mixin: bool,
+    /// Mixins this row composes directly, in declaration order: a host's
+    /// `@compose(...)` list, or a mixin's own mixin-valued `@mixin(...)`
+    /// arguments.
+    pub mixins: Vec<ClassId>,
+    /// Set by a `@protocol` declaration (`vm/protocol.rs`): the row only
+    /// names [`Self::requirements`] — never a superclass, a mixin or
+    /// instantiated (`is_abstract` is set alongside).
+    pub is_protocol: bool,
+    /// Selectors a mixin requires of every host it is applied to, checked
+    /// when a non-mixin host finalizes (`mixin.unsatisfied_requirement`); for
+    /// a protocol, the selectors a conforming object answers, its included
+    /// protocols' first.
+    pub requirements: Vec<Symbol>,
 }
 
 /// Walks `class` and its superclasses for a method bound to `selector`.
@@ -136,6 +153,10 @@ impl ClassObject {
             attributes_frozen: false,
             native_repr: false,
             is_abstract: false,
+            is_mixin: false,
+            mixins: Vec::new(),
+            is_protocol: false,
+            requirements: Vec::new(),
         }
     }
 
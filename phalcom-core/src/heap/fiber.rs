@@ -1,5 +1,6 @@
 use std::collections::{BTreeMap, HashSet};
 
+use crate::error::FrameRecord;
 use crate::frame::CallFrame;
 use crate::value::Value;
 
@@ -131,6 +132,11 @@ pub struct FiberObject {
     pub spawn_file: Option<crate::interner::Symbol>,
     /// Line number where this fiber was spawned (0 for root).
     pub spawn_line: u32,
+    /// Where this fiber's failure was raised, oldest frame first: recorded
+    /// at the fiber-floor capture when it fails, so a later
+    /// `Fiber#_$rethrow()` can stitch it under the frames of the fiber that
+    /// awaited it. Empty until the fiber fails.
+    pub failure_trace: Vec<FrameRecord>,
 }
 
 impl FiberObject {
@@ -157,6 +163,7 @@ impl FiberObject {
             seq: 0,
             spawn_file: None,
             spawn_line: 0,
+            failure_trace: Vec::new(),
         }
     }
 
@@ -182,6 +189,7 @@ impl FiberObject {
             seq: 0,
             spawn_file: None,
             spawn_line: 0,
+            failure_trace: Vec::new(),
         }
     }
 
@@ -209,6 +217,7 @@ impl FiberObject {
             seq: 1,
             spawn_file: None,
             spawn_line: 0,
+            failure_trace: Vec::new(),
         }
     }
 }
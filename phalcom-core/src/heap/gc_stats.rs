@@ -0,0 +1,119 @@
+//! Collector pause accounting behind `System.gcStats`.
+//!
+//! Every stop of the mutator for collector work — a full mark-sweep, one
+//! incremental marking slice, the atomic finish of an incremental cycle's
+//! marking, or one incremental sweep slice — is one **pause**. The VM times
+//! each pause around the heap call ([`crate::vm::VM::force_gc`],
+//! `VM::service_gc_safepoint`) and records it here; the heap itself never
+//! reads a clock.
+//!
+//! Pauses are bucketed into a fixed, roughly logarithmic histogram
+//! ([`PAUSE_BUCKET_BOUNDS_MICROS`]) so the report costs the same whether a
+//! program paused ten times or ten million.
+
+use std::time::Duration;
+
+/// Inclusive upper bounds, in microseconds, of every histogram bucket but the
+/// last. A pause longer than the final bound lands in one overflow bucket.
+pub const PAUSE_BUCKET_BOUNDS_MICROS: [u64; 12] = [10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 100_000];
+
+/// What a recorded pause did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcPauseKind {
+    /// A whole stop-the-world mark-sweep (`System.gc`, stress, or a
+    /// threshold collection with incremental marking off).
+    Full,
+    /// One budgeted incremental marking slice that left gray objects behind.
+    Slice,
+    /// The atomic end of an incremental cycle's marking: root rescan,
+    /// remaining marking and the weak phase.
+    Finish,
+    /// One budgeted incremental sweep slice.
+    Sweep,
+}
+
+/// Running totals for every collector pause since the heap was created.
+#[derive(Debug, Clone, Default)]
+pub struct GcStats {
+    /// Completed stop-the-world collections.
+    pub full_collections: u64,
+    /// Completed incremental cycles (each ended by one [`GcPauseKind::Finish`]).
+    pub incremental_cycles: u64,
+    /// Incremental marking slices that did not finish their cycle.
+    pub mark_slices: u64,
+    /// Incremental sweep slices.
+    pub sweep_slices: u64,
+    /// Objects reclaimed across all collections.
+    pub objects_swept: u64,
+    /// Sum of every pause.
+    pub total_pause: Duration,
+    /// The longest single pause.
+    pub max_pause: Duration,
+    /// The most recent pause.
+    pub last_pause: Duration,
+    histogram: [u64; PAUSE_BUCKET_BOUNDS_MICROS.len() + 1],
+}
+
+impl GcStats {
+    /// Records one pause of `kind` that lasted `pause` and swept `swept`
+    /// objects (zero for marking).
+    pub fn record(&mut self, kind: GcPauseKind, pause: Duration, swept: usize) {
+        match kind {
+            GcPauseKind::Full => self.full_collections += 1,
+            GcPauseKind::Slice => self.mark_slices += 1,
+            GcPauseKind::Finish => self.incremental_cycles += 1,
+            GcPauseKind::Sweep => self.sweep_slices += 1,
+        }
+        self.objects_swept += swept as u64;
+        self.total_pause += pause;
+        self.max_pause = self.max_pause.max(pause);
+        self.last_pause = pause;
+        let micros = u64::try_from(pause.as_micros()).unwrap_or(u64::MAX);
+        let bucket = PAUSE_BUCKET_BOUNDS_MICROS
+            .iter()
+            .position(|bound| micros <= *bound)
+            .unwrap_or(PAUSE_BUCKET_BOUNDS_MICROS.len());
+        self.histogram[bucket] += 1;
+    }
+
+    /// Total number of recorded pauses.
+    pub fn pauses(&self) -> u64 {
+        self.histogram.iter().sum()
+    }
+
+    /// `(upper bound in µs, count)` for every bucket in ascending order; the
+    /// overflow bucket's bound is `None`.
+    pub fn histogram(&self) -> impl Iterator<Item = (Option<u64>, u64)> + '_ {
+        PAUSE_BUCKET_BOUNDS_MICROS
+            .iter()
+            .map(|bound| Some(*bound))
+            .chain(std::iter::once(None))
+            .zip(self.histogram.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pauses_land_in_their_bucket_and_overflow() {
+        let mut stats = GcStats::default();
+        stats.record(GcPauseKind::Slice, Duration::from_micros(10), 0);
+        stats.record(GcPauseKind::Finish, Duration::from_micros(11), 7);
+        stats.record(GcPauseKind::Full, Duration::from_secs(1), 3);
+        stats.record(GcPauseKind::Sweep, Duration::from_micros(30), 2);
+
+        let buckets: Vec<_> = stats.histogram().collect();
+        assert_eq!(buckets.len(), PAUSE_BUCKET_BOUNDS_MICROS.len() + 1);
+        assert_eq!(buckets[0], (Some(10), 1));
+        assert_eq!(buckets[1], (Some(25), 1));
+        assert_eq!(buckets[2], (Some(50), 1));
+        assert_eq!(buckets[buckets.len() - 1], (None, 1));
+        assert_eq!(stats.pauses(), 4);
+        assert_eq!((stats.full_collections, stats.incremental_cycles, stats.mark_slices, stats.sweep_slices), (1, 1, 1, 1));
+        assert_eq!(stats.objects_swept, 12);
+        assert_eq!(stats.max_pause, Duration::from_secs(1));
+        assert_eq!(stats.last_pause, Duration::from_micros(30));
+    }
+}
@@ -34,6 +34,7 @@ mod bytes;
 mod class;
 mod closure;
 mod fiber;
+mod gc_stats;
 mod instance;
 mod list;
 mod map;
@@ -51,12 +52,14 @@ mod trace;
 mod tuple;
 pub mod typing;
 mod upvalue;
+mod weak;
 
 pub use block::BlockObject;
 pub use bytes::BytesObject;
 pub use class::{ClassObject, is_strict_subclass, lookup_method_in_hierarchy, lookup_method_with_definer};
 pub use closure::ClosureObject;
 pub use fiber::{FiberObject, FiberResumeMode, FiberStatus};
+pub use gc_stats::{GcPauseKind, GcStats, PAUSE_BUCKET_BOUNDS_MICROS};
 pub use instance::InstanceObject;
 pub use list::ListObject;
 pub use map::MapObject;
@@ -77,6 +80,7 @@ pub use trace::{trace_frame, trace_object};
 pub use tuple::TupleObject;
 pub use typing::{TypingObject, TypingPayload};
 pub use upvalue::Upvalue;
+pub use weak::{FinalizationCell, FinalizationRegistryObject, WeakMapObject, WeakRefObject};
 
 use slotmap::{Key, KeyData, SecondaryMap, SlotMap, new_key_type};
 
@@ -129,6 +133,41 @@ pub struct Heap {
     gc_stress_interval: Option<usize>,
     /// Safepoints elapsed since the last stress-triggered collection.
     gc_stress_safepoints: usize,
+    /// `FinalizationRegistry`s that the last collection gave new pending
+    /// cleanups and that have no drain fiber queued yet. Drained by
+    /// [`crate::vm::VM::force_gc`] straight after [`Self::collect`] returns, so
+    /// it never outlives the collection that filled it.
+    pending_cleanups: Vec<ObjRef>,
+    /// The incremental marking cycle in progress, if any. `None` between
+    /// cycles, which is also what makes the write barrier in [`Self::get_mut`]
+    /// free when the collector is idle.
+    cycle: Option<Box<MarkCycle>>,
+    /// Every handle in allocation order, compacted by each sweep. A `SlotMap`
+    /// cannot resume an iteration part-way, so this log is what lets the
+    /// sweep run a slice at a time (8 bytes per object).
+    allocations: Vec<ObjRef>,
+    /// The sweep in progress, if any: marking has finished and the dead are
+    /// being freed a slice at a time.
+    sweep: Option<Box<SweepState>>,
+    /// Work per incremental slice — objects plus edges traced when marking,
+    /// objects examined when sweeping. `0` disables incremental collection, so
+    /// a latched `gc_pending` runs a full stop-the-world collection.
+    mark_slice_budget: usize,
+    /// Whether `PHALCOM_GC_SLICE` was set explicitly. If so, a stress-due
+    /// safepoint advances the incremental cycle by one slice instead of
+    /// collecting fully — the lane that exercises the write barrier.
+    stress_slices: bool,
+    /// Pause accounting reported through `System.gcStats`.
+    stats: GcStats,
+    /// Estimated bytes held by live objects ([`Object::approx_bytes`]): grown
+    /// by every allocation, re-counted over the survivors at each finish.
+    live_bytes: usize,
+    /// [`VmLimits::max_heap_bytes`](crate::vm::VmLimits::max_heap_bytes).
+    byte_limit: Option<usize>,
+    /// Set when [`Self::live_bytes`] passes [`Self::byte_limit`]. Always
+    /// latched together with `gc_pending`, so the idle safepoint path checks
+    /// one flag, not two (Invariant L).
+    over_byte_limit: bool,
 }
 
 const INITIAL_GC_THRESHOLD: usize = 4096;
@@ -148,6 +187,15 @@ const GC_UNPRODUCTIVE_GROW_FACTOR: f64 = 4.0;
 /// Reclaimed fraction below which a collection is judged unproductive and the
 /// next threshold grows by [`GC_UNPRODUCTIVE_GROW_FACTOR`] instead.
 const GC_LOW_YIELD: f64 = 0.10;
+/// Slice budget when `PHALCOM_GC_SLICE` is unset, in units of tracing work
+/// (one per object, one per edge). A unit is a few nanoseconds, so a slice
+/// stays well under a millisecond however large the live heap is.
+const DEFAULT_MARK_SLICE_BUDGET: usize = 16_384;
+/// Heap growth, relative to the threshold that started an incremental cycle,
+/// past which the cycle stops slicing and finishes at the next safepoint: the
+/// mutator is allocating faster than marking keeps up, and finishing bounds
+/// the heap instead of the pause.
+const GC_RUNAWAY_FACTOR: usize = 2;
 
 fn parse_gc_stress_interval(raw: Option<&str>) -> Option<usize> {
     let raw = raw?;
@@ -166,6 +214,37 @@ fn gc_stress_interval_from_env() -> Option<usize> {
     parse_gc_stress_interval(raw.as_deref())
 }
 
+fn parse_mark_slice_budget(raw: Option<&str>) -> Option<usize> {
+    let raw = raw?.trim();
+    match raw.parse::<usize>() {
+        Ok(budget) => Some(budget),
+        Err(_) => panic!("PHALCOM_GC_SLICE must be 0 (stop-the-world) or a positive per-slice work budget; got `{raw}`"),
+    }
+}
+
+fn mark_slice_budget_from_env() -> Option<usize> {
+    let raw = std::env::var("PHALCOM_GC_SLICE").ok();
+    parse_mark_slice_budget(raw.as_deref())
+}
+
+/// What the collector owes the mutator at one dispatch safepoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SafepointWork {
+    /// Nothing to do.
+    Idle,
+    /// One full stop-the-world collection.
+    Full,
+    /// One incremental marking slice, starting a cycle first if none is
+    /// running.
+    Slice,
+    /// One incremental sweep slice.
+    Sweep,
+    /// The heap passed its byte cap: one full collection, then a
+    /// [`RuntimeError::LimitExceeded`](crate::error::RuntimeError::LimitExceeded)
+    /// if the survivors alone are still over it.
+    ByteLimit,
+}
+
 impl Default for Heap {
     fn default() -> Self {
         Self::new()
@@ -175,35 +254,82 @@ impl Default for Heap {
 impl Heap {
     /// Creates an empty heap.
     pub fn new() -> Self {
+        let slice_budget = mark_slice_budget_from_env();
         Self {
             objects: SlotMap::with_key(),
             next_gc: INITIAL_GC_THRESHOLD,
             gc_pending: false,
             gc_stress_interval: gc_stress_interval_from_env(),
             gc_stress_safepoints: 0,
+            pending_cleanups: Vec::new(),
+            cycle: None,
+            allocations: Vec::new(),
+            sweep: None,
+            mark_slice_budget: slice_budget.unwrap_or(DEFAULT_MARK_SLICE_BUDGET),
+            stress_slices: slice_budget.is_some_and(|budget| budget > 0),
+            stats: GcStats::default(),
+            live_bytes: 0,
+            byte_limit: None,
+            over_byte_limit: false,
         }
     }
 
     fn insert(&mut self, object: Object) -> ObjRef {
+        self.live_bytes += object.approx_bytes();
         let id = self.objects.insert(object);
+        self.allocations.push(id);
         if self.objects.len() >= self.next_gc {
             self.gc_pending = true; // LATCH ONLY — never collect here (Invariant L)
         }
+        if self.byte_limit.is_some_and(|limit| self.live_bytes > limit) {
+            // Likewise a latch, not a collection: the cap may be overshot by
+            // what the current instruction still allocates before it returns
+            // to the safepoint (see `vm/limits.rs`).
+            self.over_byte_limit = true;
+            self.gc_pending = true;
+        }
+        // Allocated gray, not black: the payload arrives already holding
+        // handles (`alloc_list(elements)`), possibly to objects the cycle has
+        // not reached and that the caller just took off a root.
+        if let Some(cycle) = self.cycle.as_deref_mut() {
+            cycle.shade(id);
+        }
         id
     }
 
+    /// The incremental write barrier, run on every mutable borrow of an
+    /// object.
+    ///
+    /// Every store of a handle into a heap object — `SetField`, list and map
+    /// mutation, module globals, fiber stacks parked on a switch — goes
+    /// through [`Self::get_mut`] (there is no interior mutability in the
+    /// arena, ADR-0009), so this one door covers them all. A black object that
+    /// is about to be written is turned back to gray and re-traced later
+    /// (a Steele-style backward barrier): whatever it holds at the end of the
+    /// cycle is marked, so the tri-color invariant — no black object points at
+    /// a white one — holds at the finish.
+    ///
+    /// A re-grayed object is counted again when it is re-traced, so its
+    /// current size comes off the cycle's byte count here.
+    #[inline]
+    fn write_barrier(&mut self, id: ObjRef) {
+        if let Some(cycle) = self.cycle.as_deref_mut()
+            && cycle.regray(id)
+            && let Some(object) = self.objects.get(id)
+        {
+            cycle.marked_bytes = cycle.marked_bytes.saturating_sub(object.approx_bytes());
+        }
+    }
+
     /// Returns whether a garbage collection is pending.
     pub fn gc_pending(&self) -> bool {
         self.gc_pending
     }
 
-    /// Returns whether collection is due at this legal VM safepoint.
-    ///
-    /// Normal threshold latching and stress cadence are independent: stress
-    /// never collects from `alloc`, so Invariant L and the single-safepoint
-    /// collector contract remain unchanged.
-    pub(crate) fn gc_due_at_safepoint(&mut self) -> bool {
-        let stress_due = self.gc_stress_interval.is_some_and(|interval| {
+    /// Advances the stress cadence by one safepoint; answers whether stress
+    /// is due at this one.
+    fn stress_tick(&mut self) -> bool {
+        self.gc_stress_interval.is_some_and(|interval| {
             self.gc_stress_safepoints += 1;
             if self.gc_stress_safepoints >= interval {
                 self.gc_stress_safepoints = 0;
@@ -211,8 +337,34 @@ impl Heap {
             } else {
                 false
             }
-        });
-        self.gc_pending || stress_due
+        })
+    }
+
+    /// Decides what collector work is due at this legal VM safepoint.
+    ///
+    /// Normal threshold latching and stress cadence are independent: stress
+    /// never collects from `alloc`, so Invariant L and the single-safepoint
+    /// collector contract remain unchanged. A running incremental cycle is
+    /// advanced at every safepoint until it finishes.
+    pub(crate) fn safepoint_work(&mut self) -> SafepointWork {
+        let stress_due = self.stress_tick();
+        if stress_due && !self.stress_slices {
+            SafepointWork::Full
+        } else if self.sweep.is_some() {
+            SafepointWork::Sweep
+        } else if self.cycle.is_some() || stress_due {
+            SafepointWork::Slice
+        } else if self.gc_pending {
+            if self.over_byte_limit {
+                SafepointWork::ByteLimit
+            } else if self.mark_slice_budget == 0 {
+                SafepointWork::Full
+            } else {
+                SafepointWork::Slice
+            }
+        } else {
+            SafepointWork::Idle
+        }
     }
 
     /// Allocates `object` and returns its fresh [`ObjRef`].
@@ -260,6 +412,9 @@ impl Heap {
     /// aliasing-safe seam `Bytes::copyInto_(_,_)` needs for its
     /// source→destination memmove (`impl/bytes.md` §2.6). Returns `None` if
     /// the handles are equal, stale, or either is not an [`Object::Bytes`].
+    ///
+    /// Skips the write barrier: octets are not handles, so no store through
+    /// this borrow can hide an object from the collector.
     pub fn bytes_pair_mut(&mut self, a: ObjRef, b: ObjRef) -> Option<(&mut BytesObject, &mut BytesObject)> {
         match self.objects.get_disjoint_mut([a, b])? {
             [Object::Bytes(first), Object::Bytes(second)] => Some((first, second)),
@@ -298,6 +453,7 @@ impl Heap {
     ///
     /// Panics if `id` is stale or was never allocated in this heap.
     pub fn get_mut(&mut self, id: ObjRef) -> &mut Object {
+        self.write_barrier(id);
         self.objects.get_mut(id).unwrap_or_else(|| panic!("dangling ObjRef {id:?}"))
     }
 
@@ -340,6 +496,9 @@ impl Heap {
             Some(Object::Tuple(_)) => "Tuple",
             Some(Object::Record(_)) => "Record",
             Some(Object::Range(_)) => "Range",
+            Some(Object::WeakRef(_)) => "WeakRef",
+            Some(Object::WeakMap(_)) => "WeakMap",
+            Some(Object::FinalizationRegistry(_)) => "FinalizationRegistry",
             Some(Object::Family(_)) => "Family",
             Some(Object::Selector(_)) => "Selector",
             Some(Object::SelectorPattern(_)) => "SelectorPattern",
@@ -367,6 +526,24 @@ impl Heap {
         }
     }
 
+    /// Takes the registries the last [`Self::collect`] gave new pending
+    /// cleanups. Each is already flagged `scheduled`; the caller owes it one
+    /// drain fiber on the ready queue.
+    pub(crate) fn take_pending_cleanups(&mut self) -> Vec<ObjRef> {
+        std::mem::take(&mut self.pending_cleanups)
+    }
+
+    /// Overrides the `PHALCOM_GC_STRESS` cadence — **test scaffolding**.
+    ///
+    /// Integration tests cannot set the environment variable per-test without
+    /// racing every other test in the binary, so they set the interval on the
+    /// VM they own instead.
+    #[doc(hidden)]
+    pub fn set_gc_stress_interval_for_test(&mut self, interval: Option<usize>) {
+        self.gc_stress_interval = interval;
+        self.gc_stress_safepoints = 0;
+    }
+
     /// Number of live objects currently in the arena.
     ///
     /// The collection trigger keys on this (DEC-GC-B option A: count-based, not
@@ -418,51 +595,395 @@ impl Heap {
     ///
     /// Runs **no finalizers** and resurrects nothing: there is zero `impl Drop` on
     /// the object graph (Invariant M4).
+    ///
+    /// Weak holders (`heap/weak.rs`) get one extra phase between mark and sweep:
+    /// `WeakMap` values are marked by an ephemeron fixpoint, dead `WeakRef`
+    /// targets and `WeakMap` keys are cleared, and a `FinalizationRegistry`
+    /// whose target died only *records* the held value — its callback is
+    /// scheduled afterwards by the VM ([`Self::take_pending_cleanups`]), never
+    /// run here.
+    ///
+    /// This is the stop-the-world path (`System.gc`, `PHALCOM_GC_STRESS`,
+    /// `PHALCOM_GC_SLICE=0`). Threshold-triggered collections instead run
+    /// **incrementally**: [`Self::begin_cycle`], a [`Self::mark_slice`] per
+    /// safepoint, [`Self::finish_cycle`], then a [`Self::sweep_slice`] per
+    /// safepoint — the same marking, weak phase and sweep, spread across
+    /// safepoints behind the write barrier in [`Self::get_mut`]. Still
+    /// non-moving, so nothing above changes.
     pub fn collect(&mut self, roots: &[ObjRef]) -> usize {
-        // DEC-GC-D option B: a per-collection local, not a persistent field — no
-        // stale-marks invariant to hold between cycles.
-        let mut marked: SecondaryMap<ObjRef, ()> = SecondaryMap::new();
-        let mut gray: Vec<ObjRef> = Vec::new();
+        // A sweep still in flight only frees objects that were already dead;
+        // finish it so the fresh mark below starts from a settled arena.
+        self.sweep_with_budget(None);
+        // A full collection supersedes any incremental cycle in flight: its
+        // marks may be stale (floating garbage), and a forced collection
+        // promises to reclaim everything unreachable *now*.
+        self.cycle = Some(Box::default());
+        self.finish_cycle(roots);
+        self.sweep_with_budget(None).0
+    }
+
+    /// Whether an incremental marking cycle is in progress.
+    pub fn is_marking(&self) -> bool {
+        self.cycle.is_some()
+    }
+
+    /// Whether an incremental sweep is in progress.
+    pub fn is_sweeping(&self) -> bool {
+        self.sweep.is_some()
+    }
 
+    /// Starts an incremental marking cycle by shading `roots` gray.
+    ///
+    /// Marking then proceeds in budgeted [`Self::mark_slice`]s between which
+    /// the mutator runs; [`Self::finish_cycle`] ends it. The mark state lives
+    /// on the heap only for the cycle's duration (DEC-GC-D still holds
+    /// *between* cycles: nothing is carried from one cycle to the next).
+    pub fn begin_cycle(&mut self, roots: &[ObjRef]) {
+        let mut cycle = Box::<MarkCycle>::default();
         for root in roots {
             // A root may be stale only if the VM's own bookkeeping is corrupt;
-            // `contains_key` keeps `collect` total rather than panicking mid-sweep.
-            if self.objects.contains_key(*root) && marked.insert(*root, ()).is_none() {
-                gray.push(*root);
+            // `contains_key` keeps marking total rather than panicking.
+            if self.objects.contains_key(*root) {
+                cycle.shade(*root);
             }
         }
+        self.cycle = Some(cycle);
+    }
+
+    /// Traces gray objects until the configured slice budget of work is spent.
+    /// One object's trace is indivisible, so a single huge `List` or `Map`
+    /// can overrun the budget by its own size. Returns
+    /// `true` once the cycle is ready to finish — the gray set is empty, or
+    /// the heap has outgrown the cycle ([`GC_RUNAWAY_FACTOR`]).
+    ///
+    /// Gray may refill afterwards through the write barrier or allocation;
+    /// [`Self::finish_cycle`] drains whatever is left.
+    pub fn mark_slice(&mut self) -> bool {
+        let Some(cycle) = self.cycle.as_deref_mut() else { return true };
+        let budget = self.mark_slice_budget.max(1);
+        let drained = mark_worklist(&self.objects, cycle, Some(budget));
+        drained || self.objects.len() >= self.next_gc.saturating_mul(GC_RUNAWAY_FACTOR)
+    }
+
+    /// Ends the current cycle's marking atomically: shades `roots` again,
+    /// drains the gray set and runs the weak phase, leaving the dead to
+    /// [`Self::sweep_slice`].
+    ///
+    /// `roots` must be the **complete** root set *now*, not the one the cycle
+    /// began with. Roots carry no write barrier — the operand stack and frames
+    /// change freely between slices — so this rescan is what catches a white
+    /// object whose only remaining reference moved onto the stack. The pause is
+    /// proportional to the roots plus the objects written to during the
+    /// cycle, not to the whole live heap.
+    pub fn finish_cycle(&mut self, roots: &[ObjRef]) {
+        let mut cycle = self.cycle.take().unwrap_or_default();
+        for root in roots {
+            if self.objects.contains_key(*root) {
+                cycle.shade(*root);
+            }
+        }
+        let MarkCycle { gray, dirty, .. } = &mut *cycle;
+        gray.append(dirty);
 
         let objects = &self.objects;
-        while let Some(id) = gray.pop() {
-            let Some(object) = objects.get(id) else { continue };
-            // Push children straight onto the worklist. `objects` is reborrowed
-            // immutably inside the closure alongside `object`'s own borrow (both
-            // shared, so they coexist); `marked`/`gray` are locals, disjoint from
-            // the arena. This is what keeps marking allocation-free per object.
-            trace_object(object, &mut |child| {
-                if objects.contains_key(child) && marked.insert(child, ()).is_none() {
-                    gray.push(child);
+        mark_worklist(objects, &mut cycle, None);
+
+        // Ephemeron fixpoint: a `WeakMap` value becomes reachable only once its
+        // key has been marked by some *other* path. Marking one value can mark
+        // another table's key (or discover a new table), so repeat until a pass
+        // marks nothing. `weak.maps` may grow inside the loop, hence the index.
+        loop {
+            let mut grew = false;
+            let mut index = 0;
+            while index < cycle.weak.maps.len() {
+                if let Some(Object::WeakMap(map)) = objects.get(cycle.weak.maps[index]) {
+                    for (key, value) in map.entries() {
+                        if !cycle.shades.contains_key(key) {
+                            continue;
+                        }
+                        if let Some(child) = value.gc_obj_ref()
+                            && objects.contains_key(child)
+                            && !cycle.shades.contains_key(child)
+                        {
+                            cycle.shade(child);
+                            grew = true;
+                        }
+                    }
+                }
+                index += 1;
+            }
+            if !grew {
+                break;
+            }
+            mark_worklist(objects, &mut cycle, None);
+        }
+
+        // Weak phase. Every holder below was itself marked, so it survives the
+        // sweep; only what it points *at* may be dying. Nothing here runs user
+        // code or marks anything new (Invariant M4). A holder the barrier
+        // re-grayed was traced, and so recorded, more than once.
+        let MarkCycle {
+            shades, mut weak, marked_bytes, ..
+        } = *cycle;
+        // Everything unshaded is about to be swept, so the survivors' count is
+        // the live estimate; whatever is allocated during the sweep adds on top.
+        self.live_bytes = marked_bytes;
+        self.over_byte_limit = self.byte_limit.is_some_and(|limit| marked_bytes > limit);
+        weak.dedup();
+        for id in weak.refs {
+            if let Some(Object::WeakRef(weak_ref)) = self.objects.get_mut(id)
+                && weak_ref.target.is_some_and(|target| !shades.contains_key(target))
+            {
+                weak_ref.target = None;
+            }
+        }
+        for id in weak.maps {
+            if let Some(Object::WeakMap(map)) = self.objects.get_mut(id) {
+                map.retain_live_keys(|key| shades.contains_key(key));
+            }
+        }
+        for id in weak.registries {
+            let Some(Object::FinalizationRegistry(registry)) = self.objects.get_mut(id) else { continue };
+            let before = registry.pending.len();
+            // Rebuilt in order rather than `swap_remove`d, so callbacks fire in
+            // registration order among targets that die in the same cycle.
+            for mut cell in std::mem::take(&mut registry.cells) {
+                if cell.token.is_some_and(|token| !shades.contains_key(token)) {
+                    cell.token = None;
                 }
-            });
+                if shades.contains_key(cell.target) {
+                    registry.cells.push(cell);
+                } else {
+                    registry.pending.push_back(cell.held);
+                }
+            }
+            if registry.pending.len() > before && !registry.scheduled {
+                registry.scheduled = true;
+                self.pending_cleanups.push(id);
+            }
         }
 
-        let before = self.objects.len();
-        self.objects.retain(|id, _| marked.contains_key(id));
-        let live = self.objects.len();
+        // The gray set is empty, so every shaded object is black. Objects
+        // allocated from here on land past `end` and are never examined.
+        self.sweep = Some(Box::new(SweepState {
+            shades,
+            cursor: 0,
+            end: self.allocations.len(),
+            survivors: Vec::new(),
+            before: self.objects.len(),
+            swept: 0,
+        }));
+    }
+
+    /// Frees at most the configured slice budget of the current sweep's
+    /// candidates. Returns the number of objects freed and whether the sweep
+    /// is complete.
+    ///
+    /// Unlike marking, sweeping needs no barrier: every white object was
+    /// unreachable at the finish, weak holders have already forgotten it, and
+    /// nothing can reach it again — it merely occupies its slot until freed.
+    pub fn sweep_slice(&mut self) -> (usize, bool) {
+        let budget = self.mark_slice_budget.max(1);
+        if self.objects.len() >= self.next_gc.saturating_mul(GC_RUNAWAY_FACTOR) {
+            self.sweep_with_budget(None)
+        } else {
+            self.sweep_with_budget(Some(budget))
+        }
+    }
+
+    fn sweep_with_budget(&mut self, budget: Option<usize>) -> (usize, bool) {
+        let Some(sweep) = self.sweep.as_deref_mut() else { return (0, true) };
+        let stop = budget.map_or(sweep.end, |budget| sweep.end.min(sweep.cursor + budget));
+        let mut swept = 0;
+        for &id in &self.allocations[sweep.cursor..stop] {
+            if sweep.shades.contains_key(id) {
+                sweep.survivors.push(id);
+            } else if self.objects.remove(id).is_some() {
+                swept += 1;
+            }
+        }
+        sweep.cursor = stop;
+        sweep.swept += swept;
+        if stop < sweep.end {
+            return (swept, false);
+        }
+
+        let sweep = self.sweep.take().expect("sweep in progress");
+        let mut survivors = sweep.survivors;
+        survivors.extend_from_slice(&self.allocations[sweep.end..]);
+        self.allocations = survivors;
+        let live = sweep.before - sweep.swept;
         // Scale the next threshold by how much this cycle actually reclaimed.
         // A collection that frees almost nothing has just traced the whole live
         // set for no benefit, and growing by a flat 1.5× schedules the same
         // wasted trace again almost immediately (F11).
-        let reclaimed_fraction = if before > 0 { (before - live) as f64 / before as f64 } else { 1.0 };
+        let reclaimed_fraction = if sweep.before > 0 { sweep.swept as f64 / sweep.before as f64 } else { 1.0 };
         let factor = if reclaimed_fraction < GC_LOW_YIELD {
             GC_UNPRODUCTIVE_GROW_FACTOR
         } else {
             GC_GROW_FACTOR
         };
         self.next_gc = std::cmp::max(INITIAL_GC_THRESHOLD, (live as f64 * factor) as usize);
-        self.gc_pending = false;
-        before - live
+        // Re-latch for whatever the mutator allocated while the cycle ran.
+        self.gc_pending = self.objects.len() >= self.next_gc || self.over_byte_limit;
+        (swept, true)
+    }
+
+    /// Records one collector pause — see [`GcStats::record`].
+    pub fn record_gc_pause(&mut self, kind: GcPauseKind, pause: std::time::Duration, swept: usize) {
+        self.stats.record(kind, pause, swept);
+    }
+
+    /// Pause accounting since this heap was created.
+    pub fn gc_stats(&self) -> &GcStats {
+        &self.stats
+    }
+
+    /// Estimated bytes held by live objects — exact for the survivors of the
+    /// last collection, plus everything allocated since.
+    pub fn live_bytes(&self) -> usize {
+        self.live_bytes
+    }
+
+    /// Sets the byte cap `alloc` latches against; `None` removes it.
+    pub(crate) fn set_byte_limit(&mut self, limit: Option<usize>) {
+        self.byte_limit = limit;
+        self.over_byte_limit = limit.is_some_and(|limit| self.live_bytes > limit);
+        self.gc_pending |= self.over_byte_limit;
+    }
+
+    /// Whether the live estimate is over the byte cap.
+    pub(crate) fn over_byte_limit(&self) -> bool {
+        self.over_byte_limit
+    }
+
+    /// Overrides the `PHALCOM_GC_SLICE` budget — **test scaffolding**, for the
+    /// same reason as [`Self::set_gc_stress_interval_for_test`]. A positive
+    /// budget also makes stress-due safepoints slice rather than collect.
+    #[doc(hidden)]
+    pub fn set_gc_slice_budget_for_test(&mut self, budget: usize) {
+        self.mark_slice_budget = budget;
+        self.stress_slices = budget > 0;
+    }
+}
+
+/// Tri-color shade of a marked object; an object absent from
+/// [`MarkCycle::shades`] is white.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shade {
+    /// Reached, children not yet traced — on the gray worklist.
+    Gray,
+    /// Reached and traced.
+    Black,
+}
+
+/// The mark state of one collection, full or incremental.
+#[derive(Default)]
+struct MarkCycle {
+    shades: SecondaryMap<ObjRef, Shade>,
+    gray: Vec<ObjRef>,
+    /// Black objects the write barrier turned gray again. Held back until
+    /// [`Heap::finish_cycle`] rather than re-traced by the next slice: a hot
+    /// `List` appended to at every step would otherwise be re-traced whole
+    /// at every slice, never just once.
+    dirty: Vec<ObjRef>,
+    weak: WeakHolders,
+    /// [`Object::approx_bytes`] summed over every object traced so far.
+    marked_bytes: usize,
+}
+
+impl MarkCycle {
+    /// Shades a white object gray; a no-op for one already reached.
+    fn shade(&mut self, id: ObjRef) {
+        if !self.shades.contains_key(id) {
+            self.shades.insert(id, Shade::Gray);
+            self.gray.push(id);
+        }
+    }
+
+    /// The barrier's half: turns a black object gray again, deferring its
+    /// re-trace to the finish. Returns whether it did.
+    fn regray(&mut self, id: ObjRef) -> bool {
+        if let Some(shade) = self.shades.get_mut(id)
+            && *shade == Shade::Black
+        {
+            *shade = Shade::Gray;
+            self.dirty.push(id);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// A sweep between [`Heap::finish_cycle`] and its last [`Heap::sweep_slice`].
+struct SweepState {
+    /// The finished cycle's marks: shaded means live.
+    shades: SecondaryMap<ObjRef, Shade>,
+    /// Next index into [`Heap::allocations`] to examine.
+    cursor: usize,
+    /// One past the last candidate — the allocation log's length at the finish.
+    end: usize,
+    /// Live handles already examined, in order; becomes the compacted log.
+    survivors: Vec<ObjRef>,
+    /// Arena size at the finish, for the next threshold.
+    before: usize,
+    /// Objects freed so far.
+    swept: usize,
+}
+
+/// The weak holders marking ran into, gathered on the way so the weak phase
+/// visits only them instead of rescanning the arena.
+#[derive(Default)]
+struct WeakHolders {
+    refs: Vec<ObjRef>,
+    maps: Vec<ObjRef>,
+    registries: Vec<ObjRef>,
+}
+
+impl WeakHolders {
+    fn dedup(&mut self) {
+        for holders in [&mut self.refs, &mut self.maps, &mut self.registries] {
+            let mut seen = std::collections::HashSet::new();
+            holders.retain(|id| seen.insert(*id));
+        }
+    }
+}
+
+/// Traces gray objects — until `budget` units of work (one per object, one
+/// per edge) are spent, or until the worklist is empty when `budget` is
+/// `None` — marking everything strongly reachable and noting each weak holder
+/// it passes. Returns whether the worklist drained.
+fn mark_worklist(objects: &SlotMap<ObjRef, Object>, cycle: &mut MarkCycle, budget: Option<usize>) -> bool {
+    let mut work = 0;
+    while budget.is_none_or(|budget| work < budget) {
+        let Some(id) = cycle.gray.pop() else { return true };
+        let Some(object) = objects.get(id) else { continue };
+        if let Some(shade) = cycle.shades.get_mut(id) {
+            *shade = Shade::Black;
+        }
+        cycle.marked_bytes += object.approx_bytes();
+        match object {
+            Object::WeakRef(_) => cycle.weak.refs.push(id),
+            Object::WeakMap(_) => cycle.weak.maps.push(id),
+            Object::FinalizationRegistry(_) => cycle.weak.registries.push(id),
+            _ => {}
+        }
+        // Push children straight onto the worklist. `objects` is borrowed
+        // immutably alongside `object`'s own borrow (both shared, so they
+        // coexist); the cycle is disjoint from the arena. This is what keeps
+        // marking allocation-free per object.
+        let MarkCycle { shades, gray, .. } = cycle;
+        trace_object(object, &mut |child| {
+            work += 1;
+            if objects.contains_key(child) && !shades.contains_key(child) {
+                shades.insert(child, Shade::Gray);
+                gray.push(child);
+            }
+        });
+        work += 1;
     }
+    cycle.gray.is_empty()
 }
 
 #[cfg(test)]
@@ -474,32 +995,75 @@ mod gc_stress_tests {
         heap.gc_stress_interval = interval;
         heap.gc_stress_safepoints = 0;
         heap.gc_pending = false;
+        heap.stress_slices = false;
         heap
     }
 
+    fn due(heap: &mut Heap) -> bool {
+        heap.safepoint_work() != SafepointWork::Idle
+    }
+
     #[test]
     fn stress_interval_one_is_due_at_every_safepoint() {
         let mut heap = heap_with_stress(Some(1));
-        assert!(heap.gc_due_at_safepoint());
-        assert!(heap.gc_due_at_safepoint());
+        assert!(due(&mut heap));
+        assert!(due(&mut heap));
     }
 
     #[test]
     fn stress_interval_three_repeats_exactly() {
         let mut heap = heap_with_stress(Some(3));
-        assert!(!heap.gc_due_at_safepoint());
-        assert!(!heap.gc_due_at_safepoint());
-        assert!(heap.gc_due_at_safepoint());
-        assert!(!heap.gc_due_at_safepoint());
-        assert!(!heap.gc_due_at_safepoint());
-        assert!(heap.gc_due_at_safepoint());
+        assert!(!due(&mut heap));
+        assert!(!due(&mut heap));
+        assert!(due(&mut heap));
+        assert!(!due(&mut heap));
+        assert!(!due(&mut heap));
+        assert!(due(&mut heap));
     }
 
     #[test]
     fn ordinary_gc_pending_wins_before_stress_interval() {
         let mut heap = heap_with_stress(Some(100));
         heap.gc_pending = true;
-        assert!(heap.gc_due_at_safepoint());
+        assert!(due(&mut heap));
+    }
+
+    #[test]
+    fn latched_collection_slices_unless_incremental_is_off() {
+        let mut heap = heap_with_stress(None);
+        heap.gc_pending = true;
+        assert_eq!(heap.safepoint_work(), SafepointWork::Slice);
+        heap.mark_slice_budget = 0;
+        assert_eq!(heap.safepoint_work(), SafepointWork::Full);
+    }
+
+    #[test]
+    fn stress_collects_fully_unless_slices_were_requested() {
+        let mut heap = heap_with_stress(Some(1));
+        assert_eq!(heap.safepoint_work(), SafepointWork::Full);
+        heap.stress_slices = true;
+        assert_eq!(heap.safepoint_work(), SafepointWork::Slice);
+    }
+
+    #[test]
+    fn running_cycle_advances_at_every_safepoint() {
+        let mut heap = heap_with_stress(None);
+        let garbage = heap.alloc_list(Vec::new());
+        heap.begin_cycle(&[]);
+        assert_eq!(heap.safepoint_work(), SafepointWork::Slice);
+        heap.finish_cycle(&[]);
+        assert_eq!(heap.safepoint_work(), SafepointWork::Sweep);
+        assert_eq!(heap.sweep_slice(), (1, true));
+        assert!(heap.try_get(garbage).is_none());
+        assert_eq!(heap.safepoint_work(), SafepointWork::Idle);
+    }
+
+    #[test]
+    fn slice_configuration_parser_is_strict() {
+        assert_eq!(parse_mark_slice_budget(None), None);
+        assert_eq!(parse_mark_slice_budget(Some("0")), Some(0));
+        assert_eq!(parse_mark_slice_budget(Some(" 64 ")), Some(64));
+        assert!(std::panic::catch_unwind(|| parse_mark_slice_budget(Some("-1"))).is_err());
     }
 
     #[test]
@@ -6,7 +6,7 @@
 //! per-object `RefCell`s ([ADR-0009](../../../docs/adr/accepted/0009-handle-arena-heap.md)).
 
 use crate::error::{PhResult, RuntimeError};
-use crate::heap::ObjRef;
+use crate::heap::{ClassId, ObjRef};
 use crate::interner::Symbol;
 use crate::modules::{BindingRef, RuntimeLinkedRead};
 use crate::value::Value;
@@ -85,6 +85,12 @@ pub struct ModuleObject {
     pub exports: HashMap<Symbol, RuntimeExportRef>,
     /// Module metadata retained from source.
     pub metadata: Option<Arc<phalcom_modules::ModuleMetadata>>,
+    /// Methods this module's `extend` blocks add, keyed by the class side
+    /// that holds them and the selector (`vm/extension.rs`).
+    pub extensions: HashMap<(ClassId, Symbol), ObjRef>,
+    /// Directly imported modules whose extensions sends compiled here also
+    /// see, after this module's own, in import order.
+    pub extension_sources: Vec<ObjRef>,
 }
 
 impl ModuleObject {
@@ -114,6 +120,8 @@ impl ModuleObject {
             linked_reads: Vec::new(),
             exports: HashMap::new(),
             metadata: None,
+            extensions: HashMap::new(),
+            extension_sources: Vec::new(),
         }
     }
 
@@ -11,6 +11,7 @@ use crate::heap::RecordObject;
 use crate::heap::StringObject;
 use crate::heap::TupleObject;
 use crate::heap::Upvalue;
+use crate::heap::{FinalizationRegistryObject, WeakMapObject, WeakRefObject};
 use crate::interner::Symbol;
 use crate::method::MethodObject;
 use crate::value::Value;
@@ -123,6 +124,21 @@ pub enum Object {
     /// compact two-Value-plus-flag layout. Progression and equality semantics
     /// are deferred.
     Range(RangeObject),
+    /// A weak handle to one heap object ([`WeakRefObject`]). The target is
+    /// **not** a tracing edge; the collector clears it once nothing else keeps
+    /// the referent alive (`heap/weak.rs`).
+    WeakRef(WeakRefObject),
+    /// An identity-keyed ephemeron table ([`WeakMapObject`]): each value is
+    /// reachable only while its key is. Traced by the collector's ephemeron
+    /// fixpoint, not by `trace_object`.
+    ///
+    /// **Boxed** — see [`Object::Class`].
+    WeakMap(Box<WeakMapObject>),
+    /// Weakly-watched targets plus a cleanup callback run on a scheduled fiber
+    /// after their collection ([`FinalizationRegistryObject`]).
+    ///
+    /// **Boxed** — see [`Object::Class`].
+    FinalizationRegistry(Box<FinalizationRegistryObject>),
     /// A bound `::` method reference — the callable **Family** value
     /// produced by `obj::name` (Open) or `obj::#name(...)` (Pinned)
     /// ([`FamilyObject`], selectors.md §3, U16-Open, U16-Pinned, [ADR-0047]).
@@ -185,6 +201,37 @@ pub enum Object {
     Typing(Box<super::typing::TypingObject>),
 }
 
+impl Object {
+    /// A shallow estimate of the memory this object owns: its arena slot plus
+    /// the payload buffers that grow with a program's data. Behaviour rows,
+    /// code and reflection descriptors count as their slot (and box) only —
+    /// they are bounded by the program text, not by what it computes.
+    ///
+    /// This is the measure behind
+    /// [`VmLimits::max_heap_bytes`](crate::vm::VmLimits::max_heap_bytes); it
+    /// is cheap enough to run on every allocation and every marked object.
+    pub fn approx_bytes(&self) -> usize {
+        const VALUE: usize = std::mem::size_of::<Value>();
+        // Key, value and the stored hash of an `IndexMap` entry.
+        const MAP_ENTRY: usize = 2 * VALUE + std::mem::size_of::<u64>();
+        let payload = match self {
+            Object::Instance(instance) => instance.slots.len() * VALUE,
+            Object::Str(string) => string.as_str().len(),
+            Object::List(list) => list.len() * VALUE,
+            Object::Map(map) | Object::Set(map) => map.len() * MAP_ENTRY,
+            Object::Bytes(bytes) => bytes.len(),
+            Object::Tuple(tuple) => tuple.len() * VALUE,
+            Object::Record(record) => record.len() * (VALUE + std::mem::size_of::<Symbol>()),
+            Object::WeakMap(map) => map.len() * MAP_ENTRY,
+            Object::LargeInt(int) => int.bits().div_ceil(8) as usize,
+            Object::Closure(closure) => closure.upvalues.len() * std::mem::size_of::<ObjRef>(),
+            Object::Fiber(fiber) => fiber.stack.len() * VALUE + fiber.frames.len() * std::mem::size_of::<crate::frame::CallFrame>(),
+            _ => 0,
+        };
+        std::mem::size_of::<Object>() + payload
+    }
+}
+
 /// A bound `::` method reference (selectors.md §3, U16-Open, U16-Pinned).
 ///
 /// Reached through [`Value::obj`](crate::value::Value::obj) exactly as an [`Object::List`] is — there
@@ -91,6 +91,9 @@ pub fn trace_object(obj: &Object, push: &mut impl FnMut(ObjRef)) {
             for slot in class.static_slots.iter() {
                 trace_value(*slot, push);
             }
+            for mixin in &class.mixins {
+                push(*mixin);
+            }
             // Retained `@attribute` instances (M-ATTR-ROOT). `name` is a Rust
             // `String`, `field_slots`/`base_names` hold only `Symbol`s — none are edges.
             for attr in &class.attributes {
@@ -117,6 +120,22 @@ pub fn trace_object(obj: &Object, push: &mut impl FnMut(ObjRef)) {
             for attr in &method.attributes {
                 trace_value(*attr, push);
             }
+            // The Runtime-tier chain: attribute instances (also in
+            // `attributes`) and their `aroundSend(_)` methods.
+            for (attr, hook) in &method.interceptors {
+                trace_value(*attr, push);
+                push(*hook);
+            }
+            // A multimethod dispatcher's cases and their dispatch classes;
+            // the cache only repeats cases.
+            if let Some(multi) = &method.multi {
+                for (classes, case) in &multi.cases {
+                    for &class in classes.iter() {
+                        push(class);
+                    }
+                    push(*case);
+                }
+            }
         }
         Object::Module(module) => {
             if let Some(closure) = module.closure {
@@ -143,6 +162,13 @@ pub fn trace_object(obj: &Object, push: &mut impl FnMut(ObjRef)) {
                     crate::modules::RuntimeLinkedRead::Module(obj) => push(*obj),
                 }
             }
+            for (&(holder, _), &method) in &module.extensions {
+                push(holder);
+                push(method);
+            }
+            for source in &module.extension_sources {
+                push(*source);
+            }
         }
         Object::Closure(closure) => {
             push(closure.module);
@@ -229,6 +255,26 @@ pub fn trace_object(obj: &Object, push: &mut impl FnMut(ObjRef)) {
                 trace_value(upper, push);
             }
         }
+        // Weak edges are deliberately absent here: a `WeakRef` target and a
+        // `WeakMap` entry are visited (or not) by `Heap::collect`'s own weak
+        // phase, after the strong mark. Listing them here would make them
+        // strong and the containers pointless.
+        Object::WeakRef(_) => {}
+        Object::WeakMap(_) => {}
+        // The callback, drain entry and every held value are strong; targets
+        // and unregister tokens are weak (`heap/weak.rs`).
+        Object::FinalizationRegistry(registry) => {
+            trace_value(registry.callback, push);
+            if let Some(drain) = registry.drain {
+                push(drain);
+            }
+            for cell in &registry.cells {
+                trace_value(cell.held, push);
+            }
+            for held in &registry.pending {
+                trace_value(*held, push);
+            }
+        }
         Object::Family(family) => {
             trace_value(family.receiver, push);
             if let super::object::FamilySpec::Pattern(pattern) = family.spec {
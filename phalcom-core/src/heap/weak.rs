@@ -0,0 +1,165 @@
+//! Native weak-reference payloads: `WeakRef`, the ephemeron `WeakMap`, and
+//! `FinalizationRegistry`.
+//!
+//! All three are dedicated heap variants ([`crate::heap::Object::WeakRef`],
+//! [`crate::heap::Object::WeakMap`], [`crate::heap::Object::FinalizationRegistry`]),
+//! mirroring [`crate::heap::ListObject`] — **not** [`crate::heap::InstanceObject`]s.
+//! What makes them special is entirely on the collector's side
+//! ([`Heap::collect`](super::Heap::collect)):
+//!
+//! - a [`WeakRefObject`]'s target is **not an edge**; after marking, a target
+//!   that nothing else kept alive is cleared to `None`;
+//! - a [`WeakMapObject`] is an **ephemeron table**: a value is traced only once
+//!   its key has been proven live by some *other* path, iterated to a fixpoint,
+//!   so a value that refers back to its own key never keeps that key alive;
+//! - a [`FinalizationRegistryObject`] holds its cleanup callback and every held
+//!   value strongly, but each registered target weakly. A target found dead
+//!   moves its held value onto [`FinalizationRegistryObject::pending`]; the
+//!   callback itself runs **later**, on a scheduled fiber, never during the
+//!   sweep (Invariant M4 — the collector runs no user code and resurrects
+//!   nothing).
+//!
+//! Keys and targets are compared by **identity** ([`ObjRef`] equality), never
+//! by `==`/`hash`: dispatching a user `hash` from inside a collection would be
+//! exactly the user-code-during-sweep hazard this module exists to avoid, and
+//! identity is the only notion of equality a dead object can still answer.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::value::Value;
+
+use super::ObjRef;
+
+/// A single weak handle to a heap object.
+///
+/// `target` is `Some` while the referent is live and becomes `None` at the
+/// first collection that finds it otherwise unreachable. It is never reset to
+/// `Some` afterwards — a cleared `WeakRef` stays cleared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeakRefObject {
+    /// The weakly-held referent, or `None` once it has been collected.
+    pub target: Option<ObjRef>,
+}
+
+impl WeakRefObject {
+    /// Builds a weak reference to `target`.
+    pub fn new(target: ObjRef) -> Self {
+        Self { target: Some(target) }
+    }
+}
+
+/// An identity-keyed ephemeron table.
+///
+/// Not iterable and not sized from `.ph`: both would expose *when* the
+/// collector last ran, which is an implementation detail (the same reason
+/// ECMAScript's `WeakMap` has neither).
+#[derive(Debug, Clone, Default)]
+pub struct WeakMapObject {
+    entries: HashMap<ObjRef, Value>,
+}
+
+impl WeakMapObject {
+    /// Builds an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The value stored under `key`, if any.
+    pub fn get(&self, key: ObjRef) -> Option<Value> {
+        self.entries.get(&key).copied()
+    }
+
+    /// Whether `key` has an entry.
+    pub fn contains(&self, key: ObjRef) -> bool {
+        self.entries.contains_key(&key)
+    }
+
+    /// Stores `value` under `key`, replacing any previous entry.
+    pub fn insert(&mut self, key: ObjRef, value: Value) {
+        self.entries.insert(key, value);
+    }
+
+    /// Removes `key`'s entry, returning whether one existed.
+    pub fn remove(&mut self, key: ObjRef) -> bool {
+        self.entries.remove(&key).is_some()
+    }
+
+    /// Number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the table has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Every `(key, value)` entry, in unspecified order — the collector's
+    /// ephemeron pass.
+    pub fn entries(&self) -> impl Iterator<Item = (ObjRef, Value)> + '_ {
+        self.entries.iter().map(|(key, value)| (*key, *value))
+    }
+
+    /// Drops every entry whose key fails `live` — the post-mark sweep of dead
+    /// keys.
+    pub(crate) fn retain_live_keys(&mut self, mut live: impl FnMut(ObjRef) -> bool) {
+        self.entries.retain(|key, _| live(*key));
+    }
+}
+
+/// One `register(target, held, token)` record of a [`FinalizationRegistryObject`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FinalizationCell {
+    /// The weakly-watched object.
+    pub target: ObjRef,
+    /// The value handed to the cleanup callback once `target` dies. Traced
+    /// strongly — so a held value that reaches `target` keeps it alive forever
+    /// and the cell simply never fires.
+    pub held: Value,
+    /// The optional `unregister(_)` token, held **weakly**: a token nobody can
+    /// name any more can never be passed to `unregister`, so it is cleared
+    /// rather than retained.
+    pub token: Option<ObjRef>,
+}
+
+/// A registry of weakly-watched targets plus the callback to run, on a
+/// scheduled fiber, for each one that is collected.
+#[derive(Debug, Clone)]
+pub struct FinalizationRegistryObject {
+    /// The user cleanup callable, invoked with one held value per dead target.
+    pub callback: Value,
+    /// The zero-argument entry the VM wraps in a fiber and enqueues on the
+    /// ready queue when this registry has pending cleanups. Installed by the
+    /// `.ph` constructor (`FinalizationRegistry.new(_)`), which closes over
+    /// the registry itself.
+    pub drain: Option<ObjRef>,
+    /// Live registrations.
+    pub cells: Vec<FinalizationCell>,
+    /// Held values whose targets have died, awaiting the callback, oldest
+    /// first.
+    pub pending: VecDeque<Value>,
+    /// Whether a drain fiber is already on the ready queue — a second
+    /// collection before it runs appends to [`Self::pending`] without
+    /// scheduling another.
+    pub scheduled: bool,
+}
+
+impl FinalizationRegistryObject {
+    /// Builds an empty registry over `callback`.
+    pub fn new(callback: Value) -> Self {
+        Self {
+            callback,
+            drain: None,
+            cells: Vec::new(),
+            pending: VecDeque::new(),
+            scheduled: false,
+        }
+    }
+
+    /// Removes every cell registered under `token`, returning whether any was.
+    pub fn unregister(&mut self, token: ObjRef) -> bool {
+        let before = self.cells.len();
+        self.cells.retain(|cell| cell.token != Some(token));
+        self.cells.len() != before
+    }
+}
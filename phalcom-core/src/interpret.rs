@@ -67,9 +67,38 @@ impl VM {
 
     /// Parses and compiles one already-linked module with its closed namespace.
     pub fn compile_closure_as_with_bindings(&mut self, module: ObjRef, source: &str, kind: UnitKind, bindings: Option<CompileBindings>) -> PhResult<ObjRef> {
+        self.compile_closure_filtered(module, source, kind, bindings, None)
+    }
+
+    /// [`Self::compile_closure_as_with_bindings`] for the project module at
+    /// `path`: the parsed program is filtered by `cfg` first, so the compiler
+    /// sees the same declarations the analyzer and linker did.
+    pub fn compile_closure_with_cfg(
+        &mut self,
+        module: ObjRef,
+        source: &str,
+        bindings: Option<CompileBindings>,
+        path: &phalcom_modules::ModulePath,
+        cfg: &phalcom_modules::CfgContext,
+    ) -> PhResult<ObjRef> {
+        self.compile_closure_filtered(module, source, UnitKind::File, bindings, Some((path, cfg)))
+    }
+
+    fn compile_closure_filtered(
+        &mut self,
+        module: ObjRef,
+        source: &str,
+        kind: UnitKind,
+        bindings: Option<CompileBindings>,
+        cfg: Option<(&phalcom_modules::ModulePath, &phalcom_modules::CfgContext)>,
+    ) -> PhResult<ObjRef> {
         self.unit_kind = kind;
         let source_id = self.heap.module_mut(module).push_source(Arc::new(source.to_string()));
-        let program = parse_source(source, 0).map_err(|e| PhError::Compile(CompilerError::Parse(e)))?;
+        let mut program = parse_source(source, 0).map_err(|e| PhError::Compile(CompilerError::Parse(e)))?;
+        if let Some((path, cfg)) = cfg {
+            cfg.filter_program(path, &mut program)
+                .map_err(|e| PhError::Compile(CompilerError::Message(e.to_string())))?;
+        }
 
         let compiler = match bindings {
             Some(bindings) => Compiler::new_with_bindings(self, module, source_id, kind, Some(bindings)),
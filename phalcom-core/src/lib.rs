@@ -7,6 +7,8 @@ pub mod chunk;
 
 pub mod compiler;
 pub mod diagnostics;
+pub mod doc;
+pub mod embed;
 pub mod error;
 pub mod frame;
 pub mod heap;
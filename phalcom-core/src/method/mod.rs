@@ -7,7 +7,7 @@
 
 mod object;
 
-pub use object::{ArgumentView, CallOutcome, LegacyPrimitiveFn, MethodKind, MethodObject, PrimitiveFn};
+pub use object::{ArgumentView, CallOutcome, LegacyPrimitiveFn, MethodKind, MethodObject, MultiDispatch, PrimitiveFn};
 
 use crate::interner::Symbol;
 use phalcom_common::selector as common_selector;
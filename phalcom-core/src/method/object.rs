@@ -204,6 +204,31 @@ pub struct MethodObject {
     /// `@AttrName(...)` attaches have all run — further `__attach` calls are
     /// rejected (`attr.frozen`).
     pub attributes_frozen: bool,
+    /// The Runtime-tier interception chain (`aroundSend`), outermost first:
+    /// each attribute instance paired with its `aroundSend(_)` method, looked
+    /// up once when the holder class finished defining
+    /// ([`crate::vm::VM::compose_interceptors`]). Empty for an undecorated
+    /// method — the only thing a send to it reads.
+    pub interceptors: Vec<(Value, ObjRef)>,
+    /// The cases of a multimethod dispatcher (`vm/multimethod.rs`); `None`
+    /// for every other method. A send to the dispatcher runs the case it
+    /// selects instead of the dispatcher's own `kind`.
+    pub multi: Option<Box<MultiDispatch>>,
+}
+
+/// The cases behind a multimethod dispatcher, selected by the runtime
+/// classes of the receiver and every argument.
+#[derive(Debug, Clone, Default)]
+pub struct MultiDispatch {
+    /// Each of the holder's own cases' dispatch classes — the receiver's
+    /// position first, then one per argument — with the case method.
+    /// Inherited cases are looked up on selection.
+    pub cases: Vec<(Box<[ClassId]>, ObjRef)>,
+    /// The case selected for each tuple of runtime classes seen so far.
+    pub cache: std::collections::HashMap<Box<[ClassId]>, ObjRef>,
+    /// The `world_version` `cache` was filled under; a case added here or on
+    /// a superclass moves the world on and empties it.
+    pub version: u64,
 }
 
 impl MethodObject {
@@ -219,6 +244,8 @@ impl MethodObject {
             contracts: None,
             attributes: Vec::new(),
             attributes_frozen: false,
+            interceptors: Vec::new(),
+            multi: None,
         }
     }
 
@@ -1,12 +1,13 @@
 //! Program-level compiler seam for closed linked module plans.
 
 use super::artifact::ModuleMaterializationPlan;
+use super::package::PublishedPackage;
 use phalcom_modules::{
     BuiltinProject, BuiltinProjectSourceProvider, FilesystemSourceProvider, InterfaceBuilder, InterfaceError, LinkError, LinkedModule, LinkedProgram,
-    ModuleComponent, ModuleId, ModuleKind, ModuleLinker, ModulePath, ModuleResolutionError, ModuleResolver, ProjectError, ProjectUniverse, SourceError,
-    SourceId, SourceLocation, discover_owning_project,
+    ModuleComponent, ModuleId, ModuleKind, ModuleLinker, ModulePath, ModuleResolutionError, ModuleResolver, ProjectError, ProjectIdentity, ProjectLoadOptions,
+    ProjectUniverse, ResolvedPackageArtifact, ResolvedProjectId, SourceError, SourceId, SourceLocation, SourceProvider, discover_owning_project,
 };
-use phalcom_semantic::SemanticDiagnostic;
+use phalcom_semantic::{DeclarationFingerprint, InvalidationIndex, SemanticDiagnostic};
 use std::collections::{BTreeMap, HashSet};
 use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
@@ -19,6 +20,10 @@ pub enum EntrySelection {
     ModuleId(ModuleId),
     /// Compile a project rooted at directory with `project.toml`.
     Project(PathBuf),
+    /// Compile a named module of a project: `entry` is a dotted module path,
+    /// rooted at the project namespace or relative to it. Without one, the
+    /// manifest's `entry` is used, falling back to its `default_entry`.
+    ProjectEntry { root: PathBuf, entry: Option<String> },
     /// Compile a package directory containing `package.ph` and `main.ph`.
     Package(PathBuf),
     /// Compile a single module file path.
@@ -62,6 +67,9 @@ pub struct CompiledProgram {
     pub initialization_order: Vec<ModuleId>,
     /// Immutable semantic metadata bundle, if retained by build profile.
     pub semantic_metadata: Option<Arc<phalcom_type_meta::SemanticMetadataBundle>>,
+    /// Signature fingerprints of every non-builtin declaration, compared by
+    /// [`VM::reload_module`](crate::vm::VM::reload_module).
+    pub fingerprints: Arc<InvalidationIndex>,
 }
 
 /// Semantic diagnostics grouped by module.
@@ -149,27 +157,74 @@ pub struct AnalyzedProgram {
     pub entry: ModuleId,
 }
 
+impl AnalyzedProgram {
+    /// Exports the program's runtime-public semantic metadata.
+    pub fn metadata_bundle(&self) -> Result<phalcom_type_meta::SemanticMetadataBundle, phalcom_semantic::metadata::MetadataExportError> {
+        let exporter = phalcom_semantic::metadata::MetadataExporter::new(
+            self.semantic.store(),
+            Some(self.semantic.declarations()),
+            None,
+            None,
+            phalcom_type_meta::header::MetadataProfile::RuntimePublic,
+        )
+        .with_surfaces(
+            self.semantic
+                .surfaces()
+                .iter()
+                .filter(|(declaration, _)| declaration.module.project.as_resolved().is_some()),
+        );
+        exporter.build_bundle(&[])
+    }
+}
+
+/// Every module under one project's source root, analyzed for whole-project
+/// tooling such as `phalcom doc`.
+///
+/// A linked program only reaches what its entry imports, so a project whose
+/// modules are not all reachable from one entry is covered by several
+/// analyzed programs; each module appears in at least one of them.
+#[derive(Clone, Debug)]
+pub struct AnalyzedProject {
+    pub project_universe: Arc<ProjectUniverse>,
+    pub project: ResolvedProjectId,
+    /// Module IDs found under the source root, in source-path order.
+    pub modules: Vec<ModuleId>,
+    pub programs: Vec<AnalyzedProgram>,
+}
+
+impl AnalyzedProject {
+    /// Returns the analyzed program whose linked plan contains `module`.
+    pub fn program_for(&self, module: &ModuleId) -> Option<&AnalyzedProgram> {
+        self.programs.iter().find(|program| program.sources.contains_key(module))
+    }
+}
+
+/// A whole-project build: the programs that analyzed cleanly, plus every
+/// error met along the way.
+#[derive(Clone, Debug)]
+pub struct ProjectBuild {
+    pub project: AnalyzedProject,
+    pub errors: Vec<ProgramCompileError>,
+}
+
 /// Analyzer that coordinates discovery, linking, and whole-workspace semantic analysis.
 pub struct ProgramAnalyzer;
 
 impl ProgramAnalyzer {
-    /// Analyzes an entry selection into an [`AnalyzedProgram`].
+    /// Analyzes an entry selection into an [`AnalyzedProgram`], leaving any
+    /// `phalcom.lock` as it is.
     pub fn analyze_entry_selection(entry: EntrySelection) -> Result<AnalyzedProgram, ProgramCompileError> {
+        Self::analyze_entry_selection_with_options(entry, &ProjectLoadOptions::default())
+    }
+
+    /// [`Self::analyze_entry_selection`], loading projects under `options`.
+    pub fn analyze_entry_selection_with_options(entry: EntrySelection, options: &ProjectLoadOptions) -> Result<AnalyzedProgram, ProgramCompileError> {
         match entry {
             EntrySelection::ModuleId(entry_id) => Err(ProgramCompileError::Io(format!(
                 "EntrySelection::ModuleId({entry_id}) cannot be analyzed without an existing linked project universe"
             ))),
             EntrySelection::Project(root_dir) => {
-                let manifest_path = if root_dir.ends_with("project.toml") {
-                    root_dir
-                } else {
-                    root_dir.join("project.toml")
-                };
-                if !manifest_path.exists() {
-                    return Err(ProgramCompileError::Io(format!("project manifest not found at {}", manifest_path.display())));
-                }
-                let mut universe = ProjectUniverse::new();
-                let root_id = universe.load_root(&manifest_path)?;
+                let (universe, root_id, manifest_path) = Self::load_project_root(root_dir, options)?;
                 let project = universe
                     .get_project(root_id)
                     .ok_or_else(|| ProgramCompileError::Io(format!("project {root_id:?} not found")))?;
@@ -184,12 +239,38 @@ impl ProgramAnalyzer {
                 let provider = FilesystemSourceProvider::new();
                 Self::discover_and_analyze(Arc::new(universe), provider, entry_id)
             }
+            EntrySelection::ProjectEntry { root, entry } => {
+                let (universe, root_id, manifest_path) = Self::load_project_root(root, options)?;
+                let project = universe
+                    .get_project(root_id)
+                    .ok_or_else(|| ProgramCompileError::Io(format!("project {root_id:?} not found")))?;
+                let entry_path = match entry {
+                    Some(entry) => project_entry_path(&project.namespace, &entry)?,
+                    None => match &project.entry {
+                        Some(path) => path.clone(),
+                        None => {
+                            let default_entry = project
+                                .manifest
+                                .as_ref()
+                                .and_then(|manifest| manifest.default_entry.as_deref())
+                                .ok_or_else(|| ProgramCompileError::ProjectNotExecutable(manifest_path.display().to_string()))?;
+                            project_entry_path(&project.namespace, default_entry)?
+                        }
+                    },
+                };
+                let entry_id = ModuleId {
+                    project: root_id.into(),
+                    path: entry_path,
+                };
+                let provider = FilesystemSourceProvider::new();
+                Self::discover_and_analyze(Arc::new(universe), provider, entry_id)
+            }
             EntrySelection::Package(pkg_dir) => {
                 let main_file = pkg_dir.join("main.ph");
                 if !main_file.exists() {
                     return Err(ProgramCompileError::PackageNotExecutable(pkg_dir.display().to_string()));
                 }
-                let mut universe = ProjectUniverse::new();
+                let mut universe = ProjectUniverse::with_cfg_options(options.cfg.clone());
                 let name = pkg_dir.file_name().and_then(|s| s.to_str()).unwrap_or("package");
                 let root_id = universe.load_synthetic_root(name, &pkg_dir, "main")?;
                 let entry_id = ModuleId {
@@ -203,8 +284,8 @@ impl ProgramAnalyzer {
             }
             EntrySelection::Module(file_path) => {
                 if let Ok(Some(project_root)) = discover_owning_project(&file_path) {
-                    let mut universe = ProjectUniverse::new();
-                    let root_id = universe.load_root(project_root.join("project.toml"))?;
+                    let mut universe = ProjectUniverse::with_cfg_options(options.cfg.clone());
+                    let root_id = universe.load_root_with_options(project_root.join("project.toml"), &options.lock)?;
                     let project = universe.get_project(root_id).unwrap();
                     let canonical_file = file_path
                         .canonicalize()
@@ -220,7 +301,7 @@ impl ProgramAnalyzer {
                     let provider = FilesystemSourceProvider::new();
                     Self::discover_and_analyze(Arc::new(universe), provider, entry_id)
                 } else {
-                    Self::analyze_standalone_module(file_path)
+                    Self::analyze_standalone_module(file_path, options)
                 }
             }
             EntrySelection::Inline(source_text) => {
@@ -242,8 +323,10 @@ impl ProgramAnalyzer {
                 }
                 let mut ids = phalcom_modules::SyntheticProjectIdAllocator;
                 let entry_id = ModuleId::synthetic(ids.allocate(), ModulePath::root());
-                let universe = Arc::new(ProjectUniverse::new());
-                let interface = InterfaceBuilder::build(entry_id.clone(), ModuleKind::Module, &parsed.program)?;
+                let universe = Arc::new(ProjectUniverse::with_cfg_options(options.cfg.clone()));
+                let mut program = parsed.program;
+                universe.cfg_context(entry_id.project).filter_program(&entry_id.path, &mut program)?;
+                let interface = InterfaceBuilder::build(entry_id.clone(), ModuleKind::Module, &program)?;
                 let mut interfaces = BTreeMap::new();
                 interfaces.insert(entry_id.clone(), interface);
                 let linker = ModuleLinker::new(universe.clone(), interfaces);
@@ -254,7 +337,7 @@ impl ProgramAnalyzer {
                     ModuleKind::Module,
                     None,
                     source_text,
-                    Arc::new(parsed.program),
+                    Arc::new(program),
                 ));
                 let mut sources = BTreeMap::new();
                 sources.insert(entry_id.clone(), parsed_unit);
@@ -284,7 +367,133 @@ impl ProgramAnalyzer {
         }
     }
 
-    fn analyze_standalone_module(file_path: PathBuf) -> Result<AnalyzedProgram, ProgramCompileError> {
+    /// Analyzes every module of the project rooted at `root_dir`, executable
+    /// or not. Modules the entry (or root package) does not reach are
+    /// analyzed as entries of their own.
+    pub fn analyze_project_modules(root_dir: &Path) -> Result<AnalyzedProject, ProgramCompileError> {
+        Self::analyze_project_modules_with_options(root_dir, &ProjectLoadOptions::default())
+    }
+
+    /// [`Self::analyze_project_modules`], loading the project under `options`.
+    pub fn analyze_project_modules_with_options(root_dir: &Path, options: &ProjectLoadOptions) -> Result<AnalyzedProject, ProgramCompileError> {
+        let (mut project, entries) = Self::project_module_entries(root_dir, options)?;
+        for entry in entries {
+            if project.program_for(&entry).is_some() {
+                continue;
+            }
+            let program = Self::discover_and_analyze(project.project_universe.clone(), FilesystemSourceProvider::new(), entry)?;
+            project.programs.push(program);
+        }
+        Ok(project)
+    }
+
+    /// Like [`Self::analyze_project_modules`], but a module that fails to
+    /// load, link, or type-check does not stop the build: its error is
+    /// recorded and the remaining modules are still analyzed. Only project
+    /// resolution failures are returned as `Err`.
+    pub fn build_project_modules(root_dir: &Path) -> Result<ProjectBuild, ProgramCompileError> {
+        Self::build_project_modules_with_options(root_dir, &ProjectLoadOptions::default())
+    }
+
+    /// [`Self::build_project_modules`], loading the project under `options`.
+    pub fn build_project_modules_with_options(root_dir: &Path, options: &ProjectLoadOptions) -> Result<ProjectBuild, ProgramCompileError> {
+        let (mut project, entries) = Self::project_module_entries(root_dir, options)?;
+        let mut errors = Vec::new();
+        let mut diagnosed = HashSet::new();
+        let mut reported = HashSet::new();
+        for entry in entries {
+            if project.program_for(&entry).is_some() || diagnosed.contains(&entry) {
+                continue;
+            }
+            match Self::discover_and_analyze(project.project_universe.clone(), FilesystemSourceProvider::new(), entry.clone()) {
+                Ok(program) => project.programs.push(program),
+                // A module shared by several failing entries is reported once.
+                Err(ProgramCompileError::Semantic(mut diags)) => {
+                    diags.by_module.retain(|module, _| diagnosed.insert(module.clone()));
+                    diagnosed.insert(entry);
+                    if !diags.is_empty() {
+                        errors.push(ProgramCompileError::Semantic(diags));
+                    }
+                }
+                Err(err) => {
+                    diagnosed.insert(entry);
+                    if reported.insert(err.to_string()) {
+                        errors.push(err);
+                    }
+                }
+            }
+        }
+
+        Ok(ProjectBuild { project, errors })
+    }
+
+    /// Loads the project at `root_dir` into an [`AnalyzedProject`] with no
+    /// programs yet, plus the entries that analyze its modules: the manifest
+    /// entry first, then every module.
+    pub(crate) fn project_module_entries(root_dir: &Path, options: &ProjectLoadOptions) -> Result<(AnalyzedProject, Vec<ModuleId>), ProgramCompileError> {
+        let (universe, root_id, _) = Self::load_project_root(root_dir.to_path_buf(), options)?;
+        let project = universe
+            .get_project(root_id)
+            .ok_or_else(|| ProgramCompileError::Io(format!("project {root_id:?} not found")))?;
+
+        let mut files = Vec::new();
+        collect_module_files(&project.source_root, &mut files)?;
+        files.sort();
+        let mut modules = Vec::new();
+        for file in &files {
+            let rel_path = file.strip_prefix(&project.source_root).expect("collected under the source root");
+            modules.push(ModuleId::resolved(root_id, relative_path_to_module_path(rel_path)?));
+        }
+
+        let mut entries: Vec<ModuleId> = project.entry.iter().map(|path| ModuleId::resolved(root_id, path.clone())).collect();
+        entries.extend(modules.iter().cloned());
+        let project = AnalyzedProject {
+            project_universe: Arc::new(universe),
+            project: root_id,
+            modules,
+            programs: Vec::new(),
+        };
+        Ok((project, entries))
+    }
+
+    /// Analyzes `entry` inside an already-loaded universe, reading sources
+    /// through `source_provider` (e.g. an overlay serving a synthetic module).
+    pub fn analyze_in_universe<P: SourceProvider>(
+        universe: Arc<ProjectUniverse>,
+        source_provider: P,
+        entry: ModuleId,
+    ) -> Result<AnalyzedProgram, ProgramCompileError> {
+        Self::discover_and_analyze(universe, source_provider, entry)
+    }
+
+    /// Analyzes `entry` against published packages: a module of a project
+    /// one of `packages` was opened as is linked from the archive's interfaces
+    /// and typed from its semantic metadata, and its sources are never read.
+    pub fn analyze_against_packages<P: SourceProvider>(
+        universe: Arc<ProjectUniverse>,
+        source_provider: P,
+        entry: ModuleId,
+        packages: &[PublishedPackage],
+    ) -> Result<AnalyzedProgram, ProgramCompileError> {
+        Self::analyze_with_packages(universe, source_provider, entry, packages)
+    }
+
+    /// Loads the project whose `project.toml` is `root_dir` or lies in it.
+    fn load_project_root(root_dir: PathBuf, options: &ProjectLoadOptions) -> Result<(ProjectUniverse, ResolvedProjectId, PathBuf), ProgramCompileError> {
+        let manifest_path = if root_dir.ends_with("project.toml") {
+            root_dir
+        } else {
+            root_dir.join("project.toml")
+        };
+        if !manifest_path.exists() {
+            return Err(ProgramCompileError::Io(format!("project manifest not found at {}", manifest_path.display())));
+        }
+        let mut universe = ProjectUniverse::with_cfg_options(options.cfg.clone());
+        let root_id = universe.load_root_with_options(&manifest_path, &options.lock)?;
+        Ok((universe, root_id, manifest_path))
+    }
+
+    fn analyze_standalone_module(file_path: PathBuf, options: &ProjectLoadOptions) -> Result<AnalyzedProgram, ProgramCompileError> {
         let canonical = file_path
             .canonicalize()
             .map_err(|e| ProgramCompileError::Io(format!("{}: {e}", file_path.display())))?;
@@ -299,8 +508,10 @@ impl ProgramAnalyzer {
         }
         let mut ids = phalcom_modules::SyntheticProjectIdAllocator;
         let entry_id = ModuleId::synthetic(ids.allocate(), ModulePath::root());
-        let universe = Arc::new(ProjectUniverse::new());
-        let interface = InterfaceBuilder::build(entry_id.clone(), ModuleKind::Module, &parsed.program)?;
+        let universe = Arc::new(ProjectUniverse::with_cfg_options(options.cfg.clone()));
+        let mut program = parsed.program;
+        universe.cfg_context(entry_id.project).filter_program(&entry_id.path, &mut program)?;
+        let interface = InterfaceBuilder::build(entry_id.clone(), ModuleKind::Module, &program)?;
         let mut interfaces = BTreeMap::from([(entry_id.clone(), interface)]);
         let mut resolved = BTreeMap::new();
 
@@ -313,11 +524,11 @@ impl ProgramAnalyzer {
                 display_path: canonical.clone(),
             }),
             source_text,
-            Arc::new(parsed.program.clone()),
+            Arc::new(program.clone()),
         ));
         sources.insert(entry_id.clone(), parsed_unit);
 
-        for dependency in &parsed.program.preamble.dependencies {
+        for dependency in &program.preamble.dependencies {
             let path = match dependency {
                 phalcom_ast::ast::DependencyDecl::Import(phalcom_ast::ast::ImportDecl::Module(decl)) => &decl.path,
                 phalcom_ast::ast::DependencyDecl::Import(phalcom_ast::ast::ImportDecl::Selective(decl)) => &decl.path,
@@ -329,23 +540,29 @@ impl ProgramAnalyzer {
                 }
             };
 
-            let builtin = match &path.root {
-                phalcom_ast::ast::ImportRoot::Absolute(root) if root.name == "universe" => BuiltinProject::Universe,
-                phalcom_ast::ast::ImportRoot::Absolute(root) if root.name == "std" => BuiltinProject::Std,
+            let (builtin, root) = match &path.root {
+                phalcom_ast::ast::ImportRoot::Absolute(root) => match BuiltinProject::for_import_root(&root.name) {
+                    Some(builtin) => (builtin, root),
+                    None => {
+                        return Err(ProgramCompileError::StandaloneImportRequiresPackageContext {
+                            import_name: path.to_string().into(),
+                        });
+                    }
+                },
                 _ => {
                     return Err(ProgramCompileError::StandaloneImportRequiresPackageContext {
                         import_name: path.to_string().into(),
                     });
                 }
             };
-            let target_path = ModulePath::from_components(
-                path.segments
-                    .iter()
-                    .map(|segment| ModuleComponent::from_identifier(&segment.name))
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(|e| ProgramCompileError::Io(e.to_string()))?,
-            );
-            let target_id = ModuleId::builtin(builtin, target_path);
+            let root = ModuleComponent::from_identifier(&root.name).map_err(|e| ProgramCompileError::Io(e.to_string()))?;
+            let segments = path
+                .segments
+                .iter()
+                .map(|segment| ModuleComponent::from_identifier(&segment.name))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| ProgramCompileError::Io(e.to_string()))?;
+            let target_id = builtin.import_target(root, segments);
             let provider = BuiltinProjectSourceProvider::new(builtin);
             let target_interface = provider.load_interface(&target_id)?;
             let target_parsed = provider.load_parsed(&target_id)?;
@@ -379,27 +596,97 @@ impl ProgramAnalyzer {
         })
     }
 
-    fn discover_and_analyze(
+    fn discover_and_analyze<P: SourceProvider>(
         universe: Arc<ProjectUniverse>,
-        source_provider: FilesystemSourceProvider,
+        source_provider: P,
         entry: ModuleId,
     ) -> Result<AnalyzedProgram, ProgramCompileError> {
-        let mut resolver = ModuleResolver::new(&universe, &source_provider);
+        Self::analyze_with_packages(universe, source_provider, entry, &[])
+    }
+
+    fn analyze_with_packages<P: SourceProvider>(
+        universe: Arc<ProjectUniverse>,
+        source_provider: P,
+        entry: ModuleId,
+        packages: &[PublishedPackage],
+    ) -> Result<AnalyzedProgram, ProgramCompileError> {
+        let artifacts: BTreeMap<ResolvedProjectId, ResolvedPackageArtifact> =
+            packages.iter().map(|package| (package.artifact.package_id, package.artifact.clone())).collect();
+        let DiscoveredModules { interfaces, sources, resolved } =
+            Self::discover_with_packages(&universe, &source_provider, std::slice::from_ref(&entry), &artifacts)?;
+
+        let published = artifacts.into_values().flat_map(|artifact| artifact.interfaces);
+        let linker = ModuleLinker::new(universe.clone(), interfaces).with_published(published);
+        let linked = Arc::new(linker.link(entry.clone(), &resolved)?);
+
+        let semantics: Vec<_> = packages.iter().filter_map(PublishedPackage::semantics).collect();
+        let analysis = phalcom_semantic::analyze_workspace_with_packages(
+            phalcom_semantic::SemanticWorkspaceInput {
+                linked: linked.clone(),
+                sources: sources.clone(),
+                generation: 0,
+            },
+            &semantics,
+        );
+
+        if analysis.snapshot.has_errors() {
+            let mut by_module = BTreeMap::new();
+            for (m, d) in analysis.snapshot.diagnostics.iter() {
+                by_module.insert(m.clone(), d.to_vec());
+            }
+            return Err(ProgramCompileError::Semantic(ProgramSemanticDiagnostics { by_module }));
+        }
+
+        Ok(AnalyzedProgram {
+            project_universe: universe,
+            linked,
+            semantic: analysis.snapshot,
+            sources,
+            entry,
+        })
+    }
+
+    /// Loads `entries`, the packages enclosing them, and everything they
+    /// import, resolving each import path along the way.
+    pub(crate) fn discover<P: SourceProvider>(
+        universe: &ProjectUniverse,
+        source_provider: &P,
+        entries: &[ModuleId],
+    ) -> Result<DiscoveredModules, ProgramCompileError> {
+        Self::discover_with_packages(universe, source_provider, entries, &BTreeMap::new())
+    }
+
+    /// [`Self::discover`], stopping at the modules of the projects
+    /// `packages` has a published artifact for: their imports resolve
+    /// against the artifact, and they are neither loaded nor parsed.
+    fn discover_with_packages<P: SourceProvider>(
+        universe: &ProjectUniverse,
+        source_provider: &P,
+        entries: &[ModuleId],
+        packages: &BTreeMap<ResolvedProjectId, ResolvedPackageArtifact>,
+    ) -> Result<DiscoveredModules, ProgramCompileError> {
+        let mut resolver = ModuleResolver::new(universe, source_provider).with_packages(packages);
         let mut interfaces = BTreeMap::new();
         let mut sources = BTreeMap::new();
         let mut resolved = BTreeMap::new();
         let mut visited = HashSet::new();
-        let mut pending = vec![entry.clone()];
-        visited.insert(entry.clone());
-
-        if let Some(project_id) = entry.project.as_resolved() {
-            let root_id = ModuleId::resolved(project_id, ModulePath::root());
-            if visited.insert(root_id.clone()) {
-                pending.push(root_id);
+        let mut pending = Vec::new();
+        for entry in entries {
+            if visited.insert(entry.clone()) {
+                pending.push(entry.clone());
+            }
+            if let Some(project_id) = entry.project.as_resolved() {
+                let root_id = ModuleId::resolved(project_id, ModulePath::root());
+                if visited.insert(root_id.clone()) {
+                    pending.push(root_id);
+                }
             }
         }
 
         while let Some(current_id) = pending.pop() {
+            if current_id.project.as_resolved().is_some_and(|project| packages.contains_key(&project)) {
+                continue;
+            }
             let parsed = resolver.load_parsed(&current_id)?;
             let interface = resolver.load_interface(&current_id)?;
             interfaces.insert(current_id.clone(), interface.clone());
@@ -433,33 +720,17 @@ impl ProgramAnalyzer {
             }
         }
 
-        let linker = ModuleLinker::new(universe.clone(), interfaces);
-        let linked = Arc::new(linker.link(entry.clone(), &resolved)?);
-
-        let analysis = phalcom_semantic::analyze_workspace(phalcom_semantic::SemanticWorkspaceInput {
-            linked: linked.clone(),
-            sources: sources.clone(),
-            generation: 0,
-        });
-
-        if analysis.snapshot.has_errors() {
-            let mut by_module = BTreeMap::new();
-            for (m, d) in analysis.snapshot.diagnostics.iter() {
-                by_module.insert(m.clone(), d.to_vec());
-            }
-            return Err(ProgramCompileError::Semantic(ProgramSemanticDiagnostics { by_module }));
-        }
-
-        Ok(AnalyzedProgram {
-            project_universe: universe,
-            linked,
-            semantic: analysis.snapshot,
-            sources,
-            entry,
-        })
+        Ok(DiscoveredModules { interfaces, sources, resolved })
     }
 }
 
+/// The unlinked module closure [`ProgramAnalyzer::discover`] loads.
+pub(crate) struct DiscoveredModules {
+    pub(crate) interfaces: BTreeMap<ModuleId, phalcom_modules::UnlinkedModuleInterface>,
+    pub(crate) sources: BTreeMap<ModuleId, Arc<phalcom_modules::source::ParsedModuleUnit>>,
+    pub(crate) resolved: BTreeMap<(ModuleId, String), ModuleId>,
+}
+
 /// Compiler facade over an analyzed semantic program.
 pub struct ProgramCompiler;
 
@@ -476,14 +747,13 @@ impl ProgramCompiler {
             modules.insert(id.clone(), compile_module(id.clone(), linked_module, source, source_text));
         }
 
-        let exporter = phalcom_semantic::metadata::MetadataExporter::new(
-            analyzed.semantic.store(),
-            Some(analyzed.semantic.declarations()),
-            None,
-            None,
-            phalcom_type_meta::header::MetadataProfile::RuntimePublic,
-        );
-        let metadata_bundle = exporter.build_bundle(&[]).ok().map(Arc::new);
+        let metadata_bundle = analyzed.metadata_bundle().ok().map(Arc::new);
+        let mut fingerprints = InvalidationIndex::new();
+        for (id, surface) in analyzed.semantic.surfaces().iter() {
+            if !matches!(id.module.project, ProjectIdentity::Builtin(_)) {
+                fingerprints.insert(id.clone(), DeclarationFingerprint::of_surface(surface, analyzed.semantic.store()));
+            }
+        }
 
         Ok(CompiledProgram {
             project_universe: analyzed.project_universe.clone(),
@@ -492,12 +762,18 @@ impl ProgramCompiler {
             entry: analyzed.entry.clone(),
             initialization_order: analyzed.linked.initialization_order.clone(),
             semantic_metadata: metadata_bundle,
+            fingerprints: Arc::new(fingerprints),
         })
     }
 
     /// Compiles an entry selection by analyzing it first and compiling the analyzed result.
     pub fn compile_entry_selection(entry: EntrySelection) -> Result<CompiledProgram, ProgramCompileError> {
-        let analyzed = ProgramAnalyzer::analyze_entry_selection(entry)?;
+        Self::compile_entry_selection_with_options(entry, &ProjectLoadOptions::default())
+    }
+
+    /// [`Self::compile_entry_selection`], loading projects under `options`.
+    pub fn compile_entry_selection_with_options(entry: EntrySelection, options: &ProjectLoadOptions) -> Result<CompiledProgram, ProgramCompileError> {
+        let analyzed = ProgramAnalyzer::analyze_entry_selection_with_options(entry, options)?;
         Self::compile_analyzed(&analyzed)
     }
 }
@@ -521,6 +797,35 @@ fn relative_path_to_module_path(rel_path: &Path) -> Result<ModulePath, ProgramCo
     Ok(ModulePath::from_components(components))
 }
 
+/// Parses a dotted entry path into a project-relative module path. A leading
+/// component naming the project namespace is dropped, so `app.tools.migrate`
+/// and `tools.migrate` select the same module of project `app`.
+fn project_entry_path(namespace: &ModuleComponent, entry: &str) -> Result<ModulePath, ProgramCompileError> {
+    let mut parts: Vec<&str> = entry.split('.').collect();
+    if parts.first() == Some(&namespace.as_str()) {
+        parts.remove(0);
+    }
+    let components = parts
+        .into_iter()
+        .map(|part| ModuleComponent::from_identifier(part).map_err(|e| ProjectError::InvalidEntry(entry.to_string(), e.to_string())))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ModulePath::from_components(components))
+}
+
+/// Collects every `.ph` file under `dir`, recursively.
+pub(crate) fn collect_module_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), ProgramCompileError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| ProgramCompileError::Io(format!("{}: {e}", dir.display())))?;
+    for entry in entries {
+        let path = entry.map_err(|e| ProgramCompileError::Io(format!("{}: {e}", dir.display())))?.path();
+        if path.is_dir() {
+            collect_module_files(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "ph") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
 fn compile_module(id: ModuleId, module: &LinkedModule, source: Option<SourceLocation>, source_text: Option<Arc<str>>) -> CompiledModule {
     let plan = ModuleMaterializationPlan::empty(module);
     CompiledModule {
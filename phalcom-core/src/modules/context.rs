@@ -255,7 +255,7 @@ impl ModuleExecutionContext {
                 for name in iface.exports.keys() {
                     let sym = vm.interner.intern(name);
                     let slot = vm.heap.module_mut(obj_ref).declare(sym)?;
-                    if let Some(class_id) = vm.resolve_builtin_class_name(name) {
+                    if let Some(class_id) = vm.resolve_builtin_binding(id, name) {
                         vm.heap.module_mut(obj_ref).set_global(slot, crate::value::Value::obj(class_id))?;
                     }
                     exports.insert(
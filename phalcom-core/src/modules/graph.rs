@@ -0,0 +1,429 @@
+//! `phalcom graph`: a project's module graphs, as text, DOT, or JSON.
+//!
+//! [`project_graph`] loads every module under the project's source root and
+//! everything they import, then asks the linker for its graphs without
+//! linking a program, so a runtime cycle that would fail `phalcom build` is
+//! still drawn — and highlighted — rather than reported as an error. The
+//! initialization order is shown when there is no cycle, and each module's
+//! imports are audited with [`phalcom_modules::unused_imports`].
+
+use super::compile::{DiscoveredModules, ProgramAnalyzer, ProgramCompileError};
+use phalcom_ast::ast::ImportPath;
+use phalcom_common::range::SourceRange;
+use phalcom_modules::source::ParsedModuleUnit;
+use phalcom_modules::{
+    ModuleGraphs, ModuleId, ModuleLinker, ProjectIdentity, ProjectLoadOptions, ProjectUniverse, ReferenceEdge, ReferenceKind, ResolvedProjectId,
+    RuntimeDependencyReason, SemanticEdgeKind, SemanticNodeId, UnusedImport, resolution_key,
+};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Which graph layer to render.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GraphLayer {
+    /// Eager initialization dependencies; cycles here are errors.
+    Runtime,
+    /// Interface dependencies; cycles here are resolved by fixed point.
+    Semantic,
+}
+
+/// The graphs of one project and the modules it reaches.
+#[derive(Clone, Debug)]
+pub struct ProjectGraph {
+    pub project_universe: Arc<ProjectUniverse>,
+    pub project: ResolvedProjectId,
+    pub graphs: ModuleGraphs,
+    /// Runtime cycles, as [`phalcom_modules::RuntimeDependencyGraph::cycles`] finds them.
+    pub cycles: Vec<Vec<ModuleId>>,
+    /// Dependencies first; `None` when there is a runtime cycle.
+    pub initialization_order: Option<Vec<ModuleId>>,
+    /// Unused imports in the project's own modules, by module then source order.
+    pub unused_imports: Vec<UnusedImport>,
+    pub sources: BTreeMap<ModuleId, Arc<ParsedModuleUnit>>,
+}
+
+/// Builds the graphs of the project at `root`, loaded under `options`.
+pub fn project_graph(root: &Path, options: &ProjectLoadOptions) -> Result<ProjectGraph, ProgramCompileError> {
+    let (project, entries) = ProgramAnalyzer::project_module_entries(root, options)?;
+    let universe = project.project_universe;
+    let provider = phalcom_modules::FilesystemSourceProvider::new();
+    let DiscoveredModules { interfaces, sources, resolved } = ProgramAnalyzer::discover(&universe, &provider, &entries)?;
+
+    let mut unused_imports = Vec::new();
+    for (module, interface) in &interfaces {
+        if module.project.as_resolved() == Some(project.project)
+            && let Some(unit) = sources.get(module)
+        {
+            let extends = |path: &ImportPath| {
+                resolved
+                    .get(&resolution_key(module, path))
+                    .and_then(|target| interfaces.get(target))
+                    .is_some_and(|target| !target.extensions.is_empty())
+            };
+            unused_imports.extend(phalcom_modules::unused_imports(module, interface, &unit.text, extends));
+        }
+    }
+    let graphs = ModuleLinker::new(universe.clone(), interfaces).graphs(&resolved)?;
+    let cycles = graphs.runtime.cycles();
+    let initialization_order = graphs.runtime.initialization_order().ok();
+    Ok(ProjectGraph {
+        project_universe: universe,
+        project: project.project,
+        graphs,
+        cycles,
+        initialization_order,
+        unused_imports,
+        sources,
+    })
+}
+
+impl ProjectGraph {
+    /// A module's name as an import would spell it: `app.util`, or `app`
+    /// for the root package.
+    pub fn label(&self, module: &ModuleId) -> String {
+        let root = match module.project {
+            ProjectIdentity::Resolved(id) => self
+                .project_universe
+                .get_project(id)
+                .map_or_else(|| id.to_string(), |project| project.namespace.to_string()),
+            ProjectIdentity::Builtin(builtin) => builtin.to_string(),
+            ProjectIdentity::Synthetic(id) => id.to_string(),
+        };
+        if module.path.is_root() { root } else { format!("{root}.{}", module.path) }
+    }
+
+    /// Finds a module by its [`Self::label`], or by its path within the
+    /// project (`util` for `app.util`).
+    pub fn find(&self, name: &str) -> Option<ModuleId> {
+        let nodes = self.graphs.references.nodes();
+        let root = self.label(&ModuleId::resolved(self.project, phalcom_modules::ModulePath::root()));
+        let qualified = format!("{root}.{name}");
+        let by_label = |wanted: &str| nodes.iter().find(|module| self.label(module) == wanted).cloned();
+        by_label(name).or_else(|| by_label(&qualified))
+    }
+
+    /// The chain of imports by which `from` reaches `to`, shortest first.
+    pub fn why(&self, from: &ModuleId, to: &ModuleId) -> Option<Vec<ReferenceEdge>> {
+        self.graphs.references.import_chain(from, to)
+    }
+
+    /// `file:line:column` of `range` in `module`, relative to the project
+    /// root when the file lies under it.
+    pub fn location(&self, module: &ModuleId, range: SourceRange) -> Option<String> {
+        let unit = self.sources.get(module)?;
+        let path = &unit.source.as_ref()?.display_path;
+        let root = self.project_universe.get_project(self.project).map(|project| project.root_dir.clone());
+        let path = root
+            .and_then(|root| path.strip_prefix(root).ok().map(Path::to_path_buf))
+            .unwrap_or_else(|| path.clone());
+        let (line, column) = line_col(&unit.text, range.start);
+        Some(format!("{}:{line}:{column}", path.display()))
+    }
+
+    /// The trimmed source line holding `range` in `module`.
+    pub fn source_line(&self, module: &ModuleId, range: SourceRange) -> Option<&str> {
+        let text = &self.sources.get(module)?.text;
+        let start = text[..range.start.min(text.len())].rfind('\n').map_or(0, |newline| newline + 1);
+        let end = text[start..].find('\n').map_or(text.len(), |newline| start + newline);
+        Some(text[start..end].trim())
+    }
+
+    /// Every cycle as a closed walk through its modules, e.g. `[a, b, a]`.
+    pub fn cycle_walks(&self) -> Vec<Vec<ModuleId>> {
+        self.cycles.iter().map(|cycle| self.cycle_walk(cycle)).collect()
+    }
+
+    fn cycle_walk(&self, cycle: &[ModuleId]) -> Vec<ModuleId> {
+        let members = cycle.iter().collect::<BTreeSet<_>>();
+        let start = &cycle[0];
+        let mut via: BTreeMap<ModuleId, ModuleId> = BTreeMap::new();
+        let mut queue = VecDeque::from([start.clone()]);
+        while let Some(module) = queue.pop_front() {
+            for dependency in self.graphs.runtime.dependencies(&module) {
+                if dependency == *start {
+                    let mut walk = vec![start.clone(), module.clone()];
+                    let mut current = module;
+                    while let Some(previous) = via.get(&current) {
+                        walk.push(previous.clone());
+                        current = previous.clone();
+                    }
+                    walk.reverse();
+                    return walk;
+                }
+                if members.contains(&dependency) && !via.contains_key(&dependency) && dependency != *start {
+                    via.insert(dependency.clone(), module.clone());
+                    queue.push_back(dependency);
+                }
+            }
+        }
+        cycle.to_vec()
+    }
+
+    /// The edges of `layer` as `(from, to, reasons)`, one per module pair.
+    fn edges(&self, layer: GraphLayer) -> BTreeMap<(String, String), BTreeSet<&'static str>> {
+        let mut edges: BTreeMap<(String, String), BTreeSet<&'static str>> = BTreeMap::new();
+        match layer {
+            GraphLayer::Runtime => {
+                for module in self.graphs.runtime.nodes() {
+                    for edge in self.graphs.runtime.edges_from(&module) {
+                        edges
+                            .entry((self.label(&edge.importer), self.label(&edge.dependency)))
+                            .or_default()
+                            .insert(runtime_reason(edge.reason));
+                    }
+                }
+            }
+            GraphLayer::Semantic => {
+                for node in self.graphs.semantics.nodes() {
+                    for edge in self.graphs.semantics.edges_from(&node) {
+                        edges
+                            .entry((self.node_label(&edge.from), self.node_label(&edge.to)))
+                            .or_default()
+                            .insert(semantic_kind(edge.kind));
+                    }
+                }
+            }
+        }
+        edges
+    }
+
+    fn node_label(&self, node: &SemanticNodeId) -> String {
+        match node {
+            SemanticNodeId::Module(module) => self.label(module),
+            SemanticNodeId::Declaration { module, name } => format!("{}::{name}", self.label(module)),
+        }
+    }
+
+    fn node_labels(&self, layer: GraphLayer) -> Vec<String> {
+        match layer {
+            GraphLayer::Runtime => self.graphs.runtime.nodes().iter().map(|module| self.label(module)).collect(),
+            GraphLayer::Semantic => self.graphs.semantics.nodes().iter().map(|node| self.node_label(node)).collect(),
+        }
+    }
+
+    /// Modules on a runtime cycle; empty for the semantic layer, where
+    /// cycles are allowed.
+    fn cycle_labels(&self, layer: GraphLayer) -> BTreeSet<String> {
+        match layer {
+            GraphLayer::Runtime => self.cycles.iter().flatten().map(|module| self.label(module)).collect(),
+            GraphLayer::Semantic => BTreeSet::new(),
+        }
+    }
+
+    /// Renders the graph for people: the initialization order, each
+    /// module's dependencies, cycles, and unused imports.
+    pub fn to_text(&self, layer: GraphLayer) -> String {
+        let mut out = String::new();
+        match &self.initialization_order {
+            Some(order) => {
+                out.push_str("initialization order:\n");
+                for (index, module) in order.iter().enumerate() {
+                    let _ = writeln!(out, "  {}. {}", index + 1, self.label(module));
+                }
+            }
+            None => out.push_str("initialization order: none, the runtime graph has a cycle\n"),
+        }
+
+        let _ = writeln!(out, "\n{} dependencies:", layer_name(layer));
+        let edges = self.edges(layer);
+        let mut from_seen = BTreeSet::new();
+        for ((from, to), reasons) in &edges {
+            if from_seen.insert(from) {
+                let _ = writeln!(out, "  {from}");
+            }
+            let _ = writeln!(out, "    -> {to} ({})", reasons.iter().copied().collect::<Vec<_>>().join(", "));
+        }
+
+        if !self.cycles.is_empty() {
+            out.push_str("\nruntime cycles:\n");
+            for walk in self.cycle_walks() {
+                let _ = writeln!(out, "  {}", walk.iter().map(|module| self.label(module)).collect::<Vec<_>>().join(" -> "));
+            }
+        }
+
+        if !self.unused_imports.is_empty() {
+            out.push_str("\nunused imports:\n");
+            for unused in &self.unused_imports {
+                let _ = write!(out, "  {}: `{}` from {}", self.label(&unused.module), unused.name, unused.path);
+                match self.location(&unused.module, unused.range) {
+                    Some(location) => {
+                        let _ = writeln!(out, " ({location})");
+                    }
+                    None => out.push('\n'),
+                }
+            }
+        }
+        out
+    }
+
+    /// Renders `layer` as a Graphviz digraph. Runtime cycles are drawn in
+    /// red and each module is numbered by its initialization order.
+    pub fn to_dot(&self, layer: GraphLayer) -> String {
+        let order = self
+            .initialization_order
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(index, module)| (self.label(module), index + 1))
+            .collect::<BTreeMap<_, _>>();
+        let cyclic = self.cycle_labels(layer);
+        let mut out = String::new();
+        let _ = writeln!(out, "digraph {} {{", dot_id(&format!("{} {}", self.label(&self.root()), layer_name(layer))));
+        out.push_str("  rankdir=LR;\n  node [shape=box];\n");
+        for node in self.node_labels(layer) {
+            let mut attributes = Vec::new();
+            if let Some(index) = order.get(&node) {
+                attributes.push(format!("label={}", dot_id(&format!("{index}. {node}"))));
+            }
+            if cyclic.contains(&node) {
+                attributes.push("color=red, fontcolor=red".to_string());
+            }
+            let _ = writeln!(out, "  {}{};", dot_id(&node), dot_attributes(&attributes));
+        }
+        for ((from, to), reasons) in self.edges(layer) {
+            let mut attributes = vec![format!("label={}", dot_id(&reasons.into_iter().collect::<Vec<_>>().join(", ")))];
+            if layer == GraphLayer::Runtime && self.on_cycle_labels(&from, &to) {
+                attributes.push("color=red, fontcolor=red".to_string());
+            }
+            let _ = writeln!(out, "  {} -> {}{};", dot_id(&from), dot_id(&to), dot_attributes(&attributes));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Whether the runtime edge `from -> to` lies on a cycle.
+    fn on_cycle_labels(&self, from: &str, to: &str) -> bool {
+        self.cycles.iter().any(|cycle| {
+            let labels = cycle.iter().map(|module| self.label(module)).collect::<BTreeSet<_>>();
+            labels.contains(from) && labels.contains(to)
+        })
+    }
+
+    /// Renders `layer` as JSON: nodes, edges, cycles, the initialization
+    /// order, and unused imports.
+    pub fn to_json(&self, layer: GraphLayer) -> Value {
+        let cyclic = self.cycle_labels(layer);
+        let edges = self
+            .edges(layer)
+            .into_iter()
+            .map(|((from, to), reasons)| {
+                let cycle = layer == GraphLayer::Runtime && self.on_cycle_labels(&from, &to);
+                json!({ "from": from, "to": to, "reasons": reasons, "cycle": cycle })
+            })
+            .collect::<Vec<_>>();
+        let nodes = self
+            .node_labels(layer)
+            .into_iter()
+            .map(|node| json!({ "id": node, "cycle": cyclic.contains(&node) }))
+            .collect::<Vec<_>>();
+        json!({
+            "project": self.label(&self.root()),
+            "layer": layer_name(layer),
+            "nodes": nodes,
+            "edges": edges,
+            "cycles": self
+                .cycle_walks()
+                .iter()
+                .map(|walk| walk.iter().map(|module| self.label(module)).collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            "initialization_order": self
+                .initialization_order
+                .as_ref()
+                .map(|order| order.iter().map(|module| self.label(module)).collect::<Vec<_>>()),
+            "unused_imports": self.unused_imports.iter().map(|unused| self.unused_json(unused)).collect::<Vec<_>>(),
+        })
+    }
+
+    fn unused_json(&self, unused: &UnusedImport) -> Value {
+        json!({
+            "module": self.label(&unused.module),
+            "name": unused.name,
+            "path": unused.path,
+            "location": self.location(&unused.module, unused.range),
+        })
+    }
+
+    /// Renders an import chain from [`Self::why`] as JSON.
+    pub fn chain_json(&self, chain: &[ReferenceEdge]) -> Value {
+        Value::Array(
+            chain
+                .iter()
+                .map(|edge| {
+                    json!({
+                        "from": self.label(&edge.from),
+                        "to": self.label(&edge.to),
+                        "kind": reference_kind(edge.kind),
+                        "location": self.location(&edge.from, edge.range),
+                        "source": self.source_line(&edge.from, edge.range),
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    fn root(&self) -> ModuleId {
+        ModuleId::resolved(self.project, phalcom_modules::ModulePath::root())
+    }
+}
+
+/// Human-readable name of a reference kind.
+pub fn reference_kind(kind: ReferenceKind) -> &'static str {
+    match kind {
+        ReferenceKind::WholeModuleImport => "import",
+        ReferenceKind::SelectiveImport => "selective import",
+        ReferenceKind::ReExport => "re-export",
+        ReferenceKind::InterfaceOnly => "interface reference",
+    }
+}
+
+fn runtime_reason(reason: RuntimeDependencyReason) -> &'static str {
+    match reason {
+        RuntimeDependencyReason::WholeModuleImport => "import",
+        RuntimeDependencyReason::SelectiveValueImport => "selective import",
+        RuntimeDependencyReason::ReExport => "re-export",
+        RuntimeDependencyReason::RuntimeDeclarationReference => "declaration",
+    }
+}
+
+fn semantic_kind(kind: SemanticEdgeKind) -> &'static str {
+    match kind {
+        SemanticEdgeKind::ModuleInterface => "interface",
+        SemanticEdgeKind::TypeReference => "type",
+        SemanticEdgeKind::Superclass => "superclass",
+        SemanticEdgeKind::ProtocolReference => "protocol",
+        SemanticEdgeKind::ConstraintReference => "constraint",
+        SemanticEdgeKind::CallbackSignature => "callback",
+        SemanticEdgeKind::AdtReference => "adt",
+    }
+}
+
+fn layer_name(layer: GraphLayer) -> &'static str {
+    match layer {
+        GraphLayer::Runtime => "runtime",
+        GraphLayer::Semantic => "semantic",
+    }
+}
+
+/// A quoted DOT identifier.
+fn dot_id(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn dot_attributes(attributes: &[String]) -> String {
+    if attributes.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", attributes.join(", "))
+    }
+}
+
+/// 1-based line and column of byte `offset` in `text`.
+fn line_col(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |tail| tail.chars().count()) + 1;
+    (line, column)
+}
@@ -2,7 +2,7 @@
 
 use crate::error::{PhResult, RuntimeError};
 use crate::heap::{ModuleObject, Object, RuntimeExportRef};
-use crate::modules::compile::CompiledProgram;
+use crate::modules::compile::{CompiledModule, CompiledProgram};
 use crate::modules::linkage::{BindingRef, CompileBindings, RuntimeLinkedRead};
 use crate::modules::registry::ModuleRecord;
 use crate::vm::{RuntimeRoots, VM};
@@ -13,7 +13,19 @@ use std::sync::Arc;
 impl VM {
     /// Materializes a closed, linked compiled program into VM module objects,
     /// global layouts, linked read slots, and export tables without running initializers.
+    ///
+    /// # Errors
+    ///
+    /// [`RuntimeError::CapabilityDenied`] — before anything is allocated — when
+    /// the VM's [`VmLimits`](crate::vm::VmLimits) refuse host modules and the
+    /// program links one of [`HOST_MODULES`](crate::vm::HOST_MODULES).
     pub fn materialize_program(&mut self, program: &CompiledProgram) -> PhResult<()> {
+        if !self.limits().allow_host_modules
+            && let Some(id) = program.modules.keys().find(|id| is_host_module(id))
+        {
+            return Err(RuntimeError::CapabilityDenied { module: id.to_string() }.into());
+        }
+
         // Phase 1: Allocate all module/package objects in the Prepared state (idempotent).
         for (id, compiled_mod) in &program.modules {
             if !self.module_registry.contains_key(id) {
@@ -38,6 +50,7 @@ impl VM {
                         ),
                     )
                     .map_err(|e| RuntimeError::Internal(e.to_string()))?;
+                self.module_registry.record_fingerprints(id, program);
             }
         }
 
@@ -236,90 +249,12 @@ impl VM {
 
         // Phase 4: Materialize linked reads (resolve LinkedReadSpec -> RuntimeLinkedRead).
         for (id, compiled_mod) in &program.modules {
-            let obj_ref = self.module_registry.get(id).expect("module allocated").object;
-            let mut materialized_reads = Vec::with_capacity(compiled_mod.linked_reads.len());
-
-            for read_spec in &compiled_mod.linked_reads {
-                let runtime_read = match read_spec {
-                    LinkedReadSpec::Module(target_id) => {
-                        let target_obj = self
-                            .module_registry
-                            .get(target_id)
-                            .ok_or_else(|| RuntimeError::Internal(format!("linked read target module {target_id} not registered")))?
-                            .object;
-                        RuntimeLinkedRead::Module(target_obj)
-                    }
-                    LinkedReadSpec::Binding(symbol_id) => {
-                        let target_obj = self
-                            .module_registry
-                            .get(&symbol_id.module)
-                            .ok_or_else(|| RuntimeError::Internal(format!("linked read target module {} not registered", symbol_id.module)))?
-                            .object;
-                        let sym = self.interner.intern(&symbol_id.name);
-                        let slot = match self.heap.module(target_obj).slot_of(sym) {
-                            Some(s) => s,
-                            None => self.heap.module_mut(target_obj).declare(sym)?,
-                        };
-                        if symbol_id.module.project.as_builtin().is_some() {
-                            if let Some(class_id) = self.resolve_builtin_class_name(&symbol_id.name) {
-                                self.heap.module_mut(target_obj).set_global(slot, crate::value::Value::obj(class_id))?;
-                            }
-                        }
-                        RuntimeLinkedRead::Binding(BindingRef {
-                            module: target_obj,
-                            slot: slot as u16,
-                        })
-                    }
-                };
-                materialized_reads.push(runtime_read);
-            }
-            self.heap.module_mut(obj_ref).linked_reads = materialized_reads;
+            self.link_module_reads(id, compiled_mod, program)?;
         }
 
         // Phase 5: Materialize export table on ModuleObject.
         for (id, compiled_mod) in &program.modules {
-            let obj_ref = self.module_registry.get(id).expect("module allocated").object;
-            let mut exports = HashMap::new();
-
-            for (exported_name, linked_export) in &compiled_mod.interface.exports {
-                let public_sym = self.interner.intern(exported_name);
-                match &linked_export.target {
-                    phalcom_modules::LinkedExportTarget::Binding(symbol) => {
-                        let target_mod_obj = self
-                            .module_registry
-                            .get(&symbol.module)
-                            .ok_or_else(|| RuntimeError::Internal(format!("export target module {} not registered", symbol.module)))?
-                            .object;
-                        let target_sym = self.interner.intern(&symbol.name);
-
-                        let slot = match self.heap.module(target_mod_obj).slot_of(target_sym) {
-                            Some(s) => s,
-                            None => self.heap.module_mut(target_mod_obj).declare(target_sym)?,
-                        };
-                        if symbol.module.project.as_builtin().is_some() {
-                            if let Some(class_id) = self.resolve_builtin_class_name(&symbol.name) {
-                                self.heap.module_mut(target_mod_obj).set_global(slot, crate::value::Value::obj(class_id))?;
-                            }
-                        }
-                        exports.insert(
-                            public_sym,
-                            RuntimeExportRef::Binding(BindingRef {
-                                module: target_mod_obj,
-                                slot: slot as u16,
-                            }),
-                        );
-                    }
-                    phalcom_modules::LinkedExportTarget::Module(target_mod_id) => {
-                        let target_mod_obj = self
-                            .module_registry
-                            .get(target_mod_id)
-                            .ok_or_else(|| RuntimeError::Internal(format!("export target module {target_mod_id} not registered")))?
-                            .object;
-                        exports.insert(public_sym, RuntimeExportRef::Module(target_mod_obj));
-                    }
-                }
-            }
-            self.heap.module_mut(obj_ref).exports = exports;
+            self.link_module_exports(id, compiled_mod)?;
         }
 
         // Phase 6: Top-level closures are compiled on-demand by run_compiled rather than pre-stored on plans.
@@ -365,6 +300,109 @@ impl VM {
         Ok(())
     }
 
+    /// Resolves `compiled_mod`'s linked reads against the registered modules
+    /// and records the extension sources its sends see.
+    pub(crate) fn link_module_reads(&mut self, id: &phalcom_modules::ModuleId, compiled_mod: &CompiledModule, program: &CompiledProgram) -> PhResult<()> {
+        let obj_ref = self.module_registry.get(id).expect("module allocated").object;
+        let mut materialized_reads = Vec::with_capacity(compiled_mod.linked_reads.len());
+
+        for read_spec in &compiled_mod.linked_reads {
+            let runtime_read = match read_spec {
+                LinkedReadSpec::Module(target_id) => {
+                    let target_obj = self
+                        .module_registry
+                        .get(target_id)
+                        .ok_or_else(|| RuntimeError::Internal(format!("linked read target module {target_id} not registered")))?
+                        .object;
+                    RuntimeLinkedRead::Module(target_obj)
+                }
+                LinkedReadSpec::Binding(symbol_id) => {
+                    let target_obj = self
+                        .module_registry
+                        .get(&symbol_id.module)
+                        .ok_or_else(|| RuntimeError::Internal(format!("linked read target module {} not registered", symbol_id.module)))?
+                        .object;
+                    let sym = self.interner.intern(&symbol_id.name);
+                    let slot = match self.heap.module(target_obj).slot_of(sym) {
+                        Some(s) => s,
+                        None => self.heap.module_mut(target_obj).declare(sym)?,
+                    };
+                    if let Some(class_id) = self.resolve_builtin_binding(&symbol_id.module, &symbol_id.name) {
+                        self.heap.module_mut(target_obj).set_global(slot, crate::value::Value::obj(class_id))?;
+                    }
+                    RuntimeLinkedRead::Binding(BindingRef {
+                        module: target_obj,
+                        slot: slot as u16,
+                    })
+                }
+            };
+            materialized_reads.push(runtime_read);
+        }
+        self.heap.module_mut(obj_ref).linked_reads = materialized_reads;
+
+        // Directly imported modules whose `extend` blocks this module's
+        // sends see (`vm/extension.rs`).
+        let extension_sources = program
+            .linked
+            .modules
+            .get(id)
+            .map(|linked| {
+                linked
+                    .extension_sources
+                    .iter()
+                    .filter_map(|source| self.module_registry.get(source).map(|record| record.object))
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.heap.module_mut(obj_ref).extension_sources = extension_sources;
+        Ok(())
+    }
+
+    /// Materializes `compiled_mod`'s export table on its module object.
+    pub(crate) fn link_module_exports(&mut self, id: &phalcom_modules::ModuleId, compiled_mod: &CompiledModule) -> PhResult<()> {
+        let obj_ref = self.module_registry.get(id).expect("module allocated").object;
+        let mut exports = HashMap::new();
+
+        for (exported_name, linked_export) in &compiled_mod.interface.exports {
+            let public_sym = self.interner.intern(exported_name);
+            match &linked_export.target {
+                phalcom_modules::LinkedExportTarget::Binding(symbol) => {
+                    let target_mod_obj = self
+                        .module_registry
+                        .get(&symbol.module)
+                        .ok_or_else(|| RuntimeError::Internal(format!("export target module {} not registered", symbol.module)))?
+                        .object;
+                    let target_sym = self.interner.intern(&symbol.name);
+
+                    let slot = match self.heap.module(target_mod_obj).slot_of(target_sym) {
+                        Some(s) => s,
+                        None => self.heap.module_mut(target_mod_obj).declare(target_sym)?,
+                    };
+                    if let Some(class_id) = self.resolve_builtin_binding(&symbol.module, &symbol.name) {
+                        self.heap.module_mut(target_mod_obj).set_global(slot, crate::value::Value::obj(class_id))?;
+                    }
+                    exports.insert(
+                        public_sym,
+                        RuntimeExportRef::Binding(BindingRef {
+                            module: target_mod_obj,
+                            slot: slot as u16,
+                        }),
+                    );
+                }
+                phalcom_modules::LinkedExportTarget::Module(target_mod_id) => {
+                    let target_mod_obj = self
+                        .module_registry
+                        .get(target_mod_id)
+                        .ok_or_else(|| RuntimeError::Internal(format!("export target module {target_mod_id} not registered")))?
+                        .object;
+                    exports.insert(public_sym, RuntimeExportRef::Module(target_mod_obj));
+                }
+            }
+        }
+        self.heap.module_mut(obj_ref).exports = exports;
+        Ok(())
+    }
+
     /// Compiles and attaches a source closure for `id` using precomputed bindings.
     pub fn compile_program_module_closure(&mut self, id: &phalcom_modules::ModuleId, source: &str, program: &CompiledProgram) -> PhResult<crate::heap::ObjRef> {
         let obj_ref = self
@@ -373,13 +411,22 @@ impl VM {
             .ok_or_else(|| RuntimeError::Internal(format!("module {id} not found in registry")))?
             .object;
         let bindings = program.linked.modules.get(id).map(CompileBindings::from_linked_module);
-        let closure = self
-            .compile_closure_as_with_bindings(obj_ref, source, crate::compiler::lib::UnitKind::File, bindings)
-            .inspect_err(|err| {
-                let source_id = self.heap.module(obj_ref).sources.len().saturating_sub(1) as u32;
-                self.compiler_error(err, obj_ref, source_id);
-            })?;
+        let cfg = program.project_universe.cfg_context(id.project);
+        let closure = self.compile_closure_with_cfg(obj_ref, source, bindings, &id.path, &cfg).inspect_err(|err| {
+            let source_id = self.heap.module(obj_ref).sources.len().saturating_sub(1) as u32;
+            self.compiler_error(err, obj_ref, source_id);
+        })?;
         self.heap.module_mut(obj_ref).closure = Some(closure);
         Ok(closure)
     }
 }
+
+/// Whether `id` is `std.fs`, `std.process` or `std.net`, or inside one of them.
+fn is_host_module(id: &phalcom_modules::ModuleId) -> bool {
+    matches!(id.project, phalcom_modules::ProjectIdentity::Builtin(phalcom_modules::BuiltinProject::Std))
+        && id
+            .path
+            .components()
+            .first()
+            .is_some_and(|component| crate::vm::HOST_MODULES.contains(&component.as_str()))
+}
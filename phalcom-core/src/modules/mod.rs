@@ -8,17 +8,24 @@ pub mod artifact;
 pub mod builtin_materialize;
 pub mod compile;
 pub mod context;
+pub mod graph;
 pub mod initialize;
 pub mod linkage;
 pub mod materialize;
+pub mod package;
 pub mod reflection_cache;
 pub mod registry;
+pub mod reload;
 
 pub use artifact::{ClassBlueprint, ModuleMaterializationPlan, RuntimeDeclarationBlueprint};
 pub use compile::{
-    AnalyzedProgram, CompiledModule, CompiledProgram, EntrySelection, ProgramAnalyzer, ProgramCompileError, ProgramCompiler, ProgramSemanticDiagnostics,
+    AnalyzedProgram, AnalyzedProject, CompiledModule, CompiledProgram, EntrySelection, ProgramAnalyzer, ProgramCompileError, ProgramCompiler,
+    ProgramSemanticDiagnostics, ProjectBuild,
 };
 pub use context::ModuleExecutionContext;
+pub use graph::{GraphLayer, ProjectGraph, project_graph};
 pub use linkage::{BindingRef, CompileBindings, LinkedImportInfo, RuntimeLinkedRead, TopLevelBindingInfo, TopLevelBindingKind};
+pub use package::{PackagedProject, PublishedPackage, package_project};
 pub use reflection_cache::ReflectionCache;
-pub use registry::{ModuleFailure, ModuleFailureRef, ModulePlanFingerprint, ModuleRecord, ModuleRegistry, ModuleState, RuntimeProgramId};
+pub use registry::{ModuleFailure, ModuleFailureRef, ModulePlanFingerprint, ModuleRecord, ModuleRegistry, ModuleState, ReloadPlan, RuntimeProgramId};
+pub use reload::ModuleReload;
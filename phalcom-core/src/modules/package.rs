@@ -0,0 +1,188 @@
+//! `phalcom package`: publishing a project as a verifiable archive, and
+//! loading one back for linking and type-checking.
+//!
+//! [`package_project`] analyzes every module of a project, refuses projects
+//! that cannot be published (see [`package_archive::check_publishable`]), and
+//! writes a [`PackageArchive`] holding the sources, the linked interfaces of
+//! the project's modules, and one `SemanticMetadataBundle` per analyzed
+//! program. [`PublishedPackage::open`] is the consumer side: it verifies the
+//! archive and decodes the interfaces and bundles without parsing a source,
+//! and [`ProgramAnalyzer::analyze_against_packages`] links and type-checks
+//! importers against them.
+
+use super::compile::{ProgramAnalyzer, ProgramCompileError, collect_module_files};
+use phalcom_modules::interface::LinkedExportTarget;
+use phalcom_modules::package_archive::{self, INTERFACES_FILE, METADATA_DIR};
+use phalcom_modules::{
+    LinkedModuleInterface, ModuleId, ModulePath, PackageArchive, ProjectError, ProjectLoadOptions, PublishedPackageId, ResolvedPackageArtifact,
+};
+use phalcom_semantic::metadata::PublishedSemantics;
+use phalcom_type_meta::SemanticMetadataBundle;
+use phalcom_type_meta::declaration::DeclarationTypeRecord;
+use phalcom_type_meta::identity::StableProjectRef;
+use phalcom_type_meta::validate::ValidationLimits;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// The result of [`package_project`].
+#[derive(Clone, Debug)]
+pub struct PackagedProject {
+    /// The written archive.
+    pub archive: PathBuf,
+    /// `sha256:` checksum of the archive file.
+    pub checksum: String,
+    /// Packaged source files.
+    pub sources: usize,
+    /// Modules with a packaged interface.
+    pub modules: usize,
+}
+
+/// Packages the project at `root`, loaded under `options`, into
+/// `out_dir/<name>-<version>.tar`.
+pub fn package_project(root: &Path, out_dir: &Path, options: &ProjectLoadOptions) -> Result<PackagedProject, ProgramCompileError> {
+    let analysis = ProgramAnalyzer::analyze_project_modules_with_options(root, options)?;
+    let project = analysis
+        .project_universe
+        .get_project(analysis.project)
+        .ok_or_else(|| ProgramCompileError::Io(format!("project {:?} not found", analysis.project)))?;
+    let manifest = project
+        .manifest
+        .as_ref()
+        .ok_or_else(|| ProgramCompileError::Io(format!("{} has no project.toml", project.root_dir.display())))?;
+    package_archive::check_publishable(manifest)?;
+
+    let mut files = BTreeMap::new();
+    files.insert(
+        "project.toml".to_string(),
+        package_archive::publishable_manifest(&project.root_dir.join("project.toml"))?.into_bytes(),
+    );
+
+    let not_publishable = |reason: String| ProjectError::NotPublishable {
+        project: project.name.clone(),
+        reason,
+    };
+    let mut sources = Vec::new();
+    collect_module_files(&project.source_root, &mut sources)?;
+    for source in &sources {
+        let relative = source
+            .strip_prefix(&project.root_dir)
+            .map_err(|_| not_publishable(format!("source root {} is outside the project", project.source_root.display())))?;
+        let name = relative.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+        let bytes = std::fs::read(source).map_err(|e| ProgramCompileError::Io(format!("{}: {e}", source.display())))?;
+        files.insert(name, bytes);
+    }
+
+    // A package that is only an ancestor of an analyzed module has sources
+    // but no linked plan of its own; link it as an entry so it has one.
+    let mut programs = analysis.programs.clone();
+    for module in &analysis.modules {
+        if !programs.iter().any(|program| program.linked.modules.contains_key(module)) {
+            let provider = phalcom_modules::FilesystemSourceProvider::new();
+            programs.push(ProgramAnalyzer::analyze_in_universe(
+                analysis.project_universe.clone(),
+                provider,
+                module.clone(),
+            )?);
+        }
+    }
+
+    let mut interfaces: BTreeMap<&ModuleId, &LinkedModuleInterface> = BTreeMap::new();
+    for program in &programs {
+        interfaces.extend(program.linked.modules.iter().map(|(id, module)| (id, &module.interface)));
+        let bundle = program
+            .metadata_bundle()
+            .map_err(|e| not_publishable(format!("cannot export metadata for {}: {e}", program.entry)))?;
+        let bytes = phalcom_type_meta::encode::encode_metadata_binary(&bundle).map_err(|e| ProgramCompileError::Io(e.to_string()))?;
+        files.insert(format!("{METADATA_DIR}/{}.bin", metadata_name(&program.entry.path)), bytes);
+    }
+    let exposed = package_archive::exposed_paths(&analysis.project_universe, analysis.project)?;
+    let interfaces_json = package_archive::encode_interfaces(&analysis.project_universe, analysis.project, interfaces.into_values(), &exposed)?;
+    files.insert(INTERFACES_FILE.to_string(), interfaces_json.into_bytes());
+
+    let archive = out_dir.join(PackageArchive::file_name(manifest));
+    let checksum = PackageArchive::write(&archive, &files)?;
+    Ok(PackagedProject {
+        archive,
+        checksum,
+        sources: sources.len(),
+        modules: analysis.modules.len(),
+    })
+}
+
+/// Entry name of the bundle for a program rooted at `entry`.
+fn metadata_name(entry: &ModulePath) -> String {
+    if entry.is_root() { "package".to_string() } else { entry.to_string() }
+}
+
+/// A package archive loaded for consumption.
+#[derive(Clone, Debug)]
+pub struct PublishedPackage {
+    pub archive: PackageArchive,
+    /// Linked interfaces and exposed paths, for linking importers.
+    pub artifact: ResolvedPackageArtifact,
+    /// Validated semantic metadata, by archive entry.
+    pub metadata: BTreeMap<String, Arc<SemanticMetadataBundle>>,
+}
+
+impl PublishedPackage {
+    /// Opens and verifies the archive at `path`, linked as `package` with
+    /// its dependencies' artifacts keyed by alias.
+    pub fn open(
+        path: impl AsRef<Path>,
+        package: PublishedPackageId,
+        dependency_artifacts: BTreeMap<String, PublishedPackageId>,
+        limits: &ValidationLimits,
+    ) -> Result<Self, ProgramCompileError> {
+        let archive = PackageArchive::open(path)?;
+        let artifact = archive.artifact(package, dependency_artifacts)?;
+        let mut metadata = BTreeMap::new();
+        for (name, bytes) in archive.metadata() {
+            let invalid = |reason: String| ProjectError::InvalidPackageArchive {
+                path: archive.path.clone(),
+                reason,
+            };
+            let bundle = phalcom_type_meta::encode::decode_metadata_binary(bytes, limits).map_err(|e| invalid(format!("'{name}': {e}")))?;
+            metadata.insert(name.to_string(), Arc::new(bundle));
+        }
+        Ok(Self { archive, artifact, metadata })
+    }
+
+    /// The package's semantic metadata, for
+    /// [`phalcom_semantic::analyze_workspace_with_packages`]. `None` when no
+    /// bundle declares one of the package's exports, so nothing names the
+    /// package's own declarations.
+    pub fn semantics(&self) -> Option<PublishedSemantics> {
+        let package = self.artifact.package_id;
+        let origin = self
+            .artifact
+            .interfaces
+            .values()
+            .flat_map(|interface| interface.exports.values())
+            .find_map(|export| {
+                let LinkedExportTarget::Binding(symbol) = &export.target else { return None };
+                if symbol.module.project.as_resolved() != Some(package) {
+                    return None;
+                }
+                let record = self.declaration(&symbol.module.path, &symbol.name)?;
+                let origin = &record.declaration.module.project;
+                (!matches!(origin, StableProjectRef::Builtin { .. })).then(|| origin.clone())
+            })?;
+        Some(PublishedSemantics {
+            package,
+            origin,
+            bundles: self.metadata.values().cloned().collect(),
+        })
+    }
+
+    /// The type record of the declaration `name` in the package module at
+    /// `module`, from whichever bundle carries it.
+    pub fn declaration(&self, module: &ModulePath, name: &str) -> Option<&DeclarationTypeRecord> {
+        let module: Vec<&str> = module.components().iter().map(|component| component.as_str()).collect();
+        self.metadata.values().flat_map(|bundle| bundle.declarations.iter()).find(|record| {
+            record.declaration.path.len() == 1
+                && &*record.declaration.path[0] == name
+                && record.declaration.module.path.iter().map(|part| &**part).eq(module.iter().copied())
+        })
+    }
+}
@@ -2,8 +2,10 @@
 
 use crate::error::PhError;
 use crate::heap::ObjRef;
-use phalcom_modules::ModuleId;
-use std::collections::HashMap;
+use crate::modules::compile::CompiledProgram;
+use phalcom_modules::{DeclarationId, LinkedExportTarget, LinkedReadSpec, ModuleId};
+use phalcom_semantic::InvalidationIndex;
+use std::collections::{BTreeSet, HashMap};
 
 /// Lifecycle state machine for module execution.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -70,16 +72,32 @@ impl ModuleRecord {
 #[error("duplicate module identity: {0}")]
 pub struct DuplicateModuleIdentity(pub ModuleId);
 
+/// What reloading one module from a newer [`CompiledProgram`] touches.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReloadPlan {
+    /// The module being recompiled.
+    pub module: ModuleId,
+    /// Its declarations whose signature fingerprint was added, removed or
+    /// changed; a body-only edit leaves this empty.
+    pub changed: Vec<DeclarationId>,
+    /// Registered modules that read or re-export one of `changed`, and so
+    /// are re-linked against the new program.
+    pub dependents: Vec<ModuleId>,
+}
+
 /// Runtime module registry on the VM.
 #[derive(Debug, Default)]
 pub struct ModuleRegistry {
     by_id: HashMap<ModuleId, ModuleRecord>,
+    /// Declaration fingerprints of the modules as they were last materialized
+    /// or reloaded.
+    fingerprints: InvalidationIndex,
 }
 
 impl ModuleRegistry {
     /// Creates an empty registry.
     pub fn new() -> Self {
-        Self { by_id: HashMap::new() }
+        Self::default()
     }
 
     /// Registers a module record under its semantic identity if not already present.
@@ -121,6 +139,48 @@ impl ModuleRegistry {
         self.by_id.iter()
     }
 
+    /// Returns the declaration fingerprints of the registered modules.
+    pub fn fingerprints(&self) -> &InvalidationIndex {
+        &self.fingerprints
+    }
+
+    /// Records `program`'s declaration fingerprints for module `id`, replacing
+    /// any it had.
+    pub(crate) fn record_fingerprints(&mut self, id: &ModuleId, program: &CompiledProgram) {
+        self.fingerprints.replace_module(&program.fingerprints, id);
+    }
+
+    /// Plans reloading `id` from `program`, a recompilation of the running
+    /// program after `id`'s source changed: compares its declaration
+    /// fingerprints with the registered ones and collects the dependents
+    /// that read a changed declaration.
+    pub fn plan_reload(&self, id: &ModuleId, program: &CompiledProgram) -> ReloadPlan {
+        let changed = self.fingerprints.changed_in(&program.fingerprints, id);
+        let touches = |module: &ModuleId, name: &str| module == id && changed.contains(&DeclarationId::new(module.clone(), name.into()));
+        let mut dependents = BTreeSet::new();
+        for (dependent, compiled) in &program.modules {
+            if dependent == id || !self.by_id.contains_key(dependent) {
+                continue;
+            }
+            let reads = compiled.linked_reads.iter().any(|read| match read {
+                LinkedReadSpec::Module(module) => module == id && !changed.is_empty(),
+                LinkedReadSpec::Binding(symbol) => touches(&symbol.module, &symbol.name),
+            });
+            let reexports = compiled.interface.exports.values().any(|export| match &export.target {
+                LinkedExportTarget::Binding(symbol) => touches(&symbol.module, &symbol.name),
+                LinkedExportTarget::Module(_) => false,
+            });
+            if reads || reexports {
+                dependents.insert(dependent.clone());
+            }
+        }
+        ReloadPlan {
+            module: id.clone(),
+            changed,
+            dependents: dependents.into_iter().collect(),
+        }
+    }
+
     /// Traces all registered module handles for garbage collection.
     pub fn each_handle(&self, push: &mut impl FnMut(ObjRef)) {
         for record in self.by_id.values() {
@@ -0,0 +1,416 @@
+//! Hot module reload: recompiles one module of a running program and swaps
+//! its new definitions into the live VM.
+//!
+//! The module's top-level initializer runs again against the existing module
+//! object, so its `let` bindings are re-evaluated and its side effects repeat.
+//! Classes it redefines keep their identity: the fresh rows the initializer
+//! builds donate their method tables to the old [`ClassObject`]s, and every
+//! instance, global and subclass that saw the old row keeps working.
+//!
+//! A new class shape is accepted when the old instance and static fields are
+//! a prefix of the new ones — appended fields are nil on existing instances —
+//! and the superclass is unchanged. Anything else is
+//! [`RuntimeError::IncompatibleReload`], and the running module is restored.
+//!
+//! The module's `extend` blocks run again too, so its extensions are set
+//! aside with its classes and come back only if the reload is refused. Every
+//! fallible step — compiling, running, vetting the new shapes and re-linking
+//! the module and its dependents — happens before the first class is patched,
+//! so a refused reload has nothing in place to undo.
+//!
+//! [`ClassObject`]: crate::heap::ClassObject
+
+use crate::error::{PhResult, RuntimeError};
+use crate::heap::{ClassId, ObjRef, RuntimeExportRef};
+use crate::interner::Symbol;
+use crate::modules::compile::CompiledProgram;
+use crate::modules::linkage::{CompileBindings, RuntimeLinkedRead};
+use crate::modules::registry::{ModulePlanFingerprint, ModuleState, ReloadPlan, RuntimeProgramId};
+use crate::value::Value;
+use crate::vm::{ClassKey, ClassLayout, VM};
+use indexmap::IndexMap;
+use phalcom_modules::ModuleId;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Outcome of a successful [`VM::reload_module`].
+#[derive(Clone, Debug)]
+pub struct ModuleReload {
+    /// Declarations whose signatures changed and the dependents re-linked
+    /// for them.
+    pub plan: ReloadPlan,
+    /// Names of the existing classes patched in place, sorted.
+    pub patched: Vec<String>,
+    /// Live instances whose slots grew to a new, compatible layout.
+    pub migrated: usize,
+}
+
+/// Module state captured before a reload, restored if it is refused.
+struct ReloadSnapshot {
+    layouts: Vec<(ClassKey, ClassLayout)>,
+    classes: Vec<(ClassKey, ClassId)>,
+    globals: Vec<Value>,
+    global_bindings: HashMap<Symbol, bool>,
+    closure: Option<ObjRef>,
+    extensions: HashMap<(ClassId, Symbol), ObjRef>,
+    /// The reloaded module's links, then those of each dependent.
+    links: Vec<LinkSnapshot>,
+}
+
+/// The link tables of one module the reload re-links.
+struct LinkSnapshot {
+    module: ObjRef,
+    linked_reads: Vec<RuntimeLinkedRead>,
+    extension_sources: Vec<ObjRef>,
+    exports: HashMap<Symbol, RuntimeExportRef>,
+}
+
+impl VM {
+    /// Reloads module `id` from `program`, a recompilation of the running
+    /// program after `id`'s source changed.
+    ///
+    /// Compares declaration fingerprints ([`ModuleRegistry::plan_reload`]),
+    /// recompiles and re-runs the module, patches the classes it redefines,
+    /// migrates their instances, re-links the dependents that read a changed
+    /// declaration and bumps the world version so inline caches drop. Must be
+    /// called between runs, never from inside one.
+    ///
+    /// # Errors
+    ///
+    /// [`RuntimeError::IncompatibleReload`] for a class shape change live
+    /// objects cannot follow; the compile or runtime error of the new source
+    /// otherwise. On any error the module keeps its previous definitions.
+    ///
+    /// [`ModuleRegistry::plan_reload`]: crate::modules::ModuleRegistry::plan_reload
+    pub fn reload_module(&mut self, program: &CompiledProgram, id: &ModuleId) -> PhResult<ModuleReload> {
+        let module = self
+            .module_registry
+            .get(id)
+            .ok_or_else(|| RuntimeError::Internal(format!("module {id} not found in registry")))?
+            .object;
+        let compiled = program
+            .modules
+            .get(id)
+            .ok_or_else(|| RuntimeError::Internal(format!("module {id} is not part of the reloaded program")))?;
+        let source = compiled
+            .source_text
+            .clone()
+            .ok_or_else(|| RuntimeError::Internal(format!("module {id} has no source to reload")))?;
+        let plan = self.module_registry.plan_reload(id, program);
+
+        // The recompile must not see the module's own classes or bindings as
+        // already defined, nor the re-run its own extensions, so they step
+        // aside until the new definitions are vetted.
+        let linked = std::iter::once(module)
+            .chain(
+                plan.dependents
+                    .iter()
+                    .filter_map(|dependent| self.module_registry.get(dependent))
+                    .map(|record| record.object),
+            )
+            .collect::<Vec<_>>();
+        let snapshot = ReloadSnapshot {
+            layouts: self
+                .field_layouts
+                .iter()
+                .filter(|(key, _)| key.module == module)
+                .map(|(key, layout)| (*key, layout.clone()))
+                .collect(),
+            classes: self
+                .classes
+                .iter()
+                .filter(|(key, _)| key.module == module)
+                .map(|(key, class)| (*key, *class))
+                .collect(),
+            globals: self.heap.module(module).globals.clone(),
+            global_bindings: std::mem::take(&mut self.heap.module_mut(module).global_bindings),
+            closure: self.heap.module(module).closure,
+            extensions: std::mem::take(&mut self.heap.module_mut(module).extensions),
+            links: linked
+                .into_iter()
+                .map(|linked| {
+                    let object = self.heap.module(linked);
+                    LinkSnapshot {
+                        module: linked,
+                        linked_reads: object.linked_reads.clone(),
+                        extension_sources: object.extension_sources.clone(),
+                        exports: object.exports.clone(),
+                    }
+                })
+                .collect(),
+        };
+        self.field_layouts.retain(|key, _| key.module != module);
+        self.classes.retain(|key, _| key.module != module);
+        self.world_version += 1;
+
+        let bindings = program.linked.modules.get(id).map(CompileBindings::from_linked_module);
+        let cfg = program.project_universe.cfg_context(id.project);
+        let closure = match self.compile_closure_with_cfg(module, &source, bindings, &id.path, &cfg) {
+            Ok(closure) => closure,
+            Err(err) => {
+                let source_id = self.heap.module(module).sources.len().saturating_sub(1) as u32;
+                self.compiler_error(&err, module, source_id);
+                self.restore_reload(module, snapshot);
+                return Err(err);
+            }
+        };
+        if let Err(err) = self.check_reload_layouts(id, &snapshot) {
+            self.restore_reload(module, snapshot);
+            return Err(err);
+        }
+
+        if let Err(err) = self.link_module_reads(id, compiled, program) {
+            self.restore_reload(module, snapshot);
+            return Err(err);
+        }
+        self.heap.module_mut(module).closure = Some(closure);
+        let state = std::mem::replace(
+            &mut self.module_registry.get_mut(id).expect("module registered").state,
+            ModuleState::Initializing,
+        );
+        if let Err(err) = self.run_in_module(module, closure) {
+            self.report_runtime_error(&err);
+            self.module_registry.get_mut(id).expect("module registered").state = state;
+            self.restore_reload(module, snapshot);
+            return Err(err);
+        }
+
+        // Fresh rows built by the initializer, mapped to the rows they replace.
+        let mut renames = HashMap::new();
+        for (key, old) in &snapshot.classes {
+            if let Some(&new) = self.classes.get(key)
+                && new != *old
+            {
+                renames.insert(new, *old);
+            }
+        }
+        for (key, _) in &snapshot.layouts {
+            let Some((&new, &old)) = self.classes.get(key).and_then(|new| renames.get_key_value(new)) else {
+                continue;
+            };
+            let superclass = self.heap.class(new).superclass.map(|parent| renames.get(&parent).copied().unwrap_or(parent));
+            if superclass != self.heap.class(old).superclass {
+                let err = RuntimeError::IncompatibleReload {
+                    module: id.to_string(),
+                    class: self.heap.class(old).name.clone(),
+                    reason: "changed its superclass".to_string(),
+                };
+                self.module_registry.get_mut(id).expect("module registered").state = state;
+                self.restore_reload(module, snapshot);
+                return Err(err.into());
+            }
+        }
+
+        if let Err(err) = self.relink_reloaded(id, program, &plan) {
+            self.module_registry.get_mut(id).expect("module registered").state = state;
+            self.restore_reload(module, snapshot);
+            return Err(err);
+        }
+
+        let patched = self.patch_reloaded_classes(module, &snapshot, &renames);
+        let migrated = self.migrate_reloaded_instances(&renames);
+        self.world_version += 1;
+
+        let mut hasher = DefaultHasher::new();
+        source.hash(&mut hasher);
+        let record = self.module_registry.get_mut(id).expect("module registered");
+        record.program = RuntimeProgramId(record.program.0 + 1);
+        record.plan_fingerprint = ModulePlanFingerprint(hasher.finish());
+        record.state = ModuleState::Initialized;
+        record.failure = None;
+        self.module_registry.record_fingerprints(id, program);
+
+        Ok(ModuleReload { plan, patched, migrated })
+    }
+
+    /// Re-links the reloaded module's exports, then the reads and exports of
+    /// every dependent of a changed declaration.
+    fn relink_reloaded(&mut self, id: &ModuleId, program: &CompiledProgram, plan: &ReloadPlan) -> PhResult<()> {
+        self.link_module_exports(id, &program.modules[id])?;
+        for dependent in &plan.dependents {
+            let compiled = &program.modules[dependent];
+            self.link_module_reads(dependent, compiled, program)?;
+            self.link_module_exports(dependent, compiled)?;
+        }
+        Ok(())
+    }
+
+    /// Refuses new layouts that existing instances or subclasses of the
+    /// module's classes cannot follow.
+    fn check_reload_layouts(&mut self, id: &ModuleId, snapshot: &ReloadSnapshot) -> PhResult<()> {
+        let own_classes = snapshot.classes.iter().map(|(_, class)| *class).collect::<Vec<_>>();
+        for (key, old) in &snapshot.layouts {
+            let Some(new) = self.field_layouts.get(key) else {
+                continue;
+            };
+            let refuse = |vm: &VM, reason: String| RuntimeError::IncompatibleReload {
+                module: id.to_string(),
+                class: vm.resolve_symbol(key.name).to_string(),
+                reason,
+            };
+            for (old_fields, new_fields) in [(&old.field_slots, &new.field_slots), (&old.static_field_slots, &new.static_field_slots)] {
+                if let Some(field) = first_moved_field(old_fields, new_fields) {
+                    return Err(refuse(self, format!("field `{}` was removed or moved", self.resolve_symbol(field))).into());
+                }
+            }
+            let grew = new.field_count > old.field_count || new.static_field_count > old.static_field_count;
+            if grew && let Some((_, class)) = snapshot.classes.iter().find(|(class_key, _)| class_key == key) {
+                let outside = self.heap.subclasses_of(&[*class]).into_iter().find(|sub| !own_classes.contains(sub));
+                if let Some(sub) = outside {
+                    let reason = format!("adds fields but has subclass `{}` in another module", self.heap.class(sub).name);
+                    return Err(refuse(self, reason).into());
+                }
+            }
+        }
+        // Classes the new source no longer declares stay defined as before.
+        for (key, layout) in &snapshot.layouts {
+            if self.field_layouts.contains_key(key) {
+                continue;
+            }
+            self.field_layouts.insert(*key, layout.clone());
+            let meta_name = format!("{}.class", self.resolve_symbol(key.name));
+            let meta = ClassKey {
+                module: key.module,
+                name: self.interner.intern(&meta_name),
+            };
+            for (class_key, class) in &snapshot.classes {
+                if class_key == key || *class_key == meta {
+                    self.classes.insert(*class_key, *class);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Copies the fresh rows' methods and layouts onto the rows they replace,
+    /// and points everything that saw a fresh row back at the old one.
+    fn patch_reloaded_classes(&mut self, module: ObjRef, snapshot: &ReloadSnapshot, renames: &HashMap<ClassId, ClassId>) -> Vec<String> {
+        let remap = |class: ClassId| renames.get(&class).copied().unwrap_or(class);
+        let mut patched = Vec::new();
+        for (&new, &old) in renames {
+            let fresh = self.heap.class(new).clone();
+            let mut methods = fresh.methods.values().chain(fresh.rest_methods.values()).copied().collect::<Vec<_>>();
+            // A multimethod dispatcher's cases are methods of the row too.
+            let cases = methods
+                .iter()
+                .filter_map(|&method| self.heap.method(method).multi.as_ref())
+                .flat_map(|multi| multi.cases.iter().map(|&(_, case)| case))
+                .collect::<Vec<_>>();
+            methods.extend(cases);
+            let row = self.heap.class_mut(old);
+            let mut static_slots = fresh.static_slots.into_vec();
+            for (slot, value) in static_slots.iter_mut().zip(row.static_slots.iter()) {
+                *slot = *value;
+            }
+            row.methods = fresh.methods;
+            row.rest_methods = fresh.rest_methods;
+            row.base_names = fresh.base_names;
+            row.field_slots = fresh.field_slots;
+            row.field_count = fresh.field_count;
+            row.static_slots = static_slots.into_boxed_slice();
+            row.attributes = fresh.attributes;
+            row.is_abstract = fresh.is_abstract;
+            row.is_mixin = fresh.is_mixin;
+            row.is_protocol = fresh.is_protocol;
+            row.mixins = fresh.mixins.into_iter().map(remap).collect();
+            row.requirements = fresh.requirements;
+            if !row.name.ends_with(".class") {
+                patched.push(row.name.clone());
+            }
+            for method in methods {
+                let method = self.heap.method_mut(method);
+                method.holder = method.holder.map(remap);
+                method.access_owner = method.access_owner.map(remap);
+                if let Some(multi) = method.multi.as_mut() {
+                    for class in multi.cases.iter_mut().flat_map(|(classes, _)| classes.iter_mut()) {
+                        *class = remap(*class);
+                    }
+                    multi.cache.clear();
+                }
+            }
+        }
+        patched.sort();
+
+        let fresh_rows = renames.keys().copied().collect::<Vec<_>>();
+        for sub in self.heap.subclasses_of(&fresh_rows) {
+            let row = self.heap.class_mut(sub);
+            row.superclass = row.superclass.map(remap);
+        }
+        let globals = self.heap.module(module).globals.clone();
+        for (slot, value) in globals.into_iter().enumerate() {
+            if let Some(&old) = value.as_obj().and_then(|class| renames.get(&class)) {
+                self.heap.module_mut(module).globals[slot] = Value::obj(old);
+            }
+        }
+        for (key, class) in &snapshot.classes {
+            self.classes.insert(*key, *class);
+        }
+        // Extensions the re-run declared on the module's own fresh rows.
+        let extensions = std::mem::take(&mut self.heap.module_mut(module).extensions);
+        for ((holder, selector), method) in extensions {
+            let method_row = self.heap.method_mut(method);
+            method_row.holder = method_row.holder.map(remap);
+            self.heap.module_mut(module).extensions.insert((remap(holder), selector), method);
+        }
+        patched
+    }
+
+    /// Moves instances of the fresh rows onto the old ones and grows the
+    /// slots of old instances to the patched layout.
+    fn migrate_reloaded_instances(&mut self, renames: &HashMap<ClassId, ClassId>) -> usize {
+        let fresh_rows = renames.keys().copied().collect::<Vec<_>>();
+        for instance in self.heap.instances_of(&fresh_rows) {
+            let instance = self.heap.instance_mut(instance);
+            instance.class = renames[&instance.class];
+        }
+        let old_rows = renames.values().copied().collect::<Vec<_>>();
+        let mut migrated = 0;
+        for handle in self.heap.instances_of(&old_rows) {
+            let field_count = self.heap.class(self.heap.instance(handle).class).field_count as usize;
+            let instance = self.heap.instance_mut(handle);
+            if instance.slots.len() < field_count {
+                let mut slots = std::mem::take(&mut instance.slots).into_vec();
+                slots.resize(field_count, Value::nil());
+                instance.slots = slots.into_boxed_slice();
+                migrated += 1;
+            }
+        }
+        migrated
+    }
+
+    /// Puts back the layouts, class bindings, globals, extensions, links and
+    /// initializer a refused reload replaced.
+    fn restore_reload(&mut self, module: ObjRef, snapshot: ReloadSnapshot) {
+        self.field_layouts.retain(|key, _| key.module != module);
+        self.field_layouts.extend(snapshot.layouts);
+        self.classes.retain(|key, _| key.module != module);
+        self.classes.extend(snapshot.classes);
+        let object = self.heap.module_mut(module);
+        // Globals the refused run declared go away; slots are append-only, so
+        // they are exactly those past the snapshot.
+        let restored = snapshot.globals.len();
+        object.globals.truncate(restored);
+        object.globals.copy_from_slice(&snapshot.globals);
+        object.name_to_slot.retain(|_, slot| *slot < restored);
+        object.globals_version += 1;
+        object.global_bindings = snapshot.global_bindings;
+        object.closure = snapshot.closure;
+        object.extensions = snapshot.extensions;
+        for links in snapshot.links {
+            let object = self.heap.module_mut(links.module);
+            object.linked_reads = links.linked_reads;
+            object.extension_sources = links.extension_sources;
+            object.exports = links.exports;
+        }
+        self.world_version += 1;
+    }
+}
+
+/// The first field of `old` not at the same position and slot in `new`.
+fn first_moved_field(old: &IndexMap<Symbol, u16>, new: &IndexMap<Symbol, u16>) -> Option<Symbol> {
+    old.iter()
+        .enumerate()
+        .find(|(index, (name, slot))| new.get_index(*index) != Some((name, slot)))
+        .map(|(_, (name, _))| *name)
+}
@@ -2,26 +2,52 @@
 
 use super::descriptor::{PrimitiveDescriptor, PrimitiveEntry};
 use super::registry::PRIMITIVES;
-use crate::error::PhResult;
+use crate::error::{PhResult, RuntimeError};
 use crate::heap::Object;
 use crate::method::{MemberVisibility, MethodObject, Signature};
 use crate::vm::VM;
-use phalcom_native_meta::{NativeDispatch, NativeVisibility};
+use phalcom_native_meta::{NativeDispatch, NativeOwner, NativeVisibility};
 
 /// Sorts registered descriptors by key and installs each into the VM.
+///
+/// Kernel owners sort before extension owners, so an extension primitive
+/// can never be installed ahead of the kernel floor it builds on. When the
+/// extension registry is inconsistent
+/// ([`phalcom_native_meta::validate_extensions`]) only the kernel
+/// primitives are installed.
+///
+/// # Errors
+///
+/// `native.extension_registry` listing the registry's problems, once the
+/// kernel primitives are in; or whatever installing a primitive raises.
 pub fn install_registered_primitives(vm: &mut VM) -> PhResult<()> {
-    let mut descriptors: Vec<&'static PrimitiveDescriptor> = PRIMITIVES.iter().collect();
+    let problems = phalcom_native_meta::validate_extensions();
+
+    let mut descriptors: Vec<&'static PrimitiveDescriptor> = PRIMITIVES
+        .iter()
+        .filter(|d| problems.is_empty() || matches!(d.surface.key.owner, NativeOwner::Universe(_)))
+        .collect();
     descriptors.sort_by_key(|d| d.surface.key.sort_key());
 
     for desc in descriptors {
         install_one(vm, desc)?;
     }
 
+    if !problems.is_empty() {
+        return Err(RuntimeError::NotAllowed(format!(
+            "native.extension_registry: invalid native extension registry; its primitives were not installed:\n{}",
+            problems.join("\n")
+        ))
+        .into());
+    }
     Ok(())
 }
 
 fn install_one(vm: &mut VM, desc: &PrimitiveDescriptor) -> PhResult<()> {
-    let owner = vm.universe.classes.resolve(desc.surface.key.owner);
+    let owner = match desc.surface.key.owner {
+        NativeOwner::Universe(key) => vm.universe.classes.resolve(key),
+        NativeOwner::Extension(class) => vm.universe.extension_classes.resolve(class),
+    };
 
     let target = match desc.surface.key.side {
         NativeDispatch::Instance => owner,
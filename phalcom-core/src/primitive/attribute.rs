@@ -106,5 +106,10 @@ pub fn attribute_freeze(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhRes
             .into());
         }
     }
+    // A class is frozen once, after every member's own freeze: its chains
+    // of Runtime-tier interceptors are final now.
+    if matches!(vm.heap.get(id), Object::Class(_)) {
+        vm.compose_interceptors(id);
+    }
     Ok(vm.none_value())
 }
@@ -316,6 +316,8 @@ pub fn block_while_true(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResu
 ///   snapshot at entry; a traceback rendered *above* this point sees the
 ///   stack only down to this boundary (first-match-wins, error-handling.md
 ///   §2; capture-at-boundary is PDR-0010 §3's job).
+/// - **A [`RuntimeError::LimitExceeded`]** — re-propagated after the same
+///   unwind, never matched: a sandbox limit is uncatchable (`vm/limits.rs`).
 /// - **Any other `Err`** (`DeadFrameError`, a future fiber `abort` payload,
 ///   …) — **wrapped into a synthetic base `Error` instance** carrying the
 ///   rendered message, then run through the *same* `is` probe as a real
@@ -353,6 +355,11 @@ pub fn block_on(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value
 
     match outcome {
         Ok(v) => Ok(v),
+        // A sandbox limit is never offered to a handler (`vm/limits.rs`).
+        Err(err) if err.is_limit_exceeded() => {
+            vm.unwind_to(stack_len, frames_len);
+            Err(err)
+        }
         Err(mut err) => {
             let captured_tb = vm.capture_frames(frames_len);
             let error = match &err {
@@ -418,6 +425,9 @@ pub fn block_on(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value
 /// as `run_until` produced them, so an enclosing `on`/the top-level trace
 /// renderer still sees the full stack.
 ///
+/// The one exception is a [`RuntimeError::LimitExceeded`]: the cleanup does
+/// not run at all (`vm/limits.rs`).
+///
 /// **Cleanup-supersedes** (ADR-0008 §4.2): if the cleanup block itself
 /// diverges — raises, or non-locally returns — that new outcome **replaces**
 /// the pending one instead of being merely run for effect.
@@ -429,6 +439,11 @@ pub fn block_on(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value
 pub fn block_ensure(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value> {
     let cleanup = args[0];
     let outcome = block_call(vm, receiver, &[]);
+    // A tripped sandbox limit skips the cleanup: it is untrusted `.ph` code,
+    // and running it would hand the program back the budget that ran out.
+    if outcome.as_ref().is_err_and(PhError::is_limit_exceeded) {
+        return outcome;
+    }
 
     // The pending outcome lives only in this Rust local while the cleanup block
     // runs, and that block re-enters the interpreter — so its back-edge
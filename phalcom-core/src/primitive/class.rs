@@ -70,6 +70,200 @@ pub fn behavior_methods(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhRes
     Ok(Value::obj(vm.heap.alloc_list(selectors)))
 }
 
+/// Signature: `Behavior::ancestors` — the receiver's linearization: itself,
+/// its mixins, then the same for each superclass up to `Object`
+/// (`vm/mixin.rs`). `super` sends follow this order.
+#[phalcom_native_macros::primitive(
+    Behavior,
+    "ancestors",
+    params = [],
+    returns = List,
+    types = "() -> List",
+    effects = pure
+)]
+pub fn behavior_ancestors(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    let class_id = expect_class(vm, receiver)?;
+    let ancestors: Vec<Value> = vm.ancestors(class_id).into_iter().map(Value::obj).collect();
+    Ok(Value::obj(vm.heap.alloc_list(ancestors)))
+}
+
+/// Signature: `Behavior::mixins` — the mixins the receiver composes directly,
+/// in declaration order.
+#[phalcom_native_macros::primitive(
+    Behavior,
+    "mixins",
+    params = [],
+    returns = List,
+    types = "() -> List",
+    effects = pure
+)]
+pub fn behavior_mixins(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    let class_id = expect_class(vm, receiver)?;
+    let mixins: Vec<Value> = vm.heap.class(class_id).mixins.iter().map(|mixin| Value::obj(*mixin)).collect();
+    Ok(Value::obj(vm.heap.alloc_list(mixins)))
+}
+
+/// Signature: `Behavior::isMixin` — whether the receiver is a `@mixin`
+/// declaration.
+#[phalcom_native_macros::primitive(
+    Behavior,
+    "isMixin",
+    params = [],
+    returns = Bool,
+    types = "() -> Bool",
+    effects = pure
+)]
+pub fn behavior_is_mixin(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    let class_id = expect_class(vm, receiver)?;
+    Ok(Value::bool(vm.heap.class(class_id).is_mixin))
+}
+
+/// Signature: `Behavior::isProtocol` — whether the receiver is a `@protocol`
+/// declaration.
+#[phalcom_native_macros::primitive(
+    Behavior,
+    "isProtocol",
+    params = [],
+    returns = Bool,
+    types = "() -> Bool",
+    effects = pure
+)]
+pub fn behavior_is_protocol(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    let class_id = expect_class(vm, receiver)?;
+    Ok(Value::bool(vm.heap.class(class_id).is_protocol))
+}
+
+/// Signature: `Behavior::requirements` — the selector Symbols a mixin
+/// requires of its hosts, or a protocol of its conformers; empty for an
+/// ordinary class.
+#[phalcom_native_macros::primitive(
+    Behavior,
+    "requirements",
+    params = [],
+    returns = List,
+    types = "() -> List",
+    effects = pure
+)]
+pub fn behavior_requirements(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    let class_id = expect_class(vm, receiver)?;
+    let selectors: Vec<Value> = vm.heap.class(class_id).requirements.iter().map(|selector| Value::symbol(*selector)).collect();
+    Ok(Value::obj(vm.heap.alloc_list(selectors)))
+}
+
+/// Signature: `Behavior::_$mixin(_)` — marks the receiver as a mixin; the
+/// argument is the `@mixin(...)` argument list (selector Symbols and mixins).
+#[phalcom_native_macros::primitive(
+    Behavior,
+    "_$mixin(_)",
+    params = [List],
+    returns = Option,
+    types = "(List) -> Option",
+    visibility = internal
+)]
+pub fn behavior_mixin_(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let class_id = expect_class(vm, receiver)?;
+    let items = mixin_arguments(vm, &args[0])?;
+    vm.declare_mixin(class_id, &items)?;
+    Ok(vm.none_value())
+}
+
+/// Signature: `Behavior::_$compose(_)` — records the `@compose(...)` mixin
+/// list of the receiver.
+#[phalcom_native_macros::primitive(
+    Behavior,
+    "_$compose(_)",
+    params = [List],
+    returns = Option,
+    types = "(List) -> Option",
+    visibility = internal
+)]
+pub fn behavior_compose_(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let class_id = expect_class(vm, receiver)?;
+    let items = mixin_arguments(vm, &args[0])?;
+    vm.declare_composition(class_id, &items)?;
+    Ok(vm.none_value())
+}
+
+/// Signature: `Behavior::_$protocol(_)` — marks the receiver as a protocol;
+/// the argument is the `@protocol(...)` list of included protocols.
+#[phalcom_native_macros::primitive(
+    Behavior,
+    "_$protocol(_)",
+    params = [List],
+    returns = Option,
+    types = "(List) -> Option",
+    visibility = internal
+)]
+pub fn behavior_protocol_(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let class_id = expect_class(vm, receiver)?;
+    let items = mixin_arguments(vm, &args[0])?;
+    vm.declare_protocol(class_id, &items)?;
+    Ok(vm.none_value())
+}
+
+/// Signature: `Behavior::_$multimethod(_,_,_)` — installs a `@multimethod`
+/// case: the case method, the `List` of classes it dispatches on and whether
+/// it is class-side.
+#[phalcom_native_macros::primitive(
+    Behavior,
+    "_$multimethod(_,_,_)",
+    params = [Method, List, Bool],
+    returns = Option,
+    types = "(Method, List, Bool) -> Option",
+    visibility = internal
+)]
+pub fn behavior_multimethod_(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let class_id = expect_class(vm, receiver)?;
+    let case = args[0]
+        .as_obj()
+        .filter(|&id| matches!(vm.heap.get(id), Object::Method(_)))
+        .ok_or_else(|| RuntimeError::Type {
+            expected: "Method",
+            found: args[0].type_name(),
+        })?;
+    let classes = mixin_arguments(vm, &args[1])?;
+    vm.declare_multimethod_case(class_id, case, &classes, args[2].as_bool() == Some(true))?;
+    Ok(vm.none_value())
+}
+
+/// Signature: `Behavior::_$extend(_,_)` — installs one member of an
+/// `extend` block: the method and whether it is class-side. The extension
+/// belongs to the module whose code sent this.
+#[phalcom_native_macros::primitive(
+    Behavior,
+    "_$extend(_,_)",
+    params = [Method, Bool],
+    returns = Option,
+    types = "(Method, Bool) -> Option",
+    visibility = internal
+)]
+pub fn behavior_extend_(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let class_id = expect_class(vm, receiver)?;
+    let method = args[0]
+        .as_obj()
+        .filter(|&id| matches!(vm.heap.get(id), Object::Method(_)))
+        .ok_or_else(|| RuntimeError::Type {
+            expected: "Method",
+            found: args[0].type_name(),
+        })?;
+    let module = vm
+        .current_module()
+        .ok_or_else(|| RuntimeError::Internal("`_$extend(_,_)` sent outside any module".to_string()))?;
+    vm.declare_extension(module, class_id, method, args[1].as_bool() == Some(true))?;
+    Ok(vm.none_value())
+}
+
+fn mixin_arguments(vm: &VM, list: &Value) -> PhResult<Vec<Value>> {
+    match list.as_obj().and_then(|id| vm.heap.as_list(id)) {
+        Some(list) => Ok(list.elements().to_vec()),
+        None => Err(RuntimeError::Type {
+            expected: "List",
+            found: list.type_name(),
+        }
+        .into()),
+    }
+}
+
 /// Signature: `Behavior#>>(_)` — extracts either one effective exact `Method`
 /// or an immutable `MethodFamily` snapshot from a selector-spec value.
 ///
@@ -12,7 +12,7 @@
 //! its own `base_frames` computed against the *currently* running fiber,
 //! which a switch underneath it would corrupt.
 
-use crate::error::{PhResult, RuntimeError};
+use crate::error::{FrameRecord, PhResult, RuntimeError};
 use crate::frame::CallContext;
 use crate::heap::InstanceObject;
 use crate::heap::{FiberResumeMode, FiberStatus, ObjRef, Object};
@@ -173,11 +173,23 @@ pub(crate) fn new_fiber_ref(vm: &mut VM, entry: Value) -> PhResult<ObjRef> {
     // counter.
     fiber.seq = vm.next_fiber_seq;
     vm.next_fiber_seq += 1;
-    let (spawn_file, spawn_line) = if let Some(caller_frame) = vm.frames.last() {
-        let closure = vm.heap.closure(caller_frame.closure);
+    // The spawn site is the send that spawned the fiber — unless that send is
+    // core machinery (`Future.async`, `Generator`) running a user's block, where
+    // the block's own definition is the site a reader can act on.
+    let entry_closure = match vm.heap.get(entry_id) {
+        Object::Block(block) => block.closure,
+        _ => entry_id,
+    };
+    let core = vm.core_module();
+    let user_entry = Some(vm.heap.closure(entry_closure).module) != core;
+    let site = match vm.frames.last() {
+        Some(frame) if user_entry && Some(vm.heap.closure(frame.closure).module) == core => Some((entry_closure, 0)),
+        Some(frame) => Some((frame.closure, frame.ip.saturating_sub(1))),
+        None => None,
+    };
+    let (spawn_file, spawn_line) = if let Some((closure_ref, span_index)) = site {
+        let closure = vm.heap.closure(closure_ref);
         let module = vm.heap.module(closure.module);
-        let span_index = caller_frame.ip.saturating_sub(1);
-        let span = closure.callable.chunk.span_at(span_index);
         let source = module.source_at(closure.callable.chunk.source_id);
         let line = source.as_ref().map_or(0, |text| closure.callable.chunk.line_at(span_index, text));
         (Some(module.symbol()), line)
@@ -291,6 +303,63 @@ pub fn fiber_is_root(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhResult
     Ok(Value::bool(vm.heap.fiber(fiber_ref).resumer.is_none()))
 }
 
+/// Signature: `Fiber#canSuspend` — `true` iff the receiver is the running
+/// fiber and no native re-entrant frame has opened on it since it was last
+/// resumed, i.e. a `Fiber.yield` here would pass the restricted-yield guard
+/// (ADR-0030 §4). The root fiber answers by the same depth rule, though it
+/// has nowhere to yield to; pair it with `isRoot`.
+///
+/// The predicate form of [`fiber_yield`]'s second refusal, for the same
+/// reason [`fiber_is_root`] is the first's: `Future#await` asks it before
+/// parking so that an `await` on a pending future inside a `try` body says
+/// so in its own words, rather than surfacing the guard's generic message.
+///
+/// # Errors
+///
+/// Returns [`RuntimeError::Type`] if `receiver` is not a `Fiber`.
+pub fn fiber_can_suspend(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    let fiber_ref = expect_fiber(vm, receiver)?;
+    let can = fiber_ref == vm.current && vm.native_reentry_depth == vm.heap.fiber(fiber_ref).floor_depth;
+    Ok(Value::bool(can))
+}
+
+/// Signature: `Fiber#_$rethrow()` — raises a `Failed` receiver's error again
+/// in the running fiber, its traceback stitched from the current frames, the
+/// boundary into the receiver, and the frames the receiver failed in
+/// ([`crate::heap::FiberObject::failure_trace`]). `Future#await` uses it for
+/// a future an `@async` body rejected, so the error reads through the
+/// `await` into the body that raised it.
+///
+/// # Errors
+///
+/// Returns [`RuntimeError::Type`] if `receiver` is not a `Fiber`,
+/// [`RuntimeError::NotAllowed`] (`fiber.rethrow`) if it has not failed, and
+/// otherwise [`RuntimeError::Raise`] carrying its error.
+pub fn fiber_rethrow(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    let fiber_ref = expect_fiber(vm, receiver)?;
+    if vm.heap.fiber(fiber_ref).status != FiberStatus::Failed {
+        return Err(RuntimeError::NotAllowed("fiber.rethrow: only a failed fiber has an error to raise again".to_string()).into());
+    }
+    let error = vm.heap.fiber(fiber_ref).result;
+    let message_sym = vm.get_or_intern("message");
+    let rendered = vm.send_dynamic(error, message_sym, &[])?.to_string(vm);
+    let mut traceback = vm.capture_frames(0);
+    let fiber = vm.heap.fiber(fiber_ref);
+    traceback.push(FrameRecord::FiberBoundary {
+        seq: fiber.seq,
+        spawn_file: fiber.spawn_file,
+        spawn_line: fiber.spawn_line,
+    });
+    traceback.extend(fiber.failure_trace.iter().cloned());
+    Err(RuntimeError::Raise {
+        error,
+        rendered,
+        traceback: Some(traceback),
+        help: None,
+    }
+    .into())
+}
+
 /// Signature: `Fiber#error` — the captured `Error` as `Option`, if the
 /// receiver is `Failed`; `None` otherwise (including `Done`, where
 /// [`crate::heap::FiberObject::result`] holds the return value, not an
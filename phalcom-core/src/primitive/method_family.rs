@@ -111,6 +111,46 @@ pub fn method_family_method_for_shape(vm: &mut VM, receiver: Value, args: Argume
 
 fn method_family_method_for_as(vm: &mut VM, receiver: &Value, args: &[Value], caller_authority: (Option<crate::heap::ClassId>, bool)) -> PhResult<Value> {
     let family = expect_method_family(vm, receiver)?;
+    match captured_method(vm, family, args, "methodFor")? {
+        Some(method) if vm.authorize_method_access_as(method, caller_authority.0, caller_authority.1).is_ok() => Ok(Value::obj(method)),
+        _ => Ok(vm.none_value()),
+    }
+}
+
+/// Shape-aware gateway for `MethodFamily#specializations(_)`: the cases of a
+/// captured multimethod (`vm/multimethod.rs`), inherited cases first, as a
+/// `List` of `[classes, method]` pairs — `classes` lists the receiver's dispatch
+/// class, then one per argument. A plain captured method has none; a
+/// selector the family did not capture, or one the caller may not see,
+/// answers `None`.
+///
+/// # Errors
+///
+/// Propagates the same type and arity errors as [`method_family_method_for`].
+pub fn method_family_specializations_shape(vm: &mut VM, receiver: Value, args: ArgumentView) -> PhResult<CallOutcome> {
+    let selector = args.positional(vm, 0).ok_or_else(|| RuntimeError::Arity {
+        signature: "specializations",
+        expected: 1,
+        found: args.positional_count(),
+    })?;
+    let family = expect_method_family(vm, &receiver)?;
+    let caller_authority = args.caller_authority();
+    let method = match captured_method(vm, family, &[selector], "specializations")? {
+        Some(method) if vm.authorize_method_access_as(method, caller_authority.0, caller_authority.1).is_ok() => method,
+        _ => return Ok(CallOutcome::Returned(vm.none_value())),
+    };
+    let cases = vm.multimethod_cases(method);
+    let mut specializations = Vec::with_capacity(cases.len());
+    for (classes, case) in &cases {
+        let classes = vm.heap.alloc_list(classes.iter().map(|&class| Value::obj(class)).collect());
+        specializations.push(Value::obj(vm.heap.alloc_list(vec![Value::obj(classes), Value::obj(*case)])));
+    }
+    Ok(CallOutcome::Returned(Value::obj(vm.heap.alloc_list(specializations))))
+}
+
+/// The method `family` captured for the selector in `args`, exact routes
+/// first.
+fn captured_method(vm: &VM, family: ObjRef, args: &[Value], signature: &'static str) -> PhResult<Option<ObjRef>> {
     let selector = match args.first() {
         Some(arg) => match arg.symbol_value() {
             Some(selector) => selector,
@@ -124,7 +164,7 @@ fn method_family_method_for_as(vm: &mut VM, receiver: &Value, args: &[Value], ca
         },
         None => {
             return Err(RuntimeError::Arity {
-                signature: "methodFor",
+                signature,
                 expected: 1,
                 found: 0,
             }
@@ -132,18 +172,12 @@ fn method_family_method_for_as(vm: &mut VM, receiver: &Value, args: &[Value], ca
         }
     };
 
-    let method = {
-        let family = vm.heap.method_family(family);
-        family.exact_methods.get(&selector).copied().or_else(|| {
-            family
-                .rest_candidates
-                .iter()
-                .copied()
-                .find(|method| vm.heap.method(*method).signature.selector == selector)
-        })
-    };
-    match method {
-        Some(method) if vm.authorize_method_access_as(method, caller_authority.0, caller_authority.1).is_ok() => Ok(Value::obj(method)),
-        _ => Ok(vm.none_value()),
-    }
+    let family = vm.heap.method_family(family);
+    Ok(family.exact_methods.get(&selector).copied().or_else(|| {
+        family
+            .rest_candidates
+            .iter()
+            .copied()
+            .find(|method| vm.heap.method(*method).signature.selector == selector)
+    }))
 }
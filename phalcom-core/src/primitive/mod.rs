@@ -29,6 +29,7 @@ pub mod symbol;
 pub mod system;
 pub mod tuple;
 pub mod typing;
+pub mod weak;
 
 #[non_exhaustive]
 pub struct Sig;
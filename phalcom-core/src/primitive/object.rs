@@ -16,6 +16,7 @@ use crate::expect_value;
 use crate::heap::InstanceObject;
 use crate::heap::Object;
 use crate::method::{ArgumentView, CallOutcome, SignatureKind, decode_selector};
+use crate::primitive::expect_class;
 use crate::value::Value;
 use crate::vm::VM;
 
@@ -239,6 +240,29 @@ pub fn object_responds_to(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhRe
     Ok(Value::bool(responds))
 }
 
+/// Signature: `Object::conformsTo(_)` — whether the receiver answers every
+/// requirement of a `@protocol` class (`vm/protocol.rs`), structurally:
+/// its class need not name the protocol anywhere.
+#[phalcom_native_macros::primitive(
+    Object,
+    "conformsTo(_)",
+    params = [Class],
+    returns = Bool,
+    types = "(Class) -> Bool",
+    effects = pure
+)]
+pub fn object_conforms_to(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let protocol = expect_class(vm, &args[0])?;
+    if !vm.heap.class(protocol).is_protocol {
+        return Err(RuntimeError::NotAllowed(format!(
+            "protocol.not_a_protocol: `conformsTo(_)` takes a `@protocol` class, not class `{}`",
+            vm.heap.class(protocol).name
+        ))
+        .into());
+    }
+    Ok(Value::bool(vm.conforms_to(*receiver, protocol)))
+}
+
 /// Signature: `Object::methodFor(_)` — reifies the
 #[phalcom_native_macros::primitive(
     Object,
@@ -100,6 +100,7 @@ pub fn module_package_info(vm: &mut VM, receiver: &Value, _args: &[Value]) -> Ph
             phalcom_modules::ProjectIdentity::Builtin(b) => match b {
                 phalcom_modules::BuiltinProject::Universe => PackageInfoDescriptor::builtin_universe(None),
                 phalcom_modules::BuiltinProject::Std => PackageInfoDescriptor::builtin_std(None),
+                phalcom_modules::BuiltinProject::Native => PackageInfoDescriptor::builtin_native(),
             },
             phalcom_modules::ProjectIdentity::Resolved(_) => {
                 let name = pkg.name.clone();
@@ -362,6 +363,7 @@ pub fn package_package_info(vm: &mut VM, receiver: &Value, _args: &[Value]) -> P
         phalcom_modules::ProjectIdentity::Builtin(b) => match b {
             phalcom_modules::BuiltinProject::Universe => PackageInfoDescriptor::builtin_universe(None),
             phalcom_modules::BuiltinProject::Std => PackageInfoDescriptor::builtin_std(None),
+            phalcom_modules::BuiltinProject::Native => PackageInfoDescriptor::builtin_native(),
         },
         phalcom_modules::ProjectIdentity::Resolved(_) => {
             let name = root.name.clone();
@@ -89,6 +89,65 @@ pub fn system_gc(vm: &mut VM, _receiver: &Value, _args: &[Value]) -> PhResult<Va
     Ok(vm.none_value())
 }
 
+/// Signature: `System.gcStats` — collector pause accounting since VM start,
+/// as a `Record`:
+///
+/// `#{fullCollections:, incrementalCycles:, markSlices:, sweepSlices:,
+/// pauses:, objectsSwept:, liveObjects:, liveBytes:, marking:, totalPauseMicros:,
+/// maxPauseMicros:, lastPauseMicros:, histogram:}`, where `histogram` is a
+/// `List` of `#{upToMicros:, count:}` buckets in ascending order and the last
+/// bucket's `upToMicros` is `None` (everything longer). `liveBytes` is the
+/// estimate a [`VmLimits::max_heap_bytes`](crate::vm::VmLimits::max_heap_bytes)
+/// cap is checked against. Reading the stats allocates but never collects.
+#[phalcom_native_macros::primitive(
+    System,
+    "gcStats",
+    params = [],
+    returns = Record,
+    types = "() -> Record",
+    side = class
+)]
+pub fn system_gc_stats(vm: &mut VM, _receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    fn int(n: u64) -> Value {
+        Value::int(i64::try_from(n).unwrap_or(i64::MAX))
+    }
+    fn micros(duration: std::time::Duration) -> Value {
+        int(u64::try_from(duration.as_micros()).unwrap_or(u64::MAX))
+    }
+
+    let stats = vm.heap.gc_stats().clone();
+    let up_to = vm.get_or_intern("upToMicros");
+    let count = vm.get_or_intern("count");
+    let mut buckets = Vec::new();
+    for (bound, hits) in stats.histogram() {
+        let bound = bound.map_or_else(|| vm.none_value(), int);
+        buckets.push(record(vm, vec![(up_to, bound), (count, int(hits))])?);
+    }
+    let histogram = Value::obj(vm.heap.alloc_list(buckets));
+
+    let fields = [
+        ("fullCollections", int(stats.full_collections)),
+        ("incrementalCycles", int(stats.incremental_cycles)),
+        ("markSlices", int(stats.mark_slices)),
+        ("sweepSlices", int(stats.sweep_slices)),
+        ("pauses", int(stats.pauses())),
+        ("objectsSwept", int(stats.objects_swept)),
+        ("liveObjects", int(vm.heap.live_count() as u64)),
+        ("liveBytes", int(vm.heap.live_bytes() as u64)),
+        ("marking", Value::bool(vm.heap.is_marking())),
+        ("totalPauseMicros", micros(stats.total_pause)),
+        ("maxPauseMicros", micros(stats.max_pause)),
+        ("lastPauseMicros", micros(stats.last_pause)),
+        ("histogram", histogram),
+    ];
+    let fields = fields.into_iter().map(|(label, value)| (vm.get_or_intern(label), value)).collect();
+    record(vm, fields)
+}
+
+fn record(vm: &mut VM, fields: Vec<(crate::interner::Symbol, Value)>) -> PhResult<Value> {
+    crate::product::finish_record(vm, fields).map_err(|error| crate::product::runtime_error(vm, "Record field", error).into())
+}
+
 /// Signature: `System._$write(_)` — raw stdout write of an already-formed `String`.
 #[phalcom_native_macros::primitive(
     System,
@@ -0,0 +1,248 @@
+//! Native primitives on `WeakRef`, `WeakMap` and `FinalizationRegistry`.
+//!
+//! The floor under `collections/weak.ph`: allocation, identity-keyed table
+//! access, and registry bookkeeping. None of these ever *run* a cleanup
+//! callback — that is the `.ph` `FinalizationRegistry#cleanupSome` loop, driven
+//! either by the user or by the drain fiber [`crate::vm::VM::force_gc`]
+//! schedules after a collection (`heap/weak.rs`).
+//!
+//! Every key and target is taken by **identity**: only a plain heap reference
+//! ([`Value::as_obj`]) qualifies. Immediates (`Int`, `Bool`, `Symbol`, `None`,
+//! `Some`-wrapped values) are never collected, so weakly holding one would
+//! be meaningless — they are rejected with an `ArgumentError`, as ECMAScript
+//! rejects primitive `WeakMap` keys.
+
+use crate::error::{PhResult, RuntimeError};
+use crate::heap::{FinalizationCell, FinalizationRegistryObject, ObjRef, Object, WeakMapObject, WeakRefObject};
+use crate::primitive::nil::wrap_some;
+use crate::value::Value;
+use crate::vm::VM;
+
+/// Extracts the identity handle a weak container may hold, or raises.
+fn weak_target(value: &Value, role: &str) -> PhResult<ObjRef> {
+    value.as_obj().ok_or_else(|| {
+        RuntimeError::ArgumentError(format!("{role} must be a heap object, not an immediate {}", value.type_name())).into()
+    })
+}
+
+fn expect_weak_ref(vm: &VM, value: &Value) -> PhResult<ObjRef> {
+    match value.as_obj() {
+        Some(id) if vm.heap.as_weak_ref(id).is_some() => Ok(id),
+        _ => Err(RuntimeError::Type {
+            expected: "WeakRef",
+            found: value.type_name(),
+        }
+        .into()),
+    }
+}
+
+fn expect_weak_map(vm: &VM, value: &Value) -> PhResult<ObjRef> {
+    match value.as_obj() {
+        Some(id) if matches!(vm.heap.try_get(id), Some(Object::WeakMap(_))) => Ok(id),
+        _ => Err(RuntimeError::Type {
+            expected: "WeakMap",
+            found: value.type_name(),
+        }
+        .into()),
+    }
+}
+
+fn expect_finalization_registry(vm: &VM, value: &Value) -> PhResult<ObjRef> {
+    match value.as_obj() {
+        Some(id) if matches!(vm.heap.try_get(id), Some(Object::FinalizationRegistry(_))) => Ok(id),
+        _ => Err(RuntimeError::Type {
+            expected: "FinalizationRegistry",
+            found: value.type_name(),
+        }
+        .into()),
+    }
+}
+
+/// Signature: `WeakRef.class::new(_)` — a weak handle to `args[0]`.
+///
+/// # Errors
+///
+/// Returns [`RuntimeError::ArgumentError`] if `args[0]` is an immediate.
+pub fn weak_ref_class_new(vm: &mut VM, _receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let target = weak_target(&args[0], "WeakRef target")?;
+    Ok(Value::obj(vm.heap.alloc(Object::WeakRef(WeakRefObject::new(target)))))
+}
+
+/// Signature: `WeakRef#deref` — `Some(target)` while the referent is live,
+/// `None` once a collection has cleared it.
+///
+/// # Errors
+///
+/// Returns [`RuntimeError::Type`] if the receiver is not a `WeakRef`.
+pub fn weak_ref_deref(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    let id = expect_weak_ref(vm, receiver)?;
+    match vm.heap.as_weak_ref(id).and_then(|weak_ref| weak_ref.target) {
+        Some(target) => Ok(wrap_some(vm, Value::obj(target))?),
+        None => Ok(vm.none_value()),
+    }
+}
+
+/// Signature: `WeakMap.class::new()` — allocates an empty ephemeron table.
+pub fn weak_map_class_new(vm: &mut VM, _receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    Ok(Value::obj(vm.heap.alloc(Object::WeakMap(Box::new(WeakMapObject::new())))))
+}
+
+/// Signature: `WeakMap::_$get(_)` — `Some(value)` on hit, `None` on absence.
+///
+/// # Errors
+///
+/// Returns [`RuntimeError::Type`] if the receiver is not a `WeakMap`, or
+/// [`RuntimeError::ArgumentError`] if the key is an immediate.
+pub fn weak_map_raw_get(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let id = expect_weak_map(vm, receiver)?;
+    let key = weak_target(&args[0], "WeakMap key")?;
+    match vm.heap.weak_map(id).get(key) {
+        Some(value) => Ok(wrap_some(vm, value)?),
+        None => Ok(vm.none_value()),
+    }
+}
+
+/// Signature: `WeakMap::_$put(_,_)` — stores `args[1]` under key `args[0]`.
+///
+/// # Errors
+///
+/// Returns [`RuntimeError::Type`] if the receiver is not a `WeakMap`, or
+/// [`RuntimeError::ArgumentError`] if the key is an immediate.
+pub fn weak_map_raw_put(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let id = expect_weak_map(vm, receiver)?;
+    let key = weak_target(&args[0], "WeakMap key")?;
+    vm.heap.weak_map_mut(id).insert(key, args[1]);
+    Ok(*receiver)
+}
+
+/// Signature: `WeakMap::_$has(_)` — whether the key has an entry.
+///
+/// An immediate key answers `false` rather than raising: it can never have
+/// been stored, so the question has a well-defined answer.
+///
+/// # Errors
+///
+/// Returns [`RuntimeError::Type`] if the receiver is not a `WeakMap`.
+pub fn weak_map_raw_has(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let id = expect_weak_map(vm, receiver)?;
+    Ok(Value::bool(args[0].as_obj().is_some_and(|key| vm.heap.weak_map(id).contains(key))))
+}
+
+/// Signature: `WeakMap::_$remove(_)` — drops the key's entry, answering
+/// whether one existed.
+///
+/// # Errors
+///
+/// Returns [`RuntimeError::Type`] if the receiver is not a `WeakMap`.
+pub fn weak_map_raw_remove(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let id = expect_weak_map(vm, receiver)?;
+    Ok(Value::bool(args[0].as_obj().is_some_and(|key| vm.heap.weak_map_mut(id).remove(key))))
+}
+
+/// Signature: `FinalizationRegistry.class::_$new(_)` — a registry over the
+/// cleanup callable `args[0]`, with no drain entry yet. The `.ph`
+/// `FinalizationRegistry.new(_)` installs one via `_$setDrain(_)`.
+pub fn finalization_registry_class_raw_new(vm: &mut VM, _receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let registry = FinalizationRegistryObject::new(args[0]);
+    Ok(Value::obj(vm.heap.alloc(Object::FinalizationRegistry(Box::new(registry)))))
+}
+
+/// Signature: `FinalizationRegistry::_$setDrain(_)` — installs the
+/// zero-argument entry the VM wraps in a fiber when cleanups are pending.
+///
+/// # Errors
+///
+/// Returns [`RuntimeError::Type`] if the receiver is not a
+/// `FinalizationRegistry` or `args[0]` is not a `Block`/`Closure` (the only
+/// shapes a fiber entry may take, `Fiber.new(_)`).
+pub fn finalization_registry_raw_set_drain(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let id = expect_finalization_registry(vm, receiver)?;
+    let drain = match args[0].as_obj() {
+        Some(drain) if matches!(vm.heap.get(drain), Object::Block(_) | Object::Closure(_)) => drain,
+        _ => {
+            return Err(RuntimeError::Type {
+                expected: "Closure",
+                found: args[0].type_name(),
+            }
+            .into());
+        }
+    };
+    vm.heap.finalization_registry_mut(id).drain = Some(drain);
+    Ok(vm.none_value())
+}
+
+/// Signature: `FinalizationRegistry::_$cleanup` — the cleanup callable.
+///
+/// # Errors
+///
+/// Returns [`RuntimeError::Type`] if the receiver is not a
+/// `FinalizationRegistry`.
+pub fn finalization_registry_raw_cleanup(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    let id = expect_finalization_registry(vm, receiver)?;
+    Ok(vm.heap.finalization_registry(id).callback)
+}
+
+/// Signature: `FinalizationRegistry::_$register(_,_,_)` — watches `args[0]`,
+/// handing `args[1]` to the callback once it dies; `args[2]` is the
+/// unregister token, or `None` for none.
+///
+/// # Errors
+///
+/// Returns [`RuntimeError::Type`] if the receiver is not a
+/// `FinalizationRegistry`, or [`RuntimeError::ArgumentError`] if the target
+/// (or a non-`None` token) is an immediate, or the held value *is* the target
+/// — a held value is traced strongly, so that registration could never fire.
+pub fn finalization_registry_raw_register(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let id = expect_finalization_registry(vm, receiver)?;
+    let target = weak_target(&args[0], "FinalizationRegistry target")?;
+    if args[1].as_obj() == Some(target) {
+        return Err(RuntimeError::ArgumentError("FinalizationRegistry held value must not be the target itself".to_string()).into());
+    }
+    let token = if args[2].is_none() {
+        None
+    } else {
+        Some(weak_target(&args[2], "FinalizationRegistry unregister token")?)
+    };
+    vm.heap.finalization_registry_mut(id).cells.push(FinalizationCell {
+        target,
+        held: args[1],
+        token,
+    });
+    Ok(vm.none_value())
+}
+
+/// Signature: `FinalizationRegistry::_$unregister(_)` — drops every cell
+/// registered under token `args[0]`, answering whether any was.
+///
+/// # Errors
+///
+/// Returns [`RuntimeError::Type`] if the receiver is not a
+/// `FinalizationRegistry`.
+pub fn finalization_registry_raw_unregister(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let id = expect_finalization_registry(vm, receiver)?;
+    let removed = args[0].as_obj().is_some_and(|token| vm.heap.finalization_registry_mut(id).unregister(token));
+    Ok(Value::bool(removed))
+}
+
+/// Signature: `FinalizationRegistry::_$takePending` — pops the oldest held
+/// value awaiting cleanup (`Some`), or `None` once drained.
+///
+/// Also clears the registry's `scheduled` flag: a drain is now in progress, so
+/// the next collection that finds a dead target must queue a fresh drain fiber
+/// rather than assume this one will see it. Worst case that fiber finds nothing
+/// left to do; the alternative — a callback that raises mid-drain leaving the
+/// flag stuck — would silence the registry for good.
+///
+/// # Errors
+///
+/// Returns [`RuntimeError::Type`] if the receiver is not a
+/// `FinalizationRegistry`.
+pub fn finalization_registry_raw_take_pending(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    let id = expect_finalization_registry(vm, receiver)?;
+    let registry = vm.heap.finalization_registry_mut(id);
+    registry.scheduled = false;
+    match registry.pending.pop_front() {
+        Some(held) => Ok(wrap_some(vm, held)?),
+        None => Ok(vm.none_value()),
+    }
+}
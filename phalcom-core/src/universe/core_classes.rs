@@ -134,6 +134,15 @@ impl Universe {
         // Mutable contents ⇒ identity hash, not a valid `Map`/`Set` key.
         let bytes_class = make_core_class(heap, "Bytes", iterable_class, metaclass_class);
 
+        // Kernel `WeakRef`/`WeakMap`/`FinalizationRegistry`: native heap
+        // variants the collector treats specially (`heap/weak.rs`) — weak
+        // edges, ephemeron entries, and post-collection cleanup scheduling.
+        // Directly under `Object`; `WeakMap` is deliberately *not* `Iterable`
+        // (its contents would expose collector timing).
+        let weak_ref_class = make_core_class(heap, "WeakRef", object_class, metaclass_class);
+        let weak_map_class = make_core_class(heap, "WeakMap", object_class, metaclass_class);
+        let finalization_registry_class = make_core_class(heap, "FinalizationRegistry", object_class, metaclass_class);
+
         // Kernel `Message` (method-lookup.md §2, ADR-0012): the reified miss
         // send handed to `doesNotUnderstand(_:)`. An ordinary fixed-slot
         // `InstanceObject` (four slots: selector/name/labels/args) built
@@ -227,6 +236,9 @@ impl Universe {
             record_class,
             range_class,
             bytes_class,
+            weak_ref_class,
+            weak_map_class,
+            finalization_registry_class,
             message_class,
             attribute_class,
             error_class,
@@ -273,6 +285,9 @@ impl Universe {
             res.unit_class,
             res.range_class,
             res.bytes_class,
+            res.weak_ref_class,
+            res.weak_map_class,
+            res.finalization_registry_class,
             res.fiber_class,
             res.method_class,
             res.module_class,
@@ -455,6 +470,16 @@ pub struct CoreClasses {
     /// key (collection-protocol law 4); `toTuple` is the immutable escape
     /// hatch.
     pub bytes_class: ClassId,
+    /// `WeakRef`, a single weak handle ([`crate::heap::Object::WeakRef`]);
+    /// its target is cleared by the collector, never traced.
+    pub weak_ref_class: ClassId,
+    /// `WeakMap`, the identity-keyed ephemeron table
+    /// ([`crate::heap::Object::WeakMap`]).
+    pub weak_map_class: ClassId,
+    /// `FinalizationRegistry` ([`crate::heap::Object::FinalizationRegistry`]):
+    /// weakly-watched targets whose cleanup callback runs on a scheduled
+    /// fiber after collection.
+    pub finalization_registry_class: ClassId,
     /// `Message`, the reified message-send handed to `doesNotUnderstand(_:)`
     /// on a lookup miss (method-lookup.md §2, ADR-0012). An ordinary
     /// fixed-slot [`InstanceObject`](crate::heap::InstanceObject) built by
@@ -565,6 +590,9 @@ impl CoreClasses {
             UniverseKey::Record => self.record_class,
             UniverseKey::Range => self.range_class,
             UniverseKey::Bytes => self.bytes_class,
+            UniverseKey::WeakRef => self.weak_ref_class,
+            UniverseKey::WeakMap => self.weak_map_class,
+            UniverseKey::FinalizationRegistry => self.finalization_registry_class,
             UniverseKey::Module => self.module_class,
             UniverseKey::Package => self.package_class,
             UniverseKey::Project => self.project_class,
@@ -648,6 +676,9 @@ impl CoreClasses {
             record_class,
             range_class,
             bytes_class,
+            weak_ref_class,
+            weak_map_class,
+            finalization_registry_class,
             message_class,
             error_class,
             message_not_understood_class,
@@ -711,6 +742,9 @@ impl CoreClasses {
             record_class,
             range_class,
             bytes_class,
+            weak_ref_class,
+            weak_map_class,
+            finalization_registry_class,
             message_class,
             attribute_class,
             error_class,
@@ -77,7 +77,7 @@ impl Universe {
         // `False` rows (both resolve to `Bool class`) and the absence /
         // collection / message rows. Any newly-added row that breaks the rule
         // fails boot rather than silently mis-dispatching statics.
-        let ordinary_rows: [(&str, ClassId); 38] = [
+        let ordinary_rows: [(&str, ClassId); 41] = [
             ("Number", c.number_class),
             ("Int", c.int_class),
             ("Float", c.float_class),
@@ -109,6 +109,9 @@ impl Universe {
             ("Record", c.record_class),
             ("Range", c.range_class),
             ("Bytes", c.bytes_class),
+            ("WeakRef", c.weak_ref_class),
+            ("WeakMap", c.weak_map_class),
+            ("FinalizationRegistry", c.finalization_registry_class),
             ("Message", c.message_class),
             ("Error", c.error_class),
             ("MessageNotUnderstood", c.message_not_understood_class),
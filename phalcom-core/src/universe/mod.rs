@@ -20,9 +20,10 @@ mod core_classes;
 mod invariants;
 mod primitives;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 pub use core_classes::CoreClasses;
+use phalcom_native_meta::{EXTENSION_CLASSES, NativeClassSpec};
 
 use crate::heap::{ClassId, Heap, ObjRef};
 use crate::interner::Symbol;
@@ -36,6 +37,10 @@ pub struct Universe {
     /// struct stays focused on execution primitives. These rows are still
     /// pinned universe handles and are resolved by builtin reflection imports.
     pub typing_classes: TypingClasses,
+    /// Classes declared by extension crates
+    /// ([`phalcom_native_meta::EXTENSION_CLASSES`]), pinned like the kernel
+    /// rows and exported by their native modules.
+    pub extension_classes: ExtensionClasses,
     /// Override-epoch flag for the `Bool`-receiver sacred selectors
     /// (`and(_)`, `or(_)`, `not`, `ifTrue(_)`, `ifFalse(_)`,
     /// `ifTrue(_)ifFalse(_)`). `true` from bootstrap until any of them is
@@ -111,6 +116,7 @@ impl Universe {
         let Universe {
             classes,
             typing_classes,
+            extension_classes,
             bool_sacred_pristine: _,
             block_sacred_pristine: _,
             number_tostring_pristine: _,
@@ -121,6 +127,7 @@ impl Universe {
         } = self;
         classes.each_handle(push);
         typing_classes.each_handle(push);
+        extension_classes.each_handle(push);
     }
 
     /// Bootstraps the core class tower into `heap` and returns the [`Universe`].
@@ -128,6 +135,7 @@ impl Universe {
         let classes = Self::create_core_classes(heap);
         Universe {
             typing_classes: TypingClasses::new(heap, &classes),
+            extension_classes: ExtensionClasses::new(heap, &classes),
             classes,
             bool_sacred_pristine: true,
             block_sacred_pristine: true,
@@ -344,6 +352,58 @@ impl TypingClasses {
     }
 }
 
+/// Runtime rows for the classes extension crates declare.
+///
+/// Keyed by `(module, name)`: two extension modules may each export a class
+/// of the same name. Rows are created in key order so a given set of linked
+/// extensions always allocates the same way.
+#[derive(Debug, Clone, Default)]
+pub struct ExtensionClasses {
+    rows: BTreeMap<(&'static str, &'static str), ClassId>,
+}
+
+impl ExtensionClasses {
+    fn new(heap: &mut Heap, core: &CoreClasses) -> Self {
+        let mut specs: Vec<&NativeClassSpec> = EXTENSION_CLASSES.iter().collect();
+        specs.sort();
+        let rows = specs
+            .into_iter()
+            .map(|spec| {
+                let superclass = core.resolve(spec.superclass);
+                let class = crate::universe::core_classes::make_core_class(heap, spec.name, superclass, core.metaclass_class);
+                ((spec.module, spec.name), class)
+            })
+            .collect();
+        Self { rows }
+    }
+
+    /// The row for `spec`.
+    ///
+    /// # Panics
+    ///
+    /// If `spec` was not in [`EXTENSION_CLASSES`] when the VM bootstrapped.
+    pub fn resolve(&self, spec: &NativeClassSpec) -> ClassId {
+        self.get(spec.module, spec.name)
+            .unwrap_or_else(|| panic!("extension class {}.{} is not registered", spec.module, spec.name))
+    }
+
+    /// The class `name` exported by extension module `module`.
+    pub fn get(&self, module: &str, name: &str) -> Option<ClassId> {
+        self.rows.get(&(module, name)).copied()
+    }
+
+    /// Every row, in `(module, name)` order.
+    pub fn iter(&self) -> impl Iterator<Item = ClassId> + '_ {
+        self.rows.values().copied()
+    }
+
+    pub fn each_handle(&self, push: &mut impl FnMut(ObjRef)) {
+        for &class in self.rows.values() {
+            push(class);
+        }
+    }
+}
+
 // U-CLASSCLOSE (PDR-0001): five golden fixtures used to prove
 // override-epoch/inline-cache invalidation by *reopening* a kernel class
 // from `.ph` source (`Number#toString`, `Bool#and`, `Block#whileTrue`,
@@ -6,10 +6,16 @@ use crate::primitive::bytes::{
     bytes_class_from_string, bytes_class_new, bytes_raw_at, bytes_raw_copy_into, bytes_raw_equals_constant_time, bytes_raw_fill, bytes_raw_set, bytes_raw_size,
     bytes_raw_slice, bytes_raw_utf8, bytes_raw_utf8_lossy,
 };
-use crate::primitive::class::{behavior_extract_shape, behavior_methods, behavior_name, class_add, class_new_, class_set_superclass, class_superclass};
+use crate::primitive::class::{
+    behavior_ancestors, behavior_compose_, behavior_extend_, behavior_extract_shape, behavior_is_mixin, behavior_is_protocol, behavior_methods,
+    behavior_mixin_, behavior_mixins, behavior_multimethod_, behavior_name, behavior_protocol_, behavior_requirements, class_add, class_new_,
+    class_set_superclass, class_superclass,
+};
 use crate::primitive::error::{error_message, error_raise};
 use crate::primitive::family::{family_get, family_is_exact, family_pattern, family_receiver, family_selector, family_set};
-use crate::primitive::fiber::{fiber_abort, fiber_call, fiber_current, fiber_error, fiber_is_done, fiber_is_root, fiber_new, fiber_try, fiber_yield};
+use crate::primitive::fiber::{
+    fiber_abort, fiber_call, fiber_can_suspend, fiber_current, fiber_error, fiber_is_done, fiber_is_root, fiber_new, fiber_rethrow, fiber_try, fiber_yield,
+};
 use crate::primitive::float::{
     float_abs, float_ceil, float_class_new, float_floor, float_is_finite, float_is_infinite, float_is_integer, float_is_nan, float_rounded, float_sign,
     float_to_int_exact, float_truncated,
@@ -20,7 +26,9 @@ use crate::primitive::int::{
 use crate::primitive::list::{list_class_new, list_raw_at, list_raw_length, list_raw_push, list_raw_set, list_replace_slice, list_to_string};
 use crate::primitive::map::{map_class_new, map_raw_get, map_raw_has, map_raw_key_at, map_raw_put, map_raw_remove, map_raw_size, map_raw_value_at};
 use crate::primitive::method::{method_bind, method_class_new, method_holder, method_invoke_on_shape, method_selector};
-use crate::primitive::method_family::{method_family_bind, method_family_method_for_shape, method_family_selectors, method_family_size};
+use crate::primitive::method_family::{
+    method_family_bind, method_family_method_for_shape, method_family_selectors, method_family_size, method_family_specializations_shape,
+};
 use crate::primitive::module::{module_class_new, module_does_not_understand};
 use crate::primitive::nil::{option_match, some_call, some_new};
 use crate::primitive::number::{
@@ -28,9 +36,9 @@ use crate::primitive::number::{
     number_mul, number_negate, number_pow, number_sub, number_to_string, number_unary_plus,
 };
 use crate::primitive::object::{
-    message_args, message_labels, message_name, message_selector, object_class, object_does_not_understand, object_eq, object_hash, object_invariant_enter,
-    object_invariant_exit, object_matches, object_method_for, object_name, object_neq, object_perform_shape, object_responds_to, object_same, object_set_class,
-    object_to_string, object_understands,
+    message_args, message_labels, message_name, message_selector, object_class, object_conforms_to, object_does_not_understand, object_eq, object_hash,
+    object_invariant_enter, object_invariant_exit, object_matches, object_method_for, object_name, object_neq, object_perform_shape, object_responds_to,
+    object_same, object_set_class, object_to_string, object_understands,
 };
 use crate::primitive::primitive;
 use crate::primitive::primitive_internal;
@@ -46,11 +54,16 @@ use crate::primitive::selector_pattern::*;
 use crate::primitive::set::{set_class_new, set_raw_add, set_raw_at, set_raw_has, set_raw_remove, set_raw_size};
 use crate::primitive::string::{string_add, string_class_new, string_hash, string_raw_byte_at, string_raw_byte_count, string_raw_slice};
 use crate::primitive::symbol::{symbol_class_new, symbol_hash, symbol_is_selector, symbol_is_selector_pattern, symbol_tostring};
-use crate::primitive::system::{system_class_new, system_class_print, system_gc, system_next_scheduled, system_raw_write, system_schedule};
+use crate::primitive::system::{system_class_new, system_class_print, system_gc, system_gc_stats, system_next_scheduled, system_raw_write, system_schedule};
 use crate::primitive::tuple::{
     tuple_from_list_internal, tuple_raw_at, tuple_raw_label_at, tuple_raw_labeled, tuple_raw_positional_size, tuple_raw_positionals, tuple_raw_size,
     tuple_raw_slice,
 };
+use crate::primitive::weak::{
+    finalization_registry_class_raw_new, finalization_registry_raw_cleanup, finalization_registry_raw_register, finalization_registry_raw_set_drain,
+    finalization_registry_raw_take_pending, finalization_registry_raw_unregister, weak_map_class_new, weak_map_raw_get, weak_map_raw_has, weak_map_raw_put,
+    weak_map_raw_remove, weak_ref_class_new, weak_ref_deref,
+};
 use crate::vm::VM;
 use phalcom_native_surface::{NATIVE_MEMBERS, NativeDispatch, NativeMemberKind, NativeVisibility};
 use std::collections::BTreeSet;
@@ -90,6 +103,9 @@ impl Universe {
             object_perform_shape
         );
         primitive!(vm, object_cls, "respondsTo", SignatureKind::Method(1), object_responds_to);
+        // Structural protocol conformance (`vm/protocol.rs`): `respondsTo`
+        // for every requirement of a `@protocol` class.
+        primitive!(vm, object_cls, "conformsTo", SignatureKind::Method(1), object_conforms_to);
         primitive!(vm, object_cls, "doesNotUnderstand", SignatureKind::Method(1), object_does_not_understand);
         // `Method` reflection surface (U-CORE-3, ADR-0028): reifies the
         // resolved `MethodObject` for a selector, a pure probe like
@@ -129,6 +145,25 @@ impl Universe {
         primitive!(vm, behavior_cls, "name", SignatureKind::Getter, behavior_name);
         primitive!(vm, behavior_cls, "methods", SignatureKind::Getter, behavior_methods);
         primitive_shape!(vm, behavior_cls, ">>", SignatureKind::Method(1), behavior_extract_shape);
+        // Mixins (`vm/mixin.rs`): `ancestors` is the linearization `super`
+        // follows; `_$mixin(_)`/`_$compose(_)` are the compiler's
+        // declaration sends, run before the body's `FinalizeClass`.
+        primitive!(vm, behavior_cls, "ancestors", SignatureKind::Getter, behavior_ancestors);
+        primitive!(vm, behavior_cls, "mixins", SignatureKind::Getter, behavior_mixins);
+        primitive!(vm, behavior_cls, "isMixin", SignatureKind::Getter, behavior_is_mixin);
+        primitive!(vm, behavior_cls, "requirements", SignatureKind::Getter, behavior_requirements);
+        primitive_internal!(vm, behavior_cls, "_$mixin", SignatureKind::Method(1), behavior_mixin_);
+        primitive_internal!(vm, behavior_cls, "_$compose", SignatureKind::Method(1), behavior_compose_);
+        // Protocols (`vm/protocol.rs`): `_$protocol(_)` is the declaration
+        // send; `requirements` above lists a protocol's selectors too.
+        primitive!(vm, behavior_cls, "isProtocol", SignatureKind::Getter, behavior_is_protocol);
+        primitive_internal!(vm, behavior_cls, "_$protocol", SignatureKind::Method(1), behavior_protocol_);
+        // Multimethods (`vm/multimethod.rs`): the compiler installs each
+        // `@multimethod` case with `_$multimethod(_,_,_)`.
+        primitive_internal!(vm, behavior_cls, "_$multimethod", SignatureKind::Method(3), behavior_multimethod_);
+        // Scoped extensions (`vm/extension.rs`): each member of an `extend`
+        // block installs with `_$extend(_,_)`.
+        primitive_internal!(vm, behavior_cls, "_$extend", SignatureKind::Method(2), behavior_extend_);
 
         let class_cls = vm.universe.classes.class_class;
         primitive!(vm, class_cls, "+", SignatureKind::Method(1), class_add);
@@ -329,6 +364,13 @@ impl Universe {
         primitive!(vm, method_family_cls, "selectors", SignatureKind::Getter, method_family_selectors);
         primitive!(vm, method_family_cls, "size", SignatureKind::Getter, method_family_size);
         primitive_shape!(vm, method_family_cls, "methodFor", SignatureKind::Method(1), method_family_method_for_shape);
+        primitive_shape!(
+            vm,
+            method_family_cls,
+            "specializations",
+            SignatureKind::Method(1),
+            method_family_specializations_shape
+        );
         primitive!(vm, method_family_cls, "bind", SignatureKind::Method(1), method_family_bind);
 
         let function_cls = vm.universe.classes.function_class;
@@ -377,6 +419,7 @@ impl Universe {
         primitive_static!(vm, system_cls, "schedule", SignatureKind::Method(1), system_schedule);
         primitive_static!(vm, system_cls, "nextScheduled", SignatureKind::Getter, system_next_scheduled);
         primitive_static!(vm, system_cls, "gc", SignatureKind::Getter, system_gc);
+        primitive_static!(vm, system_cls, "gcStats", SignatureKind::Getter, system_gc_stats);
         // U-STRING raw I/O seam (ADR-0019 amendment, ADR-0049): raw stdout write of
         // an already-formed `String`, no newline, no formatting — the irreducible
         // literal I/O act that `System.write`/`writeObject_` funnel over.
@@ -649,6 +692,27 @@ impl Universe {
         primitive_internal!(vm, range_cls, "_$upper", SignatureKind::Getter, range_raw_upper);
         primitive_internal!(vm, range_cls, "_$upperInclusive", SignatureKind::Getter, range_raw_upper_inclusive);
 
+        // `WeakRef`/`WeakMap`/`FinalizationRegistry` (`heap/weak.rs`): the
+        // collector does the interesting work; these only allocate and read or
+        // write the native payloads. `collections/weak.ph` wraps the raw
+        // `WeakMap`/registry members in the public protocol.
+        let weak_ref_cls = vm.universe.classes.weak_ref_class;
+        primitive_static!(vm, weak_ref_cls, "new", SignatureKind::Method(1), weak_ref_class_new);
+        primitive!(vm, weak_ref_cls, "deref", SignatureKind::Getter, weak_ref_deref);
+        let weak_map_cls = vm.universe.classes.weak_map_class;
+        primitive_static!(vm, weak_map_cls, "new", SignatureKind::Method(0), weak_map_class_new);
+        primitive_internal!(vm, weak_map_cls, "_$get", SignatureKind::Method(1), weak_map_raw_get);
+        primitive_internal!(vm, weak_map_cls, "_$put", SignatureKind::Method(2), weak_map_raw_put);
+        primitive_internal!(vm, weak_map_cls, "_$has", SignatureKind::Method(1), weak_map_raw_has);
+        primitive_internal!(vm, weak_map_cls, "_$remove", SignatureKind::Method(1), weak_map_raw_remove);
+        let registry_cls = vm.universe.classes.finalization_registry_class;
+        primitive_static_internal!(vm, registry_cls, "_$new", SignatureKind::Method(1), finalization_registry_class_raw_new);
+        primitive_internal!(vm, registry_cls, "_$setDrain", SignatureKind::Method(1), finalization_registry_raw_set_drain);
+        primitive_internal!(vm, registry_cls, "_$cleanup", SignatureKind::Getter, finalization_registry_raw_cleanup);
+        primitive_internal!(vm, registry_cls, "_$register", SignatureKind::Method(3), finalization_registry_raw_register);
+        primitive_internal!(vm, registry_cls, "_$unregister", SignatureKind::Method(1), finalization_registry_raw_unregister);
+        primitive_internal!(vm, registry_cls, "_$takePending", SignatureKind::Getter, finalization_registry_raw_take_pending);
+
         // `Error` root (U-CORE-6, ADR-0008): `message` is a native slot-0
         // accessor (mirrors `Message`'s accessors — a `.ph` getter over this
         // field would trip the read-before-write check); `raise` initiates
@@ -680,6 +744,11 @@ impl Universe {
         // wreckage — see `fiber_is_root`'s doc and E004.
         primitive!(vm, fiber_cls, "isRoot", SignatureKind::Getter, fiber_is_root);
         primitive!(vm, fiber_cls, "error", SignatureKind::Getter, fiber_error);
+        // The restricted-yield guard's predicate, asked by `Future#await`
+        // before it parks; `_$rethrow` re-raises a rejected `@async` body's
+        // error with its fiber's frames stitched in.
+        primitive!(vm, fiber_cls, "canSuspend", SignatureKind::Getter, fiber_can_suspend);
+        primitive_internal!(vm, fiber_cls, "_$rethrow", SignatureKind::Method(0), fiber_rethrow);
 
         // U-RESOURCE primitives
         let resource_cls = vm.universe.classes.resource_class;
@@ -783,6 +852,9 @@ fn validate_native_surface(vm: &VM) {
         classes.record_class,
         classes.range_class,
         classes.bytes_class,
+        classes.weak_ref_class,
+        classes.weak_map_class,
+        classes.finalization_registry_class,
         classes.message_class,
         classes.error_class,
         classes.message_not_understood_class,
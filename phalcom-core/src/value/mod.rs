@@ -127,6 +127,9 @@ impl Value {
                 Object::BoundMethod(_) => vm.universe.classes.bound_method_class,
                 Object::List(_) => vm.universe.classes.list_class,
                 Object::Bytes(_) => vm.universe.classes.bytes_class,
+                Object::WeakRef(_) => vm.universe.classes.weak_ref_class,
+                Object::WeakMap(_) => vm.universe.classes.weak_map_class,
+                Object::FinalizationRegistry(_) => vm.universe.classes.finalization_registry_class,
                 Object::Fiber(_) => vm.universe.classes.fiber_class,
                 Object::Map(_) => vm.universe.classes.map_class,
                 Object::Set(_) => vm.universe.classes.set_class,
@@ -200,6 +203,9 @@ impl Value {
                 | Object::BoundMethod(_)
                 | Object::List(_)
                 | Object::Bytes(_)
+                | Object::WeakRef(_)
+                | Object::WeakMap(_)
+                | Object::FinalizationRegistry(_)
                 | Object::Fiber(_)
                 | Object::Map(_)
                 | Object::Set(_)
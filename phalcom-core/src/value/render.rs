@@ -149,6 +149,9 @@ impl Value {
                 Object::BoundMethod(_) => "<bound method>".to_string(),
                 Object::List(_) => "<list>".to_string(),
                 Object::Bytes(_) => "<bytes>".to_string(),
+                Object::WeakRef(_) => "<weak ref>".to_string(),
+                Object::WeakMap(_) => "<weak map>".to_string(),
+                Object::FinalizationRegistry(_) => "<finalization registry>".to_string(),
                 Object::Fiber(_) => "<fiber>".to_string(),
                 Object::Map(_) => "<map>".to_string(),
                 Object::Set(_) => "<set>".to_string(),
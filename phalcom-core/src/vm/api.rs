@@ -17,6 +17,19 @@ impl VM {
             .or_else(|| self.universe.typing_classes.get(name))
     }
 
+    /// Resolves binding `name` of builtin module `module` to the class it
+    /// names: an extension class for a `native` module, otherwise a canonical
+    /// builtin class ([`Self::resolve_builtin_class_name`]).
+    pub fn resolve_builtin_binding(&self, module: &phalcom_modules::ModuleId, name: &str) -> Option<ClassId> {
+        match module.project.as_builtin()? {
+            phalcom_modules::BuiltinProject::Native => {
+                let path = module.path.components().iter().map(|c| c.as_str()).collect::<Vec<_>>().join(".");
+                self.universe.extension_classes.get(&path, name)
+            }
+            _ => self.resolve_builtin_class_name(name),
+        }
+    }
+
     /// Interns `name`, returning its [`Symbol`].
     pub fn get_or_intern(&mut self, name: &str) -> Symbol {
         self.interner.intern(name)
@@ -181,6 +194,7 @@ impl VM {
             RuntimeError::StrictZipLengthMismatch => "strictZipLengthMismatch",
             RuntimeError::PatternMismatch => "patternMismatch",
             RuntimeError::DepthExceeded { .. } => "depthExceeded",
+            RuntimeError::LimitExceeded { .. } => "limitExceeded",
             RuntimeError::DeadFrameError => "deadFrame",
             RuntimeError::NumericConversion { .. } => "numericConversion",
             RuntimeError::NumericOverflow { .. } => "numericOverflow",
@@ -233,15 +247,16 @@ impl VM {
     /// # Errors
     ///
     /// [`RuntimeError::DepthExceeded`] when the frame stack is already at
-    /// [`MAX_CALL_DEPTH`](crate::vm::MAX_CALL_DEPTH).
+    /// [`VmLimits::max_call_depth`](crate::vm::VmLimits::max_call_depth)
+    /// ([`MAX_CALL_DEPTH`](crate::vm::MAX_CALL_DEPTH) unless lowered), and
+    /// [`RuntimeError::LimitExceeded`] when the fuel meter runs out — a call
+    /// is one meter tick, like a loop back-edge.
     pub(crate) fn push_frame(&mut self, frame: crate::frame::CallFrame) -> PhResult<()> {
-        if self.frames.len() >= crate::vm::MAX_CALL_DEPTH {
-            return Err(RuntimeError::DepthExceeded {
-                what: "call depth",
-                limit: crate::vm::MAX_CALL_DEPTH,
-            }
-            .into());
+        let limit = self.limits.max_call_depth;
+        if self.frames.len() >= limit {
+            return Err(RuntimeError::DepthExceeded { what: "call depth", limit }.into());
         }
+        self.charge_fuel()?;
         self.frames.push(frame);
         Ok(())
     }
@@ -1,4 +1,4 @@
-use crate::error::PhResult;
+use crate::error::{PhError, PhResult, RuntimeError};
 use crate::heap::CORE_MODULE_NAME;
 use crate::heap::{ClassId, Object};
 use crate::universe::Universe;
@@ -68,6 +68,10 @@ impl VM {
             resources: crate::resource::ResourceTable::new(),
             strict_resources: false,
             numeric_policy: crate::value::NumericPolicy::standard(),
+            limits: crate::vm::VmLimits::unlimited(),
+            meter: crate::vm::LimitMeter::default(),
+            host: crate::embed::HostState::default(),
+            intercept: Default::default(),
 
             #[cfg(feature = "fiber-pool")]
             fiber_pool: Vec::new(),
@@ -176,7 +180,14 @@ impl VM {
             vm.heap.class_mut(uace).field_count = 4;
         }
         Universe::install_primitives(&mut vm);
-        crate::native::install::install_registered_primitives(&mut vm).expect("registered primitives must install cleanly");
+        // A malformed downstream extension registry leaves its primitives
+        // out and is reported; the kernel's must install regardless.
+        match crate::native::install::install_registered_primitives(&mut vm) {
+            Err(PhError::Runtime(RuntimeError::NotAllowed(message))) if message.starts_with("native.extension_registry") => {
+                eprintln!("{message}");
+            }
+            installed => installed.expect("registered primitives must install cleanly"),
+        }
 
         // Finalize every kernel row's base-name index (selectors.md §3.1,
         // U16-Open) now that its native primitives are installed, so `::`
@@ -305,7 +316,9 @@ impl VM {
             ("collections/range", include_str!("../../core/universe/src/collections/range.ph")),
             ("concurrency/fiber", include_str!("../../core/universe/src/concurrency/fiber.ph")),
             ("collections/bytes", include_str!("../../core/universe/src/collections/bytes.ph")),
+            ("collections/weak", include_str!("../../core/universe/src/collections/weak.ph")),
             ("reflection/attribute", include_str!("../../core/universe/src/reflection/attribute.ph")),
+            ("reflection/decorators", include_str!("../../core/universe/src/reflection/decorators.ph")),
             ("reflection/selector", include_str!("../../core/universe/src/reflection/selector.ph")),
         ];
 
@@ -419,6 +432,11 @@ impl VM {
         // mirroring `List`; the core.ph `class Bytes` block is a stub
         // completion of this row, not a fresh class.
         add_class!(bytes_class);
+        // `WeakRef`/`WeakMap`/`FinalizationRegistry`: ordinary class globals
+        // over their native heap arms; `collections/weak.ph` completes them.
+        add_class!(weak_ref_class);
+        add_class!(weak_map_class);
+        add_class!(finalization_registry_class);
         add_class!(message_class);
         add_class!(attribute_class);
         // `Error` root + `MessageNotUnderstood < Error` (U-CORE-6, ADR-0008):
@@ -482,7 +500,8 @@ impl VM {
     /// row idempotently via [`crate::bytecode::Bytecode::FinalizeClass`] —
     /// this pass only guarantees every row has *some* finalized index, even
     /// one `core.ph` never touches (`Behavior`, `Metaclass`, `Message`,
-    /// `Fiber`, …).
+    /// `Fiber`, …). Extension rows follow the kernel ones: their superclass
+    /// is always a kernel class.
     fn finalize_all_core_base_names(&mut self) {
         let c = self.universe.classes;
         let rows = [
@@ -523,7 +542,8 @@ impl VM {
             c.cannot_yield_across_native_frame_class,
             c.family_class,
         ];
-        for class_id in rows {
+        let extensions: Vec<_> = self.universe.extension_classes.iter().collect();
+        for class_id in rows.into_iter().chain(extensions) {
             self.finalize_class_base_names(class_id);
             let meta_id = self.heap.class(class_id).class;
             self.finalize_class_base_names(meta_id);
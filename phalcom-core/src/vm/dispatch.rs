@@ -139,7 +139,11 @@ impl VM {
 
     fn invoke_dynamic_selector(&mut self, receiver_idx: usize, selector: Symbol, arity: usize, source_range: SourceRange) -> PhResult<()> {
         let receiver = self.stack[receiver_idx];
-        if let Some(method) = receiver.lookup_method(self, selector) {
+        let extension = || {
+            self.current_module()
+                .and_then(|module| self.lookup_extension(module, receiver.class(self), selector))
+        };
+        if let Some(method) = receiver.lookup_method(self, selector).or_else(extension) {
             self.call_method(&receiver, method, arity, source_range)
         } else {
             let (name, slots, kind) = decode_selector(self.resolve_symbol(selector));
@@ -359,7 +363,7 @@ impl VM {
     /// for a rest Method, its secondary base-family index. Validation runs
     /// before either mutation so a duplicate rest family cannot leave the
     /// two indexes out of sync.
-    fn install_method_binding(&mut self, target_class: crate::heap::ClassId, selector: Symbol, method: ObjRef) -> PhResult<()> {
+    pub(super) fn install_method_binding(&mut self, target_class: crate::heap::ClassId, selector: Symbol, method: ObjRef) -> PhResult<()> {
         let rest_base = self.validate_method_installation(target_class, selector, method)?;
         self.heap.class_mut(target_class).add_method(selector, method);
         if let Some(base) = rest_base {
@@ -369,6 +373,27 @@ impl VM {
         Ok(())
     }
 
+    /// Binds a compiled member `method` to the class being defined,
+    /// `class` — its holder is `class`, or the metaclass for a class-side
+    /// member, and both its access owner and its closure's lexical class are
+    /// `class` — and returns the holder.
+    pub(crate) fn bind_method_to_class(&mut self, method: ObjRef, class: crate::heap::ClassId, is_static: bool) -> crate::heap::ClassId {
+        let holder = if is_static { self.heap.class(class).class } else { class };
+        let closure = {
+            let method = self.heap.method_mut(method);
+            method.set_holder(holder);
+            method.access_owner = Some(class);
+            match method.kind {
+                crate::method::MethodKind::Closure(closure) => Some(closure),
+                crate::method::MethodKind::Primitive(_) => None,
+            }
+        };
+        if let Some(closure) = closure {
+            self.heap.closure_mut(closure).lexical_class = Some(class);
+        }
+        holder
+    }
+
     /// Builds a [`CallFrame`] stamped with a fresh, monotonically-increasing
     /// generation for the frame-token infrastructure
     /// ([ADR-0013](../../../docs/adr/0013-block-closure-upvalues.md)).
@@ -794,40 +819,55 @@ impl VM {
                             return Err(e);
                         };
 
-                        if !matches!(e, PhError::Runtime(RuntimeError::Raise { .. })) {
-                            let error_class = self.universe.classes.error_class;
-                            let field_count = self.heap.class(error_class).field_count;
-                            let mut inst = crate::heap::InstanceObject::new(error_class, field_count);
-                            inst.slots[0] = self.alloc_string_value(e.to_string());
-                            if let PhError::Runtime(runtime_err) = &e {
-                                if let Some(kind_val) = self.error_kind_symbol(runtime_err) {
-                                    inst.slots[1] = kind_val;
+                        // A sandbox limit stays itself all the way to the host
+                        // (`vm/limits.rs`): it is neither reified into an `Error`
+                        // nor delivered to a `try`-mode resumer below.
+                        let uncatchable = e.is_limit_exceeded();
+                        if !uncatchable {
+                            // Where `failed` raised: the records the raise already
+                            // carries (a failure that crossed a fiber before, or a
+                            // `Fiber#_$rethrow`), else its own frames.
+                            let trace = match &e {
+                                PhError::Runtime(RuntimeError::Raise { traceback: Some(tb), .. }) => tb.clone(),
+                                _ if failed == self.current => self.capture_frames(0),
+                                _ => self.capture_parked_frames(failed),
+                            };
+                            if !matches!(e, PhError::Runtime(RuntimeError::Raise { .. })) {
+                                let error_class = self.universe.classes.error_class;
+                                let field_count = self.heap.class(error_class).field_count;
+                                let mut inst = crate::heap::InstanceObject::new(error_class, field_count);
+                                inst.slots[0] = self.alloc_string_value(e.to_string());
+                                if let PhError::Runtime(runtime_err) = &e {
+                                    if let Some(kind_val) = self.error_kind_symbol(runtime_err) {
+                                        inst.slots[1] = kind_val;
+                                    }
                                 }
+                                let error = Value::obj(self.heap.alloc(Object::Instance(inst)));
+                                let rendered = e.to_string();
+                                e = PhError::Runtime(RuntimeError::Raise {
+                                    error,
+                                    rendered,
+                                    traceback: None,
+                                    help: None,
+                                });
                             }
-                            let error = Value::obj(self.heap.alloc(Object::Instance(inst)));
-                            let rendered = e.to_string();
-                            let tb = if failed == self.current {
-                                self.capture_frames(0)
-                            } else {
-                                self.capture_parked_frames(failed)
-                            };
-                            e = PhError::Runtime(RuntimeError::Raise {
-                                error,
-                                rendered,
-                                traceback: Some(tb),
-                                help: None,
-                            });
-                        }
-
-                        if let PhError::Runtime(RuntimeError::Raise { traceback: Some(tb), .. }) = &mut e {
+                            // Stitched oldest-first, as the traceback catalog
+                            // renders a fiber chain (output-catalog.md §2): the
+                            // resumer's parked frames, the boundary into `failed`,
+                            // then where `failed` raised.
                             let failed_fiber = self.heap.fiber(failed);
-                            tb.push(crate::error::FrameRecord::FiberBoundary {
+                            let boundary = crate::error::FrameRecord::FiberBoundary {
                                 seq: failed_fiber.seq,
                                 spawn_file: failed_fiber.spawn_file,
                                 spawn_line: failed_fiber.spawn_line,
-                            });
-                            let resumer_frames = self.capture_parked_frames(resumer);
-                            tb.extend(resumer_frames);
+                            };
+                            let mut stitched = self.capture_parked_frames(resumer);
+                            stitched.push(boundary);
+                            stitched.extend(trace.iter().cloned());
+                            self.heap.fiber_mut(failed).failure_trace = trace;
+                            if let PhError::Runtime(RuntimeError::Raise { traceback, .. }) = &mut e {
+                                *traceback = Some(stitched);
+                            }
                         }
 
                         // Spec §5.1: a `Failed` fiber can never resume, so its
@@ -845,11 +885,11 @@ impl VM {
                         self.heap.fiber_mut(failed).open_upvalues.clear();
 
                         match mode {
-                            crate::heap::FiberResumeMode::Try => {
+                            crate::heap::FiberResumeMode::Try if !uncatchable => {
                                 self.switch_to_fiber_and_deliver(resumer, error_value);
                                 break;
                             }
-                            crate::heap::FiberResumeMode::Call => {
+                            crate::heap::FiberResumeMode::Try | crate::heap::FiberResumeMode::Call => {
                                 failed = resumer;
                             }
                         }
@@ -946,7 +986,7 @@ impl VM {
     /// of the kernel `Error` class carrying its rendered message, so a
     /// fiber's failure is always a catchable `Error` value, never a native
     /// Rust error type leaking across the fiber boundary.
-    fn capture_error_value(&mut self, e: &PhError) -> Value {
+    pub(super) fn capture_error_value(&mut self, e: &PhError) -> Value {
         if let PhError::Runtime(RuntimeError::Raise { error, .. }) = e {
             return *error;
         }
@@ -963,8 +1003,8 @@ impl VM {
     }
 
     /// Executes one `Invoke`-shaped send: IC probe, exact-selector lookup + refill,
-    /// rest-family fallback, then `doesNotUnderstand(_)` forward — method-lookup.md §1's
-    /// miss order, in order.
+    /// the calling module's scoped extensions, rest-family fallback, then
+    /// `doesNotUnderstand(_)` forward — method-lookup.md §1's miss order, in order.
     ///
     /// `cache_ip` is the index whose `caches`/`spans` slots this send owns. For a
     /// plain [`Bytecode::Invoke`] that is the instruction's own `ip`; for the fused
@@ -1031,6 +1071,20 @@ impl VM {
                 };
                 callable.chunk.caches[cache_ip].set(Some(entry));
                 self.call_method(&receiver, method, arity, source_range)?;
+            } else if let Some(method) = self
+                .current_module()
+                .and_then(|module| self.lookup_extension(module, receiver_class, selector_sym))
+            {
+                // A scoped extension visible to this call site's module
+                // (`vm/extension.rs`). The cache slot is this site's alone, so
+                // it can hold the extension like any hierarchy hit.
+                let entry = crate::chunk::InlineCache {
+                    class: receiver_class,
+                    method,
+                    version: self.world_version,
+                };
+                callable.chunk.caches[cache_ip].set(Some(entry));
+                self.call_method(&receiver, method, arity, source_range)?;
             } else {
                 let (name, slots, kind) = decode_selector(self.resolve_symbol(selector_sym));
                 let positional_count = slots.iter().filter(|slot| slot.is_none()).count();
@@ -1079,8 +1133,24 @@ impl VM {
     /// # Errors
     ///
     /// Returns any [`RuntimeError`] raised during execution (undefined variable,
-    /// method-not-found, unsupported operator, and so on).
+    /// method-not-found, unsupported operator, and so on) that no
+    /// `Invocation#attempt()` step above `base_frames` settles
+    /// (`intercept.rs`).
     fn run_until_inner(&mut self, base_frames: usize) -> PhResult<Value> {
+        loop {
+            match self.execute_until(base_frames) {
+                Err(error) if self.settle_attempt_error(base_frames, &error) => {
+                    if self.frames.len() <= base_frames {
+                        return Ok(self.stack.pop().unwrap_or(Value::nil()));
+                    }
+                }
+                outcome => return outcome,
+            }
+        }
+    }
+
+    /// [`Self::run_until_inner`]'s bytecode loop proper.
+    fn execute_until(&mut self, base_frames: usize) -> PhResult<Value> {
         // The hoisted callable of the frame we are executing (F14 S1a, U-HOTPATH
         // Change 1, resolution (a)). Refreshed by a one-compare guard below rather
         // than re-derived from `self.heap` every instruction.
@@ -1108,7 +1178,7 @@ impl VM {
             // non-moving collector cannot invalidate the `CallFrame` we are about to copy,
             // but keeping the whole read-decode-execute sequence GC-free is what makes that
             // independent of the collector's future shape.
-            self.service_gc_safepoint();
+            self.service_gc_safepoint()?;
 
             let frame = *self.frames.last().unwrap();
             let closure_id = frame.closure;
@@ -1406,6 +1476,21 @@ impl VM {
                         let name = self.resolve_symbol(name_sym).to_string();
                         let superclass = self.stack.pop().unwrap();
                         if let Some(sc_id) = superclass.as_obj() {
+                            if self.heap.as_class(sc_id).is_some_and(|row| row.is_mixin) {
+                                return Err(RuntimeError::NotAllowed(format!(
+                                    "mixin.superclass: `{name}` cannot inherit from mixin `{}`; compose it with `@compose({})`",
+                                    self.heap.class(sc_id).name,
+                                    self.heap.class(sc_id).name
+                                ))
+                                .into());
+                            }
+                            if self.heap.as_class(sc_id).is_some_and(|row| row.is_protocol) {
+                                return Err(RuntimeError::NotAllowed(format!(
+                                    "protocol.superclass: `{name}` cannot inherit from protocol `{}`; a protocol is satisfied, not extended",
+                                    self.heap.class(sc_id).name
+                                ))
+                                .into());
+                            }
                             if self.heap.as_class(sc_id).is_some() {
                                 let closure_module = self.heap.closure(closure_id).module;
                                 let new_class = self.create_class(closure_module, &name, Some(sc_id));
@@ -1467,6 +1552,11 @@ impl VM {
                 }
                 Bytecode::FinalizeClass => {
                     if let Some(class_id) = self.stack.last().unwrap().as_obj() {
+                        // Mixin methods join the class's own dictionary
+                        // first, so the base-name index sees them
+                        // (`vm/mixin.rs`).
+                        self.apply_mixins(class_id)?;
+                        self.check_multimethods(class_id)?;
                         self.finalize_class_base_names(class_id);
                         let meta_id = self.heap.class(class_id).class;
                         self.finalize_class_base_names(meta_id);
@@ -1488,10 +1578,12 @@ impl VM {
                     let receiver_idx = self.stack.len() - 1 - argc;
                     let receiver = self.stack[receiver_idx];
 
-                    // Start the walk at `defining.superclass` (DEC-INH-B: the
+                    // Start the walk after the defining class in the
+                    // receiver's linearization — `defining.superclass` unless
+                    // mixins are involved (`vm/mixin.rs`). DEC-INH-B: the
                     // defining class is resolved by name and its superclass read
                     // at dispatch, so a future `superclass=` mutation stays
-                    // correct). The defining class was created before any of its
+                    // correct. The defining class was created before any of its
                     // methods could run, so it is present in `self.classes`.
                     // No superclass ⇒ the walk is empty ⇒ `doesNotUnderstand`.
                     let closure_module = self.heap.closure(closure_id).module;
@@ -1499,19 +1591,22 @@ impl VM {
                         module: closure_module,
                         name: defining_sym,
                     };
-                    let parent = if let Some(&c) = self.classes.get(&defining_key) {
-                        self.heap.class(c).superclass
+                    let defining = if let Some(&c) = self.classes.get(&defining_key) {
+                        Some(c)
                     } else if let Some(core_mod) = self.core_module() {
                         let core_key = crate::vm::ClassKey {
                             module: core_mod,
                             name: defining_sym,
                         };
-                        self.classes.get(&core_key).and_then(|&c| self.heap.class(c).superclass)
+                        self.classes.get(&core_key).copied()
                     } else {
                         None
                     };
 
-                    let method = parent.and_then(|p| crate::heap::lookup_method_in_hierarchy(&self.heap, p, selector_sym));
+                    let (method, parent) = match defining {
+                        Some(defining) => self.super_method(receiver, defining, selector_sym),
+                        None => (None, None),
+                    };
 
                     if let Some(method) = method {
                         let foreign_guard = self.frames.last().and_then(|frame| frame.foreign_receiver_guard);
@@ -1549,36 +1644,9 @@ impl VM {
                     let method_val = self.stack.pop().unwrap();
                     let class_val = *self.stack.last().unwrap();
                     if let (Some(method_id), Some(class_id)) = (method_val.as_obj(), class_val.as_obj()) {
-                        let target_class = if is_static { self.heap.class(class_id).class } else { class_id };
-                        if is_static {
-                            let meta = self.heap.class(class_id).class;
-                            let closure = {
-                                let method = self.heap.method_mut(method_id);
-                                method.set_holder(meta);
-                                method.access_owner = Some(class_id);
-                                match method.kind {
-                                    crate::method::MethodKind::Closure(closure) => Some(closure),
-                                    crate::method::MethodKind::Primitive(_) => None,
-                                }
-                            };
-                            if let Some(closure) = closure {
-                                self.heap.closure_mut(closure).lexical_class = Some(class_id);
-                            }
-                            self.install_method_binding(target_class, selector, method_id)?;
-                        } else {
-                            let closure = {
-                                let method = self.heap.method_mut(method_id);
-                                method.set_holder(class_id);
-                                method.access_owner = Some(class_id);
-                                match method.kind {
-                                    crate::method::MethodKind::Closure(closure) => Some(closure),
-                                    crate::method::MethodKind::Primitive(_) => None,
-                                }
-                            };
-                            if let Some(closure) = closure {
-                                self.heap.closure_mut(closure).lexical_class = Some(class_id);
-                            }
-                            self.install_method_binding(target_class, selector, method_id)?;
+                        let target_class = self.bind_method_to_class(method_id, class_id, is_static);
+                        self.install_method_binding(target_class, selector, method_id)?;
+                        if !is_static {
                             // Sacred-selector override-epoch tracking (ADR-0018):
                             // any (re)definition of a sacred selector directly on
                             // the kernel Bool/Block class dirties the pristine
@@ -1888,6 +1956,13 @@ impl VM {
                     // (ADR-0013). Must run before the stack is truncated.
                     self.close_upvalues_from(popped.stack_offset);
                     self.stack.truncate(popped.stack_offset);
+                    // An `Invocation#attempt()` step answers `Ok(value)`
+                    // (`intercept.rs`).
+                    let return_value = if popped.interception.is_some_and(crate::frame::InterceptRole::attempt) {
+                        self.settle(Ok(return_value))
+                    } else {
+                        return_value
+                    };
                     if self.frames.len() <= base_frames {
                         return Ok(return_value);
                     }
@@ -1971,7 +2046,12 @@ impl VM {
                         self.stack.push(value);
                     }
                 }
-                Bytecode::Loop(offset) => self.apply_jump_offset(offset),
+                Bytecode::Loop(offset) => {
+                    // The back-edge is the meter's tick: every loop that can
+                    // run forever passes through here (`vm/limits.rs`).
+                    self.charge_fuel()?;
+                    self.apply_jump_offset(offset);
+                }
                 Bytecode::GuardBool(offset) => {
                     let top = *self.stack.last().ok_or(RuntimeError::Internal("Stack underflow for GuardBool".to_string()))?;
                     let takes_fast_path = top.as_bool().is_some() && self.universe.bool_sacred_pristine;
@@ -2171,9 +2251,11 @@ impl VM {
                     self.stack.extend(values);
                     let module = self.heap.closure(closure_id).module;
                     let key = crate::vm::ClassKey { module, name: defining };
-                    let parent = self.classes.get(&key).and_then(|class| self.heap.class(*class).superclass);
+                    let (exact, parent) = match self.classes.get(&key) {
+                        Some(&defining) => self.super_method(receiver, defining, selector),
+                        None => (None, None),
+                    };
                     let range = callable.chunk.span_at(ip);
-                    let exact = parent.and_then(|class| crate::heap::lookup_method_in_hierarchy(&self.heap, class, selector));
                     if let Some(method) = exact {
                         let foreign_guard = self.frames.last().and_then(|frame| frame.foreign_receiver_guard);
                         let frames_before = self.frames.len();
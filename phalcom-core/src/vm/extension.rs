@@ -0,0 +1,86 @@
+//! Scoped extensions: methods an `extend` block adds to an existing class.
+//!
+//! The compiler installs each member with `_$extend(_,_)`
+//! ([`VM::declare_extension`]), which files it in the *declaring* module's
+//! [`ModuleObject::extensions`](crate::heap::ModuleObject::extensions) rather
+//! than the class's method dictionary. A send that misses the receiver's
+//! hierarchy then asks [`VM::lookup_extension`] with the module its code was
+//! compiled in: that module's own extensions first, then those of the modules
+//! it imports directly ([`ModuleObject::extension_sources`](crate::heap::ModuleObject::extension_sources)),
+//! at each class of the receiver's chain, nearest class first. A class member
+//! always wins, and an import of an import contributes nothing.
+//!
+//! Two directly visible extensions of one class and selector are a link-time
+//! error (`phalcom-modules`' `LinkError::ExtensionConflict`), so the order
+//! among sources only matters for programs that never went through the
+//! linker. Each call site's inline cache belongs to a single module, so a
+//! resolved extension fills it like any method; installing one bumps
+//! `world_version` to retire entries that cached a miss's fallback.
+
+use crate::error::{PhResult, RuntimeError};
+use crate::heap::{ClassId, ObjRef, lookup_method_with_definer};
+use crate::interner::Symbol;
+use crate::vm::VM;
+
+impl VM {
+    /// Adds `method` to the extensions of `module` for `class`, on the class
+    /// side when `is_static` (`_$extend(_,_)`).
+    ///
+    /// # Errors
+    ///
+    /// `extension.shadowed` when the class side already answers the selector,
+    /// since the member would always win; `extension.duplicate` when `module`
+    /// already extends that class with it.
+    pub(crate) fn declare_extension(&mut self, module: ObjRef, class: ClassId, method: ObjRef, is_static: bool) -> PhResult<()> {
+        let holder = if is_static { self.heap.class(class).class } else { class };
+        let selector = self.heap.method(method).signature.selector;
+        let shown = || format!("{}{}", self.heap.class(class).name, if is_static { ".class" } else { "" });
+        if let Some((_, definer)) = lookup_method_with_definer(&self.heap, holder, selector) {
+            return Err(RuntimeError::NotAllowed(format!(
+                "extension.shadowed: `{}#{}` is already defined by `{}`; a class member always wins over an extension",
+                shown(),
+                self.resolve_symbol(selector),
+                self.heap.class(definer).name
+            ))
+            .into());
+        }
+        if self.heap.module(module).extensions.contains_key(&(holder, selector)) {
+            return Err(RuntimeError::NotAllowed(format!(
+                "extension.duplicate: module `{}` already extends `{}` with `{}`",
+                self.heap.module(module).name,
+                shown(),
+                self.resolve_symbol(selector)
+            ))
+            .into());
+        }
+        self.heap.method_mut(method).set_holder(holder);
+        self.heap.module_mut(module).extensions.insert((holder, selector), method);
+        self.world_version += 1;
+        Ok(())
+    }
+
+    /// The extension answering `selector` for a receiver of `class` in code
+    /// compiled in `module`, if any: the nearest class of the chain that any
+    /// visible source extends, `module` itself before its imports.
+    pub(crate) fn lookup_extension(&self, module: ObjRef, class: ClassId, selector: Symbol) -> Option<ObjRef> {
+        let scope = &self.heap.module(module).extension_sources;
+        if scope.is_empty() && self.heap.module(module).extensions.is_empty() {
+            return None;
+        }
+        let mut current = Some(class);
+        while let Some(class) = current {
+            for source in std::iter::once(module).chain(scope.iter().copied()) {
+                if let Some(&method) = self.heap.module(source).extensions.get(&(class, selector)) {
+                    return Some(method);
+                }
+            }
+            current = self.heap.class(class).superclass;
+        }
+        None
+    }
+
+    /// The module the innermost running frame was compiled in.
+    pub(crate) fn current_module(&self) -> Option<ObjRef> {
+        self.frames.last().map(|frame| self.heap.closure(frame.closure).module)
+    }
+}
@@ -12,8 +12,11 @@
 //! merely over-retains, which is safe and merely wasteful. When unsure whether
 //! something is a root, root it.
 
-use super::VM;
-use crate::heap::{ObjRef, trace_frame};
+use std::time::Instant;
+
+use super::{ResourceLimit, VM};
+use crate::error::PhResult;
+use crate::heap::{GcPauseKind, ObjRef, SafepointWork, trace_frame};
 
 impl VM {
     /// Collects the complete root set into `out`.
@@ -52,6 +55,14 @@ impl VM {
             // missing this frees a live object under its holder (Invariant M3).
             temp_roots,
 
+            // Values an embedder holds as `Handle`s, and the `HostFunction`
+            // class (`embed/`). Reachable from nowhere else while the host
+            // holds them.
+            host,
+
+            // The `Invocation` class (`intercept.rs`).
+            intercept,
+
             // Module handles.
             module_registry,
             runtime_roots,
@@ -103,6 +114,9 @@ impl VM {
             resources: _,
             strict_resources: _,
             numeric_policy: _,
+            // Sandbox configuration and counters — no object handles.
+            limits: _,
+            meter: _,
             typing_registry: _,
             #[cfg(feature = "fiber-pool")]
                 fiber_pool: _,
@@ -120,6 +134,8 @@ impl VM {
         out.extend(open_upvalues.values().copied());
         out.extend(ready_queue.iter().copied());
         out.extend(temp_roots.iter().copied());
+        host.each_handle(&mut |id| out.push(id));
+        intercept.each_handle(&mut |id| out.push(id));
         module_registry.each_handle(&mut |id| out.push(id));
         if let Some(roots) = runtime_roots {
             out.push(roots.core);
@@ -148,10 +164,75 @@ impl VM {
     /// Safepoint-latched triggering (`service_gc_safepoint`)
     /// and the `push_temp_root` escape hatch that makes
     /// the native side safe are both live.
+    ///
+    /// A collection that found dead `FinalizationRegistry` targets then queues
+    /// one drain fiber per affected registry on [`VM::ready_queue`] — *after*
+    /// the sweep, and without running it: cleanup callbacks execute only when
+    /// the scheduler next pumps the queue, never inside the collector
+    /// (Invariant M4, `heap/weak.rs`).
     pub fn force_gc(&mut self) -> usize {
+        let started = Instant::now();
+        let mut roots = Vec::new();
+        self.collect_roots(&mut roots);
+        let swept = self.heap.collect(&roots);
+        self.schedule_finalization_cleanups();
+        self.heap.record_gc_pause(GcPauseKind::Full, started.elapsed(), swept);
+        swept
+    }
+
+    /// Runs one incremental marking slice, starting a cycle first if none is
+    /// running, and finishes the cycle's marking once the gray set drains.
+    ///
+    /// Same safepoint discipline as [`force_gc`](Self::force_gc): roots are
+    /// enumerated here, at the start of a cycle and again at its finish
+    /// ([`Heap::finish_cycle`](crate::heap::Heap::finish_cycle) explains why
+    /// twice), never in between.
+    fn gc_slice(&mut self) {
+        let started = Instant::now();
         let mut roots = Vec::new();
+        if !self.heap.is_marking() {
+            self.collect_roots(&mut roots);
+            self.heap.begin_cycle(&roots);
+            roots.clear();
+        }
+        if !self.heap.mark_slice() {
+            self.heap.record_gc_pause(GcPauseKind::Slice, started.elapsed(), 0);
+            return;
+        }
         self.collect_roots(&mut roots);
-        self.heap.collect(&roots)
+        self.heap.finish_cycle(&roots);
+        self.schedule_finalization_cleanups();
+        self.heap.record_gc_pause(GcPauseKind::Finish, started.elapsed(), 0);
+    }
+
+    /// Frees one slice of the dead an incremental cycle left behind.
+    fn gc_sweep_slice(&mut self) {
+        let started = Instant::now();
+        let (swept, _) = self.heap.sweep_slice();
+        self.heap.record_gc_pause(GcPauseKind::Sweep, started.elapsed(), swept);
+    }
+
+    /// Enqueues a drain fiber for every registry [`Heap::collect`](crate::heap::Heap::collect)
+    /// left with new pending cleanups.
+    ///
+    /// Allocating the fibers here is safe for the same reason any allocation
+    /// is: `Heap::alloc` only latches (Invariant L), and each fiber is rooted by
+    /// `ready_queue` the moment it is pushed. A registry built without the `.ph`
+    /// constructor has no drain entry; its cleanups wait for an explicit
+    /// `cleanupSome()`.
+    fn schedule_finalization_cleanups(&mut self) {
+        for registry in self.heap.take_pending_cleanups() {
+            let Some(drain) = self.heap.finalization_registry(registry).drain else {
+                self.heap.finalization_registry_mut(registry).scheduled = false;
+                continue;
+            };
+            // `_$setDrain(_)` admitted only a `Block`/`Closure`, so this cannot
+            // fail; a failure would leave the registry unscheduled, not corrupt.
+            match crate::primitive::fiber::new_fiber_ref(self, crate::value::Value::obj(drain)) {
+                Ok(fiber) => self.ready_queue.push_back(fiber),
+                Err(_) => self.heap.finalization_registry_mut(registry).scheduled = false,
+            }
+        }
     }
 
     /// Roots `value` for the collector until the matching
@@ -216,9 +297,33 @@ impl VM {
     /// `frames` are the complete root truth. Never from `Heap::alloc` (Invariant L,
     /// memory-management.md §4), and never mid-opcode: several opcodes have a window
     /// where a value is popped or `split_off` the stack and held only in a Rust local.
-    pub(crate) fn service_gc_safepoint(&mut self) {
-        if self.heap.gc_due_at_safepoint() {
-            self.force_gc();
+    ///
+    /// A latched collection runs incrementally by default — one bounded
+    /// marking or sweep slice per safepoint until the cycle completes — so
+    /// the pause a safepoint can cost no longer grows with the heap.
+    ///
+    /// # Errors
+    ///
+    /// [`RuntimeError::LimitExceeded`](crate::error::RuntimeError::LimitExceeded)
+    /// when the heap is over [`VmLimits::max_heap_bytes`](crate::vm::VmLimits::max_heap_bytes)
+    /// even after a full collection. This is the only place the byte cap is
+    /// enforced: `alloc` merely latches it, and raising here, where the stack
+    /// is coherent, is an ordinary unwind.
+    pub(crate) fn service_gc_safepoint(&mut self) -> PhResult<()> {
+        match self.heap.safepoint_work() {
+            SafepointWork::Idle => {}
+            SafepointWork::Full => {
+                self.force_gc();
+            }
+            SafepointWork::Slice => self.gc_slice(),
+            SafepointWork::Sweep => self.gc_sweep_slice(),
+            SafepointWork::ByteLimit => {
+                self.force_gc();
+                if self.heap.over_byte_limit() {
+                    return Err(self.limit_exceeded(ResourceLimit::HeapBytes));
+                }
+            }
         }
+        Ok(())
     }
 }
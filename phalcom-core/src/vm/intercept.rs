@@ -0,0 +1,453 @@
+//! Runtime-tier interception: the `aroundSend(_)` hook of an `Attribute`
+//! subclass declared `@On(..., Runtime)` (runtime-tier.md).
+//!
+//! **Composition.** When a class finishes defining — the class-level
+//! `__freezeAttributes()` its body ends with — [`VM::compose_interceptors`]
+//! walks its own methods and records each one's chain on
+//! [`MethodObject::interceptors`](crate::method::MethodObject::interceptors):
+//! class-level Runtime attributes outermost, in source order, then the
+//! member's own. A class-level attribute intercepts the instance side only,
+//! constructors' initializer bodies aside; a static member is intercepted only
+//! by its own attributes. An attribute is
+//! Runtime-tier exactly when its class answers `aroundSend(_)` —
+//! `compiler::attributes` rejects that hook on any other tier — and the hook
+//! is looked up once here, so interceptor dispatch is never itself
+//! intercepted.
+//!
+//! **The send path.** Every method activation funnels through
+//! `call_method_with_selector_as` (`send.rs`), which reads the method's chain
+//! before anything else. Empty — every undecorated method — is today's path,
+//! unchanged. Otherwise the send is reified as an `Invocation` (receiver,
+//! selector, a `List` of the arguments, the method) and the first
+//! interceptor's `aroundSend(inv)` runs; its result is the send's result.
+//! `inv.proceed()` runs the next interceptor, or the method itself at the end
+//! of the chain. Calling it twice re-runs the rest of the chain twice; not
+//! calling it skips the method. Each interceptor receives its own
+//! `Invocation`, positioned past itself, and `proceed()` never moves it, so a
+//! stored invocation re-runs the method fresh — it is not a continuation. The
+//! `args` list is shared down the chain.
+//!
+//! Every step is an ordinary frame over the send's own stack window: the
+//! hook's activation, then the method's, return straight into the send's
+//! result slot. Nothing runs in a nested interpreter loop, so a decorated
+//! method — and an interceptor — may suspend its fiber like any other code.
+//!
+//! **Bypass.** Sends made while an interceptor body runs are not intercepted,
+//! so a tracing sink that logs through a traced object cannot recurse into
+//! itself; `proceed()` lifts the bypass again for the method it runs. The
+//! entered frames carry an [`InterceptRole`], and a decorated send is bypassed
+//! when the nearest marked frame below it is a hook. Frames belong to their
+//! fiber, so the bypass is per fiber, and a throw unwinds it with the frames.
+//! A primitive method has no frame of its own, so sends it makes back into
+//! `.ph` still see the hook below it.
+//!
+//! **Inline caches.** A cache slot records the method handle, and the chain
+//! lives on that method object, so a cached send sees interception without a
+//! re-lookup. Composition still bumps `world_version` and notes each
+//! intercepted selector as installed, so the sacred-selector fast paths
+//! (ADR-0018) deopt to real sends that reach the chain.
+
+use crate::error::{PhResult, RuntimeError};
+use crate::frame::InterceptRole;
+use crate::heap::{ClassId, InstanceObject, ObjRef, Object};
+use crate::interner::Symbol;
+use crate::method::{ArgumentView, CallOutcome, MemberVisibility, MethodObject, Signature, SignatureKind, make_signature};
+use crate::value::Value;
+use crate::vm::VM;
+use phalcom_common::range::SourceRange;
+
+type ShapePrimitiveFn = fn(&mut VM, Value, ArgumentView) -> PhResult<CallOutcome>;
+
+/// `Invocation` slot indices.
+const RECEIVER: usize = 0;
+const SELECTOR: usize = 1;
+const ARGS: usize = 2;
+const METHOD: usize = 3;
+/// Position in the method's chain of the step `proceed()` runs.
+const INDEX: usize = 4;
+/// Arity of the original send, which `proceed()` re-checks against `ARGS`.
+const ARITY: usize = 5;
+/// Labeled-argument count of the original send's shape, or `nil` when it
+/// arrived without one.
+const LABELED: usize = 6;
+const SLOT_COUNT: u16 = 7;
+
+/// Interception state the VM carries between sends.
+#[derive(Default)]
+pub(crate) struct InterceptState {
+    /// `Invocation`, created with the first intercepted send.
+    class: Option<ClassId>,
+}
+
+impl InterceptState {
+    /// Visits the handles this state holds — the collector's half.
+    pub(crate) fn each_handle(&self, f: &mut dyn FnMut(ObjRef)) {
+        if let Some(class) = self.class {
+            f(class);
+        }
+    }
+}
+
+impl VM {
+    /// Records the Runtime-tier chain of each of `class`'s own methods. Called
+    /// once, when the class's attribute store is frozen.
+    pub(crate) fn compose_interceptors(&mut self, class: ClassId) {
+        let hook = self.interner.intern(&make_signature("aroundSend", SignatureKind::Method(1)));
+        let class_chain = self.runtime_chain(&self.heap.class(class).attributes, hook);
+        let metaclass = self.heap.class(class).class;
+        let mut composed = false;
+        for (holder, outer) in [(class, class_chain), (metaclass, Vec::new())] {
+            // A multimethod's own cases are intercepted, not its dispatcher:
+            // a send runs the selected case's chain (`multimethod.rs`).
+            let methods: Vec<(Symbol, ObjRef)> = self
+                .heap
+                .class(holder)
+                .methods
+                .iter()
+                .flat_map(|(&selector, &method)| match &self.heap.method(method).multi {
+                    Some(multi) => multi.cases.iter().map(|&(_, case)| (selector, case)).collect(),
+                    None => vec![(selector, method)],
+                })
+                .collect();
+            for (selector, method) in methods {
+                let chain = {
+                    let method = self.heap.method(method);
+                    // A constructor's `init …` body is part of `new`, not a
+                    // send to the instance.
+                    if method.holder != Some(holder) || method.visibility == MemberVisibility::Internal || self.interner.lookup(selector).starts_with("init ") {
+                        continue;
+                    }
+                    let mut chain = outer.clone();
+                    chain.extend(self.runtime_chain(&method.attributes, hook));
+                    chain
+                };
+                if chain.is_empty() {
+                    continue;
+                }
+                self.heap.method_mut(method).interceptors = chain;
+                self.universe.note_method_installed(holder, selector, &self.interner);
+                composed = true;
+            }
+        }
+        if composed {
+            self.world_version += 1;
+        }
+    }
+
+    /// The Runtime-tier attributes among `attributes`, each paired with its
+    /// `hook` method.
+    fn runtime_chain(&self, attributes: &[Value], hook: Symbol) -> Vec<(Value, ObjRef)> {
+        attributes
+            .iter()
+            .filter_map(|&attribute| attribute.lookup_method(self, hook).map(|method| (attribute, method)))
+            .collect()
+    }
+
+    /// Whether a send made now runs past interception: the nearest marked
+    /// frame on this fiber is an interceptor's body.
+    pub(super) fn in_interceptor(&self) -> bool {
+        matches!(self.frames.iter().rev().find_map(|frame| frame.interception), Some(InterceptRole::Hook { .. }))
+    }
+
+    /// Starts an intercepted send of `method` whose receiver and `arity`
+    /// arguments are on top of the stack. Its result replaces them.
+    ///
+    /// # Errors
+    ///
+    /// An access violation, or whatever the first step raises on entry.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn intercept_send(
+        &mut self,
+        method: ObjRef,
+        arity: usize,
+        selector: Symbol,
+        shape: Option<(usize, usize)>,
+        source_range: SourceRange,
+        caller_authority: (Option<ClassId>, bool),
+    ) -> PhResult<()> {
+        self.authorize_method_access_as(method, caller_authority.0, caller_authority.1)?;
+        let receiver_idx = self.stack.len() - arity - 1;
+        let invocation = self.new_invocation(receiver_idx, selector, method, shape.map(|(_, labeled)| labeled));
+        self.enter_step(receiver_idx, invocation, false, source_range).map(drop)
+    }
+
+    fn new_invocation(&mut self, receiver_idx: usize, selector: Symbol, method: ObjRef, labeled: Option<usize>) -> Value {
+        let class = self.invocation_class();
+        let args = self.stack[receiver_idx + 1..].to_vec();
+        let arity = args.len();
+        let args = Value::obj(self.heap.alloc_list(args));
+        let mut instance = InstanceObject::new(class, SLOT_COUNT);
+        instance.slots[RECEIVER] = self.stack[receiver_idx];
+        instance.slots[SELECTOR] = Value::symbol(selector);
+        instance.slots[ARGS] = args;
+        instance.slots[METHOD] = Value::obj(method);
+        instance.slots[INDEX] = Value::int(0);
+        instance.slots[ARITY] = Value::int(arity as i64);
+        instance.slots[LABELED] = labeled.map_or_else(Value::nil, |labeled| Value::int(labeled as i64));
+        Value::obj(self.heap.alloc(Object::Instance(instance)))
+    }
+
+    /// Replaces the stack window at `receiver_idx` with `invocation`'s next
+    /// step — the next interceptor's `aroundSend(_)`, or the method itself
+    /// once the chain is exhausted — and enters it. Under `attempt` the step
+    /// settles into an `Ok`/`Err`: here when it completes without a frame,
+    /// otherwise when its frame returns or a throw unwinds it
+    /// ([`Self::settle_attempt_error`]).
+    fn enter_step(&mut self, receiver_idx: usize, invocation: Value, attempt: bool, source_range: SourceRange) -> PhResult<CallOutcome> {
+        match self.enter_step_frame(receiver_idx, invocation, attempt, source_range) {
+            Ok(CallOutcome::Returned(value)) if attempt => {
+                let settled = self.settle(Ok(value));
+                self.stack.truncate(receiver_idx);
+                self.stack.push(settled);
+                Ok(CallOutcome::Returned(settled))
+            }
+            Err(error) if attempt && !error.is_limit_exceeded() => {
+                let error = self.capture_error_value(&error);
+                let settled = self.settle(Err(error));
+                self.stack.truncate(receiver_idx);
+                self.stack.push(settled);
+                Ok(CallOutcome::Returned(settled))
+            }
+            outcome => outcome,
+        }
+    }
+
+    fn enter_step_frame(&mut self, receiver_idx: usize, invocation: Value, attempt: bool, source_range: SourceRange) -> PhResult<CallOutcome> {
+        let id = invocation_id(self, &invocation)?;
+        let (slots, method, index) = invocation_step(self, id)?;
+
+        let (receiver, target, arity, selector, shape, role) = if let Some(&(attribute, hook)) = self.heap.method(method).interceptors.get(index) {
+            // The hook's own invocation, one step further along.
+            let mut next = self.heap.instance(id).clone();
+            next.slots[INDEX] = Value::int(index as i64 + 1);
+            let next = Value::obj(self.heap.alloc(Object::Instance(next)));
+            self.stack.truncate(receiver_idx);
+            self.stack.extend([attribute, next]);
+            (attribute, hook, 1, self.heap.method(hook).selector(), None, InterceptRole::Hook { attempt })
+        } else {
+            let args = slots[ARGS]
+                .as_obj()
+                .and_then(|args| self.heap.as_list(args))
+                .map(|args| args.elements().to_vec())
+                .ok_or(RuntimeError::Type {
+                    expected: "List",
+                    found: slots[ARGS].type_name(),
+                })?;
+            let arity = slots[ARITY]
+                .as_int()
+                .and_then(|arity| usize::try_from(arity).ok())
+                .ok_or_else(malformed_invocation)?;
+            if args.len() != arity {
+                return Err(RuntimeError::Arity {
+                    signature: "proceed()",
+                    expected: arity,
+                    found: args.len(),
+                }
+                .into());
+            }
+            let shape = if slots[LABELED].is_nil() {
+                None
+            } else {
+                let labeled = slots[LABELED]
+                    .as_int()
+                    .and_then(|labeled| usize::try_from(labeled).ok())
+                    .filter(|&labeled| labeled <= arity)
+                    .ok_or_else(malformed_invocation)?;
+                Some((arity - labeled, labeled))
+            };
+            let selector = slots[SELECTOR].as_symbol().map_err(|_| malformed_invocation())?;
+            self.stack.truncate(receiver_idx);
+            self.stack.push(slots[RECEIVER]);
+            self.stack.extend(args);
+            (slots[RECEIVER], method, arity, selector, shape, InterceptRole::Method { attempt })
+        };
+
+        // Access was authorized when the send was intercepted; each step runs
+        // with its own holder's authority.
+        let owner = {
+            let target = self.heap.method(target);
+            target.access_owner.or(target.holder)
+        };
+        let before = self.frames.len();
+        self.activate_method_as(&receiver, target, arity, selector, shape, source_range, (owner, true))?;
+        if self.frames.len() > before {
+            if let Some(frame) = self.frames.last_mut() {
+                frame.interception = Some(role);
+            }
+            Ok(CallOutcome::EnteredFrame)
+        } else {
+            Ok(CallOutcome::Returned(self.stack.last().copied().unwrap_or_else(Value::nil)))
+        }
+    }
+
+    /// Wraps a settled step's outcome in the core `Ok`/`Err`.
+    pub(super) fn settle(&mut self, outcome: Result<Value, Value>) -> Value {
+        let (name, payload) = match outcome {
+            Ok(value) => ("Ok", value),
+            Err(error) => ("Err", error),
+        };
+        let core = self.core_module().expect("core module is loaded before any send is intercepted");
+        let name = self.interner.intern(name);
+        let class = self
+            .heap
+            .module(core)
+            .get(name)
+            .and_then(|class| class.as_obj())
+            .expect("Ok/Err are defined by the core universe");
+        let field_count = self.heap.class(class).field_count;
+        let mut instance = InstanceObject::new(class, field_count);
+        instance.slots[0] = payload;
+        Value::obj(self.heap.alloc(Object::Instance(instance)))
+    }
+
+    /// Catches `error` at the innermost `attempt` step above `base_frames`,
+    /// the run loop's counterpart of `on(_)`: the frames above the step and
+    /// the step's own are discarded and `Err(error)` is pushed as its send's
+    /// result. Answers `false` when no such step exists, and the error keeps
+    /// unwinding.
+    pub(super) fn settle_attempt_error(&mut self, base_frames: usize, error: &crate::error::PhError) -> bool {
+        if error.is_limit_exceeded() {
+            return false;
+        }
+        let Some(index) = (base_frames..self.frames.len())
+            .rev()
+            .find(|&index| self.frames[index].interception.is_some_and(InterceptRole::attempt))
+        else {
+            return false;
+        };
+        let stack_offset = self.frames[index].stack_offset;
+        let error = self.capture_error_value(error);
+        self.unwind_to(stack_offset, index);
+        let settled = self.settle(Err(error));
+        self.stack.push(settled);
+        true
+    }
+
+    fn invocation_class(&mut self) -> ClassId {
+        if let Some(class) = self.intercept.class {
+            return class;
+        }
+        let object = self.universe.classes.object_class;
+        let class = self.create_single_class("Invocation", Some(object));
+        let metaclass = self.create_single_class("Invocation.class", Some(self.heap.class(object).class));
+        self.heap.class_mut(metaclass).set_class(self.universe.classes.metaclass_class);
+        self.heap.class_mut(class).set_class(metaclass);
+        self.heap.class_mut(class).field_count = SLOT_COUNT;
+
+        let getters: [(&str, crate::method::LegacyPrimitiveFn); 4] = [
+            ("receiver", invocation_receiver),
+            ("selector", invocation_selector),
+            ("args", invocation_args),
+            ("method", invocation_method),
+        ];
+        for (name, primitive) in getters {
+            let selector = self.interner.intern(&make_signature(name, SignatureKind::Getter));
+            let method = MethodObject::new_primitive(selector, SignatureKind::Getter, primitive, class);
+            let method = self.heap.alloc(Object::Method(Box::new(method)));
+            self.heap.class_mut(class).add_method(selector, method);
+        }
+        // `proceed()`/`attempt()` enter the next step's frame over their own
+        // send window.
+        let steps: [(&str, ShapePrimitiveFn); 2] = [("proceed", invocation_proceed), ("attempt", invocation_attempt)];
+        for (name, primitive) in steps {
+            let selector = self.interner.intern(&make_signature(name, SignatureKind::Method(0)));
+            let method = MethodObject::new_shape_primitive(selector, Signature::new(selector, SignatureKind::Method(0)), primitive, class);
+            let method = self.heap.alloc(Object::Method(Box::new(method)));
+            self.heap.class_mut(class).add_method(selector, method);
+        }
+
+        self.finalize_class_base_names(class);
+        self.world_version += 1;
+        self.intercept.class = Some(class);
+        class
+    }
+}
+fn invocation_id(vm: &VM, value: &Value) -> PhResult<ObjRef> {
+    value
+        .as_obj()
+        .filter(|&id| vm.heap.as_instance(id).is_some_and(|instance| Some(instance.class) == vm.intercept.class))
+        .ok_or_else(|| {
+            RuntimeError::Type {
+                expected: "Invocation",
+                found: value.type_name(),
+            }
+            .into()
+        })
+}
+
+/// The slots of `Invocation` `id`, with the method and chain index of the
+/// step it runs next. `Invocation.new()` builds one whose slots were never
+/// filled by an intercepted send.
+///
+/// # Errors
+///
+/// `intercept.invocation` when the method or index slot is missing or of the
+/// wrong kind.
+fn invocation_step(vm: &VM, id: ObjRef) -> PhResult<(Box<[Value]>, ObjRef, usize)> {
+    let slots = vm.heap.instance(id).slots.clone();
+    if slots.len() < SLOT_COUNT as usize {
+        return Err(malformed_invocation().into());
+    }
+    let method = slots[METHOD]
+        .as_obj()
+        .filter(|&method| matches!(vm.heap.get(method), Object::Method(_)))
+        .ok_or_else(malformed_invocation)?;
+    let index = slots[INDEX]
+        .as_int()
+        .and_then(|index| usize::try_from(index).ok())
+        .ok_or_else(malformed_invocation)?;
+    Ok((slots, method, index))
+}
+
+fn malformed_invocation() -> RuntimeError {
+    RuntimeError::NotAllowed("intercept.invocation: this `Invocation` was not made by an intercepted send".to_string())
+}
+
+fn invocation_slot(vm: &VM, receiver: &Value, slot: usize) -> PhResult<Value> {
+    let id = invocation_id(vm, receiver)?;
+    Ok(vm.heap.instance(id).slots[slot])
+}
+
+/// Signature: `Invocation::receiver` — the object the send targeted.
+fn invocation_receiver(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    invocation_slot(vm, receiver, RECEIVER)
+}
+
+/// Signature: `Invocation::selector` — the send's selector `Symbol`.
+fn invocation_selector(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    invocation_slot(vm, receiver, SELECTOR)
+}
+
+/// Signature: `Invocation::args` — the send's arguments as a `List`. Edits
+/// to it are what `proceed()` passes on.
+fn invocation_args(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    invocation_slot(vm, receiver, ARGS)
+}
+
+/// Signature: `Invocation::method` — the intercepted `Method`.
+fn invocation_method(vm: &mut VM, receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    invocation_slot(vm, receiver, METHOD)
+}
+
+/// Signature: `Invocation::proceed()` — runs the rest of the chain and
+/// answers its result.
+///
+/// # Errors
+///
+/// [`RuntimeError::Arity`] when `args` no longer holds the send's argument
+/// count; otherwise whatever the rest of the chain raises.
+fn invocation_proceed(vm: &mut VM, receiver: Value, args: ArgumentView) -> PhResult<CallOutcome> {
+    vm.enter_step(args.receiver_index(), receiver, false, SourceRange::default())
+}
+
+/// Signature: `Invocation::attempt()` — as `proceed()`, answering `Ok(result)`,
+/// or `Err(error)` for a throw out of the rest of the chain. Unlike
+/// `{ inv.proceed() }.attempt()` it puts no native frame under the method, so
+/// the method may still suspend its fiber.
+///
+/// # Errors
+///
+/// Only a sandbox limit, which is never settled into a `Result`.
+fn invocation_attempt(vm: &mut VM, receiver: Value, args: ArgumentView) -> PhResult<CallOutcome> {
+    vm.enter_step(args.receiver_index(), receiver, true, SourceRange::default())
+}
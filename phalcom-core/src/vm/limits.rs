@@ -0,0 +1,233 @@
+//! Sandbox limits for running untrusted code: a fuel budget, a heap cap, a
+//! configurable call-depth ceiling, a wall-clock deadline, and a switch that
+//! refuses the host-access `std` modules.
+//!
+//! An embedder evaluating user-supplied formulas configures a [`VmLimits`]
+//! once with [`VM::set_limits`] and calls [`VM::rearm_limits`] before each
+//! evaluation. Fuel, heap and deadline overruns raise
+//! [`RuntimeError::LimitExceeded`], which `.ph` code cannot catch and which
+//! skips `ensure` cleanups on its way out to the host; the
+//! call-depth ceiling keeps raising the ordinary, catchable
+//! [`RuntimeError::DepthExceeded`] (PDR-0007 §2), because unwinding a deep
+//! stack already gives the host back everything the limit protects.
+//!
+//! **Where the meter runs.** Fuel is charged at every loop back-edge
+//! ([`Bytecode::Loop`](crate::bytecode::Bytecode::Loop)) and every call-frame
+//! push, so neither a `while (true) {}` nor unbounded recursion through a
+//! native iterator (`whileTrue`, `each`) escapes it. The deadline is checked
+//! on the same ticks, reading the clock only every [`DEADLINE_CHECK_INTERVAL`]
+//! of them. A single long-running native primitive is not interruptible; the
+//! numeric policy ([`VmLimits::numeric_policy`], which [`VmLimits::sandbox`]
+//! sets to [`NumericPolicy::sandbox`]) is what bounds those.
+//!
+//! **Heap cap.** `Heap::alloc` only latches an overrun (Invariant L); the
+//! next safepoint runs a full collection and raises only if the heap is still
+//! over the cap once garbage is gone. Sizes are the shallow estimate of
+//! [`Object::approx_bytes`](crate::heap::Object::approx_bytes), re-counted
+//! over every surviving object at each collection — so a container that grows
+//! in place without allocating is charged at the next collection.
+//!
+//! The cap is therefore soft by at most one instruction's allocations: the
+//! safepoint runs before every instruction, so the overrun is seen as soon as
+//! the instruction that caused it returns. A native primitive that builds one
+//! large object (a concatenated `String`, a `List` from a range) completes
+//! first; for numbers, the numeric policy's `max_numeric_allocation_bytes`
+//! bounds that single allocation. A primitive that calls back into `.ph` code
+//! passes through safepoints on the way, so the bound is per instruction, not
+//! per primitive.
+
+use crate::error::{PhResult, RuntimeError};
+use crate::value::NumericPolicy;
+use crate::vm::{MAX_CALL_DEPTH, VM};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Meter ticks between two reads of the clock for [`VmLimits::timeout`].
+///
+/// `Instant::now` costs tens of nanoseconds; a back-edge costs a few. Reading
+/// it every 1024 ticks keeps the deadline within microseconds of accurate
+/// without putting a syscall on every loop iteration.
+pub const DEADLINE_CHECK_INTERVAL: u32 = 1024;
+
+/// The `std` modules a [`VmLimits::allow_host_modules`] `false` sandbox refuses
+/// to import — everything that reaches outside the process.
+pub const HOST_MODULES: [&str; 3] = ["fs", "process", "net"];
+
+/// Resource limits for a [`VM`], all off by default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VmLimits {
+    /// Meter ticks (loop back-edges plus call-frame pushes) allowed between
+    /// two [`VM::rearm_limits`] calls.
+    pub fuel: Option<u64>,
+    /// Cap on the estimated bytes of live heap objects, kernel included.
+    /// Enforced at the next safepoint, so it can be overshot by what a single
+    /// instruction allocates (see the module docs).
+    pub max_heap_bytes: Option<usize>,
+    /// Ceiling on the `.ph` call-frame stack; defaults to [`MAX_CALL_DEPTH`].
+    pub max_call_depth: usize,
+    /// Wall-clock time allowed after each [`VM::rearm_limits`].
+    pub timeout: Option<Duration>,
+    /// Whether `import std.fs`, `std.process` and `std.net` may be
+    /// materialized ([`HOST_MODULES`]).
+    pub allow_host_modules: bool,
+    /// Numeric budgets installed as [`VM::numeric_policy`]; `None` keeps the
+    /// VM's current policy.
+    pub numeric_policy: Option<NumericPolicy>,
+}
+
+impl VmLimits {
+    /// No limits beyond the VM's built-in call-depth ceiling.
+    pub const fn unlimited() -> Self {
+        Self {
+            fuel: None,
+            max_heap_bytes: None,
+            max_call_depth: MAX_CALL_DEPTH,
+            timeout: None,
+            allow_host_modules: true,
+            numeric_policy: None,
+        }
+    }
+
+    /// A starting point for untrusted snippets: enough for a formula or a
+    /// small script, nowhere near enough to hang or exhaust the host.
+    pub const fn sandbox() -> Self {
+        Self {
+            fuel: Some(10_000_000),
+            max_heap_bytes: Some(64 * 1024 * 1024),
+            max_call_depth: 1_000,
+            timeout: Some(Duration::from_secs(1)),
+            allow_host_modules: false,
+            numeric_policy: Some(NumericPolicy::sandbox()),
+        }
+    }
+}
+
+impl Default for VmLimits {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+/// Which sandbox limit a [`RuntimeError::LimitExceeded`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceLimit {
+    /// [`VmLimits::fuel`] ran out.
+    Fuel,
+    /// [`VmLimits::max_heap_bytes`] was still exceeded after a collection.
+    HeapBytes,
+    /// [`VmLimits::timeout`] elapsed.
+    Deadline,
+}
+
+impl ResourceLimit {
+    /// The unit [`RuntimeError::LimitExceeded`]'s `budget` is measured in.
+    pub fn unit(self) -> &'static str {
+        match self {
+            ResourceLimit::Fuel => "ticks",
+            ResourceLimit::HeapBytes => "bytes",
+            ResourceLimit::Deadline => "ms",
+        }
+    }
+}
+
+impl fmt::Display for ResourceLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ResourceLimit::Fuel => "fuel",
+            ResourceLimit::HeapBytes => "heap",
+            ResourceLimit::Deadline => "time",
+        })
+    }
+}
+
+/// The running state behind [`VmLimits::fuel`] and [`VmLimits::timeout`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LimitMeter {
+    /// Whether either metered limit is set; the only field the hot path reads.
+    armed: bool,
+    fuel_left: Option<u64>,
+    ticks: u32,
+    deadline: Option<Instant>,
+    /// Set once the deadline has been seen to pass, so the ticks between two
+    /// clock reads raise too.
+    expired: bool,
+}
+
+impl VM {
+    /// Installs `limits` and arms them, as [`Self::rearm_limits`] does.
+    pub fn set_limits(&mut self, limits: VmLimits) {
+        self.heap.set_byte_limit(limits.max_heap_bytes);
+        if let Some(policy) = limits.numeric_policy {
+            self.numeric_policy = policy;
+        }
+        self.limits = limits;
+        self.rearm_limits();
+    }
+
+    /// The limits this VM enforces.
+    pub fn limits(&self) -> &VmLimits {
+        &self.limits
+    }
+
+    /// Refills the fuel budget and restarts the deadline clock.
+    ///
+    /// A tripped limit stays tripped — every later tick raises again — until
+    /// this is called.
+    pub fn rearm_limits(&mut self) {
+        self.meter = LimitMeter {
+            armed: self.limits.fuel.is_some() || self.limits.timeout.is_some(),
+            fuel_left: self.limits.fuel,
+            ticks: 0,
+            deadline: self.limits.timeout.map(|timeout| Instant::now() + timeout),
+            expired: false,
+        };
+    }
+
+    /// Fuel left before [`ResourceLimit::Fuel`] trips, if fuel is metered.
+    pub fn fuel_remaining(&self) -> Option<u64> {
+        self.meter.fuel_left
+    }
+
+    /// Charges one meter tick — called at every loop back-edge and frame push.
+    ///
+    /// # Errors
+    ///
+    /// [`RuntimeError::LimitExceeded`] once the fuel budget is spent or the
+    /// deadline has passed.
+    #[inline]
+    pub(crate) fn charge_fuel(&mut self) -> PhResult<()> {
+        if !self.meter.armed {
+            return Ok(());
+        }
+        self.charge_metered()
+    }
+
+    fn charge_metered(&mut self) -> PhResult<()> {
+        if let Some(fuel) = self.meter.fuel_left.as_mut() {
+            if *fuel == 0 {
+                return Err(self.limit_exceeded(ResourceLimit::Fuel));
+            }
+            *fuel -= 1;
+        }
+        if let Some(deadline) = self.meter.deadline {
+            let due = self.meter.ticks % DEADLINE_CHECK_INTERVAL == 0;
+            self.meter.ticks = self.meter.ticks.wrapping_add(1);
+            if self.meter.expired || (due && Instant::now() >= deadline) {
+                self.meter.expired = true;
+                return Err(self.limit_exceeded(ResourceLimit::Deadline));
+            }
+        }
+        Ok(())
+    }
+
+    /// The [`RuntimeError::LimitExceeded`] for `limit`, naming its configured
+    /// budget.
+    pub(crate) fn limit_exceeded(&self, limit: ResourceLimit) -> crate::error::PhError {
+        let budget = match limit {
+            ResourceLimit::Fuel => self.limits.fuel.unwrap_or_default(),
+            ResourceLimit::HeapBytes => self.limits.max_heap_bytes.unwrap_or_default() as u64,
+            ResourceLimit::Deadline => self.limits.timeout.map_or(0, |timeout| timeout.as_millis() as u64),
+        };
+        RuntimeError::LimitExceeded { limit, budget }.into()
+    }
+}
@@ -0,0 +1,326 @@
+//! Mixins: horizontal method reuse across the single-inheritance tower.
+//!
+//! **Declaration.** `@mixin class Ordering { … }` compiles to an ordinary
+//! class row whose superclass is `Object`, then `_$mixin(_)` marks it
+//! ([`VM::declare_mixin`]): the row becomes abstract, cannot be named in an
+//! `is` clause, and carries the selectors it requires of a host (named
+//! directly or through a protocol) plus any mixins it composes itself. A host
+//! names its mixins with `@compose(A, B)` ([`VM::declare_composition`]). Both
+//! sends run before the body's `FinalizeClass`, so every own method is
+//! installed by then.
+//!
+//! **Application.** `FinalizeClass` calls [`VM::apply_mixins`]. Each direct
+//! mixin's instance methods are bound into the host's own dictionary, sharing
+//! the mixin's method objects (their `holder` stays the mixin, so access
+//! checks and interceptor composition treat them as the mixin's; its
+//! `@protected` members are visible to its hosts). A selector
+//! the host declares itself is never overwritten. Two different method
+//! objects for one selector from different mixins is `mixin.conflict` unless
+//! the host declares that selector — the host's own method is the
+//! resolution, reaching either contribution with `super`. The same method
+//! object arriving twice, via a shared inner mixin, is not a conflict. Naming
+//! a mixin a superclass already applies is `mixin.redundant`; one reached
+//! only through another mixin is skipped instead, so a subclass cannot
+//! re-shadow its superclass's overrides of it. A non-mixin host must then
+//! answer every selector its new mixins require.
+//!
+//! **Linearization.** [`VM::ancestors`] is the class, its effective mixins
+//! (depth-first in declaration order, each once, minus those an ancestor
+//! applied), then the same for its superclass, up to `Object`. Ordinary
+//! lookup needs no walk — the mixins' methods are already in the host's own
+//! dictionary, which is why the host's own methods win over its mixins and
+//! its mixins over its superclass. `super` follows the list:
+//! [`VM::super_method`] continues after the defining class or mixin.
+//! Mixins are instance-side only; a mixin's class-side methods stay its own.
+
+use crate::error::{PhResult, RuntimeError};
+use crate::heap::{ClassId, ObjRef, lookup_method_in_hierarchy};
+use crate::interner::Symbol;
+use crate::value::Value;
+use crate::vm::VM;
+
+impl VM {
+    /// Marks `class` as a mixin (`_$mixin(_)`): every Symbol in `items` is a
+    /// selector its hosts must answer, every protocol a set of them
+    /// (`vm/protocol.rs`), every other class a mixin it composes.
+    ///
+    /// # Errors
+    ///
+    /// `mixin.argument` for an item that is neither, plus the
+    /// [`Self::declare_composition`] errors for the class items.
+    pub(crate) fn declare_mixin(&mut self, class: ClassId, items: &[Value]) -> PhResult<()> {
+        let mut requirements = Vec::new();
+        let mut mixins = Vec::new();
+        for item in items {
+            if let Some(selector) = item.symbol_value() {
+                if !requirements.contains(&selector) {
+                    requirements.push(selector);
+                }
+            } else if let Some(protocol) = item.as_obj().filter(|&id| self.heap.as_class(id).is_some_and(|row| row.is_protocol)) {
+                for &selector in &self.heap.class(protocol).requirements {
+                    if !requirements.contains(&selector) {
+                        requirements.push(selector);
+                    }
+                }
+            } else if item.as_obj().is_some_and(|id| self.heap.as_class(id).is_some()) {
+                mixins.push(*item);
+            } else {
+                return Err(RuntimeError::NotAllowed(format!(
+                    "mixin.argument: `@mixin` arguments are selector symbols, protocols or mixins, not {}",
+                    item.type_name()
+                ))
+                .into());
+            }
+        }
+        let row = self.heap.class_mut(class);
+        row.is_mixin = true;
+        row.is_abstract = true;
+        for selector in requirements {
+            if !row.requirements.contains(&selector) {
+                row.requirements.push(selector);
+            }
+        }
+        self.declare_composition(class, &mixins)
+    }
+
+    /// Records `items` as mixins `class` composes directly (`_$compose(_)`),
+    /// after any it already composes from an earlier declaration.
+    ///
+    /// # Errors
+    ///
+    /// `mixin.not_a_mixin` for an item that is not a mixin class,
+    /// `mixin.duplicate` for a mixin named twice, `mixin.redundant` for one a
+    /// superclass already applies, `mixin.cycle` for a mixin that composes
+    /// itself.
+    pub(crate) fn declare_composition(&mut self, class: ClassId, items: &[Value]) -> PhResult<()> {
+        let mut declared = Vec::with_capacity(items.len());
+        for item in items {
+            let Some(mixin) = item.as_obj().filter(|&id| self.heap.as_class(id).is_some_and(|row| row.is_mixin)) else {
+                let shown = match item.as_obj().and_then(|id| self.heap.as_class(id)) {
+                    Some(row) if row.is_protocol => format!("protocol `{}`", row.name),
+                    Some(row) => format!("class `{}`", row.name),
+                    None => item.type_name().to_string(),
+                };
+                return Err(RuntimeError::NotAllowed(format!(
+                    "mixin.not_a_mixin: `{}` can only compose `@mixin` declarations, not {shown}",
+                    self.heap.class(class).name
+                ))
+                .into());
+            };
+            if declared.contains(&mixin) {
+                return Err(RuntimeError::NotAllowed(format!(
+                    "mixin.duplicate: `{}` composes `{}` more than once",
+                    self.heap.class(class).name,
+                    self.heap.class(mixin).name
+                ))
+                .into());
+            }
+            if let Some(ancestor) = self.applying_ancestor(class, mixin) {
+                return Err(RuntimeError::NotAllowed(format!(
+                    "mixin.redundant: `{}` already inherits `{}` from `{}`",
+                    self.heap.class(class).name,
+                    self.heap.class(mixin).name,
+                    self.heap.class(ancestor).name
+                ))
+                .into());
+            }
+            if mixin == class || self.mixin_closure(&[mixin]).contains(&class) {
+                return Err(RuntimeError::NotAllowed(format!("mixin.cycle: `{}` cannot compose itself", self.heap.class(class).name)).into());
+            }
+            declared.push(mixin);
+        }
+        let row = self.heap.class_mut(class);
+        for mixin in declared {
+            if !row.mixins.contains(&mixin) {
+                row.mixins.push(mixin);
+            }
+        }
+        Ok(())
+    }
+
+    /// Binds the instance methods of `class`'s direct mixins into its own
+    /// dictionary and checks the requirements they bring. Runs from
+    /// `FinalizeClass`, after every member of the body is installed; a no-op
+    /// for a class that composes nothing.
+    ///
+    /// # Errors
+    ///
+    /// `mixin.conflict` when two mixins contribute different methods for a
+    /// selector the class does not declare itself;
+    /// `mixin.unsatisfied_requirement` when a non-mixin class does not answer
+    /// a selector one of its mixins requires.
+    pub(crate) fn apply_mixins(&mut self, class: ClassId) -> PhResult<()> {
+        if self.heap.class(class).mixins.is_empty() {
+            return Ok(());
+        }
+        let inherited = self.inherited_mixins(class);
+        let direct: Vec<ClassId> = self.heap.class(class).mixins.iter().copied().filter(|m| !inherited.contains(m)).collect();
+
+        // selector -> (method, contributing mixin), in first-contribution order.
+        let mut contributions: indexmap::IndexMap<Symbol, Vec<(ObjRef, ClassId)>> = indexmap::IndexMap::new();
+        for &mixin in &direct {
+            for (&selector, &method) in &self.heap.class(mixin).methods {
+                if self.heap.method(method).holder.is_some_and(|holder| inherited.contains(&holder)) {
+                    continue;
+                }
+                let entry = contributions.entry(selector).or_default();
+                if !entry.iter().any(|&(m, _)| m == method) {
+                    entry.push((method, mixin));
+                }
+            }
+        }
+        for (selector, candidates) in contributions {
+            let own = self
+                .heap
+                .class(class)
+                .methods
+                .get(&selector)
+                .is_some_and(|&m| self.heap.method(m).holder == Some(class));
+            if own {
+                continue;
+            }
+            if candidates.len() > 1 {
+                let names: Vec<String> = candidates.iter().map(|&(_, mixin)| format!("`{}`", self.heap.class(mixin).name)).collect();
+                return Err(RuntimeError::NotAllowed(format!(
+                    "mixin.conflict: `{}` receives `{}` from {}; declare `{}` in `{}` to resolve it",
+                    self.heap.class(class).name,
+                    self.resolve_symbol(selector),
+                    names.join(" and "),
+                    self.resolve_symbol(selector),
+                    self.heap.class(class).name
+                ))
+                .into());
+            }
+            self.install_method_binding(class, selector, candidates[0].0)?;
+        }
+
+        if self.heap.class(class).is_mixin {
+            return Ok(());
+        }
+        for mixin in self.effective_mixins(class) {
+            for selector in self.heap.class(mixin).requirements.clone() {
+                if lookup_method_in_hierarchy(&self.heap, class, selector).is_none() {
+                    return Err(RuntimeError::NotAllowed(format!(
+                        "mixin.unsatisfied_requirement: `{}` composes `{}`, which requires `{}`, but `{}` does not answer it",
+                        self.heap.class(class).name,
+                        self.heap.class(mixin).name,
+                        self.resolve_symbol(selector),
+                        self.heap.class(class).name
+                    ))
+                    .into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The mixins `class` itself applies, in linearization order: its
+    /// composed mixins depth-first, each once, minus any an ancestor applied.
+    pub(crate) fn effective_mixins(&self, class: ClassId) -> Vec<ClassId> {
+        let row = self.heap.class(class);
+        if row.mixins.is_empty() {
+            return Vec::new();
+        }
+        let inherited = self.inherited_mixins(class);
+        let mut closure = self.mixin_closure(&row.mixins);
+        closure.retain(|m| !inherited.contains(m));
+        closure
+    }
+
+    /// The full linearization behind `Behavior#ancestors`: each class of the
+    /// superclass chain followed by its [effective mixins](Self::effective_mixins).
+    pub(crate) fn ancestors(&self, class: ClassId) -> Vec<ClassId> {
+        let mut out = Vec::new();
+        let mut current = Some(class);
+        while let Some(row) = current {
+            out.push(row);
+            out.extend(self.effective_mixins(row));
+            current = self.heap.class(row).superclass;
+        }
+        out
+    }
+
+    /// Resolves a `super` send of `selector` from a method defined in
+    /// `defining`, on `receiver`: the first method after `defining` in the
+    /// receiver's linearization. Also returns the class whose hierarchy the
+    /// walk ended in, for the caller's rest-method fallback.
+    ///
+    /// A class with no mixins keeps the plain `defining.superclass` walk. For
+    /// a mixin, the host is the nearest class of the receiver's chain that
+    /// applies it.
+    pub(crate) fn super_method(&self, receiver: Value, defining: ClassId, selector: Symbol) -> (Option<ObjRef>, Option<ClassId>) {
+        let row = self.heap.class(defining);
+        let (rest, parent) = if row.is_mixin {
+            let mut host = Some(receiver.class(self));
+            let mut found = None;
+            while let Some(class) = host {
+                let mixins = self.effective_mixins(class);
+                if let Some(at) = mixins.iter().position(|&m| m == defining) {
+                    found = Some((mixins[at + 1..].to_vec(), self.heap.class(class).superclass));
+                    break;
+                }
+                host = self.heap.class(class).superclass;
+            }
+            found.unwrap_or((Vec::new(), row.superclass))
+        } else if row.mixins.is_empty() {
+            (Vec::new(), row.superclass)
+        } else {
+            (self.effective_mixins(defining), row.superclass)
+        };
+        for mixin in rest {
+            if let Some(&method) = self.heap.class(mixin).methods.get(&selector)
+                && self.heap.method(method).holder == Some(mixin)
+            {
+                return (Some(method), parent);
+            }
+        }
+        (parent.and_then(|class| lookup_method_in_hierarchy(&self.heap, class, selector)), parent)
+    }
+
+    /// Every mixin reachable from `roots` through composition, depth-first
+    /// in declaration order, each once.
+    fn mixin_closure(&self, roots: &[ClassId]) -> Vec<ClassId> {
+        fn visit(vm: &VM, mixin: ClassId, out: &mut Vec<ClassId>) {
+            if out.contains(&mixin) {
+                return;
+            }
+            out.push(mixin);
+            for &inner in &vm.heap.class(mixin).mixins {
+                visit(vm, inner, out);
+            }
+        }
+        let mut out = Vec::new();
+        for &root in roots {
+            visit(self, root, &mut out);
+        }
+        out
+    }
+
+    /// The nearest strict ancestor of `class` that applies `mixin`, directly
+    /// or through another mixin.
+    fn applying_ancestor(&self, class: ClassId, mixin: ClassId) -> Option<ClassId> {
+        let mut current = self.heap.class(class).superclass;
+        while let Some(row) = current {
+            if self.mixin_closure(&self.heap.class(row).mixins).contains(&mixin) {
+                return Some(row);
+            }
+            current = self.heap.class(row).superclass;
+        }
+        None
+    }
+
+    /// Every mixin some strict ancestor of `class` applies.
+    fn inherited_mixins(&self, class: ClassId) -> Vec<ClassId> {
+        let mut out = Vec::new();
+        let mut current = self.heap.class(class).superclass;
+        while let Some(row) = current {
+            for mixin in self.mixin_closure(&self.heap.class(row).mixins) {
+                if !out.contains(&mixin) {
+                    out.push(mixin);
+                }
+            }
+            current = self.heap.class(row).superclass;
+        }
+        out
+    }
+}
@@ -11,10 +11,18 @@
 mod api;
 mod bootstrap;
 mod dispatch;
+mod extension;
 #[cfg(test)]
 mod f2_pack_authority_tests;
 mod gc;
+mod intercept;
+mod limits;
+mod mixin;
+mod multimethod;
+mod protocol;
 mod send;
+pub(crate) use limits::LimitMeter;
+pub use limits::{DEADLINE_CHECK_INTERVAL, HOST_MODULES, ResourceLimit, VmLimits};
 pub(crate) use send::FamilyInvocationKind;
 pub mod walk;
 
@@ -28,8 +36,9 @@ use phalcom_common::range::SourceRange;
 use std::time::Instant;
 use std::{collections::BTreeMap, collections::HashMap, collections::VecDeque};
 
-/// Maximum depth of the `.ph` call-frame stack before
-/// [`RuntimeError::DepthExceeded`](crate::error::RuntimeError::DepthExceeded).
+/// Default maximum depth of the `.ph` call-frame stack before
+/// [`RuntimeError::DepthExceeded`](crate::error::RuntimeError::DepthExceeded);
+/// an embedder lowers it through [`VmLimits::max_call_depth`].
 ///
 /// Set well above Python's default of 1000 and Ruby's comparable ceiling, because
 /// Phalcom's message-send idiom is frame-hungry — a `for` body already costs several
@@ -365,6 +374,14 @@ pub struct VM {
     pub strict_resources: bool,
     /// Numeric budget/resource policy.
     pub numeric_policy: crate::value::NumericPolicy,
+    /// Sandbox limits; set through [`VM::set_limits`].
+    pub(crate) limits: VmLimits,
+    /// Fuel and deadline state for [`Self::limits`].
+    pub(crate) meter: LimitMeter,
+    /// Host handles and host functions of an [`Engine`](crate::embed::Engine).
+    pub(crate) host: crate::embed::HostState,
+    /// The `Invocation` class of Runtime-tier interception (`intercept.rs`).
+    pub(crate) intercept: intercept::InterceptState,
     /// Bounded free-list for recycling fiber stacks/frames to avoid
     /// allocations (U-GC step 5, `fiber-pool` feature). Measured net
     /// negative in whole-process A/B benchmarking (perf-log, 2026-07-14);
@@ -0,0 +1,316 @@
+//! Multimethods: methods selected by the runtime classes of every argument.
+//!
+//! `@multimethod` on several same-selector methods of one class makes each a
+//! *case* whose parameter annotations name the classes it dispatches on (an
+//! unannotated parameter accepts any `Object`). The compiler installs each
+//! case with `_$multimethod(_,_,_)` ([`VM::declare_multimethod_case`]) rather
+//! than `Bytecode::Method`: the class's method dictionary holds one
+//! dispatcher under the selector whose [`MultiDispatch`] lists the class's
+//! own cases. The cases it inherits — its superclass's dispatcher's, or an
+//! inherited plain method as the all-`Object` case — are looked up again on
+//! every selection ([`VM::multimethod_cases`]), so a subclass sees cases its
+//! superclass gains after it was created, as a reload adds them. The
+//! receiver is a dispatch position like any argument, so a subclass's case
+//! beats its superclass's over the same arguments.
+//!
+//! When the class finalizes, [`VM::check_multimethods`] rejects an ambiguous
+//! family: two cases that both apply to some classes, neither more specific
+//! than the other, without a case for exactly their overlap. Every send then
+//! has at most one most specific case, which [`VM::select_multimethod_case`]
+//! finds and caches under the runtime classes of the receiver and arguments.
+
+use crate::error::{PhResult, RuntimeError};
+use crate::heap::{ClassId, ObjRef, Object, lookup_method_with_definer};
+use crate::interner::Symbol;
+use crate::method::{MethodObject, MultiDispatch};
+use crate::value::Value;
+use crate::vm::VM;
+
+impl VM {
+    /// Installs `case` as a multimethod case of `class` (`_$multimethod(_,_,_)`)
+    /// dispatching on `classes`, one per parameter, on the class side when
+    /// `is_static`. The first case of a selector creates the dispatcher.
+    ///
+    /// # Errors
+    ///
+    /// `multimethod.dispatch_type` for an item that is not a class, or is a
+    /// mixin or protocol; `multimethod.plain_method` when the selector is
+    /// already a plain method of the class; `multimethod.visibility` for a case whose
+    /// visibility differs from the earlier cases'; `multimethod.duplicate`
+    /// for a second case with the same dispatch classes.
+    pub(crate) fn declare_multimethod_case(&mut self, class: ClassId, case: ObjRef, classes: &[Value], is_static: bool) -> PhResult<()> {
+        let holder = self.bind_method_to_class(case, class, is_static);
+        let selector = self.heap.method(case).signature.selector;
+        let mut dispatch = vec![holder];
+        for item in classes {
+            let shown = match item.as_obj().and_then(|id| self.heap.as_class(id).map(|row| (id, row))) {
+                Some((id, row)) if !row.is_mixin && !row.is_protocol => {
+                    dispatch.push(id);
+                    continue;
+                }
+                Some((_, row)) => format!("{} `{}`", if row.is_mixin { "mixin" } else { "protocol" }, row.name),
+                None => item.type_name().to_string(),
+            };
+            return Err(RuntimeError::NotAllowed(format!(
+                "multimethod.dispatch_type: `{}` of `{}` dispatches on classes, not {shown}",
+                self.resolve_symbol(selector),
+                self.heap.class(class).name
+            ))
+            .into());
+        }
+        let dispatch: Box<[ClassId]> = dispatch.into();
+        let dispatcher = match self.heap.class(holder).methods.get(&selector).copied() {
+            Some(dispatcher) if self.heap.method(dispatcher).multi.is_some() => dispatcher,
+            Some(_) => {
+                return Err(RuntimeError::NotAllowed(format!(
+                    "multimethod.plain_method: `{}#{}` is already a plain method",
+                    self.heap.class(class).name,
+                    self.resolve_symbol(selector)
+                ))
+                .into());
+            }
+            None => self.install_dispatcher(holder, class, case)?,
+        };
+        if self.heap.method(dispatcher).visibility != self.heap.method(case).visibility {
+            return Err(RuntimeError::NotAllowed(format!(
+                "multimethod.visibility: every case of `{}#{}` needs the same visibility",
+                self.heap.class(class).name,
+                self.resolve_symbol(selector)
+            ))
+            .into());
+        }
+        let duplicate = self
+            .heap
+            .method(dispatcher)
+            .multi
+            .as_ref()
+            .is_some_and(|multi| multi.cases.iter().any(|(classes, _)| *classes == dispatch));
+        if duplicate {
+            return Err(RuntimeError::NotAllowed(format!(
+                "multimethod.duplicate: `{}#{}` already has a case for {}",
+                self.heap.class(class).name,
+                self.resolve_symbol(selector),
+                self.render_dispatch(&dispatch)
+            ))
+            .into());
+        }
+        if let Some(multi) = self.heap.method_mut(dispatcher).multi.as_mut() {
+            multi.cases.push((dispatch, case));
+        }
+        // Subclasses' dispatchers cached selections made without this case.
+        self.world_version += 1;
+        Ok(())
+    }
+
+    /// Creates and installs the dispatcher of `case`'s selector on `holder`,
+    /// with no cases of its own yet.
+    fn install_dispatcher(&mut self, holder: ClassId, class: ClassId, case: ObjRef) -> PhResult<ObjRef> {
+        let (selector, signature, kind, visibility) = {
+            let case = self.heap.method(case);
+            (case.signature.selector, case.signature.clone(), case.kind, case.visibility)
+        };
+        let mut dispatcher = MethodObject::new(selector, signature.kind, kind, Some(holder));
+        dispatcher.signature = signature;
+        dispatcher.visibility = visibility;
+        dispatcher.access_owner = Some(class);
+        dispatcher.multi = Some(Box::new(MultiDispatch::default()));
+        let dispatcher = self.heap.alloc(Object::Method(Box::new(dispatcher)));
+        self.install_method_binding(holder, selector, dispatcher)?;
+        if holder == class {
+            self.universe.note_method_installed(class, selector, &self.interner);
+        }
+        Ok(dispatcher)
+    }
+
+    /// Every case of multimethod `dispatcher`, inherited cases first: those
+    /// of the dispatcher its holder's superclass answers for the selector, or
+    /// that superclass's plain method as the all-`Object` case, then the
+    /// holder's own.
+    pub(crate) fn multimethod_cases(&self, dispatcher: ObjRef) -> Vec<(Box<[ClassId]>, ObjRef)> {
+        let method = self.heap.method(dispatcher);
+        let Some(multi) = method.multi.as_ref() else {
+            return Vec::new();
+        };
+        let inherited = method
+            .holder
+            .and_then(|holder| self.heap.class(holder).superclass)
+            .and_then(|superclass| lookup_method_with_definer(&self.heap, superclass, method.signature.selector));
+        let mut cases = match inherited {
+            Some((inherited, _)) if self.heap.method(inherited).multi.is_some() => self.multimethod_cases(inherited),
+            Some((inherited, definer)) => {
+                let object = self.universe.classes.object_class;
+                let mut dispatch = vec![definer];
+                dispatch.extend(std::iter::repeat_n(object, method.signature.positional_arity as usize));
+                vec![(dispatch.into(), inherited)]
+            }
+            None => Vec::new(),
+        };
+        cases.extend(multi.cases.iter().cloned());
+        cases
+    }
+
+    /// Rejects an ambiguous multimethod declared by `class` or its
+    /// metaclass: two cases that overlap, neither more specific than the
+    /// other, with no case for their overlap. Runs when the class finalizes,
+    /// once every case is installed.
+    ///
+    /// # Errors
+    ///
+    /// `multimethod.ambiguous` naming both cases and the missing one.
+    pub(crate) fn check_multimethods(&self, class: ClassId) -> PhResult<()> {
+        let metaclass = self.heap.class(class).class;
+        for holder in [class, metaclass] {
+            for (&selector, &method) in &self.heap.class(holder).methods {
+                if self.heap.method(method).multi.is_none() || self.heap.method(method).holder != Some(holder) {
+                    continue;
+                }
+                let cases = self.multimethod_cases(method);
+                for (index, (first, _)) in cases.iter().enumerate() {
+                    for (second, _) in &cases[index + 1..] {
+                        if self.dispatches_within(first, second) || self.dispatches_within(second, first) {
+                            continue;
+                        }
+                        let Some(overlap) = self.dispatch_overlap(first, second) else {
+                            continue;
+                        };
+                        if !cases.iter().any(|(classes, _)| *classes == overlap) {
+                            return Err(self.ambiguity(selector, &overlap, &[first, second]).into());
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Selects the case of multimethod `dispatcher` for the receiver and
+    /// `arity` arguments on top of the stack: the one applicable case more
+    /// specific than every other applicable case, cached under their runtime
+    /// classes until the world changes.
+    ///
+    /// # Errors
+    ///
+    /// `multimethod.no_applicable_case` when no case accepts the arguments,
+    /// `multimethod.ambiguous` when several maximal ones do.
+    pub(super) fn select_multimethod_case(&mut self, dispatcher: ObjRef, arity: usize) -> PhResult<ObjRef> {
+        let receiver_idx = self.stack.len() - arity - 1;
+        let runtime: Box<[ClassId]> = self.stack[receiver_idx..].iter().map(|value| value.class(self)).collect();
+        let Some(multi) = self.heap.method(dispatcher).multi.as_ref() else {
+            return Ok(dispatcher);
+        };
+        if multi.version == self.world_version
+            && let Some(&case) = multi.cache.get(&runtime)
+        {
+            return Ok(case);
+        }
+        let cases = self.multimethod_cases(dispatcher);
+        let applicable: Vec<&(Box<[ClassId]>, ObjRef)> = cases.iter().filter(|(classes, _)| self.dispatches_within(&runtime, classes)).collect();
+        let maximal: Vec<&(Box<[ClassId]>, ObjRef)> = applicable
+            .iter()
+            .copied()
+            .filter(|(classes, _)| !applicable.iter().any(|(other, _)| other != classes && self.dispatches_within(other, classes)))
+            .collect();
+        let selector = self.heap.method(dispatcher).signature.selector;
+        match maximal.as_slice() {
+            [(_, case)] => {
+                let case = *case;
+                let world = self.world_version;
+                if let Some(multi) = self.heap.method_mut(dispatcher).multi.as_mut() {
+                    if multi.version != world {
+                        multi.cache.clear();
+                        multi.version = world;
+                    }
+                    multi.cache.insert(runtime, case);
+                }
+                Ok(case)
+            }
+            [] => Err(RuntimeError::NotAllowed(format!(
+                "multimethod.no_applicable_case: no case of `{}` applies to {}",
+                self.resolve_symbol(selector),
+                self.render_dispatch(&runtime)
+            ))
+            .into()),
+            cases => {
+                let cases: Vec<&[ClassId]> = cases.iter().map(|(classes, _)| &classes[..]).collect();
+                Err(self.ambiguity(selector, &runtime, &cases).into())
+            }
+        }
+    }
+
+    /// Whether every class of `inner` is a subclass of (or is) the class at
+    /// the same position of `outer`.
+    fn dispatches_within(&self, inner: &[ClassId], outer: &[ClassId]) -> bool {
+        inner.len() == outer.len() && inner.iter().zip(outer).all(|(&inner, &outer)| self.is_subclass_of(inner, outer))
+    }
+
+    /// The classes both `first` and `second` apply to — the more specific
+    /// class at each position — or `None` when some position has unrelated
+    /// classes, so no argument satisfies both.
+    fn dispatch_overlap(&self, first: &[ClassId], second: &[ClassId]) -> Option<Box<[ClassId]>> {
+        first
+            .iter()
+            .zip(second)
+            .map(|(&first, &second)| {
+                if self.is_subclass_of(first, second) {
+                    Some(first)
+                } else if self.is_subclass_of(second, first) {
+                    Some(second)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn ambiguity(&self, selector: Symbol, classes: &[ClassId], cases: &[&[ClassId]]) -> RuntimeError {
+        let cases: Vec<String> = cases.iter().map(|case| self.render_dispatch(case)).collect();
+        RuntimeError::NotAllowed(format!(
+            "multimethod.ambiguous: cases {} of `{}` overlap on {} with none more specific; declare a case for it",
+            cases.join(" and "),
+            self.resolve_symbol(selector),
+            self.render_dispatch(classes)
+        ))
+    }
+
+    /// Renders dispatch classes as `Receiver(Argument, …)`.
+    fn render_dispatch(&self, classes: &[ClassId]) -> String {
+        let names: Vec<&str> = classes[1..].iter().map(|&class| self.heap.class(class).name.as_str()).collect();
+        format!("`{}({})`", self.heap.class(classes[0]).name, names.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::value::Value;
+    use crate::vm::VM;
+
+    #[test]
+    fn subclasses_see_cases_their_superclass_gains_later() {
+        let mut vm = VM::new();
+        let module = vm.create_module("main", "multimethod_late_case");
+        vm.interpret_source(
+            module,
+            "class Shape {\n  @multimethod\n  describe(_ x: Int) { \"int\" }\n}\nclass Square is Shape {\n  @multimethod\n  describe(_ x: String) { \"string\" }\n}\nclass Donor {\n  describe(_ x) { \"float\" }\n}\nlet square = Square.new()\n",
+        )
+        .expect("multimethod fixture should compile and run");
+        let mut global = |name: &str| {
+            let symbol = vm.interner.intern(name);
+            vm.heap.module(module).get(symbol).expect("fixture should define the global")
+        };
+        let (shape, donor, square) = (global("Shape"), global("Donor"), global("square"));
+        let selector = vm.get_or_intern("describe(_)");
+        let describe = |vm: &mut VM, arg: Value| {
+            let result = vm.send_dynamic(square, selector, &[arg]);
+            result.and_then(|value| value.to_display_string(vm))
+        };
+        assert_eq!(describe(&mut vm, Value::int(1)).unwrap(), "int");
+        assert!(describe(&mut vm, Value::float(1.5)).is_err(), "no case applies to a Float yet");
+
+        let case = vm.heap.class(donor.as_obj().unwrap()).methods[&selector];
+        let float = vm.universe.classes.float_class;
+        vm.declare_multimethod_case(shape.as_obj().unwrap(), case, &[Value::obj(float)], false)
+            .expect("the new case should install");
+        assert_eq!(describe(&mut vm, Value::float(1.5)).unwrap(), "float");
+        assert_eq!(describe(&mut vm, Value::int(1)).unwrap(), "int");
+    }
+}
@@ -0,0 +1,68 @@
+//! Protocols: structural capability descriptors.
+//!
+//! `@protocol class Sized { size -> Int {} }` compiles to an ordinary class
+//! row whose members are bodiless signatures, then `_$protocol(_)` marks it
+//! ([`VM::declare_protocol`]): the row becomes abstract, cannot be named in an
+//! `is` clause or composed, and records its requirements — the selectors of
+//! every protocol it includes, then its own members'. Conformance is purely
+//! structural: an object conforms when it answers every requirement
+//! ([`VM::conforms_to`], `Object#conformsTo(_)`), whatever its class.
+//! A `@mixin` may name a protocol among its arguments to require all of it
+//! from its hosts (`vm/mixin.rs`).
+
+use crate::error::{PhResult, RuntimeError};
+use crate::heap::ClassId;
+use crate::value::Value;
+use crate::vm::VM;
+
+impl VM {
+    /// Marks `class` as a protocol (`_$protocol(_)`) including every protocol
+    /// in `items`. Runs after the body's members are installed, so the
+    /// class's own instance methods are its declared requirements.
+    ///
+    /// # Errors
+    ///
+    /// `protocol.argument` for an item that is not a protocol class.
+    pub(crate) fn declare_protocol(&mut self, class: ClassId, items: &[Value]) -> PhResult<()> {
+        let mut requirements = Vec::new();
+        for item in items {
+            let Some(included) = item.as_obj().filter(|&id| self.heap.as_class(id).is_some_and(|row| row.is_protocol)) else {
+                let shown = match item.as_obj().and_then(|id| self.heap.as_class(id)) {
+                    Some(row) => format!("class `{}`", row.name),
+                    None => item.type_name().to_string(),
+                };
+                return Err(RuntimeError::NotAllowed(format!(
+                    "protocol.argument: `{}` can only include `@protocol` declarations, not {shown}",
+                    self.heap.class(class).name
+                ))
+                .into());
+            };
+            for &selector in &self.heap.class(included).requirements {
+                if !requirements.contains(&selector) {
+                    requirements.push(selector);
+                }
+            }
+        }
+        for &selector in self.heap.class(class).methods.keys() {
+            if !requirements.contains(&selector) {
+                requirements.push(selector);
+            }
+        }
+        let row = self.heap.class_mut(class);
+        row.is_protocol = true;
+        row.is_abstract = true;
+        row.requirements = requirements;
+        Ok(())
+    }
+
+    /// Whether `value` answers every requirement of `protocol` with a method
+    /// the current caller may send — the same test as `Object#respondsTo(_)`
+    /// for each selector.
+    pub(crate) fn conforms_to(&self, value: Value, protocol: ClassId) -> bool {
+        self.heap.class(protocol).requirements.iter().all(|&selector| {
+            value
+                .lookup_method(self, selector)
+                .is_some_and(|method| self.authorize_method_access(method).is_ok())
+        })
+    }
+}
@@ -96,11 +96,16 @@ impl VM {
 
     /// Checks whether `receiver` can execute a method held by its defining
     /// class. Class-side methods use the receiver's metaclass automatically
-    /// because `Value::class` returns that class for class values.
+    /// because `Value::class` returns that class for class values. A mixin's
+    /// method is compatible with any receiver: a mixin has no layout to
+    /// protect (`vm/mixin.rs`).
     pub(crate) fn method_receiver_nominally_compatible(&self, method: ObjRef, receiver: Value) -> bool {
         let Some(holder) = self.heap.method(method).holder else {
             return true;
         };
+        if self.heap.class(holder).is_mixin {
+            return true;
+        }
         let mut class = receiver.class(self);
         loop {
             if class == holder {
@@ -140,7 +145,7 @@ impl VM {
         self.core_module() == Some(closure_module)
     }
 
-    fn is_subclass_of(&self, mut class: ClassId, ancestor: ClassId) -> bool {
+    pub(super) fn is_subclass_of(&self, mut class: ClassId, ancestor: ClassId) -> bool {
         loop {
             if class == ancestor {
                 return true;
@@ -209,7 +214,11 @@ impl VM {
         let allowed = match visibility {
             MemberVisibility::Public => true,
             MemberVisibility::Private => caller == owner,
-            MemberVisibility::Protected => owner.is_some_and(|owner| caller.is_some_and(|caller| self.is_subclass_of(caller, owner))),
+            // A mixin's protected members are visible to the classes that
+            // apply it, as a superclass's are to its subclasses.
+            MemberVisibility::Protected => owner.is_some_and(|owner| {
+                caller.is_some_and(|caller| self.is_subclass_of(caller, owner) || (self.heap.class(owner).is_mixin && self.ancestors(caller).contains(&owner)))
+            }),
             MemberVisibility::Internal => caller_internal,
         };
         if allowed {
@@ -336,6 +345,33 @@ impl VM {
         shape: Option<(usize, usize)>,
         source_range: SourceRange,
         caller_authority: (Option<ClassId>, bool),
+    ) -> PhResult<()> {
+        // A multimethod dispatcher stands for the case the arguments select
+        // (`multimethod.rs`); the case carries its own interception chain.
+        let method = if self.heap.method(method).multi.is_some() {
+            self.select_multimethod_case(method, arity)?
+        } else {
+            method
+        };
+        // Runtime-tier interception (`intercept.rs`): an undecorated method's
+        // chain is empty, and this read is all it pays.
+        if !self.heap.method(method).interceptors.is_empty() && !self.in_interceptor() {
+            return self.intercept_send(method, arity, selector, shape, source_range, caller_authority);
+        }
+        self.activate_method_as(callee, method, arity, selector, shape, source_range, caller_authority)
+    }
+
+    /// Activates `method` itself, past any interception.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn activate_method_as(
+        &mut self,
+        callee: &Value,
+        method: ObjRef,
+        arity: usize,
+        selector: Symbol,
+        shape: Option<(usize, usize)>,
+        source_range: SourceRange,
+        caller_authority: (Option<ClassId>, bool),
     ) -> PhResult<()> {
         if matches!(self.heap.method(method).kind, MethodKind::Primitive(PrimitiveFn::Legacy(_))) {
             return self.call_method_legacy(callee, method, arity, source_range, caller_authority);
@@ -447,7 +483,13 @@ impl VM {
     ) -> PhResult<CallOutcome> {
         let receiver = self.stack[receiver_idx];
         let before = self.frames.len();
-        if let Some(method) = receiver.lookup_method(self, selector) {
+        // Scoped extensions resolve against the module of the innermost
+        // frame: the code that called `perform` or the forwarding gateway.
+        let extension = || {
+            self.current_module()
+                .and_then(|module| self.lookup_extension(module, receiver.class(self), selector))
+        };
+        if let Some(method) = receiver.lookup_method(self, selector).or_else(extension) {
             self.call_method_with_selector_as(
                 &receiver,
                 method,
@@ -1045,7 +1087,11 @@ impl VM {
         self.stack.extend_from_slice(args);
 
         let base_frames = self.frames.len();
-        if let Some(method) = receiver.lookup_method(self, selector) {
+        let extension = || {
+            self.current_module()
+                .and_then(|module| self.lookup_extension(module, receiver.class(self), selector))
+        };
+        if let Some(method) = receiver.lookup_method(self, selector).or_else(extension) {
             self.call_method(&receiver, method, args.len(), SourceRange::default())?;
         } else {
             let (name, slots, kind) = decode_selector(self.resolve_symbol(selector));
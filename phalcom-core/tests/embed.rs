@@ -0,0 +1,167 @@
+//! The embedding facade (`embed/`): `Engine`, typed conversion, rooted
+//! handles and host functions.
+//!
+//! Every test drives the engine only through its public surface, the way a
+//! host crate would.
+
+use phalcom_core::embed::{Engine, Handle};
+use phalcom_core::error::{PhError, RuntimeError};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// **Snippets share a scope** and their values convert to Rust types.
+#[test]
+fn eval_converts_results() {
+    let mut engine = Engine::new();
+    assert_eq!(engine.eval::<i64>("let base = 40\nbase + 2").unwrap(), 42);
+    assert_eq!(engine.eval::<f64>("base / 8.0").unwrap(), 5.0);
+    assert_eq!(engine.eval::<String>("\"n=\" + base.toString").unwrap(), "n=40");
+    assert!(engine.eval::<bool>("base > 3").unwrap());
+    assert_eq!(engine.eval::<Vec<i64>>("[1, 2, 3]").unwrap(), vec![1, 2, 3]);
+    assert_eq!(engine.eval::<(i64, String)>("(7, \"seven\")").unwrap(), (7, "seven".to_string()));
+    assert_eq!(engine.eval::<Option<i64>>("Some(3)").unwrap(), Some(3));
+    assert_eq!(engine.eval::<Option<i64>>("None").unwrap(), None);
+    assert_eq!(engine.global::<i64>("base").unwrap(), 40);
+}
+
+/// **A shape mismatch is a conversion error**, not a panic or a silent zero.
+#[test]
+fn conversion_mismatch_is_an_error() {
+    let mut engine = Engine::new();
+    match engine.eval::<i64>("\"text\"") {
+        Err(PhError::Runtime(RuntimeError::TypeConversion { expected, found })) => assert_eq!((expected, found), ("Int", "String")),
+        other => panic!("expected TypeConversion, got {other:?}"),
+    }
+    match engine.eval::<u8>("300") {
+        Err(PhError::Runtime(RuntimeError::NumericOverflow { target_type, .. })) => assert_eq!(target_type, "u8"),
+        other => panic!("expected NumericOverflow, got {other:?}"),
+    }
+}
+
+/// **Wide integers round-trip**: a value too big for an `Int` is built as a
+/// `LargeInt` and read back from one, and one that does not fit the target
+/// type is an overflow.
+#[test]
+fn wide_integers_round_trip() {
+    let mut engine = Engine::new();
+    engine.set_global("big", u64::MAX).unwrap();
+    assert_eq!(engine.global::<u64>("big").unwrap(), u64::MAX);
+    assert_eq!(engine.eval::<u64>("big - 1").unwrap(), u64::MAX - 1);
+    assert_eq!(engine.eval::<u128>("big * big").unwrap(), u128::from(u64::MAX) * u128::from(u64::MAX));
+    for value in [i128::MIN, i128::MAX, -1, 0] {
+        engine.set_global("wide", value).unwrap();
+        assert_eq!(engine.global::<i128>("wide").unwrap(), value);
+    }
+    engine.set_global("huge", u128::MAX).unwrap();
+    assert_eq!(engine.global::<u128>("huge").unwrap(), u128::MAX);
+    engine.set_global("size", usize::MAX).unwrap();
+    assert_eq!(engine.global::<usize>("size").unwrap(), usize::MAX);
+    match engine.global::<i64>("big") {
+        Err(PhError::Runtime(RuntimeError::NumericOverflow { target_type, .. })) => assert_eq!(target_type, "i64"),
+        other => panic!("expected NumericOverflow, got {other:?}"),
+    }
+    match engine.eval::<u64>("0 - big") {
+        Err(PhError::Runtime(RuntimeError::NumericOverflow { target_type, .. })) => assert_eq!(target_type, "u64"),
+        other => panic!("expected NumericOverflow, got {other:?}"),
+    }
+}
+
+/// **Maps round-trip**, and a map built in Rust is an ordinary `Map` to `.ph`.
+#[test]
+fn maps_round_trip() {
+    let mut engine = Engine::new();
+    let scores = HashMap::from([("ada".to_string(), 3_i64), ("bob".to_string(), 5)]);
+    engine.set_global("scores", scores.clone()).unwrap();
+    assert_eq!(engine.eval::<i64>("scores[\"ada\"] + scores[\"bob\"]").unwrap(), 8);
+    assert_eq!(engine.eval::<HashMap<String, i64>>("scores").unwrap(), scores);
+}
+
+/// **A callable comes back as a handle** and is called with Rust arguments.
+#[test]
+fn call_a_closure_with_rust_arguments() {
+    let mut engine = Engine::new();
+    let join = engine.eval::<Handle>("|items, sep| items.join(sep)").unwrap();
+    let joined: String = engine.call(&join, (vec!["a", "b", "c"], "-")).unwrap();
+    assert_eq!(joined, "a-b-c");
+    let list = engine.handle(vec![1, 2, 3]).unwrap();
+    let size: i64 = engine.send(&list, "size", ()).unwrap();
+    assert_eq!(size, 3);
+}
+
+/// **Host closures are callable from `.ph`**, with typed arguments, captured
+/// Rust state and an arity check.
+#[test]
+fn host_functions_are_callable() {
+    let mut engine = Engine::new();
+    let calls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&calls);
+    engine
+        .register_fn("hypot", move |x: f64, y: f64| {
+            counter.set(counter.get() + 1);
+            (x * x + y * y).sqrt()
+        })
+        .unwrap();
+    engine
+        .register_fn("words", |text: String| text.split(' ').map(str::to_owned).collect::<Vec<_>>())
+        .unwrap();
+
+    assert_eq!(engine.eval::<f64>("hypot(3, 4) + hypot.call(6, 8)").unwrap(), 15.0);
+    assert_eq!(calls.get(), 2);
+    assert_eq!(engine.eval::<i64>("words(\"one two three\").size").unwrap(), 3);
+    match engine.eval::<f64>("hypot(3)") {
+        Err(PhError::Runtime(RuntimeError::Arity { expected: 2, found: 1, .. })) => {}
+        other => panic!("expected an arity error, got {other:?}"),
+    }
+    assert_eq!(calls.get(), 2, "an arity mismatch must not run the closure");
+}
+
+/// **A host error is an ordinary catchable raise** in `.ph`.
+#[test]
+fn host_errors_are_catchable() {
+    let mut engine = Engine::new();
+    engine
+        .register_fn("parse", |text: String| -> Result<i64, PhError> {
+            text.parse().map_err(|_| RuntimeError::ArgumentError(format!("not a number: {text}")).into())
+        })
+        .unwrap();
+    assert_eq!(engine.eval::<i64>("parse(\"12\")").unwrap(), 12);
+    let message: String = engine
+        .eval("let message = \"\"\ntry { parse(\"x\") } catch e { message = e.message }\nmessage")
+        .unwrap();
+    assert!(message.contains("not a number: x"), "{message}");
+    assert!(engine.eval::<i64>("parse(\"y\")").is_err());
+    assert_eq!(engine.eval::<i64>("parse(\"5\") + 1").unwrap(), 6, "the engine is usable after a failed eval");
+}
+
+/// **Handles keep their values alive** across collections, in any drop order.
+#[test]
+fn handles_root_their_values() {
+    let mut engine = Engine::new();
+    let first = engine.eval::<Handle>("[\"kept\", \"alive\"]").unwrap();
+    let second = engine.handle(vec!["also".to_string()]).unwrap();
+    let copy = first.clone();
+    assert_eq!(engine.handle_count(), 3);
+
+    drop(first);
+    engine.eval::<()>("let i = 0\nwhile (i < 5000) { [i, i.toString]\n i = i + 1 }").unwrap();
+    engine.vm_mut().force_gc();
+
+    assert_eq!(copy.get::<Vec<String>>(engine.vm_mut()).unwrap(), vec!["kept", "alive"]);
+    assert_eq!(second.get::<Vec<String>>(engine.vm_mut()).unwrap(), vec!["also"]);
+    drop((copy, second));
+    assert_eq!(engine.handle_count(), 0);
+}
+
+/// **A loaded module's exports** are looked up by name and called.
+#[test]
+fn load_and_call_an_export() {
+    let mut engine = Engine::new();
+    let module = engine.load_source("let scale = |x, by| x * by\nexport scale\n").unwrap();
+    let scale = engine.export::<Handle>(&module, "scale").unwrap();
+    assert_eq!(engine.call::<i64>(&scale, (6, 7)).unwrap(), 42);
+    assert!(matches!(
+        engine.export::<Handle>(&module, "missing"),
+        Err(PhError::Runtime(RuntimeError::UndefinedVariable { .. }))
+    ));
+}
@@ -0,0 +1,196 @@
+//! Incremental collector tests — the tri-color cycle behind threshold
+//! collections.
+//!
+//! These drive the heap's cycle API directly ([`Heap::begin_cycle`],
+//! [`Heap::mark_slice`], [`Heap::finish_cycle`], [`Heap::sweep_slice`]) so a
+//! test can mutate the graph *between* slices, which is exactly where the
+//! write barrier earns its keep. `vm.collect_roots` supplies the roots, as
+//! `VM::service_gc_safepoint` does.
+//!
+//! [`Heap::begin_cycle`]: phalcom_core::heap::Heap::begin_cycle
+//! [`Heap::mark_slice`]: phalcom_core::heap::Heap::mark_slice
+//! [`Heap::finish_cycle`]: phalcom_core::heap::Heap::finish_cycle
+//! [`Heap::sweep_slice`]: phalcom_core::heap::Heap::sweep_slice
+
+use phalcom_core::heap::{InstanceObject, ObjRef, Object};
+use phalcom_core::value::Value;
+use phalcom_core::vm::VM;
+
+/// A VM whose heap is already collected once — see `gc.rs`'s `settled_vm`.
+fn settled_vm() -> VM {
+    let mut vm = VM::new();
+    vm.force_gc();
+    vm
+}
+
+fn alloc_instance(vm: &mut VM, slots: Vec<Value>) -> ObjRef {
+    let class = vm.universe.classes.object_class;
+    vm.heap.alloc(Object::Instance(InstanceObject {
+        class,
+        slots: slots.into_boxed_slice(),
+    }))
+}
+
+fn roots(vm: &VM) -> Vec<ObjRef> {
+    let mut roots = Vec::new();
+    vm.collect_roots(&mut roots);
+    roots
+}
+
+fn begin(vm: &mut VM) {
+    let roots = roots(vm);
+    vm.heap.begin_cycle(&roots);
+}
+
+fn mark_to_completion(vm: &mut VM) {
+    while !vm.heap.mark_slice() {}
+}
+
+/// Finishes the cycle against the *current* roots, then sweeps it out.
+fn finish_and_sweep(vm: &mut VM) -> usize {
+    let roots = roots(vm);
+    vm.heap.finish_cycle(&roots);
+    let mut swept = 0;
+    loop {
+        let (freed, done) = vm.heap.sweep_slice();
+        swept += freed;
+        if done {
+            return swept;
+        }
+    }
+}
+
+/// **The write barrier.** A white object — held only in a local, as a value
+/// just popped off the stack would be — is stored into an already-black list
+/// mid-cycle. Without the barrier the list is never re-traced and the object
+/// is swept while reachable (Invariant M3).
+#[test]
+fn barrier_keeps_object_stored_into_black_holder() {
+    let mut vm = settled_vm();
+    vm.heap.set_gc_slice_budget_for_test(1);
+    let holder = vm.heap.alloc_list(Vec::new());
+    vm.push_root_for_test(Value::obj(holder));
+    // Allocated before the cycle and referenced by nothing: white throughout
+    // marking.
+    let hidden = alloc_instance(&mut vm, vec![]);
+    begin(&mut vm);
+    mark_to_completion(&mut vm);
+    assert!(vm.heap.is_marking());
+
+    vm.heap.list_mut(holder).push(Value::obj(hidden));
+
+    finish_and_sweep(&mut vm);
+    assert!(vm.heap.try_get(hidden).is_some(), "an object stored into a black holder must survive");
+    assert_eq!(vm.heap.list(holder).elements(), [Value::obj(hidden)]);
+}
+
+/// **The root rescan.** A white object reached only from the heap when the
+/// cycle starts is unlinked from there and parked on the operand stack. Roots
+/// carry no barrier, so only the finish's rescan can find it.
+#[test]
+fn finish_rescans_roots_that_changed_mid_cycle() {
+    let mut vm = settled_vm();
+    vm.heap.set_gc_slice_budget_for_test(1);
+    let child = alloc_instance(&mut vm, vec![]);
+    let parent = alloc_instance(&mut vm, vec![Value::obj(child)]);
+    vm.push_root_for_test(Value::obj(parent));
+    begin(&mut vm);
+
+    // Move `child` from the heap onto the stack before marking reaches it.
+    vm.push_root_for_test(Value::obj(child));
+    vm.heap.instance_mut(parent).slots[0] = Value::none();
+    mark_to_completion(&mut vm);
+
+    finish_and_sweep(&mut vm);
+    assert!(vm.heap.try_get(child).is_some(), "a root gained mid-cycle must be found at the finish");
+}
+
+/// **Garbage dies, live objects survive, allocations during a cycle survive
+/// it.** An object allocated mid-cycle is shaded gray, so it is not swept by
+/// the cycle that was already running when it was born.
+#[test]
+fn incremental_cycle_reclaims_garbage_and_spares_newborns() {
+    let mut vm = settled_vm();
+    vm.heap.set_gc_slice_budget_for_test(8);
+    let live = alloc_instance(&mut vm, vec![]);
+    vm.push_root_for_test(Value::obj(live));
+    let garbage = alloc_instance(&mut vm, vec![]);
+    begin(&mut vm);
+    let newborn = alloc_instance(&mut vm, vec![]);
+    mark_to_completion(&mut vm);
+
+    let swept = finish_and_sweep(&mut vm);
+    assert!(swept >= 1);
+    assert!(vm.heap.try_get(garbage).is_none(), "garbage must be swept");
+    assert!(vm.heap.try_get(live).is_some(), "a rooted object must survive");
+    assert!(vm.heap.try_get(newborn).is_some(), "an object born mid-cycle must survive that cycle");
+
+    // Unrooted, the newborn is ordinary garbage to the next cycle.
+    begin(&mut vm);
+    mark_to_completion(&mut vm);
+    finish_and_sweep(&mut vm);
+    assert!(vm.heap.try_get(newborn).is_none());
+}
+
+/// **A forced collection supersedes a cycle in flight** and reclaims
+/// everything dead now — including what the abandoned cycle had already
+/// marked.
+#[test]
+fn force_gc_abandons_running_cycle() {
+    let mut vm = settled_vm();
+    vm.heap.set_gc_slice_budget_for_test(1);
+    let doomed = alloc_instance(&mut vm, vec![]);
+    vm.push_root_for_test(Value::obj(doomed));
+    begin(&mut vm);
+    mark_to_completion(&mut vm);
+    vm.pop_root_for_test();
+
+    vm.force_gc();
+    assert!(!vm.heap.is_marking() && !vm.heap.is_sweeping());
+    assert!(vm.heap.try_get(doomed).is_none(), "a forced collection must not honor stale marks");
+}
+
+/// **Slices run from the safepoint, and `System.gcStats` reports them.** A
+/// program that churns past the collection threshold with a tiny slice budget
+/// completes incremental cycles, and the histogram accounts for every pause.
+#[test]
+fn gc_stats_reports_incremental_pauses() {
+    let mut vm = settled_vm();
+    vm.heap.set_gc_slice_budget_for_test(64);
+    let module = vm.create_module("test_gc_incremental", "gc_incremental_test");
+
+    vm.interpret_source(
+        module,
+        r#"
+        let keep = List.new()
+        let i = 0
+        while (i < 20000) {
+            keep.append(List.new())
+            if (keep.size > 500) { keep = List.new() }
+            i = i + 1
+        }
+        let stats = System.gcStats
+        let field = |label| { stats.get(label).unwrapOr(-1) }
+        let bucketed = 0
+        field.call(#histogram).each(|bucket| { bucketed = bucketed + bucket.get(#count).unwrapOr(0) })
+        let cycles = field.call(#incrementalCycles)
+        let slices = field.call(#markSlices)
+        let sweeps = field.call(#sweepSlices)
+        let snapshotPauses = field.call(#pauses)
+        // Read last: a cycle still in flight keeps pausing at every safepoint
+        // the statements above pass through.
+        let pauses = System.gcStats.get(#pauses).unwrapOr(-1)
+        "#,
+    )
+    .expect("interpret_source failed");
+
+    let global = |vm: &mut VM, name: &str| {
+        let sym = vm.interner.intern(name);
+        vm.heap.module(module).get(sym).and_then(|value| value.as_int()).expect("Int global")
+    };
+    assert!(global(&mut vm, "cycles") > 0, "threshold collections must run incrementally");
+    assert!(global(&mut vm, "slices") > 0, "a 64-unit budget cannot mark the kernel in one slice");
+    assert!(global(&mut vm, "sweeps") > 0);
+    assert_eq!(global(&mut vm, "bucketed"), global(&mut vm, "snapshotPauses"), "every pause lands in one bucket");
+    assert_eq!(vm.heap.gc_stats().pauses() as i64, global(&mut vm, "pauses"));
+}
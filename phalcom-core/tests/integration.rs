@@ -4,12 +4,15 @@ mod declaration_grammar_dispatch;
 mod depth_limits;
 mod disasm_golden;
 mod disasm_super;
+mod embed;
 mod exit_codes;
 mod f2_pack_gc;
 mod family_selector_runtime;
 mod fiber_trace;
 mod gc;
+mod gc_incremental;
 mod golden;
+mod limits;
 mod modules_runtime;
 mod numbers_u01;
 mod numbers_u05;
@@ -23,3 +26,4 @@ mod repl_source_map;
 mod send_arity_limit;
 mod symbol_selector;
 mod traceback;
+mod weak;
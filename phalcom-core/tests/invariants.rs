@@ -775,6 +775,7 @@ fn floor_census_matches_installed_bindings() {
         (c.object_class, false, "!=(_)"),
         (c.object_class, false, "perform(_,***)"),
         (c.object_class, false, "respondsTo(_)"),
+        (c.object_class, false, "conformsTo(_)"), // NEW_PROTOCOL (vm/protocol.rs)
         (c.object_class, false, "doesNotUnderstand(_)"),
         (c.object_class, false, "methodFor(_)"), // NEW (ADR-0028)
         (c.object_class, false, "===(_)"),
@@ -788,9 +789,19 @@ fn floor_census_matches_installed_bindings() {
         // §2.2 Behavior
         (c.behavior_class, false, "superclass"),
         (c.behavior_class, false, "superclass=(put)"),
-        (c.behavior_class, false, "name"),    // NEW (ADR-0023)
-        (c.behavior_class, false, "methods"), // NEW (ADR-0023)
-        (c.behavior_class, false, ">>(_)"),   // selector-pattern reflection
+        (c.behavior_class, false, "name"),                 // NEW (ADR-0023)
+        (c.behavior_class, false, "methods"),              // NEW (ADR-0023)
+        (c.behavior_class, false, ">>(_)"),                // selector-pattern reflection
+        (c.behavior_class, false, "ancestors"),            // NEW_MIXIN (vm/mixin.rs)
+        (c.behavior_class, false, "mixins"),               // NEW_MIXIN (vm/mixin.rs)
+        (c.behavior_class, false, "isMixin"),              // NEW_MIXIN (vm/mixin.rs)
+        (c.behavior_class, false, "requirements"),         // NEW_MIXIN (vm/mixin.rs)
+        (c.behavior_class, false, "_$mixin(_)"),           // NEW_MIXIN (vm/mixin.rs)
+        (c.behavior_class, false, "_$compose(_)"),         // NEW_MIXIN (vm/mixin.rs)
+        (c.behavior_class, false, "isProtocol"),           // NEW_PROTOCOL (vm/protocol.rs)
+        (c.behavior_class, false, "_$protocol(_)"),        // NEW_PROTOCOL (vm/protocol.rs)
+        (c.behavior_class, false, "_$multimethod(_,_,_)"), // NEW_MULTIMETHOD (vm/multimethod.rs)
+        (c.behavior_class, false, "_$extend(_,_)"),        // NEW_EXTENSION (vm/extension.rs)
         // §2.3 Class
         (c.class_class, false, "+(_)"),
         (c.class_class, false, "_$new()"),
@@ -873,6 +884,7 @@ fn floor_census_matches_installed_bindings() {
         (c.method_family_class, false, "selectors"),
         (c.method_family_class, false, "size"),
         (c.method_family_class, false, "methodFor(_)"),
+        (c.method_family_class, false, "specializations(_)"),
         (c.method_family_class, false, "bind(_)"),
         // §2.10 Family
         (c.family_class, false, "receiver"),
@@ -900,6 +912,7 @@ fn floor_census_matches_installed_bindings() {
         (c.system_class, true, "schedule(_)"),
         (c.system_class, true, "nextScheduled"),
         (c.system_class, true, "gc"),
+        (c.system_class, true, "gcStats"),
         // U-STRING raw I/O seam (ADR-0049 amendment)
         (c.system_class, true, "_$write(_)"), // NEW (ADR-0049)
         // §2.12 Module (U15, ADR-0045) — NEW_IMPORTS
@@ -1001,6 +1014,8 @@ fn floor_census_matches_installed_bindings() {
         (c.fiber_class, false, "isDone"),
         (c.fiber_class, false, "isRoot"),
         (c.fiber_class, false, "error"),
+        (c.fiber_class, false, "canSuspend"),
+        (c.fiber_class, false, "_$rethrow()"),
         // System (Resource tracking primitives)
         (c.system_class, true, "_$leakReport"),
         (c.system_class, true, "_$strictResources(_)"),
@@ -259,6 +259,101 @@ fn inheritance() {
     support::check_pass("inheritance");
 }
 
+#[test]
+fn mixins() {
+    // `@mixin`/`@compose` (typing/Mixins.md): methods bound into the host at
+    // finalization, host-over-mixin-over-superclass precedence, selector
+    // requirements, composed mixins, and `super` along `Behavior#ancestors`.
+    support::check_pass("mixins");
+}
+
+#[test]
+fn mixins_negative() {
+    // Compile-time shape errors (`mixin.superclass`/`field`/`constructor`/
+    // `compose`) and the finalization-time ones (`mixin.conflict`,
+    // `mixin.unsatisfied_requirement`, `mixin.duplicate`, …).
+    support::check_negative("mixins/negative");
+}
+
+#[test]
+fn protocols() {
+    // `@protocol` declarations: structural `conformsTo(_)`, included
+    // protocols, and a protocol as a `@mixin` host requirement.
+    support::check_pass("protocols");
+}
+
+#[test]
+fn protocols_negative() {
+    // Compile-time shape errors (`protocol.body`/`field`/`constructor`/
+    // `superclass`/`member`) and the runtime ones: a protocol as a
+    // superclass, a composed mixin or an instantiated class.
+    support::check_negative("protocols/negative");
+}
+
+#[test]
+fn multimethods() {
+    // `@multimethod` cases: argument- and receiver-class dispatch, class-side
+    // cases, inherited cases, decorated cases and `specializations(_)`.
+    support::check_pass("multimethods");
+}
+
+#[test]
+fn multimethods_negative() {
+    // Ambiguous families (own and inherited), a missing case, and the
+    // declaration errors: duplicates, plain clashes, protocol dispatch, rest
+    // parameters, mixed visibility and cases in a mixin.
+    support::check_negative("multimethods/negative");
+}
+
+#[test]
+fn generators() {
+    // `yield` and `@generator`: generator methods, getters and class-side
+    // members, `Generator.new` blocks, single-pass resumption, pipeline
+    // stages over infinite generators, nesting, and body errors.
+    support::check_pass("generators");
+}
+
+#[test]
+fn generators_negative() {
+    // `yield` outside a block or in a block run on the root fiber, `return`
+    // with a value, `@generator` on a constructor, and a body error escaping
+    // the consuming `for`.
+    support::check_negative("generators/negative");
+}
+
+#[test]
+fn async_await() {
+    // `@async` and `await`: async methods, getters and class-side members
+    // answering a `Future`, parking and resuming across awaits, cooperative
+    // pauses, and rejections flowing through every `await`.
+    support::check_pass("async");
+}
+
+#[test]
+fn async_await_negative() {
+    // Awaiting a pending future inside a `try` body, `@async` on a
+    // constructor, `yield` in an async body, and an unhandled body error
+    // escaping the root `await`.
+    support::check_negative("async/negative");
+}
+
+#[test]
+fn extensions() {
+    // `extend` blocks: methods, getters, setters and class-side members on
+    // builtin and user classes, members winning over extensions, the
+    // nearest extended class answering, and `perform`/spread/family sends
+    // reaching them too.
+    support::check_pass("extensions");
+}
+
+#[test]
+fn extensions_negative() {
+    // Extending a selector the class answers, extending one twice, and the
+    // members an `extend` block refuses: fields, constructors, private
+    // members, field reads and `super` sends.
+    support::check_negative("extensions/negative");
+}
+
 #[test]
 fn iteration() {
     // U-ITER (ADR-0035, iteration.md): the two-selector cursor protocol
@@ -420,10 +515,10 @@ fn indexing_negative() {
 
 #[test]
 fn decorators() {
-    // Standalone core-library classes the ratified decorator specs need
-    // (Tracer/OffBehavior/Backoff, decorators-behavioral.md/
-    // decorators-dispatch-observability.md) — shipped ahead of the
-    // Install/Dispatch/Runtime decorator mechanism itself (PLAN-DECORATORS.md).
+    // Attribute retention, Runtime-tier interception, and the behavioral
+    // decorator library over it (`@memoize`/`@retry`/`@lazy`/`@traced`/
+    // `@synchronized`, decorators-behavioral.md) with its helper classes
+    // (Tracer/OffBehavior/Backoff).
     support::check_pass("decorators");
 }
 
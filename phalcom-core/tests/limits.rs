@@ -0,0 +1,203 @@
+//! Sandbox limits (`vm/limits.rs`): fuel, heap cap, call depth, deadline and
+//! the host-module capability switch.
+//!
+//! The shape every test pins: a runaway computation ends in a
+//! `LimitExceeded` the *host* sees, no `.ph` handler can intercept it, and the
+//! VM is usable again after [`VM::rearm_limits`].
+
+use phalcom_core::compiler::lib::UnitKind;
+use phalcom_core::error::{PhError, RuntimeError};
+use phalcom_core::modules::compile::{EntrySelection, ProgramCompiler};
+use phalcom_core::value::{NumericPolicy, Value};
+use phalcom_core::vm::{ResourceLimit, VM, VmLimits};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+fn sandboxed(limits: VmLimits) -> (VM, phalcom_core::heap::ObjRef) {
+    let mut vm = VM::new();
+    vm.set_limits(limits);
+    let module = vm.create_module("main", "<sandbox>");
+    (vm, module)
+}
+
+fn run(vm: &mut VM, module: phalcom_core::heap::ObjRef, source: &str) -> Result<(), PhError> {
+    let closure = vm.compile_closure_as(module, source, UnitKind::File)?;
+    vm.run_in_module(module, closure)
+}
+
+fn tripped(result: Result<(), PhError>) -> ResourceLimit {
+    match result {
+        Err(PhError::Runtime(RuntimeError::LimitExceeded { limit, .. })) => limit,
+        other => panic!("expected LimitExceeded, got {other:?}"),
+    }
+}
+
+fn global(vm: &mut VM, module: phalcom_core::heap::ObjRef, name: &str) -> Value {
+    let sym = vm.interner.intern(name);
+    vm.heap.module(module).get(sym).expect("global is bound")
+}
+
+fn fuel(fuel: u64) -> VmLimits {
+    VmLimits {
+        fuel: Some(fuel),
+        ..VmLimits::unlimited()
+    }
+}
+
+/// **The case the limits exist for:** an empty infinite loop stops.
+#[test]
+fn fuel_stops_an_infinite_loop() {
+    let (mut vm, module) = sandboxed(fuel(10_000));
+    assert_eq!(tripped(run(&mut vm, module, "while (true) {}\n")), ResourceLimit::Fuel);
+    assert_eq!(vm.fuel_remaining(), Some(0));
+}
+
+/// **Calls are metered too**, so recursion through a native iterator — which
+/// never takes a bytecode back-edge — is bounded by fuel as well as depth.
+#[test]
+fn fuel_is_charged_per_call() {
+    let (mut vm, module) = sandboxed(fuel(500));
+    let result = run(&mut vm, module, "let i = 0\n(1..1000000).each(|x| { i = i + 1 })\n");
+    assert_eq!(tripped(result), ResourceLimit::Fuel);
+}
+
+/// **A budget that suffices is invisible**, and rearming refills it so one VM
+/// can evaluate snippet after snippet.
+#[test]
+fn rearming_refills_the_budget() {
+    let mut vm = VM::new();
+    vm.set_limits(fuel(1_000));
+    let spin = |vm: &mut VM| {
+        let module = vm.create_module("snippet", "<sandbox>");
+        run(vm, module, "let i = 0\nwhile (i < 600) { i = i + 1 }\n")
+    };
+    spin(&mut vm).expect("600 back-edges fit in 1000");
+    assert_eq!(tripped(spin(&mut vm)), ResourceLimit::Fuel, "the budget spans evaluations until rearmed");
+    vm.rearm_limits();
+    spin(&mut vm).expect("rearmed");
+}
+
+/// **Uncatchable.** Neither `catch` nor `ensure` gets to run more `.ph`
+/// code once a limit has tripped.
+#[test]
+fn limit_skips_catch_and_ensure() {
+    let (mut vm, module) = sandboxed(fuel(10_000));
+    let result = run(
+        &mut vm,
+        module,
+        "let caught = false\n\
+         let cleaned = false\n\
+         try {\n  try { while (true) {} } catch e { caught = true }\n} ensure {\n  cleaned = true\n}\n",
+    );
+    assert_eq!(tripped(result), ResourceLimit::Fuel);
+    assert_eq!(global(&mut vm, module, "caught"), Value::bool(false), "a handler must never see the limit");
+    assert_eq!(global(&mut vm, module, "cleaned"), Value::bool(false), "cleanup is untrusted code too");
+}
+
+/// **A `Fiber#try` boundary does not capture it either**: the limit reaches
+/// the host, not the resumer as an `Error` value.
+#[test]
+fn limit_crosses_fiber_try() {
+    let (mut vm, module) = sandboxed(fuel(10_000));
+    let result = run(
+        &mut vm,
+        module,
+        "let reached = false\nconst f = Fiber.new || { while (true) {} }\nconst r = f.try()\nreached = true\n",
+    );
+    assert_eq!(tripped(result), ResourceLimit::Fuel);
+    assert_eq!(global(&mut vm, module, "reached"), Value::bool(false));
+}
+
+/// **The deadline stops a loop with no fuel budget**, promptly.
+#[test]
+fn deadline_stops_a_loop() {
+    let (mut vm, module) = sandboxed(VmLimits {
+        timeout: Some(Duration::from_millis(50)),
+        ..VmLimits::unlimited()
+    });
+    let started = Instant::now();
+    assert_eq!(tripped(run(&mut vm, module, "while (true) {}\n")), ResourceLimit::Deadline);
+    assert!(started.elapsed() < Duration::from_secs(5), "the deadline must be checked while looping");
+}
+
+/// **The heap cap stops an allocation bomb**, but garbage does not count:
+/// a loop that drops everything it allocates runs to completion under the
+/// same cap.
+#[test]
+fn heap_cap_counts_only_what_survives() {
+    let mut vm = VM::new();
+    vm.force_gc();
+    let cap = vm.heap.live_bytes() + 2 * 1024 * 1024;
+    vm.set_limits(VmLimits {
+        max_heap_bytes: Some(cap),
+        ..VmLimits::unlimited()
+    });
+    let module = vm.create_module("main", "<sandbox>");
+
+    run(
+        &mut vm,
+        module,
+        "let churn = || { let row = List.new()\n  let j = 0\n  while (j < 100) { row.append(j.toString)\n j = j + 1 }\n  None }\n\
+         let i = 0\nwhile (i < 2000) { churn.call()\n i = i + 1 }\n",
+    )
+    .expect("garbage must not count against the cap");
+
+    let bomb = run(&mut vm, module, "let keep = List.new()\nwhile (true) { keep.append(List.new()) }\n");
+    assert_eq!(tripped(bomb), ResourceLimit::HeapBytes);
+}
+
+/// **The sandbox preset brings its numeric policy**, so a single big-integer
+/// primitive — which no safepoint interrupts — stays small too.
+#[test]
+fn sandbox_applies_the_sandbox_numeric_policy() {
+    let (mut vm, module) = sandboxed(VmLimits::sandbox());
+    assert_eq!(vm.numeric_policy, NumericPolicy::sandbox());
+    let error = run(&mut vm, module, "let big = 1 << 300000\n").expect_err("262144 bits is the sandbox ceiling");
+    assert!(format!("{error:?}").contains("bit limit"), "{error:?}");
+
+    let (mut vm, module) = sandboxed(VmLimits::unlimited());
+    assert_eq!(vm.numeric_policy, NumericPolicy::standard(), "`None` keeps the VM's policy");
+    run(&mut vm, module, "let big = 1 << 300000\n").expect("within the standard policy");
+}
+
+/// **Call depth is configurable** and keeps PDR-0007's catchable error.
+#[test]
+fn call_depth_is_configurable() {
+    let (mut vm, module) = sandboxed(VmLimits {
+        max_call_depth: 64,
+        ..VmLimits::unlimited()
+    });
+    match run(&mut vm, module, "let f = |n| { f.call(n + 1) }\nf.call(0)\n") {
+        Err(PhError::Runtime(RuntimeError::DepthExceeded { limit, .. })) => assert_eq!(limit, 64),
+        other => panic!("expected DepthExceeded, got {other:?}"),
+    }
+}
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/limits").join(name)
+}
+
+/// **The capability switch refuses `std.fs`** before anything runs, and
+/// leaves pure `std` modules importable.
+#[test]
+fn host_modules_are_refused_when_disallowed() {
+    let denied = VmLimits {
+        allow_host_modules: false,
+        ..VmLimits::unlimited()
+    };
+
+    let program = ProgramCompiler::compile_entry_selection(EntrySelection::Module(fixture("imports_fs.ph"))).expect("compiles");
+    let mut vm = VM::new();
+    vm.set_limits(denied.clone());
+    match vm.run_compiled(&program) {
+        Err(PhError::Runtime(RuntimeError::CapabilityDenied { module })) => assert!(module.contains("fs"), "{module}"),
+        other => panic!("expected CapabilityDenied, got {other:?}"),
+    }
+    let mut trusted = VM::new();
+    trusted.run_compiled(&program).expect("an unrestricted VM imports std.fs");
+
+    let program = ProgramCompiler::compile_entry_selection(EntrySelection::Module(fixture("imports_json.ph"))).expect("compiles");
+    let mut vm = VM::new();
+    vm.set_limits(denied);
+    vm.run_compiled(&program).expect("std.json is not a host module");
+}
@@ -35,6 +35,7 @@ fn linked_module() -> LinkedModule {
         },
         linked_reads: vec![LinkedReadSpec::Binding(symbol)],
         runtime_dependencies: Vec::new(),
+        extension_sources: Vec::new(),
     }
 }
 
@@ -321,3 +321,100 @@ fn mat_06_module_import_binding_resolves_to_module() {
         RuntimeLinkedRead::Binding(_) => panic!("expected Module linked read"),
     }
 }
+
+/// Writes a `scoped` project whose entry is `app` and whose package exposes
+/// every listed module.
+fn scoped_project(modules: &[(&str, &str)]) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::write(
+        root.join("project.toml"),
+        "[project]\nname = \"scoped\"\nnamespace = \"scoped\"\nversion = \"0.1.0\"\nentry = \"scoped.app\"\n",
+    )
+    .unwrap();
+    let src_dir = root.join("src");
+    std::fs::create_dir_all(&src_dir).unwrap();
+    let exposes: String = modules.iter().map(|(name, _)| format!("expose .{name}\n")).collect();
+    std::fs::write(src_dir.join("package.ph"), exposes).unwrap();
+    for (name, source) in modules {
+        std::fs::write(src_dir.join(format!("{name}.ph")), source).unwrap();
+    }
+    dir
+}
+
+/// EXT-01 — A direct importer sees a module's `extend` block; an importer of
+/// that importer does not
+#[test]
+fn ext_01_extensions_reach_direct_importers_only() {
+    let dir = scoped_project(&[
+        ("strings", "extend String {\n  shout() { self + \"!\" }\n}\nlet ready = true\nexport ready\n"),
+        ("middle", "from .strings import ready\nlet loud = \"hi\".shout()\nexport loud\n"),
+        (
+            "app",
+            "from .middle import loud\nlet seen = loud == \"hi!\"\nlet transitive = \"visible\"\ntry { \"x\".shout() } catch e { transitive = \"hidden\" }\nlet hidden = transitive == \"hidden\"\n",
+        ),
+    ]);
+    let program = ProgramCompiler::compile_entry_selection(EntrySelection::Project(dir.path().to_path_buf())).expect("compile scoped");
+
+    let app_id = program.entry.clone();
+    let middle_id = program
+        .modules
+        .keys()
+        .find(|id| id.path.to_string().contains("middle"))
+        .expect("middle present");
+    let strings_id = program
+        .modules
+        .keys()
+        .find(|id| id.path.to_string().contains("strings"))
+        .expect("strings present");
+    assert_eq!(program.linked.modules[middle_id].extension_sources, vec![strings_id.clone()]);
+    assert_eq!(program.linked.modules[&app_id].extension_sources, vec![middle_id.clone()]);
+
+    let mut vm = VM::new();
+    vm.run_compiled(&program).expect("run scoped");
+    let app_obj = vm.module_registry.get(&app_id).unwrap().object;
+    for name in ["seen", "hidden"] {
+        let sym = vm.interner.intern(name);
+        assert_eq!(vm.heap.module(app_obj).get(sym), Some(Value::bool(true)), "`{name}` in app");
+    }
+}
+
+/// EXT-02 — Two directly imported modules extending one class with the same
+/// selector fail to link
+#[test]
+fn ext_02_conflicting_visible_extensions_fail_link() {
+    let dir = scoped_project(&[
+        ("loud", "extend String {\n  shout() { self + \"!\" }\n}\nlet a = 1\nexport a\n"),
+        ("louder", "extend String {\n  shout() { self + \"!!\" }\n}\nlet b = 1\nexport b\n"),
+        ("app", "from .loud import a\nfrom .louder import b\n"),
+    ]);
+    let result = ProgramCompiler::compile_entry_selection(EntrySelection::Project(dir.path().to_path_buf()));
+
+    match result {
+        Err(ProgramCompileError::Link(LinkError::ExtensionConflict { member, .. })) => assert_eq!(member, "String#shout()"),
+        other => panic!("expected ExtensionConflict, got {other:?}"),
+    }
+}
+
+/// EXT-03 — The same two extensions never meet in one module when each is
+/// imported by a different module
+#[test]
+fn ext_03_extensions_in_separate_importers_do_not_conflict() {
+    let dir = scoped_project(&[
+        ("loud", "extend String {\n  shout() { self + \"!\" }\n}\nlet a = 1\nexport a\n"),
+        ("louder", "extend String {\n  shout() { self + \"!!\" }\n}\nlet b = 1\nexport b\n"),
+        ("left", "from .loud import a\nlet one = \"x\".shout()\nexport one\n"),
+        ("right", "from .louder import b\nlet two = \"x\".shout()\nexport two\n"),
+        (
+            "app",
+            "from .left import one\nfrom .right import two\nlet ok = one == \"x!\" and two == \"x!!\"\n",
+        ),
+    ]);
+    let program = ProgramCompiler::compile_entry_selection(EntrySelection::Project(dir.path().to_path_buf())).expect("compile scoped");
+
+    let mut vm = VM::new();
+    vm.run_compiled(&program).expect("run scoped");
+    let app_obj = vm.module_registry.get(&program.entry).unwrap().object;
+    let sym = vm.interner.intern("ok");
+    assert_eq!(vm.heap.module(app_obj).get(sym), Some(Value::bool(true)));
+}
@@ -0,0 +1,223 @@
+use phalcom_core::error::{PhError, RuntimeError};
+use phalcom_core::modules::compile::{CompiledProgram, EntrySelection, ProgramCompiler};
+use phalcom_core::value::Value;
+use phalcom_core::vm::VM;
+use phalcom_modules::ModuleId;
+use std::fs;
+use std::path::Path;
+
+const COUNTER: &str =
+    "class Counter {\n  @constructor\n  new() { _count = 0 }\n  bump { _count = _count + 1 }\n  count { _count }\n  label { \"v1\" }\n}\nexport Counter\n";
+
+/// An `app` project whose entry keeps a bumped `Counter` in a global.
+fn project(root: &Path) {
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("project.toml"),
+        "[project]\nname = \"app\"\nnamespace = \"app\"\nentry = \"app.main\"\n",
+    )
+    .unwrap();
+    fs::write(root.join("src/package.ph"), "expose .counter\nexpose .main\n").unwrap();
+    fs::write(root.join("src/counter.ph"), COUNTER).unwrap();
+    fs::write(root.join("src/main.ph"), "from .counter import Counter\nlet c = Counter.new()\nc.bump\n").unwrap();
+}
+
+fn compile(root: &Path) -> CompiledProgram {
+    ProgramCompiler::compile_entry_selection(EntrySelection::Project(root.to_path_buf())).expect("project compiles")
+}
+
+fn module_id(program: &CompiledProgram, path: &str) -> ModuleId {
+    program.modules.keys().find(|id| id.path.to_string() == path).cloned().unwrap()
+}
+
+fn global(vm: &mut VM, program: &CompiledProgram, path: &str, name: &str) -> Value {
+    let module = vm.module_registry.get(&module_id(program, path)).unwrap().object;
+    let name = vm.interner.intern(name);
+    vm.heap.module(module).get(name).unwrap()
+}
+
+fn send(vm: &mut VM, receiver: Value, selector: &str) -> String {
+    let selector = vm.interner.intern(selector);
+    let result = vm.send_dynamic(receiver, selector, &[]).unwrap();
+    result.to_display_string(vm).unwrap()
+}
+
+/// Rewrites `counter.ph` and reloads it from a fresh compile of the project.
+fn reload(vm: &mut VM, root: &Path, source: &str) -> Result<phalcom_core::modules::ModuleReload, PhError> {
+    fs::write(root.join("src/counter.ph"), source).unwrap();
+    let program = compile(root);
+    let id = module_id(&program, "counter");
+    vm.reload_module(&program, &id)
+}
+
+#[test]
+fn reload_patches_methods_and_migrates_instances() {
+    let dir = tempfile::tempdir().unwrap();
+    project(dir.path());
+    let program = compile(dir.path());
+    let mut vm = VM::new();
+    vm.run_compiled(&program).unwrap();
+    let counter = global(&mut vm, &program, "counter", "Counter");
+    let c = global(&mut vm, &program, "main", "c");
+    assert_eq!(send(&mut vm, c, "label"), "v1");
+
+    let body_only = reload(&mut vm, dir.path(), &COUNTER.replace("\"v1\"", "\"v2\"")).unwrap();
+    assert!(
+        body_only.plan.changed.is_empty() && body_only.plan.dependents.is_empty(),
+        "{:?}",
+        body_only.plan
+    );
+    assert_eq!(body_only.patched, ["Counter"]);
+    assert_eq!(body_only.migrated, 0);
+    assert_eq!(send(&mut vm, c, "label"), "v2");
+    assert_eq!(send(&mut vm, c, "count"), "1");
+
+    let grown = COUNTER
+        .replace(
+            "bump { _count = _count + 1 }",
+            "bump {\n    _hits = 1\n    _count = _count + 1\n  }\n  hits { _hits }",
+        )
+        .replace("\"v1\"", "\"v3\"");
+    let report = reload(&mut vm, dir.path(), &grown).unwrap();
+    assert_eq!(report.plan.changed.iter().map(|id| &*id.name).collect::<Vec<_>>(), ["Counter"]);
+    assert_eq!(report.plan.dependents, [module_id(&program, "main")]);
+    assert_eq!(report.migrated, 1);
+    assert_eq!(global(&mut vm, &program, "counter", "Counter"), counter, "the class keeps its identity");
+    assert_eq!(send(&mut vm, c, "label"), "v3");
+    assert_eq!(send(&mut vm, c, "hits"), "None");
+    send(&mut vm, c, "bump");
+    assert_eq!(send(&mut vm, c, "hits"), "1");
+    assert_eq!(send(&mut vm, c, "count"), "2");
+    let fresh = send(&mut vm, counter, "new()");
+    assert!(fresh.contains("Counter"), "{fresh}");
+}
+
+#[test]
+fn incompatible_shape_changes_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    project(dir.path());
+    let program = compile(dir.path());
+    let mut vm = VM::new();
+    vm.run_compiled(&program).unwrap();
+    let c = global(&mut vm, &program, "main", "c");
+
+    let renamed = COUNTER.replace("_count", "_total").replace("\"v1\"", "\"v2\"");
+    match reload(&mut vm, dir.path(), &renamed) {
+        Err(PhError::Runtime(RuntimeError::IncompatibleReload { class, reason, .. })) => {
+            assert_eq!(class, "Counter");
+            assert!(reason.contains("`_count`"), "{reason}");
+        }
+        other => panic!("expected IncompatibleReload, got {other:?}"),
+    }
+
+    let rebased = format!("class Base {{}}\n{}", COUNTER.replace("class Counter {", "class Counter is Base {"));
+    match reload(&mut vm, dir.path(), &rebased) {
+        Err(PhError::Runtime(RuntimeError::IncompatibleReload { reason, .. })) => assert_eq!(reason, "changed its superclass"),
+        other => panic!("expected IncompatibleReload, got {other:?}"),
+    }
+
+    assert_eq!(send(&mut vm, c, "label"), "v1", "a refused reload leaves the module as it was");
+    send(&mut vm, c, "bump");
+    assert_eq!(send(&mut vm, c, "count"), "2");
+    reload(&mut vm, dir.path(), &COUNTER.replace("\"v1\"", "\"v2\"")).unwrap();
+    assert_eq!(send(&mut vm, c, "label"), "v2");
+}
+
+/// **A module with an `extend` block reloads**: its extensions are replaced,
+/// not declared a second time, and a refused reload brings the old ones back.
+#[test]
+fn reload_replaces_extensions() {
+    let dir = tempfile::tempdir().unwrap();
+    project(dir.path());
+    let extended = format!("{COUNTER}extend String {{\n  shout {{ self + \"!\" }}\n}}\n");
+    fs::write(dir.path().join("src/counter.ph"), &extended).unwrap();
+    fs::write(
+        dir.path().join("src/main.ph"),
+        "from .counter import Counter\nlet c = Counter.new()\nlet shout = || \"hi\".shout\n",
+    )
+    .unwrap();
+    let program = compile(dir.path());
+    let mut vm = VM::new();
+    vm.run_compiled(&program).unwrap();
+    let shout = global(&mut vm, &program, "main", "shout");
+    assert_eq!(send(&mut vm, shout, "call()"), "hi!");
+
+    reload(&mut vm, dir.path(), &extended.replace("\"!\"", "\"!!\"")).expect("an extension is redeclared, not duplicated");
+    assert_eq!(send(&mut vm, shout, "call()"), "hi!!");
+
+    let rebased = format!(
+        "class Base {{}}\n{}",
+        extended.replace("class Counter {", "class Counter is Base {").replace("\"!\"", "\"?\"")
+    );
+    assert!(reload(&mut vm, dir.path(), &rebased).is_err());
+    assert_eq!(send(&mut vm, shout, "call()"), "hi!!", "a refused reload restores the extensions");
+}
+
+#[test]
+fn reloaded_multimethods_dispatch_on_existing_instances() {
+    let dir = tempfile::tempdir().unwrap();
+    project(dir.path());
+    let counter = COUNTER.replace("  label {", "  @multimethod\n  describe(_ x: Int) { \"int\" }\n  label {")
+        + "class Tally is Counter {\n  @multimethod\n  describe(_ x: String) { \"string\" }\n}\nexport Tally\n";
+    fs::write(dir.path().join("src/counter.ph"), &counter).unwrap();
+    fs::write(
+        dir.path().join("src/main.ph"),
+        "from .counter import Tally\nlet t = Tally.new()\nlet int = || t.describe(1)\nlet float = || t.describe(1.5)\nlet string = || t.describe(\"s\")\n",
+    )
+    .unwrap();
+    let program = compile(dir.path());
+    let mut vm = VM::new();
+    vm.run_compiled(&program).unwrap();
+    let [int, float, string] = ["int", "float", "string"].map(|name| global(&mut vm, &program, "main", name));
+    assert_eq!(send(&mut vm, int, "call()"), "int");
+    assert_eq!(send(&mut vm, string, "call()"), "string");
+
+    let grown = counter.replacen("  label {", "  @multimethod\n  describe(_ x: Float) { \"float\" }\n  label {", 1);
+    reload(&mut vm, dir.path(), &grown).unwrap();
+    assert_eq!(send(&mut vm, float, "call()"), "float");
+    assert_eq!(send(&mut vm, int, "call()"), "int");
+    assert_eq!(send(&mut vm, string, "call()"), "string");
+}
+
+#[test]
+fn reload_carries_class_kind_flags() {
+    let dir = tempfile::tempdir().unwrap();
+    project(dir.path());
+    fs::write(dir.path().join("src/counter.ph"), format!("{COUNTER}class Loud {{\n  shout {{ \"!\" }}\n}}\n")).unwrap();
+    let program = compile(dir.path());
+    let mut vm = VM::new();
+    vm.run_compiled(&program).unwrap();
+    let loud = global(&mut vm, &program, "counter", "Loud");
+    assert!(!vm.heap.class(loud.as_obj().unwrap()).is_mixin);
+
+    reload(&mut vm, dir.path(), &format!("{COUNTER}@mixin\nclass Loud {{\n  shout {{ \"!\" }}\n}}\n")).unwrap();
+    let row = vm.heap.class(loud.as_obj().unwrap());
+    assert!(row.is_mixin && row.is_abstract, "the patched row is a mixin now");
+    let new = vm.interner.intern("new");
+    assert!(vm.send_dynamic(loud, new, &[]).is_err(), "a mixin is never instantiated");
+}
+
+#[test]
+fn failed_reloads_drop_the_globals_they_defined() {
+    let dir = tempfile::tempdir().unwrap();
+    project(dir.path());
+    let program = compile(dir.path());
+    let mut vm = VM::new();
+    vm.run_compiled(&program).unwrap();
+    let module = vm.module_registry.get(&module_id(&program, "counter")).unwrap().object;
+    let extra = vm.interner.intern("extra");
+
+    let failing = format!("{COUNTER}let extra = 1\nthrow Error.new(\"reload.test: boom\")\n");
+    assert!(reload(&mut vm, dir.path(), &failing).is_err());
+    assert_eq!(vm.heap.module(module).get(extra), None);
+
+    reload(&mut vm, dir.path(), &format!("{COUNTER}let extra = 2\n")).unwrap();
+    assert_eq!(
+        vm.heap
+            .module(module)
+            .get(extra)
+            .map(|value| value.to_display_string(&mut vm).unwrap())
+            .as_deref(),
+        Some("2")
+    );
+}
@@ -0,0 +1,108 @@
+//! Native classes registered from outside the kernel
+//! (`phalcom_native_meta::EXTENSION_CLASSES`).
+//!
+//! This test crate plays the extension crate: it declares `acme.crypto.Digest`
+//! and its primitives exactly as a downstream crate would, and `.ph` code
+//! imports them like any other module.
+
+use linkme::distributed_slice;
+use phalcom_core::embed::{Engine, FromPhalcom, IntoPhalcom};
+use phalcom_core::error::{PhError, PhResult, RuntimeError};
+use phalcom_core::modules::compile::{EntrySelection, ProgramCompileError, ProgramCompiler};
+use phalcom_core::value::Value;
+use phalcom_core::vm::VM;
+use phalcom_native_meta::{EXTENSION_CLASSES, NativeClassSpec, NativeDispatch, UniverseKey};
+
+#[distributed_slice(EXTENSION_CLASSES)]
+static DIGEST: NativeClassSpec = NativeClassSpec {
+    module: "acme.crypto",
+    name: "Digest",
+    superclass: UniverseKey::Object,
+};
+
+/// Signature: `Digest.class::fnv(_)` — the 64-bit FNV-1a hash of a string.
+#[phalcom_native_macros::primitive(DIGEST, "fnv(_)", side = class, types = "(String) -> Int", raises = [Error], effects = pure)]
+fn digest_class_fnv(vm: &mut VM, _receiver: &Value, args: &[Value]) -> PhResult<Value> {
+    let text = String::from_phalcom(vm, args[0])?;
+    fnv(&text).into_phalcom(vm)
+}
+
+/// Signature: `Digest::algorithm` — the name of the instance's algorithm.
+#[phalcom_native_macros::primitive(DIGEST, "algorithm", types = "() -> String", effects = pure)]
+fn digest_algorithm(vm: &mut VM, _receiver: &Value, _args: &[Value]) -> PhResult<Value> {
+    "fnv1a".into_phalcom(vm)
+}
+
+/// FNV-1a, shifted into `Int` range.
+fn fnv(text: &str) -> i64 {
+    (text
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x100_0000_01b3))
+        >> 1) as i64
+}
+
+/// **An extension module imports like `std`**, and its class answers both
+/// class-side and inherited instance-side messages.
+#[test]
+fn extension_class_is_importable() {
+    let mut engine = Engine::new();
+    assert_eq!(engine.eval::<i64>("import acme.crypto\ncrypto.Digest.fnv(\"abc\")").unwrap(), fnv("abc"));
+    assert_eq!(engine.eval::<String>("crypto.Digest.new().algorithm").unwrap(), "fnv1a");
+    assert_eq!(engine.eval::<String>("crypto.Digest.name").unwrap(), "Digest");
+    assert!(engine.eval::<bool>("crypto.Digest.new() is Object").unwrap());
+}
+
+/// **A project module sees the class through a selective import**, and a
+/// bad argument is an ordinary raise.
+#[test]
+fn extension_class_in_a_project() {
+    let project = project("from acme.crypto import Digest\nlet hash = |text| Digest.fnv(text)\nexport hash\n");
+    let mut engine = Engine::new();
+    let module = engine.load(EntrySelection::Project(project.path().to_path_buf())).unwrap();
+    let hash = engine.export(&module, "hash").unwrap();
+    assert_eq!(engine.call::<i64>(&hash, ("phalcom",)).unwrap(), fnv("phalcom"));
+    assert!(matches!(
+        engine.call::<i64>(&hash, (3,)),
+        Err(PhError::Runtime(RuntimeError::TypeConversion { .. }))
+    ));
+}
+
+/// **Primitive type contracts reach the checker**: a statically ill-typed
+/// argument is a compile error, as it is for a kernel primitive.
+#[test]
+fn extension_contracts_are_checked() {
+    let project = project("from acme.crypto import Digest\nlet hash = Digest.fnv(3)\n");
+    match ProgramCompiler::compile_entry_selection(EntrySelection::Project(project.path().to_path_buf())) {
+        Err(ProgramCompileError::Semantic(diagnostics)) => {
+            let rendered = format!("{diagnostics:?}");
+            assert!(rendered.contains("ArgumentMismatch"), "{rendered}");
+        }
+        other => panic!("expected a semantic error, got {:?}", other.map(|_| ())),
+    }
+}
+
+/// **The registry is valid and the surfaces are visible** to VM-free tooling.
+#[test]
+fn extension_surfaces_are_registered() {
+    assert!(phalcom_native_meta::validate_extensions().is_empty());
+    assert!(phalcom_native_meta::is_extension_root("acme"));
+    assert_eq!(phalcom_native_meta::extension_class("acme.crypto", "Digest"), Some(&DIGEST));
+    let surfaces = phalcom_native_meta::extension_primitives(&DIGEST);
+    let selectors: Vec<_> = surfaces.iter().map(|surface| (surface.key.side, surface.key.selector)).collect();
+    assert_eq!(selectors, [(NativeDispatch::Instance, "algorithm"), (NativeDispatch::Class, "fnv(_)")]);
+}
+
+/// A one-module project whose `main` is `source`.
+fn project(source: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::write(
+        root.join("project.toml"),
+        "[project]\nname = \"hasher\"\nnamespace = \"hasher\"\nversion = \"0.1.0\"\nentry = \"hasher.main\"\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(root.join("src/package.ph"), "expose .main\n").unwrap();
+    std::fs::write(root.join("src/main.ph"), source).unwrap();
+    dir
+}
@@ -0,0 +1,147 @@
+use phalcom_core::modules::{ProgramAnalyzer, ProgramCompileError, PublishedPackage, package_project};
+use phalcom_modules::{FilesystemSourceProvider, ModuleComponent, ModuleId, ModulePath, ProjectError, ProjectLoadOptions, ProjectUniverse, PublishedPackageId};
+use phalcom_semantic::DiagnosticCode;
+use phalcom_type_meta::validate::ValidationLimits;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// A `shapes` library exposing `geo.circle`, with extra `[project]` lines.
+fn shapes(root: &Path, manifest: &str) -> PathBuf {
+    let dir = root.join("shapes");
+    fs::create_dir_all(dir.join("src/geo")).unwrap();
+    fs::write(
+        dir.join("project.toml"),
+        format!("[project]\nname = \"shapes\"\nnamespace = \"shapes\"\n{manifest}"),
+    )
+    .unwrap();
+    fs::write(dir.join("src/package.ph"), "expose .geo\n").unwrap();
+    fs::write(dir.join("src/geo/package.ph"), "expose .circle\n").unwrap();
+    fs::write(
+        dir.join("src/geo/circle.ph"),
+        "class Circle {\n  @constructor\n  new(r: Int) { _r = r }\n  radius -> Int { _r }\n}\nexport Circle\n",
+    )
+    .unwrap();
+    dir
+}
+
+fn path(dotted: &str) -> ModulePath {
+    ModulePath::from_components(
+        dotted
+            .split('.')
+            .map(|part| ModuleComponent::from_identifier(part).unwrap())
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn not_publishable(result: Result<impl std::fmt::Debug, ProgramCompileError>) -> String {
+    match result.unwrap_err() {
+        ProgramCompileError::Project(ProjectError::NotPublishable { reason, .. }) => reason,
+        other => panic!("expected NotPublishable, got {other:?}"),
+    }
+}
+
+#[test]
+fn packages_are_deterministic_and_verified() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = shapes(dir.path(), "version = \"1.2.0\"\nlicense = \"MIT\"\n");
+    let first = package_project(&root, &dir.path().join("a"), &ProjectLoadOptions::default()).unwrap();
+    let second = package_project(&root, &dir.path().join("b"), &ProjectLoadOptions::default()).unwrap();
+    assert_eq!(first.checksum, second.checksum);
+    assert_eq!(fs::read(&first.archive).unwrap(), fs::read(&second.archive).unwrap());
+    assert!(first.archive.ends_with("shapes-1.2.0.tar"));
+    assert_eq!(first.sources, 3);
+
+    let package = PublishedPackageId::from_raw(7);
+    let published = PublishedPackage::open(&first.archive, package, BTreeMap::new(), &ValidationLimits::default()).unwrap();
+    let artifact = &published.artifact;
+    assert!(artifact.is_path_exposed(&path("geo.circle")));
+    let circle = &artifact.interfaces[&ModuleId::resolved(package, path("geo.circle"))];
+    assert!(circle.exports.contains_key("Circle"), "{:?}", circle.exports);
+    assert!(published.declaration(&path("geo.circle"), "Circle").is_some());
+
+    // Flip one byte of a packaged source; the checksum manifest catches it.
+    let mut bytes = fs::read(&first.archive).unwrap();
+    let at = bytes.windows(6).position(|window| window == b"Circle").unwrap();
+    bytes[at] = b'K';
+    fs::write(&first.archive, bytes).unwrap();
+    let err = PublishedPackage::open(&first.archive, package, BTreeMap::new(), &ValidationLimits::default()).unwrap_err();
+    assert!(
+        matches!(err, ProgramCompileError::Project(ProjectError::InvalidPackageArchive { .. })),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn consumers_type_check_against_the_archive_alone() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = shapes(dir.path(), "version = \"1.2.0\"\nlicense = \"MIT\"\n");
+    let packaged = package_project(&root, &dir.path().join("out"), &ProjectLoadOptions::default()).unwrap();
+    // Only the archive describes `shapes` from here on.
+    fs::write(root.join("src/geo/circle.ph"), "class Circle {\n").unwrap();
+
+    let app = dir.path().join("app");
+    fs::create_dir_all(app.join("src")).unwrap();
+    fs::write(
+        app.join("project.toml"),
+        "[project]\nname = \"app\"\nnamespace = \"app\"\n\n[dependencies]\nshapes = { path = \"../shapes\" }\n",
+    )
+    .unwrap();
+    let consumer = "from shapes.geo.circle import Circle\nconst c: Circle = Circle.new(r: 2)\nconst r: Int = c.radius\n";
+    fs::write(app.join("src/package.ph"), consumer).unwrap();
+
+    let mut universe = ProjectUniverse::new();
+    let app_id = universe.load_root(app.join("project.toml")).unwrap();
+    let shapes_id = universe.get_project(app_id).unwrap().dependencies[&ModuleComponent::from_identifier("shapes").unwrap()];
+    let published = PublishedPackage::open(&packaged.archive, shapes_id, BTreeMap::new(), &ValidationLimits::default()).unwrap();
+    let universe = Arc::new(universe);
+    let analyze = || {
+        ProgramAnalyzer::analyze_against_packages(
+            universe.clone(),
+            FilesystemSourceProvider::new(),
+            ModuleId::resolved(app_id, ModulePath::root()),
+            std::slice::from_ref(&published),
+        )
+    };
+
+    let program = analyze().unwrap();
+    assert!(program.linked.modules.contains_key(&ModuleId::resolved(shapes_id, path("geo.circle"))));
+    assert!(!program.sources.keys().any(|module| module.project.as_resolved() == Some(shapes_id)));
+
+    fs::write(app.join("src/package.ph"), format!("{consumer}const bad: String = c.radius\n")).unwrap();
+    match analyze().unwrap_err() {
+        ProgramCompileError::Semantic(diagnostics) => {
+            let codes: Vec<_> = diagnostics.by_module.values().flatten().map(|diagnostic| diagnostic.code).collect();
+            assert_eq!(codes, [DiagnosticCode::BindingInitializerMismatch]);
+        }
+        other => panic!("expected a type error, got {other:?}"),
+    }
+}
+
+#[test]
+fn unpublishable_projects_are_refused() {
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("out");
+
+    let root = shapes(dir.path(), "version = \"1.2.0\"\n");
+    assert!(not_publishable(package_project(&root, &out, &ProjectLoadOptions::default())).contains("license"));
+
+    let root = shapes(dir.path(), "license = \"MIT\"\n");
+    assert!(not_publishable(package_project(&root, &out, &ProjectLoadOptions::default())).contains("version"));
+
+    let local = dir.path().join("local");
+    fs::create_dir_all(local.join("src")).unwrap();
+    fs::write(
+        local.join("project.toml"),
+        "[project]\nname = \"local\"\nnamespace = \"local\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(local.join("src/package.ph"), "").unwrap();
+    let root = shapes(
+        dir.path(),
+        "version = \"1.2.0\"\nlicense = \"MIT\"\n\n[dependencies]\nlocal = { path = \"../local\" }\n",
+    );
+    assert!(not_publishable(package_project(&root, &out, &ProjectLoadOptions::default())).contains("local"));
+    assert!(!out.exists());
+}
@@ -0,0 +1,165 @@
+use phalcom_core::doc::{self, ClassKind, Doctest, MemberKind, ProjectDocs, collect_doctests, document_project, run_doctests};
+use phalcom_core::modules::{AnalyzedProject, ProgramAnalyzer};
+
+const GEOMETRY: &str = r#"//! Plane geometry primitives.
+
+/// A circle centred at the origin.
+///
+/// Circles are immutable once built.
+///
+/// @since 0.1
+/// @example
+/// ```
+/// from .geometry import Circle
+/// if (Circle.new(2).radius != 2) { Fiber.abort("bad radius") }
+/// ```
+class Circle {
+  /// Builds a circle.
+  /// @param r the radius
+  @constructor
+  new(_ r: Int) { _r = r }
+
+  /// The radius.
+  radius -> Int { _r }
+
+  /// Scales the circle.
+  /// @param factor multiplier
+  /// @returns a new `Circle`
+  /// @frobnicate nonsense
+  /// @example
+  /// ```
+  /// from .geometry import Circle
+  /// Circle.new(1).scale(by: 3).missing
+  /// ```
+  scale(by factor: Int) -> Circle { Circle.new(_r * factor) }
+}
+
+export Circle
+"#;
+
+/// A two-module `shapes` project; only `main` is reachable from the entry,
+/// so `geometry` must be documented through its own analysis.
+fn project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::write(
+        root.join("project.toml"),
+        "[project]\nname = \"shapes\"\nnamespace = \"shapes\"\nversion = \"0.1.0\"\nentry = \"shapes.main\"\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(root.join("src/package.ph"), "expose .geometry\nexpose .main\n").unwrap();
+    std::fs::write(root.join("src/geometry.ph"), GEOMETRY).unwrap();
+    std::fs::write(root.join("src/main.ph"), "System.print(1)\n").unwrap();
+    dir
+}
+
+fn analyze(dir: &tempfile::TempDir) -> (AnalyzedProject, ProjectDocs) {
+    let analysis = ProgramAnalyzer::analyze_project_modules(dir.path()).expect("project analyzes");
+    let docs = document_project(&analysis);
+    (analysis, docs)
+}
+
+#[test]
+fn model_keys_members_by_selector_with_semantic_signatures() {
+    let dir = project();
+    let (_, docs) = analyze(&dir);
+    assert_eq!(docs.name, "shapes");
+    let names: Vec<_> = docs.modules.iter().map(|module| module.name.as_str()).collect();
+    assert_eq!(names, ["shapes", "shapes.geometry", "shapes.main"]);
+
+    let geometry = docs.module("shapes.geometry").expect("geometry documented");
+    assert_eq!(geometry.path, "src/geometry.ph");
+    assert_eq!(geometry.doc.as_ref().unwrap().summary, "Plane geometry primitives.");
+
+    let circle = geometry.class("Circle").expect("Circle documented");
+    assert_eq!(circle.kind, ClassKind::Class);
+    assert!(circle.exported);
+    let doc = circle.doc.as_ref().unwrap();
+    assert_eq!(doc.summary, "A circle centred at the origin.");
+    assert_eq!(doc.details, "Circles are immutable once built.");
+    assert_eq!(doc.tag_payloads("since").collect::<Vec<_>>(), ["0.1"]);
+
+    let members: Vec<_> = circle
+        .members
+        .iter()
+        .map(|member| (member.selector.as_str(), member.kind, member.class_side, member.signature.as_str()))
+        .collect();
+    assert_eq!(
+        members,
+        [
+            ("new(_)", MemberKind::Constructor, true, "new(_ r: Int)"),
+            ("radius", MemberKind::Getter, false, "radius -> Int"),
+            ("scale(by)", MemberKind::Method, false, "scale(by factor: Int) -> Circle"),
+        ]
+    );
+    let scale = &circle.members[2];
+    assert_eq!(scale.parameters[0].label.as_deref(), Some("by"));
+    assert_eq!(scale.parameters[0].ty.as_deref(), Some("Int"));
+    assert_eq!(scale.returns.as_deref(), Some("Circle"));
+}
+
+#[test]
+fn unknown_tags_warn_with_module_and_line() {
+    let dir = project();
+    let (_, docs) = analyze(&dir);
+    let warning = docs
+        .warnings
+        .iter()
+        .find(|warning| warning.warning.message.contains("frobnicate"))
+        .expect("unknown tag warns");
+    assert_eq!(warning.module, "shapes.geometry");
+    assert_eq!(warning.path, "src/geometry.ph");
+    assert_eq!(warning.warning.line, 25);
+}
+
+#[test]
+fn site_pages_link_classes_and_member_anchors() {
+    let dir = project();
+    let (_, docs) = analyze(&dir);
+    let pages = doc::html::render_site(&docs);
+    let page = |name: &str| &pages.iter().find(|(file, _)| file == name).unwrap_or_else(|| panic!("missing {name}")).1;
+
+    assert!(page("index.html").contains(r#"href="shapes.geometry.html#Circle""#));
+    let geometry = page("shapes.geometry.html");
+    assert!(geometry.contains(r#"id="Circle.scale(by)""#));
+    assert!(geometry.contains(r#"id="Circle.class.new(_)""#));
+    // The return type links back to the class it names.
+    assert!(geometry.contains(r#"<a href="shapes.geometry.html#Circle">Circle</a>"#));
+
+    let out = tempfile::tempdir().unwrap();
+    doc::write_site(&docs, out.path()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(out.path().join(doc::MODEL_FILE)).unwrap()).unwrap();
+    assert_eq!(json["format_version"], 1);
+    assert_eq!(json["modules"][1]["classes"][0]["members"][2]["selector"], "scale(by)");
+    assert!(out.path().join("style.css").is_file());
+}
+
+#[test]
+fn doctests_run_against_sibling_modules_and_report_failures() {
+    let dir = project();
+    let (analysis, docs) = analyze(&dir);
+    let doctests = collect_doctests(&docs);
+    let targets: Vec<_> = doctests.iter().map(|doctest| (doctest.target.as_str(), doctest.line)).collect();
+    assert_eq!(targets, [("shapes.geometry::Circle", 10), ("shapes.geometry::Circle#scale(by)", 28)]);
+
+    let outcomes = run_doctests(&analysis, &doctests);
+    assert!(outcomes[0].passed(), "{:?}", outcomes[0].failure);
+    let failure = outcomes[1].failure.as_deref().expect("second example fails");
+    assert!(failure.contains("missing"), "{failure}");
+}
+
+#[test]
+fn doctests_that_never_finish_fail_on_the_sandbox_limits() {
+    let dir = project();
+    let (analysis, _) = analyze(&dir);
+    let doctest = Doctest {
+        target: "shapes.geometry".into(),
+        path: "src/geometry.ph".into(),
+        line: 1,
+        code: "while (true) {}\n".into(),
+    };
+    let outcomes = run_doctests(&analysis, &[doctest]);
+    let failure = outcomes[0].failure.as_deref().expect("a runaway example fails");
+    assert!(failure.contains("limit"), "{failure}");
+}
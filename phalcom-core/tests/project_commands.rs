@@ -0,0 +1,228 @@
+use phalcom_core::modules::{EntrySelection, ProgramAnalyzer, ProgramCompileError};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn phalcom(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_phalcom"))
+        .args(args)
+        .current_dir(dir)
+        .env_remove("RUST_LOG")
+        .output()
+        .expect("failed to spawn the `phalcom` binary")
+}
+
+/// A `tools` project with the given extra `[project]` lines and modules.
+fn project(root: &Path, manifest: &str, modules: &[(&str, &str)]) -> PathBuf {
+    let dir = root.join("tools");
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("project.toml"),
+        format!("[project]\nname = \"tools\"\nnamespace = \"tools\"\nversion = \"0.1.0\"\n{manifest}"),
+    )
+    .unwrap();
+    fs::write(dir.join("src/package.ph"), "").unwrap();
+    for (path, source) in modules {
+        fs::write(dir.join("src").join(path), source).unwrap();
+    }
+    dir
+}
+
+fn entry_of(root: PathBuf, entry: Option<&str>) -> Result<String, ProgramCompileError> {
+    let selection = EntrySelection::ProjectEntry {
+        root,
+        entry: entry.map(str::to_string),
+    };
+    ProgramAnalyzer::analyze_entry_selection(selection).map(|program| program.entry.path.to_string())
+}
+
+#[test]
+fn project_entries_fall_back_to_the_default_entry() {
+    let dir = tempfile::tempdir().unwrap();
+    let modules = [("migrate.ph", "System.print(1)\n"), ("seed.ph", "System.print(2)\n")];
+    let root = project(dir.path(), "entry = \"tools.migrate\"\ndefault_entry = \"tools.seed\"\n", &modules);
+    assert_eq!(entry_of(root.clone(), None).unwrap(), "migrate");
+    assert_eq!(entry_of(root.clone(), Some("seed")).unwrap(), "seed");
+    assert_eq!(entry_of(root.clone(), Some("tools.seed")).unwrap(), "seed");
+
+    let root = project(dir.path(), "default_entry = \"tools.seed\"\n", &modules);
+    assert_eq!(entry_of(root.clone(), None).unwrap(), "seed");
+
+    let root = project(dir.path(), "", &modules);
+    let err = entry_of(root, None).unwrap_err();
+    assert!(matches!(err, ProgramCompileError::ProjectNotExecutable(_)), "{err:?}");
+}
+
+#[test]
+fn builds_report_every_broken_module() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = project(
+        dir.path(),
+        "",
+        &[
+            ("fine.ph", "class Fine {}\nexport Fine\n"),
+            ("bad-one.ph", "let x: Int = \"one\"\nclass Broken {}\nexport Broken\n"),
+            ("bad-two.ph", "let y: String = 2\n"),
+            ("uses-bad.ph", "from .bad_one import Broken\n"),
+        ],
+    );
+    let build = ProgramAnalyzer::build_project_modules(&root).unwrap();
+    assert_eq!(build.project.modules.len(), 5);
+    let mut diagnosed: Vec<String> = build
+        .errors
+        .iter()
+        .flat_map(|err| match err {
+            ProgramCompileError::Semantic(diags) => diags.by_module.keys().map(|module| module.path.to_string()).collect::<Vec<_>>(),
+            other => panic!("unexpected build error: {other:?}"),
+        })
+        .collect();
+    diagnosed.sort();
+    assert_eq!(diagnosed, ["bad_one", "bad_two"], "each broken module is reported once");
+}
+
+#[test]
+fn new_projects_build_and_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = phalcom(dir.path(), &["new", "hello-app"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let manifest = fs::read_to_string(dir.path().join("hello-app/project.toml")).unwrap();
+    assert!(manifest.contains("namespace = \"hello_app\""), "{manifest}");
+    assert!(dir.path().join("hello-app/src/package.ph").is_file());
+    assert_eq!(phalcom(dir.path(), &["new", "hello-app"]).status.code(), Some(73));
+
+    let app = dir.path().join("hello-app");
+    assert!(phalcom(&app, &["build"]).status.success());
+    let output = phalcom(&app, &["run"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "Hello from hello-app!\n");
+
+    let output = phalcom(dir.path(), &["new", "--lib", "shapes"]);
+    assert!(output.status.success());
+    let shapes = dir.path().join("shapes");
+    assert!(!fs::read_to_string(shapes.join("project.toml")).unwrap().contains("entry"));
+    assert!(phalcom(&shapes, &["build"]).status.success());
+    assert_eq!(phalcom(&shapes, &["run"]).status.code(), Some(65));
+}
+
+#[test]
+fn cfg_declarations_follow_the_compile_mode_and_features() {
+    let dir = tempfile::tempdir().unwrap();
+    let main = "class Greeter {\n  @constructor\n  new() {}\n\n  @cfg(mode: \"debug\")\n  word -> String { \"debug\" }\n  @cfg(mode: \"release\")\n  word -> String { \"release\" }\n\n  @cfg(feature: \"loud\")\n  suffix -> String { \"!\" }\n}\n\nSystem.print(Greeter.new().word + Greeter.new().suffix)\n";
+    let root = project(
+        dir.path(),
+        "entry = \"tools.main\"\n\n[features]\ndefault = [\"loud\"]\nloud = []\n",
+        &[("main.ph", main)],
+    );
+    assert_eq!(String::from_utf8_lossy(&phalcom(&root, &["run"]).stdout), "debug!\n");
+    assert_eq!(String::from_utf8_lossy(&phalcom(&root, &["--release", "run"]).stdout), "release!\n");
+    let output = phalcom(&root, &["run", "--no-default-features"]);
+    assert_eq!(output.status.code(), Some(70), "`suffix` is compiled out");
+    assert_eq!(phalcom(&root, &["run", "--features", "turbo"]).status.code(), Some(65));
+}
+
+#[test]
+fn graphs_show_cycles_import_chains_and_unused_imports() {
+    let dir = tempfile::tempdir().unwrap();
+    let helper = "class Helper {\n  @constructor\n  new() {}\n  word -> String { \"hi\" }\n}\nexport Helper\n";
+    let util = "from .helpers import Helper\n\nclass Util {\n  @constructor\n  new() {}\n  word -> String { Helper.new().word }\n}\nexport Util\n";
+    let main = "from .util import Util\nfrom .helpers import Helper\n\nSystem.print(Util.new().word)\n";
+    let root = project(dir.path(), "", &[("helpers.ph", helper), ("util.ph", util), ("main.ph", main)]);
+
+    let output = phalcom(&root, &["graph"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(
+        stdout.contains("  1. tools\n  2. tools.helpers\n  3. tools.util\n  4. tools.main\n"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("tools.main: `Helper` from .helpers (src/main.ph:2:22)"), "{stdout}");
+
+    let output = phalcom(&root, &["graph", "--why", "main", "tools.helpers"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("tools.main -> tools.helpers  `from .helpers import Helper`"), "{stdout}");
+    assert_eq!(phalcom(&root, &["graph", "--why", "helpers", "main"]).status.code(), Some(1));
+
+    // `helpers` importing `util` closes a runtime cycle `build` rejects.
+    let cyclic = format!("from .util import Util\n\n{}", helper.replace("export Helper", "export Helper, Util"));
+    fs::write(root.join("src/helpers.ph"), cyclic).unwrap();
+    let output = phalcom(&root, &["graph", "--format", "json"]);
+    let graph: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(graph["cycles"], serde_json::json!([["tools.helpers", "tools.util", "tools.helpers"]]));
+    assert!(graph["initialization_order"].is_null());
+    let output = phalcom(&root, &["graph", "--format", "dot"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("\"tools.util\" -> \"tools.helpers\" [label=\"selective import\", color=red, fontcolor=red];"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn vendored_projects_run_offline() {
+    let dir = tempfile::tempdir().unwrap();
+    let words = dir.path().join("words");
+    fs::create_dir_all(words.join("src")).unwrap();
+    fs::write(words.join("project.toml"), "[project]\nname = \"words\"\nnamespace = \"words\"\n").unwrap();
+    fs::write(
+        words.join("src/package.ph"),
+        "class Word {\n  @constructor\n  new() {}\n  text -> String { \"vendored\" }\n}\nexport Word\n",
+    )
+    .unwrap();
+    let root = project(
+        dir.path(),
+        "entry = \"tools.main\"\n\n[dependencies]\nwords = { path = \"../words\" }\n",
+        &[("main.ph", "from words import Word\nSystem.print(Word.new().text)\n")],
+    );
+    assert_eq!(phalcom(&root, &["run", "--offline"]).status.code(), Some(65), "nothing is vendored yet");
+
+    let output = phalcom(&root, &["vendor"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("words sha256:"));
+    assert!(fs::read_to_string(root.join("phalcom.lock")).unwrap().contains("checksum = \"sha256:"));
+    fs::remove_dir_all(&words).unwrap();
+    let output = phalcom(&root, &["run", "--offline"]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "vendored\n",
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!phalcom(&root, &["vendor", "--offline"]).status.success());
+}
+
+#[test]
+fn only_project_commands_write_the_lockfile() {
+    let dir = tempfile::tempdir().unwrap();
+    let words = dir.path().join("words");
+    fs::create_dir_all(words.join("src")).unwrap();
+    fs::write(words.join("project.toml"), "[project]\nname = \"words\"\nnamespace = \"words\"\n").unwrap();
+    fs::write(words.join("src/package.ph"), "class Word {}\nexport Word\n").unwrap();
+    let root = project(
+        dir.path(),
+        "entry = \"tools.main\"\n\n[dependencies]\nwords = { path = \"../words\" }\n",
+        &[("main.ph", "from words import Word\nSystem.print(\"ran\")\n")],
+    );
+    let lock = root.join("phalcom.lock");
+
+    let output = phalcom(&root, &["src/main.ph"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ran\n", "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(phalcom(&root, &["check", "src/main.ph"]).status.success());
+    assert!(!lock.exists(), "running or checking a file leaves the lock alone");
+    assert!(ProgramAnalyzer::analyze_project_modules(&root).is_ok());
+    assert!(!lock.exists(), "library loads leave the lock alone");
+
+    assert!(phalcom(&root, &["build"]).status.success());
+    assert!(fs::read_to_string(&lock).unwrap().contains("name = \"words\""));
+}
+
+#[test]
+fn only_project_commands_take_dependency_flags() {
+    let dir = tempfile::tempdir().unwrap();
+    for args in [
+        &["tokenize", "--offline", "-s", "1"][..],
+        &["disasm", "--locked", "-s", "1"],
+        &["--frozen", "-i", "1"],
+        &["--offline", "run"],
+    ] {
+        assert_eq!(phalcom(dir.path(), args).status.code(), Some(2), "{args:?}");
+    }
+}
@@ -0,0 +1,223 @@
+//! Weak references, ephemeron tables and finalization registries.
+//!
+//! The collector-side contract of `heap/weak.rs`: a `WeakRef` target is not an
+//! edge, a `WeakMap` value lives only as long as its key (ephemeron fixpoint),
+//! and a `FinalizationRegistry` cleanup runs on a scheduled fiber *after* the
+//! collection that found its target dead — never during the sweep
+//! (Invariant M4).
+
+use phalcom_core::heap::{InstanceObject, ObjRef, Object, WeakMapObject, WeakRefObject};
+use phalcom_core::value::Value;
+use phalcom_core::vm::VM;
+
+/// A VM whose heap is already collected once — see `gc.rs`'s `settled_vm`.
+fn settled_vm() -> VM {
+    let mut vm = VM::new();
+    vm.force_gc();
+    vm
+}
+
+fn alloc_instance(vm: &mut VM, slots: Vec<Value>) -> ObjRef {
+    let class = vm.universe.classes.object_class;
+    vm.heap.alloc(Object::Instance(InstanceObject {
+        class,
+        slots: slots.into_boxed_slice(),
+    }))
+}
+
+fn weak_target(vm: &VM, weak: ObjRef) -> Option<ObjRef> {
+    vm.heap.as_weak_ref(weak).expect("a live WeakRef").target
+}
+
+/// Renders the `List` of `String`s bound to global `name` in `module`.
+fn string_list_global(vm: &mut VM, module: ObjRef, name: &str) -> Vec<String> {
+    let sym = vm.interner.intern(name);
+    let list = vm.heap.module(module).get(sym).and_then(|v| v.as_obj()).expect("list global");
+    vm.heap
+        .list(list)
+        .elements()
+        .iter()
+        .map(|v| vm.heap.string(v.as_obj().expect("string element")).as_str().to_string())
+        .collect()
+}
+
+/// **A weak target is not an edge.** The referent dies when nothing else holds
+/// it, the `WeakRef` itself survives, and its target reads back as cleared.
+#[test]
+fn weak_ref_is_cleared_when_target_dies() {
+    let mut vm = settled_vm();
+    let target = alloc_instance(&mut vm, vec![]);
+    let weak = vm.heap.alloc(Object::WeakRef(WeakRefObject::new(target)));
+    vm.push_root_for_test(Value::obj(weak));
+
+    vm.force_gc();
+
+    assert!(vm.heap.try_get(target).is_none(), "a weakly-held target must be swept");
+    assert_eq!(weak_target(&vm, weak), None, "the WeakRef must read back as cleared");
+}
+
+/// **A strongly-held target survives, and its `WeakRef` keeps pointing at it.**
+#[test]
+fn weak_ref_survives_while_target_is_rooted() {
+    let mut vm = settled_vm();
+    let target = alloc_instance(&mut vm, vec![]);
+    let weak = vm.heap.alloc(Object::WeakRef(WeakRefObject::new(target)));
+    vm.push_root_for_test(Value::obj(weak));
+    vm.push_root_for_test(Value::obj(target));
+
+    vm.force_gc();
+
+    assert_eq!(weak_target(&vm, weak), Some(target));
+}
+
+/// **Ephemeron: a value that points back at its own key keeps neither alive.**
+/// A plain strong table would leak both forever; this is the case `WeakMap`
+/// exists for.
+#[test]
+fn weak_map_value_referencing_its_key_does_not_retain_it() {
+    let mut vm = settled_vm();
+    let key = alloc_instance(&mut vm, vec![]);
+    let value = alloc_instance(&mut vm, vec![Value::obj(key)]);
+    let mut table = WeakMapObject::new();
+    table.insert(key, Value::obj(value));
+    let map = vm.heap.alloc(Object::WeakMap(Box::new(table)));
+    vm.push_root_for_test(Value::obj(map));
+
+    vm.force_gc();
+
+    assert!(vm.heap.try_get(key).is_none() && vm.heap.try_get(value).is_none());
+    assert!(!vm.heap.weak_map(map).contains(key), "a dead key's entry must be dropped");
+}
+
+/// **Ephemeron fixpoint.** `k2` is reachable only through the value stored
+/// under the live `k1`, so its own value must be marked on a later pass.
+#[test]
+fn weak_map_values_are_marked_to_a_fixpoint() {
+    let mut vm = settled_vm();
+    let k1 = alloc_instance(&mut vm, vec![]);
+    let k2 = alloc_instance(&mut vm, vec![]);
+    let v1 = alloc_instance(&mut vm, vec![Value::obj(k2)]);
+    let v2 = alloc_instance(&mut vm, vec![]);
+    let mut table = WeakMapObject::new();
+    // Insert the dependent entry first so a single pass in insertion order
+    // could not get lucky.
+    table.insert(k2, Value::obj(v2));
+    table.insert(k1, Value::obj(v1));
+    let map = vm.heap.alloc(Object::WeakMap(Box::new(table)));
+    vm.push_root_for_test(Value::obj(map));
+    vm.push_root_for_test(Value::obj(k1));
+
+    vm.force_gc();
+
+    for id in [k1, k2, v1, v2] {
+        assert!(vm.heap.try_get(id).is_some(), "every object reachable through live keys must survive");
+    }
+    assert_eq!(vm.heap.weak_map(map).get(k2), Some(Value::obj(v2)));
+}
+
+/// **Cleanup is scheduled, not run, by the collector.** After `System.gc` the
+/// callback has not fired; it fires when the ready queue is pumped.
+#[test]
+fn finalization_callback_runs_on_a_scheduled_fiber() {
+    let mut vm = settled_vm();
+    let module = vm.create_module("test_weak_finalize", "weak_finalize_test");
+
+    vm.interpret_source(
+        module,
+        r#"
+        let log = List.new()
+        let registry = FinalizationRegistry.new(|held| { log.append(held) })
+        let register = || {
+            registry.register(List.new(), "first")
+            registry.register(List.new(), "second")
+            None
+        }
+        register.call()
+        System.gc
+        let during = log.size
+        System.runScheduled()
+        log.append("pumped " + during.toString)
+        "#,
+    )
+    .expect("interpret_source failed");
+
+    assert_eq!(string_list_global(&mut vm, module, "log"), ["first", "second", "pumped 0"]);
+}
+
+/// **Unregistered targets never fire**, and a live target does not either.
+#[test]
+fn unregistered_and_live_targets_do_not_fire() {
+    let mut vm = settled_vm();
+    let module = vm.create_module("test_weak_unregister", "weak_unregister_test");
+
+    vm.interpret_source(
+        module,
+        r#"
+        let log = List.new()
+        let registry = FinalizationRegistry.new(|held| { log.append(held) })
+        let token = List.new()
+        let kept = List.new()
+        let register = || {
+            registry.register(List.new(), "unregistered", token: token)
+            registry.register(kept, "kept")
+            registry.register(List.new(), "collected")
+            None
+        }
+        register.call()
+        registry.unregister(token)
+        System.gc
+        System.runScheduled()
+        "#,
+    )
+    .expect("interpret_source failed");
+
+    assert_eq!(string_list_global(&mut vm, module, "log"), ["collected"]);
+}
+
+/// **Under a collection at every safepoint**, weak containers stay coherent:
+/// live keys and targets are never cleared mid-program and every dead target
+/// is cleaned up exactly once.
+#[test]
+fn weak_containers_hold_under_gc_stress() {
+    let mut vm = settled_vm();
+    vm.heap.set_gc_stress_interval_for_test(Some(1));
+    let module = vm.create_module("test_weak_stress", "weak_stress_test");
+
+    vm.interpret_source(
+        module,
+        r#"
+        let log = List.new()
+        let registry = FinalizationRegistry.new(|held| { log.append(held) })
+        let table = WeakMap.new()
+        let anchor = List.new()
+        let anchorRef = WeakRef.new(anchor)
+        table[anchor] = "anchored"
+        let churn = |n| {
+            let garbage = List.new()
+            table[garbage] = "transient"
+            registry.register(garbage, "g" + n.toString)
+            None
+        }
+        let i = 0
+        while (i < 20) {
+            churn.call(i)
+            i = i + 1
+        }
+        System.gc
+        System.runScheduled()
+        log.append(table[anchor])
+        log.append(anchorRef.deref.isSome.toString)
+        "#,
+    )
+    .expect("interpret_source failed");
+    vm.heap.set_gc_stress_interval_for_test(None);
+
+    let log = string_list_global(&mut vm, module, "log");
+    let (cleanups, tail) = log.split_at(log.len() - 2);
+    assert_eq!(tail, ["anchored", "true"]);
+    let mut cleanups = cleanups.to_vec();
+    cleanups.sort();
+    let mut expected: Vec<String> = (0..20).map(|i| format!("g{i}")).collect();
+    expected.sort();
+    assert_eq!(cleanups, expected, "every dead target must be cleaned up exactly once");
+}
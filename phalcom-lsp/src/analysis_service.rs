@@ -1040,18 +1040,26 @@ fn run_static_workspace_analysis(
             source_text.insert(loc.source_id.clone(), text.clone());
         }
 
+        // Filter open documents by their project's features exactly as
+        // `ModuleResolver::load_parsed` filters the ones read from disk. An
+        // invalid `@cfg` leaves the document unfiltered.
+        let mut filtered = program.clone();
+        let program = match universe.cfg_context(sem_mod_id.project).filter_program(&sem_mod_id.path, &mut filtered) {
+            Ok(()) => filtered,
+            Err(_) => program.clone(),
+        };
+        if let Ok(interface) = phalcom_modules::InterfaceBuilder::build(sem_mod_id.clone(), kind, &program) {
+            interfaces.insert(sem_mod_id.clone(), interface);
+        }
+
         let parsed_unit = Arc::new(phalcom_modules::source::ParsedModuleUnit::new(
             sem_mod_id.clone(),
             kind,
             loc,
             text.clone(),
-            Arc::new(program.clone()),
+            Arc::new(program),
         ));
         sources.insert(sem_mod_id.clone(), parsed_unit);
-
-        if let Ok(interface) = phalcom_modules::InterfaceBuilder::build(sem_mod_id.clone(), kind, program) {
-            interfaces.insert(sem_mod_id.clone(), interface);
-        }
     }
 
     if sources.is_empty() {
@@ -1178,6 +1186,7 @@ fn builtin_module_from_uri(uri: &Url) -> Option<phalcom_modules::ModuleId> {
     let project = match uri.host_str()? {
         "universe" => phalcom_modules::BuiltinProject::Universe,
         "std" => phalcom_modules::BuiltinProject::Std,
+        "native" => phalcom_modules::BuiltinProject::Native,
         _ => return None,
     };
     let components = uri
@@ -745,7 +745,7 @@ impl Backend {
                     })
                 };
                 Some(Hover {
-                    contents: markdown_contents(hover::render_class_hover(&class.id, class.superclass.as_ref(), phaldoc.as_ref())),
+                    contents: markdown_contents(hover::render_class_hover(class, phaldoc.as_ref())),
                     range: Some(span),
                 })
             }
@@ -942,7 +942,7 @@ impl Backend {
                 }
             }
             if sites.is_empty() {
-                if let Some(native) = phalcom_native_surface::NATIVE_MEMBERS.iter().find(|member| member.selector == selector) {
+                if let Some(native) = phalcom_native_surface::native_members().find(|member| member.selector == selector) {
                     let kind = match native.kind {
                         phalcom_native_surface::NativeMemberKind::Getter => crate::index::MemberKind::Getter,
                         phalcom_native_surface::NativeMemberKind::Setter => crate::index::MemberKind::Setter,
@@ -1018,9 +1018,12 @@ fn statement_has_binding(statement: &phalcom_ast::ast::Statement, name: &str) ->
             matches!(&binding.pattern, phalcom_ast::ast::Pattern::Name { name: binding_name, .. } if binding_name == name)
         }
         phalcom_ast::ast::Statement::For(for_statement) => for_statement.body.iter().any(|statement| statement_has_binding(statement, name)),
-        phalcom_ast::ast::Statement::Expr { expr, .. } | phalcom_ast::ast::Statement::Throw { expr, .. } => expr_has_binding(expr, name),
+        phalcom_ast::ast::Statement::Expr { expr, .. } | phalcom_ast::ast::Statement::Throw { expr, .. } | phalcom_ast::ast::Statement::Yield { expr, .. } => {
+            expr_has_binding(expr, name)
+        }
         phalcom_ast::ast::Statement::Return(_)
         | phalcom_ast::ast::Statement::Class(_)
+        | phalcom_ast::ast::Statement::Extend(_)
         | phalcom_ast::ast::Statement::Export(_)
         | phalcom_ast::ast::Statement::Break { .. }
         | phalcom_ast::ast::Statement::Continue { .. } => false,
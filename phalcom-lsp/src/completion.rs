@@ -5,7 +5,7 @@
 
 use phalcom_ast::ast::{ClassMember, Expr, MethodDef, PackItem, Pattern, Program, Statement};
 use phalcom_common::range::SourceRange;
-use phalcom_native_surface::{NATIVE_MEMBERS, NativeDispatch, NativeMemberKind, NativeVisibility};
+use phalcom_native_surface::{NativeDispatch, NativeMemberKind, NativeVisibility, native_members};
 use tower_lsp::lsp_types::{CompletionItem, CompletionItemKind, InsertTextFormat, Position, Url};
 
 use crate::documents::{Document, DocumentSnapshot};
@@ -440,8 +440,7 @@ fn shallow_union_items(candidates: Vec<Vec<CompletionItem>>) -> Vec<CompletionIt
 }
 
 fn native_object_items() -> Vec<CompletionItem> {
-    NATIVE_MEMBERS
-        .iter()
+    native_members()
         .filter(|member| member.class == "Object" && member.side == NativeDispatch::Instance && member.visibility == NativeVisibility::Public)
         .map(|member| {
             let kind = match member.kind {
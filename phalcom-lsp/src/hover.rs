@@ -37,7 +37,7 @@ use phalcom_ast::token::Token;
 
 use crate::line_index::LineIndex;
 use crate::selectors::class_member_selector;
-use crate::semantic::{BindingInfo, ClassId, Confidence, InferredValue, SemanticBindingKind, ValueShape};
+use crate::semantic::{BindingInfo, ClassId, ClassSurface, Confidence, InferredValue, SemanticBindingKind, ValueShape};
 
 /// The contextual (non-keyword-token) words that carry their own hover blurb
 /// only by convention, not by reserved-word status: they lex as
@@ -92,6 +92,14 @@ const KEYWORD_DOCS: &[(&str, &str)] = &[
         "Cleanup clause of a `try` statement, sugar for `.ensure{ ... }`, always runs; a contextual keyword, reserved only inside `try`-clauses.",
     ),
     ("throw", "Prefix statement/expression that raises an `Error` value — sugar for `expr.raise()`."),
+    (
+        "yield",
+        "Statement that hands a value out of a `@generator` member or generator block and suspends it until the next value is asked for — sugar for `Fiber.yield(expr)`.",
+    ),
+    (
+        "await",
+        "Prefix operator that parks the current fiber until a `Future` settles, answering its value or raising its error — sugar for `expr.await`.",
+    ),
     (
         "break",
         "Loop-control keyword: leaves the enclosing `for`/`while` loop immediately (lowers to a direct jump, not a block send).",
@@ -197,6 +205,8 @@ fn keyword_spelling(token: &Token) -> Option<&'static str> {
         Token::Construct => Some("construct"),
         Token::Throw => Some("throw"),
         Token::Try => Some("try"),
+        Token::Yield => Some("yield"),
+        Token::Await => Some("await"),
         Token::Identifier(name) => CONTEXTUAL_WORDS.iter().find(|word| **word == name.as_str()).copied(),
         _ => None,
     }
@@ -655,11 +665,25 @@ pub fn render_keyword_hover(word: &str, blurb: &str) -> String {
 }
 
 /// Renders a class declaration/reference and its declaration-identity docs.
-pub fn render_class_hover(class: &ClassId, superclass: Option<&ClassId>, phaldoc: Option<&PhaldocDoc>) -> String {
-    let header = match superclass {
-        Some(superclass) => format!("`{}` — class, is {}", class.name, superclass.name),
-        None => format!("`{}` — class", class.name),
+/// The header names the superclass and, when present, the composed mixins;
+/// a `@mixin` or `@protocol` declaration is labelled as such rather than a
+/// class, and a protocol lists the protocols it includes.
+pub fn render_class_hover(class: &ClassSurface, phaldoc: Option<&PhaldocDoc>) -> String {
+    let mut header = if class.is_protocol {
+        format!("`{}` — protocol", class.id.name)
+    } else if class.is_mixin {
+        format!("`{}` — mixin", class.id.name)
+    } else {
+        match &class.superclass {
+            Some(superclass) => format!("`{}` — class, is {}", class.id.name, superclass.name),
+            None => format!("`{}` — class", class.id.name),
+        }
     };
+    if !class.mixins.is_empty() {
+        let names: Vec<&str> = class.mixins.iter().map(|mixin| mixin.name.as_str()).collect();
+        let verb = if class.is_protocol { "includes" } else { "composes" };
+        header.push_str(&format!(", {verb} {}", names.join(", ")));
+    }
     let mut sections = vec![header];
     if let Some(doc) = phaldoc {
         if !doc.summary.is_empty() {
@@ -864,6 +888,8 @@ mod tests {
             "construct",
             "throw",
             "try",
+            "yield",
+            "await",
             "on",
             "catch",
             "ensure",
@@ -1062,4 +1088,28 @@ mod tests {
         let rendered = render_selector_hover("ping()", &sites, None).unwrap();
         assert!(rendered.contains("User (a.ph), User (b.ph)"), "{rendered:?}");
     }
+
+    #[test]
+    fn render_class_hover_labels_mixins_and_composition() {
+        let src = "@mixin\nclass Greeter {\n  greet() { \"hi\" }\n}\n\n@compose(Greeter)\nclass Host {\n}\n";
+        let (program, _) = parsed(src);
+        let module = crate::semantic::ModuleId::new("file:///host.ph");
+        let surface = crate::semantic::build_module_surface(module.clone(), &program);
+        let greeter = &surface.classes[&ClassId::new(module.clone(), "Greeter")];
+        let host = &surface.classes[&ClassId::new(module, "Host")];
+        assert_eq!(render_class_hover(greeter, None), "`Greeter` — mixin");
+        assert_eq!(render_class_hover(host, None), "`Host` — class, composes Greeter");
+    }
+
+    #[test]
+    fn render_class_hover_labels_protocols_and_inclusion() {
+        let src = "@protocol\nclass Sized {\n  size -> Int {}\n}\n\n@protocol(Sized)\nclass Container {\n  contains(item) -> Bool {}\n}\n";
+        let (program, _) = parsed(src);
+        let module = crate::semantic::ModuleId::new("file:///container.ph");
+        let surface = crate::semantic::build_module_surface(module.clone(), &program);
+        let sized = &surface.classes[&ClassId::new(module.clone(), "Sized")];
+        let container = &surface.classes[&ClassId::new(module, "Container")];
+        assert_eq!(render_class_hover(sized, None), "`Sized` — protocol");
+        assert_eq!(render_class_hover(container, None), "`Container` — protocol, includes Sized");
+    }
 }
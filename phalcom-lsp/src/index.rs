@@ -631,8 +631,8 @@ fn collect_var_occurrences(statement: &Statement, names: &std::collections::Hash
                 collect_var_occurrences(s, names, out);
             }
         }
-        Statement::Throw { expr, .. } => collect_var_occurrences_in_expr(expr, names, out),
-        Statement::Break { .. } | Statement::Continue { .. } | Statement::Export(_) | Statement::Class(_) => {}
+        Statement::Throw { expr, .. } | Statement::Yield { expr, .. } => collect_var_occurrences_in_expr(expr, names, out),
+        Statement::Break { .. } | Statement::Continue { .. } | Statement::Export(_) | Statement::Class(_) | Statement::Extend(_) => {}
     }
 }
 
@@ -835,6 +835,21 @@ impl Collector {
     fn walk_statement(&mut self, statement: &Statement) {
         match statement {
             Statement::Class(class_def) => self.walk_class(class_def),
+            // Extension members belong to a class declared elsewhere; only
+            // their bodies are walked here.
+            Statement::Extend(extend_def) => {
+                for member in &extend_def.members {
+                    let body = match member {
+                        ClassMember::Method(m) => &m.body,
+                        ClassMember::Getter(g) => &g.body,
+                        ClassMember::Setter(s) => &s.body,
+                        _ => continue,
+                    };
+                    for statement in body {
+                        self.walk_statement(statement);
+                    }
+                }
+            }
             Statement::Let(binding) => {
                 self.walk_pattern(&binding.pattern);
                 if let Some(value) = &binding.value {
@@ -849,7 +864,7 @@ impl Collector {
             Statement::Expr { expr, .. } => self.walk_expr(expr),
             Statement::For(f) => self.walk_for(f),
             Statement::Break { .. } | Statement::Continue { .. } => {}
-            Statement::Throw { expr, .. } => self.walk_expr(expr),
+            Statement::Throw { expr, .. } | Statement::Yield { expr, .. } => self.walk_expr(expr),
             Statement::Export(_) => {}
         }
     }
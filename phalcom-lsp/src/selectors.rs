@@ -211,6 +211,7 @@ pub(crate) fn unary_selector_name(op: &UnaryOp) -> &'static str {
         UnaryOp::Minus => "-",
         UnaryOp::Not => "not",
         UnaryOp::BitNot => "~",
+        UnaryOp::Await => "await",
     }
 }
 
@@ -849,7 +849,7 @@ fn analyze_statement(statement: &phalcom_ast::ast::Statement, context: &Analysis
                 let _ = analyze_expr(expr, context);
             }
         }
-        phalcom_ast::ast::Statement::Expr { expr, .. } | phalcom_ast::ast::Statement::Throw { expr, .. } => {
+        phalcom_ast::ast::Statement::Expr { expr, .. } | phalcom_ast::ast::Statement::Throw { expr, .. } | phalcom_ast::ast::Statement::Yield { expr, .. } => {
             let _ = analyze_expr(expr, context);
         }
         phalcom_ast::ast::Statement::For(for_statement) => {
@@ -861,6 +861,7 @@ fn analyze_statement(statement: &phalcom_ast::ast::Statement, context: &Analysis
             }
         }
         phalcom_ast::ast::Statement::Class(_)
+        | phalcom_ast::ast::Statement::Extend(_)
         | phalcom_ast::ast::Statement::Break { .. }
         | phalcom_ast::ast::Statement::Continue { .. }
         | phalcom_ast::ast::Statement::Export(_) => {}
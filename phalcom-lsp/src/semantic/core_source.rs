@@ -7,10 +7,13 @@
 
 use phalcom_ast::ast::Program;
 use phalcom_ast::parser::Parse;
-use phalcom_native_surface::{NATIVE_CLASSES, NATIVE_MEMBERS, NativeDispatch, NativeMemberKind, NativeVisibility};
+use phalcom_native_surface::{
+    NATIVE_CLASSES, NATIVE_MEMBERS, NativeClass, NativeDispatch, NativeMember, NativeMemberKind, NativeVisibility, extension_classes, extension_members,
+    extension_modules,
+};
 
-use super::ids::{CORE_MODULE_URI, ClassId, DispatchSide, ModuleId};
-use super::surface::{ClassSurface, MemberKind, MemberSurface, MemberVisibility, ModuleSurface, build_module_surface};
+use super::ids::{CORE_MODULE_URI, ClassId, DispatchSide, ModuleId, SemanticModuleId};
+use super::surface::{ClassSurface, ExportSurface, MemberKind, MemberSurface, MemberVisibility, ModuleSurface, build_module_surface};
 
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -107,24 +110,88 @@ pub fn bundled_parse() -> Parse {
     phalcom_ast::parser::parse(BUNDLED_CORE_SOURCE, 0)
 }
 
-/// Builds the core surface from source and the canonical native declarations.
+/// Builds the core surface from source and the kernel's native declarations.
+///
+/// Extension classes are not part of it: each extension module has a surface
+/// of its own ([`build_extension_surfaces`]), visible only where imported.
 pub fn build_core_surface(program: &Program) -> ModuleSurface {
     let module = ModuleId::new(CORE_MODULE_URI);
     let mut source = build_module_surface(module.clone(), program);
-    for native_class in NATIVE_CLASSES {
-        let class_id = ClassId::new(module.clone(), native_class.name);
+    add_native_classes(&mut source, NATIVE_CLASSES.iter().copied());
+    add_native_members(&mut source, NATIVE_MEMBERS.iter().copied());
+    source
+}
+
+/// The LSP key of extension module `path` (`"acme.crypto"`): the semantic
+/// identity [`SemanticModuleId::native`] gives it, as documents key theirs.
+pub fn extension_module_id(path: &str) -> ModuleId {
+    ModuleId::new(SemanticModuleId::native(path).to_string())
+}
+
+/// The extension module an absolute import path names, if one is linked.
+pub fn extension_module_for_import(import: &str) -> Option<ModuleId> {
+    extension_modules().contains(&import).then(|| extension_module_id(import))
+}
+
+/// Builds one surface per extension module linked into the binary, keyed by
+/// [`extension_module_id`]. Every class of a module is an export of it.
+pub fn build_extension_surfaces() -> Vec<ModuleSurface> {
+    extension_modules()
+        .into_iter()
+        .map(|path| {
+            let mut surface = ModuleSurface {
+                module: extension_module_id(path),
+                exports: Default::default(),
+                imports: Default::default(),
+                exposed_children: Default::default(),
+                metadata: Default::default(),
+                classes: Default::default(),
+            };
+            let classes = extension_classes(path);
+            for class in &classes {
+                surface.exports.insert(
+                    class.name.to_string(),
+                    ExportSurface {
+                        public_name: class.name.to_string(),
+                        local_name: class.name.to_string(),
+                        range: Default::default(),
+                    },
+                );
+            }
+            add_native_classes(&mut surface, classes.into_iter());
+            add_native_members(&mut surface, extension_members(path).into_iter());
+            surface
+        })
+        .collect()
+}
+
+/// Adds a class surface for every native class `source` does not declare.
+/// Superclasses are always kernel classes, so they resolve in core.
+fn add_native_classes(source: &mut ModuleSurface, classes: impl Iterator<Item = NativeClass>) {
+    let core = ModuleId::new(CORE_MODULE_URI);
+    for native_class in classes {
+        let class_id = ClassId::new(source.module.clone(), native_class.name);
         source.classes.entry(class_id.clone()).or_insert_with(|| ClassSurface {
             id: class_id.clone(),
-            superclass: native_class.superclass.map(|name| ClassId::new(module.clone(), name)),
+            superclass: native_class.superclass.map(|name| ClassId::new(core.clone(), name)),
             superclass_reference: None,
+            is_mixin: false,
+            is_protocol: false,
+            mixins: Vec::new(),
+            variants: Vec::new(),
             members: Default::default(),
             fields: Default::default(),
             source_range: Default::default(),
             name_range: Default::default(),
         });
     }
-    for native in NATIVE_MEMBERS {
-        let class_id = ClassId::new(module.clone(), native.class);
+}
+
+/// Adds every native member whose class is in `source` and whose selector the
+/// source does not already declare.
+fn add_native_members(source: &mut ModuleSurface, members: impl Iterator<Item = NativeMember>) {
+    for native in members {
+        let class_id = ClassId::new(source.module.clone(), native.class);
         let Some(class) = source.classes.get_mut(&class_id) else {
             continue;
         };
@@ -172,7 +239,6 @@ pub fn build_core_surface(program: &Program) -> ModuleSurface {
             DispatchSide::Class => members.class = Some(member),
         }
     }
-    source
 }
 
 #[cfg(test)]
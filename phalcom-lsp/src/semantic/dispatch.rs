@@ -98,21 +98,8 @@ impl<'a, T: ClassTable + ?Sized> DispatchResolver<'a, T> {
 
     /// Resolves `selector` and returns the actual declaration owner.
     pub(crate) fn resolve(&self, receiver: &DispatchReceiver, selector: &str) -> Option<ResolvedDispatch> {
-        let (receiver_class, side, start) = match receiver {
-            DispatchReceiver::Instance(class) => (class.clone(), DispatchSide::Instance, Some(class.clone())),
-            DispatchReceiver::ClassObject(class) => (class.clone(), DispatchSide::Class, Some(class.clone())),
-            DispatchReceiver::Super { lexical_class, side } => {
-                let start = self.superclass_of(lexical_class);
-                (lexical_class.clone(), *side, start)
-            }
-        };
-
-        let mut current = start;
-        let mut visited = BTreeSet::new();
-        while let Some(class) = current {
-            if !visited.insert(class.clone()) {
-                return None;
-            }
+        let (receiver_class, side, order) = self.lookup_order(receiver);
+        for class in order {
             let surface = self.classes.class(&class)?;
             if let Some(member) = surface.member(selector, side) {
                 return Some(ResolvedDispatch {
@@ -121,33 +108,19 @@ impl<'a, T: ClassTable + ?Sized> DispatchResolver<'a, T> {
                     side,
                 });
             }
-            current = self.superclass_of(&class);
         }
         None
     }
 
     /// Captures an effective immutable snapshot of a method family starting from `receiver`.
     pub(crate) fn capture_method_family(&self, receiver: &DispatchReceiver, pattern: &SelectorPattern) -> CapturedMethodFamilyShape {
-        let (source_behavior, side, start) = match receiver {
-            DispatchReceiver::Instance(class) => (class.clone(), DispatchSide::Instance, Some(class.clone())),
-            DispatchReceiver::ClassObject(class) => (class.clone(), DispatchSide::Class, Some(class.clone())),
-            DispatchReceiver::Super { lexical_class, side } => {
-                let start = self.superclass_of(lexical_class);
-                (lexical_class.clone(), *side, start)
-            }
-        };
+        let (source_behavior, side, order) = self.lookup_order(receiver);
 
         let mut exact = Vec::new();
         let mut rest = Vec::new();
         let mut seen_exact = BTreeSet::new();
 
-        let mut current = start;
-        let mut visited = BTreeSet::new();
-
-        while let Some(class) = current {
-            if !visited.insert(class.clone()) {
-                break;
-            }
+        for class in order {
             let Some(surface) = self.classes.class(&class) else {
                 break;
             };
@@ -173,8 +146,6 @@ impl<'a, T: ClassTable + ?Sized> DispatchResolver<'a, T> {
                     }
                 }
             }
-
-            current = self.superclass_of(&class);
         }
 
         // Sort exact deterministically by canonical selector encoding
@@ -188,6 +159,61 @@ impl<'a, T: ClassTable + ?Sized> DispatchResolver<'a, T> {
         }
     }
 
+    /// Returns the receiver's class, dispatch side and the classes a send
+    /// searches, in order. The instance side follows the mixin
+    /// linearization — each class of the superclass chain, then the mixins
+    /// it applies itself, each once — and `super` continues it after the
+    /// lexical class.
+    fn lookup_order(&self, receiver: &DispatchReceiver) -> (ClassId, DispatchSide, Vec<ClassId>) {
+        match receiver {
+            DispatchReceiver::Instance(class) => (class.clone(), DispatchSide::Instance, self.linearization(class, DispatchSide::Instance)),
+            DispatchReceiver::ClassObject(class) => (class.clone(), DispatchSide::Class, self.linearization(class, DispatchSide::Class)),
+            DispatchReceiver::Super { lexical_class, side } => {
+                let mut order = self.linearization(lexical_class, *side);
+                order.remove(0);
+                (lexical_class.clone(), *side, order)
+            }
+        }
+    }
+
+    fn linearization(&self, class: &ClassId, side: DispatchSide) -> Vec<ClassId> {
+        let mut chain = vec![class.clone()];
+        while let Some(parent) = self.superclass_of(chain.last().expect("chain is non-empty")) {
+            if chain.contains(&parent) {
+                break;
+            }
+            chain.push(parent);
+        }
+        if side == DispatchSide::Class {
+            return chain;
+        }
+        let mut applied: Vec<ClassId> = Vec::new();
+        let mut per_class = Vec::with_capacity(chain.len());
+        for class in chain.iter().rev() {
+            let mut own = Vec::new();
+            let mut pending: Vec<ClassId> = self.mixins_of(class).into_iter().rev().collect();
+            while let Some(mixin) = pending.pop() {
+                if applied.contains(&mixin) || own.contains(&mixin) {
+                    continue;
+                }
+                pending.extend(self.mixins_of(&mixin).into_iter().rev());
+                own.push(mixin);
+            }
+            applied.extend(own.iter().cloned());
+            per_class.push(own);
+        }
+        let mut order = Vec::new();
+        for (class, own) in chain.into_iter().zip(per_class.into_iter().rev()) {
+            order.push(class);
+            order.extend(own);
+        }
+        order
+    }
+
+    fn mixins_of(&self, class: &ClassId) -> Vec<ClassId> {
+        self.classes.class(class).map(|surface| surface.mixins.clone()).unwrap_or_default()
+    }
+
     fn superclass_of(&self, class: &ClassId) -> Option<ClassId> {
         let surface = self.classes.class(class)?;
         surface
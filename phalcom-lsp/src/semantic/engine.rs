@@ -208,6 +208,13 @@ impl SemanticEngine {
                 precise_body_frontier = false;
                 Arc::make_mut(&mut self.state.classes).retain(|id, _| id.module != module);
                 Arc::make_mut(&mut self.state.classes).extend(source.surface.classes.iter().map(|(id, class)| (id.clone(), Arc::new(class.clone()))));
+                if module.as_str() == CORE_MODULE_URI {
+                    // Extension modules have no source file; their link-time
+                    // surfaces arrive with core, which their classes extend.
+                    for extension in core_source::build_extension_surfaces() {
+                        Arc::make_mut(&mut self.state.classes).extend(extension.classes.into_iter().map(|(id, class)| (id, Arc::new(class))));
+                    }
+                }
             }
             affected.insert(module.clone());
             if delta.kind == SourceChangeKind::BodyOnly {
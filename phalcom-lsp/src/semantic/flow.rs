@@ -3,8 +3,8 @@
 use std::collections::{BTreeMap, BTreeSet};
 
 use phalcom_ast::ast::{
-    BinaryOp, BlockExpr, Expr, ListLiteralElement, MapLiteralEntry, MapLiteralKey, PackItem, PackLabel, Pattern, RelationOp, SetLiteralEntry, Statement,
-    TupleLiteralEntry, UnaryOp,
+    BinaryOp, BlockExpr, Expr, IfLetExpr, ListLiteralElement, MapLiteralEntry, MapLiteralKey, PackItem, PackLabel, Pattern, RelationOp, SetLiteralEntry,
+    Statement, TupleLiteralEntry, UnaryOp,
 };
 use phalcom_common::range::SourceRange;
 use phalcom_common::selector::SelectorPattern;
@@ -189,6 +189,8 @@ pub struct SolverContext<'ctx> {
     pub member_surface: &'ctx dyn Fn(&CallableId) -> Option<MemberSurface>,
     /// Tests trusted nominal type-test membership against the current class universe.
     pub is_same_or_subclass: &'ctx dyn Fn(&ClassId, &ClassId) -> bool,
+    /// Lists the closed variant set of a `@sealed` class; empty for any other class.
+    pub sealed_variants: &'ctx dyn Fn(&ClassId) -> Vec<ClassId>,
 }
 
 /// Runs one structured pass over a module and all source members.
@@ -241,6 +243,7 @@ fn analyze_surface_for_callable(
         callable_effects: context.callable_effects,
         member_surface: context.member_surface,
         is_same_or_subclass: context.is_same_or_subclass,
+        sealed_variants: context.sealed_variants,
         local_facts: LocalFacts::default(),
         field_facts: FieldFacts::default(),
         parameter_facts: ParameterFacts::default(),
@@ -394,6 +397,7 @@ struct FlowAnalyzer<'ctx> {
     callable_effects: &'ctx dyn Fn(&CallableId) -> Option<super::callable::SummaryEffects>,
     member_surface: &'ctx dyn Fn(&CallableId) -> Option<MemberSurface>,
     is_same_or_subclass: &'ctx dyn Fn(&ClassId, &ClassId) -> bool,
+    sealed_variants: &'ctx dyn Fn(&ClassId) -> Vec<ClassId>,
     local_facts: LocalFacts,
     field_facts: FieldFacts,
     parameter_facts: ParameterFacts,
@@ -650,6 +654,13 @@ impl FlowAnalyzer<'_> {
                     ..StatementFlow::default()
                 }
             }
+            Statement::Yield { expr, .. } => {
+                self.eval(expr, state, current_class, side);
+                StatementFlow {
+                    normal: Some(state.clone()),
+                    ..StatementFlow::default()
+                }
+            }
             Statement::Break { .. } => StatementFlow {
                 normal: None,
                 breaks: vec![state.clone()],
@@ -660,7 +671,7 @@ impl FlowAnalyzer<'_> {
                 continues: vec![state.clone()],
                 ..StatementFlow::default()
             },
-            Statement::Class(_) | Statement::Export(_) => StatementFlow {
+            Statement::Class(_) | Statement::Extend(_) | Statement::Export(_) => StatementFlow {
                 normal: Some(state.clone()),
                 ..StatementFlow::default()
             },
@@ -758,6 +769,25 @@ impl FlowAnalyzer<'_> {
                 self.collect_events(&binary.left, state, current_class, side);
                 Some(self.analyze_if(state, None, None, None, Some(block), current_class, side, target))
             }
+            Expr::IfLet(if_let) => {
+                let value = self.eval(&if_let.value, state, current_class, side);
+                let then_state = self.refine_variant_pattern_state(if_let, state, true).map(|mut refined| {
+                    self.bind_pattern(&if_let.pattern, &value, &mut refined);
+                    refined
+                });
+                let else_state = self.refine_variant_pattern_state(if_let, state, false);
+                Some(self.analyze_branches(
+                    state,
+                    then_state,
+                    else_state,
+                    None,
+                    Some(&if_let.then_body),
+                    if_let.else_body.as_ref(),
+                    current_class,
+                    side,
+                    target,
+                ))
+            }
             _ => None,
         }
     }
@@ -780,6 +810,34 @@ impl FlowAnalyzer<'_> {
         let else_state = condition
             .map(|condition| self.refine_condition_state(condition, state, false, current_class, side))
             .unwrap_or_else(|| Some(state.clone()));
+        self.analyze_branches(
+            state,
+            then_state,
+            else_state,
+            condition_truth,
+            then_block,
+            else_block,
+            current_class,
+            side,
+            target,
+        )
+    }
+
+    /// Analyzes the arms of a conditional from the states each arm is entered
+    /// with; `None` marks an arm the condition rules out.
+    #[allow(clippy::too_many_arguments)]
+    fn analyze_branches(
+        &mut self,
+        state: &mut FlowState,
+        then_state: Option<FlowState>,
+        else_state: Option<FlowState>,
+        condition_truth: Option<bool>,
+        then_block: Option<&BlockExpr>,
+        else_block: Option<&BlockExpr>,
+        current_class: Option<&ClassId>,
+        side: Option<DispatchSide>,
+        target: Option<&CallableId>,
+    ) -> StatementFlow {
         let then_flow = if condition_truth != Some(false) {
             then_state
                 .as_ref()
@@ -821,6 +879,11 @@ impl FlowAnalyzer<'_> {
                 (None, None) => Some(state.clone()),
             },
         };
+        for (entry, block) in [(&then_state, then_block), (&else_state, else_block)] {
+            if let (Some(entry), Some(block)) = (entry, block) {
+                self.record_branch_facts(state, entry, block, normal.as_ref());
+            }
+        }
         let mut returns = Vec::new();
         let mut breaks = Vec::new();
         let mut continues = Vec::new();
@@ -890,12 +953,52 @@ impl FlowAnalyzer<'_> {
         let Some(current) = state.bindings.get(&binding) else {
             return Some(state.clone());
         };
-        let shape = narrow_type_test_shape(&current.shape, &target, is_exact, truth, self.is_same_or_subclass)?;
+        let shape = narrow_type_test_shape(&current.shape, &target, is_exact, truth, self.is_same_or_subclass, self.sealed_variants)?;
         let mut refined = state.clone();
         refined.bindings.insert(binding, InferredValue::flow(shape, condition.range()));
         Some(refined)
     }
 
+    /// Refines the scrutinee of `if let Variant(...) = name` like an exact
+    /// `name is! Variant` test.
+    fn refine_variant_pattern_state(&self, if_let: &IfLetExpr, state: &FlowState, truth: bool) -> Option<FlowState> {
+        let Pattern::Variant { constructor, .. } = &if_let.pattern else {
+            return Some(state.clone());
+        };
+        let Expr::Var {
+            value: name,
+            range: name_range,
+        } = &if_let.value
+        else {
+            return Some(state.clone());
+        };
+        let (Some(binding), Some(target)) = (self.binding_for_name(name, *name_range, state), (self.known_class)(constructor)) else {
+            return Some(state.clone());
+        };
+        let Some(current) = state.bindings.get(&binding) else {
+            return Some(state.clone());
+        };
+        let shape = narrow_type_test_shape(&current.shape, &target, true, truth, self.is_same_or_subclass, self.sealed_variants)?;
+        let mut refined = state.clone();
+        refined.bindings.insert(binding, InferredValue::flow(shape, if_let.pattern.range()));
+        Some(refined)
+    }
+
+    /// Records the bindings a branch entry narrowed as facts scoped to its
+    /// block, so hover inside the block reads the narrowed value, and the
+    /// value after the branch from the block's end onwards.
+    fn record_branch_facts(&mut self, entry: &FlowState, branch: &FlowState, block: &BlockExpr, after: Option<&FlowState>) {
+        for (binding, value) in &branch.bindings {
+            let Some(outer) = entry.bindings.get(binding) else { continue };
+            if outer == value {
+                continue;
+            }
+            self.local_facts.record(*binding, block.range, value.clone());
+            let restored = after.and_then(|after| after.bindings.get(binding)).unwrap_or(outer);
+            self.local_facts.record(*binding, (block.range.end..block.range.end).into(), restored.clone());
+        }
+    }
+
     fn analyze_while(
         &mut self,
         state: &mut FlowState,
@@ -1849,24 +1952,27 @@ fn narrow_type_test_shape(
     is_exact: bool,
     truth: bool,
     is_same_or_subclass: &dyn Fn(&ClassId, &ClassId) -> bool,
+    sealed_variants: &dyn Fn(&ClassId) -> Vec<ClassId>,
 ) -> Option<ValueShape> {
     let matches = |class: &ClassId| {
         if is_exact { class == target } else { is_same_or_subclass(class, target) }
     };
-    match shape {
+    // A wider class tested to be `target` is known to be at least `target`.
+    let narrow = |alternative: &ValueShape| match alternative {
+        ValueShape::Instance(class) if matches(class) == truth => Some(alternative.clone()),
+        ValueShape::Instance(class) if truth && is_same_or_subclass(target, class) => Some(ValueShape::Instance(target.clone())),
+        ValueShape::Instance(_) => None,
+        _ => Some(alternative.clone()),
+    };
+    match expand_sealed_shape(shape, sealed_variants, 0) {
         ValueShape::Unknown if truth => Some(ValueShape::Instance(target.clone())),
         ValueShape::Unknown => Some(ValueShape::Unknown),
-        ValueShape::Instance(class) if matches(class) == truth => Some(shape.clone()),
-        ValueShape::Instance(_) => None,
+        expanded @ ValueShape::Instance(_) => narrow(&expanded),
         ValueShape::Union(alternatives) => {
-            let retained = alternatives
-                .iter()
-                .filter(|alternative| match alternative {
-                    ValueShape::Instance(class) => matches(class) == truth,
-                    _ => true,
-                })
-                .cloned()
-                .collect::<Vec<_>>();
+            let retained = alternatives.iter().filter_map(narrow).collect::<Vec<_>>();
+            if retained == alternatives {
+                return Some(shape.clone());
+            }
             (!retained.is_empty()).then(|| ValueShape::bounded_union(retained))
         }
         _ if truth => None,
@@ -1874,6 +1980,32 @@ fn narrow_type_test_shape(
     }
 }
 
+/// Deepest chain of sealed classes whose variants are themselves sealed that
+/// type-test narrowing expands.
+const MAX_SEALED_DEPTH: usize = 16;
+
+/// Replaces every instance of a `@sealed` class in `shape` by the union of
+/// its variants' instances.
+fn expand_sealed_shape(shape: &ValueShape, sealed_variants: &dyn Fn(&ClassId) -> Vec<ClassId>, depth: usize) -> ValueShape {
+    match shape {
+        ValueShape::Instance(class) if depth < MAX_SEALED_DEPTH => {
+            let variants = sealed_variants(class);
+            if variants.is_empty() {
+                return shape.clone();
+            }
+            ValueShape::bounded_union(
+                variants
+                    .into_iter()
+                    .map(|variant| expand_sealed_shape(&ValueShape::Instance(variant), sealed_variants, depth + 1)),
+            )
+        }
+        ValueShape::Union(alternatives) => {
+            ValueShape::bounded_union(alternatives.iter().map(|alternative| expand_sealed_shape(alternative, sealed_variants, depth)))
+        }
+        _ => shape.clone(),
+    }
+}
+
 fn receiver_targets(expr: &Expr, shape: &ValueShape, current_class: Option<&ClassId>, side: Option<DispatchSide>) -> Vec<DispatchReceiver> {
     if matches!(expr, Expr::SuperVar { .. }) {
         return current_class
@@ -105,6 +105,7 @@ pub(crate) fn analyze_source(
     let family_resolver = |receiver: &DispatchReceiver, pattern: &phalcom_common::selector::SelectorPattern| resolver.capture_method_family(receiver, pattern);
     let is_same_or_subclass = |child: &ClassId, ancestor: &ClassId| super::is_same_or_subclass(classes, child, ancestor);
     let member_surface = |id: &CallableId| classes.get(&id.owner).and_then(|class| class.member_by_id(id).cloned());
+    let sealed_variants = |class: &ClassId| classes.get(class).map(|class| class.variants.clone()).unwrap_or_default();
     let context = SolverContext {
         known_class: &known_class,
         contains_class: &contains_class,
@@ -116,6 +117,7 @@ pub(crate) fn analyze_source(
         family_resolver: &family_resolver,
         member_surface: &member_surface,
         is_same_or_subclass: &is_same_or_subclass,
+        sealed_variants: &sealed_variants,
     };
     super::flow::analyze_surface(source, &context, generation, counters)
 }
@@ -143,6 +145,7 @@ pub(crate) fn analyze_callable_source(
     let family_resolver = |receiver: &DispatchReceiver, pattern: &phalcom_common::selector::SelectorPattern| resolver.capture_method_family(receiver, pattern);
     let is_same_or_subclass = |child: &ClassId, ancestor: &ClassId| super::is_same_or_subclass(classes, child, ancestor);
     let member_surface = |id: &CallableId| classes.get(&id.owner).and_then(|class| class.member_by_id(id).cloned());
+    let sealed_variants = |class: &ClassId| classes.get(class).map(|class| class.variants.clone()).unwrap_or_default();
     let context = SolverContext {
         known_class: &known_class,
         contains_class: &contains_class,
@@ -154,6 +157,7 @@ pub(crate) fn analyze_callable_source(
         family_resolver: &family_resolver,
         member_surface: &member_surface,
         is_same_or_subclass: &is_same_or_subclass,
+        sealed_variants: &sealed_variants,
     };
     super::flow::analyze_callable(source, &context, generation, callable, include_top_level, counters)
 }
@@ -50,6 +50,7 @@ struct FieldDeclarationFingerprint {
 #[derive(Clone, Debug, Eq, PartialEq)]
 struct ClassDeclarationFingerprint {
     superclass: Option<super::ids::ClassId>,
+    mixins: Vec<super::ids::ClassId>,
     members: Vec<MemberDeclarationFingerprint>,
     fields: Vec<FieldDeclarationFingerprint>,
 }
@@ -205,6 +206,7 @@ fn declaration_fingerprint(surface: &ModuleSurface) -> DeclarationFingerprint {
                 class_id.clone(),
                 ClassDeclarationFingerprint {
                     superclass: class.superclass.clone(),
+                    mixins: class.mixins.clone(),
                     members,
                     fields,
                 },
@@ -243,9 +245,14 @@ fn top_level_source(source: &FileSourceSnapshot) -> Vec<String> {
 fn statement_range(statement: &Statement) -> phalcom_common::range::SourceRange {
     match statement {
         Statement::Class(class) => class.range,
+        Statement::Extend(extend_def) => extend_def.range,
         Statement::Let(binding) => binding.range,
         Statement::Return(returned) => returned.range,
-        Statement::Expr { range, .. } | Statement::Break { range } | Statement::Continue { range } | Statement::Throw { range, .. } => *range,
+        Statement::Expr { range, .. }
+        | Statement::Break { range }
+        | Statement::Continue { range }
+        | Statement::Throw { range, .. }
+        | Statement::Yield { range, .. } => *range,
         Statement::For(for_statement) => for_statement.range,
         Statement::Export(export_decl) => export_decl.range,
     }
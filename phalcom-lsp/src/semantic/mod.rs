@@ -390,6 +390,15 @@ fn resolve_named_class<C: ClassTable + ?Sized>(classes: &C, graph: &ModuleGraph,
     if classes.contains_class(&local) {
         return Some(local);
     }
+    let selective = graph
+        .imports(module)
+        .iter()
+        .filter(|edge| edge.kind == ImportEdgeKind::Selective && edge.binding == name)
+        .find_map(|edge| edge.target.as_ref().map(|target| ClassId::new(target.clone(), name)))
+        .filter(|class| classes.contains_class(class));
+    if selective.is_some() {
+        return selective;
+    }
     let core = ClassId::new(ModuleId::new(CORE_MODULE_URI), name);
     classes.contains_class(&core).then_some(core)
 }
@@ -778,6 +787,52 @@ class Child is Base { run() { _client } }
         );
     }
 
+    #[test]
+    fn sealed_type_tests_and_variant_patterns_narrow_hover_facts() {
+        let db = SemanticDb::new();
+        let bundled = core_source::bundled_parse();
+        db.update_core(FileRevision(1), &bundled.program);
+        let uri = uri("file:///shapes.ph");
+        let source = r#"@sealed
+@data
+class Shape {
+  @variant Circle(radius:)
+  @variant Rect(w:, h:)
+}
+class Factory {
+  @constructor new() { }
+  pick(_ flag) {
+    if flag { return Circle.new(radius: 1) }
+    return Rect.new(w: 1, h: 2)
+  }
+}
+let s = Factory.new().pick(true)
+if s is Circle { s /*circle*/ } else { s /*rect*/ }
+s /*after*/
+if let Rect(w, h) = s { s /*destructured*/ }
+let whole = Shape.new()
+if whole is Circle { } else { whole /*remaining*/ }
+"#;
+        let parsed = parse(source, 0);
+        assert!(parsed.errors.is_empty(), "sealed fixture parse errors: {:?}", parsed.errors);
+        db.update_file(&uri, FileRevision(1), &parsed.program);
+
+        let binding_shape_at = |name: &str, marker: &str| {
+            let offset = source.find(marker).unwrap_or_else(|| panic!("missing marker {marker}"));
+            db.binding_at(&uri, name, offset).expect("binding fact").shape
+        };
+        let shape_at = |marker: &str| binding_shape_at("s", marker);
+        let named = |shape: &ValueShape, expected: &str| matches!(shape, ValueShape::Instance(ClassId { name, .. }) if name == expected);
+
+        assert!(named(&shape_at("/*circle*/"), "Circle"), "then arm: {:?}", shape_at("/*circle*/"));
+        assert!(named(&shape_at("/*rect*/"), "Rect"), "else arm: {:?}", shape_at("/*rect*/"));
+        assert!(matches!(shape_at("/*after*/"), ValueShape::Union(_)), "after if: {:?}", shape_at("/*after*/"));
+        assert!(named(&shape_at("/*destructured*/"), "Rect"), "if let arm: {:?}", shape_at("/*destructured*/"));
+        // A failed test on the sealed class itself leaves the other variant.
+        let remaining = binding_shape_at("whole", "/*remaining*/");
+        assert!(named(&remaining, "Rect"), "sealed else arm: {remaining:?}");
+    }
+
     #[test]
     fn invoked_literal_block_contributes_nonlocal_return() {
         let db = SemanticDb::new();
@@ -383,6 +383,9 @@ impl ModuleGraph {
 }
 
 fn resolve_import(module: &ModuleId, import: &str, available: &BTreeSet<ModuleId>) -> Option<ModuleId> {
+    if let Some(extension) = super::core_source::extension_module_for_import(import) {
+        return Some(extension);
+    }
     for candidate in import_candidates(module, import) {
         if available.contains(&candidate) {
             return Some(candidate);
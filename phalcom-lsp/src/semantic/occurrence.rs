@@ -188,6 +188,12 @@ impl OccurrenceBuilder<'_> {
                         self.visit_member(&class_id, member);
                     }
                 }
+                Statement::Extend(extend_def) => {
+                    let class_id = ClassId::new(self.module.clone(), extend_def.target.leaf_name().to_string());
+                    for member in &extend_def.members {
+                        self.visit_member(&class_id, member);
+                    }
+                }
                 Statement::Let(binding) => {
                     if let Some(value) = &binding.value {
                         self.visit_expr(value, scope);
@@ -223,7 +229,7 @@ impl OccurrenceBuilder<'_> {
                         .unwrap_or(scope);
                     self.visit_statements(&statement.body, body_scope);
                 }
-                Statement::Throw { expr, .. } => self.visit_expr(expr, scope),
+                Statement::Throw { expr, .. } | Statement::Yield { expr, .. } => self.visit_expr(expr, scope),
                 Statement::Export(export) => {
                     for item in &export.items {
                         if let Some(target) = self.name_target(&item.local_or_remote_name, item.name_range, scope) {
@@ -736,12 +742,13 @@ fn occurrence_priority(kind: SemanticOccurrenceKind) -> u8 {
 fn statement_range(statement: &Statement) -> SourceRange {
     match statement {
         Statement::Class(class) => class.range,
+        Statement::Extend(extend_def) => extend_def.range,
         Statement::Let(binding) => binding.range,
         Statement::Return(return_statement) => return_statement.range,
         Statement::Expr { range, .. } => *range,
         Statement::For(for_statement) => for_statement.range,
         Statement::Break { range } | Statement::Continue { range } => *range,
-        Statement::Throw { range, .. } => *range,
+        Statement::Throw { range, .. } | Statement::Yield { range, .. } => *range,
         Statement::Export(export_decl) => export_decl.range,
     }
 }
@@ -780,6 +787,7 @@ fn unary_name(op: &UnaryOp) -> String {
         UnaryOp::Minus => "-",
         UnaryOp::Not => "not",
         UnaryOp::BitNot => "~",
+        UnaryOp::Await => "await",
     }
     .to_string()
 }
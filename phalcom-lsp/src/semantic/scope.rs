@@ -334,6 +334,11 @@ impl ScopeBuilder {
                         self.visit_member(scope, member);
                     }
                 }
+                Statement::Extend(extend_def) => {
+                    for member in &extend_def.members {
+                        self.visit_member(scope, member);
+                    }
+                }
                 Statement::Let(binding) => self.visit_let(scope, binding, top_level),
                 Statement::Return(return_statement) => {
                     if let Some(value) = &return_statement.value {
@@ -342,7 +347,7 @@ impl ScopeBuilder {
                 }
                 Statement::Expr { expr, .. } => self.visit_expr(scope, expr),
                 Statement::For(for_statement) => self.visit_for(scope, for_statement),
-                Statement::Throw { expr, .. } => self.visit_expr(scope, expr),
+                Statement::Throw { expr, .. } | Statement::Yield { expr, .. } => self.visit_expr(scope, expr),
                 Statement::Export(_) => {}
                 Statement::Break { .. } | Statement::Continue { .. } => {}
             }
@@ -625,12 +630,13 @@ fn statements_range(statements: &[Statement]) -> SourceRange {
 fn statement_range(statement: &Statement) -> SourceRange {
     match statement {
         Statement::Class(class) => class.range,
+        Statement::Extend(extend_def) => extend_def.range,
         Statement::Let(binding) => binding.range,
         Statement::Return(return_statement) => return_statement.range,
         Statement::Expr { range, .. } => *range,
         Statement::For(for_statement) => for_statement.range,
         Statement::Break { range } | Statement::Continue { range } => *range,
-        Statement::Throw { range, .. } => *range,
+        Statement::Throw { range, .. } | Statement::Yield { range, .. } => *range,
         Statement::Export(export_decl) => export_decl.range,
     }
 }
@@ -3,9 +3,11 @@
 use std::collections::{BTreeMap, BTreeSet};
 
 use phalcom_ast::ast::{
-    AttrKind, Attribute, ClassMember, DependencyDecl, ImportDecl, IndexAccessor, ParameterDef, Program, RestMode, Statement, StaticSymbolRef,
+    AttrKind, Attribute, BuiltinAttr, ClassMember, DependencyDecl, Expr, ImportDecl, IndexAccessor, ParameterDef, Program, RestMode, Statement,
+    StaticSymbolRef, VariantDef,
 };
 use phalcom_common::range::SourceRange;
+use phalcom_common::selector::SelectorSlot;
 pub use phalcom_common::selector::{Selector, SelectorPattern};
 use phalcom_native_surface::NativeReturnShape;
 
@@ -61,6 +63,16 @@ pub struct ClassSurface {
     pub superclass: Option<ClassId>,
     /// Original static superclass reference, before module linking.
     pub superclass_reference: Option<StaticSymbolRef>,
+    /// Whether the class is declared with `@mixin`.
+    pub is_mixin: bool,
+    /// Whether the class is declared with `@protocol`.
+    pub is_protocol: bool,
+    /// Mixins named by `@compose(...)` (or by a mixin's own `@mixin(...)`),
+    /// or the protocols a `@protocol(...)` includes, in declaration order.
+    pub mixins: Vec<ClassId>,
+    /// For a `@sealed` class, the closed set of classes its values can be:
+    /// the `@variant` siblings, then same-module direct subclasses.
+    pub variants: Vec<ClassId>,
     /// Members keyed by canonical selector, preserving both dispatch sides.
     pub members: BTreeMap<String, MemberSides>,
     /// Fields keyed by source field name, preserving both storage sides.
@@ -400,6 +412,7 @@ pub fn build_module_surface(module: ModuleId, program: &Program) -> ModuleSurfac
             }
         }
     }
+    let mut sealed = Vec::new();
     for (class_stmt_idx, statement) in program.statements.iter().enumerate() {
         if let Statement::Export(export) = statement {
             for item in &export.items {
@@ -431,10 +444,46 @@ pub fn build_module_surface(module: ModuleId, program: &Program) -> ModuleSurfac
             .map(|attribute| attribute.range.start)
             .min()
             .unwrap_or(class.range.start);
+        let is_mixin = class
+            .attributes
+            .iter()
+            .any(|attribute| matches!(attribute.kind, AttrKind::Builtin(BuiltinAttr::Mixin)));
+        let is_protocol = class
+            .attributes
+            .iter()
+            .any(|attribute| matches!(attribute.kind, AttrKind::Builtin(BuiltinAttr::Protocol)));
+        let mixins = class
+            .attributes
+            .iter()
+            .filter(|attribute| {
+                matches!(
+                    attribute.kind,
+                    AttrKind::Builtin(BuiltinAttr::Mixin | BuiltinAttr::Compose | BuiltinAttr::Protocol)
+                )
+            })
+            .flat_map(|attribute| &attribute.args)
+            .filter_map(|arg| match arg {
+                Expr::Var { value, .. } => Some(value.clone()),
+                Expr::GetProperty(get) => Some(get.property.clone()),
+                _ => None,
+            })
+            .map(|name| ClassId::new(module.clone(), name))
+            .collect();
+        let is_sealed = class
+            .attributes
+            .iter()
+            .any(|attribute| matches!(attribute.kind, AttrKind::Builtin(BuiltinAttr::Sealed)));
+        if is_sealed {
+            sealed.push(id.clone());
+        }
         let mut class_surface = ClassSurface {
             id: id.clone(),
             superclass,
             superclass_reference,
+            is_mixin,
+            is_protocol,
+            mixins,
+            variants: Vec::new(),
             members: BTreeMap::new(),
             fields: BTreeMap::new(),
             source_range: (class_start..class.range.end).into(),
@@ -488,9 +537,27 @@ pub fn build_module_surface(module: ModuleId, program: &Program) -> ModuleSurfac
                 ast,
             };
             class_surface.members.entry(selector).or_default().insert(side, member_surface);
+            if let ClassMember::Variant(variant) = member
+                && is_sealed
+            {
+                let sibling = variant_surface(&module, &id, variant, ast);
+                class_surface.variants.push(sibling.id.clone());
+                surface.classes.insert(sibling.id.clone(), sibling);
+            }
         }
         surface.classes.insert(id, class_surface);
     }
+    for sealed_id in sealed {
+        let subclasses: Vec<ClassId> = surface
+            .classes
+            .values()
+            .filter(|class| class.superclass_reference.is_some() && class.superclass.as_ref() == Some(&sealed_id))
+            .map(|class| class.id.clone())
+            .collect();
+        if let Some(class) = surface.classes.get_mut(&sealed_id) {
+            class.variants.extend(subclasses);
+        }
+    }
     for statement in &program.statements {
         let Statement::Export(export) = statement else { continue };
         for item in &export.items {
@@ -512,6 +579,84 @@ pub fn build_module_surface(module: ModuleId, program: &Program) -> ModuleSurfac
     surface
 }
 
+/// Builds the sibling class a `@variant Name(labels...)` arm of the sealed
+/// class `parent` expands into: a subclass with one field and getter per
+/// label and a labeled class-side constructor. Every member points back at
+/// the variant declaration.
+fn variant_surface(module: &ModuleId, parent: &ClassId, variant: &VariantDef, ast: MemberAstRef) -> ClassSurface {
+    let id = ClassId::new(module.clone(), variant.name.clone());
+    let mut class = ClassSurface {
+        id: id.clone(),
+        superclass: Some(parent.clone()),
+        superclass_reference: None,
+        is_mixin: false,
+        is_protocol: false,
+        mixins: Vec::new(),
+        variants: Vec::new(),
+        members: BTreeMap::new(),
+        fields: BTreeMap::new(),
+        source_range: variant.range,
+        name_range: variant.name_range,
+    };
+    let member = |selector: Selector, kind: MemberKind, side: DispatchSide, params: Vec<ParamSurface>| MemberSurface {
+        callable: CallableId {
+            owner: id.clone(),
+            selector: selector.encode(),
+            side,
+        },
+        selector,
+        rest: None,
+        kind,
+        visibility: MemberVisibility::Public,
+        side,
+        is_constructor: side == DispatchSide::Class,
+        native_return: None,
+        source_range: variant.range,
+        name_range: variant.name_range,
+        params,
+        ast,
+    };
+    let mut params = Vec::new();
+    for label in &variant.labels {
+        let name = format!("_{label}");
+        let field = FieldSurface {
+            name: name.clone(),
+            kind: FieldKind::Source,
+            is_class_side: false,
+            source_range: variant.range,
+            name_range: variant.name_range,
+            ast,
+        };
+        class.fields.entry(name).or_default().instance = Some(field);
+        if let Ok(selector) = Selector::getter(label) {
+            let getter = member(selector, MemberKind::Getter, DispatchSide::Instance, Vec::new());
+            class
+                .members
+                .entry(getter.callable.selector.clone())
+                .or_default()
+                .insert(DispatchSide::Instance, getter);
+        }
+        params.push(ParamSurface {
+            name: label.clone(),
+            label: Some(label.clone()),
+            rest_mode: RestMode::None,
+            source_range: variant.range,
+            name_range: variant.name_range,
+            label_range: None,
+        });
+    }
+    let slots = variant.labels.iter().map(|label| SelectorSlot::Label(label.clone())).collect::<Vec<_>>();
+    if let Ok(selector) = Selector::method("new", slots) {
+        let constructor = member(selector, MemberKind::Method, DispatchSide::Class, params);
+        class
+            .members
+            .entry(constructor.callable.selector.clone())
+            .or_default()
+            .insert(DispatchSide::Class, constructor);
+    }
+    class
+}
+
 /// Normalizes AST parameters into a rest layout surface descriptor if variable-arity.
 pub fn rest_surface_from_params(params: &[ParameterDef]) -> Option<RestSurface> {
     let mut fixed_positionals = 0;
@@ -191,7 +191,9 @@ fn classify(token: &Token) -> Option<SemanticTokenKind> {
         | Token::Static
         | Token::Construct
         | Token::Throw
-        | Token::Try => Some(Keyword),
+        | Token::Try
+        | Token::Yield
+        | Token::Await => Some(Keyword),
 
         Token::Identifier(_) | Token::FieldIdentifier(_) | Token::ImplementationFieldIdentifier(_) => Some(Variable),
 
@@ -654,6 +656,8 @@ fn collect_decl_names(statements: &[Statement], out: &mut Vec<DeclNameOverride>)
             | Statement::Break { .. }
             | Statement::Continue { .. }
             | Statement::Throw { .. }
+            | Statement::Yield { .. }
+            | Statement::Extend(_)
             | Statement::Export(_) => {}
         }
     }
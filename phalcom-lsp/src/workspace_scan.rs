@@ -6,6 +6,10 @@
 //! steps so that interactive (open-document) work can preempt background
 //! scanning.
 //!
+//! Roots inside a Phalcom workspace (a `project.toml` with `[workspace]`)
+//! widen to the workspace root, so opening one member folder discovers every
+//! member instead of scanning one project per folder.
+//!
 //! Exclusion rules:
 //! - Built-in: hidden directories (`.`-prefixed), `target`, `node_modules`.
 //! - User-configured: path fragment strings from `phalcom.analysis.exclude`.
@@ -250,9 +254,10 @@ impl WorkspaceScanState {
     /// Set workspace roots and reset scanner state.
     ///
     /// This replaces all pending work so that discovery restarts from the new
-    /// roots (e.g. after `workspaceFolders/didChange`).
+    /// roots (e.g. after `workspaceFolders/didChange`). Each root is first
+    /// widened by [`workspace_roots`].
     pub fn set_roots(&mut self, roots: Vec<PathBuf>, core_physical_path: Option<PathBuf>) {
-        self.roots = roots;
+        self.roots = workspace_roots(roots);
         self.pending_dirs.clear();
         self.active_dir = None;
         self.pending_files.clear();
@@ -352,6 +357,33 @@ impl WorkspaceScanState {
     }
 }
 
+/// Replaces each root that lies inside a Phalcom workspace member (or is the
+/// member itself) with the workspace root, dropping roots that end up
+/// covered by another. Roots outside any workspace are kept as given.
+pub fn workspace_roots(roots: Vec<PathBuf>) -> Vec<PathBuf> {
+    let widened: Vec<PathBuf> = roots
+        .into_iter()
+        .map(|root| {
+            let owner = phalcom_modules::discover_owning_project(&root).ok().flatten();
+            owner
+                .and_then(|project| phalcom_modules::Workspace::enclosing(project).ok().flatten())
+                .map_or(root, |workspace| workspace.root)
+        })
+        .collect();
+    let mut kept: Vec<PathBuf> = Vec::new();
+    for root in widened {
+        let canonical = root.canonicalize().unwrap_or_else(|_| root.clone());
+        let covered = kept
+            .iter()
+            .any(|other| canonical.starts_with(other.canonicalize().unwrap_or_else(|_| other.clone())));
+        if !covered {
+            kept.retain(|other| !other.canonicalize().unwrap_or_else(|_| other.clone()).starts_with(&canonical));
+            kept.push(root);
+        }
+    }
+    kept
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -494,6 +526,41 @@ mod tests {
         let _ = fs::remove_dir_all(&root);
     }
 
+    #[test]
+    fn member_roots_widen_to_the_workspace_root() {
+        let root = tmpdir("workspace_roots");
+        fs::write(root.join("project.toml"), "[workspace]\nmembers = [\"packages/*\"]\n").unwrap();
+        for member in ["alpha", "beta"] {
+            fs::create_dir_all(root.join("packages").join(member).join("src")).unwrap();
+            fs::write(
+                root.join("packages").join(member).join("project.toml"),
+                format!("[project]\nname = \"{member}\"\nnamespace = \"{member}\"\n"),
+            )
+            .unwrap();
+            fs::write(root.join("packages").join(member).join("src/package.ph"), "").unwrap();
+        }
+        let canonical = root.canonicalize().unwrap();
+
+        let alpha = root.join("packages/alpha");
+        let beta_src = root.join("packages/beta/src");
+        assert_eq!(workspace_roots(vec![alpha.clone(), beta_src]), vec![canonical.clone()]);
+
+        let mut scanner = WorkspaceScanState::new(AnalysisMode::Local, ExcludeMatcher::new(&[]));
+        scanner.set_roots(vec![alpha], None);
+        let mut all = Vec::new();
+        while scanner.has_work() {
+            all.extend(scanner.step(SCAN_BUDGET));
+        }
+        let paths: std::collections::BTreeSet<_> = all.iter().map(|f| f.path.canonicalize().unwrap()).collect();
+        assert!(paths.contains(&canonical.join("packages/beta/src/package.ph")), "{paths:?}");
+
+        let outside = tmpdir("workspace_roots_outside");
+        assert_eq!(workspace_roots(vec![outside.clone()]), vec![outside.clone()]);
+
+        let _ = fs::remove_dir_all(&root);
+        let _ = fs::remove_dir_all(&outside);
+    }
+
     #[test]
     fn analysis_mode_from_str() {
         assert_eq!("local".parse::<AnalysisMode>(), Ok(AnalysisMode::Local));
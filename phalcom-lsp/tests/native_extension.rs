@@ -0,0 +1,60 @@
+//! Extension classes (`phalcom_native_meta::EXTENSION_CLASSES`) in the
+//! semantic database.
+//!
+//! This test crate links `acme.crypto.Digest` the way a downstream extension
+//! crate would. Its class lives in the `acme.crypto` module's surface, so a
+//! module sees it only through an import, never as a bare core name.
+
+use linkme::distributed_slice;
+use phalcom_lsp::semantic::{CORE_MODULE_URI, ClassId, FileRevision, ModuleId, SemanticEngine, SemanticModuleId, SemanticSnapshot, ValueShape};
+use phalcom_native_meta::{EXTENSION_CLASSES, NativeClassSpec, UniverseKey};
+use tower_lsp::lsp_types::Url;
+
+#[distributed_slice(EXTENSION_CLASSES)]
+static DIGEST: NativeClassSpec = NativeClassSpec {
+    module: "acme.crypto",
+    name: "Digest",
+    superclass: UniverseKey::Object,
+};
+
+fn analyze(uri: &Url, source: &str) -> SemanticSnapshot {
+    let mut engine = SemanticEngine::new();
+    let core = phalcom_ast::parser::parse(include_str!("../../phalcom-core/core/core.ph"), 0);
+    engine.update_core(FileRevision(1), &core.program);
+    engine.update_file(uri, FileRevision(1), &phalcom_ast::parser::parse(source, 0).program);
+    engine.snapshot()
+}
+
+fn digest() -> ClassId {
+    ClassId::new(ModuleId::new(SemanticModuleId::native("acme.crypto").to_string()), "Digest")
+}
+
+/// **An unimported extension class does not resolve**: it is not a core class.
+#[test]
+fn unimported_extension_class_does_not_resolve() {
+    let uri = Url::parse("file:///main.ph").unwrap();
+    let db = analyze(&uri, "let d = Digest.new()\n");
+    assert_eq!(db.class_for_name(&uri, "Digest"), None);
+    assert!(!db.classes.contains_key(&ClassId::new(ModuleId::new(CORE_MODULE_URI), "Digest")));
+    assert!(!matches!(db.binding_at(&uri, "d", 21).map(|value| value.shape), Some(ValueShape::Instance(class)) if class == digest()));
+}
+
+/// **A module import exposes it under the module's binding**, keyed by the
+/// extension module's own identity.
+#[test]
+fn module_import_resolves_the_extension_class() {
+    let uri = Url::parse("file:///main.ph").unwrap();
+    let db = analyze(&uri, "import acme.crypto\nlet d = crypto.Digest.new()\n");
+    assert_eq!(db.class_for_name(&uri, "crypto.Digest"), Some(digest()));
+    assert_eq!(db.class_for_name(&uri, "Digest"), None);
+    let surface = db.classes.get(&digest()).expect("the extension module has a surface");
+    assert_eq!(surface.superclass.as_ref().map(|class| class.name.as_str()), Some("Object"));
+}
+
+/// **A selective import brings the bare name into scope.**
+#[test]
+fn selective_import_resolves_the_bare_name() {
+    let uri = Url::parse("file:///main.ph").unwrap();
+    let db = analyze(&uri, "from acme.crypto import Digest\nlet d = Digest.new()\n");
+    assert_eq!(db.class_for_name(&uri, "Digest"), Some(digest()));
+}
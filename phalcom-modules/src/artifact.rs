@@ -61,3 +61,9 @@ pub trait PackageArtifactProvider {
     /// Retrieves a published package artifact by ID.
     fn get_package_artifact(&self, id: ResolvedPackageId) -> Result<Option<&ResolvedPackageArtifact>, ProjectError>;
 }
+
+impl PackageArtifactProvider for BTreeMap<ResolvedPackageId, ResolvedPackageArtifact> {
+    fn get_package_artifact(&self, id: ResolvedPackageId) -> Result<Option<&ResolvedPackageArtifact>, ProjectError> {
+        Ok(self.get(&id))
+    }
+}
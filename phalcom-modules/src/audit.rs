@@ -0,0 +1,108 @@
+//! Import auditing for `phalcom graph`.
+//!
+//! An import is unused when the name it binds never appears as an
+//! identifier outside the module's import declarations. The scan is
+//! token-based rather than scope-aware, so a local that shadows an imported
+//! name counts as a use: it may miss an unused import, but never reports a
+//! used one. Re-exports are always used, by the modules importing them, and
+//! so is an import of a module that declares `extend` blocks: importing it
+//! is what brings its extensions into scope.
+
+use crate::identity::ModuleId;
+use crate::interface::{ImportSurface, UnlinkedModuleInterface};
+use crate::linker::default_module_binding;
+use phalcom_ast::ast::ImportPath;
+use phalcom_ast::lexer::Lexer;
+use phalcom_ast::token::{StringSegment, Token};
+use phalcom_common::range::SourceRange;
+use std::collections::BTreeSet;
+
+/// An imported binding its module never reads.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnusedImport {
+    /// Module containing the import.
+    pub module: ModuleId,
+    /// Local name the import binds.
+    pub name: String,
+    /// Import path as written, e.g. `.util`.
+    pub path: String,
+    /// Source range of the imported item, or of the whole-module import.
+    pub range: SourceRange,
+}
+
+/// Returns the imports of `interface` whose bound names `source` never uses,
+/// in source order. `extends` tells whether the module an import path names
+/// declares extensions; such imports are never reported.
+pub fn unused_imports(module: &ModuleId, interface: &UnlinkedModuleInterface, source: &str, extends: impl Fn(&ImportPath) -> bool) -> Vec<UnusedImport> {
+    let imports = interface
+        .imports
+        .iter()
+        .map(|import| match import {
+            ImportSurface::Module(decl) => decl.range,
+            ImportSurface::Selective(decl) => decl.range,
+            ImportSurface::ReExport(decl) => decl.range,
+        })
+        .collect::<Vec<_>>();
+    let mut used = BTreeSet::new();
+    collect_identifiers(source, 0, &imports, &mut used);
+
+    let mut unused = Vec::new();
+    let mut check = |name: String, path: String, range: SourceRange| {
+        if !used.contains(&name) {
+            unused.push(UnusedImport {
+                module: module.clone(),
+                name,
+                path,
+                range,
+            });
+        }
+    };
+    for import in &interface.imports {
+        match import {
+            ImportSurface::Module(decl) if extends(&decl.path) => {}
+            ImportSurface::Selective(decl) if extends(&decl.path) => {}
+            ImportSurface::Module(decl) => {
+                let name = decl
+                    .alias
+                    .as_ref()
+                    .map(|alias| alias.name.as_str())
+                    .or_else(|| default_module_binding(&decl.path));
+                if let Some(name) = name {
+                    check(name.to_string(), decl.path.to_string(), decl.range);
+                }
+            }
+            ImportSurface::Selective(decl) => {
+                for item in &decl.items {
+                    let name = item.alias.as_ref().map(|alias| alias.name.clone()).unwrap_or_else(|| item.name.clone());
+                    check(name, decl.path.to_string(), item.range);
+                }
+            }
+            ImportSurface::ReExport(_) => {}
+        }
+    }
+    unused
+}
+
+/// Adds every identifier of `source` (which starts at byte `offset` of the
+/// module) lying outside `skip` to `used`, including those inside string
+/// interpolations.
+fn collect_identifiers(source: &str, offset: usize, skip: &[SourceRange], used: &mut BTreeSet<String>) {
+    for (start, token, _) in Lexer::new(source).flatten() {
+        if skip.iter().any(|range| range.contains(offset + start)) {
+            continue;
+        }
+        match token {
+            Token::Identifier(name) => {
+                used.insert(name);
+            }
+            Token::StringInterp(segments) => {
+                for segment in segments {
+                    if let StringSegment::Expr { source, range } = segment {
+                        collect_identifiers(&source, offset + range.start, skip, used);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -4,6 +4,8 @@ use crate::error::{ModuleLoadError, ModuleResolutionError};
 use crate::identity::{BuiltinProject, ModuleId, ModulePath, ProjectIdentity, SourceId};
 use crate::interface::UnlinkedModuleInterface;
 use crate::source::ModuleKind;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::OnceLock;
 
 #[derive(Clone, Copy, Debug)]
 pub struct BuiltinNodeSpec {
@@ -107,7 +109,7 @@ pub const UNIVERSE_NODES: &[BuiltinNodeSpec] = &[
     BuiltinNodeSpec {
         path: &["collections"],
         kind: ModuleKind::Package,
-        children: &["iterable", "list", "map", "set", "tuple", "record", "range", "bytes"],
+        children: &["iterable", "list", "map", "set", "tuple", "record", "range", "bytes", "weak"],
     },
     BuiltinNodeSpec {
         path: &["collections", "iterable"],
@@ -149,6 +151,11 @@ pub const UNIVERSE_NODES: &[BuiltinNodeSpec] = &[
         kind: ModuleKind::Module,
         children: &[],
     },
+    BuiltinNodeSpec {
+        path: &["collections", "weak"],
+        kind: ModuleKind::Module,
+        children: &[],
+    },
     BuiltinNodeSpec {
         path: &["errors"],
         kind: ModuleKind::Package,
@@ -208,6 +215,7 @@ pub const UNIVERSE_NODES: &[BuiltinNodeSpec] = &[
             "selector",
             "message",
             "attribute",
+            "decorators",
             "typing",
         ],
     },
@@ -311,6 +319,11 @@ pub const UNIVERSE_NODES: &[BuiltinNodeSpec] = &[
         kind: ModuleKind::Module,
         children: &[],
     },
+    BuiltinNodeSpec {
+        path: &["reflection", "decorators"],
+        kind: ModuleKind::Module,
+        children: &[],
+    },
     BuiltinNodeSpec {
         path: &["reflection", "typing"],
         kind: ModuleKind::Package,
@@ -462,10 +475,14 @@ impl BuiltinProjectSourceProvider {
     pub fn source_text(&self, id: &ModuleId) -> Result<std::sync::Arc<str>, ModuleLoadError> {
         use std::sync::Arc;
         self.validate_id(id)?;
-        let _node = self
+        let node = self
             .node_spec(&id.path)
             .ok_or_else(|| ModuleResolutionError::ModuleNotFound(format!("builtin module {id} is not part of the {} project graph", self.builtin)))?;
 
+        if self.builtin == BuiltinProject::Native {
+            return Ok(Arc::from(native_source_text(node)));
+        }
+
         let components = id.path.components();
         let content = match (self.builtin, components) {
             (BuiltinProject::Universe, []) => include_str!("../../phalcom-core/core/universe/src/package.ph"),
@@ -537,6 +554,9 @@ impl BuiltinProjectSourceProvider {
             (BuiltinProject::Universe, [c, m]) if c.as_str() == "collections" && m.as_str() == "bytes" => {
                 include_str!("../../phalcom-core/core/universe/src/collections/bytes.ph")
             }
+            (BuiltinProject::Universe, [c, m]) if c.as_str() == "collections" && m.as_str() == "weak" => {
+                include_str!("../../phalcom-core/core/universe/src/collections/weak.ph")
+            }
             (BuiltinProject::Universe, [c]) if c.as_str() == "errors" => include_str!("../../phalcom-core/core/universe/src/errors/package.ph"),
             (BuiltinProject::Universe, [c, m]) if c.as_str() == "errors" && m.as_str() == "error" => {
                 include_str!("../../phalcom-core/core/universe/src/errors/error.ph")
@@ -617,6 +637,9 @@ impl BuiltinProjectSourceProvider {
             (BuiltinProject::Universe, [c, m]) if c.as_str() == "reflection" && m.as_str() == "attribute" => {
                 include_str!("../../phalcom-core/core/universe/src/reflection/attribute.ph")
             }
+            (BuiltinProject::Universe, [c, m]) if c.as_str() == "reflection" && m.as_str() == "decorators" => {
+                include_str!("../../phalcom-core/core/universe/src/reflection/decorators.ph")
+            }
             (BuiltinProject::Universe, [c, m]) if c.as_str() == "reflection" && m.as_str() == "typing" => {
                 include_str!("../../phalcom-core/core/universe/src/reflection/typing/package.ph")
             }
@@ -676,6 +699,7 @@ impl BuiltinProjectSourceProvider {
         match self.builtin {
             BuiltinProject::Universe => UNIVERSE_NODES,
             BuiltinProject::Std => STD_NODES,
+            BuiltinProject::Native => native_nodes(),
         }
     }
 
@@ -686,3 +710,51 @@ impl BuiltinProjectSourceProvider {
         Ok(())
     }
 }
+
+/// The `native` project graph, derived once from the linked extension
+/// registry: every extension module is a leaf, every proper prefix of one a
+/// package of its children, and the project root a package of the roots.
+fn native_nodes() -> &'static [BuiltinNodeSpec] {
+    static NODES: OnceLock<Vec<BuiltinNodeSpec>> = OnceLock::new();
+    NODES.get_or_init(|| {
+        let mut children: BTreeMap<Vec<&'static str>, BTreeSet<&'static str>> = BTreeMap::new();
+        children.insert(Vec::new(), BTreeSet::new());
+        for module in phalcom_native_meta::extension_modules() {
+            let path: Vec<&'static str> = module.split('.').collect();
+            for depth in 0..path.len() {
+                children.entry(path[..depth].to_vec()).or_default().insert(path[depth]);
+            }
+            children.entry(path).or_default();
+        }
+        children
+            .into_iter()
+            .map(|(path, children)| {
+                let kind = if children.is_empty() && !path.is_empty() {
+                    ModuleKind::Module
+                } else {
+                    ModuleKind::Package
+                };
+                BuiltinNodeSpec {
+                    path: Vec::leak(path),
+                    kind,
+                    children: Vec::leak(children.into_iter().collect()),
+                }
+            })
+            .collect()
+    })
+}
+
+/// Generated source of a `native` node. A package exposes and imports its
+/// children; a module's classes are all native overlay, so its source is
+/// only documentation.
+fn native_source_text(node: &BuiltinNodeSpec) -> String {
+    let mut source = if node.path.is_empty() {
+        "@!documentation(\"Native classes contributed by extension crates.\")\n".to_owned()
+    } else {
+        format!("@!documentation(\"Extension module {}.\")\n", node.path.join("."))
+    };
+    for child in node.children {
+        source.push_str(&format!("\nexpose .{child}\nimport .{child}\n"));
+    }
+    source
+}
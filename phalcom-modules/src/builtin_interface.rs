@@ -85,9 +85,18 @@ impl BuiltinInterfaceBuilder {
         })?;
 
         let id = &parsed.id;
-        // Overlay primordial native universe bindings for the universe root package or matching submodules
-        if id.project == ProjectIdentity::Builtin(BuiltinProject::Universe) {
-            let names: Vec<&'static str> = if id.path.is_root() {
+        // Overlay primordial native universe bindings for the universe root package or matching submodules,
+        // and extension classes for their `native` module.
+        let overlay = match id.project {
+            ProjectIdentity::Builtin(BuiltinProject::Universe) => true,
+            ProjectIdentity::Builtin(BuiltinProject::Native) => !id.path.is_root(),
+            _ => false,
+        };
+        if overlay {
+            let names: Vec<&'static str> = if id.project == ProjectIdentity::Builtin(BuiltinProject::Native) {
+                let module = id.path.components().iter().map(|c| c.as_str()).collect::<Vec<_>>().join(".");
+                phalcom_native_meta::extension_classes(&module).iter().map(|class| class.name).collect()
+            } else if id.path.is_root() {
                 phalcom_native_meta::UNIVERSE_BINDINGS.iter().filter(|b| b.exported).map(|b| b.name).collect()
             } else {
                 let comps: Vec<&str> = id.path.components().iter().map(|c| c.as_str()).collect();
@@ -168,6 +177,7 @@ impl BuiltinInterfaceBuilder {
                     ["collections", "record"] => vec!["Record"],
                     ["collections", "range"] => vec!["Range"],
                     ["collections", "bytes"] => vec!["Bytes"],
+                    ["collections", "weak"] => vec!["WeakRef", "WeakMap", "FinalizationRegistry"],
                     ["collections", "iterable"] => vec!["Iterable"],
                     _ => vec![],
                 }
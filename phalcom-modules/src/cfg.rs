@@ -0,0 +1,238 @@
+//! Conditional compilation: `[features]`, feature-gated `[modules]` and
+//! `@cfg(...)` declarations.
+//!
+//! A project declares its features in `project.toml`:
+//!
+//! ```toml
+//! [features]
+//! default = ["fast-json"]
+//! fast-json = ["json/native"]   # also enables `native` on dependency `json`
+//! tracing = []
+//!
+//! [dependencies]
+//! json = { path = "../json", features = ["pretty"], default-features = false }
+//!
+//! [modules]
+//! "codec.native" = { feature = "fast-json" }
+//! "debug.checks" = { mode = "debug" }
+//! ```
+//!
+//! [`crate::ProjectUniverse`] resolves the active features of every project
+//! after loading its dependency graph, unioning what each dependent asks for.
+//! A [`CfgContext`] pairs them with the compile mode and host OS, and
+//! [`crate::ModuleResolver`] applies it before building interfaces: a module
+//! whose `[modules]` condition fails cannot be resolved or exposed, and a
+//! class or member carrying a failing `@cfg(feature: "x")` is removed along
+//! with its exports. Kept declarations lose their `@cfg` attributes, so the
+//! linker, checker, compiler and LSP all see the same filtered programs.
+
+use crate::error::InterfaceError;
+use crate::identity::{ModuleComponent, ModulePath};
+use phalcom_ast::ast::{Attribute, ClassMember, DependencyDecl, Expr, Program, Statement};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// The compile modes a `mode` condition may name, matching the CLI's
+/// `--release`/`--unchecked` flags.
+pub const CFG_MODES: [&str; 3] = ["debug", "release", "unchecked"];
+
+/// A conjunction of conditions, from a `[modules]` entry or a `@cfg(...)`
+/// attribute. Every condition present must hold.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CfgPredicate {
+    /// A feature of the declaring project that must be active.
+    pub feature: Option<String>,
+    /// The compile mode (one of [`CFG_MODES`]) that must be active.
+    pub mode: Option<String>,
+    /// The host operating system, as `std::env::consts::OS` names it.
+    pub os: Option<String>,
+}
+
+impl CfgPredicate {
+    /// Reads a `@cfg(feature: "x", mode: "release", os: "linux")` attribute.
+    pub fn from_attribute(attr: &Attribute) -> Result<Self, InterfaceError> {
+        let invalid = |reason: String| InterfaceError::InvalidCfg { reason, range: attr.range };
+        if !attr.args.is_empty() || attr.labeled_args.is_empty() {
+            return Err(invalid("expected labeled conditions, e.g. `@cfg(feature: \"json\")`".to_string()));
+        }
+        let mut predicate = Self::default();
+        for (label, value) in &attr.labeled_args {
+            let Expr::String { value, .. } = value else {
+                return Err(invalid(format!("`{label}:` must be a string literal")));
+            };
+            let slot = match label.as_str() {
+                "feature" => &mut predicate.feature,
+                "mode" => &mut predicate.mode,
+                "os" => &mut predicate.os,
+                _ => return Err(invalid(format!("unknown condition `{label}:`; expected `feature:`, `mode:` or `os:`"))),
+            };
+            if slot.replace(value.clone()).is_some() {
+                return Err(invalid(format!("condition `{label}:` is given twice")));
+            }
+        }
+        predicate.validate().map_err(invalid)?;
+        Ok(predicate)
+    }
+
+    /// Checks the parts that do not depend on a project: a condition is
+    /// present, and a `mode` names a real compile mode.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.feature.is_none() && self.mode.is_none() && self.os.is_none() {
+            return Err("a condition needs at least one of `feature`, `mode` or `os`".to_string());
+        }
+        match &self.mode {
+            Some(mode) if !CFG_MODES.contains(&mode.as_str()) => Err(format!("unknown mode '{mode}'; expected one of {}", CFG_MODES.join(", "))),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for CfgPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts = [("feature", &self.feature), ("mode", &self.mode), ("os", &self.os)]
+            .into_iter()
+            .filter_map(|(key, value)| value.as_ref().map(|value| format!("{key} = \"{value}\"")))
+            .collect::<Vec<_>>();
+        write!(f, "{{ {} }}", parts.join(", "))
+    }
+}
+
+/// Build-wide condition inputs: the compile mode, plus the features
+/// requested for the root projects on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CfgOptions {
+    /// One of [`CFG_MODES`].
+    pub mode: String,
+    /// Extra features to enable on every root project that declares them.
+    pub features: Vec<String>,
+    /// Whether root projects enable their `default` features.
+    pub default_features: bool,
+}
+
+impl Default for CfgOptions {
+    fn default() -> Self {
+        Self {
+            mode: "debug".to_string(),
+            features: Vec::new(),
+            default_features: true,
+        }
+    }
+}
+
+/// The conditions one project's modules are filtered under.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CfgContext {
+    /// Features the project declares; a condition naming any other is an error.
+    pub declared: BTreeSet<String>,
+    /// Features active for the project.
+    pub features: BTreeSet<String>,
+    pub mode: String,
+    pub os: String,
+    /// The project's `[modules]` gates.
+    pub modules: BTreeMap<ModulePath, CfgPredicate>,
+}
+
+impl CfgContext {
+    /// A context with no features or gates, for standalone and builtin modules.
+    pub fn plain(mode: &str) -> Self {
+        Self {
+            mode: mode.to_string(),
+            os: std::env::consts::OS.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Whether every condition of `predicate` holds.
+    pub fn holds(&self, predicate: &CfgPredicate) -> bool {
+        predicate.feature.as_ref().is_none_or(|feature| self.features.contains(feature))
+            && predicate.mode.as_ref().is_none_or(|mode| *mode == self.mode)
+            && predicate.os.as_ref().is_none_or(|os| *os == self.os)
+    }
+
+    /// The `[modules]` gate that disables `path` — its own, or an enclosing
+    /// package's — if any.
+    pub fn disabled_by(&self, path: &ModulePath) -> Option<(ModulePath, &CfgPredicate)> {
+        let mut current = Some(path.clone());
+        while let Some(path) = current {
+            if let Some(predicate) = self.modules.get(&path).filter(|predicate| !self.holds(predicate)) {
+                return Some((path, predicate));
+            }
+            current = path.parent();
+        }
+        None
+    }
+
+    /// Removes the parts of `program` (the module at `path`) these
+    /// conditions disable: `expose`s of disabled child modules, classes and
+    /// members whose `@cfg` fails, and exports of removed classes. Kept
+    /// declarations lose their `@cfg` attributes.
+    pub fn filter_program(&self, path: &ModulePath, program: &mut Program) -> Result<(), InterfaceError> {
+        program.preamble.dependencies.retain(|dependency| match dependency {
+            DependencyDecl::Expose(expose) => {
+                ModuleComponent::from_identifier(&expose.child.name).map_or(true, |child| self.disabled_by(&path.join(child)).is_none())
+            }
+            _ => true,
+        });
+
+        let mut removed = BTreeSet::new();
+        let mut kept = BTreeSet::new();
+        let mut statements = Vec::with_capacity(program.statements.len());
+        for mut statement in std::mem::take(&mut program.statements) {
+            match &mut statement {
+                Statement::Class(class) => {
+                    if !self.keep(&mut class.attributes)? {
+                        removed.insert(class.name.clone());
+                        continue;
+                    }
+                    kept.insert(class.name.clone());
+                    self.filter_members(&mut class.members)?;
+                }
+                Statement::Extend(extend) => self.filter_members(&mut extend.members)?,
+                _ => {}
+            }
+            statements.push(statement);
+        }
+
+        for statement in &mut statements {
+            if let Statement::Export(export) = statement {
+                export
+                    .items
+                    .retain(|item| !removed.contains(&item.local_or_remote_name) || kept.contains(&item.local_or_remote_name));
+            }
+        }
+        statements.retain(|statement| !matches!(statement, Statement::Export(export) if export.items.is_empty()));
+        program.statements = statements;
+        Ok(())
+    }
+
+    fn filter_members(&self, members: &mut Vec<ClassMember>) -> Result<(), InterfaceError> {
+        let mut kept = Vec::with_capacity(members.len());
+        for mut member in std::mem::take(members) {
+            if self.keep(member.attributes_mut())? {
+                kept.push(member);
+            }
+        }
+        *members = kept;
+        Ok(())
+    }
+
+    /// Evaluates and strips the `@cfg` attributes in `attributes`, returning
+    /// whether the declaration they annotate is kept.
+    fn keep(&self, attributes: &mut Vec<Attribute>) -> Result<bool, InterfaceError> {
+        let mut keep = true;
+        for attr in attributes.iter().filter(|attr| attr.name == "cfg") {
+            let predicate = CfgPredicate::from_attribute(attr)?;
+            if let Some(feature) = predicate.feature.as_ref().filter(|feature| !self.declared.contains(*feature)) {
+                return Err(InterfaceError::InvalidCfg {
+                    reason: format!("feature '{feature}' is not declared in [features]"),
+                    range: attr.range,
+                });
+            }
+            keep &= self.holds(&predicate);
+        }
+        attributes.retain(|attr| attr.name != "cfg");
+        Ok(keep)
+    }
+}
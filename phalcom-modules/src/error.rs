@@ -30,6 +30,39 @@ pub enum ProjectError {
     #[error("Unresolved package dependency '{package}' ({version_requirement})")]
     UnresolvedPackageDependency { package: String, version_requirement: String },
 
+    #[error("Conflicting requirements for package '{package}': {explanation}")]
+    DependencyConflict { package: String, explanation: String },
+
+    #[error("Invalid lockfile '{path}': {reason}")]
+    InvalidLockfile { path: PathBuf, reason: String },
+
+    #[error("Lockfile '{path}' is out of date: {changes}")]
+    LockfileDrift { path: PathBuf, changes: String },
+
+    #[error("Invalid package registry at '{root}': {reason}")]
+    InvalidRegistry { root: PathBuf, reason: String },
+
+    #[error("Invalid vendor directory '{root}': {reason}")]
+    InvalidVendorDirectory { root: PathBuf, reason: String },
+
+    #[error("Vendored package '{package}' does not match phalcom.lock: expected {expected}, found {found}")]
+    VendorChecksumMismatch { package: String, expected: String, found: String },
+
+    #[error("Cannot resolve dependency '{dependency}' offline: {reason}")]
+    OfflineResolution { dependency: String, reason: String },
+
+    #[error("Invalid workspace at '{root}': {reason}")]
+    InvalidWorkspace { root: PathBuf, reason: String },
+
+    #[error("Project '{project}' cannot be packaged: {reason}")]
+    NotPublishable { project: String, reason: String },
+
+    #[error("Invalid package archive '{path}': {reason}")]
+    InvalidPackageArchive { path: PathBuf, reason: String },
+
+    #[error("Project '{project}' has no feature '{feature}'")]
+    UnknownFeature { project: String, feature: String },
+
     #[error("Project path dependency not found: {0}")]
     PathDependencyNotFound(PathBuf),
 
@@ -89,6 +122,9 @@ pub enum ModuleResolutionError {
     #[error("Duplicate source identity: '{0}'")]
     DuplicateSourceIdentity(String),
 
+    #[error("Module '{path}' in project '{project}' is disabled by its [modules] condition {condition}")]
+    ModuleDisabled { path: String, project: String, condition: String },
+
     #[error("Module path not exposed: '{path}' in project '{project}' is private")]
     ModulePathNotExposed { path: String, project: String, exposed: Vec<String> },
 
@@ -180,6 +216,9 @@ pub enum InterfaceError {
 
     #[error("Module attribute outside header: @! attributes must appear at the very top of the file before imports")]
     ModuleAttributeOutsideHeader(SourceRange),
+
+    #[error("Invalid @cfg attribute: {reason}")]
+    InvalidCfg { reason: String, range: SourceRange },
 }
 
 /// Errors raised while validating the linked module graph.
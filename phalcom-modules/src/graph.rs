@@ -6,7 +6,7 @@
 
 use crate::identity::ModuleId;
 use phalcom_common::range::SourceRange;
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::hash::Hash;
 
 /// Phase required by a resolved dependency.
@@ -157,6 +157,34 @@ impl ReferenceGraph {
         }
         nodes.into_iter().collect()
     }
+
+    /// Returns a shortest chain of references leading from `from` to `to`,
+    /// or `None` when `to` is not reachable. Ties resolve to the first edge
+    /// in source order, so the answer is deterministic.
+    pub fn import_chain(&self, from: &ModuleId, to: &ModuleId) -> Option<Vec<ReferenceEdge>> {
+        let mut via: BTreeMap<&ModuleId, &ReferenceEdge> = BTreeMap::new();
+        let mut seen = BTreeSet::from([from]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(module) = queue.pop_front() {
+            if module == to {
+                let mut chain = Vec::new();
+                let mut current = to;
+                while let Some(edge) = via.get(current) {
+                    chain.push((*edge).clone());
+                    current = &edge.from;
+                }
+                chain.reverse();
+                return Some(chain);
+            }
+            for edge in self.edges_from(module) {
+                if seen.insert(&edge.to) {
+                    via.insert(&edge.to, edge);
+                    queue.push_back(&edge.to);
+                }
+            }
+        }
+        None
+    }
 }
 
 /// Graph of semantic/interface dependencies.
@@ -248,15 +276,22 @@ impl RuntimeDependencyGraph {
         nodes.into_iter().collect()
     }
 
+    /// Returns every cycle [`Self::validate_acyclic`] rejects: each strongly
+    /// connected component with more than one module, or one module that
+    /// depends on itself.
+    pub fn cycles(&self) -> Vec<Vec<ModuleId>> {
+        self.components()
+            .into_iter()
+            .filter(|component| component.len() > 1 || self.has_self_edge(&component[0]))
+            .collect()
+    }
+
     /// Validates that eager initialization dependencies form a DAG.
     pub fn validate_acyclic(&self) -> Result<(), crate::error::ModuleGraphError> {
-        let components = self.components();
-        for component in components {
-            if component.len() > 1 || self.has_self_edge(&component[0]) {
-                return Err(crate::error::ModuleGraphError::RuntimeCycle { cycle: component });
-            }
+        match self.cycles().into_iter().next() {
+            Some(cycle) => Err(crate::error::ModuleGraphError::RuntimeCycle { cycle }),
+            None => Ok(()),
         }
-        Ok(())
     }
 
     /// Returns a deterministic initialization order with dependencies first.
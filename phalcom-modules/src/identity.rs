@@ -12,6 +12,11 @@ use std::sync::atomic::{AtomicU64, Ordering};
 pub enum BuiltinProject {
     Universe,
     Std,
+    /// Modules of native classes contributed by extension crates
+    /// (`phalcom_native_meta::EXTENSION_CLASSES`). Unlike `universe` and
+    /// `std`, each module's path keeps its own import root: `import
+    /// acme.crypto` names the module `native:acme.crypto`.
+    Native,
 }
 
 /// Type alias for staged vocabulary convergence (§7).
@@ -22,8 +27,30 @@ impl BuiltinProject {
         match self {
             Self::Universe => "universe",
             Self::Std => "std",
+            Self::Native => "native",
         }
     }
+
+    /// The builtin project an absolute import rooted at `root` names, if
+    /// any: `universe`, `std`, or the first component of a linked extension
+    /// module.
+    pub fn for_import_root(root: &str) -> Option<Self> {
+        match root {
+            "universe" => Some(Self::Universe),
+            "std" => Some(Self::Std),
+            _ if phalcom_native_meta::is_extension_root(root) => Some(Self::Native),
+            _ => None,
+        }
+    }
+
+    /// The module an absolute import `root.segments…` of this project names.
+    pub fn import_target(self, root: ModuleComponent, segments: Vec<ModuleComponent>) -> ModuleId {
+        let path = match self {
+            Self::Native => ModulePath::from_components(std::iter::once(root).chain(segments).collect::<Vec<_>>()),
+            Self::Universe | Self::Std => ModulePath::from_components(segments),
+        };
+        ModuleId::builtin(self, path)
+    }
 }
 
 impl fmt::Display for BuiltinProject {
@@ -323,6 +350,17 @@ impl ModuleId {
         )
     }
 
+    /// The `native` module of an extension class, from its dotted
+    /// `NativeClassSpec::module` path. The registry validates those paths, so
+    /// an invalid one is a programming error.
+    pub fn native(module: &str) -> Self {
+        let components = module
+            .split('.')
+            .map(|part| ModuleComponent::from_identifier(part).expect("extension module paths are validated identifiers"))
+            .collect::<Vec<_>>();
+        Self::builtin(BuiltinProject::Native, ModulePath::from_components(components))
+    }
+
     /// Synthetic module identity. The path does not participate in allocation;
     /// callers must supply an ID from a monotonic allocator.
     pub fn synthetic(project: SyntheticProjectId, path: ModulePath) -> Self {
@@ -3,8 +3,12 @@ use crate::error::InterfaceError;
 use crate::identity::{ModuleComponent, ModuleId};
 use crate::metadata::ModuleMetadata;
 use crate::source::ModuleKind;
-use phalcom_ast::ast::{BindingKind, DependencyDecl, ImportDecl, ImportPath, ModuleImportDecl, Pattern, Program, ReExportDecl, SelectiveImportDecl, Statement};
+use phalcom_ast::ast::{
+    AttrKind, Attribute, BindingKind, BuiltinAttr, ClassMember, DependencyDecl, ImportDecl, ImportPath, ModuleImportDecl, Pattern, Program, ReExportDecl,
+    SelectiveImportDecl, Statement, StaticSymbolRef,
+};
 use phalcom_common::range::SourceRange;
+use phalcom_common::selector::{Selector, SelectorSlot};
 use std::collections::{BTreeMap, BTreeSet};
 
 /// Surface declaration for a top-level binding defined in a module.
@@ -25,6 +29,21 @@ pub struct ExportSurface {
     pub range: SourceRange,
 }
 
+/// Surface of one member an `extend` block adds to an existing class.
+///
+/// The linker needs only the class reference, the side and the selector to
+/// find two extensions one importer would see for the same send.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtensionSurface {
+    /// The extended class as written in the declaring module.
+    pub target: StaticSymbolRef,
+    /// Whether the member is class-side (`@class`).
+    pub is_static: bool,
+    /// Canonical selector of the member.
+    pub selector: String,
+    pub range: SourceRange,
+}
+
 /// Export target before import paths have been resolved to module identities.
 #[derive(Clone, Debug, PartialEq)]
 pub enum UnlinkedExportTarget {
@@ -92,6 +111,8 @@ pub struct UnlinkedModuleInterface {
     pub exports: BTreeMap<String, ExportSurface>,
     pub imports: Vec<ImportSurface>,
     pub exposed_children: BTreeSet<ModuleComponent>,
+    /// Members of the module's `extend` blocks, in source order.
+    pub extensions: Vec<ExtensionSurface>,
     pub metadata: ModuleMetadata,
 }
 
@@ -279,6 +300,21 @@ impl InterfaceBuilder {
             }
         }
 
+        let extensions = program
+            .statements
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::Extend(extend_def) => Some(extend_def),
+                _ => None,
+            })
+            .flat_map(|extend_def| {
+                extend_def
+                    .members
+                    .iter()
+                    .filter_map(|member| Self::extension_surface(&extend_def.target, member))
+            })
+            .collect();
+
         Ok(UnlinkedModuleInterface {
             id,
             kind,
@@ -286,10 +322,36 @@ impl InterfaceBuilder {
             exports,
             imports,
             exposed_children,
+            extensions,
             metadata,
         })
     }
 
+    /// The surface of an `extend` block member, or `None` for a member the
+    /// compiler refuses there anyway (fields, constructors, subscripts).
+    fn extension_surface(target: &StaticSymbolRef, member: &ClassMember) -> Option<ExtensionSurface> {
+        let is_class_side = |attributes: &[Attribute]| attributes.iter().any(|attr| matches!(attr.kind, AttrKind::Builtin(BuiltinAttr::Class)));
+        let (selector, is_static, range) = match member {
+            ClassMember::Method(method) if !method.is_constructor => {
+                let slots: Vec<SelectorSlot> = method
+                    .params
+                    .iter()
+                    .map(|param| param.label.clone().map_or(SelectorSlot::Positional, SelectorSlot::Label))
+                    .collect();
+                (Selector::method(&method.name, slots), is_class_side(&method.attributes), method.range)
+            }
+            ClassMember::Getter(getter) => (Selector::getter(&getter.name), is_class_side(&getter.attributes), getter.range),
+            ClassMember::Setter(setter) => (Selector::setter(&setter.name), is_class_side(&setter.attributes), setter.range),
+            _ => return None,
+        };
+        Some(ExtensionSurface {
+            target: target.clone(),
+            is_static,
+            selector: selector.ok()?.encode(),
+            range,
+        })
+    }
+
     fn validate_dunder(name: &str, role: DunderRole, range: SourceRange) -> Result<(), InterfaceError> {
         DunderPolicy::default()
             .validate_user_declaration(name, role)
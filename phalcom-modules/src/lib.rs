@@ -1,8 +1,10 @@
 //! `phalcom-modules` — logical module system, project manifests, source identity, and path visibility for Phalcom.
 
 pub mod artifact;
+pub mod audit;
 pub mod builtin;
 pub mod builtin_interface;
+pub mod cfg;
 pub mod declaration;
 pub mod dunder;
 pub mod error;
@@ -10,17 +12,25 @@ pub mod graph;
 pub mod identity;
 pub mod interface;
 pub mod linker;
+pub mod lockfile;
 pub mod manifest;
 pub mod metadata;
+pub mod package_archive;
 pub mod package_info;
 pub mod project;
+pub mod registry;
 pub mod resolver;
+pub mod semver;
 pub mod source;
 pub mod stabilization;
+pub mod vendor;
+pub mod workspace;
 
 // Re-export common types
 pub use artifact::{PackageArtifactProvider, ResolvedPackageArtifact, ResolvedPackageId as PublishedPackageId};
+pub use audit::{UnusedImport, unused_imports};
 pub use builtin::{BuiltinNodeSpec, BuiltinProjectSourceProvider, UNIVERSE_NODES};
+pub use cfg::{CfgContext, CfgOptions, CfgPredicate};
 pub use declaration::{DeclarationBlueprint, DeclarationId, DeclarationKind, DeclarationRealizationError, DeclarationShell, DeclarationShellTable, ShellState};
 pub use dunder::{DunderCategory, DunderPolicy, DunderPolicyError, DunderRole};
 pub use error::{InterfaceError, ModuleGraphError, ModuleLoadError, ModuleResolutionError, ProjectError, SourceError};
@@ -40,12 +50,18 @@ pub use linker::{
     GlobalBindingId, ImportBindingId, LinkError, LinkedModule, LinkedProgram, LinkedReadSpec, ModuleBindingLayout, ModuleLinker, SymbolId, dependency_phase,
     module_path, resolution_key,
 };
-pub use manifest::{DependencyProvider, DependencySpec, NullDependencyProvider, ProjectManifest, ValidatedProjectManifest};
+pub use lockfile::{LockMode, LockOptions, LockPins, LockedPackage, Lockfile, Refresh};
+pub use manifest::{DependencyFeatures, DependencyProvider, DependencySpec, NullDependencyProvider, ProjectManifest, ValidatedProjectManifest};
 pub use metadata::{MetadataTarget, ModuleMetadata, ModuleMetadataAttribute};
+pub use package_archive::PackageArchive;
 pub use package_info::{
     PackageArtifactIdentity, PackageAuthorDescriptor, PackageInfoDescriptor, PackageOrigin, PackageRequirementDescriptor, ResolvedProjectDependencyDescriptor,
 };
-pub use project::{ProjectUniverse, ResolvedProject, discover_owning_project};
+pub use project::{ProjectLoadOptions, ProjectOrigin, ProjectUniverse, ResolvedProject, discover_owning_project};
+pub use registry::{DirectoryRegistryProvider, PackageSource, SelectedPackage};
 pub use resolver::ModuleResolver;
-pub use source::{EntryOwnership, FilesystemSourceProvider, ModuleKind, ParsedModuleUnit, SourceProvider, SourceUnit};
+pub use semver::{SemverError, Version, VersionReq};
+pub use source::{EntryOwnership, FilesystemSourceProvider, ModuleKind, OverlaySourceProvider, ParsedModuleUnit, SourceProvider, SourceUnit};
 pub use stabilization::{ResolvedDocumentIdentity, ResolverGeneration};
+pub use vendor::VendorDependencyProvider;
+pub use workspace::Workspace;
@@ -59,6 +59,9 @@ pub struct LinkedModule {
     pub linked_reads: Vec<LinkedReadSpec>,
     /// Runtime dependencies in initialization order-independent form.
     pub runtime_dependencies: Vec<ModuleId>,
+    /// Directly imported modules whose `extend` blocks are visible here, in
+    /// import order.
+    pub extension_sources: Vec<ModuleId>,
 }
 
 /// A closed, statically linked program plan.
@@ -98,6 +101,16 @@ pub enum LinkError {
     /// A re-export loop cannot produce a canonical symbol.
     #[error("cyclic re-export involving module {module} and '{name}'")]
     CyclicReExport { module: ModuleId, name: String },
+    /// Two extensions visible in one module add the same selector to the
+    /// same class. `range` is the second module's extension.
+    #[error("modules {first} and {second} both extend '{member}' for one importer")]
+    ExtensionConflict {
+        /// `Class#selector`, with `.class` after a class-side target.
+        member: String,
+        first: ModuleId,
+        second: ModuleId,
+        range: SourceRange,
+    },
     /// Runtime graph validation failed.
     #[error(transparent)]
     RuntimeCycle(#[from] ModuleGraphError),
@@ -107,13 +120,31 @@ pub enum LinkError {
 pub struct ModuleLinker {
     universe: Arc<ProjectUniverse>,
     interfaces: BTreeMap<ModuleId, UnlinkedModuleInterface>,
+    published: BTreeMap<ModuleId, LinkedModuleInterface>,
 }
 
 impl ModuleLinker {
     /// Creates a linker. The interface map is the complete source universe for
     /// this linking generation; no source is parsed during linking.
     pub fn new(universe: Arc<ProjectUniverse>, interfaces: BTreeMap<ModuleId, UnlinkedModuleInterface>) -> Self {
-        Self { universe, interfaces }
+        Self {
+            universe,
+            interfaces,
+            published: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the already-linked interfaces of published packages. Their
+    /// modules are linked as they were published: importers resolve their
+    /// exports, and they import nothing this linker has to follow.
+    pub fn with_published(mut self, interfaces: impl IntoIterator<Item = (ModuleId, LinkedModuleInterface)>) -> Self {
+        self.published.extend(interfaces);
+        self
+    }
+
+    /// Whether `module` has a source or a published interface.
+    fn knows(&self, module: &ModuleId) -> bool {
+        self.interfaces.contains_key(module) || self.published.contains_key(module)
     }
 
     /// Links all interfaces using `(importer, logical path)` resolutions.
@@ -128,7 +159,12 @@ impl ModuleLinker {
             .filter(|(module, _)| reachable.contains(*module))
             .map(|(module, interface)| (module.clone(), interface.clone()))
             .collect();
-        let reachable_linker = ModuleLinker::new(self.universe.clone(), reachable_interfaces);
+        let reachable_published = self
+            .published
+            .iter()
+            .filter(|(module, _)| reachable.contains(*module))
+            .map(|(module, interface)| (module.clone(), interface.clone()));
+        let reachable_linker = ModuleLinker::new(self.universe.clone(), reachable_interfaces).with_published(reachable_published);
         let mut context = LinkContext::new(&reachable_linker, resolved);
         context.build().map(|(modules, graphs, initialization_order)| LinkedProgram {
             universe: self.universe.clone(),
@@ -139,6 +175,15 @@ impl ModuleLinker {
         })
     }
 
+    /// Builds the reference, semantic, and runtime graphs of every interface
+    /// without linking a program. Unlike [`Self::link`], a runtime cycle is
+    /// not an error here, so tooling can still show the graph that has one.
+    pub fn graphs(&self, resolved: &BTreeMap<(ModuleId, String), ModuleId>) -> Result<ModuleGraphs, LinkError> {
+        let mut context = LinkContext::new(self, resolved);
+        context.collect_imports_and_graphs()?;
+        Ok(context.graphs)
+    }
+
     fn reachable_interfaces(&self, entry: &ModuleId, resolved: &BTreeMap<(ModuleId, String), ModuleId>) -> Result<BTreeSet<ModuleId>, LinkError> {
         if !self.interfaces.contains_key(entry) {
             return Err(LinkError::MissingModule { module: entry.clone() });
@@ -159,7 +204,7 @@ impl ModuleLinker {
         }
 
         while let Some(module) = pending.pop() {
-            let interface = self.interfaces.get(&module).expect("reachable interface exists");
+            let Some(interface) = self.interfaces.get(&module) else { continue };
             for import in &interface.imports {
                 let path = match import {
                     ImportSurface::Module(decl) => (&decl.path, decl.range),
@@ -169,7 +214,7 @@ impl ModuleLinker {
                 let target = resolved
                     .get(&(module.clone(), path.0.to_string()))
                     .cloned()
-                    .filter(|target| self.interfaces.contains_key(target))
+                    .filter(|target| self.knows(target))
                     .ok_or_else(|| LinkError::UnresolvedImport {
                         module: module.clone(),
                         path: path.0.to_string(),
@@ -182,13 +227,13 @@ impl ModuleLinker {
                     let mut curr_path = target.path.parent();
                     while let Some(parent) = curr_path {
                         let pkg_id = ModuleId::resolved(project_id, parent.clone());
-                        if self.interfaces.contains_key(&pkg_id) && reachable.insert(pkg_id.clone()) {
+                        if self.knows(&pkg_id) && reachable.insert(pkg_id.clone()) {
                             pending.push(pkg_id);
                         }
                         curr_path = parent.parent();
                     }
                     let root_id = ModuleId::resolved(project_id, ModulePath::root());
-                    if self.interfaces.contains_key(&root_id) && reachable.insert(root_id.clone()) {
+                    if self.knows(&root_id) && reachable.insert(root_id.clone()) {
                         pending.push(root_id);
                     }
                 }
@@ -306,6 +351,14 @@ struct LinkContext<'a> {
 
 type LinkBuild = (BTreeMap<ModuleId, LinkedModule>, ModuleGraphs, Vec<ModuleId>);
 
+/// Identity of an extended class: its canonical symbol, or the bare name of a
+/// class no module declares (a builtin).
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum ExtensionTarget {
+    Symbol(SymbolId),
+    Builtin(String),
+}
+
 impl<'a> LinkContext<'a> {
     fn new(linker: &'a ModuleLinker, resolved: &'a BTreeMap<(ModuleId, String), ModuleId>) -> Self {
         Self {
@@ -336,6 +389,18 @@ impl<'a> LinkContext<'a> {
 
         let initialization_order = self.graphs.runtime.initialization_order()?;
         let mut modules = BTreeMap::new();
+        for (module, interface) in &self.linker.published {
+            modules.insert(
+                module.clone(),
+                LinkedModule {
+                    interface: interface.clone(),
+                    bindings: ModuleBindingLayout::default(),
+                    linked_reads: Vec::new(),
+                    runtime_dependencies: Vec::new(),
+                    extension_sources: Vec::new(),
+                },
+            );
+        }
         for module in module_ids {
             let interface = self.linked_interface(&module)?;
             let mut bindings = ModuleBindingLayout::default();
@@ -364,6 +429,8 @@ impl<'a> LinkContext<'a> {
                 .collect::<Vec<_>>();
             runtime_dependencies.sort();
             runtime_dependencies.dedup();
+            let extension_sources = self.extension_sources(&module)?;
+            self.check_extension_conflicts(&module, &extension_sources)?;
             modules.insert(
                 module,
                 LinkedModule {
@@ -371,6 +438,7 @@ impl<'a> LinkContext<'a> {
                     bindings,
                     linked_reads,
                     runtime_dependencies,
+                    extension_sources,
                 },
             );
         }
@@ -378,6 +446,10 @@ impl<'a> LinkContext<'a> {
     }
 
     fn collect_imports_and_graphs(&mut self) -> Result<(), LinkError> {
+        for module in self.linker.published.keys() {
+            self.graphs.references.add_node(module.clone());
+            self.graphs.runtime.add_node(module.clone());
+        }
         for (module, interface) in &self.linker.interfaces {
             self.graphs.references.add_node(module.clone());
             self.graphs.runtime.add_node(module.clone());
@@ -525,6 +597,84 @@ impl<'a> LinkContext<'a> {
         Ok(())
     }
 
+    /// The modules `module` imports directly, in import order: the only
+    /// modules besides itself whose extensions its sends see.
+    fn extension_sources(&self, module: &ModuleId) -> Result<Vec<ModuleId>, LinkError> {
+        let mut sources: Vec<ModuleId> = Vec::new();
+        let Some(interface) = self.linker.interfaces.get(module) else {
+            return Ok(sources);
+        };
+        for import in &interface.imports {
+            let (path, range) = match import {
+                ImportSurface::Module(decl) => (&decl.path, decl.range),
+                ImportSurface::Selective(decl) => (&decl.path, decl.range),
+                ImportSurface::ReExport(decl) => (&decl.path, decl.range),
+            };
+            let target = self.target(module, path, range)?;
+            if &target != module && !sources.contains(&target) {
+                sources.push(target);
+            }
+        }
+        Ok(sources)
+    }
+
+    /// Rejects two visible extensions of one class side and selector coming
+    /// from different modules. The runtime would pick the first source; the
+    /// linker refuses to let import order decide.
+    fn check_extension_conflicts(&self, module: &ModuleId, sources: &[ModuleId]) -> Result<(), LinkError> {
+        let mut seen: BTreeMap<(ExtensionTarget, bool, &str), &ModuleId> = BTreeMap::new();
+        for source in std::iter::once(module).chain(sources) {
+            let Some(interface) = self.linker.interfaces.get(source) else { continue };
+            for extension in &interface.extensions {
+                let target = self.extension_target(source, &extension.target);
+                match seen.get(&(target.clone(), extension.is_static, extension.selector.as_str())) {
+                    Some(first) if *first != source => {
+                        return Err(LinkError::ExtensionConflict {
+                            member: format!(
+                                "{}{}#{}",
+                                extension.target.leaf_name(),
+                                if extension.is_static { ".class" } else { "" },
+                                extension.selector
+                            ),
+                            first: (*first).clone(),
+                            second: source.clone(),
+                            range: extension.range,
+                        });
+                    }
+                    Some(_) => {}
+                    None => {
+                        seen.insert((target, extension.is_static, extension.selector.as_str()), source);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Canonical identity of the class `reference` names in `module`.
+    fn extension_target(&self, module: &ModuleId, reference: &StaticSymbolRef) -> ExtensionTarget {
+        if reference.is_bare() {
+            let declared = self
+                .linker
+                .interfaces
+                .get(module)
+                .is_some_and(|interface| interface.declarations.contains_key(&reference.root));
+            if declared {
+                return ExtensionTarget::Symbol(SymbolId {
+                    module: module.clone(),
+                    name: reference.root.clone().into_boxed_str(),
+                });
+            }
+            return match self.import_symbols.get(&(module.clone(), reference.root.clone())) {
+                Some(Some(symbol)) => ExtensionTarget::Symbol(symbol.clone()),
+                _ => ExtensionTarget::Builtin(reference.root.clone()),
+            };
+        }
+        self.linker
+            .resolve_static_symbol(module, reference, self.resolved)
+            .map_or_else(|_| ExtensionTarget::Builtin(reference.leaf_name().to_string()), ExtensionTarget::Symbol)
+    }
+
     fn add_import(&mut self, module: &ModuleId, local: String, target: LinkedReadSpec, symbol: Option<SymbolId>, range: SourceRange) -> Result<(), LinkError> {
         let key = (module.clone(), local.clone());
         if self.import_targets.contains_key(&key) {
@@ -543,7 +693,7 @@ impl<'a> LinkContext<'a> {
         self.resolved
             .get(&(module.clone(), path.to_string()))
             .cloned()
-            .filter(|target| self.linker.interfaces.contains_key(target))
+            .filter(|target| self.linker.knows(target))
             .ok_or_else(|| LinkError::UnresolvedImport {
                 module: module.clone(),
                 path: path.to_string(),
@@ -556,6 +706,13 @@ impl<'a> LinkContext<'a> {
         if let Some(export) = self.linked_exports.get(&key) {
             return Ok(export.clone());
         }
+        if let Some(published) = self.linker.published.get(module) {
+            return published.exports.get(name).cloned().ok_or_else(|| LinkError::MissingExport {
+                module: module.clone(),
+                name: name.to_string(),
+                range: SourceRange::default(),
+            });
+        }
         if !self.resolving_exports.insert(key.clone()) {
             return Err(LinkError::CyclicReExport {
                 module: module.clone(),
@@ -650,7 +807,7 @@ pub fn module_path(module: &ModuleId) -> String {
     ModulePath::from_components(module.path.components().to_vec()).to_string()
 }
 
-fn default_module_binding(path: &ImportPath) -> Option<&str> {
+pub(crate) fn default_module_binding(path: &ImportPath) -> Option<&str> {
     path.segments.last().map(|segment| segment.name.as_str()).or(match &path.root {
         phalcom_ast::ast::ImportRoot::Absolute(segment) => Some(segment.name.as_str()),
         phalcom_ast::ast::ImportRoot::Relative { .. } => None,
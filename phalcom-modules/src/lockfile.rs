@@ -0,0 +1,391 @@
+//! `phalcom.lock`: the recorded dependency graph of a root project.
+//!
+//! The lockfile sits next to the root `project.toml` and lists every project
+//! the root depends on, directly or not: its id (`name version`), version,
+//! source, the ids of its own dependencies, a hash of its public
+//! interface and, once `phalcom vendor` copied it, a checksum of its source
+//! tree. [`ProjectUniverse::load_root_locked`] reads it before resolving,
+//! pins registry packages to their locked versions, and compares the graph it
+//! loaded against it afterwards; what happens on a difference is the
+//! [`LockMode`].
+//!
+//! The interface hash covers what consumers can see of a package: the modules
+//! reachable through `expose` from its root package, and for each one its
+//! exposed children, exports and `extend` members. Editing a dependency's
+//! implementation leaves the lock alone; changing its API is drift.
+//!
+//! The file is TOML, written with packages sorted by name and paths relative
+//! to the root project, so it is byte-identical across machines.
+//!
+//! [`ProjectUniverse::load_root_locked`]: crate::project::ProjectUniverse::load_root_locked
+
+use crate::error::ProjectError;
+use crate::identity::{ModuleId, ModulePath, ResolvedProjectId};
+use crate::interface::UnlinkedExportTarget;
+use crate::project::{ProjectOrigin, ProjectUniverse};
+use crate::resolver::ModuleResolver;
+use crate::semver::Version;
+use crate::source::FilesystemSourceProvider;
+use crate::workspace::Workspace;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::path::{Component, Path, PathBuf};
+
+/// File name of the lockfile, next to the root `project.toml`.
+pub const LOCKFILE_NAME: &str = "phalcom.lock";
+
+/// Current lockfile format version.
+pub const LOCKFILE_VERSION: u32 = 1;
+
+const HEADER: &str = "# This file is generated by phalcom. Do not edit it by hand.\n\n";
+
+/// The lockfile of the project in `project_dir`: the one at its workspace
+/// root when it belongs to a workspace (see [`crate::workspace`]).
+pub fn lockfile_path(project_dir: &Path) -> Result<PathBuf, ProjectError> {
+    let root = Workspace::enclosing(project_dir)?.map_or_else(|| project_dir.to_path_buf(), |workspace| workspace.root);
+    Ok(root.join(LOCKFILE_NAME))
+}
+
+/// How loading treats a lockfile that no longer matches the resolved graph.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LockMode {
+    /// Prefer locked versions, and leave the lockfile alone when the graph
+    /// changed. Library, editor and script loads use this: only project
+    /// commands write `phalcom.lock`.
+    #[default]
+    ReadOnly,
+    /// Prefer locked versions, and rewrite the lockfile when the graph changed.
+    Update,
+    /// Prefer locked versions, and fail instead of rewriting (`--locked`).
+    Locked,
+    /// Use only locked registry versions, and fail on any difference or a
+    /// missing lockfile (`--frozen`).
+    Frozen,
+}
+
+/// Which locked versions to forget before resolving (`phalcom update`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Refresh {
+    #[default]
+    Nothing,
+    Everything,
+    Packages(BTreeSet<String>),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LockOptions {
+    pub mode: LockMode,
+    pub refresh: Refresh,
+    /// Resolve dependencies only from the `vendor/` directory (`--offline`);
+    /// see [`crate::vendor`].
+    pub offline: bool,
+}
+
+/// Registry versions a provider must prefer while resolving.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LockPins {
+    pub versions: BTreeMap<String, Version>,
+    /// Only the pinned versions may be chosen; an unpinned package fails.
+    pub strict: bool,
+}
+
+impl LockPins {
+    /// Pins no package; resolution is unconstrained.
+    pub fn none() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Lockfile {
+    pub version: u32,
+    #[serde(default, rename = "package", skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<LockedPackage>,
+}
+
+/// One locked dependency.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct LockedPackage {
+    /// `name version`, or just `name` for an unversioned path project.
+    pub id: String,
+    pub name: String,
+    pub version: Option<String>,
+    /// `registry+<root>` or `path+<dir>`, relative to the root project.
+    pub source: String,
+    /// Ids of this package's direct dependencies, sorted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+    /// `sha256:<hex>` of the package's public interface.
+    pub interface_hash: String,
+    /// `sha256:<hex>` of the package's source tree as `phalcom vendor`
+    /// copied it; see [`crate::vendor::tree_checksum`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            packages: Vec::new(),
+        }
+    }
+}
+
+impl Lockfile {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let lockfile: Self = toml::from_str(text).map_err(|e| e.to_string())?;
+        if lockfile.version != LOCKFILE_VERSION {
+            return Err(format!("unsupported lockfile version {} (expected {LOCKFILE_VERSION})", lockfile.version));
+        }
+        Ok(lockfile)
+    }
+
+    /// Reads the lockfile at `path`; `Ok(None)` when there is none.
+    pub fn load(path: &Path) -> Result<Option<Self>, ProjectError> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let text = std::fs::read_to_string(path).map_err(|e| invalid(path, e.to_string()))?;
+        Self::parse(&text).map(Some).map_err(|reason| invalid(path, reason))
+    }
+
+    pub fn to_toml(&self) -> String {
+        format!("{HEADER}{}", toml::to_string(self).expect("lockfile serializes"))
+    }
+
+    /// Records every project reachable from `root` in `universe`, except `root` itself.
+    pub fn from_universe(universe: &ProjectUniverse, root: ResolvedProjectId) -> Self {
+        let root_dir = universe.get_project(root).map(|project| project.root_dir.clone()).unwrap_or_default();
+        Self::from_roots(universe, &[root], &root_dir)
+    }
+
+    /// Records every project reachable from any of `roots`, except the roots
+    /// themselves, with paths relative to `root_dir`. A workspace locks its
+    /// members this way.
+    pub fn from_roots(universe: &ProjectUniverse, roots: &[ResolvedProjectId], root_dir: &Path) -> Self {
+        let mut reachable = BTreeSet::new();
+        let mut pending = roots.to_vec();
+        while let Some(id) = pending.pop() {
+            if let Some(project) = universe.get_project(id) {
+                for dependency in project.dependencies.values() {
+                    if reachable.insert(*dependency) {
+                        pending.push(*dependency);
+                    }
+                }
+            }
+        }
+        for root in roots {
+            reachable.remove(root);
+        }
+
+        let mut packages: Vec<LockedPackage> = reachable
+            .into_iter()
+            .filter_map(|id| {
+                let project = universe.get_project(id)?;
+                let version = project.manifest.as_ref().and_then(|manifest| manifest.version.clone());
+                let source = match &project.origin {
+                    ProjectOrigin::Package { registry } => format!("registry+{}", relative_to(registry, root_dir)),
+                    ProjectOrigin::Vendored { source } => source.clone(),
+                    _ => format!("path+{}", relative_to(&project.root_dir, root_dir)),
+                };
+                let mut dependencies: Vec<String> = project
+                    .dependencies
+                    .values()
+                    .filter_map(|dependency| {
+                        universe
+                            .get_project(*dependency)
+                            .map(|project| package_id(&project.name, project_version(project)))
+                    })
+                    .collect();
+                dependencies.sort();
+                dependencies.dedup();
+                Some(LockedPackage {
+                    id: package_id(&project.name, version.as_deref()),
+                    name: project.name.clone(),
+                    version,
+                    source,
+                    dependencies,
+                    interface_hash: interface_hash(universe, id),
+                    checksum: None,
+                })
+            })
+            .collect();
+        packages.sort_by(|a, b| (&a.name, &a.version, &a.source).cmp(&(&b.name, &b.version, &b.source)));
+        Self {
+            version: LOCKFILE_VERSION,
+            packages,
+        }
+    }
+
+    /// Registry versions to pin for the next resolution under `options`.
+    pub fn pins(&self, options: &LockOptions) -> LockPins {
+        let versions = self
+            .packages
+            .iter()
+            .filter(|package| package.source.starts_with("registry+"))
+            .filter(|package| match &options.refresh {
+                Refresh::Nothing => true,
+                Refresh::Everything => false,
+                Refresh::Packages(names) => !names.contains(&package.name),
+            })
+            .filter_map(|package| Some((package.name.clone(), Version::parse(package.version.as_deref()?).ok()?)))
+            .collect();
+        LockPins {
+            versions,
+            strict: options.mode == LockMode::Frozen,
+        }
+    }
+
+    /// Copies the vendor checksums of `old` onto the packages of `self` that
+    /// kept their id and source, so rewriting the lock does not forget them.
+    pub fn keep_checksums(&mut self, old: &Lockfile) {
+        for package in &mut self.packages {
+            package.checksum = old
+                .packages
+                .iter()
+                .find(|before| before.id == package.id && before.source == package.source)
+                .and_then(|before| before.checksum.clone());
+        }
+    }
+
+    /// Human-readable differences from `self` (the old lock) to `new`, by package name.
+    pub fn changes(&self, new: &Lockfile) -> Vec<String> {
+        let old: BTreeMap<&str, &LockedPackage> = self.packages.iter().map(|package| (package.name.as_str(), package)).collect();
+        let current: BTreeMap<&str, &LockedPackage> = new.packages.iter().map(|package| (package.name.as_str(), package)).collect();
+        let mut changes = Vec::new();
+        for (name, before) in &old {
+            let Some(after) = current.get(name) else {
+                changes.push(format!("removed {}", before.id));
+                continue;
+            };
+            if before.version != after.version {
+                let show = |version: &Option<String>| version.clone().unwrap_or_else(|| "(unversioned)".to_string());
+                changes.push(format!("{name} {} -> {}", show(&before.version), show(&after.version)));
+            } else if before.source != after.source {
+                changes.push(format!("{} source {} -> {}", after.id, before.source, after.source));
+            } else if before.interface_hash != after.interface_hash {
+                changes.push(format!("{} interface changed", after.id));
+            } else if before.dependencies != after.dependencies {
+                changes.push(format!("{} dependencies changed", after.id));
+            }
+        }
+        for (name, after) in &current {
+            if !old.contains_key(name) {
+                changes.push(format!("added {} ({})", after.id, after.source));
+            }
+        }
+        changes
+    }
+}
+
+fn package_id(name: &str, version: Option<&str>) -> String {
+    match version {
+        Some(version) => format!("{name} {version}"),
+        None => name.to_string(),
+    }
+}
+
+fn project_version(project: &crate::project::ResolvedProject) -> Option<&str> {
+    project.manifest.as_ref().and_then(|manifest| manifest.version.as_deref())
+}
+
+/// `sha256:<hex>` over a canonical rendering of `project`'s exposed modules.
+/// A module that fails to load hashes as an error marker, so a broken
+/// dependency reads as drift rather than blocking the load.
+pub fn interface_hash(universe: &ProjectUniverse, project: ResolvedProjectId) -> String {
+    let provider = FilesystemSourceProvider::new();
+    let mut resolver = ModuleResolver::new(universe, &provider);
+    let mut canonical = String::new();
+    let mut pending = vec![ModulePath::root()];
+    let mut seen = BTreeSet::new();
+    while let Some(path) = pending.pop() {
+        if !seen.insert(path.clone()) {
+            continue;
+        }
+        let module = ModuleId::resolved(project, path.clone());
+        let interface = match resolver.load_interface(&module) {
+            Ok(interface) => interface,
+            Err(_) => {
+                let _ = writeln!(canonical, "module {path} unavailable");
+                continue;
+            }
+        };
+        let _ = writeln!(canonical, "module {path} {:?}", interface.kind);
+        for child in &interface.exposed_children {
+            let _ = writeln!(canonical, "  expose {child}");
+            let mut components = path.components().to_vec();
+            components.push(child.clone());
+            pending.push(ModulePath::from_components(components));
+        }
+        for export in interface.exports.values() {
+            let target = match &export.target {
+                UnlinkedExportTarget::Local(name) => match interface.declarations.get(name) {
+                    Some(declaration) if declaration.is_const => format!("const {name}"),
+                    Some(_) => format!("let {name}"),
+                    None => format!("import {name}"),
+                },
+                UnlinkedExportTarget::ReExport { path, remote } => format!("from {path} import {remote}"),
+            };
+            let _ = writeln!(canonical, "  export {} = {target}", export.exported_name);
+        }
+        let mut extensions: Vec<String> = interface
+            .extensions
+            .iter()
+            .map(|extension| {
+                format!(
+                    "  extend {:?} {} {}",
+                    extension.target,
+                    if extension.is_static { "class" } else { "instance" },
+                    extension.selector
+                )
+            })
+            .collect();
+        extensions.sort();
+        for extension in extensions {
+            let _ = writeln!(canonical, "{extension}");
+        }
+    }
+    sha256_hex(canonical.as_bytes())
+}
+
+/// `sha256:` followed by the lowercase hex digest of `bytes`.
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    let digest = Sha256::digest(bytes);
+    let mut hex = String::with_capacity(7 + digest.len() * 2);
+    hex.push_str("sha256:");
+    for byte in digest {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
+
+/// `path` relative to `base` with `/` separators; absolute when they share no root.
+pub(crate) fn relative_to(path: &Path, base: &Path) -> String {
+    let path_components: Vec<Component<'_>> = path.components().collect();
+    let base_components: Vec<Component<'_>> = base.components().collect();
+    let shared = path_components.iter().zip(&base_components).take_while(|(a, b)| a == b).count();
+    if shared == 0 {
+        return path.display().to_string();
+    }
+    let mut relative = PathBuf::new();
+    for _ in shared..base_components.len() {
+        relative.push("..");
+    }
+    for component in &path_components[shared..] {
+        relative.push(component);
+    }
+    let text = relative.to_string_lossy().replace('\\', "/");
+    if text.is_empty() { ".".to_string() } else { text }
+}
+
+fn invalid(path: &Path, reason: String) -> ProjectError {
+    ProjectError::InvalidLockfile {
+        path: path.to_path_buf(),
+        reason,
+    }
+}
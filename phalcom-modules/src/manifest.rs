@@ -1,7 +1,12 @@
 //! Project manifest decoding and validation (`project.toml`).
 
+use crate::cfg::CfgPredicate;
 use crate::error::ProjectError;
-use crate::identity::ModuleComponent;
+use crate::identity::{ModuleComponent, ModulePath};
+use crate::lockfile::LockPins;
+use crate::project::ProjectOrigin;
+use crate::semver::VersionReq;
+use crate::workspace::Workspace;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
@@ -10,13 +15,30 @@ fn default_source_root() -> PathBuf {
     PathBuf::from("src")
 }
 
+const fn default_true() -> bool {
+    true
+}
+
 /// Raw parsed `project.toml` document structure.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct ProjectManifest {
-    pub project: ProjectSection,
+    /// Absent only in a virtual workspace manifest, which lists members but
+    /// is not a project itself.
+    pub project: Option<ProjectSection>,
     #[serde(default)]
     pub dependencies: BTreeMap<String, toml::Value>,
+    pub registry: Option<RegistrySection>,
+    pub workspace: Option<WorkspaceSection>,
+    /// `[features]`: what enabling each feature implies — other features of
+    /// this project (`name`) or of a dependency (`alias/name`). `default`
+    /// lists the features enabled unless a dependent opts out.
+    #[serde(default)]
+    pub features: BTreeMap<String, Vec<String>>,
+    /// `[modules]`: project-relative module paths that only exist while
+    /// their condition holds (see [`crate::cfg`]).
+    #[serde(default)]
+    pub modules: BTreeMap<String, CfgPredicate>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
@@ -36,10 +58,36 @@ pub struct ProjectSection {
     pub default_entry: Option<String>,
 }
 
+/// `[registry]`: the local package registry `package` dependencies resolve
+/// against (see [`crate::registry`]).
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RegistrySection {
+    /// Registry root, relative to the project directory.
+    pub path: PathBuf,
+}
+
+/// `[workspace]`: the member projects that share this manifest's lockfile,
+/// registry and dependency versions (see [`crate::workspace`]).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WorkspaceSection {
+    /// Member directories relative to the workspace root; a final `*`
+    /// component matches every child directory holding a `project.toml`.
+    pub members: Vec<String>,
+    /// Dependencies members inherit with `alias = { workspace = true }`.
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, toml::Value>,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct PathDependency {
     pub path: PathBuf,
+    #[serde(default)]
+    pub features: Vec<String>,
+    #[serde(default = "default_true", rename = "default-features")]
+    pub default_features: bool,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -47,6 +95,18 @@ pub struct PathDependency {
 pub struct PackageDependency {
     pub package: String,
     pub version: String,
+    #[serde(default)]
+    pub features: Vec<String>,
+    #[serde(default = "default_true", rename = "default-features")]
+    pub default_features: bool,
+}
+
+/// The features a dependent enables on one dependency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyFeatures {
+    pub features: Vec<String>,
+    /// False for `default-features = false`.
+    pub default_features: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -71,6 +131,11 @@ pub struct ValidatedProjectManifest {
     pub entry: Option<String>,
     pub default_entry: Option<String>,
     pub dependencies: BTreeMap<ModuleComponent, (String, DependencySpec)>, // snake_case component -> (original kebab alias, spec)
+    /// Features each dependency is asked for, keyed like [`Self::dependencies`].
+    pub dependency_features: BTreeMap<ModuleComponent, DependencyFeatures>,
+    pub registry: Option<PathBuf>,
+    pub features: BTreeMap<String, Vec<String>>,
+    pub modules: BTreeMap<ModulePath, CfgPredicate>,
 }
 
 impl ProjectManifest {
@@ -79,21 +144,76 @@ impl ProjectManifest {
         toml::from_str(toml_str).map_err(|e| ProjectError::InvalidProjectManifest(e.to_string()))
     }
 
-    /// Loads and parses a manifest from a `project.toml` file path.
+    /// Loads and parses a manifest from a `project.toml` file path, replacing
+    /// `{ workspace = true }` dependencies with the enclosing workspace's.
     pub fn load_file(path: impl AsRef<Path>) -> Result<Self, ProjectError> {
-        let content = std::fs::read_to_string(path.as_ref())
-            .map_err(|e| ProjectError::InvalidProjectManifest(format!("Failed to read {}: {}", path.as_ref().display(), e)))?;
-        Self::parse(&content)
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| ProjectError::InvalidProjectManifest(format!("Failed to read {}: {}", path.display(), e)))?;
+        let mut manifest = Self::parse(&content)?;
+        if manifest.dependencies.values().any(inherits_workspace) {
+            let dir = path.parent().unwrap_or(Path::new("."));
+            let workspace = Workspace::enclosing(dir)?.ok_or_else(|| {
+                ProjectError::InvalidProjectManifest(format!(
+                    "{} inherits `workspace = true` dependencies but is not a workspace member",
+                    path.display()
+                ))
+            })?;
+            manifest.inherit_workspace_dependencies(&workspace)?;
+        }
+        Ok(manifest)
+    }
+
+    /// Replaces every `alias = { workspace = true }` dependency with the
+    /// workspace's `[workspace.dependencies]` entry of the same alias. Path
+    /// dependencies there are relative to the workspace root.
+    fn inherit_workspace_dependencies(&mut self, workspace: &Workspace) -> Result<(), ProjectError> {
+        let shared = workspace.manifest.workspace.as_ref().map(|section| &section.dependencies);
+        for (alias, value) in self.dependencies.iter_mut().filter(|(_, value)| inherits_workspace(value)) {
+            if value
+                .as_table()
+                .is_some_and(|table| table.keys().any(|key| key != "workspace" && key != "features"))
+            {
+                return Err(ProjectError::InvalidProjectManifest(format!(
+                    "dependency '{alias}' can only combine `workspace = true` with `features`"
+                )));
+            }
+            let mut inherited = shared
+                .and_then(|shared| shared.get(alias))
+                .cloned()
+                .ok_or_else(|| ProjectError::InvalidProjectManifest(format!("dependency '{alias}' is not declared in [workspace.dependencies]")))?;
+            if let Some(table) = inherited.as_table_mut() {
+                if let Some(toml::Value::String(path)) = table.get_mut("path") {
+                    *path = workspace.root.join(&*path).to_string_lossy().into_owned();
+                }
+                // A member's `features` add to the workspace's.
+                if let Some(toml::Value::Array(extra)) = value.get("features") {
+                    match table.entry("features").or_insert_with(|| toml::Value::Array(Vec::new())) {
+                        toml::Value::Array(features) => features.extend(extra.iter().cloned()),
+                        _ => {
+                            return Err(ProjectError::InvalidProjectManifest(format!(
+                                "dependency '{alias}' in [workspace.dependencies] has non-array `features`"
+                            )));
+                        }
+                    }
+                }
+            }
+            *value = inherited;
+        }
+        Ok(())
     }
 
     /// Performs semantic validation on the manifest.
     pub fn validate(&self) -> Result<ValidatedProjectManifest, ProjectError> {
-        if self.project.name.trim().is_empty() {
+        let project = self
+            .project
+            .as_ref()
+            .ok_or_else(|| ProjectError::InvalidProjectManifest("missing [project] table (a virtual workspace manifest is not a project)".to_string()))?;
+        if project.name.trim().is_empty() {
             return Err(ProjectError::InvalidProjectManifest("project name cannot be empty".to_string()));
         }
 
-        let raw_name = self.project.name.clone();
-        let raw_namespace = self.project.namespace.as_ref().ok_or(ProjectError::MissingProjectNamespace)?;
+        let raw_name = project.name.clone();
+        let raw_namespace = project.namespace.as_ref().ok_or(ProjectError::MissingProjectNamespace)?;
         let namespace = ModuleComponent::from_identifier(raw_namespace).map_err(|e| ProjectError::InvalidProjectNamespace(raw_namespace.clone(), e))?;
 
         if matches!(namespace.as_str(), "core" | "universe" | "std") {
@@ -104,6 +224,7 @@ impl ProjectManifest {
         }
 
         let mut validated_deps: BTreeMap<ModuleComponent, (String, DependencySpec)> = BTreeMap::new();
+        let mut dependency_features = BTreeMap::new();
         for (raw_alias, toml_val) in &self.dependencies {
             let component = ModuleComponent::from_identifier(raw_alias).map_err(|e| ProjectError::InvalidDependencyAlias(raw_alias.clone(), e))?;
 
@@ -133,6 +254,12 @@ impl ProjectManifest {
                 .as_table()
                 .ok_or_else(|| ProjectError::InvalidProjectManifest(format!("dependency '{raw_alias}' must be a table")))?;
 
+            if table.contains_key("workspace") {
+                return Err(ProjectError::InvalidProjectManifest(format!(
+                    "dependency '{raw_alias}' inherits from a workspace, which only a manifest loaded from disk can do"
+                )));
+            }
+
             let has_path = table.contains_key("path");
             let has_package = table.contains_key("package");
             let has_version = table.contains_key("version");
@@ -143,31 +270,79 @@ impl ProjectManifest {
                 )));
             }
 
-            let spec = if has_path {
+            let (spec, features) = if has_path {
                 let path_dep: PathDependency = toml_val
                     .clone()
                     .try_into()
                     .map_err(|e: toml::de::Error| ProjectError::InvalidProjectManifest(format!("invalid path dependency '{raw_alias}': {e}")))?;
-                DependencySpec::Path { path: path_dep.path }
+                let features = DependencyFeatures {
+                    features: path_dep.features,
+                    default_features: path_dep.default_features,
+                };
+                (DependencySpec::Path { path: path_dep.path }, features)
             } else if has_package || has_version {
                 let pkg_dep: PackageDependency = toml_val
                     .clone()
                     .try_into()
                     .map_err(|e: toml::de::Error| ProjectError::InvalidProjectManifest(format!("invalid package dependency '{raw_alias}': {e}")))?;
-                DependencySpec::Package {
+                VersionReq::parse(&pkg_dep.version)
+                    .map_err(|e| ProjectError::InvalidProjectManifest(format!("invalid version requirement for dependency '{raw_alias}': {e}")))?;
+                let features = DependencyFeatures {
+                    features: pkg_dep.features,
+                    default_features: pkg_dep.default_features,
+                };
+                let spec = DependencySpec::Package {
                     package: pkg_dep.package,
                     version: pkg_dep.version,
-                }
+                };
+                (spec, features)
             } else {
                 return Err(ProjectError::InvalidProjectManifest(format!(
                     "dependency '{raw_alias}' must specify either 'path' or 'package' + 'version'"
                 )));
             };
 
+            for feature in &features.features {
+                validate_feature_name(feature).map_err(|e| ProjectError::InvalidProjectManifest(format!("dependency '{raw_alias}': {e}")))?;
+            }
+            dependency_features.insert(component.clone(), features);
             validated_deps.insert(component, (raw_alias.clone(), spec));
         }
 
-        if let Some(entry) = &self.project.entry {
+        for (feature, implied) in &self.features {
+            validate_feature_name(feature).map_err(ProjectError::InvalidProjectManifest)?;
+            for entry in implied {
+                let known = match entry.split_once('/') {
+                    Some((alias, dep_feature)) => {
+                        validate_feature_name(dep_feature).map_err(ProjectError::InvalidProjectManifest)?;
+                        ModuleComponent::from_identifier(alias).is_ok_and(|alias| validated_deps.contains_key(&alias))
+                    }
+                    None => self.features.contains_key(entry),
+                };
+                if !known {
+                    return Err(ProjectError::InvalidProjectManifest(format!(
+                        "feature '{feature}' enables '{entry}', which is neither a feature of this project nor `dependency/feature`"
+                    )));
+                }
+            }
+        }
+
+        let mut modules = BTreeMap::new();
+        for (raw_path, predicate) in &self.modules {
+            let invalid = |reason: String| ProjectError::InvalidProjectManifest(format!("[modules] entry '{raw_path}': {reason}"));
+            let components = raw_path
+                .split('.')
+                .map(ModuleComponent::from_identifier)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| invalid(e.to_string()))?;
+            predicate.validate().map_err(invalid)?;
+            if let Some(feature) = predicate.feature.as_ref().filter(|feature| !self.features.contains_key(*feature)) {
+                return Err(invalid(format!("feature '{feature}' is not declared in [features]")));
+            }
+            modules.insert(ModulePath::from_components(components), predicate.clone());
+        }
+
+        if let Some(entry) = &project.entry {
             if entry.trim().is_empty() {
                 return Err(ProjectError::InvalidEntry(entry.clone(), "entry path cannot be empty".to_string()));
             }
@@ -180,7 +355,7 @@ impl ProjectManifest {
             }
         }
 
-        if let Some(default_entry) = &self.project.default_entry {
+        if let Some(default_entry) = &project.default_entry {
             if default_entry.trim().is_empty() {
                 return Err(ProjectError::InvalidEntry(
                     default_entry.clone(),
@@ -200,29 +375,74 @@ impl ProjectManifest {
             name: raw_name.clone(),
             raw_name,
             namespace,
-            version: self.project.version.clone(),
-            authors: self.project.authors.clone().unwrap_or_default(),
-            description: self.project.description.clone(),
-            license: self.project.license.clone(),
-            homepage: self.project.homepage.clone(),
-            repository: self.project.repository.clone(),
-            source: self.project.source.clone(),
-            entry: self.project.entry.clone(),
-            default_entry: self.project.default_entry.clone(),
+            version: project.version.clone(),
+            authors: project.authors.clone().unwrap_or_default(),
+            description: project.description.clone(),
+            license: project.license.clone(),
+            homepage: project.homepage.clone(),
+            repository: project.repository.clone(),
+            source: project.source.clone(),
+            entry: project.entry.clone(),
+            default_entry: project.default_entry.clone(),
             dependencies: validated_deps,
+            dependency_features,
+            registry: self.registry.as_ref().map(|registry| registry.path.clone()),
+            features: self.features.clone(),
+            modules,
         })
     }
 }
 
+/// Feature names are lowercase kebab- or snake-case words; `/` is reserved
+/// for `dependency/feature`.
+fn validate_feature_name(name: &str) -> Result<(), String> {
+    let valid =
+        name.starts_with(|c: char| c.is_ascii_lowercase()) && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if valid { Ok(()) } else { Err(format!("invalid feature name '{name}'")) }
+}
+
+fn inherits_workspace(value: &toml::Value) -> bool {
+    value.get("workspace").and_then(toml::Value::as_bool) == Some(true)
+}
+
 /// Resolved dependency source location from an external dependency provider.
 #[derive(Debug, Clone)]
 pub struct ResolvedDependencySource {
     pub manifest_path: PathBuf,
+    /// How the dependency enters the universe, and so the source `phalcom.lock` records.
+    pub origin: ProjectOrigin,
 }
 
 /// Trait for package acquisition / registry resolution boundary.
 pub trait DependencyProvider {
+    /// Called with the root `project.toml` before its dependency graph is
+    /// walked, so a provider that resolves the graph as a whole can choose
+    /// every version up front, honoring the versions `phalcom.lock` pins.
+    /// The default does nothing.
+    fn prepare(&self, _root_manifest: &Path, _pins: &LockPins) -> Result<(), ProjectError> {
+        Ok(())
+    }
+
     fn resolve_package(&self, package: &str, version_requirement: &str) -> Result<ResolvedDependencySource, ProjectError>;
+
+    /// Resolves the `{ path = ... }` dependency `path` of the project in
+    /// `dependent_dir`. The default reads it in place.
+    fn resolve_path(&self, dependent_dir: &Path, path: &Path) -> Result<ResolvedDependencySource, ProjectError> {
+        resolve_path_in_place(dependent_dir, path)
+    }
+}
+
+/// The `project.toml` of the path dependency `path` of the project in `dependent_dir`, where it lies.
+pub(crate) fn resolve_path_in_place(dependent_dir: &Path, path: &Path) -> Result<ResolvedDependencySource, ProjectError> {
+    let dep_dir = if path.is_absolute() { path.to_path_buf() } else { dependent_dir.join(path) };
+    let manifest_path = dep_dir.join("project.toml");
+    if !manifest_path.is_file() {
+        return Err(ProjectError::PathDependencyNotFound(manifest_path));
+    }
+    Ok(ResolvedDependencySource {
+        manifest_path,
+        origin: ProjectOrigin::Path,
+    })
 }
 
 /// A default null dependency provider that rejects all registry package dependencies.
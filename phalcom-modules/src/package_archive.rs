@@ -0,0 +1,549 @@
+//! Package archives written by `phalcom package`.
+//!
+//! A package archive is the publishable form of a project: an uncompressed
+//! tar in the layout [`crate::registry`] unpacks, whose entries are relative
+//! to the package root:
+//!
+//! ```text
+//! CHECKSUMS              # `sha256:<hex>  <entry>` for every other entry
+//! project.toml           # the manifest, without [workspace] or [registry]
+//! src/package.ph         # every source under the manifest's `source`
+//! interfaces.json        # linked interfaces of the package's modules
+//! metadata/package.bin   # binary semantic metadata bundles, one per analyzed program
+//! ```
+//!
+//! Entries are sorted and carry no timestamps or owners, so packaging the
+//! same project twice produces the same bytes. [`PackageArchive::open`]
+//! rejects an archive whose entries do not match `CHECKSUMS`.
+//!
+//! A consumer links against [`PackageArchive::artifact`] and type-checks
+//! against the bundled metadata; neither reads the sources.
+
+use crate::artifact::{ResolvedPackageArtifact, ResolvedPackageId};
+use crate::error::ProjectError;
+use crate::identity::{BuiltinProject, ModuleComponent, ModuleId, ModulePath, ProjectIdentity, ResolvedProjectId};
+use crate::interface::{LinkedExport, LinkedExportTarget, LinkedModuleInterface};
+use crate::linker::SymbolId;
+use crate::lockfile::sha256_hex;
+use crate::manifest::{DependencySpec, ProjectManifest, ValidatedProjectManifest};
+use crate::metadata::{MetadataTarget, ModuleMetadata, ModuleMetadataAttribute};
+use crate::project::ProjectUniverse;
+use crate::resolver::ModuleResolver;
+use crate::semver::Version;
+use crate::source::{FilesystemSourceProvider, ModuleKind};
+use phalcom_ast::ast::MetadataLiteral;
+use phalcom_common::range::SourceRange;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+/// Checksum manifest entry, listing every other entry.
+pub const CHECKSUMS_FILE: &str = "CHECKSUMS";
+
+/// Linked interfaces entry.
+pub const INTERFACES_FILE: &str = "interfaces.json";
+
+/// Directory of semantic metadata bundle entries.
+pub const METADATA_DIR: &str = "metadata";
+
+/// A verified package archive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageArchive {
+    pub path: PathBuf,
+    /// The packaged manifest.
+    pub manifest: ProjectManifest,
+    /// Every entry except `CHECKSUMS`, by archive path.
+    pub files: BTreeMap<String, Vec<u8>>,
+}
+
+impl PackageArchive {
+    /// Archive file name for a package: `<name>-<version>.tar`.
+    pub fn file_name(manifest: &ValidatedProjectManifest) -> String {
+        format!("{}-{}.tar", manifest.name, manifest.version.as_deref().unwrap_or("0.0.0"))
+    }
+
+    /// Writes `files` and their `CHECKSUMS` to `path` as a deterministic tar;
+    /// returns the archive's own checksum.
+    pub fn write(path: &Path, files: &BTreeMap<String, Vec<u8>>) -> Result<String, ProjectError> {
+        let mut checksums = String::new();
+        for (name, bytes) in files {
+            checksums.push_str(&format!("{}  {name}\n", sha256_hex(bytes)));
+        }
+
+        let mut builder = tar::Builder::new(Vec::new());
+        let entries = std::iter::once((CHECKSUMS_FILE, checksums.as_bytes())).chain(files.iter().map(|(name, bytes)| (name.as_str(), bytes.as_slice())));
+        for (name, bytes) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(bytes.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(0);
+            header.set_uid(0);
+            header.set_gid(0);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, bytes)
+                .map_err(|e| invalid(path, format!("cannot add '{name}': {e}")))?;
+        }
+        let bytes = builder.into_inner().map_err(|e| invalid(path, e.to_string()))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| invalid(path, format!("cannot create {}: {e}", parent.display())))?;
+        }
+        std::fs::write(path, &bytes).map_err(|e| invalid(path, format!("cannot write: {e}")))?;
+        Ok(sha256_hex(&bytes))
+    }
+
+    /// Reads the archive at `path`, verifying every entry against `CHECKSUMS`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ProjectError> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| invalid(path, format!("cannot open: {e}")))?;
+        let mut archive = tar::Archive::new(file);
+        let mut files = BTreeMap::new();
+        let mut checksums = None;
+        for entry in archive.entries().map_err(|e| invalid(path, e.to_string()))? {
+            let mut entry = entry.map_err(|e| invalid(path, e.to_string()))?;
+            let name = entry.path().map_err(|e| invalid(path, e.to_string()))?.into_owned();
+            if !name.components().all(|component| matches!(component, Component::Normal(_))) {
+                return Err(invalid(path, format!("entry '{}' escapes the package root", name.display())));
+            }
+            let name = name.to_string_lossy().replace('\\', "/");
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes).map_err(|e| invalid(path, format!("cannot read '{name}': {e}")))?;
+            if name == CHECKSUMS_FILE {
+                checksums = Some(String::from_utf8(bytes).map_err(|_| invalid(path, "CHECKSUMS is not UTF-8".to_string()))?);
+            } else if files.insert(name.clone(), bytes).is_some() {
+                return Err(invalid(path, format!("entry '{name}' appears twice")));
+            }
+        }
+
+        let checksums = checksums.ok_or_else(|| invalid(path, "missing CHECKSUMS".to_string()))?;
+        let mut listed = BTreeSet::new();
+        for line in checksums.lines() {
+            let Some((digest, name)) = line.split_once("  ") else {
+                return Err(invalid(path, format!("malformed CHECKSUMS line '{line}'")));
+            };
+            let bytes = files
+                .get(name)
+                .ok_or_else(|| invalid(path, format!("'{name}' is listed in CHECKSUMS but missing")))?;
+            if sha256_hex(bytes) != digest {
+                return Err(invalid(path, format!("checksum mismatch for '{name}'")));
+            }
+            listed.insert(name);
+        }
+        if let Some(unlisted) = files.keys().find(|name| !listed.contains(name.as_str())) {
+            return Err(invalid(path, format!("'{unlisted}' is not listed in CHECKSUMS")));
+        }
+
+        let manifest = files.get("project.toml").ok_or_else(|| invalid(path, "missing project.toml".to_string()))?;
+        let manifest = std::str::from_utf8(manifest).map_err(|_| invalid(path, "project.toml is not UTF-8".to_string()))?;
+        let manifest = ProjectManifest::parse(manifest)?;
+        check_publishable(&manifest.validate()?)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            manifest,
+            files,
+        })
+    }
+
+    /// The package's linked interfaces, owned by `package`; a module a
+    /// dependency owns is attributed to `dependencies[alias]`.
+    pub fn interfaces(
+        &self,
+        package: ResolvedPackageId,
+        dependencies: &BTreeMap<String, ResolvedPackageId>,
+    ) -> Result<BTreeMap<ModuleId, LinkedModuleInterface>, ProjectError> {
+        let records = self.interface_records()?;
+        let owners = OwnerMap { package, dependencies };
+        let mut interfaces = BTreeMap::new();
+        for record in records {
+            let interface = record.decode(&owners).map_err(|reason| invalid(&self.path, reason))?;
+            interfaces.insert(interface.module.clone(), interface);
+        }
+        Ok(interfaces)
+    }
+
+    /// The published artifact for this archive, linked as `package`.
+    pub fn artifact(
+        &self,
+        package: ResolvedPackageId,
+        dependency_artifacts: BTreeMap<String, ResolvedPackageId>,
+    ) -> Result<ResolvedPackageArtifact, ProjectError> {
+        let records = self.interface_records()?;
+        let exposed_paths = records
+            .iter()
+            .filter(|record| record.exposed)
+            .map(|record| parse_path(&record.path))
+            .collect::<Result<BTreeSet<_>, _>>()
+            .map_err(|reason| invalid(&self.path, reason))?;
+        let interfaces = self.interfaces(package, &dependency_artifacts)?;
+        Ok(ResolvedPackageArtifact::new(
+            package,
+            self.manifest.clone(),
+            ModuleId::resolved(package, ModulePath::root()),
+            interfaces,
+            exposed_paths,
+            dependency_artifacts,
+        ))
+    }
+
+    /// The bundled semantic metadata, by archive path, still encoded.
+    pub fn metadata(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        let prefix = format!("{METADATA_DIR}/");
+        self.files
+            .iter()
+            .filter(move |(name, _)| name.starts_with(&prefix))
+            .map(|(name, bytes)| (name.as_str(), bytes.as_slice()))
+    }
+
+    fn interface_records(&self) -> Result<Vec<InterfaceRecord>, ProjectError> {
+        let bytes = self
+            .files
+            .get(INTERFACES_FILE)
+            .ok_or_else(|| invalid(&self.path, format!("missing {INTERFACES_FILE}")))?;
+        serde_json::from_slice(bytes).map_err(|e| invalid(&self.path, format!("malformed {INTERFACES_FILE}: {e}")))
+    }
+}
+
+/// Checks that a project can be published: it declares a license and a
+/// semver version, and depends on no path dependencies.
+pub fn check_publishable(manifest: &ValidatedProjectManifest) -> Result<(), ProjectError> {
+    let refuse = |reason: String| ProjectError::NotPublishable {
+        project: manifest.name.clone(),
+        reason,
+    };
+    if manifest.license.as_deref().is_none_or(|license| license.trim().is_empty()) {
+        return Err(refuse("the manifest declares no license".to_string()));
+    }
+    let Some(version) = &manifest.version else {
+        return Err(refuse("the manifest declares no version".to_string()));
+    };
+    Version::parse(version).map_err(|e| refuse(format!("version '{version}' is not semver: {e}")))?;
+    if let Some((alias, _)) = manifest.dependencies.values().find(|(_, spec)| matches!(spec, DependencySpec::Path { .. })) {
+        return Err(refuse(format!(
+            "'{alias}' is a path dependency; published packages depend only on registry packages"
+        )));
+    }
+    Ok(())
+}
+
+/// The `project.toml` to publish for the manifest at `manifest_path`.
+/// Dependencies inherited with `{ workspace = true }` are written out, and
+/// `[workspace]` and `[registry]`, which only mean something beside the
+/// project's checkout, are dropped.
+pub fn publishable_manifest(manifest_path: &Path) -> Result<String, ProjectError> {
+    let text = std::fs::read_to_string(manifest_path)
+        .map_err(|e| ProjectError::InvalidProjectManifest(format!("Failed to read {}: {}", manifest_path.display(), e)))?;
+    let mut table: toml::Table = toml::from_str(&text).map_err(|e| ProjectError::InvalidProjectManifest(e.to_string()))?;
+    table.remove("workspace");
+    table.remove("registry");
+    let dependencies = ProjectManifest::load_file(manifest_path)?.dependencies;
+    if !dependencies.is_empty() {
+        table.insert("dependencies".to_string(), toml::Value::Table(dependencies.into_iter().collect()));
+    }
+    toml::to_string(&table).map_err(|e| ProjectError::InvalidProjectManifest(e.to_string()))
+}
+
+/// Module paths of `package` a consumer may import: the root package and,
+/// transitively, every child an exposed package exposes.
+pub fn exposed_paths(universe: &ProjectUniverse, package: ResolvedProjectId) -> Result<BTreeSet<ModulePath>, ProjectError> {
+    let provider = FilesystemSourceProvider::new();
+    let mut resolver = ModuleResolver::new(universe, &provider);
+    let mut exposed = BTreeSet::new();
+    let mut pending = vec![ModulePath::root()];
+    while let Some(path) = pending.pop() {
+        if !exposed.insert(path.clone()) {
+            continue;
+        }
+        let interface = resolver
+            .load_interface(&ModuleId::resolved(package, path.clone()))
+            .map_err(|e| ProjectError::InvalidProjectManifest(format!("cannot load module '{path}': {e}")))?;
+        for child in &interface.exposed_children {
+            let mut components = path.components().to_vec();
+            components.push(child.clone());
+            pending.push(ModulePath::from_components(components));
+        }
+    }
+    Ok(exposed)
+}
+
+/// Encodes the linked interfaces of `package`'s own modules as the archive's
+/// `interfaces.json`.
+pub fn encode_interfaces<'a>(
+    universe: &ProjectUniverse,
+    package: ResolvedProjectId,
+    interfaces: impl IntoIterator<Item = &'a LinkedModuleInterface>,
+    exposed: &BTreeSet<ModulePath>,
+) -> Result<String, ProjectError> {
+    let project = universe
+        .get_project(package)
+        .ok_or_else(|| ProjectError::InvalidProjectManifest(format!("project {package:?} is not loaded")))?;
+    let aliases: BTreeMap<ResolvedProjectId, String> = project.dependencies.iter().map(|(alias, id)| (*id, alias.as_str().to_string())).collect();
+    let encoder = OwnerEncoder { package, aliases: &aliases };
+    let mut records = Vec::new();
+    for interface in interfaces {
+        if interface.module.project != ProjectIdentity::Resolved(package) {
+            continue;
+        }
+        records.push(
+            InterfaceRecord::encode(interface, exposed.contains(&interface.module.path), &encoder).map_err(|reason| ProjectError::NotPublishable {
+                project: project.name.clone(),
+                reason,
+            })?,
+        );
+    }
+    records.sort_by(|a, b| a.path.cmp(&b.path));
+    records.dedup_by(|a, b| a.path == b.path);
+    serde_json::to_string_pretty(&records).map_err(|e| ProjectError::InvalidProjectManifest(e.to_string()))
+}
+
+fn invalid(path: &Path, reason: String) -> ProjectError {
+    ProjectError::InvalidPackageArchive {
+        path: path.to_path_buf(),
+        reason,
+    }
+}
+
+fn parse_path(dotted: &str) -> Result<ModulePath, String> {
+    if dotted.is_empty() {
+        return Ok(ModulePath::root());
+    }
+    dotted
+        .split('.')
+        .map(|part| ModuleComponent::from_identifier(part).map_err(|e| format!("invalid module path '{dotted}': {e}")))
+        .collect::<Result<Vec<_>, _>>()
+        .map(ModulePath::from_components)
+}
+
+/// Encodes module owners relative to the package being archived.
+struct OwnerEncoder<'a> {
+    package: ResolvedProjectId,
+    aliases: &'a BTreeMap<ResolvedProjectId, String>,
+}
+
+/// Decodes module owners onto the consumer's project identities.
+struct OwnerMap<'a> {
+    package: ResolvedPackageId,
+    dependencies: &'a BTreeMap<String, ResolvedPackageId>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct InterfaceRecord {
+    /// Dotted project-relative path; empty for the root package.
+    path: String,
+    kind: KindRecord,
+    /// Importable by consumers.
+    exposed: bool,
+    exports: Vec<ExportRecord>,
+    attributes: Vec<AttributeRecord>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum KindRecord {
+    Package,
+    Module,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportRecord {
+    name: String,
+    target: TargetRecord,
+    range: (usize, usize),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TargetRecord {
+    Binding { module: ModuleRecord, name: String },
+    Module(ModuleRecord),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ModuleRecord {
+    owner: OwnerRecord,
+    path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum OwnerRecord {
+    /// The archived package itself.
+    Package,
+    /// A dependency, by alias.
+    Dependency(String),
+    /// A builtin project, by import root.
+    Builtin(String),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AttributeRecord {
+    name: String,
+    arguments: Vec<LiteralRecord>,
+    range: (usize, usize),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum LiteralRecord {
+    Unit,
+    Bool(bool),
+    Int(String),
+    Float(f64),
+    String(String),
+    Symbol(String),
+    Tuple(Vec<LiteralRecord>),
+    Record(Vec<(String, LiteralRecord)>),
+}
+
+impl InterfaceRecord {
+    fn encode(interface: &LinkedModuleInterface, exposed: bool, owners: &OwnerEncoder<'_>) -> Result<Self, String> {
+        let exports = interface
+            .exports
+            .values()
+            .map(|export| {
+                let target = match &export.target {
+                    LinkedExportTarget::Binding(symbol) => TargetRecord::Binding {
+                        module: ModuleRecord::encode(&symbol.module, owners)?,
+                        name: symbol.name.to_string(),
+                    },
+                    LinkedExportTarget::Module(module) => TargetRecord::Module(ModuleRecord::encode(module, owners)?),
+                };
+                Ok(ExportRecord {
+                    name: export.public_name.to_string(),
+                    target,
+                    range: (export.range.start, export.range.end),
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        let attributes = interface
+            .metadata
+            .attributes
+            .iter()
+            .map(|attribute| AttributeRecord {
+                name: attribute.name.clone(),
+                arguments: attribute.arguments.iter().map(LiteralRecord::encode).collect(),
+                range: (attribute.range.start, attribute.range.end),
+            })
+            .collect();
+        Ok(Self {
+            path: interface.module.path.to_string(),
+            kind: match interface.kind {
+                ModuleKind::Package => KindRecord::Package,
+                ModuleKind::Module => KindRecord::Module,
+            },
+            exposed,
+            exports,
+            attributes,
+        })
+    }
+
+    fn decode(self, owners: &OwnerMap<'_>) -> Result<LinkedModuleInterface, String> {
+        let module = ModuleId::resolved(owners.package, parse_path(&self.path)?);
+        let (kind, target) = match self.kind {
+            KindRecord::Package => (ModuleKind::Package, MetadataTarget::Package),
+            KindRecord::Module => (ModuleKind::Module, MetadataTarget::Module),
+        };
+        let mut exports = BTreeMap::new();
+        for export in self.exports {
+            let target = match export.target {
+                TargetRecord::Binding { module, name } => LinkedExportTarget::Binding(SymbolId {
+                    module: module.decode(owners)?,
+                    name: name.into(),
+                }),
+                TargetRecord::Module(module) => LinkedExportTarget::Module(module.decode(owners)?),
+            };
+            let name: Box<str> = export.name.into();
+            exports.insert(
+                name.clone(),
+                LinkedExport {
+                    public_name: name,
+                    target,
+                    range: SourceRange::new(export.range.0, export.range.1),
+                },
+            );
+        }
+        let attributes = self
+            .attributes
+            .into_iter()
+            .map(|attribute| ModuleMetadataAttribute {
+                target,
+                name: attribute.name,
+                arguments: attribute.arguments.into_iter().map(LiteralRecord::decode).collect(),
+                range: SourceRange::new(attribute.range.0, attribute.range.1),
+            })
+            .collect();
+        Ok(LinkedModuleInterface {
+            module,
+            kind,
+            exports,
+            metadata: ModuleMetadata { attributes },
+        })
+    }
+}
+
+impl ModuleRecord {
+    fn encode(module: &ModuleId, owners: &OwnerEncoder<'_>) -> Result<Self, String> {
+        let owner = match module.project {
+            ProjectIdentity::Resolved(id) if id == owners.package => OwnerRecord::Package,
+            ProjectIdentity::Resolved(id) => match owners.aliases.get(&id) {
+                Some(alias) => OwnerRecord::Dependency(alias.clone()),
+                None => return Err(format!("module {module} belongs to a project that is not a direct dependency")),
+            },
+            ProjectIdentity::Builtin(builtin) => OwnerRecord::Builtin(builtin.import_root().to_string()),
+            ProjectIdentity::Synthetic(_) => return Err(format!("module {module} belongs to a synthetic project")),
+        };
+        Ok(Self {
+            owner,
+            path: module.path.to_string(),
+        })
+    }
+
+    fn decode(self, owners: &OwnerMap<'_>) -> Result<ModuleId, String> {
+        let path = parse_path(&self.path)?;
+        Ok(match self.owner {
+            OwnerRecord::Package => ModuleId::resolved(owners.package, path),
+            OwnerRecord::Dependency(alias) => {
+                let id = owners.dependencies.get(&alias).ok_or_else(|| format!("no artifact for dependency '{alias}'"))?;
+                ModuleId::resolved(*id, path)
+            }
+            OwnerRecord::Builtin(root) => {
+                let builtin = match root.as_str() {
+                    "universe" => BuiltinProject::Universe,
+                    "std" => BuiltinProject::Std,
+                    "native" => BuiltinProject::Native,
+                    other => return Err(format!("unknown builtin project '{other}'")),
+                };
+                ModuleId::builtin(builtin, path)
+            }
+        })
+    }
+}
+
+impl LiteralRecord {
+    fn encode(literal: &MetadataLiteral) -> Self {
+        match literal {
+            MetadataLiteral::Unit => Self::Unit,
+            MetadataLiteral::Bool(value) => Self::Bool(*value),
+            MetadataLiteral::Int(value) => Self::Int(value.clone()),
+            MetadataLiteral::Float(value) => Self::Float(*value),
+            MetadataLiteral::String(value) => Self::String(value.clone()),
+            MetadataLiteral::Symbol(value) => Self::Symbol(value.clone()),
+            MetadataLiteral::Tuple(items) => Self::Tuple(items.iter().map(Self::encode).collect()),
+            MetadataLiteral::Record(fields) => Self::Record(fields.iter().map(|(name, value)| (name.clone(), Self::encode(value))).collect()),
+        }
+    }
+
+    fn decode(self) -> MetadataLiteral {
+        match self {
+            Self::Unit => MetadataLiteral::Unit,
+            Self::Bool(value) => MetadataLiteral::Bool(value),
+            Self::Int(value) => MetadataLiteral::Int(value),
+            Self::Float(value) => MetadataLiteral::Float(value),
+            Self::String(value) => MetadataLiteral::String(value),
+            Self::Symbol(value) => MetadataLiteral::Symbol(value),
+            Self::Tuple(items) => MetadataLiteral::Tuple(items.into_iter().map(Self::decode).collect()),
+            Self::Record(fields) => MetadataLiteral::Record(fields.into_iter().map(|(name, value)| (name, value.decode())).collect()),
+        }
+    }
+}
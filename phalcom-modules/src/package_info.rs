@@ -243,6 +243,23 @@ impl PackageInfoDescriptor {
         }
     }
 
+    /// Constructs the canonical `PackageInfoDescriptor` for the builtin `native` package of extension modules.
+    pub fn builtin_native() -> Self {
+        Self {
+            name: "native".to_string(),
+            namespace: "native".to_string().into_boxed_str(),
+            version: None,
+            authors: vec![],
+            description: Some("Native classes contributed by extension crates.".to_string()),
+            license: None,
+            homepage: None,
+            repository: None,
+            requirements: vec![],
+            default_entry: None,
+            identity: PackageArtifactIdentity::Builtin("native".to_string().into_boxed_str()),
+        }
+    }
+
     /// Constructs a minimal `PackageInfoDescriptor` for a standalone package without a manifest.
     pub fn standalone(namespace: &str) -> Self {
         Self {
@@ -1,11 +1,17 @@
 //! Project universe, resolved projects, and project dependency graph management.
 
+use crate::cfg::{CfgContext, CfgOptions};
 use crate::error::ProjectError;
 use crate::identity::{
-    BuiltinProject, ImportRootTarget, ModuleComponent, ModulePath, ProjectSourceIdentity, ResolvedProjectId, SyntheticProjectId, SyntheticProjectIdAllocator,
+    BuiltinProject, ImportRootTarget, ModuleComponent, ModulePath, ProjectIdentity, ProjectSourceIdentity, ResolvedProjectId, SyntheticProjectId,
+    SyntheticProjectIdAllocator,
 };
+use crate::lockfile::{LOCKFILE_NAME, LockMode, LockOptions, Lockfile};
 use crate::manifest::{DependencyProvider, DependencySpec, NullDependencyProvider, ProjectManifest};
-use std::collections::{BTreeMap, HashSet};
+use crate::registry::DirectoryRegistryProvider;
+use crate::vendor::VendorDependencyProvider;
+use crate::workspace::Workspace;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::path::{Path, PathBuf};
 
 /// A fully resolved project in the `ProjectUniverse`.
@@ -23,8 +29,26 @@ pub struct ResolvedProject {
     /// True only for a persistent project.toml boundary. Synthetic resolved
     /// roots are standalone Package compatibility contexts, not Projects.
     pub persistent_project: bool,
+    /// How the project entered the universe.
+    pub origin: ProjectOrigin,
     /// The validated project manifest, if loaded from a project.toml.
     pub manifest: Option<crate::manifest::ValidatedProjectManifest>,
+    /// Active `[features]`, resolved across the whole loaded graph.
+    pub features: BTreeSet<String>,
+}
+
+/// How a project entered the universe; `phalcom.lock` records it as the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectOrigin {
+    /// The project a `load_root*` call named, or a synthetic root.
+    Root,
+    /// A `{ path = ... }` dependency.
+    Path,
+    /// A `{ package = ..., version = ... }` dependency, from the registry rooted at `registry`.
+    Package { registry: PathBuf },
+    /// A dependency read from its copy under `vendor/` (see [`crate::vendor`]),
+    /// recorded under the `source` its lock entry names.
+    Vendored { source: String },
 }
 
 impl ResolvedProject {
@@ -40,12 +64,22 @@ impl ResolvedProject {
     }
 }
 
+/// How a project is loaded: what happens to its `phalcom.lock`, and the
+/// compile mode and features its modules are filtered under. The default
+/// never writes the lock; project commands ask for [`LockMode::Update`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectLoadOptions {
+    pub lock: LockOptions,
+    pub cfg: CfgOptions,
+}
+
 /// The set of all resolved projects participating in a compilation or analysis session.
 #[derive(Debug)]
 pub struct ProjectUniverse {
     projects: Vec<ResolvedProject>,
     roots: BTreeMap<ProjectSourceIdentity, ResolvedProjectId>,
     synthetic_ids: SyntheticProjectIdAllocator,
+    cfg: CfgOptions,
 }
 
 impl Default for ProjectUniverse {
@@ -55,12 +89,38 @@ impl Default for ProjectUniverse {
 }
 
 impl ProjectUniverse {
+    /// An empty universe under the default [`CfgOptions`] (debug, default features).
     pub fn new() -> Self {
+        Self::with_cfg_options(CfgOptions::default())
+    }
+
+    /// An empty universe whose projects are filtered under `cfg`.
+    pub fn with_cfg_options(cfg: CfgOptions) -> Self {
         Self {
             projects: Vec::new(),
             roots: BTreeMap::new(),
             synthetic_ids: SyntheticProjectIdAllocator,
+            cfg,
+        }
+    }
+
+    /// The compile mode and root features this universe was loaded under.
+    pub fn cfg_options(&self) -> &CfgOptions {
+        &self.cfg
+    }
+
+    /// The conditions modules owned by `project` are filtered under. Builtin
+    /// and synthetic projects have no features or `[modules]` gates.
+    pub fn cfg_context(&self, project: ProjectIdentity) -> CfgContext {
+        let mut context = CfgContext::plain(&self.cfg.mode);
+        if let Some(project) = project.as_resolved().and_then(|id| self.get_project(id)) {
+            context.features = project.features.clone();
+            if let Some(manifest) = &project.manifest {
+                context.declared = manifest.features.keys().cloned().collect();
+                context.modules = manifest.modules.clone();
+            }
         }
+        context
     }
 
     /// Returns a reference to all resolved projects.
@@ -79,16 +139,42 @@ impl ProjectUniverse {
     }
 
     /// Loads and resolves a root project and its full dependency graph from a `project.toml` file path.
+    /// Registry dependencies resolve against the registry the project
+    /// configures (see [`DirectoryRegistryProvider::configured`]), if any,
+    /// vendored dependencies against their copies (see [`crate::vendor`]),
+    /// and `phalcom.lock` is honored but never written ([`LockMode::ReadOnly`]).
     pub fn load_root(&mut self, manifest_path: impl AsRef<Path>) -> Result<ResolvedProjectId, ProjectError> {
-        let dep_provider = NullDependencyProvider;
-        self.load_root_with_provider(manifest_path, &dep_provider)
+        self.load_root_with_options(manifest_path, &LockOptions::default())
     }
 
-    /// Loads and resolves a root project with a custom dependency provider.
+    /// [`Self::load_root`] under explicit lock options.
+    pub fn load_root_with_options(&mut self, manifest_path: impl AsRef<Path>, options: &LockOptions) -> Result<ResolvedProjectId, ProjectError> {
+        let manifest_path = manifest_path.as_ref();
+        with_configured_provider(manifest_path, options, |provider| self.load_root_locked(manifest_path, provider, options))
+    }
+
+    /// Loads and resolves a root project with a custom dependency provider,
+    /// leaving `phalcom.lock` as it is.
     pub fn load_root_with_provider(
         &mut self,
         manifest_path: impl AsRef<Path>,
         dep_provider: &dyn DependencyProvider,
+    ) -> Result<ResolvedProjectId, ProjectError> {
+        self.load_root_locked(manifest_path, dep_provider, &LockOptions::default())
+    }
+
+    /// Loads and resolves a root project against its `phalcom.lock`: locked
+    /// registry versions are preferred (or, under [`LockMode::Frozen`],
+    /// required), and the loaded graph is compared with the lock afterwards.
+    /// [`LockMode::Update`] rewrites a lock that changed and
+    /// [`LockMode::ReadOnly`] ignores the change; the other modes fail with
+    /// [`ProjectError::LockfileDrift`] instead. A workspace member loads
+    /// its whole workspace, which shares one lock.
+    pub fn load_root_locked(
+        &mut self,
+        manifest_path: impl AsRef<Path>,
+        dep_provider: &dyn DependencyProvider,
+        options: &LockOptions,
     ) -> Result<ResolvedProjectId, ProjectError> {
         let manifest_path = manifest_path.as_ref();
         let canonical_manifest = manifest_path
@@ -106,19 +192,177 @@ impl ProjectUniverse {
             return Ok(id);
         }
 
+        let Some(workspace) = Workspace::enclosing(&root_dir)? else {
+            let ids = self.load_roots_locked(&canonical_manifest, std::slice::from_ref(&canonical_manifest), &root_dir, dep_provider, options)?;
+            return Ok(ids[0]);
+        };
+        self.load_workspace_locked(&workspace, dep_provider, options)?;
+        self.roots.get(&source_identity).copied().ok_or_else(|| {
+            ProjectError::InvalidProjectManifest(format!(
+                "{} is a virtual workspace manifest, not a project; load its members instead",
+                canonical_manifest.display()
+            ))
+        })
+    }
+
+    /// Loads every project of the workspace rooted at `manifest_path` (see
+    /// [`crate::workspace`]) against its registry and shared `phalcom.lock`,
+    /// returning their ids in [`Workspace::project_manifests`] order. The lock
+    /// is never written.
+    pub fn load_workspace(&mut self, manifest_path: impl AsRef<Path>) -> Result<Vec<ResolvedProjectId>, ProjectError> {
+        self.load_workspace_with_options(manifest_path, &LockOptions::default())
+    }
+
+    /// [`Self::load_workspace`] under explicit lock options.
+    pub fn load_workspace_with_options(&mut self, manifest_path: impl AsRef<Path>, options: &LockOptions) -> Result<Vec<ResolvedProjectId>, ProjectError> {
+        let workspace = Workspace::load(manifest_path)?;
+        with_configured_provider(&workspace.manifest_path, options, |provider| {
+            self.load_workspace_locked(&workspace, provider, options)
+        })
+    }
+
+    pub(crate) fn load_workspace_locked(
+        &mut self,
+        workspace: &Workspace,
+        dep_provider: &dyn DependencyProvider,
+        options: &LockOptions,
+    ) -> Result<Vec<ResolvedProjectId>, ProjectError> {
+        self.load_roots_locked(&workspace.manifest_path, &workspace.project_manifests(), &workspace.root, dep_provider, options)
+    }
+
+    /// Loads `roots` under the lockfile in `lock_dir`. `graph_manifest` is the
+    /// manifest the provider resolves the whole graph from: the root project,
+    /// or the workspace root.
+    fn load_roots_locked(
+        &mut self,
+        graph_manifest: &Path,
+        roots: &[PathBuf],
+        lock_dir: &Path,
+        dep_provider: &dyn DependencyProvider,
+        options: &LockOptions,
+    ) -> Result<Vec<ResolvedProjectId>, ProjectError> {
+        let lock_path = lock_dir.join(LOCKFILE_NAME);
+        let existing = Lockfile::load(&lock_path)?;
+        let drift = |changes: Vec<String>| ProjectError::LockfileDrift {
+            path: lock_path.clone(),
+            changes: changes.join("; "),
+        };
+        if options.mode == LockMode::Frozen && existing.is_none() {
+            return Err(drift(vec!["the lockfile is missing".to_string()]));
+        }
+        let pins = existing.as_ref().map(|lock| lock.pins(options)).unwrap_or_default();
+        let prepared = dep_provider.prepare(graph_manifest, &pins);
+        if let (LockMode::Frozen, Err(error)) = (options.mode, &prepared) {
+            return Err(drift(vec![error.to_string()]));
+        }
+        prepared?;
+
         // We load the graph using DFS and detect cycles
         let mut visiting = Vec::new();
         let mut visited_stack = HashSet::new();
 
-        let root_id = self.resolve_project_recursive(&canonical_manifest, dep_provider, &mut visiting, &mut visited_stack)?;
+        let mut root_ids = Vec::with_capacity(roots.len());
+        for root in roots {
+            root_ids.push(self.resolve_project_recursive(root, dep_provider, ProjectOrigin::Root, &mut visiting, &mut visited_stack)?);
+        }
 
-        Ok(root_id)
+        let mut lock = Lockfile::from_roots(self, &root_ids, lock_dir);
+        let changes = match &existing {
+            Some(existing) => existing.changes(&lock),
+            None if lock.packages.is_empty() => Vec::new(),
+            None => vec!["the lockfile is missing".to_string()],
+        };
+        if !changes.is_empty() && options.mode != LockMode::ReadOnly {
+            if options.mode != LockMode::Update {
+                return Err(drift(changes));
+            }
+            if let Some(existing) = &existing {
+                lock.keep_checksums(existing);
+            }
+            std::fs::write(&lock_path, lock.to_toml()).map_err(|e| ProjectError::InvalidLockfile {
+                path: lock_path.clone(),
+                reason: format!("cannot write: {e}"),
+            })?;
+        }
+
+        self.resolve_features()?;
+        Ok(root_ids)
+    }
+
+    /// Recomputes every project's active features. Root projects enable
+    /// their `default` features (unless [`CfgOptions::default_features`] is
+    /// off) and [`CfgOptions::features`]; a dependency gets the union of what
+    /// its dependents ask for, in their `[dependencies]` tables and through
+    /// `alias/feature` entries of their own enabled features.
+    fn resolve_features(&mut self) -> Result<(), ProjectError> {
+        let declares = |project: &ResolvedProject, feature: &str| project.manifest.as_ref().is_some_and(|manifest| manifest.features.contains_key(feature));
+        let roots = self.projects.iter().filter(|project| project.origin == ProjectOrigin::Root).collect::<Vec<_>>();
+        let mut pending = Vec::new();
+        for feature in &self.cfg.features {
+            let before = pending.len();
+            pending.extend(roots.iter().filter(|root| declares(root, feature)).map(|root| (root.id, feature.clone())));
+            if pending.len() == before {
+                return Err(ProjectError::UnknownFeature {
+                    project: roots.iter().map(|root| root.name.as_str()).collect::<Vec<_>>().join(", "),
+                    feature: feature.clone(),
+                });
+            }
+        }
+        for project in &self.projects {
+            let Some(manifest) = &project.manifest else {
+                continue;
+            };
+            if project.origin == ProjectOrigin::Root && self.cfg.default_features {
+                pending.push((project.id, "default".to_string()));
+            }
+            for (alias, dependency) in &project.dependencies {
+                let Some(asked) = manifest.dependency_features.get(alias) else {
+                    continue;
+                };
+                if asked.default_features {
+                    pending.push((*dependency, "default".to_string()));
+                }
+                pending.extend(asked.features.iter().map(|feature| (*dependency, feature.clone())));
+            }
+        }
+
+        let mut active: BTreeMap<ResolvedProjectId, BTreeSet<String>> = BTreeMap::new();
+        while let Some((id, feature)) = pending.pop() {
+            let project = self.get_project(id).expect("dependency ids name loaded projects");
+            let Some(implied) = project.manifest.as_ref().and_then(|manifest| manifest.features.get(&feature)) else {
+                // `default` is implicit: a project without one enables nothing.
+                if feature == "default" {
+                    continue;
+                }
+                return Err(ProjectError::UnknownFeature {
+                    project: project.name.clone(),
+                    feature,
+                });
+            };
+            if !active.entry(id).or_default().insert(feature) {
+                continue;
+            }
+            for entry in implied {
+                match entry.split_once('/') {
+                    Some((alias, dep_feature)) => {
+                        let alias = ModuleComponent::from_identifier(alias).expect("validated feature entry");
+                        pending.push((project.dependencies[&alias], dep_feature.to_string()));
+                    }
+                    None => pending.push((id, entry.clone())),
+                }
+            }
+        }
+        for project in &mut self.projects {
+            project.features = active.remove(&project.id).unwrap_or_default();
+        }
+        Ok(())
     }
 
     fn resolve_project_recursive(
         &mut self,
         manifest_path: &Path,
         dep_provider: &dyn DependencyProvider,
+        origin: ProjectOrigin,
         visiting: &mut Vec<(String, PathBuf)>, // (display_name, path)
         visited_stack: &mut HashSet<PathBuf>,
     ) -> Result<ResolvedProjectId, ProjectError> {
@@ -186,22 +430,12 @@ impl ProjectUniverse {
         let mut resolved_dependencies = BTreeMap::new();
 
         for (alias, (_raw_alias, spec)) in &validated.dependencies {
-            let dep_manifest_path = match spec {
-                DependencySpec::Path { path } => {
-                    let dep_dir = if path.is_absolute() { path.clone() } else { root_dir.join(path) };
-                    let dep_manifest = dep_dir.join("project.toml");
-                    if !dep_manifest.is_file() {
-                        return Err(ProjectError::PathDependencyNotFound(dep_manifest));
-                    }
-                    dep_manifest
-                }
-                DependencySpec::Package { package, version } => {
-                    let res = dep_provider.resolve_package(package, version)?;
-                    res.manifest_path
-                }
+            let resolved = match spec {
+                DependencySpec::Path { path } => dep_provider.resolve_path(&root_dir, path)?,
+                DependencySpec::Package { package, version } => dep_provider.resolve_package(package, version)?,
             };
 
-            let dep_id = self.resolve_project_recursive(&dep_manifest_path, dep_provider, visiting, visited_stack)?;
+            let dep_id = self.resolve_project_recursive(&resolved.manifest_path, dep_provider, resolved.origin, visiting, visited_stack)?;
 
             resolved_dependencies.insert(alias.clone(), dep_id);
         }
@@ -232,7 +466,9 @@ impl ProjectUniverse {
             import_roots,
             source_identity: source_identity.clone(),
             persistent_project: true,
+            origin,
             manifest: Some(validated),
+            features: BTreeSet::new(),
         };
 
         self.projects.push(resolved_project);
@@ -279,7 +515,9 @@ impl ProjectUniverse {
             import_roots,
             source_identity: source_identity.clone(),
             persistent_project: false,
+            origin: ProjectOrigin::Root,
             manifest: None,
+            features: BTreeSet::new(),
         };
 
         self.projects.push(resolved_project);
@@ -288,6 +526,30 @@ impl ProjectUniverse {
     }
 }
 
+/// Runs `load` with the dependency provider the project at `manifest_path`
+/// configures: its registry, or none, behind its `vendor/` copies when it has
+/// them. Offline, only the vendored copies are consulted.
+fn with_configured_provider<T>(
+    manifest_path: &Path,
+    options: &LockOptions,
+    load: impl FnOnce(&dyn DependencyProvider) -> Result<T, ProjectError>,
+) -> Result<T, ProjectError> {
+    let registry = if options.offline {
+        None
+    } else {
+        DirectoryRegistryProvider::configured(manifest_path)?
+    };
+    let fallback: Option<&dyn DependencyProvider> = match &registry {
+        _ if options.offline => None,
+        Some(registry) => Some(registry),
+        None => Some(&NullDependencyProvider),
+    };
+    match VendorDependencyProvider::configured(manifest_path, fallback)? {
+        Some(vendor) => load(&vendor),
+        None => load(fallback.unwrap_or(&NullDependencyProvider)),
+    }
+}
+
 /// Discovers the nearest enclosing project root directory containing `project.toml`.
 pub fn discover_owning_project(source_path: &Path) -> Result<Option<PathBuf>, ProjectError> {
     let mut current = if source_path.is_file() {
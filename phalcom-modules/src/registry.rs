@@ -0,0 +1,601 @@
+//! Local directory package registry: a [`DependencyProvider`] that resolves
+//! `{ package = "...", version = "..." }` dependencies against packages on disk.
+//!
+//! A registry root holds either an `index.toml`, which then lists every
+//! published version, or — without one — one directory per version:
+//!
+//! ```text
+//! <registry>/
+//!   index.toml                 # optional
+//!   json/1.2.0/project.toml    # directory layout: <package>/<version>/
+//!   archives/json-1.4.1.tar    # referenced from index.toml
+//! ```
+//!
+//! ```toml
+//! [[package]]
+//! name = "json"
+//! version = "1.4.1"
+//! archive = "archives/json-1.4.1.tar"   # or: path = "json/1.4.1"
+//! ```
+//!
+//! An archive is an uncompressed tar whose entries are relative to the
+//! package root (`project.toml`, `src/package.ph`, ...). Published versions
+//! are immutable, so an archive is unpacked once into the unpack directory
+//! (`<registry>/.unpacked` unless overridden) and reused afterwards.
+//!
+//! Resolution is whole-graph: [`DependencyProvider::prepare`] gathers the
+//! package requirements of the root project and its path dependencies, then
+//! searches for one version per package that satisfies every requirement,
+//! newest first, backtracking when a choice's own dependencies conflict.
+//! When no assignment exists the error names the requirements that could not
+//! be met together.
+//!
+//! The registry is chosen by the `PHALCOM_REGISTRY` environment variable or,
+//! failing that, the root manifest's `[registry] path` (relative to the
+//! project); see [`DirectoryRegistryProvider::configured`].
+
+use crate::error::ProjectError;
+use crate::lockfile::LockPins;
+use crate::manifest::{DependencyProvider, DependencySpec, ProjectManifest, ResolvedDependencySource, ValidatedProjectManifest};
+use crate::project::ProjectOrigin;
+use crate::semver::{Version, VersionReq};
+use crate::workspace::Workspace;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Environment variable naming the registry root; overrides `[registry]`.
+pub const REGISTRY_ENV: &str = "PHALCOM_REGISTRY";
+
+/// Index file name within a registry root.
+pub const INDEX_FILE: &str = "index.toml";
+
+/// Candidate versions tried before resolution gives up on a pathological graph.
+const MAX_ATTEMPTS: usize = 100_000;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RegistryIndex {
+    #[serde(default)]
+    package: Vec<IndexEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IndexEntry {
+    name: String,
+    version: String,
+    path: Option<PathBuf>,
+    archive: Option<PathBuf>,
+}
+
+/// Where a published version's files live.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackageSource {
+    Directory(PathBuf),
+    Archive(PathBuf),
+}
+
+#[derive(Debug, Clone)]
+struct RegistryEntry {
+    version: Version,
+    source: PackageSource,
+}
+
+/// One `package = ..., version = ...` edge of the dependency graph.
+#[derive(Debug, Clone)]
+struct Requirement {
+    package: String,
+    req: VersionReq,
+    /// Who asked: `app` for a project, `json 1.2.0` for a registry package.
+    by: String,
+}
+
+/// A package version chosen by resolution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectedPackage {
+    pub name: String,
+    pub version: Version,
+    pub source: PackageSource,
+}
+
+/// Resolves registry dependencies against a registry directory on disk.
+#[derive(Debug)]
+pub struct DirectoryRegistryProvider {
+    root: PathBuf,
+    unpack_dir: PathBuf,
+    /// Published versions per package, newest first.
+    packages: BTreeMap<String, Vec<RegistryEntry>>,
+    /// The version chosen for each package by the last [`DependencyProvider::prepare`].
+    selection: Mutex<Option<BTreeMap<String, Version>>>,
+}
+
+impl DirectoryRegistryProvider {
+    /// Opens the registry at `root`, reading `index.toml` when present and
+    /// scanning `<package>/<version>/` directories otherwise.
+    pub fn open(root: impl AsRef<Path>) -> Result<Self, ProjectError> {
+        let root = root.as_ref();
+        let root = root.canonicalize().map_err(|e| invalid(root, format!("cannot open registry: {e}")))?;
+        let mut packages: BTreeMap<String, Vec<RegistryEntry>> = BTreeMap::new();
+
+        let index_path = root.join(INDEX_FILE);
+        if index_path.is_file() {
+            let text = std::fs::read_to_string(&index_path).map_err(|e| invalid(&root, format!("cannot read {INDEX_FILE}: {e}")))?;
+            let index: RegistryIndex = toml::from_str(&text).map_err(|e| invalid(&root, format!("invalid {INDEX_FILE}: {e}")))?;
+            for entry in index.package {
+                let version = Version::parse(&entry.version).map_err(|e| invalid(&root, format!("package '{}': {e}", entry.name)))?;
+                let source = match (entry.path, entry.archive) {
+                    (Some(path), None) => PackageSource::Directory(root.join(path)),
+                    (None, Some(archive)) => PackageSource::Archive(root.join(archive)),
+                    _ => {
+                        return Err(invalid(
+                            &root,
+                            format!("package '{} {version}' must give exactly one of 'path' or 'archive'", entry.name),
+                        ));
+                    }
+                };
+                packages.entry(entry.name).or_default().push(RegistryEntry { version, source });
+            }
+        } else {
+            for package_dir in read_dir_sorted(&root)? {
+                let Some(name) = package_dir.file_name().and_then(|name| name.to_str()).map(str::to_string) else {
+                    continue;
+                };
+                if name.starts_with('.') || !package_dir.is_dir() {
+                    continue;
+                }
+                for version_dir in read_dir_sorted(&package_dir)? {
+                    let Some(version) = version_dir
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .and_then(|name| Version::parse(name).ok())
+                    else {
+                        continue;
+                    };
+                    if version_dir.join("project.toml").is_file() {
+                        packages.entry(name.clone()).or_default().push(RegistryEntry {
+                            version,
+                            source: PackageSource::Directory(version_dir),
+                        });
+                    }
+                }
+            }
+        }
+
+        for (name, entries) in &mut packages {
+            entries.sort_by(|a, b| b.version.cmp(&a.version));
+            if let Some(pair) = entries.windows(2).find(|pair| pair[0].version == pair[1].version) {
+                return Err(invalid(&root, format!("package '{name}' lists version {} twice", pair[0].version)));
+            }
+        }
+
+        Ok(Self {
+            unpack_dir: root.join(".unpacked"),
+            root,
+            packages,
+            selection: Mutex::new(None),
+        })
+    }
+
+    /// The registry configured for the project whose manifest is
+    /// `manifest_path`: `$PHALCOM_REGISTRY` if set, else `[registry] path`
+    /// of its workspace root, or of the project itself outside a workspace.
+    /// `Ok(None)` means the project uses no registry; a manifest that does not
+    /// load is left for project loading to report.
+    pub fn configured(manifest_path: impl AsRef<Path>) -> Result<Option<Self>, ProjectError> {
+        if let Some(root) = std::env::var_os(REGISTRY_ENV).filter(|root| !root.is_empty()) {
+            return Self::open(PathBuf::from(root)).map(Some);
+        }
+        let manifest_path = manifest_path.as_ref();
+        let workspace = Workspace::enclosing(manifest_path.parent().unwrap_or(Path::new(".")))?;
+        let (manifest_path, manifest) = match workspace {
+            Some(workspace) => (workspace.manifest_path, Ok(workspace.manifest)),
+            None => (manifest_path.to_path_buf(), ProjectManifest::load_file(manifest_path)),
+        };
+        let Ok(manifest) = manifest else {
+            return Ok(None);
+        };
+        let Some(registry) = manifest.registry else {
+            return Ok(None);
+        };
+        let root = if registry.path.is_absolute() {
+            registry.path
+        } else {
+            manifest_path.parent().unwrap_or(Path::new(".")).join(registry.path)
+        };
+        Self::open(root).map(Some)
+    }
+
+    /// Unpacks archives under `dir` instead of `<registry>/.unpacked`.
+    pub fn with_unpack_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.unpack_dir = dir.into();
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Published versions of `package`, newest first.
+    pub fn versions(&self, package: &str) -> Vec<Version> {
+        self.packages
+            .get(package)
+            .map(|entries| entries.iter().map(|entry| entry.version.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// The packages chosen by the last [`DependencyProvider::prepare`], by name.
+    pub fn selected_packages(&self) -> Vec<SelectedPackage> {
+        let selection = self.selection.lock().expect("registry selection lock");
+        selection
+            .iter()
+            .flatten()
+            .filter_map(|(name, version)| {
+                let entry = self.entry(name, version)?;
+                Some(SelectedPackage {
+                    name: name.clone(),
+                    version: version.clone(),
+                    source: entry.source.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Resolves the whole dependency graph rooted at `root_manifest`: one
+    /// version per registry package, satisfying every requirement in the graph.
+    pub fn resolve_graph(&self, root_manifest: &Path) -> Result<BTreeMap<String, Version>, ProjectError> {
+        self.resolve_graph_pinned(root_manifest, &LockPins::none())
+    }
+
+    /// [`Self::resolve_graph`], trying each package's pinned version before
+    /// any other; strict pins rule out every other version.
+    pub fn resolve_graph_pinned(&self, root_manifest: &Path, pins: &LockPins) -> Result<BTreeMap<String, Version>, ProjectError> {
+        let requirements = collect_root_requirements(root_manifest)?;
+        let mut search = Search {
+            registry: self,
+            pins,
+            selected: BTreeMap::new(),
+            requirements,
+            attempts: 0,
+            conflict: None,
+        };
+        if search.run()? {
+            return Ok(search.selected);
+        }
+        Err(search.into_error())
+    }
+
+    fn entry(&self, package: &str, version: &Version) -> Option<&RegistryEntry> {
+        self.packages.get(package)?.iter().find(|entry| entry.version == *version)
+    }
+
+    /// The registry requirements declared by `package` at `entry`'s version.
+    fn dependencies_of(&self, package: &str, entry: &RegistryEntry) -> Result<Vec<Requirement>, ProjectError> {
+        let text = match &entry.source {
+            PackageSource::Directory(dir) => std::fs::read_to_string(dir.join("project.toml"))
+                .map_err(|e| invalid(&self.root, format!("cannot read manifest of '{package} {}': {e}", entry.version)))?,
+            PackageSource::Archive(archive) => read_archive_manifest(archive).map_err(|reason| invalid(&self.root, reason))?,
+        };
+        let manifest = ProjectManifest::parse(&text)
+            .and_then(|manifest| manifest.validate())
+            .map_err(|e| invalid(&self.root, format!("'{package} {}': {e}", entry.version)))?;
+        if manifest.name != package {
+            return Err(invalid(
+                &self.root,
+                format!(
+                    "'{package} {}' is listed under the wrong name; its manifest names '{}'",
+                    entry.version, manifest.name
+                ),
+            ));
+        }
+        if manifest.version.as_deref().map(Version::parse) != Some(Ok(entry.version.clone())) {
+            return Err(invalid(
+                &self.root,
+                format!("'{package} {}' has manifest version {:?}", entry.version, manifest.version.unwrap_or_default()),
+            ));
+        }
+        let by = format!("{package} {}", entry.version);
+        if let Some((alias, _)) = manifest.dependencies.values().find(|(_, spec)| matches!(spec, DependencySpec::Path { .. })) {
+            return Err(invalid(
+                &self.root,
+                format!("'{by}' declares path dependency '{alias}'; registry packages can only depend on registry packages"),
+            ));
+        }
+        package_requirements(&manifest, &by)
+    }
+
+    /// The on-disk `project.toml` of a chosen version, unpacking it if needed.
+    fn materialize(&self, package: &str, entry: &RegistryEntry) -> Result<PathBuf, ProjectError> {
+        let archive = match &entry.source {
+            PackageSource::Directory(dir) => return Ok(dir.join("project.toml")),
+            PackageSource::Archive(archive) => archive,
+        };
+        let target = self.unpack_dir.join(format!("{package}-{}", entry.version));
+        if !target.join("project.toml").is_file() {
+            // Unpack beside the target and rename into place, so a concurrent
+            // loader never sees a half-written package.
+            let staging = self.unpack_dir.join(format!(".{package}-{}.{}", entry.version, std::process::id()));
+            let _ = std::fs::remove_dir_all(&staging);
+            std::fs::create_dir_all(&staging).map_err(|e| invalid(&self.root, format!("cannot create {}: {e}", staging.display())))?;
+            let file = File::open(archive).map_err(|e| invalid(&self.root, format!("cannot open {}: {e}", archive.display())))?;
+            tar::Archive::new(file)
+                .unpack(&staging)
+                .map_err(|e| invalid(&self.root, format!("cannot unpack {}: {e}", archive.display())))?;
+            if std::fs::rename(&staging, &target).is_err() {
+                let _ = std::fs::remove_dir_all(&staging);
+            }
+        }
+        let manifest = target.join("project.toml");
+        if !manifest.is_file() {
+            return Err(invalid(&self.root, format!("{} has no project.toml at its root", archive.display())));
+        }
+        Ok(manifest)
+    }
+}
+
+impl DependencyProvider for DirectoryRegistryProvider {
+    fn prepare(&self, root_manifest: &Path, pins: &LockPins) -> Result<(), ProjectError> {
+        let selection = self.resolve_graph_pinned(root_manifest, pins)?;
+        *self.selection.lock().expect("registry selection lock") = Some(selection);
+        Ok(())
+    }
+
+    fn resolve_package(&self, package: &str, version_requirement: &str) -> Result<ResolvedDependencySource, ProjectError> {
+        let req = parse_requirement(package, version_requirement)?;
+        let selected = self
+            .selection
+            .lock()
+            .expect("registry selection lock")
+            .as_ref()
+            .and_then(|selection| selection.get(package).cloned());
+        let entry = match selected {
+            Some(version) => self
+                .entry(package, &version)
+                .filter(|entry| req.matches(&entry.version))
+                .ok_or_else(|| ProjectError::DependencyConflict {
+                    package: package.to_string(),
+                    explanation: format!("resolved version {version} does not satisfy {req}"),
+                })?,
+            // Not part of a prepared graph: take the newest match.
+            None => self
+                .packages
+                .get(package)
+                .and_then(|entries| entries.iter().find(|entry| req.matches(&entry.version)))
+                .ok_or_else(|| ProjectError::UnresolvedPackageDependency {
+                    package: package.to_string(),
+                    version_requirement: version_requirement.to_string(),
+                })?,
+        };
+        Ok(ResolvedDependencySource {
+            manifest_path: self.materialize(package, entry)?,
+            origin: ProjectOrigin::Package { registry: self.root.clone() },
+        })
+    }
+}
+
+/// The deepest dead end the search hit, kept to explain a failed resolution.
+#[derive(Debug)]
+struct Conflict {
+    depth: usize,
+    package: String,
+    requirements: Vec<Requirement>,
+}
+
+struct Search<'r> {
+    registry: &'r DirectoryRegistryProvider,
+    pins: &'r LockPins,
+    selected: BTreeMap<String, Version>,
+    requirements: Vec<Requirement>,
+    attempts: usize,
+    conflict: Option<Conflict>,
+}
+
+impl<'r> Search<'r> {
+    fn run(&mut self) -> Result<bool, ProjectError> {
+        // Decide the most constrained open package first: fewest candidates
+        // left, then name, so resolution is deterministic.
+        let open: HashSet<&str> = self
+            .requirements
+            .iter()
+            .map(|requirement| requirement.package.as_str())
+            .filter(|package| !self.selected.contains_key(*package))
+            .collect();
+        let Some((package, candidates)) = open
+            .into_iter()
+            .map(|package| (package.to_string(), self.candidates(package)))
+            .min_by(|(a, a_candidates), (b, b_candidates)| a_candidates.len().cmp(&b_candidates.len()).then_with(|| a.cmp(b)))
+        else {
+            return Ok(true);
+        };
+        if candidates.is_empty() {
+            self.record_conflict(&package, None);
+            return Ok(false);
+        }
+
+        for entry in candidates {
+            self.attempts += 1;
+            if self.attempts > MAX_ATTEMPTS {
+                return Err(ProjectError::DependencyConflict {
+                    package,
+                    explanation: format!("resolution gave up after trying {MAX_ATTEMPTS} candidate versions"),
+                });
+            }
+            let dependencies = self.registry.dependencies_of(&package, entry)?;
+            if let Some(clash) = dependencies
+                .iter()
+                .find(|dependency| self.selected.get(&dependency.package).is_some_and(|version| !dependency.req.matches(version)))
+            {
+                self.record_conflict(&clash.package.clone(), Some(clash.clone()));
+                continue;
+            }
+            let mark = self.requirements.len();
+            self.selected.insert(package.clone(), entry.version.clone());
+            self.requirements.extend(dependencies);
+            if self.run()? {
+                return Ok(true);
+            }
+            self.requirements.truncate(mark);
+            self.selected.remove(&package);
+        }
+        Ok(false)
+    }
+
+    /// Versions of `package` every current requirement accepts: the pinned
+    /// version first, then newest first.
+    fn candidates(&self, package: &str) -> Vec<&'r RegistryEntry> {
+        let Some(entries) = self.registry.packages.get(package) else {
+            return Vec::new();
+        };
+        let pinned = self.pins.versions.get(package);
+        let mut candidates: Vec<_> = entries
+            .iter()
+            .filter(|entry| !self.pins.strict || pinned == Some(&entry.version))
+            .filter(|entry| {
+                self.requirements
+                    .iter()
+                    .filter(|requirement| requirement.package == package)
+                    .all(|requirement| requirement.req.matches(&entry.version))
+            })
+            .collect();
+        candidates.sort_by_key(|entry| pinned != Some(&entry.version));
+        candidates
+    }
+
+    fn record_conflict(&mut self, package: &str, extra: Option<Requirement>) {
+        let depth = self.selected.len();
+        if self.conflict.as_ref().is_some_and(|conflict| conflict.depth > depth) {
+            return;
+        }
+        let mut requirements: Vec<Requirement> = self.requirements.iter().filter(|requirement| requirement.package == package).cloned().collect();
+        requirements.extend(extra);
+        self.conflict = Some(Conflict {
+            depth,
+            package: package.to_string(),
+            requirements,
+        });
+    }
+
+    fn into_error(self) -> ProjectError {
+        let conflict = self.conflict.expect("a failed search records a conflict");
+        let available = self.registry.versions(&conflict.package);
+        let mut lines: Vec<String> = Vec::new();
+        for requirement in &conflict.requirements {
+            let line = format!("{} requires {} {}", requirement.by, conflict.package, requirement.req);
+            if !lines.contains(&line) {
+                lines.push(line);
+            }
+        }
+        if let Some(version) = self.selected.get(&conflict.package) {
+            lines.push(format!("{} {version} was already selected", conflict.package));
+        }
+        if available.is_empty() {
+            return ProjectError::UnresolvedPackageDependency {
+                version_requirement: conflict
+                    .requirements
+                    .iter()
+                    .map(|requirement| requirement.req.to_string())
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                package: conflict.package,
+            };
+        }
+        let available = available.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+        ProjectError::DependencyConflict {
+            explanation: format!("no version satisfies every requirement: {}; available: {available}", lines.join("; ")),
+            package: conflict.package,
+        }
+    }
+}
+
+/// Registry requirements of the root project and of every project reachable
+/// from it through path dependencies or, for a workspace root, membership.
+fn collect_root_requirements(root_manifest: &Path) -> Result<Vec<Requirement>, ProjectError> {
+    let mut requirements = Vec::new();
+    let mut seen = HashSet::new();
+    let mut pending = vec![root_manifest.to_path_buf()];
+    while let Some(manifest_path) = pending.pop() {
+        let Ok(canonical) = manifest_path.canonicalize() else {
+            // Missing path dependencies are reported when the graph is loaded.
+            continue;
+        };
+        if !seen.insert(canonical.clone()) {
+            continue;
+        }
+        let manifest = ProjectManifest::load_file(&canonical)?;
+        if manifest.workspace.is_some() {
+            let workspace = Workspace::from_manifest(&canonical, manifest.clone())?;
+            pending.extend(workspace.members.iter().map(|member| member.join("project.toml")));
+        }
+        if manifest.project.is_none() {
+            continue;
+        }
+        let manifest = manifest.validate()?;
+        let root_dir = canonical.parent().unwrap_or(Path::new("."));
+        for (_raw_alias, spec) in manifest.dependencies.values() {
+            if let DependencySpec::Path { path } = spec {
+                let dir = if path.is_absolute() { path.clone() } else { root_dir.join(path) };
+                pending.push(dir.join("project.toml"));
+            }
+        }
+        requirements.extend(package_requirements(&manifest, &manifest.name)?);
+    }
+    Ok(requirements)
+}
+
+/// The `package = ..., version = ...` dependencies of `manifest`, as asked by `by`.
+fn package_requirements(manifest: &ValidatedProjectManifest, by: &str) -> Result<Vec<Requirement>, ProjectError> {
+    let mut requirements = Vec::new();
+    for (_raw_alias, spec) in manifest.dependencies.values() {
+        if let DependencySpec::Package { package, version } = spec {
+            requirements.push(Requirement {
+                package: package.clone(),
+                req: parse_requirement(package, version)?,
+                by: by.to_string(),
+            });
+        }
+    }
+    Ok(requirements)
+}
+
+fn parse_requirement(package: &str, requirement: &str) -> Result<VersionReq, ProjectError> {
+    VersionReq::parse(requirement).map_err(|e| ProjectError::InvalidProjectManifest(format!("invalid version requirement for package '{package}': {e}")))
+}
+
+fn read_archive_manifest(archive: &Path) -> Result<String, String> {
+    let file = File::open(archive).map_err(|e| format!("cannot open {}: {e}", archive.display()))?;
+    let mut tar = tar::Archive::new(file);
+    let entries = tar.entries().map_err(|e| format!("cannot read {}: {e}", archive.display()))?;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("cannot read {}: {e}", archive.display()))?;
+        let is_manifest = entry.path().is_ok_and(|path| {
+            path.components()
+                .filter(|c| !matches!(c, std::path::Component::CurDir))
+                .eq(Path::new("project.toml").components())
+        });
+        if is_manifest {
+            let mut text = String::new();
+            entry
+                .read_to_string(&mut text)
+                .map_err(|e| format!("cannot read project.toml in {}: {e}", archive.display()))?;
+            return Ok(text);
+        }
+    }
+    Err(format!("{} has no project.toml at its root", archive.display()))
+}
+
+fn read_dir_sorted(dir: &Path) -> Result<Vec<PathBuf>, ProjectError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| invalid(dir, format!("cannot read directory: {e}")))?;
+    let mut paths: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect();
+    paths.sort();
+    Ok(paths)
+}
+
+fn invalid(root: &Path, reason: String) -> ProjectError {
+    ProjectError::InvalidRegistry {
+        root: root.to_path_buf(),
+        reason,
+    }
+}
@@ -1,8 +1,9 @@
+use crate::artifact::{PackageArtifactProvider, ResolvedPackageArtifact};
 use crate::builtin::BuiltinProjectSourceProvider;
 use crate::error::{ModuleLoadError, ModuleResolutionError};
 use crate::identity::{ImportRootTarget, ModuleComponent, ModuleId, ModulePath, ResolvedProjectId, SourceLocation};
 use crate::interface::{InterfaceBuilder, PackagePathSurface, UnlinkedModuleInterface};
-use crate::project::ProjectUniverse;
+use crate::project::{ProjectUniverse, ResolvedProject};
 use crate::source::{ModuleKind, ParsedModuleUnit, SourceProvider, SourceUnit};
 use phalcom_ast::ast::{ImportPath, ImportRoot};
 use std::collections::HashMap;
@@ -15,6 +16,7 @@ pub struct ModuleResolver<'u, P: SourceProvider> {
     pub source: &'u P,
     parsed_cache: HashMap<ModuleId, Result<Arc<ParsedModuleUnit>, ModuleLoadError>>,
     interface_cache: HashMap<ModuleId, Result<UnlinkedModuleInterface, ModuleLoadError>>,
+    packages: Option<&'u dyn PackageArtifactProvider>,
 }
 
 impl<'u, P: SourceProvider> ModuleResolver<'u, P> {
@@ -24,9 +26,18 @@ impl<'u, P: SourceProvider> ModuleResolver<'u, P> {
             source,
             parsed_cache: HashMap::new(),
             interface_cache: HashMap::new(),
+            packages: None,
         }
     }
 
+    /// Resolves imports of the projects `packages` has an artifact for
+    /// against that artifact's exposed paths and interfaces, without
+    /// locating or parsing their sources.
+    pub fn with_packages(mut self, packages: &'u dyn PackageArtifactProvider) -> Self {
+        self.packages = Some(packages);
+        self
+    }
+
     /// Resolves an AST `ImportPath` written inside the context of `importer`.
     pub fn resolve_import(&mut self, importer: &ModuleId, syntax: &ImportPath) -> Result<SourceUnit, ModuleResolutionError> {
         let importer_project = match importer.project {
@@ -42,16 +53,19 @@ impl<'u, P: SourceProvider> ModuleResolver<'u, P> {
                 let root_comp =
                     ModuleComponent::from_identifier(&root_seg.name).map_err(|e| ModuleResolutionError::InvalidModuleName(root_seg.name.clone(), e))?;
 
+                // A project's own namespace and dependency aliases shadow an
+                // extension module's root; `universe` and `std` are reserved.
+                let project_root = importer_project.and_then(|proj| proj.import_roots().get(&root_comp).copied());
                 let (target_root, is_self) = if root_seg.name == "universe" {
                     (ImportRootTarget::Builtin(crate::identity::BuiltinProject::Universe), false)
                 } else if root_seg.name == "std" {
                     (ImportRootTarget::Builtin(crate::identity::BuiltinProject::Std), false)
-                } else if let Some(proj) = importer_project {
-                    let roots = proj.import_roots();
-                    roots
-                        .get(&root_comp)
-                        .copied()
-                        .ok_or_else(|| ModuleResolutionError::UnknownImportRoot(root_seg.name.clone()))?
+                } else if let Some(root) = project_root {
+                    root
+                } else if phalcom_native_meta::is_extension_root(&root_seg.name) {
+                    (ImportRootTarget::Builtin(crate::identity::BuiltinProject::Native), false)
+                } else if importer_project.is_some() {
+                    return Err(ModuleResolutionError::UnknownImportRoot(root_seg.name.clone()));
                 } else {
                     return Err(ModuleResolutionError::ModuleNotFound(format!(
                         "standalone module {} cannot import user dependency '{}' without a project context",
@@ -65,10 +79,9 @@ impl<'u, P: SourceProvider> ModuleResolver<'u, P> {
                     let comp = ModuleComponent::from_identifier(&seg.name).map_err(|e| ModuleResolutionError::InvalidModuleName(seg.name.clone(), e))?;
                     components.push(comp);
                 }
-                let target_path = ModulePath::from_components(components);
-
                 let target_project_id = match target_root {
                     ImportRootTarget::Builtin(builtin) => {
+                        let target_path = builtin.import_target(root_comp, components).path;
                         let provider = BuiltinProjectSourceProvider::new(builtin);
                         let kind = provider
                             .kind(&target_path)
@@ -93,6 +106,11 @@ impl<'u, P: SourceProvider> ModuleResolver<'u, P> {
                     }
                     ImportRootTarget::Resolved(id) => id,
                 };
+                let target_path = ModulePath::from_components(components);
+
+                if let Some(artifact) = self.published_artifact(target_project_id)? {
+                    return published_unit(artifact, &target_path);
+                }
 
                 let target_project = self
                     .universe
@@ -104,7 +122,7 @@ impl<'u, P: SourceProvider> ModuleResolver<'u, P> {
                     self.validate_external_path(target_project_id, &target_path)?;
                 }
 
-                self.source.locate(target_project, &target_path)
+                self.locate(target_project, &target_path)
             }
             ImportRoot::Relative { dots, range: _ } => {
                 let dots = *dots as usize;
@@ -122,7 +140,7 @@ impl<'u, P: SourceProvider> ModuleResolver<'u, P> {
                 })?;
 
                 // Determine importer package depth
-                let importer_unit = self.source.locate(importer_project, &importer.path)?;
+                let importer_unit = self.locate(importer_project, &importer.path)?;
                 let package_path = match importer_unit.kind {
                     ModuleKind::Package => importer.path.clone(),
                     ModuleKind::Module => importer.path.parent().unwrap_or_else(ModulePath::root),
@@ -147,11 +165,31 @@ impl<'u, P: SourceProvider> ModuleResolver<'u, P> {
                 }
 
                 let target_path = ModulePath::from_components(resolved_components);
-                self.source.locate(importer_project, &target_path)
+                self.locate(importer_project, &target_path)
             }
         }
     }
 
+    /// Locates `path` in `project`, unless a `[modules]` condition disables it.
+    fn locate(&self, project: &ResolvedProject, path: &ModulePath) -> Result<SourceUnit, ModuleResolutionError> {
+        if let Some((gated, condition)) = self.universe.cfg_context(project.id.into()).disabled_by(path) {
+            return Err(ModuleResolutionError::ModuleDisabled {
+                path: gated.to_string(),
+                project: project.name.clone(),
+                condition: condition.to_string(),
+            });
+        }
+        self.source.locate(project, path)
+    }
+
+    /// The published artifact standing in for `project`'s sources, if any.
+    fn published_artifact(&self, project: ResolvedProjectId) -> Result<Option<&'u ResolvedPackageArtifact>, ModuleResolutionError> {
+        let Some(packages) = self.packages else { return Ok(None) };
+        packages
+            .get_package_artifact(project)
+            .map_err(|e| ModuleResolutionError::PackageNotFoundError(format!("{project}: {e}")))
+    }
+
     /// Validates that an external module path is exposed hierarchically by each intermediate package.
     pub fn validate_external_path(&mut self, target_project_id: ResolvedProjectId, path: &ModulePath) -> Result<(), ModuleResolutionError> {
         let components = path.components();
@@ -204,7 +242,9 @@ impl<'u, P: SourceProvider> ModuleResolver<'u, P> {
         })
     }
 
-    /// Loads and parses a module unit, caching the parsed AST and source artifact.
+    /// Loads and parses a module unit, caching the parsed AST and source
+    /// artifact. The AST is already filtered by the owning project's
+    /// [`crate::cfg::CfgContext`].
     pub fn load_parsed(&mut self, module_id: &ModuleId) -> Result<Arc<ParsedModuleUnit>, ModuleLoadError> {
         if let Some(res) = self.parsed_cache.get(module_id) {
             return res.clone();
@@ -225,7 +265,7 @@ impl<'u, P: SourceProvider> ModuleResolver<'u, P> {
             .get_project(project_id)
             .ok_or_else(|| ModuleResolutionError::ModuleNotFound(format!("Project {:?} not found", project_id)))?;
 
-        let unit = self.source.locate(project, &module_id.path)?;
+        let unit = self.locate(project, &module_id.path)?;
         let source_text = self.source.read(&unit.source.source_id).map_err(ModuleResolutionError::Source)?;
 
         let parse_result = phalcom_ast::parse(&source_text, 0);
@@ -240,12 +280,22 @@ impl<'u, P: SourceProvider> ModuleResolver<'u, P> {
             return Err(load_err);
         }
 
+        let mut program = parse_result.program;
+        if let Err(error) = self.universe.cfg_context(module_id.project).filter_program(&module_id.path, &mut program) {
+            let load_err = ModuleLoadError::Interface {
+                module: module_id.clone(),
+                error,
+            };
+            self.parsed_cache.insert(module_id.clone(), Err(load_err.clone()));
+            return Err(load_err);
+        }
+
         let parsed = Arc::new(ParsedModuleUnit {
             id: module_id.clone(),
             kind: unit.kind,
             source: Some(unit.source),
             text: source_text,
-            program: Arc::new(parse_result.program),
+            program: Arc::new(program),
         });
 
         self.parsed_cache.insert(module_id.clone(), Ok(parsed.clone()));
@@ -283,3 +333,44 @@ impl<'u, P: SourceProvider> ModuleResolver<'u, P> {
         Ok(unlinked)
     }
 }
+
+/// The source unit of the module at `path` in a published package, after
+/// the same hierarchical exposure check a source package gets.
+fn published_unit(artifact: &ResolvedPackageArtifact, path: &ModulePath) -> Result<SourceUnit, ModuleResolutionError> {
+    let name = artifact
+        .manifest
+        .project
+        .as_ref()
+        .map_or_else(|| artifact.package_id.to_string(), |project| project.name.clone());
+    let mut current = ModulePath::root();
+    for component in path.components() {
+        let child = current.join(component.clone());
+        if !artifact.is_path_exposed(&child) {
+            let exposed = artifact
+                .exposed_paths
+                .iter()
+                .filter(|exposed| exposed.parent().as_ref() == Some(&current))
+                .filter_map(|exposed| exposed.components().last().map(|c| c.as_str().to_string()))
+                .collect();
+            return Err(ModuleResolutionError::ModulePathNotExposed {
+                path: path.to_string(),
+                project: name,
+                exposed,
+            });
+        }
+        current = child;
+    }
+    let id = ModuleId::resolved(artifact.package_id, path.clone());
+    let interface = artifact
+        .interfaces
+        .get(&id)
+        .ok_or_else(|| ModuleResolutionError::ModuleNotFound(format!("{id} is not in the published package")))?;
+    Ok(SourceUnit {
+        id: id.clone(),
+        kind: interface.kind,
+        source: SourceLocation {
+            source_id: crate::identity::SourceId(format!("package:{id}").into_boxed_str()),
+            display_path: PathBuf::from(format!("<package:{name}>/{path}")),
+        },
+    })
+}
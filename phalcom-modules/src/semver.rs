@@ -0,0 +1,369 @@
+//! Semantic versions and version requirements for registry dependencies.
+//!
+//! Versions are `MAJOR.MINOR.PATCH[-PRE][+BUILD]` (build metadata is accepted
+//! and ignored for ordering). A requirement is a comma-separated conjunction
+//! of comparators, with the Cargo meanings:
+//!
+//! | Comparator | Matches |
+//! |---|---|
+//! | `^1.2.3`, `1.2.3` | `>=1.2.3, <2.0.0` (`^0.3` → `<0.4.0`, `^0.0.3` → `<0.0.4`) |
+//! | `~1.2` | `>=1.2.0, <1.3.0` (`~1` → `<2.0.0`) |
+//! | `=1.2` | `>=1.2.0, <1.3.0` |
+//! | `>=1`, `>1.2`, `<2`, `<=1.4.1` | the plain ordering, missing parts read as `0` |
+//! | `*`, `1.*`, `1.2.*` | any version / that major / that minor |
+//!
+//! A pre-release version only matches a requirement that names a pre-release
+//! of the same `MAJOR.MINOR.PATCH`, so `^1.0` never selects `1.1.0-beta`.
+
+use std::cmp::Ordering;
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("invalid version '{input}': {reason}")]
+pub struct SemverError {
+    pub input: String,
+    pub reason: String,
+}
+
+impl SemverError {
+    fn new(input: &str, reason: impl Into<String>) -> Self {
+        Self {
+            input: input.to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// A parsed semantic version.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Dot-separated pre-release identifiers (`beta.2` → `["beta", "2"]`).
+    pub pre: Vec<String>,
+}
+
+impl Version {
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre: Vec::new(),
+        }
+    }
+
+    /// Parses a full `MAJOR.MINOR.PATCH[-PRE][+BUILD]` version.
+    pub fn parse(input: &str) -> Result<Self, SemverError> {
+        let text = input.trim();
+        let text = text.split_once('+').map_or(text, |(version, _build)| version);
+        let (core, pre) = match text.split_once('-') {
+            Some((core, pre)) => (core, parse_pre(input, pre)?),
+            None => (text, Vec::new()),
+        };
+        let parts: Vec<&str> = core.split('.').collect();
+        if parts.len() != 3 {
+            return Err(SemverError::new(input, "expected MAJOR.MINOR.PATCH"));
+        }
+        Ok(Self {
+            major: parse_number(input, parts[0])?,
+            minor: parse_number(input, parts[1])?,
+            patch: parse_number(input, parts[2])?,
+            pre,
+        })
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    fn same_release(&self, other: &Version) -> bool {
+        (self.major, self.minor, self.patch) == (other.major, other.minor, other.patch)
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                // A release sorts after all of its pre-releases.
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => compare_pre(&self.pre, &other.pre),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_number(input: &str, part: &str) -> Result<u64, SemverError> {
+    if part.is_empty() || !part.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(SemverError::new(input, format!("'{part}' is not a number")));
+    }
+    if part.len() > 1 && part.starts_with('0') {
+        return Err(SemverError::new(input, format!("'{part}' has a leading zero")));
+    }
+    part.parse().map_err(|_| SemverError::new(input, format!("'{part}' is out of range")))
+}
+
+fn parse_pre(input: &str, pre: &str) -> Result<Vec<String>, SemverError> {
+    pre.split('.')
+        .map(|identifier| {
+            if identifier.is_empty() || !identifier.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'-') {
+                Err(SemverError::new(input, format!("invalid pre-release identifier '{identifier}'")))
+            } else {
+                Ok(identifier.to_string())
+            }
+        })
+        .collect()
+}
+
+/// SemVer §11: numeric identifiers compare numerically and sort before
+/// alphanumeric ones; a shorter prefix sorts first.
+fn compare_pre(left: &[String], right: &[String]) -> Ordering {
+    for (a, b) in left.iter().zip(right) {
+        let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => a.cmp(b),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    left.len().cmp(&right.len())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+    /// `1.*` / `1.2.*`: the spelled-out parts must agree.
+    Wildcard,
+    /// `*`
+    Any,
+}
+
+/// One comparator; `minor`/`patch` are `None` when the requirement left them out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Comparator {
+    op: Op,
+    major: u64,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    pre: Vec<String>,
+}
+
+impl Comparator {
+    fn parse(input: &str, text: &str) -> Result<Self, SemverError> {
+        let text = text.trim();
+        let (op, rest) = [
+            (">=", Op::GreaterEq),
+            ("<=", Op::LessEq),
+            (">", Op::Greater),
+            ("<", Op::Less),
+            ("=", Op::Exact),
+            ("~", Op::Tilde),
+            ("^", Op::Caret),
+        ]
+        .into_iter()
+        .find_map(|(prefix, op)| text.strip_prefix(prefix).map(|rest| (op, rest.trim_start())))
+        .unwrap_or((Op::Caret, text));
+
+        let rest = rest.split_once('+').map_or(rest, |(version, _build)| version);
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => (core, parse_pre(input, pre)?),
+            None => (rest, Vec::new()),
+        };
+        let parts: Vec<&str> = core.split('.').collect();
+        if parts.is_empty() || parts.len() > 3 {
+            return Err(SemverError::new(input, format!("'{text}' is not a version comparator")));
+        }
+        let wildcard_at = parts.iter().position(|part| matches!(*part, "*" | "x" | "X"));
+        if let Some(index) = wildcard_at {
+            if op != Op::Caret || index + 1 != parts.len() || !pre.is_empty() {
+                return Err(SemverError::new(
+                    input,
+                    format!("wildcard must be the last part of a bare comparator in '{text}'"),
+                ));
+            }
+            if index == 0 {
+                return Ok(Self {
+                    op: Op::Any,
+                    major: 0,
+                    minor: None,
+                    patch: None,
+                    pre,
+                });
+            }
+        }
+        let numbers = &parts[..wildcard_at.unwrap_or(parts.len())];
+        let op = if wildcard_at.is_some() { Op::Wildcard } else { op };
+        if !pre.is_empty() && numbers.len() != 3 {
+            return Err(SemverError::new(input, format!("pre-release requires a full version in '{text}'")));
+        }
+        Ok(Self {
+            op,
+            major: parse_number(input, numbers[0])?,
+            minor: numbers.get(1).map(|part| parse_number(input, part)).transpose()?,
+            patch: numbers.get(2).map(|part| parse_number(input, part)).transpose()?,
+            pre,
+        })
+    }
+
+    fn lower(&self) -> Version {
+        Version {
+            major: self.major,
+            minor: self.minor.unwrap_or(0),
+            patch: self.patch.unwrap_or(0),
+            pre: self.pre.clone(),
+        }
+    }
+
+    fn matches(&self, version: &Version) -> bool {
+        let lower = self.lower();
+        match self.op {
+            Op::Any => true,
+            Op::Exact => match (self.minor, self.patch) {
+                (Some(_), Some(_)) => *version == lower,
+                _ => self.within_partial(version),
+            },
+            Op::Wildcard => self.within_partial(version),
+            Op::Greater => match (self.minor, self.patch) {
+                (Some(_), Some(_)) => *version > lower,
+                (Some(minor), None) => (version.major, version.minor) > (self.major, minor),
+                _ => version.major > self.major,
+            },
+            Op::GreaterEq => *version >= lower,
+            Op::Less => *version < lower,
+            Op::LessEq => match (self.minor, self.patch) {
+                (Some(_), Some(_)) => *version <= lower,
+                _ => self.within_partial(version) || *version < lower,
+            },
+            Op::Tilde => {
+                let upper = match self.minor {
+                    Some(minor) => minor.checked_add(1).map(|minor| Version::new(self.major, minor, 0)),
+                    None => self.major.checked_add(1).map(|major| Version::new(major, 0, 0)),
+                };
+                *version >= lower && upper.is_none_or(|upper| version.pre_free_lt(&upper))
+            }
+            Op::Caret => {
+                // A bump past `u64::MAX` leaves the range without an upper bound.
+                let upper = match (self.major, self.minor, self.patch) {
+                    (0, Some(0), Some(patch)) => patch.checked_add(1).map(|patch| Version::new(0, 0, patch)),
+                    (0, Some(0), None) => Some(Version::new(0, 1, 0)),
+                    (0, Some(minor), _) => minor.checked_add(1).map(|minor| Version::new(0, minor, 0)),
+                    (0, None, _) => Some(Version::new(1, 0, 0)),
+                    (major, _, _) => major.checked_add(1).map(|major| Version::new(major, 0, 0)),
+                };
+                *version >= lower && upper.is_none_or(|upper| version.pre_free_lt(&upper))
+            }
+        }
+    }
+
+    /// Whether `version` agrees with every part the comparator spelled out.
+    fn within_partial(&self, version: &Version) -> bool {
+        version.major == self.major && self.minor.is_none_or(|minor| version.minor == minor) && self.patch.is_none_or(|patch| version.patch == patch)
+    }
+
+    fn allows_prerelease_of(&self, version: &Version) -> bool {
+        !self.pre.is_empty() && self.lower().same_release(version)
+    }
+}
+
+impl Version {
+    /// `self < upper`, where `upper` is an exclusive release bound that its own
+    /// pre-releases (`2.0.0-alpha` against `<2.0.0`) must not slip under.
+    fn pre_free_lt(&self, upper: &Version) -> bool {
+        *self < *upper && !(self.is_prerelease() && self.same_release(upper))
+    }
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self.op {
+            Op::Exact => "=",
+            Op::Greater => ">",
+            Op::GreaterEq => ">=",
+            Op::Less => "<",
+            Op::LessEq => "<=",
+            Op::Tilde => "~",
+            Op::Caret => "^",
+            Op::Any => return f.write_str("*"),
+            Op::Wildcard => "",
+        };
+        write!(f, "{op}{}", self.major)?;
+        if let Some(minor) = self.minor {
+            write!(f, ".{minor}")?;
+        }
+        if let Some(patch) = self.patch {
+            write!(f, ".{patch}")?;
+        }
+        if self.op == Op::Wildcard {
+            f.write_str(".*")?;
+        }
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+/// A parsed version requirement: every comparator must match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    comparators: Vec<Comparator>,
+}
+
+impl VersionReq {
+    pub fn parse(input: &str) -> Result<Self, SemverError> {
+        if input.trim().is_empty() {
+            return Err(SemverError::new(input, "empty requirement"));
+        }
+        let comparators = input.split(',').map(|part| Comparator::parse(input, part)).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { comparators })
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        if version.is_prerelease() && !self.comparators.iter().any(|comparator| comparator.allows_prerelease_of(version)) {
+            return false;
+        }
+        self.comparators.iter().all(|comparator| comparator.matches(version))
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, comparator) in self.comparators.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{comparator}")?;
+        }
+        Ok(())
+    }
+}
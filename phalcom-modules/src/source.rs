@@ -374,3 +374,59 @@ impl SourceProvider for FilesystemSourceProvider {
         Ok(arc)
     }
 }
+
+/// Source provider that serves in-memory text for a fixed set of modules and
+/// delegates every other lookup to an inner provider.
+///
+/// Tooling uses this to analyze a synthetic module (a doc example, an unsaved
+/// buffer) inside a real project without writing it to disk: the overlaid
+/// module resolves its imports against the project exactly as a file at the
+/// same logical path would.
+#[derive(Debug)]
+pub struct OverlaySourceProvider<P: SourceProvider> {
+    inner: P,
+    overlays: HashMap<ModuleId, (SourceLocation, Arc<str>)>,
+}
+
+impl<P: SourceProvider> OverlaySourceProvider<P> {
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            overlays: HashMap::new(),
+        }
+    }
+
+    /// Serves `text` as the ordinary module `id`, reported under `display_path`.
+    pub fn insert(&mut self, id: ModuleId, display_path: impl Into<PathBuf>, text: impl Into<Arc<str>>) {
+        let display_path = display_path.into();
+        let source = SourceLocation {
+            source_id: SourceId(format!("overlay:{id}").into()),
+            display_path,
+        };
+        self.overlays.insert(id, (source, text.into()));
+    }
+}
+
+impl<P: SourceProvider> SourceProvider for OverlaySourceProvider<P> {
+    fn locate(&self, project: &ResolvedProject, path: &ModulePath) -> Result<SourceUnit, ModuleResolutionError> {
+        let id = ModuleId {
+            project: project.id.into(),
+            path: path.clone(),
+        };
+        match self.overlays.get(&id) {
+            Some((source, _)) => Ok(SourceUnit {
+                id,
+                kind: ModuleKind::Module,
+                source: source.clone(),
+            }),
+            None => self.inner.locate(project, path),
+        }
+    }
+
+    fn read(&self, source: &SourceId) -> Result<Arc<str>, SourceError> {
+        match self.overlays.values().find(|(location, _)| &location.source_id == source) {
+            Some((_, text)) => Ok(text.clone()),
+            None => self.inner.read(source),
+        }
+    }
+}
@@ -14,6 +14,7 @@ pub fn builtin_module_uri(module: &ModuleId) -> Option<String> {
     let host = match project {
         BuiltinProject::Universe => "universe",
         BuiltinProject::Std => "std",
+        BuiltinProject::Native => "native",
     };
     if module.path.is_root() {
         return Some(format!("phalcom://{host}/"));
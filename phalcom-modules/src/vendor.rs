@@ -0,0 +1,383 @@
+//! `vendor/`: a committed copy of every dependency, for builds without
+//! network access.
+//!
+//! `phalcom vendor` ([`vendor_dependencies`]) copies the source tree of each
+//! package `phalcom.lock` lists, path dependencies and registry packages
+//! alike, into a `vendor/` directory next to the lockfile, and records a
+//! [`tree_checksum`] of every copy in the lock:
+//!
+//! ```text
+//! app/
+//!   phalcom.lock
+//!   vendor/json-1.4.1/project.toml   # <name>-<version>
+//!   vendor/util/project.toml         # <name>, for an unversioned path project
+//! ```
+//!
+//! Once `vendor/` exists, loading resolves through a
+//! [`VendorDependencyProvider`]. A locked package with a checksum is read
+//! from its copy, which must still match that checksum, and keeps the source
+//! the lock records, so vendoring never reads as drift. Anything else falls
+//! back to the registry, or to the path dependency in place, unless
+//! [`LockOptions::offline`](crate::lockfile::LockOptions::offline) is set (`--offline`): then every resolution
+//! outside `vendor/` is a [`ProjectError::OfflineResolution`].
+
+use crate::error::ProjectError;
+use crate::lockfile::{LockPins, LockedPackage, Lockfile, lockfile_path, relative_to, sha256_hex};
+use crate::manifest::{DependencyProvider, NullDependencyProvider, ResolvedDependencySource, resolve_path_in_place};
+use crate::project::{ProjectLoadOptions, ProjectOrigin, ProjectUniverse};
+use crate::registry::DirectoryRegistryProvider;
+use crate::semver::{Version, VersionReq};
+use crate::workspace::Workspace;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+/// Directory of vendored packages, next to the lockfile.
+pub const VENDOR_DIR: &str = "vendor";
+
+/// Where `vendor_dependencies` builds the new copies before they replace
+/// `vendor/`.
+const STAGING_DIR: &str = ".vendor.staging";
+
+/// Where the previous `vendor/` waits while the staged copies move in.
+const PREVIOUS_DIR: &str = ".vendor.previous";
+
+/// Resolves dependencies from `vendor/`, checking each copy against the
+/// checksum `phalcom.lock` records for it.
+pub struct VendorDependencyProvider<'a> {
+    /// Canonical directory holding the lockfile and `vendor/`.
+    root: PathBuf,
+    lock: Lockfile,
+    /// Original directory of each vendored path project, by its copy, so
+    /// the copy's own path dependencies resolve as they were locked.
+    originals: BTreeMap<PathBuf, PathBuf>,
+    /// Projects of the workspace itself, which path dependencies reach in place.
+    members: BTreeSet<PathBuf>,
+    /// Provider for what is not vendored; `None` when offline.
+    fallback: Option<&'a dyn DependencyProvider>,
+    /// Ids of the copies already checked.
+    verified: Mutex<BTreeSet<String>>,
+}
+
+impl<'a> VendorDependencyProvider<'a> {
+    /// Opens the vendor directory of the lockfile in `root`, falling back to
+    /// `fallback` for unvendored dependencies, or failing on them when it is `None`.
+    pub fn open(root: impl AsRef<Path>, fallback: Option<&'a dyn DependencyProvider>) -> Result<Self, ProjectError> {
+        let root = root.as_ref();
+        let root = root.canonicalize().map_err(|e| invalid(root, format!("cannot open: {e}")))?;
+        let lock = Lockfile::load(&root.join(crate::lockfile::LOCKFILE_NAME))?.unwrap_or_default();
+        let originals = lock
+            .packages
+            .iter()
+            .filter_map(|package| {
+                let original = package.source.strip_prefix("path+")?;
+                Some((root.join(VENDOR_DIR).join(vendor_name(package)), normalize(&root.join(original))))
+            })
+            .collect();
+        let members = match Workspace::enclosing(&root)? {
+            Some(workspace) => workspace
+                .project_manifests()
+                .iter()
+                .filter_map(|manifest| manifest.parent().map(Path::to_path_buf))
+                .collect(),
+            None => BTreeSet::from([root.clone()]),
+        };
+        Ok(Self {
+            root,
+            lock,
+            originals,
+            members,
+            fallback,
+            verified: Mutex::new(BTreeSet::new()),
+        })
+    }
+
+    /// The provider for the project at `manifest_path` when it has a
+    /// `vendor/` directory, or always when `fallback` is `None` (offline).
+    pub fn configured(manifest_path: impl AsRef<Path>, fallback: Option<&'a dyn DependencyProvider>) -> Result<Option<Self>, ProjectError> {
+        let project_dir = manifest_path.as_ref().parent().unwrap_or(Path::new("."));
+        let lock_path = lockfile_path(project_dir)?;
+        let root = lock_path.parent().unwrap_or(Path::new("."));
+        if fallback.is_some() && !root.join(VENDOR_DIR).is_dir() {
+            return Ok(None);
+        }
+        Self::open(root, fallback).map(Some)
+    }
+
+    /// Reads `package` from its copy, checking the copy once per provider.
+    fn vendored(&self, package: &LockedPackage, expected: &str) -> Result<ResolvedDependencySource, ProjectError> {
+        let vendor_dir = self.root.join(VENDOR_DIR);
+        let dir = vendor_dir.join(vendor_name(package));
+        let manifest_path = dir.join("project.toml");
+        if !manifest_path.is_file() {
+            return Err(invalid(&vendor_dir, format!("'{}' is missing; run `phalcom vendor`", package.id)));
+        }
+        let mut verified = self.verified.lock().expect("vendor verification lock");
+        if !verified.contains(&package.id) {
+            let found = tree_checksum(&dir)?;
+            if found != expected {
+                return Err(ProjectError::VendorChecksumMismatch {
+                    package: package.id.clone(),
+                    expected: expected.to_string(),
+                    found,
+                });
+            }
+            verified.insert(package.id.clone());
+        }
+        Ok(ResolvedDependencySource {
+            manifest_path,
+            origin: ProjectOrigin::Vendored {
+                source: package.source.clone(),
+            },
+        })
+    }
+}
+
+impl DependencyProvider for VendorDependencyProvider<'_> {
+    fn prepare(&self, root_manifest: &Path, pins: &LockPins) -> Result<(), ProjectError> {
+        self.fallback.map_or(Ok(()), |fallback| fallback.prepare(root_manifest, pins))
+    }
+
+    fn resolve_package(&self, package: &str, version_requirement: &str) -> Result<ResolvedDependencySource, ProjectError> {
+        let req = VersionReq::parse(version_requirement)
+            .map_err(|e| ProjectError::InvalidProjectManifest(format!("invalid version requirement for package '{package}': {e}")))?;
+        let locked = self.lock.packages.iter().find(|locked| {
+            locked.name == package
+                && locked.source.starts_with("registry+")
+                && locked
+                    .version
+                    .as_deref()
+                    .and_then(|version| Version::parse(version).ok())
+                    .is_some_and(|version| req.matches(&version))
+        });
+        if let Some((locked, checksum)) = locked.and_then(|locked| Some((locked, locked.checksum.as_deref()?))) {
+            return self.vendored(locked, checksum);
+        }
+        match self.fallback {
+            Some(fallback) => fallback.resolve_package(package, version_requirement),
+            None => Err(ProjectError::OfflineResolution {
+                dependency: format!("{package} {version_requirement}"),
+                reason: "no vendored version satisfies it".to_string(),
+            }),
+        }
+    }
+
+    fn resolve_path(&self, dependent_dir: &Path, path: &Path) -> Result<ResolvedDependencySource, ProjectError> {
+        let base = self.originals.get(dependent_dir).map_or(dependent_dir, PathBuf::as_path);
+        let dir = normalize(&base.join(path));
+        let source = format!("path+{}", relative_to(&dir, &self.root));
+        let locked = self.lock.packages.iter().find(|locked| locked.source == source);
+        if let Some((locked, checksum)) = locked.and_then(|locked| Some((locked, locked.checksum.as_deref()?))) {
+            return self.vendored(locked, checksum);
+        }
+        match self.fallback {
+            Some(fallback) => fallback.resolve_path(base, path),
+            None if self.members.contains(&dir) => resolve_path_in_place(base, path),
+            None => Err(ProjectError::OfflineResolution {
+                dependency: dir.display().to_string(),
+                reason: "the path dependency is not vendored".to_string(),
+            }),
+        }
+    }
+}
+
+/// Copies every package locked for the project (or workspace) in
+/// `project_dir` into its `vendor/` directory, replacing what was there, and
+/// records the copies' checksums in `phalcom.lock`. The copies are made in a
+/// staging directory that replaces `vendor/` only once every one succeeded,
+/// so a failed run leaves the previous `vendor/` as it was. Dependencies are read
+/// from their sources, never from a previous `vendor/`, and loaded under
+/// `options`: `--locked` and `--frozen` fail on a lock that no longer
+/// matches the graph. Returns the locked packages, checksums included.
+pub fn vendor_dependencies(project_dir: &Path, options: &ProjectLoadOptions) -> Result<Vec<LockedPackage>, ProjectError> {
+    let manifest = project_dir.join("project.toml");
+    let registry = DirectoryRegistryProvider::configured(&manifest)?;
+    let provider: &dyn DependencyProvider = match &registry {
+        Some(registry) => registry,
+        None => &NullDependencyProvider,
+    };
+    let mut universe = ProjectUniverse::with_cfg_options(options.cfg.clone());
+    match Workspace::enclosing(project_dir)? {
+        Some(workspace) => {
+            universe.load_workspace_locked(&workspace, provider, &options.lock)?;
+        }
+        None => {
+            universe.load_root_locked(&manifest, provider, &options.lock)?;
+        }
+    }
+
+    let lock_path = lockfile_path(project_dir)?;
+    let root = lock_path.parent().unwrap_or(Path::new("."));
+    let vendor_dir = root.join(VENDOR_DIR);
+    let staging = root.join(STAGING_DIR);
+    clear(&staging)?;
+    std::fs::create_dir_all(&staging).map_err(|e| invalid(&staging, format!("cannot create: {e}")))?;
+
+    let mut lock = Lockfile::load(&lock_path)?.unwrap_or_default();
+    let copied = lock.packages.iter_mut().try_for_each(|package| {
+        let project = universe
+            .projects()
+            .iter()
+            .find(|project| {
+                project.origin != ProjectOrigin::Root
+                    && project.name == package.name
+                    && project.manifest.as_ref().and_then(|manifest| manifest.version.as_ref()) == package.version.as_ref()
+            })
+            .ok_or_else(|| invalid(&vendor_dir, format!("'{}' is locked but was not loaded", package.id)))?;
+        let target = staging.join(vendor_name(package));
+        copy_tree(&project.root_dir, &target, true)?;
+        package.checksum = Some(tree_checksum(&target)?);
+        Ok(())
+    });
+    if let Err(error) = copied.and_then(|()| replace_vendor_dir(&staging, &vendor_dir)) {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(error);
+    }
+    if !lock.packages.is_empty() {
+        std::fs::write(&lock_path, lock.to_toml()).map_err(|e| ProjectError::InvalidLockfile {
+            path: lock_path.clone(),
+            reason: format!("cannot write: {e}"),
+        })?;
+    }
+    Ok(lock.packages)
+}
+
+/// Moves the finished `staging` copy into place as `vendor_dir`. The old
+/// `vendor/` is set aside first and restored if the move fails.
+fn replace_vendor_dir(staging: &Path, vendor_dir: &Path) -> Result<(), ProjectError> {
+    let previous = vendor_dir.with_file_name(PREVIOUS_DIR);
+    clear(&previous)?;
+    let had_previous = vendor_dir.exists();
+    if had_previous {
+        std::fs::rename(vendor_dir, &previous).map_err(|e| invalid(vendor_dir, format!("cannot set aside: {e}")))?;
+    }
+    if let Err(e) = std::fs::rename(staging, vendor_dir) {
+        if had_previous {
+            let _ = std::fs::rename(&previous, vendor_dir);
+        }
+        return Err(invalid(vendor_dir, format!("cannot replace: {e}")));
+    }
+    clear(&previous)
+}
+
+/// Removes `dir` and everything under it, if it exists.
+fn clear(dir: &Path) -> Result<(), ProjectError> {
+    if std::fs::symlink_metadata(dir).is_ok() {
+        std::fs::remove_dir_all(dir).map_err(|e| invalid(dir, format!("cannot clear: {e}")))?;
+    }
+    Ok(())
+}
+
+/// `sha256:<hex>` over the relative path and contents of every file under
+/// `dir`, in path order. Hidden entries are skipped, as `phalcom vendor`
+/// does not copy them.
+pub fn tree_checksum(dir: &Path) -> Result<String, ProjectError> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files)?;
+    files.sort();
+    let mut canonical = Vec::new();
+    for (relative, path) in files {
+        let bytes = std::fs::read(&path).map_err(|e| invalid(dir, format!("cannot read {}: {e}", path.display())))?;
+        canonical.extend_from_slice(relative.as_bytes());
+        canonical.push(0);
+        canonical.extend_from_slice(bytes.len().to_string().as_bytes());
+        canonical.push(0);
+        canonical.extend_from_slice(&bytes);
+    }
+    Ok(sha256_hex(&canonical))
+}
+
+/// `<name>-<version>`, or `<name>` for an unversioned project.
+fn vendor_name(package: &LockedPackage) -> String {
+    match &package.version {
+        Some(version) => format!("{}-{version}", package.name),
+        None => package.name.clone(),
+    }
+}
+
+/// Whether `phalcom vendor` copies the entry `name`: hidden entries never,
+/// and at a package's top level not its own `target/` or `vendor/` either.
+fn is_vendored(name: &str, top_level: bool) -> bool {
+    !name.starts_with('.') && !(top_level && (name == "target" || name == VENDOR_DIR))
+}
+
+fn copy_tree(from: &Path, to: &Path, top_level: bool) -> Result<(), ProjectError> {
+    std::fs::create_dir_all(to).map_err(|e| invalid(to, format!("cannot create: {e}")))?;
+    for (name, path, is_dir) in read_dir(from)? {
+        if !is_vendored(&name, top_level) {
+            continue;
+        }
+        let target = to.join(&name);
+        if is_dir {
+            copy_tree(&path, &target, false)?;
+        } else {
+            std::fs::copy(&path, &target).map_err(|e| invalid(to, format!("cannot copy {}: {e}", path.display())))?;
+        }
+    }
+    Ok(())
+}
+
+/// Adds `(relative path, path)` for every vendored file under `dir` to `files`.
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<(String, PathBuf)>) -> Result<(), ProjectError> {
+    for (name, path, is_dir) in read_dir(dir)? {
+        if !is_vendored(&name, dir == root) {
+            continue;
+        }
+        if is_dir {
+            collect_files(root, &path, files)?;
+        } else {
+            files.push((relative_to(&path, root), path));
+        }
+    }
+    Ok(())
+}
+
+/// The entries of `dir` as `(name, path, is directory)`. A symlink to a file
+/// reads as that file; a symlink to a directory is refused rather than
+/// followed, so a link loop cannot recurse forever.
+fn read_dir(dir: &Path) -> Result<Vec<(String, PathBuf, bool)>, ProjectError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| invalid(dir, format!("cannot read directory: {e}")))?;
+    let mut listed = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| invalid(dir, format!("cannot read directory: {e}")))?;
+        let path = entry.path();
+        let name = entry
+            .file_name()
+            .into_string()
+            .map_err(|name| invalid(dir, format!("entry name {name:?} is not valid UTF-8")))?;
+        let metadata = std::fs::symlink_metadata(&path).map_err(|e| invalid(dir, format!("cannot read {}: {e}", path.display())))?;
+        let is_dir = if metadata.file_type().is_symlink() {
+            if path.is_dir() {
+                return Err(invalid(dir, format!("{} is a symlink to a directory", path.display())));
+            }
+            false
+        } else {
+            metadata.is_dir()
+        };
+        listed.push((name, path, is_dir));
+    }
+    Ok(listed)
+}
+
+/// `path` with `.` and `..` components resolved lexically, so a path
+/// dependency names the same directory whether or not it exists.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normal = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normal.pop();
+            }
+            other => normal.push(other),
+        }
+    }
+    normal
+}
+
+fn invalid(root: &Path, reason: String) -> ProjectError {
+    ProjectError::InvalidVendorDirectory {
+        root: root.to_path_buf(),
+        reason,
+    }
+}
@@ -0,0 +1,147 @@
+//! Multi-project workspaces.
+//!
+//! A `project.toml` with a `[workspace]` table makes its directory a
+//! workspace root. The listed members load together: they share one
+//! `phalcom.lock` and the root's `[registry]`, and registry dependencies
+//! resolve across every member at once, so each package has one version in
+//! the whole workspace. Members may inherit a dependency declared once in
+//! `[workspace.dependencies]` with `alias = { workspace = true }`.
+//!
+//! ```toml
+//! [workspace]
+//! members = ["packages/*", "tools/cli"]
+//!
+//! [workspace.dependencies]
+//! json = { package = "json", version = "^1.2" }
+//! ```
+//!
+//! A root manifest without `[project]` is a virtual workspace: it only
+//! groups its members. Loading any member through
+//! [`ProjectUniverse::load_root`] loads the whole workspace.
+//!
+//! [`ProjectUniverse::load_root`]: crate::project::ProjectUniverse::load_root
+
+use crate::error::ProjectError;
+use crate::manifest::ProjectManifest;
+use std::path::{Path, PathBuf};
+
+/// A loaded `[workspace]` root and its member projects.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Workspace {
+    /// Canonical workspace root directory.
+    pub root: PathBuf,
+    /// The root `project.toml`.
+    pub manifest_path: PathBuf,
+    pub manifest: ProjectManifest,
+    /// Canonical member directories, sorted, each holding a `project.toml`.
+    pub members: Vec<PathBuf>,
+}
+
+impl Workspace {
+    /// Loads the workspace whose root manifest is `manifest_path`.
+    pub fn load(manifest_path: impl AsRef<Path>) -> Result<Self, ProjectError> {
+        let manifest_path = manifest_path.as_ref();
+        let text = std::fs::read_to_string(manifest_path)
+            .map_err(|e| ProjectError::InvalidProjectManifest(format!("Failed to read {}: {}", manifest_path.display(), e)))?;
+        Self::from_manifest(manifest_path, ProjectManifest::parse(&text)?)
+    }
+
+    /// Builds the workspace declared by an already parsed root manifest.
+    pub fn from_manifest(manifest_path: &Path, manifest: ProjectManifest) -> Result<Self, ProjectError> {
+        let manifest_path = manifest_path
+            .canonicalize()
+            .map_err(|e| ProjectError::InvalidProjectManifest(format!("Failed to canonicalize {}: {}", manifest_path.display(), e)))?;
+        let root = manifest_path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let Some(section) = &manifest.workspace else {
+            return Err(invalid(&root, "the manifest has no [workspace] table".to_string()));
+        };
+
+        let mut members = Vec::new();
+        for pattern in &section.members {
+            for dir in expand_member(&root, pattern)? {
+                if dir == root {
+                    return Err(invalid(&root, format!("member '{pattern}' is the workspace root itself")));
+                }
+                members.push(dir);
+            }
+        }
+        members.sort();
+        members.dedup();
+        Ok(Self {
+            root,
+            manifest_path,
+            manifest,
+            members,
+        })
+    }
+
+    /// The workspace `dir` belongs to: the nearest ancestor of `dir` (or
+    /// `dir` itself) whose manifest has a `[workspace]` table, if that
+    /// workspace is rooted at `dir` or lists it as a member. Ancestor
+    /// manifests that do not parse are skipped.
+    pub fn enclosing(dir: impl AsRef<Path>) -> Result<Option<Self>, ProjectError> {
+        let dir = dir.as_ref();
+        let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        for ancestor in dir.ancestors() {
+            let manifest_path = ancestor.join("project.toml");
+            let Ok(text) = std::fs::read_to_string(&manifest_path) else {
+                continue;
+            };
+            let Ok(manifest) = ProjectManifest::parse(&text) else {
+                continue;
+            };
+            if manifest.workspace.is_none() {
+                continue;
+            }
+            let workspace = Self::from_manifest(&manifest_path, manifest)?;
+            let belongs = workspace.root == dir || workspace.members.contains(&dir);
+            return Ok(belongs.then_some(workspace));
+        }
+        Ok(None)
+    }
+
+    /// True when the root manifest has no `[project]` of its own.
+    pub fn is_virtual(&self) -> bool {
+        self.manifest.project.is_none()
+    }
+
+    /// Manifests of every project in the workspace: the root project (unless
+    /// virtual), then the members.
+    pub fn project_manifests(&self) -> Vec<PathBuf> {
+        let root = (!self.is_virtual()).then(|| self.manifest_path.clone());
+        root.into_iter().chain(self.members.iter().map(|member| member.join("project.toml"))).collect()
+    }
+}
+
+/// The member directories `pattern` names under `root`.
+fn expand_member(root: &Path, pattern: &str) -> Result<Vec<PathBuf>, ProjectError> {
+    if let Some(parent) = pattern.strip_suffix("/*").or_else(|| (pattern == "*").then_some("")) {
+        let parent = root.join(parent);
+        let entries = std::fs::read_dir(&parent).map_err(|e| invalid(root, format!("cannot read member directory '{}': {e}", parent.display())))?;
+        let mut members: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.join("project.toml").is_file())
+            .filter_map(|path| path.canonicalize().ok())
+            .collect();
+        members.sort();
+        return Ok(members);
+    }
+    if pattern.contains('*') {
+        return Err(invalid(root, format!("member '{pattern}' may only use `*` as its last component")));
+    }
+    let dir = root.join(pattern);
+    if !dir.join("project.toml").is_file() {
+        return Err(invalid(root, format!("member '{pattern}' has no project.toml")));
+    }
+    dir.canonicalize()
+        .map(|dir| vec![dir])
+        .map_err(|e| invalid(root, format!("cannot resolve member '{pattern}': {e}")))
+}
+
+fn invalid(root: &Path, reason: String) -> ProjectError {
+    ProjectError::InvalidWorkspace {
+        root: root.to_path_buf(),
+        reason,
+    }
+}
@@ -0,0 +1,201 @@
+use phalcom_ast::ast::Statement;
+use phalcom_modules::error::InterfaceError;
+use phalcom_modules::{
+    CfgOptions, FilesystemSourceProvider, ModuleComponent, ModuleId, ModuleLoadError, ModulePath, ModuleResolutionError, ModuleResolver, ProjectError,
+    ProjectUniverse, ResolvedProjectId,
+};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn write_project(dir: &Path, name: &str, extra: &str) -> PathBuf {
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("project.toml"),
+        format!("[project]\nname = \"{name}\"\nnamespace = \"{name}\"\n{extra}"),
+    )
+    .unwrap();
+    fs::write(dir.join("src/package.ph"), "").unwrap();
+    dir.join("project.toml")
+}
+
+fn features(universe: &ProjectUniverse, id: ResolvedProjectId) -> Vec<&str> {
+    universe.get_project(id).unwrap().features.iter().map(String::as_str).collect()
+}
+
+fn path(dotted: &str) -> ModulePath {
+    ModulePath::from_components(
+        dotted
+            .split('.')
+            .map(|part| ModuleComponent::from_identifier(part).unwrap())
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// `app` depends on `json` without its defaults; `fast` turns on `json/native`.
+fn app_and_json(root: &Path) -> PathBuf {
+    write_project(&root.join("json"), "json", "\n[features]\ndefault = [\"pretty\"]\npretty = []\nnative = []\n");
+    write_project(
+        &root.join("app"),
+        "app",
+        "\n[features]\ndefault = [\"fast\"]\nfast = [\"json/native\"]\nextra = []\n\n[dependencies]\njson = { path = \"../json\", default-features = false }\n",
+    )
+}
+
+#[test]
+fn features_resolve_across_the_dependency_graph() {
+    let dir = tempfile::tempdir().unwrap();
+    let manifest = app_and_json(dir.path());
+
+    let mut universe = ProjectUniverse::with_cfg_options(CfgOptions::default());
+    let app = universe.load_root(&manifest).unwrap();
+    let json = universe.get_project(app).unwrap().dependencies.values().copied().next().unwrap();
+    assert_eq!(features(&universe, app), ["default", "fast"]);
+    assert_eq!(features(&universe, json), ["native"]);
+
+    let mut universe = ProjectUniverse::with_cfg_options(CfgOptions {
+        features: vec!["extra".to_string()],
+        default_features: false,
+        ..CfgOptions::default()
+    });
+    let app = universe.load_root(&manifest).unwrap();
+    assert_eq!(features(&universe, app), ["extra"]);
+    assert!(features(&universe, json).is_empty());
+}
+
+#[test]
+fn unknown_features_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let manifest = app_and_json(dir.path());
+    let mut universe = ProjectUniverse::with_cfg_options(CfgOptions {
+        features: vec!["turbo".to_string()],
+        ..CfgOptions::default()
+    });
+    let err = universe.load_root(&manifest).unwrap_err();
+    assert!(matches!(&err, ProjectError::UnknownFeature { feature, .. } if feature == "turbo"), "{err:?}");
+
+    let manifest = write_project(
+        &dir.path().join("asks"),
+        "asks",
+        "\n[dependencies]\njson = { path = \"../json\", features = [\"colour\"] }\n",
+    );
+    let err = ProjectUniverse::new().load_root(&manifest).unwrap_err();
+    assert!(
+        matches!(&err, ProjectError::UnknownFeature { project, feature } if project == "json" && feature == "colour"),
+        "{err:?}"
+    );
+
+    let manifest = write_project(&dir.path().join("bad"), "bad", "\n[features]\nfast = [\"other/native\"]\n");
+    let err = ProjectUniverse::new().load_root(&manifest).unwrap_err();
+    assert!(matches!(err, ProjectError::InvalidProjectManifest(_)), "{err:?}");
+
+    let manifest = write_project(&dir.path().join("gated"), "gated", "\n[modules]\n\"codec.native\" = { feature = \"fast\" }\n");
+    let err = ProjectUniverse::new().load_root(&manifest).unwrap_err();
+    assert!(matches!(err, ProjectError::InvalidProjectManifest(_)), "{err:?}");
+}
+
+/// A `shapes` project with a feature-gated `codec.native` module and `@cfg`
+/// declarations in `geo`.
+fn shapes(root: &Path) -> PathBuf {
+    let manifest = write_project(
+        &root.join("shapes"),
+        "shapes",
+        "\n[features]\nfast = []\n\n[modules]\n\"codec.native\" = { feature = \"fast\" }\n",
+    );
+    let src = root.join("shapes/src");
+    fs::create_dir_all(src.join("codec")).unwrap();
+    fs::write(src.join("package.ph"), "expose .codec\nexpose .geo\n").unwrap();
+    fs::write(src.join("codec/package.ph"), "expose .native\nexpose .portable\n").unwrap();
+    fs::write(src.join("codec/native.ph"), "class Native {\n  id -> Int { 1 }\n}\nexport Native\n").unwrap();
+    fs::write(src.join("codec/portable.ph"), "class Portable {\n  id -> Int { 2 }\n}\nexport Portable\n").unwrap();
+    fs::write(
+        src.join("geo.ph"),
+        "@cfg(feature: \"fast\")\nclass Fast {\n  id -> Int { 1 }\n}\n\n@cfg(os: \"plan9\")\nclass Never {\n  id -> Int { 2 }\n}\n\nclass Circle {\n  @cfg(mode: \"release\")\n  area -> Int { 0 }\n  radius -> Int { 1 }\n}\nexport Fast, Never, Circle\n",
+    )
+    .unwrap();
+    manifest
+}
+
+fn exports(resolver: &mut ModuleResolver<'_, FilesystemSourceProvider>, id: &ModuleId) -> BTreeSet<String> {
+    resolver.load_interface(id).unwrap().exports.keys().cloned().collect()
+}
+
+#[test]
+fn cfg_filters_modules_and_declarations() {
+    let dir = tempfile::tempdir().unwrap();
+    let manifest = shapes(dir.path());
+    let provider = FilesystemSourceProvider::new();
+
+    let mut universe = ProjectUniverse::with_cfg_options(CfgOptions {
+        features: vec!["fast".to_string()],
+        ..CfgOptions::default()
+    });
+    let id = universe.load_root(&manifest).unwrap();
+    let mut resolver = ModuleResolver::new(&universe, &provider);
+    let geo = ModuleId::resolved(id, path("geo"));
+    assert_eq!(exports(&mut resolver, &geo), BTreeSet::from(["Circle".to_string(), "Fast".to_string()]));
+    let parsed = resolver.load_parsed(&geo).unwrap();
+    let Some(Statement::Class(circle)) = parsed
+        .program
+        .statements
+        .iter()
+        .find(|s| matches!(s, Statement::Class(c) if c.name == "Circle"))
+    else {
+        panic!("Circle was filtered out");
+    };
+    assert_eq!(circle.members.len(), 1, "the release-only getter is dropped in debug mode");
+    assert!(
+        parsed
+            .program
+            .statements
+            .iter()
+            .all(|s| !matches!(s, Statement::Class(c) if !c.attributes.is_empty()))
+    );
+    let codec = resolver.load_interface(&ModuleId::resolved(id, path("codec"))).unwrap();
+    assert_eq!(codec.exposed_children.len(), 2);
+    assert!(resolver.load_parsed(&ModuleId::resolved(id, path("codec.native"))).is_ok());
+
+    let mut universe = ProjectUniverse::with_cfg_options(CfgOptions {
+        mode: "release".to_string(),
+        ..CfgOptions::default()
+    });
+    let id = universe.load_root(&manifest).unwrap();
+    let mut resolver = ModuleResolver::new(&universe, &provider);
+    let geo = ModuleId::resolved(id, path("geo"));
+    assert_eq!(exports(&mut resolver, &geo), BTreeSet::from(["Circle".to_string()]));
+    let codec = resolver.load_interface(&ModuleId::resolved(id, path("codec"))).unwrap();
+    assert_eq!(codec.exposed_children.iter().map(|child| child.as_str()).collect::<Vec<_>>(), ["portable"]);
+    let err = resolver.load_parsed(&ModuleId::resolved(id, path("codec.native"))).unwrap_err();
+    assert!(
+        matches!(&err, ModuleLoadError::Resolution(ModuleResolutionError::ModuleDisabled { path, .. }) if path == "codec.native"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn invalid_cfg_attributes_are_interface_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let manifest = shapes(dir.path());
+    let geo = dir.path().join("shapes/src/geo.ph");
+    let provider = FilesystemSourceProvider::new();
+    for (source, expected) in [
+        ("@cfg(feature: \"turbo\")\nclass A {\n  id -> Int { 1 }\n}\n", "not declared"),
+        ("@cfg(mode: \"fast\")\nclass A {\n  id -> Int { 1 }\n}\n", "unknown mode"),
+        ("@cfg(arch: \"x86\")\nclass A {\n  id -> Int { 1 }\n}\n", "unknown condition"),
+        ("@cfg(\"fast\")\nclass A {\n  id -> Int { 1 }\n}\n", "labeled conditions"),
+    ] {
+        fs::write(&geo, source).unwrap();
+        let mut universe = ProjectUniverse::with_cfg_options(CfgOptions::default());
+        let id = universe.load_root(&manifest).unwrap();
+        provider.clear_cache();
+        let mut resolver = ModuleResolver::new(&universe, &provider);
+        let err = resolver.load_parsed(&ModuleId::resolved(id, path("geo"))).unwrap_err();
+        match err {
+            ModuleLoadError::Interface {
+                error: InterfaceError::InvalidCfg { reason, .. },
+                ..
+            } => assert!(reason.contains(expected), "{reason}"),
+            other => panic!("expected InvalidCfg, got {other:?}"),
+        }
+    }
+}
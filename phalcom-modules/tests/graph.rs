@@ -1,7 +1,8 @@
+use phalcom_ast::parser::parse;
 use phalcom_common::range::SourceRange;
 use phalcom_modules::{
-    DependencyPhase, ModuleComponent, ModuleId, ModulePath, ReferenceEdge, ReferenceGraph, ReferenceKind, RuntimeDependencyEdge, RuntimeDependencyGraph,
-    RuntimeDependencyReason, SemanticEdge, SemanticEdgeKind, SemanticGraph, SemanticNodeId,
+    DependencyPhase, InterfaceBuilder, ModuleComponent, ModuleId, ModuleKind, ModulePath, ReferenceEdge, ReferenceGraph, ReferenceKind, RuntimeDependencyEdge,
+    RuntimeDependencyGraph, RuntimeDependencyReason, SemanticEdge, SemanticEdgeKind, SemanticGraph, SemanticNodeId, unused_imports,
 };
 
 fn module(name: &str) -> ModuleId {
@@ -54,6 +55,69 @@ fn runtime_self_and_multi_node_cycles_are_rejected() {
     assert!(two_cycle.initialization_order().is_err());
 }
 
+#[test]
+fn runtime_cycles_lists_every_component_validation_rejects() {
+    let [a, b, c, d] = ["a", "b", "c", "d"].map(module);
+    let mut graph = RuntimeDependencyGraph::default();
+    graph.add(runtime(&a, &b));
+    graph.add(runtime(&b, &a));
+    graph.add(runtime(&b, &c));
+    graph.add(runtime(&d, &d));
+    assert_eq!(graph.cycles(), vec![vec![a.clone(), b.clone()], vec![d.clone()]]);
+
+    let mut acyclic = RuntimeDependencyGraph::default();
+    acyclic.add(runtime(&a, &b));
+    assert!(acyclic.cycles().is_empty());
+}
+
+#[test]
+fn import_chain_takes_the_shortest_path() {
+    let [a, b, c, d] = ["a", "b", "c", "d"].map(module);
+    let mut references = ReferenceGraph::default();
+    for (from, to) in [(&a, &b), (&b, &c), (&c, &d), (&a, &c)] {
+        references.add(ReferenceEdge {
+            from: from.clone(),
+            to: to.clone(),
+            kind: ReferenceKind::SelectiveImport,
+            range: SourceRange::default(),
+        });
+    }
+    let chain = references.import_chain(&a, &d).unwrap();
+    assert_eq!(chain.iter().map(|edge| edge.to.clone()).collect::<Vec<_>>(), [c.clone(), d.clone()]);
+    assert!(references.import_chain(&a, &a).unwrap().is_empty());
+    assert!(references.import_chain(&d, &a).is_none());
+}
+
+#[test]
+fn unused_imports_ignore_names_read_outside_the_preamble() {
+    let id = module("main");
+    let source =
+        "from .shapes import Circle, Square\nfrom .util import Log as L\nimport .colors\nexport Extra from .extra\n\nSystem.print(\"\\(Circle.new())\")\n";
+    let parsed = parse(source, 0);
+    assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+    let interface = InterfaceBuilder::build(id.clone(), ModuleKind::Module, &parsed.program).unwrap();
+    let unused = unused_imports(&id, &interface, source, |_| false);
+    assert_eq!(unused.iter().map(|unused| unused.name.as_str()).collect::<Vec<_>>(), ["Square", "L", "colors"]);
+    assert_eq!(unused[1].path, ".util");
+    assert_eq!(&source[unused[0].range.start..unused[0].range.end], "Square");
+}
+
+#[test]
+fn unused_imports_keep_imports_of_modules_with_extensions() {
+    let id = module("main");
+    let source = "import .shout
+from .loud import Loud
+import .quiet
+
+System.print(\"a\".shout())
+";
+    let parsed = parse(source, 0);
+    assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+    let interface = InterfaceBuilder::build(id.clone(), ModuleKind::Module, &parsed.program).unwrap();
+    let unused = unused_imports(&id, &interface, source, |path| path.to_string() != ".quiet");
+    assert_eq!(unused.iter().map(|unused| unused.name.as_str()).collect::<Vec<_>>(), ["quiet"]);
+}
+
 #[test]
 fn semantic_scc_is_retained_without_runtime_cycle_policy() {
     let a = module("a");
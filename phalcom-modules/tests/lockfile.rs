@@ -0,0 +1,189 @@
+use phalcom_modules::{LockMode, LockOptions, Lockfile, ProjectError, ProjectUniverse, Refresh};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn manifest(name: &str, version: &str, deps: &[(&str, &str)]) -> String {
+    let mut text = format!("[project]\nname = \"{name}\"\nnamespace = \"{name}\"\nversion = \"{version}\"\n[dependencies]\n");
+    for (package, req) in deps {
+        text.push_str(&format!("{package} = {{ package = \"{package}\", version = \"{req}\" }}\n"));
+    }
+    text
+}
+
+fn publish(registry: &Path, name: &str, version: &str, deps: &[(&str, &str)]) {
+    let dir = registry.join(name).join(version);
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("project.toml"), manifest(name, version, deps)).unwrap();
+    fs::write(dir.join("src/package.ph"), "").unwrap();
+}
+
+/// An `app` project using `../registry`, plus any extra manifest lines.
+fn app(root: &Path, deps: &[(&str, &str)], extra: &str) -> PathBuf {
+    let dir = root.join("app");
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("project.toml"),
+        manifest("app", "0.1.0", deps) + extra + "[registry]\npath = \"../registry\"\n",
+    )
+    .unwrap();
+    fs::write(dir.join("src/package.ph"), "").unwrap();
+    dir.join("project.toml")
+}
+
+fn load(manifest: &Path, mode: LockMode) -> Result<ProjectUniverse, ProjectError> {
+    load_refreshing(manifest, mode, Refresh::Nothing)
+}
+
+fn load_refreshing(manifest: &Path, mode: LockMode, refresh: Refresh) -> Result<ProjectUniverse, ProjectError> {
+    let mut universe = ProjectUniverse::new();
+    universe.load_root_with_options(manifest, &LockOptions { mode, refresh, offline: false })?;
+    Ok(universe)
+}
+
+fn locked_versions(manifest: &Path) -> Vec<String> {
+    let lock = Lockfile::load(&manifest.with_file_name("phalcom.lock")).unwrap().expect("lockfile exists");
+    lock.packages.into_iter().map(|package| package.id).collect()
+}
+
+#[test]
+fn loading_writes_a_deterministic_lockfile() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = dir.path().join("registry");
+    publish(&registry, "json", "1.4.1", &[("text", "^1")]);
+    publish(&registry, "text", "1.0.3", &[]);
+    let manifest = app(dir.path(), &[("json", "^1")], "");
+
+    load(&manifest, LockMode::Update).unwrap();
+    let lock_path = manifest.with_file_name("phalcom.lock");
+    let first = fs::read_to_string(&lock_path).unwrap();
+    assert!(first.starts_with("# This file is generated by phalcom."), "{first}");
+    assert!(first.contains("id = \"json 1.4.1\""), "{first}");
+    assert!(first.contains("source = \"registry+../registry\""), "{first}");
+    assert!(first.contains("dependencies = [\"text 1.0.3\"]"), "{first}");
+    assert!(first.contains("interface-hash = \"sha256:"), "{first}");
+    assert_eq!(locked_versions(&manifest), ["json 1.4.1", "text 1.0.3"]);
+
+    fs::remove_file(&lock_path).unwrap();
+    load(&manifest, LockMode::Update).unwrap();
+    assert_eq!(fs::read_to_string(&lock_path).unwrap(), first);
+    load(&manifest, LockMode::Locked).expect("an up-to-date lock passes --locked");
+}
+
+#[test]
+fn locked_versions_survive_new_releases_until_updated() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = dir.path().join("registry");
+    publish(&registry, "json", "1.0.0", &[]);
+    let manifest = app(dir.path(), &[("json", "^1")], "");
+    load(&manifest, LockMode::Update).unwrap();
+
+    publish(&registry, "json", "1.1.0", &[]);
+    load(&manifest, LockMode::Frozen).expect("the locked version is still available");
+    load(&manifest, LockMode::Update).unwrap();
+    assert_eq!(locked_versions(&manifest), ["json 1.0.0"]);
+
+    let before = Lockfile::load(&manifest.with_file_name("phalcom.lock")).unwrap().unwrap();
+    load_refreshing(&manifest, LockMode::Update, Refresh::Everything).unwrap();
+    let after = Lockfile::load(&manifest.with_file_name("phalcom.lock")).unwrap().unwrap();
+    assert_eq!(before.changes(&after), ["json 1.0.0 -> 1.1.0"]);
+}
+
+#[test]
+fn updating_one_package_keeps_the_others_locked() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = dir.path().join("registry");
+    publish(&registry, "json", "1.0.0", &[]);
+    publish(&registry, "text", "1.0.0", &[]);
+    let manifest = app(dir.path(), &[("json", "^1"), ("text", "^1")], "");
+    load(&manifest, LockMode::Update).unwrap();
+
+    publish(&registry, "json", "1.2.0", &[]);
+    publish(&registry, "text", "1.2.0", &[]);
+    let refresh = Refresh::Packages(["json".to_string()].into_iter().collect());
+    load_refreshing(&manifest, LockMode::Update, refresh).unwrap();
+    assert_eq!(locked_versions(&manifest), ["json 1.2.0", "text 1.0.0"]);
+}
+
+#[test]
+fn locked_mode_reports_drift_instead_of_rewriting() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = dir.path().join("registry");
+    publish(&registry, "json", "1.0.0", &[]);
+    publish(&registry, "text", "1.0.0", &[]);
+    let manifest = app(dir.path(), &[("json", "^1")], "");
+    load(&manifest, LockMode::Update).unwrap();
+    let lock_path = manifest.with_file_name("phalcom.lock");
+    let locked = fs::read_to_string(&lock_path).unwrap();
+
+    app(dir.path(), &[("json", "^1"), ("text", "^1")], "");
+    let err = load(&manifest, LockMode::Locked).unwrap_err();
+    assert!(
+        matches!(&err, ProjectError::LockfileDrift { changes, .. } if changes.contains("added text 1.0.0")),
+        "{err:?}"
+    );
+    assert_eq!(fs::read_to_string(&lock_path).unwrap(), locked);
+}
+
+#[test]
+fn plain_loads_never_write_the_lockfile() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = dir.path().join("registry");
+    publish(&registry, "json", "1.0.0", &[]);
+    publish(&registry, "text", "1.0.0", &[]);
+    let manifest = app(dir.path(), &[("json", "^1")], "");
+    let lock_path = manifest.with_file_name("phalcom.lock");
+
+    ProjectUniverse::new().load_root(&manifest).expect("a missing lock is not an error");
+    assert!(!lock_path.exists());
+
+    load(&manifest, LockMode::Update).unwrap();
+    let locked = fs::read_to_string(&lock_path).unwrap();
+    app(dir.path(), &[("json", "^1"), ("text", "^1")], "");
+    let universe = load(&manifest, LockMode::ReadOnly).expect("a stale lock is not an error");
+    assert_eq!(universe.projects().len(), 3);
+    assert_eq!(fs::read_to_string(&lock_path).unwrap(), locked);
+}
+
+#[test]
+fn frozen_mode_requires_a_lockfile() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = dir.path().join("registry");
+    publish(&registry, "json", "1.0.0", &[]);
+    let manifest = app(dir.path(), &[("json", "^1")], "");
+
+    let err = load(&manifest, LockMode::Frozen).unwrap_err();
+    assert!(
+        matches!(&err, ProjectError::LockfileDrift { changes, .. } if changes.contains("missing")),
+        "{err:?}"
+    );
+    assert!(!manifest.with_file_name("phalcom.lock").exists());
+}
+
+#[test]
+fn path_dependency_interface_changes_are_drift() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("registry")).unwrap();
+    let util = dir.path().join("util");
+    fs::create_dir_all(util.join("src")).unwrap();
+    fs::write(util.join("project.toml"), manifest("util", "0.1.0", &[])).unwrap();
+    fs::write(util.join("src/package.ph"), "expose .strings\n").unwrap();
+    fs::write(util.join("src/strings.ph"), "class Strings {}\nexport Strings\n").unwrap();
+    let manifest = app(dir.path(), &[], "util = { path = \"../util\" }\n");
+    load(&manifest, LockMode::Update).unwrap();
+    assert!(
+        fs::read_to_string(manifest.with_file_name("phalcom.lock"))
+            .unwrap()
+            .contains("source = \"path+../util\"")
+    );
+
+    // Implementation-only edits keep the interface hash.
+    fs::write(util.join("src/strings.ph"), "class Strings {}\nlet unused = 1\nexport Strings\n").unwrap();
+    load(&manifest, LockMode::Locked).expect("the interface is unchanged");
+
+    fs::write(util.join("src/strings.ph"), "class Strings {}\nclass Runes {}\nexport Strings\nexport Runes\n").unwrap();
+    let err = load(&manifest, LockMode::Locked).unwrap_err();
+    assert!(
+        matches!(&err, ProjectError::LockfileDrift { changes, .. } if changes.contains("util 0.1.0 interface changed")),
+        "{err:?}"
+    );
+}
@@ -99,6 +99,7 @@ fn test_all_builtin_nodes_have_consistent_source_kind() {
                 vec!["collections", "record"],
                 vec!["collections", "range"],
                 vec!["collections", "bytes"],
+                vec!["collections", "weak"],
                 vec!["errors"],
                 vec!["errors", "error"],
                 vec!["errors", "argument"],
@@ -111,6 +112,7 @@ fn test_all_builtin_nodes_have_consistent_source_kind() {
                 vec!["reflection", "selector"],
                 vec!["reflection", "message"],
                 vec!["reflection", "attribute"],
+                vec!["reflection", "decorators"],
                 vec!["concurrency"],
                 vec!["concurrency", "fiber"],
             ],
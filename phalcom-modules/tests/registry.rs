@@ -0,0 +1,223 @@
+use phalcom_modules::{DependencyProvider, DirectoryRegistryProvider, LockPins, PackageSource, ProjectError, ProjectUniverse, Version, VersionReq};
+use std::fs;
+use std::path::Path;
+
+fn matches(req: &str, version: &str) -> bool {
+    VersionReq::parse(req).unwrap().matches(&Version::parse(version).unwrap())
+}
+
+fn manifest(name: &str, version: &str, deps: &[(&str, &str)]) -> String {
+    let mut text = format!("[project]\nname = \"{name}\"\nnamespace = \"{name}\"\nversion = \"{version}\"\n[dependencies]\n");
+    for (package, req) in deps {
+        text.push_str(&format!("{package} = {{ package = \"{package}\", version = \"{req}\" }}\n"));
+    }
+    text
+}
+
+/// Publishes `name` at `version` in the directory layout.
+fn publish(registry: &Path, name: &str, version: &str, deps: &[(&str, &str)]) {
+    let dir = registry.join(name).join(version);
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("project.toml"), manifest(name, version, deps)).unwrap();
+    fs::write(dir.join("src/package.ph"), "").unwrap();
+}
+
+/// An `app` project whose manifest points `[registry]` at `../registry`.
+fn app(root: &Path, deps: &[(&str, &str)]) -> std::path::PathBuf {
+    let dir = root.join("app");
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("project.toml"),
+        manifest("app", "0.1.0", deps) + "[registry]\npath = \"../registry\"\n",
+    )
+    .unwrap();
+    fs::write(dir.join("src/package.ph"), "").unwrap();
+    dir.join("project.toml")
+}
+
+#[test]
+fn requirements_follow_caret_tilde_range_and_prerelease_rules() {
+    assert!(matches("^1.2", "1.9.0"));
+    assert!(!matches("^1.2", "2.0.0"));
+    assert!(!matches("^1.2", "1.1.9"));
+    assert!(matches("1.2", "1.4.0"));
+    assert!(matches("^0.3", "0.3.7"));
+    assert!(!matches("^0.3", "0.4.0"));
+    assert!(!matches("^0.0.3", "0.0.4"));
+    assert!(matches("~0.3", "0.3.9"));
+    assert!(!matches("~0.3", "0.4.0"));
+    assert!(matches("~1", "1.8.0"));
+    assert!(matches(">=1, <2", "1.99.0"));
+    assert!(!matches(">=1, <2", "2.0.0"));
+    assert!(matches("<=1.4", "1.4.9"));
+    assert!(!matches(">1.2", "1.2.5"));
+    assert!(matches("=1.2.3", "1.2.3"));
+    assert!(matches("1.*", "1.5.0") && !matches("1.*", "2.0.0"));
+    assert!(matches("*", "7.0.0"));
+    assert!(!matches("^1.0", "1.1.0-beta.1"));
+    assert!(!matches("<2", "2.0.0-alpha"));
+    assert!(matches(">=1.1.0-beta.1", "1.1.0-beta.2"));
+    assert!(Version::parse("1.0.0-alpha.2").unwrap() < Version::parse("1.0.0-alpha.10").unwrap());
+    assert!(Version::parse("1.0.0-rc.1").unwrap() < Version::parse("1.0.0").unwrap());
+    assert!(VersionReq::parse("^1.x.2").is_err());
+    assert!(VersionReq::parse(">=01").is_err());
+    assert_eq!(VersionReq::parse(">=1 , <2").unwrap().to_string(), ">=1, <2");
+}
+
+#[test]
+fn requirements_at_u64_max_have_no_upper_bound() {
+    let max = u64::MAX;
+    assert!(matches(&format!("^{max}"), &format!("{max}.3.0")));
+    assert!(matches(&format!("~1.{max}"), &format!("1.{max}.7")));
+    assert!(matches(&format!("^0.{max}"), &format!("0.{max}.1")));
+    assert!(matches(&format!("^0.0.{max}"), &format!("0.0.{max}")));
+    assert!(matches(&format!("~{max}"), &format!("{max}.1.0")));
+}
+
+#[test]
+fn manifest_registry_resolves_newest_matching_versions() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = dir.path().join("registry");
+    publish(&registry, "json", "1.2.0", &[]);
+    publish(&registry, "json", "1.4.1", &[]);
+    publish(&registry, "json", "2.0.0", &[]);
+    let manifest = app(dir.path(), &[("json", "^1.2")]);
+
+    let mut universe = ProjectUniverse::new();
+    let app_id = universe.load_root(&manifest).expect("registry dependency resolves");
+    let app = universe.get_project(app_id).unwrap();
+    let json = universe.get_project(app.dependencies.values().next().copied().unwrap()).unwrap();
+    assert_eq!(json.manifest.as_ref().unwrap().version.as_deref(), Some("1.4.1"));
+    assert!(json.root_dir.ends_with("json/1.4.1"));
+}
+
+#[test]
+fn resolution_backtracks_across_the_graph() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = dir.path().join("registry");
+    // The newest `http` needs `text ^2`, but `json` pins `text ^1`.
+    publish(&registry, "http", "1.0.0", &[("text", "^1")]);
+    publish(&registry, "http", "1.1.0", &[("text", "^2")]);
+    publish(&registry, "json", "1.0.0", &[("text", "~1.0")]);
+    publish(&registry, "text", "1.0.3", &[]);
+    publish(&registry, "text", "2.0.0", &[]);
+    let manifest = app(dir.path(), &[("http", "^1"), ("json", "^1")]);
+
+    let provider = DirectoryRegistryProvider::open(&registry).unwrap();
+    provider.prepare(&manifest, &LockPins::none()).expect("a consistent assignment exists");
+    let selected: Vec<_> = provider
+        .selected_packages()
+        .into_iter()
+        .map(|package| (package.name, package.version.to_string()))
+        .collect();
+    assert_eq!(
+        selected,
+        [
+            ("http".to_string(), "1.0.0".to_string()),
+            ("json".to_string(), "1.0.0".to_string()),
+            ("text".to_string(), "1.0.3".to_string())
+        ]
+    );
+
+    let mut universe = ProjectUniverse::new();
+    universe.load_root_with_provider(&manifest, &provider).unwrap();
+    assert_eq!(universe.projects().len(), 4);
+}
+
+#[test]
+fn conflicting_requirements_are_explained() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = dir.path().join("registry");
+    publish(&registry, "text", "1.0.0", &[]);
+    publish(&registry, "text", "2.0.0", &[]);
+    publish(&registry, "yaml", "1.0.0", &[("text", ">=2, <3")]);
+    let manifest = app(dir.path(), &[("text", "^1"), ("yaml", "^1")]);
+
+    let err = ProjectUniverse::new().load_root(&manifest).unwrap_err();
+    let ProjectError::DependencyConflict { package, explanation } = &err else {
+        panic!("expected a dependency conflict, got {err:?}");
+    };
+    assert_eq!(package, "text");
+    assert!(explanation.contains("app requires text ^1"), "{explanation}");
+    assert!(explanation.contains("yaml 1.0.0 requires text >=2, <3"), "{explanation}");
+    assert!(explanation.contains("available: 2.0.0, 1.0.0"), "{explanation}");
+}
+
+#[test]
+fn missing_packages_stay_unresolved_dependencies() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("registry")).unwrap();
+    let manifest = app(dir.path(), &[("json", "^1")]);
+    let err = ProjectUniverse::new().load_root(&manifest).unwrap_err();
+    assert!(
+        matches!(err, ProjectError::UnresolvedPackageDependency { ref package, .. } if package == "json"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn indexed_archives_are_unpacked_on_selection() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = dir.path().join("registry");
+    fs::create_dir_all(registry.join("archives")).unwrap();
+    let archive = registry.join("archives/json-1.0.0.tar");
+    let mut builder = tar::Builder::new(fs::File::create(&archive).unwrap());
+    for (path, text) in [
+        ("project.toml", manifest("json", "1.0.0", &[])),
+        ("src/package.ph", "expose .value\n".to_string()),
+        ("src/value.ph", "class Value {}\nexport Value\n".to_string()),
+    ] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(text.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, path, text.as_bytes()).unwrap();
+    }
+    builder.finish().unwrap();
+    drop(builder);
+    fs::write(
+        registry.join("index.toml"),
+        "[[package]]\nname = \"json\"\nversion = \"1.0.0\"\narchive = \"archives/json-1.0.0.tar\"\n",
+    )
+    .unwrap();
+    let manifest = app(dir.path(), &[("json", "=1.0.0")]);
+
+    let unpack = dir.path().join("unpacked");
+    let provider = DirectoryRegistryProvider::open(&registry).unwrap().with_unpack_dir(&unpack);
+    let mut universe = ProjectUniverse::new();
+    universe.load_root_with_provider(&manifest, &provider).unwrap();
+    assert_eq!(
+        provider.selected_packages()[0].source,
+        PackageSource::Archive(registry.canonicalize().unwrap().join("archives/json-1.0.0.tar"))
+    );
+    assert!(unpack.join("json-1.0.0/src/value.ph").is_file());
+}
+
+#[test]
+fn registry_packages_cannot_declare_path_dependencies() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = dir.path().join("registry");
+    publish(&registry, "json", "1.0.0", &[]);
+    let json_manifest = registry.join("json/1.0.0/project.toml");
+    let text = fs::read_to_string(&json_manifest).unwrap() + "local = { path = \"../../local\" }\n";
+    fs::write(&json_manifest, text).unwrap();
+    let manifest = app(dir.path(), &[("json", "^1")]);
+
+    let err = ProjectUniverse::new().load_root(&manifest).unwrap_err();
+    assert!(
+        matches!(&err, ProjectError::InvalidRegistry { reason, .. } if reason.contains("path dependency 'local'")),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn malformed_version_requirements_are_manifest_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("registry")).unwrap();
+    let manifest = app(dir.path(), &[("json", "one point two")]);
+    let err = ProjectUniverse::new().load_root(&manifest).unwrap_err();
+    assert!(
+        matches!(&err, ProjectError::InvalidProjectManifest(message) if message.contains("dependency 'json'")),
+        "{err:?}"
+    );
+}
@@ -0,0 +1,188 @@
+use phalcom_modules::vendor::vendor_dependencies;
+use phalcom_modules::{LockMode, LockOptions, LockedPackage, Lockfile, ProjectError, ProjectLoadOptions, ProjectOrigin, ProjectUniverse, Refresh};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn write_project(dir: &Path, manifest: &str) {
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("project.toml"), manifest).unwrap();
+    fs::write(dir.join("src/package.ph"), "").unwrap();
+}
+
+/// An `app` with a path dependency on `../util` and a registry dependency on
+/// `json`, which itself needs `text`.
+fn workspace(root: &Path) -> PathBuf {
+    for (name, version, deps) in [("json", "1.4.1", "text = { package = \"text\", version = \"^1\" }\n"), ("text", "1.0.3", "")] {
+        write_project(
+            &root.join("registry").join(name).join(version),
+            &format!("[project]\nname = \"{name}\"\nnamespace = \"{name}\"\nversion = \"{version}\"\n[dependencies]\n{deps}"),
+        );
+    }
+    write_project(&root.join("util"), "[project]\nname = \"util\"\nnamespace = \"util\"\n");
+    let app = root.join("app");
+    write_project(
+        &app,
+        "[project]\nname = \"app\"\nnamespace = \"app\"\n[dependencies]\nutil = { path = \"../util\" }\njson = { package = \"json\", version = \"^1\" }\n[registry]\npath = \"../registry\"\n",
+    );
+    app
+}
+
+fn load(app: &Path, offline: bool) -> Result<ProjectUniverse, ProjectError> {
+    let mut universe = ProjectUniverse::new();
+    let options = LockOptions {
+        mode: LockMode::Locked,
+        refresh: Refresh::Nothing,
+        offline,
+    };
+    universe.load_root_with_options(app.join("project.toml"), &options)?;
+    Ok(universe)
+}
+
+/// Vendors `app` as `phalcom vendor` would under `mode`.
+fn vendor(app: &Path, mode: LockMode) -> Result<Vec<LockedPackage>, ProjectError> {
+    let options = ProjectLoadOptions {
+        lock: LockOptions {
+            mode,
+            ..LockOptions::default()
+        },
+        ..ProjectLoadOptions::default()
+    };
+    vendor_dependencies(app, &options)
+}
+
+#[test]
+fn vendored_projects_load_offline_without_their_sources() {
+    let dir = tempfile::tempdir().unwrap();
+    let app = workspace(dir.path());
+    let packages = vendor(&app, LockMode::Update).unwrap();
+    assert_eq!(
+        packages.iter().map(|package| package.id.as_str()).collect::<Vec<_>>(),
+        ["json 1.4.1", "text 1.0.3", "util"]
+    );
+    assert!(
+        packages
+            .iter()
+            .all(|package| package.checksum.as_deref().is_some_and(|sum| sum.starts_with("sha256:")))
+    );
+    for copy in ["json-1.4.1", "text-1.0.3", "util"] {
+        assert!(app.join("vendor").join(copy).join("src/package.ph").is_file(), "{copy} was not copied");
+    }
+    let lock = fs::read_to_string(app.join("phalcom.lock")).unwrap();
+    assert_eq!(Lockfile::parse(&lock).unwrap().packages, packages);
+
+    fs::remove_dir_all(dir.path().join("registry")).unwrap();
+    fs::remove_dir_all(dir.path().join("util")).unwrap();
+    let universe = load(&app, true).unwrap();
+    let vendor = app.canonicalize().unwrap().join("vendor");
+    for project in universe.projects().iter().filter(|project| project.name != "app") {
+        assert!(
+            project.root_dir.starts_with(&vendor),
+            "{} loaded from {}",
+            project.name,
+            project.root_dir.display()
+        );
+        assert!(matches!(project.origin, ProjectOrigin::Vendored { .. }), "{:?}", project.origin);
+    }
+    assert_eq!(fs::read_to_string(app.join("phalcom.lock")).unwrap(), lock, "vendoring is not drift");
+}
+
+#[test]
+fn tampered_copies_fail_their_checksum() {
+    let dir = tempfile::tempdir().unwrap();
+    let app = workspace(dir.path());
+    vendor(&app, LockMode::Update).unwrap();
+    fs::write(app.join("vendor/text-1.0.3/src/package.ph"), "let injected = 1\n").unwrap();
+    match load(&app, false) {
+        Err(ProjectError::VendorChecksumMismatch { package, .. }) => assert_eq!(package, "text 1.0.3"),
+        other => panic!("expected a checksum mismatch, got {other:?}"),
+    }
+}
+
+#[test]
+fn offline_loads_reject_unvendored_dependencies() {
+    let dir = tempfile::tempdir().unwrap();
+    let app = workspace(dir.path());
+    match load(&app, true) {
+        Err(ProjectError::OfflineResolution { dependency, .. }) => assert_eq!(dependency, "json ^1"),
+        other => panic!("expected an offline resolution error, got {other:?}"),
+    }
+    fs::write(
+        app.join("project.toml"),
+        fs::read_to_string(app.join("project.toml")).unwrap().replace("json = ", "# json = "),
+    )
+    .unwrap();
+    match load(&app, true) {
+        Err(ProjectError::OfflineResolution { dependency, .. }) => assert!(dependency.ends_with("util"), "{dependency}"),
+        other => panic!("expected an offline resolution error, got {other:?}"),
+    }
+
+    vendor(&app, LockMode::Update).unwrap();
+    fs::write(
+        app.join("project.toml"),
+        fs::read_to_string(app.join("project.toml"))
+            .unwrap()
+            .replace("[registry]", "extra = { package = \"text\", version = \"^2\" }\n[registry]"),
+    )
+    .unwrap();
+    match load(&app, true) {
+        Err(ProjectError::OfflineResolution { dependency, .. }) => assert_eq!(dependency, "text ^2"),
+        other => panic!("expected an offline resolution error, got {other:?}"),
+    }
+}
+
+#[test]
+fn locked_vendoring_fails_on_lock_drift() {
+    let dir = tempfile::tempdir().unwrap();
+    let app = workspace(dir.path());
+    vendor(&app, LockMode::Update).unwrap();
+    let lock = fs::read_to_string(app.join("phalcom.lock")).unwrap();
+    vendor(&app, LockMode::Locked).unwrap();
+
+    write_project(&dir.path().join("extra"), "[project]\nname = \"extra\"\nnamespace = \"extra\"\n");
+    fs::write(
+        app.join("project.toml"),
+        fs::read_to_string(app.join("project.toml"))
+            .unwrap()
+            .replace("[registry]", "extra = { path = \"../extra\" }\n[registry]"),
+    )
+    .unwrap();
+    for mode in [LockMode::Locked, LockMode::Frozen] {
+        match vendor(&app, mode) {
+            Err(ProjectError::LockfileDrift { .. }) => {}
+            other => panic!("expected lockfile drift under {mode:?}, got {other:?}"),
+        }
+    }
+    assert_eq!(fs::read_to_string(app.join("phalcom.lock")).unwrap(), lock);
+}
+
+#[cfg(unix)]
+#[test]
+fn failed_vendoring_keeps_the_previous_copies() {
+    let dir = tempfile::tempdir().unwrap();
+    let app = workspace(dir.path());
+    vendor(&app, LockMode::Update).unwrap();
+    let vendored = fs::read_dir(app.join("vendor")).unwrap().count();
+
+    std::os::unix::fs::symlink("..", dir.path().join("util/src/loop")).unwrap();
+    let error = vendor(&app, LockMode::Update).unwrap_err();
+    assert!(
+        matches!(&error, ProjectError::InvalidVendorDirectory { reason, .. } if reason.contains("symlink to a directory")),
+        "{error:?}"
+    );
+    assert_eq!(fs::read_dir(app.join("vendor")).unwrap().count(), vendored);
+    assert!(!app.join(".vendor.staging").exists());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn non_utf8_names_are_reported() {
+    use std::os::unix::ffi::OsStrExt;
+    let dir = tempfile::tempdir().unwrap();
+    let app = workspace(dir.path());
+    fs::write(dir.path().join("util/src").join(std::ffi::OsStr::from_bytes(b"bad\xff.ph")), "").unwrap();
+    let error = vendor(&app, LockMode::Update).unwrap_err();
+    assert!(
+        matches!(&error, ProjectError::InvalidVendorDirectory { reason, .. } if reason.contains("not valid UTF-8")),
+        "{error:?}"
+    );
+}
@@ -0,0 +1,178 @@
+use phalcom_modules::{LockMode, LockOptions, ProjectError, ProjectUniverse, Workspace};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn write_project(dir: &Path, name: &str, version: &str, deps: &str) -> PathBuf {
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("project.toml"),
+        format!("[project]\nname = \"{name}\"\nnamespace = \"{name}\"\nversion = \"{version}\"\n[dependencies]\n{deps}"),
+    )
+    .unwrap();
+    fs::write(dir.join("src/package.ph"), "").unwrap();
+    dir.join("project.toml")
+}
+
+fn updating() -> LockOptions {
+    LockOptions {
+        mode: LockMode::Update,
+        ..LockOptions::default()
+    }
+}
+
+fn publish(registry: &Path, name: &str, version: &str) {
+    write_project(&registry.join(name).join(version), name, version, "");
+}
+
+/// A virtual workspace at `root/ws` using `root/registry`.
+fn workspace(root: &Path, shared: &str) -> PathBuf {
+    let dir = root.join("ws");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("project.toml"),
+        format!("[workspace]\nmembers = [\"packages/*\"]\n\n[workspace.dependencies]\n{shared}\n[registry]\npath = \"../registry\"\n"),
+    )
+    .unwrap();
+    dir.join("project.toml")
+}
+
+fn version_of(universe: &ProjectUniverse, project: phalcom_modules::ResolvedProjectId, alias: &str) -> String {
+    let project = universe.get_project(project).unwrap();
+    let dependency = project.dependencies.iter().find(|(name, _)| name.as_str() == alias).map(|(_, id)| *id).unwrap();
+    universe.get_project(dependency).unwrap().manifest.as_ref().unwrap().version.clone().unwrap()
+}
+
+#[test]
+fn members_share_dependencies_and_one_lockfile() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = dir.path().join("registry");
+    publish(&registry, "json", "1.2.0");
+    publish(&registry, "json", "1.3.0");
+    let manifest = workspace(dir.path(), "json = { package = \"json\", version = \"^1.2\" }");
+    let packages = manifest.with_file_name("packages");
+    write_project(&packages.join("api"), "api", "0.1.0", "json = { workspace = true }\n");
+    write_project(
+        &packages.join("cli"),
+        "cli",
+        "0.1.0",
+        "json = { workspace = true }\napi = { path = \"../api\" }\n",
+    );
+
+    let mut universe = ProjectUniverse::new();
+    let members = universe.load_workspace_with_options(&manifest, &updating()).unwrap();
+    assert_eq!(members.len(), 2);
+    let names: Vec<_> = members.iter().map(|id| universe.get_project(*id).unwrap().name.clone()).collect();
+    assert_eq!(names, ["api", "cli"]);
+    assert_eq!(version_of(&universe, members[0], "json"), "1.3.0");
+    assert_eq!(version_of(&universe, members[1], "json"), "1.3.0");
+    // Two members plus one shared copy of `json`.
+    assert_eq!(universe.projects().len(), 3);
+
+    let lock = fs::read_to_string(manifest.with_file_name("phalcom.lock")).unwrap();
+    assert!(lock.contains("id = \"json 1.3.0\""), "{lock}");
+    assert!(!lock.contains("name = \"api\""), "members are not locked: {lock}");
+    assert!(!packages.join("api/phalcom.lock").exists());
+    assert!(!packages.join("cli/phalcom.lock").exists());
+}
+
+#[test]
+fn registry_versions_are_unified_across_members() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = dir.path().join("registry");
+    publish(&registry, "text", "1.0.0");
+    publish(&registry, "text", "1.5.0");
+    publish(&registry, "text", "2.0.0");
+    let manifest = workspace(dir.path(), "");
+    let packages = manifest.with_file_name("packages");
+    write_project(&packages.join("a"), "a", "0.1.0", "text = { package = \"text\", version = \"^1\" }\n");
+    write_project(&packages.join("b"), "b", "0.1.0", "text = { package = \"text\", version = \">=1.0\" }\n");
+
+    let mut universe = ProjectUniverse::new();
+    let members = universe.load_workspace(&manifest).unwrap();
+    assert_eq!(version_of(&universe, members[0], "text"), "1.5.0");
+    assert_eq!(version_of(&universe, members[1], "text"), "1.5.0");
+}
+
+#[test]
+fn loading_a_member_loads_its_workspace() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = dir.path().join("registry");
+    publish(&registry, "json", "1.0.0");
+    let manifest = workspace(
+        dir.path(),
+        "json = { package = \"json\", version = \"^1\" }\nutil = { path = \"packages/util\" }",
+    );
+    let packages = manifest.with_file_name("packages");
+    write_project(&packages.join("util"), "util", "0.1.0", "");
+    let app = write_project(
+        &packages.join("app"),
+        "app",
+        "0.1.0",
+        "json = { workspace = true }\nutil = { workspace = true }\n",
+    );
+
+    let mut universe = ProjectUniverse::new();
+    let app_id = universe.load_root_with_options(&app, &updating()).unwrap();
+    assert_eq!(universe.get_project(app_id).unwrap().name, "app");
+    assert_eq!(universe.projects().len(), 3);
+    assert!(manifest.with_file_name("phalcom.lock").is_file());
+    assert!(!packages.join("app/phalcom.lock").exists());
+
+    let err = ProjectUniverse::new().load_root(&manifest).unwrap_err();
+    assert!(
+        matches!(&err, ProjectError::InvalidProjectManifest(message) if message.contains("virtual workspace")),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn root_projects_and_explicit_members_are_listed() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("mono");
+    write_project(&root, "mono", "0.1.0", "");
+    let text = fs::read_to_string(root.join("project.toml")).unwrap();
+    fs::write(root.join("project.toml"), text + "[workspace]\nmembers = [\"tools/cli\"]\n").unwrap();
+    write_project(&root.join("tools/cli"), "cli", "0.1.0", "");
+    write_project(&root.join("scratch"), "scratch", "0.1.0", "");
+
+    let workspace = Workspace::load(root.join("project.toml")).unwrap();
+    assert!(!workspace.is_virtual());
+    let canonical = root.canonicalize().unwrap();
+    assert_eq!(
+        workspace.project_manifests(),
+        [canonical.join("project.toml"), canonical.join("tools/cli/project.toml")]
+    );
+    assert_eq!(Workspace::enclosing(root.join("tools/cli")).unwrap(), Some(workspace));
+    assert_eq!(Workspace::enclosing(root.join("scratch")).unwrap(), None);
+}
+
+#[test]
+fn malformed_workspaces_are_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("registry")).unwrap();
+    let manifest = workspace(dir.path(), "");
+    fs::create_dir_all(manifest.with_file_name("packages")).unwrap();
+    fs::write(
+        &manifest,
+        fs::read_to_string(&manifest).unwrap().replace("\"packages/*\"", "\"packages/*\", \"missing\""),
+    )
+    .unwrap();
+    let err = Workspace::load(&manifest).unwrap_err();
+    assert!(
+        matches!(&err, ProjectError::InvalidWorkspace { reason, .. } if reason.contains("member 'missing' has no project.toml")),
+        "{err:?}"
+    );
+
+    let manifest = workspace(dir.path(), "");
+    let app = write_project(
+        &manifest.with_file_name("packages").join("app"),
+        "app",
+        "0.1.0",
+        "json = { workspace = true }\n",
+    );
+    let err = ProjectUniverse::new().load_root(&app).unwrap_err();
+    assert!(
+        matches!(&err, ProjectError::InvalidProjectManifest(message) if message.contains("'json' is not declared in [workspace.dependencies]")),
+        "{err:?}"
+    );
+}
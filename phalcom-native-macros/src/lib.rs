@@ -1,4 +1,8 @@
 //! Procedural macro `#[primitive]` for native Phalcom primitives.
+//!
+//! The owner is either a kernel `UniverseKey` name, for primitives compiled
+//! into `phalcom-core`, or the path of an extension crate's
+//! `NativeClassSpec` static (`phalcom_native_meta::extension`).
 
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenStream as TokenStream2};
@@ -11,9 +15,17 @@ use phalcom_common::selector::{Selector, SelectorKind, SelectorSlot};
 use phalcom_native_meta::UniverseKey;
 use phalcom_type_syntax::{CallableType, ParameterTuple, TypeExpr, parse_callable_type, parse_type_expr};
 
+/// Who owns the primitive, which decides the paths the expansion names.
+enum Owner {
+    /// A kernel class; the primitive is compiled into `phalcom-core` itself.
+    Universe(Ident),
+    /// An extension class: the path of a `NativeClassSpec` static declared in
+    /// the downstream crate that invokes the macro.
+    Extension(syn::Path),
+}
+
 struct PrimitiveAttrArgs {
-    _owner: UniverseKey,
-    owner_ident: Ident,
+    owner: Owner,
     selector_str: String,
     selector_span: Span,
 
@@ -41,9 +53,8 @@ struct PrimitiveAttrArgs {
 
 impl Parse for PrimitiveAttrArgs {
     fn parse(input: ParseStream) -> Result<Self> {
-        let owner_ident: Ident = input.parse()?;
-        let owner = UniverseKey::from_name(&owner_ident.to_string())
-            .ok_or_else(|| Error::new(owner_ident.span(), format!("unknown universe owner '{}'", owner_ident)))?;
+        let owner_path: syn::Path = input.parse()?;
+        let owner = parse_owner(owner_path)?;
 
         input.parse::<Token![,]>()?;
         let selector_lit: LitStr = input.parse()?;
@@ -51,8 +62,7 @@ impl Parse for PrimitiveAttrArgs {
         let selector_span = selector_lit.span();
 
         let mut args = PrimitiveAttrArgs {
-            _owner: owner,
-            owner_ident,
+            owner,
             selector_str,
             selector_span,
             params_str: None,
@@ -183,6 +193,28 @@ impl Parse for PrimitiveAttrArgs {
     }
 }
 
+/// A kernel owner is a bare `UniverseKey` name (`String`); an extension owner
+/// is the path of a `NativeClassSpec` static, whose last segment is
+/// SCREAMING_CASE like every static (`DIGEST`, `crypto::DIGEST`).
+fn parse_owner(path: syn::Path) -> Result<Owner> {
+    if let Some(ident) = path.get_ident()
+        && UniverseKey::from_name(&ident.to_string()).is_some()
+    {
+        return Ok(Owner::Universe(ident.clone()));
+    }
+    let last = path.segments.last().map(|segment| segment.ident.to_string()).unwrap_or_default();
+    if !last.is_empty() && last.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_') {
+        return Ok(Owner::Extension(path));
+    }
+    Err(Error::new(
+        path.span(),
+        format!(
+            "unknown universe owner '{}' (an extension owner is the path of its NativeClassSpec static)",
+            quote!(#path)
+        ),
+    ))
+}
+
 #[proc_macro_attribute]
 pub fn primitive(args: TokenStream, input: TokenStream) -> TokenStream {
     let attr_args = parse_macro_input!(args as PrimitiveAttrArgs);
@@ -291,6 +323,17 @@ fn expand_primitive(args: PrimitiveAttrArgs, item_fn: &ItemFn) -> Result<TokenSt
         }
         None => quote!(None),
     };
+    if matches!(args.owner, Owner::Extension(_)) {
+        if args.intrinsic.is_some() {
+            return Err(Error::new(
+                Span::call_site(),
+                "intrinsic IDs are kernel-only and cannot be claimed by an extension primitive",
+            ));
+        }
+        if trust == phalcom_native_meta::NativeTrust::Privileged {
+            return Err(Error::new(Span::call_site(), "trust = privileged is kernel-only"));
+        }
+    }
 
     // 8. Lifecycle
     let since_tokens = match &args.since {
@@ -451,8 +494,15 @@ fn expand_primitive(args: PrimitiveAttrArgs, item_fn: &ItemFn) -> Result<TokenSt
         )
     };
 
-    // Surface spec tokens
-    let owner_key_ident = &args.owner_ident;
+    // Surface spec tokens. Kernel primitives name the runtime through
+    // `crate`; extension primitives live downstream of `phalcom-core`.
+    let (owner_tokens, native) = match &args.owner {
+        Owner::Universe(key) => (
+            quote!(::phalcom_native_meta::NativeOwner::Universe(::phalcom_native_meta::UniverseKey::#key)),
+            quote!(crate::native),
+        ),
+        Owner::Extension(class) => (quote!(::phalcom_native_meta::NativeOwner::Extension(&#class)), quote!(::phalcom_core::native)),
+    };
     let selector_str = &args.selector_str;
     let visibility_tokens = match visibility {
         phalcom_native_meta::NativeVisibility::Public => quote!(::phalcom_native_meta::NativeVisibility::Public),
@@ -480,28 +530,38 @@ fn expand_primitive(args: PrimitiveAttrArgs, item_fn: &ItemFn) -> Result<TokenSt
     let abi_check = match abi {
         phalcom_native_meta::PrimitiveAbi::Value => {
             quote! {
-                const _: crate::native::PrimitiveValueFn = #fn_name;
+                const _: #native::PrimitiveValueFn = #fn_name;
             }
         }
         phalcom_native_meta::PrimitiveAbi::Shape => {
             quote! {
-                const _: crate::native::PrimitiveShapeFn = #fn_name;
+                const _: #native::PrimitiveShapeFn = #fn_name;
             }
         }
     };
 
     let entry_tokens = match abi {
         phalcom_native_meta::PrimitiveAbi::Value => {
-            quote!(crate::native::PrimitiveEntry::Value(#fn_name))
+            quote!(#native::PrimitiveEntry::Value(#fn_name))
         }
         phalcom_native_meta::PrimitiveAbi::Shape => {
-            quote!(crate::native::PrimitiveEntry::Shape(#fn_name))
+            quote!(#native::PrimitiveEntry::Shape(#fn_name))
         }
     };
 
     let fn_name_upper = fn_name_str.to_ascii_uppercase();
     let descriptor_ident = format_ident!("__PHALCOM_PRIMITIVE_DESCRIPTOR_{}", fn_name_upper);
     let surface_ident = format_ident!("__PHALCOM_PRIMITIVE_SURFACE_{}", fn_name_upper);
+    let extension_surface = match args.owner {
+        Owner::Universe(_) => quote!(),
+        Owner::Extension(_) => {
+            let extension_ident = format_ident!("__PHALCOM_EXTENSION_SURFACE_{}", fn_name_upper);
+            quote! {
+                #[::linkme::distributed_slice(::phalcom_native_meta::EXTENSION_PRIMITIVES)]
+                static #extension_ident: &'static ::phalcom_native_meta::PrimitiveSurfaceSpec = &#surface_ident;
+            }
+        }
+    };
 
     let expanded = quote! {
         #item_fn
@@ -511,7 +571,7 @@ fn expand_primitive(args: PrimitiveAttrArgs, item_fn: &ItemFn) -> Result<TokenSt
         #[allow(non_upper_case_globals)]
         pub static #surface_ident: ::phalcom_native_meta::PrimitiveSurfaceSpec = ::phalcom_native_meta::PrimitiveSurfaceSpec {
             key: ::phalcom_native_meta::PrimitiveKey {
-                owner: ::phalcom_native_meta::UniverseKey::#owner_key_ident,
+                owner: #owner_tokens,
                 side: #side_tokens,
                 selector: #selector_str,
             },
@@ -530,8 +590,10 @@ fn expand_primitive(args: PrimitiveAttrArgs, item_fn: &ItemFn) -> Result<TokenSt
             trust: #trust_tokens,
         };
 
-        #[::linkme::distributed_slice(crate::native::PRIMITIVES)]
-        static #descriptor_ident: crate::native::PrimitiveDescriptor = crate::native::PrimitiveDescriptor {
+        #extension_surface
+
+        #[::linkme::distributed_slice(#native::PRIMITIVES)]
+        static #descriptor_ident: #native::PrimitiveDescriptor = #native::PrimitiveDescriptor {
             surface: &#surface_ident,
             abi: #abi_tokens,
             entry: #entry_tokens,
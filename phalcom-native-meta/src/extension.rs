@@ -0,0 +1,128 @@
+//! Native classes contributed by crates outside the kernel.
+//!
+//! A kernel primitive is owned by a [`UniverseKey`]. An extension crate
+//! instead declares its own class as a [`NativeClassSpec`] in
+//! [`EXTENSION_CLASSES`] and hangs `#[primitive(CLASS, "selector", …)]`
+//! functions off it. Each class lives in an importable module named by
+//! [`NativeClassSpec::module`]; every class of one module is an export of it:
+//!
+//! ```ignore
+//! #[linkme::distributed_slice(phalcom_native_meta::EXTENSION_CLASSES)]
+//! pub static DIGEST: NativeClassSpec = NativeClassSpec {
+//!     module: "acme.crypto",
+//!     name: "Digest",
+//!     superclass: UniverseKey::Object,
+//! };
+//!
+//! #[primitive(DIGEST, "sha256(_)", side = class, types = "(Bytes) -> String", raises = [Error], effects = pure)]
+//! fn digest_sha256(vm: &mut VM, receiver: &Value, args: &[Value]) -> PhResult<Value> { … }
+//! ```
+//!
+//! Both slices are link-time registries, so tooling sees an extension exactly
+//! when its binary links the extension crate — the same rule that makes the
+//! kernel's own primitives visible.
+
+use crate::primitive::PrimitiveSurfaceSpec;
+use crate::universe::UniverseKey;
+use linkme::distributed_slice;
+use std::collections::BTreeSet;
+
+/// One native class declared by an extension crate.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct NativeClassSpec {
+    /// Dotted module path the class is exported from, e.g. `"acme.crypto"`.
+    pub module: &'static str,
+    /// Class name, unique within its module.
+    pub name: &'static str,
+    /// Kernel superclass. Extension classes have no instance fields, so any
+    /// kernel class with an ordinary instance layout will do.
+    pub superclass: UniverseKey,
+}
+
+/// Every extension class linked into the binary.
+#[distributed_slice]
+pub static EXTENSION_CLASSES: [NativeClassSpec];
+
+/// Surface of every extension primitive linked into the binary — the
+/// VM-free half of each extension `#[primitive]`, for tooling that does not
+/// link the runtime.
+#[distributed_slice]
+pub static EXTENSION_PRIMITIVES: [&'static PrimitiveSurfaceSpec];
+
+/// Import roots no extension module may claim.
+pub const RESERVED_EXTENSION_ROOTS: [&str; 3] = ["universe", "std", "core"];
+
+/// Dotted paths of every extension module, sorted and deduplicated.
+pub fn extension_modules() -> BTreeSet<&'static str> {
+    EXTENSION_CLASSES.iter().map(|class| class.module).collect()
+}
+
+/// Whether `root` is the first component of some extension module.
+pub fn is_extension_root(root: &str) -> bool {
+    EXTENSION_CLASSES.iter().any(|class| class.module.split('.').next() == Some(root))
+}
+
+/// Extension classes exported by `module`, in name order.
+pub fn extension_classes(module: &str) -> Vec<&'static NativeClassSpec> {
+    let mut classes: Vec<_> = EXTENSION_CLASSES.iter().filter(|class| class.module == module).collect();
+    classes.sort_by_key(|class| class.name);
+    classes
+}
+
+/// The extension class `name` of `module`.
+pub fn extension_class(module: &str, name: &str) -> Option<&'static NativeClassSpec> {
+    EXTENSION_CLASSES.iter().find(|class| class.module == module && class.name == name)
+}
+
+/// Surfaces of the extension primitives owned by `class`, in key order.
+pub fn extension_primitives(class: &NativeClassSpec) -> Vec<&'static PrimitiveSurfaceSpec> {
+    let mut surfaces: Vec<&'static PrimitiveSurfaceSpec> = EXTENSION_PRIMITIVES
+        .iter()
+        .copied()
+        .filter(|surface| matches!(surface.key.owner, crate::NativeOwner::Extension(owner) if owner == class))
+        .collect();
+    surfaces.sort_by_key(|surface| surface.key.sort_key());
+    surfaces
+}
+
+/// Checks the extension registry for the mistakes the macro cannot see
+/// across crates, returning one message per problem.
+///
+/// A module path must be dotted snake_case outside the
+/// [`RESERVED_EXTENSION_ROOTS`]; a class name must be unique within its
+/// module and must not shadow a kernel class; and every extension primitive
+/// must be owned by a registered class.
+pub fn validate_extensions() -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut seen = BTreeSet::new();
+    for class in EXTENSION_CLASSES {
+        let components: Vec<&str> = class.module.split('.').collect();
+        if components.iter().any(|c| !is_snake_case(c)) {
+            problems.push(format!("extension module '{}' is not a dotted snake_case path", class.module));
+        } else if RESERVED_EXTENSION_ROOTS.contains(&components[0]) {
+            problems.push(format!("extension module '{}' uses the reserved import root '{}'", class.module, components[0]));
+        }
+        if UniverseKey::from_name(class.name).is_some() {
+            problems.push(format!("extension class '{}.{}' shadows a kernel class", class.module, class.name));
+        }
+        if !seen.insert((class.module, class.name)) {
+            problems.push(format!("extension class '{}.{}' is registered twice", class.module, class.name));
+        }
+    }
+    for surface in EXTENSION_PRIMITIVES {
+        if let crate::NativeOwner::Extension(owner) = surface.key.owner
+            && !seen.contains(&(owner.module, owner.name))
+        {
+            problems.push(format!(
+                "primitive '{}' is owned by '{}.{}', which is not in EXTENSION_CLASSES",
+                surface.key.selector, owner.module, owner.name
+            ));
+        }
+    }
+    problems
+}
+
+fn is_snake_case(component: &str) -> bool {
+    let mut chars = component.chars();
+    chars.next().is_some_and(|c| c.is_ascii_lowercase()) && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
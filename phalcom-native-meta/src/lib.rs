@@ -1,9 +1,11 @@
 //! VM-free native metadata models, universe keys, and static primitive specifications.
 
+pub mod extension;
 pub mod primitive;
 pub mod types;
 pub mod universe;
 
+pub use extension::*;
 pub use primitive::*;
 pub use types::*;
 pub use universe::*;
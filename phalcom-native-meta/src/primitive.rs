@@ -1,5 +1,6 @@
 //! Semantic primitive metadata and descriptor types.
 
+use crate::extension::NativeClassSpec;
 use crate::types::{CallableTypeSpec, ParameterTupleSpec, TypeExprSpec};
 use crate::universe::UniverseKey;
 
@@ -73,15 +74,33 @@ pub enum PrimitiveAbi {
     Shape,
 }
 
+/// The class a primitive is installed on: a kernel class, or a class an
+/// extension crate declared (`extension.rs`). Kernel owners sort first.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum NativeOwner {
+    Universe(UniverseKey),
+    Extension(&'static NativeClassSpec),
+}
+
+impl NativeOwner {
+    /// The owner's class name.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Universe(key) => key.name(),
+            Self::Extension(class) => class.name,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct PrimitiveKey {
-    pub owner: UniverseKey,
+    pub owner: NativeOwner,
     pub side: NativeDispatch,
     pub selector: &'static str,
 }
 
 impl PrimitiveKey {
-    pub const fn sort_key(&self) -> (UniverseKey, NativeDispatch, &'static str) {
+    pub const fn sort_key(&self) -> (NativeOwner, NativeDispatch, &'static str) {
         (self.owner, self.side, self.selector)
     }
 }
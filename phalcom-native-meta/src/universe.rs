@@ -44,6 +44,9 @@ pub enum UniverseKey {
     Record,
     Range,
     Bytes,
+    WeakRef,
+    WeakMap,
+    FinalizationRegistry,
 
     Module,
     Package,
@@ -113,6 +116,9 @@ impl UniverseKey {
             Self::Record => "Record",
             Self::Range => "Range",
             Self::Bytes => "Bytes",
+            Self::WeakRef => "WeakRef",
+            Self::WeakMap => "WeakMap",
+            Self::FinalizationRegistry => "FinalizationRegistry",
             Self::Module => "Module",
             Self::Package => "Package",
             Self::Project => "Project",
@@ -178,6 +184,9 @@ impl UniverseKey {
             "Record" => Some(Self::Record),
             "Range" => Some(Self::Range),
             "Bytes" => Some(Self::Bytes),
+            "WeakRef" => Some(Self::WeakRef),
+            "WeakMap" => Some(Self::WeakMap),
+            "FinalizationRegistry" => Some(Self::FinalizationRegistry),
             "Module" => Some(Self::Module),
             "Package" => Some(Self::Package),
             "Project" => Some(Self::Project),
@@ -463,6 +472,27 @@ pub const UNIVERSE_BINDINGS: &[UniverseBindingSpec] = &[
         exported: true,
         prelude: true,
     },
+    UniverseBindingSpec {
+        key: UniverseKey::WeakRef,
+        name: "WeakRef",
+        kind: UniverseBindingKind::Class,
+        exported: true,
+        prelude: true,
+    },
+    UniverseBindingSpec {
+        key: UniverseKey::WeakMap,
+        name: "WeakMap",
+        kind: UniverseBindingKind::Class,
+        exported: true,
+        prelude: true,
+    },
+    UniverseBindingSpec {
+        key: UniverseKey::FinalizationRegistry,
+        name: "FinalizationRegistry",
+        kind: UniverseBindingKind::Class,
+        exported: true,
+        prelude: true,
+    },
     UniverseBindingSpec {
         key: UniverseKey::Module,
         name: "Module",
@@ -3,6 +3,12 @@
 //! This crate deliberately contains no VM, AST, or LSP dependency. The runtime
 //! validates its primitive registration against this surface, while the LSP
 //! uses it to expose native members without linking the runtime.
+//!
+//! The tables here are the kernel's. Classes contributed by extension crates
+//! live in `phalcom_native_meta`'s link-time registry instead;
+//! [`native_classes`] and [`native_members`] append them to the kernel rows,
+//! and [`extension_classes`] and [`extension_members`] list one extension
+//! module's rows on their own, for tooling that scopes them to their module.
 
 /// Native member category.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -169,6 +175,18 @@ pub const NATIVE_CLASSES: &[NativeClass] = &[
         name: "Bytes",
         superclass: Some("Object"),
     },
+    NativeClass {
+        name: "WeakRef",
+        superclass: Some("Object"),
+    },
+    NativeClass {
+        name: "WeakMap",
+        superclass: Some("Object"),
+    },
+    NativeClass {
+        name: "FinalizationRegistry",
+        superclass: Some("Object"),
+    },
     NativeClass {
         name: "Map",
         superclass: Some("Object"),
@@ -311,6 +329,7 @@ pub const NATIVE_MEMBERS: &[NativeMember] = &[
     native_with_return!("Object", "understands(_)", Method, Instance, Public, NativeReturnShape::Instance("Bool")),
     native!("Object", "perform(_,***)", Method, Instance, Public),
     native!("Object", "respondsTo(_)", Method, Instance, Public),
+    native_with_return!("Object", "conformsTo(_)", Method, Instance, Public, NativeReturnShape::Instance("Bool")),
     native!("Object", "doesNotUnderstand(_)", Method, Instance, Public),
     native!("Object", "methodFor(_)", Method, Instance, Public),
     native!("Object", "_$invariantEnter()", Method, Instance, Internal),
@@ -327,6 +346,16 @@ pub const NATIVE_MEMBERS: &[NativeMember] = &[
     native!("Behavior", "name", Getter, Instance, Public),
     native!("Behavior", "methods", Getter, Instance, Public),
     native!("Behavior", ">>(_)", Method, Instance, Public),
+    native!("Behavior", "ancestors", Getter, Instance, Public),
+    native!("Behavior", "mixins", Getter, Instance, Public),
+    native!("Behavior", "isMixin", Getter, Instance, Public),
+    native!("Behavior", "requirements", Getter, Instance, Public),
+    native!("Behavior", "_$mixin(_)", Method, Instance, Internal),
+    native!("Behavior", "_$compose(_)", Method, Instance, Internal),
+    native!("Behavior", "isProtocol", Getter, Instance, Public),
+    native!("Behavior", "_$protocol(_)", Method, Instance, Internal),
+    native!("Behavior", "_$multimethod(_,_,_)", Method, Instance, Internal),
+    native!("Behavior", "_$extend(_,_)", Method, Instance, Internal),
     native!("Class", "+(_)", Method, Instance, Public),
     native!("Class", "_$new()", Method, Instance, Internal),
     native!("Number", "+(_)", Method, Instance, Public),
@@ -430,6 +459,7 @@ pub const NATIVE_MEMBERS: &[NativeMember] = &[
     native!("MethodFamily", "selectors", Getter, Instance, Public),
     native!("MethodFamily", "size", Getter, Instance, Public),
     native!("MethodFamily", "methodFor(_)", Method, Instance, Public),
+    native!("MethodFamily", "specializations(_)", Method, Instance, Public),
     native!("Function", "arity", Getter, Instance, Public),
     native!("Function", "name", Getter, Instance, Public),
     native!("Function", "callWith(_)", Method, Instance, Public),
@@ -444,6 +474,7 @@ pub const NATIVE_MEMBERS: &[NativeMember] = &[
     native!("System", "schedule(_)", Method, Class, Public),
     native!("System", "nextScheduled", Getter, Class, Public),
     native!("System", "gc", Getter, Class, Public),
+    native!("System", "gcStats", Getter, Class, Public),
     native!("System", "_$write(_)", Method, Class, Internal),
     native!("List", "new()", Method, Class, Public),
     native!("List", "_$length", Getter, Instance, Internal),
@@ -463,6 +494,19 @@ pub const NATIVE_MEMBERS: &[NativeMember] = &[
     native!("Bytes", "_$utf8", Getter, Instance, Internal),
     native!("Bytes", "_$utf8Lossy", Getter, Instance, Internal),
     native!("Bytes", "_$equalsConstantTime(_)", Method, Instance, Internal),
+    native!("WeakRef", "new(_)", Method, Class, Public),
+    native!("WeakRef", "deref", Getter, Instance, Public),
+    native!("WeakMap", "new()", Method, Class, Public),
+    native!("WeakMap", "_$get(_)", Method, Instance, Internal),
+    native!("WeakMap", "_$put(_,_)", Method, Instance, Internal),
+    native!("WeakMap", "_$has(_)", Method, Instance, Internal),
+    native!("WeakMap", "_$remove(_)", Method, Instance, Internal),
+    native!("FinalizationRegistry", "_$new(_)", Method, Class, Internal),
+    native!("FinalizationRegistry", "_$setDrain(_)", Method, Instance, Internal),
+    native!("FinalizationRegistry", "_$cleanup", Getter, Instance, Internal),
+    native!("FinalizationRegistry", "_$register(_,_,_)", Method, Instance, Internal),
+    native!("FinalizationRegistry", "_$unregister(_)", Method, Instance, Internal),
+    native!("FinalizationRegistry", "_$takePending", Getter, Instance, Internal),
     native!("Map", "new()", Method, Class, Public),
     native!("Map", "_$size", Getter, Instance, Internal),
     native!("Map", "_$get(_)", Method, Instance, Internal),
@@ -505,6 +549,8 @@ pub const NATIVE_MEMBERS: &[NativeMember] = &[
     native!("Fiber", "isDone", Getter, Instance, Public),
     native!("Fiber", "isRoot", Getter, Instance, Public),
     native!("Fiber", "error", Getter, Instance, Public),
+    native!("Fiber", "canSuspend", Getter, Instance, Public),
+    native!("Fiber", "_$rethrow()", Method, Instance, Internal),
     native!("Resource", "_$register(_)", Method, Class, Internal),
     native!("Resource", "_$close()", Method, Instance, Internal),
     native!("Resource", "_$isClosed", Getter, Instance, Internal),
@@ -632,6 +678,84 @@ pub const NATIVE_MEMBERS: &[NativeMember] = &[
     native!("ProjectIdentity", "toString", Getter, Instance, Public),
 ];
 
+/// Every native class: [`NATIVE_CLASSES`] followed by the extension classes
+/// linked into the binary.
+pub fn native_classes() -> impl Iterator<Item = NativeClass> {
+    let extensions = phalcom_native_meta::EXTENSION_CLASSES.iter().map(|class| NativeClass {
+        name: class.name,
+        superclass: Some(class.superclass.name()),
+    });
+    NATIVE_CLASSES.iter().copied().chain(extensions)
+}
+
+/// Every native member: [`NATIVE_MEMBERS`] followed by the members of the
+/// extension classes linked into the binary, derived from their primitive
+/// surfaces.
+pub fn native_members() -> impl Iterator<Item = NativeMember> {
+    NATIVE_MEMBERS
+        .iter()
+        .copied()
+        .chain(phalcom_native_meta::EXTENSION_PRIMITIVES.iter().map(|surface| extension_member(surface)))
+}
+
+/// Dotted paths of the extension modules linked into the binary, sorted.
+pub fn extension_modules() -> Vec<&'static str> {
+    phalcom_native_meta::extension_modules().into_iter().collect()
+}
+
+/// The classes exported by extension module `module`, in name order.
+pub fn extension_classes(module: &str) -> Vec<NativeClass> {
+    phalcom_native_meta::extension_classes(module)
+        .into_iter()
+        .map(|class| NativeClass {
+            name: class.name,
+            superclass: Some(class.superclass.name()),
+        })
+        .collect()
+}
+
+/// The members of the classes of extension module `module`.
+pub fn extension_members(module: &str) -> Vec<NativeMember> {
+    phalcom_native_meta::extension_classes(module)
+        .into_iter()
+        .flat_map(phalcom_native_meta::extension_primitives)
+        .map(extension_member)
+        .collect()
+}
+
+fn extension_member(surface: &phalcom_native_meta::PrimitiveSurfaceSpec) -> NativeMember {
+    use phalcom_native_meta::{ReturnFlowSpec, TypeExprSpec};
+
+    let selector = surface.key.selector;
+    let kind = if selector.ends_with("=(put)") {
+        NativeMemberKind::Setter
+    } else if selector.contains('(') || selector.contains('[') {
+        NativeMemberKind::Method
+    } else {
+        NativeMemberKind::Getter
+    };
+    let return_shape = match (surface.flow, surface.returns) {
+        (ReturnFlowSpec::Receiver, _) | (_, TypeExprSpec::SelfType) => NativeReturnShape::Receiver,
+        (ReturnFlowSpec::Argument(index), _) => NativeReturnShape::Argument(index),
+        (_, TypeExprSpec::Universe(key)) => NativeReturnShape::Instance(key.name()),
+        _ => NativeReturnShape::Unknown,
+    };
+    NativeMember {
+        class: surface.key.owner.name(),
+        selector,
+        kind,
+        side: match surface.key.side {
+            phalcom_native_meta::NativeDispatch::Instance => NativeDispatch::Instance,
+            phalcom_native_meta::NativeDispatch::Class => NativeDispatch::Class,
+        },
+        visibility: match surface.visibility {
+            phalcom_native_meta::NativeVisibility::Public => NativeVisibility::Public,
+            phalcom_native_meta::NativeVisibility::Internal => NativeVisibility::Internal,
+        },
+        return_shape,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
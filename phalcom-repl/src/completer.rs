@@ -164,6 +164,8 @@ const BUILTIN_KEYWORDS: &[&str] = &[
     "construct",
     "throw",
     "try",
+    "yield",
+    "await",
     "fn",
 ];
 
@@ -97,7 +97,9 @@ impl Highlighter for PhalcomHighlighter {
                 | Token::Static
                 | Token::Construct
                 | Token::Throw
-                | Token::Try => {
+                | Token::Try
+                | Token::Yield
+                | Token::Await => {
                     color(start, end, kw_style);
                 }
                 Token::True | Token::False => {
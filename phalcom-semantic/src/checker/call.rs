@@ -5,7 +5,7 @@ use super::expression::synthesize_expr;
 use crate::diagnostic::{DiagnosticCode, SemanticDiagnostic};
 use crate::dispatch::CallableSignature;
 use crate::types::evidence::TypeKnowledge;
-use crate::types::relation::{Assignability, check_assignability};
+use crate::types::relation::Assignability;
 use phalcom_ast::ast::{Expr, PackItem, PackLabel};
 use phalcom_common::range::SourceRange;
 
@@ -14,7 +14,7 @@ pub fn check_arguments(ctx: &mut CheckingContext<'_>, args: &[Expr], param_types
     for (i, arg) in args.iter().enumerate() {
         let arg_k = synthesize_expr(ctx, arg);
         if let Some(param_k) = param_types.get(i) {
-            let assignability = check_assignability(ctx.store, ctx.hierarchy, &arg_k, param_k);
+            let assignability = ctx.check_assignability(&arg_k, param_k, call_range);
             if let Assignability::Refuted { .. } = assignability {
                 ctx.diagnostics.push(SemanticDiagnostic::error(
                     DiagnosticCode::ArgumentMismatch,
@@ -38,7 +38,7 @@ pub fn match_callable_arguments(ctx: &mut CheckingContext<'_>, args: &[PackItem]
                     let param = &signature.parameters[positional_idx];
                     positional_idx += 1;
                     if param.external_label.is_none() {
-                        let assignability = check_assignability(ctx.store, ctx.hierarchy, &arg_k, &param.ty);
+                        let assignability = ctx.check_assignability(&arg_k, &param.ty, *range);
                         if let Assignability::Refuted { .. } = assignability {
                             ctx.diagnostics.push(SemanticDiagnostic::error(
                                 DiagnosticCode::ArgumentMismatch,
@@ -56,7 +56,7 @@ pub fn match_callable_arguments(ctx: &mut CheckingContext<'_>, args: &[PackItem]
                     for param in &signature.parameters {
                         if let Some(ref ext_label) = param.external_label {
                             if ext_label == text {
-                                let assignability = check_assignability(ctx.store, ctx.hierarchy, &arg_k, &param.ty);
+                                let assignability = ctx.check_assignability(&arg_k, &param.ty, *range);
                                 if let Assignability::Refuted { .. } = assignability {
                                     ctx.diagnostics.push(SemanticDiagnostic::error(
                                         DiagnosticCode::ArgumentMismatch,
@@ -1,17 +1,18 @@
 //! Checking context and scope environments.
 
 use crate::declarations::DeclarationTypeTable;
-use crate::diagnostic::SemanticDiagnostic;
-use crate::dispatch::{DispatchResult, SurfaceDispatchResolver};
+use crate::diagnostic::{DiagnosticCode, SemanticDiagnostic};
+use crate::dispatch::{DispatchResult, SurfaceDispatchResolver, SurfaceHierarchy};
 use crate::identity::{DeclarationId, DispatchSide, ModuleId};
 use crate::types::annotation::TypeResolver;
 use crate::types::constraint::LocalConstraintSolver;
 use crate::types::denotation::ValueSemanticFact;
 use crate::types::evidence::TypeKnowledge;
 use crate::types::id::TypeId;
-use crate::types::native::register_standard_surfaces;
-use crate::types::relation::TypeHierarchy;
+use crate::types::native::{register_extension_surfaces, register_standard_surfaces};
+use crate::types::relation::{Assignability, RefutationReason, TypeHierarchy, check_assignability};
 use crate::types::store::{TypeData, TypeStore};
+use phalcom_common::range::SourceRange;
 use phalcom_common::selector::Selector;
 use std::collections::HashMap;
 
@@ -65,6 +66,7 @@ impl<'a> CheckingContext<'a> {
     ) -> Self {
         let mut dispatch = SurfaceDispatchResolver::new();
         register_standard_surfaces(store, declarations, resolver, &current_module, &mut dispatch);
+        register_extension_surfaces(store, declarations, &mut dispatch);
 
         Self {
             store,
@@ -120,33 +122,35 @@ impl<'a> CheckingContext<'a> {
     }
 
     pub fn resolve_dispatch(&mut self, receiver: TypeId, selector: &Selector, lookup: crate::dispatch::DispatchLookup) -> DispatchResult {
-        let (decl, side) = match lookup {
+        let res = match lookup {
             crate::dispatch::DispatchLookup::Super { defining_class, side } => {
-                if let Some(super_decl) = self.hierarchy.superclass(&defining_class) {
-                    (super_decl.clone(), side)
-                } else {
-                    return DispatchResult::Missing;
-                }
+                // `super` continues the linearization after the defining
+                // class: its own mixins first, then the superclass chain.
+                let order = crate::dispatch::lookup_order(self.hierarchy, &defining_class, side);
+                self.dispatch.resolve_dispatch_in_order(&order[1..], side, selector)
             }
-            crate::dispatch::DispatchLookup::Normal => match self.store.get(receiver) {
-                TypeData::ClassObject { declaration } => (declaration.clone(), DispatchSide::Class),
-                TypeData::Nominal { declaration } => (declaration.clone(), DispatchSide::Instance),
-                TypeData::Applied { origin, .. } => {
-                    let mut curr_origin = *origin;
-                    while let TypeData::Applied { origin: inner_origin, .. } = self.store.get(curr_origin) {
-                        curr_origin = *inner_origin;
-                    }
-                    if let TypeData::Nominal { declaration } = self.store.get(curr_origin) {
-                        (declaration.clone(), DispatchSide::Instance)
-                    } else {
-                        return DispatchResult::Missing;
+            crate::dispatch::DispatchLookup::Normal => {
+                let (decl, side) = match self.store.get(receiver) {
+                    TypeData::ClassObject { declaration } => (declaration.clone(), DispatchSide::Class),
+                    // A protocol receiver answers its requirements, included
+                    // protocols' and `Object`'s methods, all on the instance side.
+                    TypeData::Nominal { declaration } | TypeData::Protocol { declaration } => (declaration.clone(), DispatchSide::Instance),
+                    TypeData::Applied { origin, .. } => {
+                        let mut curr_origin = *origin;
+                        while let TypeData::Applied { origin: inner_origin, .. } = self.store.get(curr_origin) {
+                            curr_origin = *inner_origin;
+                        }
+                        if let TypeData::Nominal { declaration } = self.store.get(curr_origin) {
+                            (declaration.clone(), DispatchSide::Instance)
+                        } else {
+                            return DispatchResult::Missing;
+                        }
                     }
-                }
-                _ => return DispatchResult::Missing,
-            },
+                    _ => return DispatchResult::Missing,
+                };
+                self.dispatch.resolve_dispatch_on_owner(self.hierarchy, &decl, side, selector)
+            }
         };
-
-        let res = self.dispatch.resolve_dispatch_on_owner(self.hierarchy, &decl, side, selector);
         if let DispatchResult::Found(mut sig) = res {
             if let Some(subst) = crate::types::substitution::substitution_for_applied(self.declarations, self.store, receiver) {
                 for param in &mut sig.parameters {
@@ -164,6 +168,35 @@ impl<'a> CheckingContext<'a> {
         }
     }
 
+    /// Checks `actual` against `expected`, deciding protocol conformance from
+    /// the registered class surfaces. A refutation for missing protocol
+    /// requirements also reports the missing selectors at `range`.
+    pub fn check_assignability(&mut self, actual: &TypeKnowledge, expected: &TypeKnowledge, range: SourceRange) -> Assignability {
+        let hierarchy = SurfaceHierarchy {
+            hierarchy: self.hierarchy,
+            dispatch: &self.dispatch,
+        };
+        let assignability = check_assignability(self.store, &hierarchy, actual, expected);
+        if let Assignability::Refuted {
+            reason: RefutationReason::MissingRequirements { actual, protocol, missing },
+            ..
+        } = &assignability
+        {
+            let missing: Vec<String> = missing.iter().map(|selector| format!("`{}`", selector.encode())).collect();
+            self.diagnostics.push(SemanticDiagnostic::error(
+                DiagnosticCode::ProtocolRequirementMissing,
+                format!(
+                    "`{}` does not conform to protocol `{}`: missing {}",
+                    actual.name,
+                    protocol.name,
+                    missing.join(", ")
+                ),
+                range,
+            ));
+        }
+        assignability
+    }
+
     pub fn register_surface(&mut self, decl: DeclarationId, surface: crate::surface::DeclarationSurface) {
         self.dispatch.register_surface(decl, surface);
     }
@@ -3,33 +3,117 @@
 use super::context::CheckingContext;
 use super::expression::synthesize_expr;
 use super::statement::check_statement;
+use crate::declarations::variant_match_keyword;
 use crate::diagnostic::{DiagnosticCode, SemanticDiagnostic};
 use crate::dispatch::{CallableParameter, CallableSignature};
-use crate::identity::DeclarationId;
+use crate::identity::{DeclarationId, DispatchSide};
 use crate::surface::DeclarationSurface;
 use crate::types::annotation::resolve_type_annotation;
 use crate::types::denotation::ValueSemanticFact;
 use crate::types::evidence::{EvidenceAuthority, TypeKnowledge, UnknownReason};
-use crate::types::relation::{Assignability, check_assignability};
-use phalcom_ast::ast::{ClassDef, ClassMember, ParameterDef, Statement};
+use crate::types::relation::Assignability;
+use phalcom_ast::ast::{AttrKind, BuiltinAttr, ClassDef, ClassMember, ExtendDef, ParameterDef, Statement, VariantDef};
 use phalcom_common::selector::{Selector, SelectorSlot};
 
-fn member_side(member: &ClassMember) -> crate::identity::DispatchSide {
+fn member_side(member: &ClassMember) -> DispatchSide {
     if member.is_static() || member.attributes().iter().any(|attribute| attribute.name == "class") {
-        crate::identity::DispatchSide::Class
+        DispatchSide::Class
     } else {
-        crate::identity::DispatchSide::Instance
+        DispatchSide::Instance
     }
 }
 
+/// Whether `class_def` carries `@sealed`.
+pub fn is_sealed(class_def: &ClassDef) -> bool {
+    class_def
+        .attributes
+        .iter()
+        .any(|attr| matches!(attr.kind, AttrKind::Builtin(BuiltinAttr::Sealed)))
+}
+
+/// The `@variant` arms of a `@sealed` class, in declaration order. Arms of a
+/// class that is not sealed generate nothing (the compiler rejects them).
+pub fn sealed_variant_defs(class_def: &ClassDef) -> impl Iterator<Item = &VariantDef> {
+    let sealed = is_sealed(class_def);
+    class_def.members.iter().filter_map(move |member| match member {
+        ClassMember::Variant(variant) if sealed => Some(variant),
+        _ => None,
+    })
+}
+
 /// Pre-registers a class surface and its callable signatures in the context's dispatch table.
 pub fn register_class_surface(ctx: &mut CheckingContext<'_>, class_def: &ClassDef) {
     let decl_id = DeclarationId::new(ctx.current_module.clone(), class_def.name.clone().into());
     let mut surface = DeclarationSurface::new(Some(decl_id.clone()));
     let class_ty = ctx.nominal_type_of(&decl_id);
     ctx.dispatch.register_type(class_ty, decl_id.clone());
+    add_member_surfaces(ctx, &class_def.members, &mut surface);
+    add_variant_surfaces(ctx, class_def, &mut surface);
+    ctx.register_surface(decl_id, surface);
+}
 
-    for member in &class_def.members {
+/// Registers the sibling class each `@variant` arm of a `@sealed` class
+/// generates — its labelled constructor, fields and getters — and adds the
+/// `match(...)` visitor, one keyword per arm, to the sealed class's surface.
+fn add_variant_surfaces(ctx: &mut CheckingContext<'_>, class_def: &ClassDef, surface: &mut DeclarationSurface) {
+    let unknown = || TypeKnowledge::Unknown(UnknownReason::UnannotatedDeclaration);
+    let mut arms = Vec::new();
+    for variant in sealed_variant_defs(class_def) {
+        let variant_id = DeclarationId::new(ctx.current_module.clone(), variant.name.clone().into());
+        let variant_ty = ctx.nominal_type_of(&variant_id);
+        ctx.dispatch.register_type(variant_ty, variant_id.clone());
+        let mut variant_surface = DeclarationSurface::new(Some(variant_id.clone()));
+        let mut params = Vec::new();
+        for label in &variant.labels {
+            variant_surface.add_field(DispatchSide::Instance, format!("_{label}"), unknown());
+            if let Ok(sel) = Selector::getter(label) {
+                variant_surface.add_callable(DispatchSide::Instance, CallableSignature::new(sel, Vec::new(), unknown()));
+            }
+            params.push(CallableParameter::new(label.clone(), unknown()).with_label(label.clone()));
+        }
+        let slots: Vec<_> = variant.labels.iter().map(|label| SelectorSlot::Label(label.clone())).collect();
+        if let Ok(sel) = Selector::method("new", slots) {
+            let instance = TypeKnowledge::known(variant_ty, EvidenceAuthority::Declared);
+            variant_surface.add_callable(DispatchSide::Class, CallableSignature::new(sel, params, instance));
+        }
+        ctx.register_surface(variant_id, variant_surface);
+        arms.push(variant_match_keyword(&variant.name));
+    }
+    if arms.is_empty() {
+        return;
+    }
+    let slots: Vec<_> = arms.iter().map(|arm| SelectorSlot::Label(arm.clone())).collect();
+    let params = arms
+        .iter()
+        .map(|arm| CallableParameter::new(arm.clone(), unknown()).with_label(arm.clone()))
+        .collect();
+    if let Ok(sel) = Selector::method("match", slots) {
+        surface.add_callable(DispatchSide::Instance, CallableSignature::new(sel, params, unknown()));
+    }
+}
+
+/// Resolves an `extend` block's target class and collects the surface its
+/// members add. Returns `None`, after reporting it, when the target does not
+/// name a known declaration.
+pub fn extension_surface(ctx: &mut CheckingContext<'_>, extend_def: &ExtendDef) -> Option<(DeclarationId, DeclarationSurface)> {
+    let target = &extend_def.target;
+    let members: Vec<String> = target.members.iter().map(|segment| segment.name.clone()).collect();
+    let Some(decl_id) = ctx.resolver.resolve_type_name(&ctx.current_module, &target.root, &members) else {
+        ctx.diagnostics.push(SemanticDiagnostic::error(
+            DiagnosticCode::AnnotationUnresolved,
+            format!("unresolved extended class `{}`", target.leaf_name()),
+            target.range,
+        ));
+        return None;
+    };
+    let mut surface = DeclarationSurface::new(Some(decl_id.clone()));
+    add_member_surfaces(ctx, &extend_def.members, &mut surface);
+    Some((decl_id, surface))
+}
+
+/// Adds the callable and field signatures `members` declare to `surface`.
+fn add_member_surfaces(ctx: &mut CheckingContext<'_>, members: &[ClassMember], surface: &mut DeclarationSurface) {
+    for member in members {
         let side = member_side(member);
         match member {
             ClassMember::Field(f) => {
@@ -184,8 +268,6 @@ pub fn register_class_surface(ctx: &mut CheckingContext<'_>, class_def: &ClassDe
             _ => {}
         }
     }
-
-    ctx.register_surface(decl_id, surface);
 }
 
 /// Checks the member bodies of an already-registered class declaration.
@@ -208,7 +290,7 @@ pub fn check_class_bodies(ctx: &mut CheckingContext<'_>, class_def: &ClassDef) {
 
                 if let (Some(decl_k), Some(default_expr)) = (declared_k, &f.default) {
                     let init_k = synthesize_expr(ctx, default_expr);
-                    let assignability = check_assignability(ctx.store, ctx.hierarchy, &init_k, &decl_k);
+                    let assignability = ctx.check_assignability(&init_k, &decl_k, f.range);
                     if let Assignability::Refuted { .. } = assignability {
                         ctx.diagnostics.push(SemanticDiagnostic::error(
                             DiagnosticCode::FieldMismatch,
@@ -242,6 +324,30 @@ pub fn check_class_bodies(ctx: &mut CheckingContext<'_>, class_def: &ClassDef) {
     ctx.current_class = old_class;
 }
 
+/// Checks the member bodies of an `extend` block against its target class.
+/// An unresolved target was already reported with the surfaces; its bodies
+/// are still checked, with no enclosing class.
+pub fn check_extension_bodies(ctx: &mut CheckingContext<'_>, extend_def: &ExtendDef) {
+    let target = &extend_def.target;
+    let members: Vec<String> = target.members.iter().map(|segment| segment.name.clone()).collect();
+    let decl_id = ctx.resolver.resolve_type_name(&ctx.current_module, &target.root, &members);
+    let old_class = std::mem::replace(&mut ctx.current_class, decl_id);
+
+    for member in &extend_def.members {
+        let old_side = ctx.current_side;
+        ctx.current_side = member_side(member);
+        match member {
+            ClassMember::Method(m) => check_callable_body(ctx, &m.params, m.return_annotation.as_ref(), &m.body),
+            ClassMember::Getter(g) => check_callable_body(ctx, &[], g.return_annotation.as_ref(), &g.body),
+            ClassMember::Setter(s) => check_callable_body(ctx, std::slice::from_ref(&s.param), s.return_annotation.as_ref(), &s.body),
+            _ => {}
+        }
+        ctx.current_side = old_side;
+    }
+
+    ctx.current_class = old_class;
+}
+
 /// Checks a class declaration and all its members.
 pub fn check_class(ctx: &mut CheckingContext<'_>, class_def: &ClassDef) {
     register_class_surface(ctx, class_def);
@@ -288,7 +394,7 @@ fn check_callable_body(
             if let Statement::Expr { expr, range } = stmt {
                 if let Some(expected) = &expected_return {
                     let tail_k = synthesize_expr(ctx, expr);
-                    let assignability = check_assignability(ctx.store, ctx.hierarchy, &tail_k, expected);
+                    let assignability = ctx.check_assignability(&tail_k, expected, *range);
                     if let Assignability::Refuted { .. } = assignability {
                         ctx.diagnostics.push(SemanticDiagnostic::error(
                             DiagnosticCode::ReturnMismatch,
@@ -2,6 +2,7 @@
 
 use super::call::match_callable_arguments;
 use super::context::CheckingContext;
+use super::sealed;
 use super::statement::check_statement;
 use super::typed_expr::TypedExpression;
 use crate::diagnostic::{DiagnosticCode, SemanticDiagnostic};
@@ -9,7 +10,7 @@ use crate::dispatch::DispatchResult;
 use crate::types::denotation::{SemanticDenotation, ValueSemanticFact};
 use crate::types::evidence::{DynamicReason, EvidenceAuthority, TypeKnowledge, UnknownReason};
 use crate::types::id::KindId;
-use crate::types::relation::{Assignability, check_assignability};
+use crate::types::relation::Assignability;
 use crate::types::store::{RecordTypeField, TupleTypeElement, TypeData};
 use phalcom_ast::ast::{
     BinaryExpr, BinaryOp, Expr, GetPropertyExpr, IndexExpr, ListLiteralElement, MapLiteralEntry, MapLiteralKey, MethodCallExpr, PackItem, PackLabel, Pattern,
@@ -137,7 +138,7 @@ pub fn synthesize_typed_expr(ctx: &mut CheckingContext<'_>, expr: &Expr) -> Type
             let val_k = &val_typed.knowledge;
             if let Expr::Var { value: var_name, .. } = &*assign.name {
                 if let Some(target_fact) = ctx.lookup_local(var_name).cloned() {
-                    let assignability = check_assignability(ctx.store, ctx.hierarchy, val_k, &target_fact.knowledge);
+                    let assignability = ctx.check_assignability(val_k, &target_fact.knowledge, assign.range);
                     if let Assignability::Refuted { .. } = assignability {
                         ctx.diagnostics.push(SemanticDiagnostic::error(
                             DiagnosticCode::AssignmentMismatch,
@@ -173,6 +174,10 @@ pub fn synthesize_typed_expr(ctx: &mut CheckingContext<'_>, expr: &Expr) -> Type
                             synthesize_expr(ctx, expr);
                             tail_typed = TypedExpression::known(ctx.store.never(), EvidenceAuthority::ExactSyntax, block.range);
                         }
+                        Statement::Return(_) => {
+                            check_statement(ctx, stmt);
+                            tail_typed = TypedExpression::known(ctx.store.never(), EvidenceAuthority::ExactSyntax, block.range);
+                        }
                         _ => {
                             check_statement(ctx, stmt);
                             tail_typed = TypedExpression::known(ctx.store.unit(), EvidenceAuthority::ExactSyntax, block.range);
@@ -187,13 +192,36 @@ pub fn synthesize_typed_expr(ctx: &mut CheckingContext<'_>, expr: &Expr) -> Type
         }
         Expr::IfLet(if_let) => {
             let val_typed = synthesize_typed_expr(ctx, &if_let.value);
+            // `if let Variant(..) = local` is an exact class test of `local`.
+            let narrowing = match (&if_let.pattern, &if_let.value) {
+                (Pattern::Variant { constructor, .. }, Expr::Var { value: subject, .. }) => ctx
+                    .resolver
+                    .resolve_type_name(&ctx.current_module, constructor, &[])
+                    .filter(|_| ctx.lookup_local(subject).is_some())
+                    .map(|target| (subject.clone(), target)),
+                _ => None,
+            };
+            let narrowed = |ctx: &mut CheckingContext<'_>, truth: bool| {
+                let (subject, target) = narrowing.as_ref()?;
+                let knowledge = ctx.lookup_local(subject)?.knowledge.clone();
+                sealed::narrow_type_test(ctx, &knowledge, target, true, truth).map(|k| (subject.clone(), ValueSemanticFact::new(k)))
+            };
+            let then_narrowed = narrowed(ctx, true);
+            let else_narrowed = narrowed(ctx, false);
+
             ctx.push_scope();
+            if let Some((subject, fact)) = then_narrowed {
+                ctx.bind_local(subject, fact);
+            }
             bind_pattern(ctx, &if_let.pattern, val_typed.fact());
             let then_typed = synthesize_typed_expr(ctx, &Expr::Block(Box::new(if_let.then_body.clone())));
             ctx.pop_scope();
 
             let else_typed = if let Some(ref else_body) = if_let.else_body {
                 ctx.push_scope();
+                if let Some((subject, fact)) = else_narrowed {
+                    ctx.bind_local(subject, fact);
+                }
                 let typed = synthesize_typed_expr(ctx, &Expr::Block(Box::new(else_body.clone())));
                 ctx.pop_scope();
                 typed
@@ -504,8 +532,16 @@ fn synthesize_record_literal(ctx: &mut CheckingContext<'_>, rec: &phalcom_ast::a
 // ---------------------------------------------------------------------------
 
 fn synthesize_method_call(ctx: &mut CheckingContext<'_>, call: &MethodCallExpr) -> TypedExpression {
+    if let Some(typed) = sealed::synthesize_type_test_conditional(ctx, call) {
+        return typed;
+    }
     let recv_typed = synthesize_typed_expr(ctx, &call.object);
     let recv_k = &recv_typed.knowledge;
+    if call.method == "match" {
+        if let Some(recv_ty) = recv_k.ty() {
+            sealed::check_match_arms(ctx, recv_ty, call);
+        }
+    }
 
     // Build selector from method name + pack items
     let mut slots = Vec::new();
@@ -716,6 +752,7 @@ fn synthesize_unary_expr(ctx: &mut CheckingContext<'_>, unary: &UnaryExpr) -> Ty
         UnaryOp::Minus => "-",
         UnaryOp::Not => "not",
         UnaryOp::BitNot => "~",
+        UnaryOp::Await => "await",
     };
 
     if let Ok(sel) = Selector::getter(op_name) {
@@ -788,9 +825,10 @@ fn synthesize_set_property(ctx: &mut CheckingContext<'_>, set: &SetPropertyExpr)
                 .get_surface(&declaration)
                 .and_then(|s| s.get_field(crate::identity::DispatchSide::Instance, &set.property)),
             _ => None,
-        };
+        }
+        .cloned();
         if let Some(field_k) = field_opt {
-            let assignability = check_assignability(ctx.store, ctx.hierarchy, &val_k, field_k);
+            let assignability = ctx.check_assignability(&val_k, &field_k, set.range);
             if let Assignability::Refuted { .. } = assignability {
                 ctx.diagnostics.push(SemanticDiagnostic::error(
                     DiagnosticCode::FieldMismatch,
@@ -806,7 +844,7 @@ fn synthesize_set_property(ctx: &mut CheckingContext<'_>, set: &SetPropertyExpr)
             let dispatch_res = ctx.resolve_dispatch(recv_ty, &sel, recv_typed.dispatch_lookup);
             if let DispatchResult::Found(sig) = dispatch_res {
                 if let Some(param) = sig.parameters.first() {
-                    let assignability = check_assignability(ctx.store, ctx.hierarchy, &val_k, &param.ty);
+                    let assignability = ctx.check_assignability(&val_k, &param.ty, set.range);
                     if let Assignability::Refuted { .. } = assignability {
                         ctx.diagnostics.push(SemanticDiagnostic::error(
                             DiagnosticCode::AssignmentMismatch,
@@ -912,7 +950,7 @@ fn synthesize_set_index_expr(ctx: &mut CheckingContext<'_>, set_idx: &SetIndexEx
             if let Some(list_decl) = ctx.resolver.resolve_type_name(&ctx.current_module, "List", &[]) {
                 if origin == ctx.nominal_type_of(&list_decl) && arguments.len() == 1 {
                     let elem_k = TypeKnowledge::known(arguments[0], EvidenceAuthority::Declared);
-                    let assignability = check_assignability(ctx.store, ctx.hierarchy, &val_k, &elem_k);
+                    let assignability = ctx.check_assignability(&val_k, &elem_k, set_idx.range);
                     if let Assignability::Refuted { .. } = assignability {
                         ctx.diagnostics.push(SemanticDiagnostic::error(
                             DiagnosticCode::AssignmentMismatch,
@@ -934,6 +972,15 @@ fn bind_pattern(ctx: &mut CheckingContext<'_>, pattern: &Pattern, fact: ValueSem
         Pattern::Name { name, .. } => {
             ctx.bind_local(name.clone(), fact);
         }
+        Pattern::Variant { arguments, .. } => {
+            for argument in arguments {
+                bind_pattern(
+                    ctx,
+                    argument,
+                    ValueSemanticFact::new(TypeKnowledge::Unknown(UnknownReason::UnannotatedDeclaration)),
+                );
+            }
+        }
         _ => {}
     }
 }
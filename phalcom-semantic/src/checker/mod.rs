@@ -5,6 +5,7 @@ pub mod context;
 pub mod declaration;
 pub mod expression;
 pub mod result;
+mod sealed;
 pub mod statement;
 pub mod typed_expr;
 
@@ -34,10 +35,17 @@ pub fn check_program(
 ) -> TypeCheckReport {
     let mut ctx = CheckingContext::new(store, hierarchy, resolver, declarations, module);
 
-    // Pre-pass: register top-level class surfaces
+    // Pre-pass: register top-level class surfaces and the program's own
+    // `extend` blocks
     for stmt in &program.statements {
-        if let phalcom_ast::ast::Statement::Class(class_def) = stmt {
-            declaration::register_class_surface(&mut ctx, class_def);
+        match stmt {
+            phalcom_ast::ast::Statement::Class(class_def) => declaration::register_class_surface(&mut ctx, class_def),
+            phalcom_ast::ast::Statement::Extend(extend_def) => {
+                if let Some((decl_id, surface)) = declaration::extension_surface(&mut ctx, extend_def) {
+                    ctx.dispatch.register_extension(decl_id, &surface);
+                }
+            }
+            _ => {}
         }
     }
 
@@ -0,0 +1,286 @@
+//! Type-test narrowing and closed `@sealed` hierarchies.
+//!
+//! A local tested with `is`/`is!` in an `if`, or destructured by an `if let`
+//! variant pattern, is rebound with the narrowed type for each arm. A value of
+//! a `@sealed` class stands for its closed set of direct subclasses, so a
+//! failed test narrows it to the variants that remain. A `match(...)` send on
+//! such a value is checked against the `@variant` arms it has to cover.
+
+use super::context::CheckingContext;
+use super::expression::synthesize_typed_expr;
+use super::typed_expr::TypedExpression;
+use crate::declarations::SealedHierarchy;
+use crate::diagnostic::{DiagnosticCode, SemanticDiagnostic};
+use crate::identity::DeclarationId;
+use crate::types::denotation::ValueSemanticFact;
+use crate::types::evidence::{EvidenceAuthority, TypeKnowledge};
+use crate::types::id::TypeId;
+use crate::types::store::TypeData;
+use phalcom_ast::ast::{Expr, MethodCallExpr, PackItem, PackLabel, UnaryOp};
+use std::collections::BTreeSet;
+
+/// Deepest chain of sealed classes whose variants are themselves sealed that
+/// narrowing expands.
+const MAX_SEALED_DEPTH: usize = 16;
+
+/// A type test `subject is Target` read from a condition.
+pub(crate) struct TypeTest {
+    pub subject: String,
+    pub target: DeclarationId,
+    /// `is!`: the class must be `target` itself, not a subclass.
+    pub exact: bool,
+    /// The test is wrapped in `not` (`is not`, `not (x is T)`).
+    pub negated: bool,
+}
+
+/// Reads `condition` as a type test of a local against a class named
+/// statically, or `None` for any other condition.
+pub(crate) fn type_test(ctx: &CheckingContext<'_>, condition: &Expr) -> Option<TypeTest> {
+    match condition {
+        Expr::Unary(unary) if matches!(unary.op, UnaryOp::Not) => type_test(ctx, &unary.expr).map(|test| TypeTest {
+            negated: !test.negated,
+            ..test
+        }),
+        Expr::MethodCall(call) if matches!(call.method.as_str(), "is" | "is!") && call.args.len() == 1 => {
+            let Expr::Var { value: subject, .. } = &call.object else { return None };
+            ctx.lookup_local(subject)?;
+            let PackItem::Positional {
+                expr: Expr::Var { value: class_name, .. },
+                ..
+            } = &call.args[0]
+            else {
+                return None;
+            };
+            let target = ctx.resolver.resolve_type_name(&ctx.current_module, class_name, &[])?;
+            Some(TypeTest {
+                subject: subject.clone(),
+                target,
+                exact: call.method == "is!",
+                negated: false,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// The knowledge a value of `knowledge` has once a test against `target`
+/// answered `truth`, or `None` when the test teaches nothing new.
+///
+/// A passing test proves at least `target`; a failing one only removes the
+/// classes it rules out, which takes a nominal or union type whose sealed
+/// members are expanded into their variants.
+pub(crate) fn narrow_type_test(
+    ctx: &mut CheckingContext<'_>,
+    knowledge: &TypeKnowledge,
+    target: &DeclarationId,
+    exact: bool,
+    truth: bool,
+) -> Option<TypeKnowledge> {
+    let target_ty = ctx.nominal_type_of(target);
+    let Some(current) = knowledge.ty() else {
+        return truth.then(|| TypeKnowledge::known(target_ty, EvidenceAuthority::Proven));
+    };
+    let members = closed_members(ctx, current)?;
+    let mut kept = Vec::new();
+    for member in &members {
+        let passes = if exact { member == target } else { ctx.hierarchy.is_subclass(member, target) };
+        if passes == truth {
+            kept.push(ctx.nominal_type_of(member));
+        } else if truth && ctx.hierarchy.is_subclass(target, member) {
+            // A wider class only narrows down to the tested one.
+            kept.push(target_ty);
+        }
+    }
+    if kept.is_empty() {
+        return None;
+    }
+    let narrowed = ctx.store.union(&kept);
+    (narrowed != current).then(|| TypeKnowledge::known(narrowed, EvidenceAuthority::Proven))
+}
+
+/// The classes a value of `ty` can be, with every sealed class replaced by
+/// its variants, or `None` when `ty` is not a nominal class or a union of them.
+fn closed_members(ctx: &CheckingContext<'_>, ty: TypeId) -> Option<Vec<DeclarationId>> {
+    let mut members = Vec::new();
+    match ctx.store.get(ty) {
+        TypeData::Nominal { declaration } => expand_sealed(ctx, declaration, 0, &mut members),
+        TypeData::Union(alternatives) => {
+            for &alternative in alternatives.iter() {
+                let TypeData::Nominal { declaration } = ctx.store.get(alternative) else {
+                    return None;
+                };
+                expand_sealed(ctx, declaration, 0, &mut members);
+            }
+        }
+        _ => return None,
+    }
+    Some(members)
+}
+
+fn expand_sealed(ctx: &CheckingContext<'_>, declaration: &DeclarationId, depth: usize, out: &mut Vec<DeclarationId>) {
+    match ctx.declarations.sealed(declaration) {
+        Some(hierarchy) if !hierarchy.variants.is_empty() && depth < MAX_SEALED_DEPTH => {
+            for variant in &hierarchy.variants {
+                expand_sealed(ctx, &variant.declaration, depth + 1, out);
+            }
+        }
+        _ => {
+            if !out.contains(declaration) {
+                out.push(declaration.clone());
+            }
+        }
+    }
+}
+
+/// Checks the `ifTrue`/`ifFalse` send an `if` whose condition is a type test
+/// lowers to, rebinding the tested local with its narrowed type in each
+/// literal arm. Returns `None`, checking nothing, for any other send.
+pub(crate) fn synthesize_type_test_conditional(ctx: &mut CheckingContext<'_>, call: &MethodCallExpr) -> Option<TypedExpression> {
+    let (first, second) = match (call.method.as_str(), call.args.as_slice()) {
+        ("ifTrue", [PackItem::Positional { expr: then_arm, .. }]) => ((then_arm, true), None),
+        (
+            "ifTrue",
+            [
+                PackItem::Positional { expr: then_arm, .. },
+                PackItem::Labeled {
+                    label: PackLabel::Static { text, .. },
+                    value: else_arm,
+                    ..
+                },
+            ],
+        ) if text == "ifFalse" => ((then_arm, true), Some((else_arm, false))),
+        ("ifFalse", [PackItem::Positional { expr: else_arm, .. }]) => ((else_arm, false), None),
+        _ => return None,
+    };
+    if !matches!(first.0, Expr::Block(_)) || second.is_some_and(|(arm, _)| !matches!(arm, Expr::Block(_))) {
+        return None;
+    }
+    let test = type_test(ctx, &call.object)?;
+    synthesize_typed_expr(ctx, &call.object);
+
+    let mut arms = Vec::new();
+    for (arm, branch) in std::iter::once(first).chain(second) {
+        let truth = branch != test.negated;
+        arms.push(synthesize_narrowed_arm(ctx, &test, truth, arm));
+    }
+    let range = call.range;
+    let mut result = match arms.as_slice() {
+        [then_typed, else_typed] => {
+            let ty = match (then_typed.ty(), else_typed.ty()) {
+                (Some(t1), Some(t2)) => ctx.store.union(&[t1, t2]),
+                (Some(t), None) | (None, Some(t)) => t,
+                (None, None) => ctx.store.unit(),
+            };
+            TypedExpression::known(ty, EvidenceAuthority::Proven, range)
+        }
+        _ => TypedExpression::known(ctx.store.unit(), EvidenceAuthority::ExactSyntax, range),
+    };
+    if let [then_typed, else_typed] = arms.as_slice() {
+        if then_typed.denotation.is_some() && then_typed.denotation == else_typed.denotation {
+            result.denotation = then_typed.denotation;
+        }
+    }
+    Some(result)
+}
+
+/// Checks one arm with `test.subject` narrowed for a test that answered
+/// `truth`. A reassignment inside the arm writes through to the outer
+/// binding.
+fn synthesize_narrowed_arm(ctx: &mut CheckingContext<'_>, test: &TypeTest, truth: bool, arm: &Expr) -> TypedExpression {
+    let outer = ctx.lookup_local(&test.subject).cloned();
+    let narrowed = outer
+        .as_ref()
+        .and_then(|fact| narrow_type_test(ctx, &fact.knowledge, &test.target, test.exact, truth))
+        .map(ValueSemanticFact::new);
+    ctx.push_scope();
+    if let Some(fact) = narrowed.clone() {
+        ctx.bind_local(test.subject.clone(), fact);
+    }
+    let typed = synthesize_typed_expr(ctx, arm);
+    let reassigned = ctx
+        .local_envs
+        .last()
+        .and_then(|env| env.get(&test.subject))
+        .cloned()
+        .filter(|after| Some(after) != narrowed.as_ref());
+    ctx.pop_scope();
+    if let Some(fact) = reassigned {
+        ctx.assign_existing(&test.subject, fact);
+    }
+    typed
+}
+
+/// The sealed hierarchy whose `match(...)` visitor a receiver of `ty`
+/// answers: the sealed class's own, or the one its variant inherits. A union
+/// answers it when all its members share one.
+fn match_family<'t>(ctx: &CheckingContext<'t>, ty: TypeId) -> Option<&'t SealedHierarchy> {
+    let declarations = ctx.declarations;
+    let family = |declaration: &DeclarationId| declarations.sealed(declaration).or_else(|| declarations.sealed_parent(declaration));
+    match ctx.store.get(ty) {
+        TypeData::Nominal { declaration } => family(declaration),
+        TypeData::Union(alternatives) => {
+            let mut shared: Option<&SealedHierarchy> = None;
+            for &alternative in alternatives.iter() {
+                let TypeData::Nominal { declaration } = ctx.store.get(alternative) else {
+                    return None;
+                };
+                let hierarchy = family(declaration)?;
+                if shared.is_some_and(|shared| shared.declaration != hierarchy.declaration) {
+                    return None;
+                }
+                shared = Some(hierarchy);
+            }
+            shared
+        }
+        _ => None,
+    }
+}
+
+/// Warns when a `match(...)` send to a value of a sealed family leaves out
+/// one of its `@variant` arms or passes a keyword none of them takes. A send
+/// with a positional, spread or computed argument is left to dispatch.
+pub(crate) fn check_match_arms(ctx: &mut CheckingContext<'_>, receiver: TypeId, call: &MethodCallExpr) {
+    let Some(hierarchy) = match_family(ctx, receiver) else { return };
+    let expected: Vec<(String, &DeclarationId)> = hierarchy
+        .match_arms()
+        .filter_map(|variant| variant.match_keyword().map(|keyword| (keyword, &variant.declaration)))
+        .collect();
+    if expected.is_empty() {
+        return;
+    }
+    let mut given = Vec::new();
+    for arg in &call.args {
+        let PackItem::Labeled {
+            label: PackLabel::Static { text, range },
+            ..
+        } = arg
+        else {
+            return;
+        };
+        given.push((text.as_str(), *range));
+    }
+
+    let sealed_name = &hierarchy.declaration.name;
+    let given_keywords: BTreeSet<&str> = given.iter().map(|(keyword, _)| *keyword).collect();
+    let missing: Vec<String> = expected
+        .iter()
+        .filter(|(keyword, _)| !given_keywords.contains(keyword.as_str()))
+        .map(|(keyword, variant)| format!("`{}` (`{keyword}:`)", variant.name))
+        .collect();
+    if !missing.is_empty() {
+        ctx.diagnostics.push(SemanticDiagnostic::warning(
+            DiagnosticCode::MatchMissingVariant,
+            format!("`match` on sealed `{sealed_name}` has no arm for {}", missing.join(", ")),
+            call.range,
+        ));
+    }
+    for (keyword, range) in given {
+        if !expected.iter().any(|(expected, _)| expected == keyword) {
+            ctx.diagnostics.push(SemanticDiagnostic::warning(
+                DiagnosticCode::MatchSuperfluousArm,
+                format!("`match` arm `{keyword}:` names no variant of sealed `{sealed_name}`"),
+                range,
+            ));
+        }
+    }
+}
@@ -7,7 +7,7 @@ use crate::diagnostic::{DiagnosticCode, SemanticDiagnostic};
 use crate::types::annotation::resolve_type_annotation;
 use crate::types::denotation::ValueSemanticFact;
 use crate::types::evidence::{EvidenceAuthority, TypeKnowledge, UnknownReason};
-use crate::types::relation::{Assignability, check_assignability};
+use crate::types::relation::Assignability;
 use phalcom_ast::ast::{Pattern, Statement};
 
 /// Checks a single statement, updating context bindings and recording diagnostics.
@@ -30,7 +30,7 @@ pub fn check_statement(ctx: &mut CheckingContext<'_>, statement: &Statement) {
             let mut is_assignable = true;
             if let Some(ref decl_k) = declared_k {
                 if binding.value.is_some() {
-                    let assignability = check_assignability(ctx.store, ctx.hierarchy, &val_typed.knowledge, decl_k);
+                    let assignability = ctx.check_assignability(&val_typed.knowledge, decl_k, binding.range);
                     if let Assignability::Refuted { .. } = assignability {
                         is_assignable = false;
                         let mut diag = SemanticDiagnostic::error(
@@ -71,7 +71,7 @@ pub fn check_statement(ctx: &mut CheckingContext<'_>, statement: &Statement) {
             };
 
             if let Some(expected) = ctx.expected_return.clone() {
-                let assignability = check_assignability(ctx.store, ctx.hierarchy, &val_k, &expected);
+                let assignability = ctx.check_assignability(&val_k, &expected, ret.range);
                 if let Assignability::Refuted { .. } = assignability {
                     ctx.diagnostics.push(SemanticDiagnostic::error(
                         DiagnosticCode::ReturnMismatch,
@@ -84,12 +84,15 @@ pub fn check_statement(ctx: &mut CheckingContext<'_>, statement: &Statement) {
         Statement::Expr { expr, .. } => {
             synthesize_expr(ctx, expr);
         }
-        Statement::Throw { expr, .. } => {
+        Statement::Throw { expr, .. } | Statement::Yield { expr, .. } => {
             synthesize_expr(ctx, expr);
         }
         Statement::Class(class_def) => {
             super::declaration::check_class(ctx, class_def);
         }
+        Statement::Extend(extend_def) => {
+            super::declaration::check_extension_bodies(ctx, extend_def);
+        }
         Statement::For(for_stmt) => {
             let mut lane_facts = Vec::new();
             for lane in &for_stmt.lanes {
@@ -1,9 +1,10 @@
 //! Canonical declaration type forms and metadata table.
 
-use crate::identity::DeclarationId;
+use crate::identity::{DeclarationId, ModuleId};
 use crate::types::id::{KindId, TypeId};
 use crate::types::parameter::{GenericSignature, TypeParameterData, TypeParameterOwner};
 use crate::types::store::TypeStore;
+use phalcom_native_meta::extension::{EXTENSION_CLASSES, NativeClassSpec};
 use phalcom_native_meta::types::{KindSpec, UniverseTypeFormSpec};
 use phalcom_native_meta::universe::{UNIVERSE_BINDINGS, UNIVERSE_TYPE_FORMS, UniverseKey};
 use std::collections::HashMap;
@@ -25,9 +26,50 @@ pub struct DeclarationTypeInfo {
     pub supertype_template: Option<GenericSupertypeTemplate>,
 }
 
+/// One direct subclass of a `@sealed` class.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SealedVariant {
+    pub declaration: DeclarationId,
+    /// The field labels of a `@variant Name(labels...)` arm, or `None` for a
+    /// subclass the module declares itself with `class Name is Sealed`.
+    pub labels: Option<Vec<String>>,
+}
+
+impl SealedVariant {
+    /// The keyword this variant's arm takes in the generated `match(...)`
+    /// visitor — its name with the first letter lowered — or `None` when it
+    /// is not a `@variant` arm.
+    pub fn match_keyword(&self) -> Option<String> {
+        self.labels.as_ref().map(|_| variant_match_keyword(&self.declaration.name))
+    }
+}
+
+/// The `match(...)` keyword of the `@variant` arm named `name`: `Circle`
+/// answers `circle:`.
+pub fn variant_match_keyword(name: &str) -> String {
+    let mut chars = name.chars();
+    chars.next().into_iter().flat_map(char::to_lowercase).chain(chars).collect()
+}
+
+/// A `@sealed` class and its closed set of direct subclasses: the `@variant`
+/// arms in declaration order, then the module's own `is` subclasses.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SealedHierarchy {
+    pub declaration: DeclarationId,
+    pub variants: Vec<SealedVariant>,
+}
+
+impl SealedHierarchy {
+    /// The `@variant` arms alone, which the `match(...)` visitor covers.
+    pub fn match_arms(&self) -> impl Iterator<Item = &SealedVariant> {
+        self.variants.iter().filter(|variant| variant.labels.is_some())
+    }
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct DeclarationTypeTable {
     entries: HashMap<DeclarationId, DeclarationTypeInfo>,
+    sealed: HashMap<DeclarationId, SealedHierarchy>,
 }
 
 impl DeclarationTypeTable {
@@ -66,6 +108,22 @@ impl DeclarationTypeTable {
     pub fn iter(&self) -> impl Iterator<Item = (&DeclarationId, &DeclarationTypeInfo)> {
         self.entries.iter()
     }
+
+    pub fn insert_sealed(&mut self, hierarchy: SealedHierarchy) {
+        self.sealed.insert(hierarchy.declaration.clone(), hierarchy);
+    }
+
+    /// The closed hierarchy `declaration` roots, if it is `@sealed`.
+    pub fn sealed(&self, declaration: &DeclarationId) -> Option<&SealedHierarchy> {
+        self.sealed.get(declaration)
+    }
+
+    /// The sealed hierarchy `declaration` is a direct subclass of, if any.
+    pub fn sealed_parent(&self, declaration: &DeclarationId) -> Option<&SealedHierarchy> {
+        self.sealed
+            .values()
+            .find(|hierarchy| hierarchy.variants.iter().any(|variant| &variant.declaration == declaration))
+    }
 }
 
 /// Helper function to lower a `KindSpec` into a canonical `KindId`.
@@ -138,3 +196,26 @@ pub fn bootstrap_universe_declarations(store: &mut TypeStore, universe_resolver:
 
     table
 }
+
+/// The declaration of an extension class: its name in its `native` module.
+pub fn extension_declaration(class: &NativeClassSpec) -> DeclarationId {
+    DeclarationId::new(ModuleId::native(class.module), class.name.into())
+}
+
+/// Adds nominal declaration forms for every linked extension class to `table`.
+/// Extension classes are never generic.
+pub fn bootstrap_extension_declarations(store: &mut TypeStore, table: &mut DeclarationTypeTable) {
+    for class in EXTENSION_CLASSES {
+        let decl = extension_declaration(class);
+        let form = store.nominal_type(decl.clone());
+        let class_obj_type = store.class_object_type(decl.clone());
+        table.insert(DeclarationTypeInfo {
+            declaration: decl,
+            form,
+            class_object_type: class_obj_type,
+            kind: KindId::TYPE,
+            generic_signature: None,
+            supertype_template: None,
+        });
+    }
+}
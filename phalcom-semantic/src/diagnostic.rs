@@ -35,6 +35,9 @@ pub enum DiagnosticCode {
     AnalysisInternalFailure,
     TypeRelationCycle,
     TypeDynamicBoundary,
+    ProtocolRequirementMissing,
+    MatchMissingVariant,
+    MatchSuperfluousArm,
 }
 
 impl DiagnosticCode {
@@ -62,6 +65,9 @@ impl DiagnosticCode {
             Self::AnalysisInternalFailure => "analysis.internal_failure",
             Self::TypeRelationCycle => "type.relation.cycle",
             Self::TypeDynamicBoundary => "type.dynamic_boundary",
+            Self::ProtocolRequirementMissing => "type.protocol.missing_requirements",
+            Self::MatchMissingVariant => "type.match.missing_variant",
+            Self::MatchSuperfluousArm => "type.match.superfluous_arm",
         }
     }
 }
@@ -2,6 +2,7 @@
 
 use crate::identity::{CallableId, DeclarationId};
 
+use crate::declarations::GenericSupertypeTemplate;
 pub use crate::identity::DispatchSide;
 use crate::surface::DeclarationSurface;
 use crate::types::evidence::TypeKnowledge;
@@ -108,6 +109,9 @@ pub trait DispatchResolver {
 pub struct SurfaceDispatchResolver {
     surfaces: HashMap<DeclarationId, DeclarationSurface>,
     type_declarations: HashMap<TypeId, DeclarationId>,
+    /// Scoped `extend` surfaces visible to the module being checked. They
+    /// answer a send only once the whole lookup order misses, as at runtime.
+    extensions: HashMap<DeclarationId, DeclarationSurface>,
 }
 
 impl SurfaceDispatchResolver {
@@ -123,6 +127,20 @@ impl SurfaceDispatchResolver {
         self.type_declarations.insert(ty, decl);
     }
 
+    /// Makes the callables of an `extend` block's surface visible to
+    /// dispatch. An earlier registration of the same selector is kept; the
+    /// linker has already rejected two visible extensions that disagree.
+    pub fn register_extension(&mut self, decl: DeclarationId, surface: &DeclarationSurface) {
+        let scoped = self.extensions.entry(decl.clone()).or_insert_with(|| DeclarationSurface::new(Some(decl)));
+        for side in [DispatchSide::Instance, DispatchSide::Class] {
+            for (selector, signature) in &surface.surface(side).callable_signatures {
+                if scoped.get_callable(side, selector).is_none() {
+                    scoped.add_callable(side, signature.clone());
+                }
+            }
+        }
+    }
+
     pub fn get_surface(&self, decl: &DeclarationId) -> Option<&DeclarationSurface> {
         self.surfaces.get(decl)
     }
@@ -142,14 +160,31 @@ impl SurfaceDispatchResolver {
         side: DispatchSide,
         selector: &Selector,
     ) -> DispatchResult {
-        let mut curr = Some(start_decl);
-        while let Some(decl) = curr {
+        let order = lookup_order(hierarchy, start_decl, side);
+        match self.resolve_dispatch_in_order(&order, side, selector) {
+            DispatchResult::Missing => self
+                .resolve_extension(&order, side, selector)
+                .map_or(DispatchResult::Missing, |sig| DispatchResult::Found(sig.clone())),
+            found => found,
+        }
+    }
+
+    /// The nearest scoped extension along `order` answering `selector`.
+    fn resolve_extension(&self, order: &[DeclarationId], side: DispatchSide, selector: &Selector) -> Option<&CallableSignature> {
+        order
+            .iter()
+            .find_map(|decl| self.extensions.get(decl).and_then(|surface| surface.get_callable(side, selector)))
+    }
+
+    /// Resolves `selector` against an explicit lookup order, such as the
+    /// tail of a linearization that a `super` send continues from.
+    pub fn resolve_dispatch_in_order(&self, order: &[DeclarationId], side: DispatchSide, selector: &Selector) -> DispatchResult {
+        for decl in order {
             if let Some(surface) = self.surfaces.get(decl) {
                 if let Some(sig) = surface.get_callable(side, selector) {
                     return DispatchResult::Found(sig.clone());
                 }
             }
-            curr = hierarchy.superclass(decl);
         }
         DispatchResult::Missing
     }
@@ -161,15 +196,107 @@ impl SurfaceDispatchResolver {
         side: DispatchSide,
         selector: &Selector,
     ) -> Option<CallableId> {
-        let mut curr = Some(start_decl);
-        while let Some(decl) = curr {
+        let order = lookup_order(hierarchy, start_decl, side);
+        for decl in &order {
             if let Some(surface) = self.surfaces.get(decl) {
                 if let Some(id) = surface.get_callable_id(side, selector) {
                     return Some(id.clone());
                 }
             }
-            curr = hierarchy.superclass(decl);
         }
-        None
+        order
+            .iter()
+            .find_map(|decl| self.extensions.get(decl).and_then(|surface| surface.get_callable_id(side, selector)))
+            .cloned()
+    }
+}
+
+/// A [`TypeHierarchy`] that also sees the registered class surfaces, so
+/// protocol conformance can be decided from each class's method surface.
+pub struct SurfaceHierarchy<'a> {
+    pub hierarchy: &'a dyn TypeHierarchy,
+    pub dispatch: &'a SurfaceDispatchResolver,
+}
+
+impl SurfaceHierarchy<'_> {
+    /// Instance-side selectors `protocol` requires: its own, then those of
+    /// every protocol it includes, each once.
+    fn requirements(&self, protocol: &DeclarationId) -> Option<Vec<Selector>> {
+        let mut requirements = Vec::new();
+        let mut seen = vec![protocol.clone()];
+        let mut pending = vec![protocol.clone()];
+        while let Some(decl) = pending.pop() {
+            let surface = self.dispatch.get_surface(&decl)?;
+            let mut own: Vec<&Selector> = surface.instance.callable_signatures.keys().collect();
+            own.sort_by_key(|selector| selector.encode());
+            for selector in own {
+                if !requirements.contains(selector) {
+                    requirements.push(selector.clone());
+                }
+            }
+            for included in self.hierarchy.mixins(&decl) {
+                if !seen.contains(included) {
+                    seen.push(included.clone());
+                    pending.push(included.clone());
+                }
+            }
+        }
+        Some(requirements)
+    }
+}
+
+impl TypeHierarchy for SurfaceHierarchy<'_> {
+    fn superclass(&self, declaration: &DeclarationId) -> Option<&DeclarationId> {
+        self.hierarchy.superclass(declaration)
+    }
+
+    fn is_subclass(&self, sub: &DeclarationId, sup: &DeclarationId) -> bool {
+        self.hierarchy.is_subclass(sub, sup)
+    }
+
+    fn supertype_template(&self, declaration: &DeclarationId) -> Option<&GenericSupertypeTemplate> {
+        self.hierarchy.supertype_template(declaration)
+    }
+
+    fn mixins(&self, declaration: &DeclarationId) -> &[DeclarationId] {
+        self.hierarchy.mixins(declaration)
+    }
+
+    fn missing_requirements(&self, class: &DeclarationId, protocol: &DeclarationId) -> Option<Vec<Selector>> {
+        self.dispatch.get_surface(class)?;
+        let requirements = self.requirements(protocol)?;
+        Some(
+            requirements
+                .into_iter()
+                .filter(|selector| {
+                    // Conformance is a property of the class everywhere, so
+                    // scoped extensions never satisfy a requirement.
+                    !self
+                        .dispatch
+                        .resolve_dispatch_in_order(&lookup_order(self.hierarchy, class, DispatchSide::Instance), DispatchSide::Instance, selector)
+                        .is_found()
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Returns the declarations a send on `start_decl` searches, in order. The
+/// instance side follows the mixin linearization
+/// ([`TypeHierarchy::ancestors`]); the class side walks only the superclass
+/// chain, since mixins contribute instance methods alone.
+pub fn lookup_order(hierarchy: &dyn TypeHierarchy, start_decl: &DeclarationId, side: DispatchSide) -> Vec<DeclarationId> {
+    match side {
+        DispatchSide::Instance => hierarchy.ancestors(start_decl),
+        DispatchSide::Class => {
+            let mut chain = vec![start_decl.clone()];
+            while let Some(parent) = hierarchy.superclass(chain.last().expect("chain is non-empty")) {
+                if chain.contains(parent) {
+                    break;
+                }
+                chain.push(parent.clone());
+            }
+            chain
+        }
     }
 }
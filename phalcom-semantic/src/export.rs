@@ -100,7 +100,9 @@ pub fn export_type_form(store: &TypeStore, form: TypeId) -> Result<CompiledTypeR
     match store.get(form) {
         TypeData::Never => Ok(CompiledTypeRef::Never),
         TypeData::Unit => Ok(CompiledTypeRef::Unit),
-        TypeData::Nominal { declaration } => Ok(CompiledTypeRef::Nominal(declaration.clone())),
+        // A protocol is named by its declaration; conformance is re-derived
+        // from the consumer's own class surfaces.
+        TypeData::Nominal { declaration } | TypeData::Protocol { declaration } => Ok(CompiledTypeRef::Nominal(declaration.clone())),
         TypeData::Applied { origin, arguments } => {
             let orig = export_type_form(store, *origin)?;
             let args = arguments.iter().map(|&a| export_type_form(store, a)).collect::<Result<Vec<_>, _>>()?;
@@ -1,12 +1,51 @@
 //! Declaration fingerprinting and fine-grained invalidation.
 
-use crate::identity::DeclarationId;
-use std::collections::HashMap;
+use crate::identity::{DeclarationId, ModuleId};
+use crate::surface::{DeclarationSurface, MemberSurface};
+use crate::types::{TypeKnowledge, TypeStore};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
 
 /// Fingerprint representing the signature interface of a declaration.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct DeclarationFingerprint(pub u64);
 
+impl DeclarationFingerprint {
+    /// Fingerprints the fields and callable signatures `surface` publishes on
+    /// both dispatch sides. Bodies do not take part, so editing a method body
+    /// keeps the fingerprint; adding a field or changing a parameter type
+    /// changes it.
+    pub fn of_surface(surface: &DeclarationSurface, store: &TypeStore) -> Self {
+        let mut entries = BTreeSet::new();
+        for (side, members) in [("instance", &surface.instance), ("class", &surface.class)] {
+            member_entries(side, members, store, &mut entries);
+        }
+        let mut hasher = DefaultHasher::new();
+        entries.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+}
+
+fn member_entries(side: &str, members: &MemberSurface, store: &TypeStore, entries: &mut BTreeSet<String>) {
+    let spell = |ty: &TypeKnowledge| store.display_knowledge(ty).unwrap_or_else(|| "_".to_string());
+    for (name, ty) in &members.fields {
+        entries.insert(format!("{side} field {name}: {}", spell(ty)));
+    }
+    for (selector, signature) in &members.callable_signatures {
+        let params = signature
+            .parameters
+            .iter()
+            .map(|param| {
+                let rest = if param.rest { "..." } else { "" };
+                format!("{}{rest}: {}", param.external_label.as_deref().unwrap_or("_"), spell(&param.ty))
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        entries.insert(format!("{side} {selector}({params}) -> {}", spell(&signature.return_type)));
+    }
+}
+
 /// Fingerprint registry for incremental invalidation analysis.
 #[derive(Clone, Debug, Default)]
 pub struct InvalidationIndex {
@@ -25,4 +64,26 @@ impl InvalidationIndex {
     pub fn get(&self, id: &DeclarationId) -> Option<&DeclarationFingerprint> {
         self.fingerprints.get(id)
     }
+
+    /// Declarations of `module` that were added, removed or whose fingerprint
+    /// differs in `newer`, sorted.
+    pub fn changed_in(&self, newer: &InvalidationIndex, module: &ModuleId) -> Vec<DeclarationId> {
+        let ids = self
+            .fingerprints
+            .keys()
+            .chain(newer.fingerprints.keys())
+            .filter(|id| &id.module == module)
+            .collect::<BTreeSet<_>>();
+        ids.into_iter().filter(|id| self.get(id) != newer.get(id)).cloned().collect()
+    }
+
+    /// Replaces every fingerprint of `module` with those `newer` records.
+    pub fn replace_module(&mut self, newer: &InvalidationIndex, module: &ModuleId) {
+        self.fingerprints.retain(|id, _| &id.module != module);
+        for (id, fp) in &newer.fingerprints {
+            if &id.module == module {
+                self.fingerprints.insert(id.clone(), fp.clone());
+            }
+        }
+    }
 }
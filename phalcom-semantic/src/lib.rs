@@ -23,7 +23,10 @@ pub use checker::{
     CheckingContext, TypeCheckReport, TypedExpression, check_arguments, check_class, check_class_bodies, check_statement, match_callable_arguments,
     register_class_surface, synthesize_expr, synthesize_typed_expr,
 };
-pub use declarations::{DeclarationTypeInfo, DeclarationTypeTable, GenericSupertypeTemplate, bootstrap_universe_declarations, lower_kind_spec};
+pub use declarations::{
+    DeclarationTypeInfo, DeclarationTypeTable, GenericSupertypeTemplate, bootstrap_extension_declarations, bootstrap_universe_declarations,
+    extension_declaration, lower_kind_spec,
+};
 pub use diagnostic::{DiagnosticCode, DiagnosticLabel, DiagnosticSeverity, SemanticDiagnostic, SemanticSourceSpan};
 pub use dispatch::{CallableParameter, CallableSignature, DispatchResolver, DispatchResult, DispatchSide, DispatchTarget, SurfaceDispatchResolver};
 pub use export::{
@@ -47,7 +50,7 @@ pub use types::{
     NativeTypeResolutionError, QueryBudget, RecordTypeField, RefutationReason, RelationEvidence, RelationFailure, RelationOutcome, SemanticDenotation,
     SimpleTypeResolver, TupleTypeElement, TypeApplicationError, TypeConstraint, TypeData, TypeEvidence, TypeHierarchy, TypeId, TypeKnowledge,
     TypeParameterData, TypeParameterId, TypeParameterOwner, TypeResolver, TypeStore, TypeSubstitution, UnknownReason, ValueSemanticFact, check_assignability,
-    check_assignability_bounded, check_subtype_bounded, is_subtype, normalize_native_type, register_standard_surfaces, resolve_native_type_form,
-    resolve_type_annotation, resolve_type_form, substitution_for_applied,
+    check_assignability_bounded, check_subtype_bounded, is_subtype, normalize_native_type, register_extension_surfaces, register_standard_surfaces,
+    resolve_native_type_form, resolve_type_annotation, resolve_type_form, substitution_for_applied,
 };
-pub use workspace::{SemanticAnalysis, SemanticWorkspaceInput, analyze_single_module, analyze_workspace};
+pub use workspace::{SemanticAnalysis, SemanticWorkspaceInput, analyze_single_module, analyze_workspace, analyze_workspace_with_packages};
@@ -4,8 +4,10 @@
 use super::stable_identity::*;
 use crate::declarations::DeclarationTypeTable;
 use crate::diagnostic::SemanticSourceSpan;
-use crate::identity::{CallableId, DeclarationId, FieldId};
+use crate::identity::{CallableId, DeclarationId, DispatchSide, FieldId};
 use crate::signature::{CallableSignatureTable, FieldSignatureTable};
+use crate::surface::DeclarationSurface;
+use crate::types::evidence::{DynamicReason, EvidenceAuthority, TypeKnowledge, UnknownReason};
 use crate::types::id::{KindId, ScopedTypeId, TypeId, TypeLambdaId, TypeParameterId};
 use crate::types::kind::KindData;
 use crate::types::parameter::{GenericConstraint, GenericSignature, SelfRole, SelfTypeTerm, TypeParameterData, TypeParameterOwner, TypeTerm};
@@ -15,7 +17,8 @@ use crate::types::variance::Variance;
 use phalcom_type_meta::bundle::{ModuleMetadataRoot, RuntimeTypeFormKey, RuntimeTypeFormRoot, SemanticMetadataBundle};
 use phalcom_type_meta::declaration::{
     AliasRecordId, CallableParameterRecord, CallableRecordId, CallableSemanticRecord, DeclarationRecordId, DeclarationTypeFlags, DeclarationTypeRecord,
-    FieldMutabilityRef, FieldRecordId, FieldSemanticRecord, MetadataUnavailableReason, PublishedTypeAuthority, PublishedTypeSlot, RestModeRef, TypeAliasRecord,
+    DynamicReasonRef, FieldMutabilityRef, FieldRecordId, FieldSemanticRecord, MetadataUnavailableReason, PublishedTypeAuthority, PublishedTypeSlot,
+    RestModeRef, TypeAliasRecord, UnknownReasonRef,
 };
 use phalcom_type_meta::fingerprint::{Fingerprint128, FingerprintBuilder};
 use phalcom_type_meta::generic::{
@@ -35,7 +38,7 @@ use phalcom_type_meta::scoped_type::{
 use phalcom_type_meta::type_node::{
     CallableParamRef, CallableTypeRef, RecordFieldRef, SelfRoleRef, SelfTypeRef, TupleElementRef, TypeNode, TypeNodeEntry, TypeNodeId,
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
 pub enum MetadataExportError {
@@ -51,6 +54,7 @@ pub struct MetadataExporter<'a> {
     declarations: Option<&'a DeclarationTypeTable>,
     callables: Option<&'a CallableSignatureTable>,
     fields: Option<&'a FieldSignatureTable>,
+    surfaces: Vec<(&'a DeclarationId, &'a DeclarationSurface)>,
     profile: MetadataProfile,
 
     // Deduplication index maps
@@ -83,6 +87,7 @@ impl<'a> MetadataExporter<'a> {
             declarations,
             callables,
             fields,
+            surfaces: Vec::new(),
             profile,
             kind_map: HashMap::new(),
             kinds: Vec::new(),
@@ -97,6 +102,13 @@ impl<'a> MetadataExporter<'a> {
         }
     }
 
+    /// Also exports the callables of `surfaces`, the dispatch surfaces a
+    /// checked program registered, as callable records of their owners.
+    pub fn with_surfaces(mut self, surfaces: impl IntoIterator<Item = (&'a DeclarationId, &'a DeclarationSurface)>) -> Self {
+        self.surfaces.extend(surfaces);
+        self
+    }
+
     pub fn export_kind(&mut self, kind: KindId) -> KindNodeId {
         let data = self.store.get_kind(kind).clone();
         if let Some(&id) = self.kind_map.get(&data) {
@@ -321,7 +333,7 @@ impl<'a> MetadataExporter<'a> {
         let form = match data {
             TypeData::Never => TypeNode::Never,
             TypeData::Unit => TypeNode::Unit,
-            TypeData::Nominal { declaration } => TypeNode::Nominal {
+            TypeData::Nominal { declaration } | TypeData::Protocol { declaration } => TypeNode::Nominal {
                 declaration: to_stable_declaration(&declaration),
             },
             TypeData::Applied { origin, ref arguments } => {
@@ -521,6 +533,44 @@ impl<'a> MetadataExporter<'a> {
         }
     }
 
+    /// Publishes what a dispatch surface knows about one type slot. Advisory
+    /// evidence never rejects code, so it is not published as a fact.
+    fn export_knowledge(&mut self, knowledge: &TypeKnowledge) -> PublishedTypeSlot {
+        match knowledge {
+            TypeKnowledge::Known(evidence) => {
+                let authority = match evidence.authority {
+                    EvidenceAuthority::Declared => PublishedTypeAuthority::DeclaredAnnotation,
+                    EvidenceAuthority::TrustedNative => PublishedTypeAuthority::TrustedNative,
+                    EvidenceAuthority::Proven | EvidenceAuthority::ExactSyntax => PublishedTypeAuthority::CompilerInferred,
+                    EvidenceAuthority::Advisory => {
+                        return PublishedTypeSlot::Unknown {
+                            reason: UnknownReasonRef::InferenceFailed,
+                        };
+                    }
+                };
+                match self.export_type_form(evidence.ty) {
+                    Ok(form) => PublishedTypeSlot::Known { form, authority },
+                    Err(_) => PublishedTypeSlot::Unavailable {
+                        reason: MetadataUnavailableReason::IncompatibleModel,
+                    },
+                }
+            }
+            TypeKnowledge::Unknown(reason) => PublishedTypeSlot::Unknown {
+                reason: match reason {
+                    UnknownReason::UnannotatedDeclaration => UnknownReasonRef::UnannotatedDeclaration,
+                    UnknownReason::OpaqueNative => UnknownReasonRef::OpaqueNative,
+                    _ => UnknownReasonRef::InferenceFailed,
+                },
+            },
+            TypeKnowledge::Dynamic(reason) => PublishedTypeSlot::Dynamic {
+                reason: match reason {
+                    DynamicReason::ExplicitEscape => DynamicReasonRef::ExplicitEscape,
+                    DynamicReason::DynamicRestPack | DynamicReason::RuntimeReflection => DynamicReasonRef::UncheckedBoundary,
+                },
+            },
+        }
+    }
+
     pub fn export_generic_signature(&mut self, sig: &GenericSignature) -> Result<GenericSignatureRecordId, MetadataExportError> {
         let key = (sig.owner.clone(), sig.parameters.len());
         if let Some(&id) = self.sig_map.get(&key) {
@@ -569,8 +619,12 @@ impl<'a> MetadataExporter<'a> {
         let mut out_fields = Vec::new();
         let mut out_roots = Vec::new();
 
+        // Tables iterate in hash order; exporting in stable-reference order
+        // keeps the bundle, and its node numbering, identical across runs.
         if let Some(decls) = self.declarations {
-            for (decl_id, info) in decls.iter() {
+            let mut decls: Vec<_> = decls.iter().collect();
+            decls.sort_by_cached_key(|(decl_id, _)| to_stable_declaration(decl_id));
+            for (decl_id, info) in decls {
                 let form_id = self.export_type_form(info.form)?;
                 let kind_id = self.export_kind(info.kind);
                 let sig_id = if let Some(ref sig) = info.generic_signature {
@@ -601,7 +655,9 @@ impl<'a> MetadataExporter<'a> {
         }
 
         if let Some(call_table) = self.callables {
-            for (call_id, sig) in call_table.iter() {
+            let mut call_table: Vec<_> = call_table.iter().collect();
+            call_table.sort_by_cached_key(|(call_id, _)| to_stable_callable(call_id));
+            for (call_id, sig) in call_table {
                 let sig_id = if let Some(ref g) = sig.generics {
                     Some(self.export_generic_signature(g)?)
                 } else {
@@ -650,8 +706,60 @@ impl<'a> MetadataExporter<'a> {
             }
         }
 
+        let mut surfaces = std::mem::take(&mut self.surfaces);
+        surfaces.sort_by_cached_key(|(decl_id, _)| to_stable_declaration(decl_id));
+        let mut owned: BTreeMap<_, (Vec<_>, Vec<_>)> = BTreeMap::new();
+        for (decl_id, surface) in surfaces {
+            for side in [DispatchSide::Instance, DispatchSide::Class] {
+                let mut signatures: Vec<_> = surface.surface(side).callable_signatures.values().collect();
+                signatures.sort_by_cached_key(|sig| sig.selector.encode());
+                for sig in signatures {
+                    let callable = to_stable_callable(&CallableId::new(decl_id.clone(), sig.selector.clone(), side));
+                    if out_callables.iter().any(|record: &CallableSemanticRecord| record.callable == callable) {
+                        continue;
+                    }
+                    let mut params = Vec::new();
+                    for (index, p) in sig.parameters.iter().enumerate() {
+                        params.push(CallableParameterRecord {
+                            index: index as u32,
+                            local_name: p.local_name.as_str().into(),
+                            external_label: p.external_label.as_deref().map(Into::into),
+                            rest: match (p.rest, &p.external_label) {
+                                (false, _) => RestModeRef::None,
+                                (true, None) => RestModeRef::Anonymous,
+                                (true, Some(_)) => RestModeRef::Named,
+                            },
+                            ty: self.export_knowledge(&p.ty),
+                            source: None,
+                        });
+                    }
+                    let return_type = self.export_knowledge(&sig.return_type);
+                    let entry = owned.entry(to_stable_declaration(decl_id)).or_default();
+                    match side {
+                        DispatchSide::Instance => entry.0.push(callable.clone()),
+                        DispatchSide::Class => entry.1.push(callable.clone()),
+                    }
+                    out_callables.push(CallableSemanticRecord {
+                        callable,
+                        generic_signature: None,
+                        parameters: params.into_boxed_slice(),
+                        return_type,
+                        source: None,
+                    });
+                }
+            }
+        }
+        for record in &mut out_declarations {
+            if let Some((instance, class)) = owned.remove(&record.declaration) {
+                record.instance_callables = instance.into_boxed_slice();
+                record.class_callables = class.into_boxed_slice();
+            }
+        }
+
         if let Some(field_table) = self.fields {
-            for (field_id, sig) in field_table.iter() {
+            let mut field_table: Vec<_> = field_table.iter().collect();
+            field_table.sort_by_cached_key(|(field_id, _)| to_stable_field(field_id));
+            for (field_id, sig) in field_table {
                 let ty_slot = match self.export_type_term(&sig.ty) {
                     Ok(t) => PublishedTypeSlot::Known {
                         form: t,
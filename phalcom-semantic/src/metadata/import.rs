@@ -0,0 +1,165 @@
+//! Importer: declares the classes and dispatch surfaces a published package's
+//! `SemanticMetadataBundle`s describe, so importers of the package type-check
+//! against its metadata instead of its sources.
+
+use crate::declarations::{DeclarationTypeInfo, DeclarationTypeTable};
+use crate::dispatch::{CallableParameter, CallableSignature, SurfaceDispatchResolver};
+use crate::identity::{DeclarationId, DispatchSide, ModuleComponent, ModuleId, ModulePath, ResolvedProjectId};
+use crate::surface::DeclarationSurface;
+use crate::types::evidence::{DynamicReason, EvidenceAuthority, TypeKnowledge, UnknownReason};
+use crate::types::id::KindId;
+use crate::types::relation::MapTypeHierarchy;
+use crate::types::store::TypeStore;
+use phalcom_common::selector::Selector;
+use phalcom_modules::BuiltinProject;
+use phalcom_type_meta::SemanticMetadataBundle;
+use phalcom_type_meta::declaration::{DynamicReasonRef, PublishedTypeAuthority, PublishedTypeSlot, RestModeRef, UnknownReasonRef};
+use phalcom_type_meta::identity::{StableDeclarationRef, StableDispatchSide, StableProjectRef};
+use phalcom_type_meta::type_node::TypeNode;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// The semantic metadata of one published package, as an importer links it.
+#[derive(Clone, Debug)]
+pub struct PublishedSemantics {
+    /// The package's project in the importer's universe.
+    pub package: ResolvedProjectId,
+    /// The project reference the bundles name the package's own declarations
+    /// by; it was assigned when the package was built.
+    pub origin: StableProjectRef,
+    pub bundles: Vec<Arc<SemanticMetadataBundle>>,
+}
+
+impl PublishedSemantics {
+    /// The importer's identity for a declaration the bundles name: one of the
+    /// package's own, or a builtin one. Declarations of the package's own
+    /// dependencies are not imported.
+    fn declaration(&self, stable: &StableDeclarationRef) -> Option<DeclarationId> {
+        let [name] = &*stable.path else { return None };
+        let components = stable
+            .module
+            .path
+            .iter()
+            .map(|part| ModuleComponent::from_identifier(part).ok())
+            .collect::<Option<Vec<_>>>()?;
+        let path = ModulePath::from_components(components);
+        let module = match &stable.module.project {
+            project if *project == self.origin => ModuleId::resolved(self.package, path),
+            StableProjectRef::Builtin { namespace, .. } => {
+                let builtin = [BuiltinProject::Universe, BuiltinProject::Std, BuiltinProject::Native]
+                    .into_iter()
+                    .find(|builtin| builtin.import_root() == &**namespace)?;
+                ModuleId::builtin(builtin, path)
+            }
+            _ => return None,
+        };
+        Some(DeclarationId::new(module, name.clone()))
+    }
+}
+
+/// Declares every class the packages' bundles record, each extending
+/// `Object`, as the predeclaration phase does for source classes.
+pub fn declare_published(store: &mut TypeStore, declarations: &mut DeclarationTypeTable, hierarchy: &mut MapTypeHierarchy, packages: &[PublishedSemantics]) {
+    let object = DeclarationId::new(ModuleId::core(), "Object".into());
+    for package in packages {
+        for record in package.bundles.iter().flat_map(|bundle| bundle.declarations.iter()) {
+            if record.declaration.module.project != package.origin {
+                continue;
+            }
+            let Some(declaration) = package.declaration(&record.declaration) else {
+                continue;
+            };
+            if declarations.get(&declaration).is_some() {
+                continue;
+            }
+            let form = store.nominal_type(declaration.clone());
+            let class_object_type = store.class_object_type(declaration.clone());
+            hierarchy.insert(declaration.clone(), object.clone());
+            declarations.insert(DeclarationTypeInfo {
+                declaration,
+                form,
+                class_object_type,
+                kind: KindId::TYPE,
+                generic_signature: None,
+                supertype_template: None,
+            });
+        }
+    }
+}
+
+/// Registers the dispatch surface the packages' callable records give each
+/// declaration [`declare_published`] declared.
+pub fn register_published_surfaces(declarations: &DeclarationTypeTable, dispatch: &mut SurfaceDispatchResolver, packages: &[PublishedSemantics]) {
+    for package in packages {
+        let mut surfaces: BTreeMap<DeclarationId, DeclarationSurface> = BTreeMap::new();
+        for bundle in &package.bundles {
+            for record in bundle.callables.iter() {
+                if record.callable.owner.module.project != package.origin {
+                    continue;
+                }
+                let Some(owner) = package.declaration(&record.callable.owner) else { continue };
+                let Ok(selector) = Selector::try_decode_exact(&record.callable.selector) else {
+                    continue;
+                };
+                let slot = |slot: &PublishedTypeSlot| knowledge(package, bundle, declarations, slot);
+                let parameters = record
+                    .parameters
+                    .iter()
+                    .map(|p| {
+                        let parameter = CallableParameter::new(&*p.local_name, slot(&p.ty)).with_rest(p.rest != RestModeRef::None);
+                        match &p.external_label {
+                            Some(label) => parameter.with_label(&**label),
+                            None => parameter,
+                        }
+                    })
+                    .collect();
+                let side = match record.callable.side {
+                    StableDispatchSide::Instance => DispatchSide::Instance,
+                    StableDispatchSide::Class => DispatchSide::Class,
+                };
+                surfaces
+                    .entry(owner.clone())
+                    .or_insert_with(|| DeclarationSurface::new(Some(owner)))
+                    .add_callable(side, CallableSignature::new(selector, parameters, slot(&record.return_type)));
+            }
+        }
+        for (declaration, surface) in surfaces {
+            if let Some(ty) = declarations.form(&declaration) {
+                dispatch.register_type(ty, declaration.clone());
+            }
+            dispatch.register_surface(declaration, surface);
+        }
+    }
+}
+
+/// What a published type slot tells the checker. A form this importer
+/// cannot rebuild is as opaque as a native one.
+fn knowledge(package: &PublishedSemantics, bundle: &SemanticMetadataBundle, declarations: &DeclarationTypeTable, slot: &PublishedTypeSlot) -> TypeKnowledge {
+    let opaque = TypeKnowledge::Unknown(UnknownReason::OpaqueNative);
+    match slot {
+        PublishedTypeSlot::Known { form, authority } => {
+            let Some(TypeNode::Nominal { declaration }) = bundle.types.get(form.0 as usize).map(|entry| &entry.form) else {
+                return opaque;
+            };
+            let Some(ty) = package.declaration(declaration).and_then(|declaration| declarations.form(&declaration)) else {
+                return opaque;
+            };
+            let authority = match authority {
+                PublishedTypeAuthority::DeclaredAnnotation | PublishedTypeAuthority::GeneratedDeclaration => EvidenceAuthority::Declared,
+                PublishedTypeAuthority::TrustedNative => EvidenceAuthority::TrustedNative,
+                PublishedTypeAuthority::CompilerInferred => EvidenceAuthority::Proven,
+            };
+            TypeKnowledge::known(ty, authority)
+        }
+        PublishedTypeSlot::Dynamic { reason } => TypeKnowledge::Dynamic(match reason {
+            DynamicReasonRef::ExplicitEscape => DynamicReason::ExplicitEscape,
+            DynamicReasonRef::UncheckedBoundary | DynamicReasonRef::UnsupportedNative => DynamicReason::RuntimeReflection,
+        }),
+        PublishedTypeSlot::Unknown { reason } => TypeKnowledge::Unknown(match reason {
+            UnknownReasonRef::UnannotatedDeclaration => UnknownReason::UnannotatedDeclaration,
+            UnknownReasonRef::InferenceFailed => UnknownReason::UncheckedExpression,
+            UnknownReasonRef::OpaqueNative => UnknownReason::OpaqueNative,
+        }),
+        PublishedTypeSlot::Unavailable { .. } => opaque,
+    }
+}
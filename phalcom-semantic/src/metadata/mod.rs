@@ -1,6 +1,8 @@
-//! Durable semantic metadata export, reachability, and fingerprinting.
+//! Durable semantic metadata export and import, reachability, and fingerprinting.
 
 pub mod export;
+pub mod import;
 pub mod stable_identity;
 
 pub use export::{MetadataExportError, MetadataExporter};
+pub use import::PublishedSemantics;
@@ -0,0 +1,187 @@
+//! Source-shaped rendering of canonical types.
+//!
+//! Spells a [`TypeId`] the way an annotation would write it (`Name`,
+//! `Origin<A, B>`, `A | B`, `(a, label: T, ...T) -> R`), so a signature shown
+//! to a reader — doc pages, tooling output — reads like the declaration it
+//! came from. The spelling follows `phalcom-type-syntax`'s `Display` impls;
+//! forms with no source syntax (class objects, type lambdas, inference
+//! variables) get a descriptive placeholder instead.
+
+use crate::types::evidence::TypeKnowledge;
+use crate::types::id::TypeId;
+use crate::types::store::{TypeData, TypeStore};
+use std::fmt;
+
+/// Displays one canonical type through the store that owns it.
+#[derive(Clone, Copy)]
+pub struct TypeDisplay<'s> {
+    store: &'s TypeStore,
+    ty: TypeId,
+}
+
+impl<'s> TypeDisplay<'s> {
+    pub fn new(store: &'s TypeStore, ty: TypeId) -> Self {
+        Self { store, ty }
+    }
+
+    fn nested(&self, ty: TypeId) -> Self {
+        Self { store: self.store, ty }
+    }
+
+    /// Whether this type needs parentheses as a union arm or callable return.
+    fn is_compound(&self) -> bool {
+        matches!(self.store.get(self.ty), TypeData::Union(_) | TypeData::Callable(_))
+    }
+}
+
+impl fmt::Display for TypeDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.store.get(self.ty) {
+            TypeData::Never => write!(f, "Never"),
+            TypeData::Unit => write!(f, "Unit"),
+            TypeData::ClassObject { declaration } => write!(f, "{} class", declaration.name),
+            TypeData::Nominal { declaration } | TypeData::Protocol { declaration } => write!(f, "{}", declaration.name),
+            TypeData::Applied { origin, arguments } => {
+                write!(f, "{}<", self.nested(*origin))?;
+                for (i, argument) in arguments.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", self.nested(*argument))?;
+                }
+                write!(f, ">")
+            }
+            TypeData::Union(arms) => {
+                for (i, arm) in arms.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " | ")?;
+                    }
+                    let arm = self.nested(*arm);
+                    if arm.is_compound() { write!(f, "({arm})")? } else { write!(f, "{arm}")? }
+                }
+                Ok(())
+            }
+            TypeData::Tuple(elements) => {
+                write!(f, "(")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    if let Some(label) = &element.label {
+                        write!(f, "{label}: ")?;
+                    }
+                    write!(f, "{}", self.nested(element.ty))?;
+                }
+                write!(f, ")")
+            }
+            TypeData::Record(fields) => {
+                write!(f, "{{")?;
+                for (i, field) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", field.name, self.nested(field.ty))?;
+                }
+                write!(f, "}}")
+            }
+            TypeData::Callable(callable) => {
+                write!(f, "(")?;
+                for (i, parameter) in callable.parameters.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    if parameter.rest {
+                        write!(f, "...")?;
+                    } else if let Some(label) = &parameter.label {
+                        write!(f, "{label}: ")?;
+                    }
+                    write!(f, "{}", self.nested(parameter.ty))?;
+                }
+                let return_type = self.nested(callable.return_type);
+                if return_type.is_compound() {
+                    write!(f, ") -> ({return_type})")
+                } else {
+                    write!(f, ") -> {return_type}")
+                }
+            }
+            TypeData::Parameter(parameter) => write!(f, "{}", self.store.type_parameter(*parameter).name),
+            TypeData::Lambda(_) => write!(f, "<type lambda>"),
+            TypeData::SelfType(_) => write!(f, "Self"),
+            TypeData::Infer(_) => write!(f, "_"),
+        }
+    }
+}
+
+impl TypeStore {
+    /// Returns a [`fmt::Display`] adapter spelling `ty` in annotation syntax.
+    pub fn display(&self, ty: TypeId) -> TypeDisplay<'_> {
+        TypeDisplay::new(self, ty)
+    }
+
+    /// Spells a [`TypeKnowledge`] in annotation syntax: the known type,
+    /// `Dynamic` for an explicit dynamic boundary, or `None` when nothing is
+    /// known (an unannotated declaration has no type to show).
+    pub fn display_knowledge(&self, knowledge: &TypeKnowledge) -> Option<String> {
+        match knowledge {
+            TypeKnowledge::Known(evidence) => Some(self.display(evidence.ty).to_string()),
+            TypeKnowledge::Dynamic(_) => Some("Dynamic".to_string()),
+            TypeKnowledge::Unknown(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::DeclarationId;
+    use crate::types::store::{CallableParameterType, CallableType, TupleTypeElement};
+    use phalcom_modules::identity::ModuleId;
+
+    #[test]
+    fn renders_types_in_annotation_syntax() {
+        let mut store = TypeStore::new();
+        let int = store.nominal(DeclarationId::new(ModuleId::core(), "Int".into()));
+        let string = store.nominal(DeclarationId::new(ModuleId::core(), "String".into()));
+
+        let union = store.union(&[int, string]);
+        let rendered = store.display(union).to_string();
+        assert!(rendered == "Int | String" || rendered == "String | Int", "{rendered}");
+
+        let tuple = store.tuple(
+            vec![
+                TupleTypeElement { label: None, ty: int },
+                TupleTypeElement {
+                    label: Some("name".into()),
+                    ty: string,
+                },
+            ]
+            .into(),
+        );
+        assert_eq!(store.display(tuple).to_string(), "(Int, name: String)");
+
+        let unit = store.unit();
+        let callable = store.callable(CallableType {
+            parameters: vec![
+                CallableParameterType {
+                    label: Some("to".into()),
+                    ty: int,
+                    rest: false,
+                },
+                CallableParameterType {
+                    label: None,
+                    ty: string,
+                    rest: true,
+                },
+            ]
+            .into(),
+            return_type: unit,
+        });
+        assert_eq!(store.display(callable).to_string(), "(to: Int, ...String) -> Unit");
+
+        let returns_union = store.callable(CallableType {
+            parameters: Box::new([]),
+            return_type: union,
+        });
+        assert!(store.display(returns_union).to_string().starts_with("() -> ("));
+    }
+}
@@ -4,6 +4,7 @@ pub mod annotation;
 pub mod application;
 pub mod constraint;
 pub mod denotation;
+pub mod display;
 pub mod environment;
 pub mod evidence;
 pub mod id;
@@ -21,11 +22,12 @@ pub use annotation::{SimpleTypeResolver, TypeFormResolution, TypeResolver, resol
 pub use application::TypeApplicationError;
 pub use constraint::{ConstraintSet, InferVarState, LocalConstraintSolver, TypeConstraint};
 pub use denotation::{SemanticDenotation, ValueSemanticFact};
+pub use display::TypeDisplay;
 pub use environment::{SpecializedCallableView, SpecializedMemberView, TypeEnvironment, TypeView};
 pub use evidence::{DynamicReason, EvidenceAuthority, EvidenceSet, TypeEvidence, TypeKnowledge, UnknownReason};
 pub use id::{InferVarId, KindId, ProperTypeId, ScopedTypeId, TypeId, TypeLambdaId, TypeParameterId, TypeStoreId};
 pub use kind::{KindApplicationError, KindData};
-pub use native::{NativeTypeResolutionError, normalize_native_type, register_standard_surfaces, resolve_native_type_form};
+pub use native::{NativeTypeResolutionError, normalize_native_type, register_extension_surfaces, register_standard_surfaces, resolve_native_type_form};
 pub use outcome::{
     BlockReason, BudgetKind, BudgetReport, CancellationToken, DynamicBoundaryObligation, QueryBudget, RelationEvidence, RelationFailure, RelationOutcome,
 };
@@ -320,3 +320,62 @@ pub fn register_standard_surfaces(
         dispatch.register_surface(decl, surface);
     }
 }
+
+/// Registers the dispatch surface of every linked extension class, derived
+/// from the type contracts its primitives declare.
+///
+/// `Self` in a contract is the extension class's own instance type; a
+/// contract naming a type parameter stays opaque.
+pub fn register_extension_surfaces(store: &mut TypeStore, declarations: &DeclarationTypeTable, dispatch: &mut crate::dispatch::SurfaceDispatchResolver) {
+    use crate::dispatch::{CallableParameter, CallableSignature};
+    use crate::identity::{DispatchSide, ModuleId};
+    use crate::surface::DeclarationSurface;
+    use phalcom_common::selector::Selector;
+    use phalcom_native_meta::extension::{EXTENSION_CLASSES, extension_primitives};
+    use phalcom_native_meta::primitive::NativeDispatch;
+
+    let universe = |key: UniverseKey| DeclarationId::new(ModuleId::core(), key.name().into());
+    let no_parameters = HashMap::new();
+
+    for class in EXTENSION_CLASSES {
+        let decl = crate::declarations::extension_declaration(class);
+        let Some(t_self) = declarations.form(&decl) else {
+            continue;
+        };
+        let knowledge = |store: &mut TypeStore, spec: &TypeExprSpec| match spec {
+            TypeExprSpec::SelfType => TypeKnowledge::known(t_self, EvidenceAuthority::TrustedNative),
+            spec => normalize_native_type(store, declarations, &no_parameters, &universe, spec),
+        };
+
+        let mut surface = DeclarationSurface::new(Some(decl.clone()));
+        dispatch.register_type(t_self, decl.clone());
+        for primitive in extension_primitives(class) {
+            let Ok(selector) = Selector::try_decode_exact(primitive.key.selector) else {
+                continue;
+            };
+            let params = primitive.params;
+            let mut parameters = Vec::new();
+            for (index, spec) in params.positional.iter().enumerate() {
+                parameters.push(CallableParameter::new(format!("arg{index}"), knowledge(store, spec)));
+            }
+            for labeled in params.labeled {
+                parameters.push(CallableParameter::new(labeled.label, knowledge(store, labeled.ty)).with_label(labeled.label));
+            }
+            if let Some(rest) = params.rest {
+                let ty = rest
+                    .ty
+                    .map_or(TypeKnowledge::Dynamic(crate::types::evidence::DynamicReason::ExplicitEscape), |spec| {
+                        knowledge(store, spec)
+                    });
+                parameters.push(CallableParameter::new("rest", ty).with_rest(true));
+            }
+            let side = match primitive.key.side {
+                NativeDispatch::Instance => DispatchSide::Instance,
+                NativeDispatch::Class => DispatchSide::Class,
+            };
+            let returns = knowledge(store, primitive.returns);
+            surface.add_callable(side, CallableSignature::new(selector, parameters, returns));
+        }
+        dispatch.register_surface(decl, surface);
+    }
+}
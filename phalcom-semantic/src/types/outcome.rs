@@ -4,6 +4,7 @@ use crate::diagnostic::DiagnosticCode;
 use crate::identity::{DeclarationId, StableModuleKey};
 use crate::types::evidence::UnknownReason;
 use crate::types::id::TypeId;
+use phalcom_common::selector::Selector;
 use std::fmt;
 
 /// Distinction of budget dimensions.
@@ -164,10 +165,27 @@ pub struct RelationEvidence {
 /// Cause refuting a relation judgment.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum RelationFailure {
-    TypeMismatch { actual: TypeId, expected: TypeId },
-    IncompatibleNominal { actual: DeclarationId, expected: DeclarationId },
-    UnionMemberMismatch { actual: TypeId, expected: TypeId },
-    CycleDetected { sub: TypeId, sup: TypeId },
+    TypeMismatch {
+        actual: TypeId,
+        expected: TypeId,
+    },
+    IncompatibleNominal {
+        actual: DeclarationId,
+        expected: DeclarationId,
+    },
+    UnionMemberMismatch {
+        actual: TypeId,
+        expected: TypeId,
+    },
+    MissingRequirements {
+        actual: DeclarationId,
+        protocol: DeclarationId,
+        missing: Vec<Selector>,
+    },
+    CycleDetected {
+        sub: TypeId,
+        sup: TypeId,
+    },
     DepthExceeded,
     Custom(String),
 }
@@ -6,6 +6,7 @@ use super::store::{TypeData, TypeStore};
 use super::variance::Variance;
 use crate::declarations::GenericSupertypeTemplate;
 use crate::identity::DeclarationId;
+use phalcom_common::selector::Selector;
 use std::collections::HashSet;
 
 /// Environment for querying class hierarchies and module declaration relations.
@@ -20,6 +21,56 @@ pub trait TypeHierarchy {
     fn supertype_template(&self, declaration: &DeclarationId) -> Option<&GenericSupertypeTemplate> {
         None
     }
+
+    /// Returns the mixins a declaration composes directly, in declaration
+    /// order — a class's `@compose(...)` list, a mixin's own mixin
+    /// arguments, or the protocols a `@protocol` includes. Mixins add
+    /// methods, not subtyping: [`Self::is_subclass`] stays nominal.
+    fn mixins(&self, _declaration: &DeclarationId) -> &[DeclarationId] {
+        &[]
+    }
+
+    /// Returns the requirements of `protocol` that instances of `class` do not
+    /// answer, or `None` when this hierarchy cannot see the class surfaces
+    /// that decide it. An empty list means `class` conforms.
+    fn missing_requirements(&self, _class: &DeclarationId, _protocol: &DeclarationId) -> Option<Vec<Selector>> {
+        None
+    }
+
+    /// Returns the method-lookup linearization the runtime's
+    /// `Behavior#ancestors` reports: each class of the superclass chain,
+    /// followed by the mixins it applies itself — depth-first in declaration
+    /// order, each once, skipping any an ancestor already applies.
+    fn ancestors(&self, declaration: &DeclarationId) -> Vec<DeclarationId> {
+        let mut chain = vec![declaration.clone()];
+        while let Some(parent) = self.superclass(chain.last().expect("chain is non-empty")) {
+            if chain.contains(parent) {
+                break;
+            }
+            chain.push(parent.clone());
+        }
+        let mut applied: Vec<DeclarationId> = Vec::new();
+        let mut per_class = Vec::with_capacity(chain.len());
+        for class in chain.iter().rev() {
+            let mut own = Vec::new();
+            let mut pending: Vec<DeclarationId> = self.mixins(class).iter().rev().cloned().collect();
+            while let Some(mixin) = pending.pop() {
+                if applied.contains(&mixin) || own.contains(&mixin) {
+                    continue;
+                }
+                pending.extend(self.mixins(&mixin).iter().rev().cloned());
+                own.push(mixin);
+            }
+            applied.extend(own.iter().cloned());
+            per_class.push(own);
+        }
+        let mut out = Vec::new();
+        for (class, own) in chain.into_iter().zip(per_class.into_iter().rev()) {
+            out.push(class);
+            out.extend(own);
+        }
+        out
+    }
 }
 
 /// A simple hierarchy based on direct parent maps and supertype templates.
@@ -27,6 +78,7 @@ pub trait TypeHierarchy {
 pub struct MapTypeHierarchy {
     pub superclasses: std::collections::HashMap<DeclarationId, DeclarationId>,
     pub templates: std::collections::HashMap<DeclarationId, GenericSupertypeTemplate>,
+    pub mixins: std::collections::HashMap<DeclarationId, Vec<DeclarationId>>,
 }
 
 impl MapTypeHierarchy {
@@ -38,6 +90,10 @@ impl MapTypeHierarchy {
         self.superclasses.insert(class, superclass);
     }
 
+    pub fn insert_mixins(&mut self, class: DeclarationId, mixins: Vec<DeclarationId>) {
+        self.mixins.insert(class, mixins);
+    }
+
     pub fn insert_template(&mut self, template: GenericSupertypeTemplate) {
         self.superclasses.insert(
             template.declaration.clone(),
@@ -56,6 +112,10 @@ impl TypeHierarchy for MapTypeHierarchy {
         self.templates.get(declaration)
     }
 
+    fn mixins(&self, declaration: &DeclarationId) -> &[DeclarationId] {
+        self.mixins.get(declaration).map_or(&[], Vec::as_slice)
+    }
+
     fn is_subclass(&self, sub: &DeclarationId, sup: &DeclarationId) -> bool {
         if sub == sup {
             return true;
@@ -144,6 +204,11 @@ impl From<RelationOutcome> for Assignability {
                     expected,
                     reason: RefutationReason::UnionMemberMismatch,
                 },
+                RelationFailure::MissingRequirements { actual, protocol, missing } => Self::Refuted {
+                    actual: TypeId::DUMMY,
+                    expected: TypeId::DUMMY,
+                    reason: RefutationReason::MissingRequirements { actual, protocol, missing },
+                },
                 _ => Self::Refuted {
                     actual: TypeId::DUMMY,
                     expected: TypeId::DUMMY,
@@ -164,6 +229,12 @@ pub enum RefutationReason {
     IncompatibleNominal,
     TypeMismatch,
     UnionMemberMismatch,
+    /// The actual class lacks these requirements of the expected protocol.
+    MissingRequirements {
+        actual: DeclarationId,
+        protocol: DeclarationId,
+        missing: Vec<Selector>,
+    },
 }
 
 /// Evaluates subtyping with explicit budgets, cancellation, and cycle detection.
@@ -247,6 +318,39 @@ fn check_subtype_impl(
                 RelationOutcome::Refuted(RelationFailure::TypeMismatch { actual: sub, expected: sup })
             }
         }
+        // Protocols are structural: any class answering every requirement
+        // conforms, whatever its superclass chain.
+        (_, TypeData::Protocol { declaration: protocol }) => {
+            let class = match sub_data {
+                TypeData::Nominal { declaration } | TypeData::Protocol { declaration } => Some(declaration),
+                TypeData::Applied { origin, .. } => match store.get(*origin) {
+                    TypeData::Nominal { declaration } => Some(declaration),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match class.map(|class| (class, hierarchy.missing_requirements(class, protocol))) {
+                Some((_, Some(missing))) if missing.is_empty() => RelationOutcome::proven(()),
+                Some((class, Some(missing))) => RelationOutcome::Refuted(RelationFailure::MissingRequirements {
+                    actual: class.clone(),
+                    protocol: protocol.clone(),
+                    missing,
+                }),
+                Some((_, None)) => RelationOutcome::Blocked(BlockReason::OpaqueNative("protocol conformance needs class surfaces".into())),
+                None => RelationOutcome::Refuted(RelationFailure::TypeMismatch { actual: sub, expected: sup }),
+            }
+        }
+        // A protocol value is still an object of some class under `Object`.
+        (TypeData::Protocol { declaration: sub_decl }, TypeData::Nominal { declaration: sup_decl }) => {
+            if hierarchy.is_subclass(sub_decl, sup_decl) {
+                RelationOutcome::proven(())
+            } else {
+                RelationOutcome::Refuted(RelationFailure::IncompatibleNominal {
+                    actual: sub_decl.clone(),
+                    expected: sup_decl.clone(),
+                })
+            }
+        }
         (TypeData::ClassObject { declaration: sub_decl }, TypeData::ClassObject { declaration: sup_decl }) => {
             if hierarchy.is_subclass(sub_decl, sup_decl) {
                 RelationOutcome::proven(())
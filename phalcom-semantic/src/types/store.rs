@@ -48,6 +48,9 @@ pub enum TypeData {
     ClassObject { declaration: DeclarationId },
     /// Canonical nominal class declaration type or constructor form.
     Nominal { declaration: DeclarationId },
+    /// Structural type of a `@protocol` declaration, satisfied by any class
+    /// whose method surface answers every requirement.
+    Protocol { declaration: DeclarationId },
     /// Generic type application (e.g. `List<Int>`).
     Applied { origin: TypeId, arguments: Box<[TypeId]> },
     /// Flat, deduplicated, sorted union of two or more distinct types.
@@ -323,6 +326,10 @@ impl TypeStore {
         self.intern_with_kind(TypeData::ClassObject { declaration }, KindId::TYPE)
     }
 
+    pub fn protocol_type(&mut self, declaration: DeclarationId) -> TypeId {
+        self.intern_with_kind(TypeData::Protocol { declaration }, KindId::TYPE)
+    }
+
     /// Legacy compatibility helper for tests / callers expecting nominal type.
     pub fn nominal(&mut self, declaration: DeclarationId) -> TypeId {
         self.nominal_type(declaration)
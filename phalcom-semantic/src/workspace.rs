@@ -1,21 +1,25 @@
 //! Semantic workspace analysis engine for whole linked programs.
 
 use crate::checker::context::CheckingContext;
-use crate::checker::declaration::{check_class_bodies, register_class_surface};
+use crate::checker::declaration::{check_class_bodies, extension_surface, is_sealed, register_class_surface, sealed_variant_defs};
 use crate::checker::statement::check_statement;
-use crate::declarations::{DeclarationTypeInfo, bootstrap_universe_declarations};
+use crate::declarations::{
+    DeclarationTypeInfo, SealedHierarchy, SealedVariant, bootstrap_extension_declarations, bootstrap_universe_declarations, extension_declaration,
+};
 use crate::diagnostic::{DiagnosticCode, SemanticDiagnostic};
 use crate::dispatch::SurfaceDispatchResolver;
 use crate::identity::{DeclarationId, ModuleId};
+use crate::metadata::import::{PublishedSemantics, declare_published, register_published_surfaces};
 use crate::resolver::LinkedTypeResolver;
 use crate::snapshot::SemanticSnapshot;
 use crate::source::ParsedModuleUnit;
+use crate::surface::DeclarationSurface;
 use crate::types::annotation::TypeResolver;
 use crate::types::id::KindId;
-use crate::types::native::register_standard_surfaces;
-use crate::types::relation::MapTypeHierarchy;
+use crate::types::native::{register_extension_surfaces, register_standard_surfaces};
+use crate::types::relation::{MapTypeHierarchy, TypeHierarchy};
 use crate::types::store::TypeStore;
-use phalcom_ast::ast::{Program, Statement};
+use phalcom_ast::ast::{AttrKind, BuiltinAttr, Expr, Program, Statement};
 use phalcom_modules::declaration::{DeclarationBlueprint, DeclarationKind, DeclarationRealizationError, DeclarationShellTable};
 use phalcom_modules::graph::{SemanticEdge, SemanticEdgeKind, SemanticNodeId};
 use phalcom_modules::interface::InterfaceBuilder;
@@ -41,11 +45,19 @@ pub struct SemanticAnalysis {
 
 /// Analyzes an entire linked workspace and returns an immutable semantic snapshot for this generation.
 pub fn analyze_workspace(input: SemanticWorkspaceInput) -> SemanticAnalysis {
+    analyze_workspace_with_packages(input, &[])
+}
+
+/// Analyzes a linked workspace whose modules import published packages.
+/// Their modules have no sources in `input`; their classes and surfaces come
+/// from the packages' semantic metadata.
+pub fn analyze_workspace_with_packages(input: SemanticWorkspaceInput, packages: &[PublishedSemantics]) -> SemanticAnalysis {
     // -------------------------------------------------------------------------
     // Phase A: Universe Bootstrap
     // -------------------------------------------------------------------------
     let mut store = TypeStore::new();
     let mut declarations = bootstrap_universe_declarations(&mut store, &|key| DeclarationId::new(ModuleId::core(), key.name().into()));
+    bootstrap_extension_declarations(&mut store, &mut declarations);
 
     let mut hierarchy = MapTypeHierarchy::new();
     for (sub_name, super_name) in [
@@ -71,6 +83,13 @@ pub fn analyze_workspace(input: SemanticWorkspaceInput) -> SemanticAnalysis {
             DeclarationId::new(ModuleId::core(), super_name.into()),
         );
     }
+    for class in phalcom_native_meta::EXTENSION_CLASSES {
+        hierarchy.insert(
+            extension_declaration(class),
+            DeclarationId::new(ModuleId::core(), class.superclass.name().into()),
+        );
+    }
+    declare_published(&mut store, &mut declarations, &mut hierarchy, packages);
 
     // -------------------------------------------------------------------------
     // Phase B: Predeclare Every Source Declaration
@@ -93,7 +112,17 @@ pub fn analyze_workspace(input: SemanticWorkspaceInput) -> SemanticAnalysis {
                     kind: DeclarationKind::Class,
                 });
                 if declarations.get(&decl_id).is_none() {
-                    let form = store.nominal_type(decl_id.clone());
+                    // A `@protocol` names a structural type, not a class to
+                    // inherit from.
+                    let is_protocol = class_def
+                        .attributes
+                        .iter()
+                        .any(|attr| matches!(attr.kind, AttrKind::Builtin(BuiltinAttr::Protocol)));
+                    let form = if is_protocol {
+                        store.protocol_type(decl_id.clone())
+                    } else {
+                        store.nominal_type(decl_id.clone())
+                    };
                     let class_obj_type = store.class_object_type(decl_id.clone());
                     declarations.insert(DeclarationTypeInfo {
                         declaration: decl_id,
@@ -104,6 +133,27 @@ pub fn analyze_workspace(input: SemanticWorkspaceInput) -> SemanticAnalysis {
                         supertype_template: None,
                     });
                 }
+                // Each `@variant` arm of a `@sealed` class is a sibling class
+                // of its own, generated beside the enclosing one.
+                for variant in sealed_variant_defs(class_def) {
+                    let variant_id = DeclarationId::new(module_id.clone(), variant.name.clone().into());
+                    initial_blueprints.push(DeclarationBlueprint {
+                        id: variant_id.clone(),
+                        kind: DeclarationKind::Class,
+                    });
+                    if declarations.get(&variant_id).is_none() {
+                        let form = store.nominal_type(variant_id.clone());
+                        let class_obj_type = store.class_object_type(variant_id.clone());
+                        declarations.insert(DeclarationTypeInfo {
+                            declaration: variant_id,
+                            form,
+                            class_object_type: class_obj_type,
+                            kind: KindId::TYPE,
+                            generic_signature: None,
+                            supertype_template: None,
+                        });
+                    }
+                }
             }
         }
     }
@@ -192,7 +242,7 @@ pub fn analyze_workspace(input: SemanticWorkspaceInput) -> SemanticAnalysis {
                 if let Some(ref super_ref) = class_def.superclass {
                     let members: Vec<String> = super_ref.members.iter().map(|m| m.name.clone()).collect();
                     if let Some(super_decl) = resolver.resolve_type_name(module_id, &super_ref.root, &members) {
-                        hierarchy.insert(class_decl, super_decl);
+                        hierarchy.insert(class_decl.clone(), super_decl);
                     } else {
                         diags_by_module.entry(module_id.clone()).or_default().push(SemanticDiagnostic::error(
                             DiagnosticCode::AnnotationUnresolved,
@@ -203,11 +253,66 @@ pub fn analyze_workspace(input: SemanticWorkspaceInput) -> SemanticAnalysis {
                 } else {
                     let obj_decl = DeclarationId::new(ModuleId::core(), "Object".into());
                     if class_decl != obj_decl {
-                        hierarchy.insert(class_decl, obj_decl);
+                        hierarchy.insert(class_decl.clone(), obj_decl);
+                    }
+                }
+                let mut mixins = Vec::new();
+                for attr in &class_def.attributes {
+                    if !matches!(attr.kind, AttrKind::Builtin(BuiltinAttr::Mixin | BuiltinAttr::Compose | BuiltinAttr::Protocol)) {
+                        continue;
                     }
+                    // `@mixin` also takes requirement symbols; only the name
+                    // arguments are composed mixins or included protocols.
+                    for (root, members, range) in attr.args.iter().filter_map(static_name_path) {
+                        if let Some(mixin_decl) = resolver.resolve_type_name(module_id, &root, &members) {
+                            mixins.push(mixin_decl);
+                        } else {
+                            diags_by_module.entry(module_id.clone()).or_default().push(SemanticDiagnostic::error(
+                                DiagnosticCode::AnnotationUnresolved,
+                                format!("unresolved mixin or protocol `{root}`"),
+                                range,
+                            ));
+                        }
+                    }
+                }
+                for variant in sealed_variant_defs(class_def) {
+                    hierarchy.insert(DeclarationId::new(module_id.clone(), variant.name.clone().into()), class_decl.clone());
+                }
+                if !mixins.is_empty() {
+                    hierarchy.insert_mixins(class_decl, mixins);
                 }
             }
         }
+
+        // A `@sealed` class only admits subclasses from its own module, so
+        // its variants and the module's `is` subclasses close the hierarchy.
+        for stmt in &parsed_unit.program.statements {
+            let Statement::Class(class_def) = stmt else { continue };
+            if !is_sealed(class_def) {
+                continue;
+            }
+            let sealed_decl = DeclarationId::new(module_id.clone(), class_def.name.clone().into());
+            let mut variants: Vec<SealedVariant> = sealed_variant_defs(class_def)
+                .map(|variant| SealedVariant {
+                    declaration: DeclarationId::new(module_id.clone(), variant.name.clone().into()),
+                    labels: Some(variant.labels.clone()),
+                })
+                .collect();
+            for stmt in &parsed_unit.program.statements {
+                let Statement::Class(subclass) = stmt else { continue };
+                let subclass_decl = DeclarationId::new(module_id.clone(), subclass.name.clone().into());
+                if subclass.superclass.is_some() && hierarchy.superclass(&subclass_decl) == Some(&sealed_decl) {
+                    variants.push(SealedVariant {
+                        declaration: subclass_decl,
+                        labels: None,
+                    });
+                }
+            }
+            declarations.insert_sealed(SealedHierarchy {
+                declaration: sealed_decl,
+                variants,
+            });
+        }
     }
 
     // -------------------------------------------------------------------------
@@ -215,13 +320,27 @@ pub fn analyze_workspace(input: SemanticWorkspaceInput) -> SemanticAnalysis {
     // -------------------------------------------------------------------------
     let mut dispatch = SurfaceDispatchResolver::new();
     register_standard_surfaces(&mut store, &declarations, &resolver, &ModuleId::core(), &mut dispatch);
+    register_extension_surfaces(&mut store, &declarations, &mut dispatch);
+    register_published_surfaces(&declarations, &mut dispatch, packages);
 
+    // `extend` surfaces stay per declaring module: each module sees only its
+    // own and those of the modules it imports directly.
+    let mut extend_surfaces: BTreeMap<ModuleId, Vec<(DeclarationId, DeclarationSurface)>> = BTreeMap::new();
     for (module_id, parsed_unit) in &input.sources {
         let mut dummy_ctx = CheckingContext::new(&mut store, &hierarchy, &resolver, &declarations, module_id.clone());
 
         for stmt in &parsed_unit.program.statements {
-            if let Statement::Class(class_def) = stmt {
-                register_class_surface(&mut dummy_ctx, class_def);
+            match stmt {
+                Statement::Class(class_def) => register_class_surface(&mut dummy_ctx, class_def),
+                Statement::Extend(extend_def) => {
+                    let reported = dummy_ctx.diagnostics.len();
+                    if let Some(extension) = extension_surface(&mut dummy_ctx, extend_def) {
+                        extend_surfaces.entry(module_id.clone()).or_default().push(extension);
+                    }
+                    let unresolved = dummy_ctx.diagnostics.split_off(reported);
+                    diags_by_module.entry(module_id.clone()).or_default().extend(unresolved);
+                }
+                _ => {}
             }
         }
 
@@ -239,6 +358,17 @@ pub fn analyze_workspace(input: SemanticWorkspaceInput) -> SemanticAnalysis {
     for (module_id, parsed_unit) in &input.sources {
         let mut ctx = CheckingContext::new(&mut store, &hierarchy, &resolver, &declarations, module_id.clone());
         ctx.dispatch = dispatch.clone();
+        let sources = input
+            .linked
+            .modules
+            .get(module_id)
+            .map(|linked| linked.extension_sources.as_slice())
+            .unwrap_or_default();
+        for source in std::iter::once(module_id).chain(sources) {
+            for (decl_id, surface) in extend_surfaces.get(source).into_iter().flatten() {
+                ctx.dispatch.register_extension(decl_id.clone(), surface);
+            }
+        }
 
         for stmt in &parsed_unit.program.statements {
             match stmt {
@@ -279,6 +409,21 @@ pub fn analyze_workspace(input: SemanticWorkspaceInput) -> SemanticAnalysis {
     SemanticAnalysis { snapshot }
 }
 
+/// Reads a class-attribute argument written as a static name — `Mixin` or
+/// `alias.Mixin` — into the root, member path and span that
+/// [`TypeResolver::resolve_type_name`] takes.
+fn static_name_path(expr: &Expr) -> Option<(String, Vec<String>, phalcom_common::range::SourceRange)> {
+    match expr {
+        Expr::Var { value, range } => Some((value.clone(), Vec::new(), *range)),
+        Expr::GetProperty(get) => {
+            let (root, mut members, _) = static_name_path(&get.object)?;
+            members.push(get.property.clone());
+            Some((root, members, get.range))
+        }
+        _ => None,
+    }
+}
+
 /// Convenience helper to analyze a single module as a standalone workspace.
 pub fn analyze_single_module(module: ModuleId, source: Arc<str>, program: Arc<Program>) -> SemanticAnalysis {
     let _ = InterfaceBuilder::build(module.clone(), ModuleKind::Module, &program);
@@ -292,6 +437,7 @@ pub fn analyze_single_module(module: ModuleId, source: Arc<str>, program: Arc<Pr
         bindings: phalcom_modules::linker::ModuleBindingLayout::default(),
         linked_reads: Vec::new(),
         runtime_dependencies: Vec::new(),
+        extension_sources: Vec::new(),
     };
 
     let mut modules = BTreeMap::new();
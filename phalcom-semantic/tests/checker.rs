@@ -88,3 +88,14 @@ fn unannotated_dynamic_code_has_no_false_errors() {
     let report = check_program(&mut store, &hier, &resolver, &decls, module, &program);
     assert!(!report.has_errors(), "dynamic unannotated code should have no errors");
 }
+
+#[test]
+fn extension_member_types_sends_in_its_module() {
+    let (mut store, hier, resolver, decls, module) = setup_test_env();
+    let source = "extend String {\n  shout() -> Int { 1 }\n}\nconst loud: String = \"a\".shout()\n";
+    let program = parse_source(source, 0).expect("valid parse");
+
+    let report = check_program(&mut store, &hier, &resolver, &decls, module, &program);
+    assert!(report.has_errors(), "expected the extension's return type to reach the binding");
+    assert_eq!(report.diagnostics[0].code, DiagnosticCode::BindingInitializerMismatch);
+}
@@ -197,3 +197,52 @@ class SubFactory is BaseFactory {
         report.diagnostics
     );
 }
+
+#[test]
+fn instance_receiver_dispatches_through_composed_mixin() {
+    let (mut store, mut hier, mut resolver, mut decls, module) = setup_class_dispatch_env();
+
+    let greeter_decl = DeclarationId::new(module.clone(), "Greeter".into());
+    let host_decl = DeclarationId::new(module.clone(), "Host".into());
+    let obj_decl = DeclarationId::new(module.clone(), "Object".into());
+
+    register_nominal_declaration(&mut store, &mut decls, greeter_decl.clone());
+    register_nominal_declaration(&mut store, &mut decls, host_decl.clone());
+
+    hier.insert(greeter_decl.clone(), obj_decl.clone());
+    hier.insert(host_decl.clone(), obj_decl);
+    hier.insert_mixins(host_decl.clone(), vec![greeter_decl.clone()]);
+
+    resolver.insert("Greeter", greeter_decl);
+    resolver.insert("Host", host_decl);
+
+    let source = r#"
+@mixin
+class Greeter {
+  greet -> String {
+    "hello"
+  }
+}
+
+@compose(Greeter)
+class Host {
+  shout -> Int {
+    super.greet
+  }
+}
+
+class Main {
+  test(h: Host) -> Int {
+    h.greet
+  }
+}
+"#;
+    let program = parse_source(source, 0).expect("valid parse");
+    let report = check_program(&mut store, &hier, &resolver, &decls, module, &program);
+    assert_eq!(
+        report.diagnostics.len(),
+        2,
+        "expected the plain and `super` sends to resolve the composed `greet` and mismatch `Int`, got: {:?}",
+        report.diagnostics
+    );
+}
@@ -101,6 +101,7 @@ fn workspace_multi_module_linking_resolution_and_cycles() {
             },
             linked_reads: Vec::new(),
             runtime_dependencies: Vec::new(),
+            extension_sources: Vec::new(),
         },
     );
 
@@ -136,6 +137,7 @@ fn workspace_multi_module_linking_resolution_and_cycles() {
                 name: "Point".into(),
             })],
             runtime_dependencies: vec![point_mod.clone()],
+            extension_sources: Vec::new(),
         },
     );
 
@@ -222,6 +224,7 @@ fn inheritance_cycle_is_rejected_in_workspace() {
                 name: "B".into(),
             })],
             runtime_dependencies: vec![mod_b.clone()],
+            extension_sources: Vec::new(),
         },
     );
 
@@ -253,6 +256,7 @@ fn inheritance_cycle_is_rejected_in_workspace() {
                 name: "A".into(),
             })],
             runtime_dependencies: vec![mod_a.clone()],
+            extension_sources: Vec::new(),
         },
     );
 
@@ -313,6 +317,7 @@ fn same_leaf_name_in_two_modules_stays_distinct() {
             bindings: ModuleBindingLayout::default(),
             linked_reads: Vec::new(),
             runtime_dependencies: Vec::new(),
+            extension_sources: Vec::new(),
         },
     );
     modules.insert(
@@ -327,6 +332,7 @@ fn same_leaf_name_in_two_modules_stays_distinct() {
             bindings: ModuleBindingLayout::default(),
             linked_reads: Vec::new(),
             runtime_dependencies: Vec::new(),
+            extension_sources: Vec::new(),
         },
     );
 
@@ -449,6 +455,7 @@ fn deterministic_fresh_store_analysis_matches_structurally() {
                 },
                 linked_reads: Vec::new(),
                 runtime_dependencies: Vec::new(),
+                extension_sources: Vec::new(),
             },
         );
 
@@ -480,6 +487,7 @@ fn deterministic_fresh_store_analysis_matches_structurally() {
                     name: "Base".into(),
                 })],
                 runtime_dependencies: vec![mod_a.clone()],
+                extension_sources: Vec::new(),
             },
         );
 
@@ -525,3 +533,143 @@ fn deterministic_fresh_store_analysis_matches_structurally() {
         assert_eq!(kind1, kind2);
     }
 }
+
+fn analyze_protocol_source(source: &str) -> Vec<phalcom_semantic::diagnostic::SemanticDiagnostic> {
+    let source: Arc<str> = Arc::from(source);
+    let program = Arc::new(phalcom_ast::parse(&source, 0).program);
+    let analysis = analyze_single_module(ModuleId::core(), source, program);
+    analysis.snapshot.all_diagnostics().cloned().collect()
+}
+
+#[test]
+fn protocol_annotation_accepts_structurally_conforming_class() {
+    let diagnostics = analyze_protocol_source(
+        r#"
+@protocol
+class Sized {
+  size -> Int {}
+}
+
+class Bag {
+  size -> Int { 3 }
+}
+
+class Main {
+  make(b: Bag) -> Sized { b }
+  measure(s: Sized) -> Int { s.size }
+}
+"#,
+    );
+    assert!(diagnostics.is_empty(), "a class answering every requirement conforms, got: {diagnostics:?}");
+}
+
+#[test]
+fn protocol_annotation_names_missing_requirements() {
+    let diagnostics = analyze_protocol_source(
+        r#"
+@protocol
+class Sized {
+  size -> Int {}
+}
+
+@protocol(Sized)
+class Container {
+  contains(item) -> Bool {}
+}
+
+class Point {
+  x -> Int { 1 }
+}
+
+class Main {
+  make(p: Point) -> Container { p }
+  label(c: Container) -> String { c.size }
+}
+"#,
+    );
+    let missing: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.code == phalcom_semantic::diagnostic::DiagnosticCode::ProtocolRequirementMissing)
+        .collect();
+    assert_eq!(missing.len(), 1, "expected one conformance diagnostic, got: {diagnostics:?}");
+    assert_eq!(
+        missing[0].message,
+        "`Point` does not conform to protocol `Container`: missing `contains(item)`, `size`"
+    );
+    // `c.size` resolves through the included `Sized` requirement.
+    assert!(
+        diagnostics
+            .iter()
+            .any(|d| d.code == phalcom_semantic::diagnostic::DiagnosticCode::ReturnMismatch && d.primary_range != missing[0].primary_range),
+        "expected the included `size -> Int` requirement to mismatch `String`, got: {diagnostics:?}"
+    );
+}
+
+#[test]
+fn sealed_type_tests_narrow_to_the_remaining_variants() {
+    let diagnostics = analyze_protocol_source(
+        r#"
+@sealed
+@data
+class Shape {
+  @variant Circle(radius:)
+  @variant Rect(w:, h:)
+}
+
+class Main {
+  circle(s: Shape) -> Circle {
+    if s is Circle { return s }
+    Circle.new(radius: 1)
+  }
+  rect(s: Shape) -> Rect {
+    if s is Circle { return Rect.new(w: 1, h: 1) } else { return s }
+  }
+  destructured(s: Shape) -> Rect {
+    if let Circle(r) = s { return Rect.new(w: r, h: r) } else { return s }
+  }
+  wrong(s: Shape) -> Rect {
+    if s is not Rect { return s }
+    Rect.new(w: 1, h: 1)
+  }
+}
+"#,
+    );
+    let mismatches: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.code == phalcom_semantic::diagnostic::DiagnosticCode::ReturnMismatch)
+        .collect();
+    assert_eq!(mismatches.len(), 1, "only `wrong`'s narrowed `Circle` should mismatch, got: {diagnostics:?}");
+}
+
+#[test]
+fn sealed_match_warns_on_missing_and_superfluous_arms() {
+    let diagnostics = analyze_protocol_source(
+        r#"
+@sealed
+@data
+class Shape {
+  @variant Circle(radius:)
+  @variant Rect(w:, h:)
+}
+
+class Main {
+  full(s: Shape) { s.match(circle: |c| { 1 }, rect: |r| { 2 }) }
+  partial(s: Shape) { s.match(circle: |c| { 1 }, square: |q| { 2 }) }
+}
+"#,
+    );
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.code, d.message.as_str())).collect();
+    assert_eq!(
+        messages,
+        vec![
+            (
+                phalcom_semantic::diagnostic::DiagnosticCode::MatchMissingVariant,
+                "`match` on sealed `Shape` has no arm for `Rect` (`rect:`)"
+            ),
+            (
+                phalcom_semantic::diagnostic::DiagnosticCode::MatchSuperfluousArm,
+                "`match` arm `square:` names no variant of sealed `Shape`"
+            ),
+        ]
+    );
+}
@@ -0,0 +1,862 @@
+//! Compact, versioned binary container for [`SemanticMetadataBundle`].
+//!
+//! ```text
+//! prelude  magic "PHMB" | format version | schema version | semantic model version   (u32 LE)
+//! section  tag (u8) | payload length (u32 LE) | payload checksum (16 bytes) | payload
+//! trailer  checksum of every preceding byte (16 bytes)
+//! ```
+//!
+//! Every section appears exactly once, in tag order: the string table, the
+//! header, the kind, type and scoped-type node tables, and the remaining
+//! records. Payloads are the bundle's serde shape written positionally:
+//! integers as LEB128 varints, enums as variant indices, sequences behind a
+//! length, and every string as an index into the string table, which lists
+//! each distinct string once in first-use order. Checksums are
+//! [`Fingerprint128`]s. Nothing in the encoding depends on hashing or
+//! allocation order, so one bundle always encodes to the same bytes.
+
+use crate::bundle::{MetadataExtensionSection, ModuleMetadataRoot, RuntimeTypeFormRoot, SemanticMetadataBundle, TypeUseRecord};
+use crate::declaration::{CallableSemanticRecord, DeclarationTypeRecord, FieldSemanticRecord, TypeAliasRecord};
+use crate::encode::MetadataDecodeError;
+use crate::fingerprint::{Fingerprint128, FingerprintBuilder};
+use crate::generic::{GenericSignatureRecord, TypeParameterRecord};
+use crate::header::{BINARY_METADATA_FORMAT_VERSION, BINARY_METADATA_MAGIC, SEMANTIC_MODEL_VERSION, SemanticMetadataHeader, TYPE_METADATA_SCHEMA_VERSION};
+use crate::kind::KindNodeEntry;
+use crate::scoped_type::ScopedTypeNodeEntry;
+use crate::type_node::TypeNodeEntry;
+use crate::validate::MetadataValidationError;
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::ser::{self, Serialize};
+use serde::{Deserialize, Deserializer, Serializer};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+const SECTION_STRINGS: u8 = 1;
+const SECTION_HEADER: u8 = 2;
+const SECTION_KINDS: u8 = 3;
+const SECTION_TYPES: u8 = 4;
+const SECTION_SCOPED_TYPES: u8 = 5;
+const SECTION_RECORDS: u8 = 6;
+
+const SECTION_NAMES: [(u8, &str); 6] = [
+    (SECTION_STRINGS, "strings"),
+    (SECTION_HEADER, "header"),
+    (SECTION_KINDS, "kinds"),
+    (SECTION_TYPES, "types"),
+    (SECTION_SCOPED_TYPES, "scoped types"),
+    (SECTION_RECORDS, "records"),
+];
+
+const PRELUDE_LEN: usize = 16;
+const CHECKSUM_LEN: usize = 16;
+
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum BinaryMetadataError {
+    #[error("not a binary metadata bundle")]
+    BadMagic,
+    #[error("unsupported binary metadata format version: {0} (expected {BINARY_METADATA_FORMAT_VERSION})")]
+    UnsupportedFormatVersion(u32),
+    #[error("truncated binary metadata")]
+    Truncated,
+    #[error("checksum mismatch in {0}")]
+    ChecksumMismatch(&'static str),
+    #[error("expected the {expected} section, found tag {found}")]
+    UnexpectedSection { expected: &'static str, found: u8 },
+    #[error("{count} trailing byte(s) after the {section}")]
+    TrailingBytes { section: &'static str, count: usize },
+    #[error("string index {index} out of range ({total} strings)")]
+    InvalidString { index: usize, total: usize },
+    #[error("{0} section exceeds 4 GiB")]
+    SectionTooLarge(&'static str),
+    #[error("{0}")]
+    Custom(String),
+}
+
+impl ser::Error for BinaryMetadataError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+impl de::Error for BinaryMetadataError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+/// Bundle tables after the node graphs, in bundle field order.
+#[derive(serde::Serialize)]
+struct RecordsRef<'a> {
+    parameters: &'a [TypeParameterRecord],
+    generic_signatures: &'a [GenericSignatureRecord],
+    declarations: &'a [DeclarationTypeRecord],
+    aliases: &'a [TypeAliasRecord],
+    callables: &'a [CallableSemanticRecord],
+    fields: &'a [FieldSemanticRecord],
+    module_roots: &'a [ModuleMetadataRoot],
+    runtime_roots: &'a [RuntimeTypeFormRoot],
+    occurrences: &'a [TypeUseRecord],
+    extensions: &'a [MetadataExtensionSection],
+}
+
+#[derive(serde::Deserialize)]
+struct Records {
+    parameters: Box<[TypeParameterRecord]>,
+    generic_signatures: Box<[GenericSignatureRecord]>,
+    declarations: Box<[DeclarationTypeRecord]>,
+    aliases: Box<[TypeAliasRecord]>,
+    callables: Box<[CallableSemanticRecord]>,
+    fields: Box<[FieldSemanticRecord]>,
+    module_roots: Box<[ModuleMetadataRoot]>,
+    runtime_roots: Box<[RuntimeTypeFormRoot]>,
+    occurrences: Box<[TypeUseRecord]>,
+    extensions: Box<[MetadataExtensionSection]>,
+}
+
+pub(crate) fn write_bundle(bundle: &SemanticMetadataBundle) -> Result<Vec<u8>, BinaryMetadataError> {
+    let mut strings = StringTable::default();
+    let records = RecordsRef {
+        parameters: &bundle.parameters,
+        generic_signatures: &bundle.generic_signatures,
+        declarations: &bundle.declarations,
+        aliases: &bundle.aliases,
+        callables: &bundle.callables,
+        fields: &bundle.fields,
+        module_roots: &bundle.module_roots,
+        runtime_roots: &bundle.runtime_roots,
+        occurrences: &bundle.occurrences,
+        extensions: &bundle.extensions,
+    };
+    let sections = [
+        (SECTION_HEADER, strings.write(&bundle.header)?),
+        (SECTION_KINDS, strings.write(&bundle.kinds)?),
+        (SECTION_TYPES, strings.write(&bundle.types)?),
+        (SECTION_SCOPED_TYPES, strings.write(&bundle.scoped_types)?),
+        (SECTION_RECORDS, strings.write(&records)?),
+    ];
+
+    let mut out = Vec::new();
+    out.extend_from_slice(&BINARY_METADATA_MAGIC);
+    for word in [BINARY_METADATA_FORMAT_VERSION, TYPE_METADATA_SCHEMA_VERSION, SEMANTIC_MODEL_VERSION] {
+        out.extend_from_slice(&word.to_le_bytes());
+    }
+    for (tag, payload) in std::iter::once((SECTION_STRINGS, strings.encode())).chain(sections) {
+        let length = u32::try_from(payload.len()).map_err(|_| BinaryMetadataError::SectionTooLarge(section_name(tag)))?;
+        out.push(tag);
+        out.extend_from_slice(&length.to_le_bytes());
+        out.extend_from_slice(checksum(&payload).as_bytes());
+        out.extend_from_slice(&payload);
+    }
+    let trailer = checksum(&out);
+    out.extend_from_slice(trailer.as_bytes());
+    Ok(out)
+}
+
+/// Decodes a bundle without validating it; the caller runs
+/// `validate_metadata_bundle`.
+pub(crate) fn read_bundle(bytes: &[u8]) -> Result<SemanticMetadataBundle, MetadataDecodeError> {
+    if bytes.len() < PRELUDE_LEN + CHECKSUM_LEN {
+        return Err(BinaryMetadataError::Truncated.into());
+    }
+    if bytes[..4] != BINARY_METADATA_MAGIC {
+        return Err(BinaryMetadataError::BadMagic.into());
+    }
+    let (body, trailer) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+    if checksum(body).as_bytes() != trailer {
+        return Err(BinaryMetadataError::ChecksumMismatch("bundle").into());
+    }
+    let word = |at: usize| u32::from_le_bytes(body[at..at + 4].try_into().expect("four bytes"));
+    if word(4) != BINARY_METADATA_FORMAT_VERSION {
+        return Err(BinaryMetadataError::UnsupportedFormatVersion(word(4)).into());
+    }
+    // The positional layout is only meaningful for the schema that wrote it.
+    if word(8) != TYPE_METADATA_SCHEMA_VERSION {
+        return Err(MetadataValidationError::UnsupportedSchemaVersion(word(8)).into());
+    }
+    if word(12) != SEMANTIC_MODEL_VERSION {
+        return Err(MetadataValidationError::UnsupportedSemanticModelVersion(word(12)).into());
+    }
+
+    let mut rest = &body[PRELUDE_LEN..];
+    let mut payloads = Vec::with_capacity(SECTION_NAMES.len());
+    for (tag, name) in SECTION_NAMES {
+        let (&found, tail) = rest.split_first().ok_or(BinaryMetadataError::Truncated)?;
+        if found != tag {
+            return Err(BinaryMetadataError::UnexpectedSection { expected: name, found }.into());
+        }
+        let (length, tail) = split(tail, 4)?;
+        let length = u32::from_le_bytes(length.try_into().expect("four bytes")) as usize;
+        let (sum, tail) = split(tail, CHECKSUM_LEN)?;
+        let (payload, tail) = split(tail, length)?;
+        if checksum(payload).as_bytes() != sum {
+            return Err(BinaryMetadataError::ChecksumMismatch(name).into());
+        }
+        payloads.push(payload);
+        rest = tail;
+    }
+    if !rest.is_empty() {
+        return Err(BinaryMetadataError::TrailingBytes {
+            section: "records",
+            count: rest.len(),
+        }
+        .into());
+    }
+
+    let strings = read_strings(payloads[0])?;
+    let header: SemanticMetadataHeader = read_section(payloads[1], &strings, SECTION_HEADER)?;
+    let kinds: Box<[KindNodeEntry]> = read_section(payloads[2], &strings, SECTION_KINDS)?;
+    let types: Box<[TypeNodeEntry]> = read_section(payloads[3], &strings, SECTION_TYPES)?;
+    let scoped_types: Box<[ScopedTypeNodeEntry]> = read_section(payloads[4], &strings, SECTION_SCOPED_TYPES)?;
+    let records: Records = read_section(payloads[5], &strings, SECTION_RECORDS)?;
+    Ok(SemanticMetadataBundle {
+        header,
+        kinds,
+        types,
+        scoped_types,
+        parameters: records.parameters,
+        generic_signatures: records.generic_signatures,
+        declarations: records.declarations,
+        aliases: records.aliases,
+        callables: records.callables,
+        fields: records.fields,
+        module_roots: records.module_roots,
+        runtime_roots: records.runtime_roots,
+        occurrences: records.occurrences,
+        extensions: records.extensions,
+    })
+}
+
+fn section_name(tag: u8) -> &'static str {
+    SECTION_NAMES.iter().find(|(t, _)| *t == tag).map_or("unknown", |(_, name)| name)
+}
+
+fn checksum(bytes: &[u8]) -> Fingerprint128 {
+    let mut builder = FingerprintBuilder::new();
+    builder.write_bytes(bytes);
+    builder.finish()
+}
+
+fn split(bytes: &[u8], at: usize) -> Result<(&[u8], &[u8]), BinaryMetadataError> {
+    if bytes.len() < at {
+        Err(BinaryMetadataError::Truncated)
+    } else {
+        Ok(bytes.split_at(at))
+    }
+}
+
+fn read_strings(payload: &[u8]) -> Result<Vec<Box<str>>, BinaryMetadataError> {
+    let mut reader = Reader { input: payload, strings: &[] };
+    let count = reader.length()?;
+    let mut strings = Vec::with_capacity(count);
+    for _ in 0..count {
+        let length = reader.length()?;
+        let bytes = reader.take(length)?;
+        let string = std::str::from_utf8(bytes).map_err(|e| BinaryMetadataError::Custom(format!("string table: {e}")))?;
+        strings.push(string.into());
+    }
+    reader.finish(SECTION_STRINGS)?;
+    Ok(strings)
+}
+
+fn read_section<'de, T: Deserialize<'de>>(payload: &'de [u8], strings: &[Box<str>], tag: u8) -> Result<T, BinaryMetadataError> {
+    let mut reader = Reader { input: payload, strings };
+    let value = T::deserialize(&mut reader)?;
+    reader.finish(tag)?;
+    Ok(value)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Distinct strings in first-use order, shared by every section.
+#[derive(Default)]
+struct StringTable {
+    strings: Vec<Box<str>>,
+    indices: HashMap<Box<str>, u64>,
+}
+
+impl StringTable {
+    fn intern(&mut self, value: &str) -> u64 {
+        if let Some(&index) = self.indices.get(value) {
+            return index;
+        }
+        let index = self.strings.len() as u64;
+        self.strings.push(value.into());
+        self.indices.insert(value.into(), index);
+        index
+    }
+
+    fn write<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<Vec<u8>, BinaryMetadataError> {
+        let mut writer = Writer {
+            out: Vec::new(),
+            strings: self,
+        };
+        value.serialize(&mut writer)?;
+        Ok(writer.out)
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_varint(&mut out, self.strings.len() as u64);
+        for string in &self.strings {
+            write_varint(&mut out, string.len() as u64);
+            out.extend_from_slice(string.as_bytes());
+        }
+        out
+    }
+}
+
+struct Writer<'t> {
+    out: Vec<u8>,
+    strings: &'t mut StringTable,
+}
+
+impl Writer<'_> {
+    fn length(&mut self, len: Option<usize>) -> Result<(), BinaryMetadataError> {
+        let len = len.ok_or_else(|| BinaryMetadataError::Custom("sequences must know their length".to_string()))?;
+        write_varint(&mut self.out, len as u64);
+        Ok(())
+    }
+}
+
+impl Serializer for &mut Writer<'_> {
+    type Ok = ();
+    type Error = BinaryMetadataError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Self::Error> {
+        self.out.push(v as u8);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Self::Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Self::Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Self::Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Self::Error> {
+        write_varint(&mut self.out, zigzag(v));
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Self::Error> {
+        self.out.push(v);
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Self::Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Self::Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Self::Error> {
+        write_varint(&mut self.out, v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Self::Error> {
+        self.out.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Self::Error> {
+        self.out.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Self::Error> {
+        self.serialize_u32(v.into())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Self::Error> {
+        let index = self.strings.intern(v);
+        write_varint(&mut self.out, index);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Self::Error> {
+        write_varint(&mut self.out, v.len() as u64);
+        self.out.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), Self::Error> {
+        self.out.push(0);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Self::Error> {
+        self.out.push(1);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, variant_index: u32, _variant: &'static str) -> Result<(), Self::Error> {
+        self.serialize_u32(variant_index)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        write_varint(&mut self.out, variant_index.into());
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, Self::Error> {
+        self.length(len)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self, Self::Error> {
+        write_varint(&mut self.out, variant_index.into());
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self, Self::Error> {
+        self.length(len)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self, Self::Error> {
+        write_varint(&mut self.out, variant_index.into());
+        Ok(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+impl ser::SerializeSeq for &mut Writer<'_> {
+    type Ok = ();
+    type Error = BinaryMetadataError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for &mut Writer<'_> {
+    type Ok = ();
+    type Error = BinaryMetadataError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut Writer<'_> {
+    type Ok = ();
+    type Error = BinaryMetadataError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut Writer<'_> {
+    type Ok = ();
+    type Error = BinaryMetadataError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for &mut Writer<'_> {
+    type Ok = ();
+    type Error = BinaryMetadataError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for &mut Writer<'_> {
+    type Ok = ();
+    type Error = BinaryMetadataError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for &mut Writer<'_> {
+    type Ok = ();
+    type Error = BinaryMetadataError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+struct Reader<'de, 's> {
+    input: &'de [u8],
+    strings: &'s [Box<str>],
+}
+
+impl<'de> Reader<'de, '_> {
+    fn take(&mut self, len: usize) -> Result<&'de [u8], BinaryMetadataError> {
+        let (head, tail) = split(self.input, len)?;
+        self.input = tail;
+        Ok(head)
+    }
+
+    fn byte(&mut self) -> Result<u8, BinaryMetadataError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64, BinaryMetadataError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            let bits = u64::from(byte & 0x7f);
+            if shift == 63 && bits > 1 {
+                break;
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(BinaryMetadataError::Custom("varint overflows 64 bits".to_string()))
+    }
+
+    fn narrow<T: TryFrom<u64>>(&mut self) -> Result<T, BinaryMetadataError> {
+        let value = self.varint()?;
+        T::try_from(value).map_err(|_| BinaryMetadataError::Custom(format!("integer {value} out of range")))
+    }
+
+    /// A sequence length. Every element of the metadata schema encodes to at
+    /// least one byte, so a length beyond the remaining input is corrupt and
+    /// is refused before anything is allocated for it.
+    fn length(&mut self) -> Result<usize, BinaryMetadataError> {
+        let len: usize = self.narrow()?;
+        if len > self.input.len() {
+            return Err(BinaryMetadataError::Truncated);
+        }
+        Ok(len)
+    }
+
+    fn signed(&mut self) -> Result<i64, BinaryMetadataError> {
+        let raw = self.varint()?;
+        Ok((raw >> 1) as i64 ^ -((raw & 1) as i64))
+    }
+
+    fn string(&mut self) -> Result<&str, BinaryMetadataError> {
+        let index: usize = self.narrow()?;
+        self.strings.get(index).map(|s| &**s).ok_or(BinaryMetadataError::InvalidString {
+            index,
+            total: self.strings.len(),
+        })
+    }
+
+    fn finish(&self, tag: u8) -> Result<(), BinaryMetadataError> {
+        if self.input.is_empty() {
+            Ok(())
+        } else {
+            Err(BinaryMetadataError::TrailingBytes {
+                section: section_name(tag),
+                count: self.input.len(),
+            })
+        }
+    }
+}
+
+struct Counted<'r, 'de, 's> {
+    reader: &'r mut Reader<'de, 's>,
+    remaining: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for Counted<'_, 'de, '_> {
+    type Error = BinaryMetadataError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.reader).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Counted<'_, 'de, '_> {
+    type Error = BinaryMetadataError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.reader).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        seed.deserialize(&mut *self.reader)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> Deserializer<'de> for &mut Reader<'de, '_> {
+    type Error = BinaryMetadataError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(BinaryMetadataError::Custom("binary metadata is not self-describing".to_string()))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.byte()? {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            other => Err(BinaryMetadataError::Custom(format!("invalid bool {other}"))),
+        }
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let value = self.signed()?;
+        visitor.visit_i8(i8::try_from(value).map_err(de::Error::custom)?)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let value = self.signed()?;
+        visitor.visit_i16(i16::try_from(value).map_err(de::Error::custom)?)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let value = self.signed()?;
+        visitor.visit_i32(i32::try_from(value).map_err(de::Error::custom)?)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i64(self.signed()?)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u8(self.byte()?)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u16(self.narrow()?)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u32(self.narrow()?)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u64(self.varint()?)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_f32(f32::from_le_bytes(self.take(4)?.try_into().expect("four bytes")))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_f64(f64::from_le_bytes(self.take(8)?.try_into().expect("eight bytes")))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let value: u32 = self.narrow()?;
+        visitor.visit_char(char::from_u32(value).ok_or_else(|| BinaryMetadataError::Custom(format!("invalid char {value}")))?)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_str(self.string()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let len = self.length()?;
+        visitor.visit_borrowed_bytes(self.take(len)?)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.byte()? {
+            0 => visitor.visit_none(),
+            1 => visitor.visit_some(self),
+            other => Err(BinaryMetadataError::Custom(format!("invalid option tag {other}"))),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let remaining = self.length()?;
+        visitor.visit_seq(Counted { reader: self, remaining })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(Counted { reader: self, remaining: len })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(self, _name: &'static str, len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let remaining = self.length()?;
+        visitor.visit_map(Counted { reader: self, remaining })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, _name: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(BinaryMetadataError::Custom("binary metadata has no field names".to_string()))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_any(visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut Reader<'de, '_> {
+    type Error = BinaryMetadataError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Self::Error> {
+        let index: u32 = self.narrow()?;
+        let value = seed.deserialize(IntoDeserializer::<BinaryMetadataError>::into_deserializer(index))?;
+        Ok((value, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Reader<'de, '_> {
+    type Error = BinaryMetadataError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(Counted { reader: self, remaining: len })
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(Counted {
+            reader: self,
+            remaining: fields.len(),
+        })
+    }
+}
@@ -1,5 +1,6 @@
 //! Deterministic JSON / binary serialization wrappers.
 
+use crate::binary::{self, BinaryMetadataError};
 use crate::bundle::SemanticMetadataBundle;
 use crate::validate::{MetadataValidationError, ValidationLimits, validate_metadata_bundle};
 
@@ -10,22 +11,43 @@ pub fn encode_metadata_json(bundle: &SemanticMetadataBundle) -> Result<String, s
 
 /// Decodes and validates a metadata bundle from a JSON string.
 pub fn decode_metadata_json(json: &str, limits: &ValidationLimits) -> Result<SemanticMetadataBundle, MetadataDecodeError> {
-    if json.len() > limits.max_total_bytes {
+    check_size(json.len(), limits)?;
+    let bundle: SemanticMetadataBundle = serde_json::from_str(json).map_err(MetadataDecodeError::Json)?;
+    validate_metadata_bundle(&bundle, limits).map_err(MetadataDecodeError::Validation)?;
+    Ok(bundle)
+}
+
+/// Encodes a metadata bundle in the compact binary format described in
+/// [`crate::binary`].
+pub fn encode_metadata_binary(bundle: &SemanticMetadataBundle) -> Result<Vec<u8>, BinaryMetadataError> {
+    binary::write_bundle(bundle)
+}
+
+/// Decodes and validates a metadata bundle from the binary format.
+pub fn decode_metadata_binary(bytes: &[u8], limits: &ValidationLimits) -> Result<SemanticMetadataBundle, MetadataDecodeError> {
+    check_size(bytes.len(), limits)?;
+    let bundle = binary::read_bundle(bytes)?;
+    validate_metadata_bundle(&bundle, limits).map_err(MetadataDecodeError::Validation)?;
+    Ok(bundle)
+}
+
+fn check_size(len: usize, limits: &ValidationLimits) -> Result<(), MetadataDecodeError> {
+    if len > limits.max_total_bytes {
         return Err(MetadataDecodeError::Validation(MetadataValidationError::BudgetExceeded {
             resource: "bytes",
-            count: json.len(),
+            count: len,
             limit: limits.max_total_bytes,
         }));
     }
-    let bundle: SemanticMetadataBundle = serde_json::from_str(json).map_err(MetadataDecodeError::Json)?;
-    validate_metadata_bundle(&bundle, limits).map_err(MetadataDecodeError::Validation)?;
-    Ok(bundle)
+    Ok(())
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum MetadataDecodeError {
     #[error("json decode error: {0}")]
     Json(#[from] serde_json::Error),
+    #[error("binary decode error: {0}")]
+    Binary(#[from] BinaryMetadataError),
     #[error("validation error: {0}")]
     Validation(#[from] MetadataValidationError),
 }
@@ -7,6 +7,11 @@ pub const TYPE_METADATA_SCHEMA_VERSION: u32 = 1;
 pub const SEMANTIC_MODEL_VERSION: u32 = 1;
 pub const NATIVE_SURFACE_SCHEMA_VERSION: u32 = 1;
 
+/// Leading bytes of a binary-encoded metadata bundle.
+pub const BINARY_METADATA_MAGIC: [u8; 4] = *b"PHMB";
+/// Version of the binary container layout (see [`crate::binary`]).
+pub const BINARY_METADATA_FORMAT_VERSION: u32 = 1;
+
 /// Metadata retention profile.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
 pub enum MetadataProfile {
@@ -1,5 +1,6 @@
 //! Standalone, store-independent, versioned semantic metadata models, indexed graphs, and artifact schema.
 
+pub mod binary;
 pub mod bundle;
 pub mod declaration;
 pub mod encode;
@@ -12,6 +13,7 @@ pub mod scoped_type;
 pub mod type_node;
 pub mod validate;
 
+pub use binary::BinaryMetadataError;
 pub use bundle::*;
 pub use declaration::*;
 pub use encode::*;
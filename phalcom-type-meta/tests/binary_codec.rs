@@ -0,0 +1,521 @@
+//! Property tests: random valid bundles round-trip through JSON and binary to
+//! equal bundles, and binary encoding is byte-for-byte deterministic.
+
+use phalcom_type_meta::*;
+
+/// SplitMix64; a fixed seed per case keeps failures reproducible.
+struct Gen(u64);
+
+impl Gen {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn u32(&mut self) -> u32 {
+        // Favour small values, but cover every varint width.
+        match self.below(4) {
+            0 => u32::MAX - self.below(3) as u32,
+            _ => (self.next() >> (self.below(32) + 32)) as u32,
+        }
+    }
+
+    fn flag(&mut self) -> bool {
+        self.below(2) == 0
+    }
+
+    fn pick<T: Clone>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len())].clone()
+    }
+
+    fn many<T>(&mut self, max: usize, mut item: impl FnMut(&mut Self) -> T) -> Box<[T]> {
+        let count = self.below(max + 1);
+        (0..count).map(|_| item(self)).collect()
+    }
+
+    fn maybe<T>(&mut self, item: impl FnOnce(&mut Self) -> T) -> Option<T> {
+        if self.flag() { Some(item(self)) } else { None }
+    }
+
+    fn string(&mut self) -> Box<str> {
+        const POOL: [&str; 6] = ["", "List", "map", "größe", "名前", "a.b"];
+        if self.flag() {
+            self.pick(&POOL).into()
+        } else {
+            (0..self.below(12))
+                .map(|_| char::from_u32(0x20 + self.below(0x500) as u32).unwrap_or('?'))
+                .collect::<String>()
+                .into()
+        }
+    }
+
+    fn fingerprint(&mut self) -> Fingerprint128 {
+        Fingerprint128::from_u128(((self.next() as u128) << 64) | self.next() as u128)
+    }
+
+    fn span(&mut self) -> Option<SourceSpanRef> {
+        self.maybe(|g| SourceSpanRef { start: g.u32(), end: g.u32() })
+    }
+
+    fn project(&mut self) -> StableProjectRef {
+        match self.below(4) {
+            0 => StableProjectRef::Builtin {
+                namespace: self.string(),
+                version: self.string(),
+            },
+            1 => StableProjectRef::Package {
+                package: self.string(),
+                version: self.string(),
+                artifact_fingerprint: self.fingerprint(),
+            },
+            2 => StableProjectRef::SourceArtifact {
+                logical_uri: self.string(),
+                source_fingerprint: self.fingerprint(),
+            },
+            _ => StableProjectRef::Session {
+                session_fingerprint: self.fingerprint(),
+            },
+        }
+    }
+
+    fn module(&mut self) -> StableModuleRef {
+        StableModuleRef {
+            project: self.project(),
+            path: self.many(3, Self::string),
+        }
+    }
+
+    fn declaration(&mut self) -> StableDeclarationRef {
+        StableDeclarationRef {
+            module: self.module(),
+            path: self.many(2, Self::string),
+        }
+    }
+
+    fn side(&mut self) -> StableDispatchSide {
+        self.pick(&[StableDispatchSide::Instance, StableDispatchSide::Class])
+    }
+
+    fn callable(&mut self) -> StableCallableRef {
+        StableCallableRef {
+            owner: self.declaration(),
+            side: self.side(),
+            selector: self.string(),
+        }
+    }
+
+    fn field(&mut self) -> StableFieldRef {
+        StableFieldRef {
+            owner: self.declaration(),
+            side: self.side(),
+            name: self.string(),
+        }
+    }
+
+    fn owner(&mut self) -> StableTypeParameterOwnerRef {
+        if self.flag() {
+            StableTypeParameterOwnerRef::Declaration(self.declaration())
+        } else {
+            StableTypeParameterOwnerRef::Callable(self.callable())
+        }
+    }
+
+    fn slot(&mut self, types: usize) -> PublishedTypeSlot {
+        match self.below(4) {
+            0 if types > 0 => PublishedTypeSlot::Known {
+                form: TypeNodeId(self.below(types) as u32),
+                authority: self.pick(&[
+                    PublishedTypeAuthority::DeclaredAnnotation,
+                    PublishedTypeAuthority::TrustedNative,
+                    PublishedTypeAuthority::GeneratedDeclaration,
+                    PublishedTypeAuthority::CompilerInferred,
+                ]),
+            },
+            1 => PublishedTypeSlot::Dynamic {
+                reason: self.pick(&[
+                    DynamicReasonRef::ExplicitEscape,
+                    DynamicReasonRef::UncheckedBoundary,
+                    DynamicReasonRef::UnsupportedNative,
+                ]),
+            },
+            2 => PublishedTypeSlot::Unknown {
+                reason: self.pick(&[
+                    UnknownReasonRef::UnannotatedDeclaration,
+                    UnknownReasonRef::InferenceFailed,
+                    UnknownReasonRef::OpaqueNative,
+                ]),
+            },
+            _ => PublishedTypeSlot::Unavailable {
+                reason: self.pick(&[
+                    MetadataUnavailableReason::StrippedByProfile,
+                    MetadataUnavailableReason::UnloadedModule,
+                    MetadataUnavailableReason::IncompatibleModel,
+                    MetadataUnavailableReason::DynamicReplacement,
+                ]),
+            },
+        }
+    }
+
+    fn kinds(&mut self) -> Box<[KindNodeEntry]> {
+        let count = 1 + self.below(6);
+        (0..count)
+            .map(|i| KindNodeEntry {
+                node: if i == 0 || self.flag() {
+                    KindNode::Type
+                } else {
+                    KindNode::Arrow {
+                        parameters: self.many(3, |g| KindNodeId(g.below(i) as u32)),
+                        result: KindNodeId(self.below(i) as u32),
+                    }
+                },
+                structural_fingerprint: self.fingerprint(),
+            })
+            .collect()
+    }
+
+    fn scoped_types(&mut self, kinds: usize, types: usize) -> Box<[ScopedTypeNodeEntry]> {
+        let count = self.below(8);
+        (0..count)
+            .map(|i| {
+                let form = match self.below(if i == 0 { 2 } else { 8 }) {
+                    0 => ScopedTypeNode::Bound {
+                        depth: self.below(4) as u32,
+                        index: self.u32(),
+                    },
+                    1 if types > 0 => ScopedTypeNode::Free(TypeNodeId(self.below(types) as u32)),
+                    1 => ScopedTypeNode::Bound { depth: 0, index: 0 },
+                    2 => ScopedTypeNode::Applied {
+                        origin: ScopedTypeNodeId(self.below(i) as u32),
+                        arguments: self.many(3, |g| ScopedTypeNodeId(g.below(i) as u32)),
+                    },
+                    3 => ScopedTypeNode::Union(self.many(3, |g| ScopedTypeNodeId(g.below(i) as u32))),
+                    4 => ScopedTypeNode::Tuple(self.many(3, |g| ScopedTupleElementRef {
+                        label: g.maybe(Self::string),
+                        ty: ScopedTypeNodeId(g.below(i) as u32),
+                    })),
+                    5 => ScopedTypeNode::Record(self.many(3, |g| ScopedRecordFieldRef {
+                        name: g.string(),
+                        ty: ScopedTypeNodeId(g.below(i) as u32),
+                    })),
+                    6 => ScopedTypeNode::Callable(ScopedCallableTypeRef {
+                        parameters: self.many(3, |g| ScopedCallableParamRef {
+                            label: g.maybe(Self::string),
+                            ty: ScopedTypeNodeId(g.below(i) as u32),
+                            rest: g.flag(),
+                        }),
+                        return_type: ScopedTypeNodeId(self.below(i) as u32),
+                    }),
+                    _ => ScopedTypeNode::Lambda {
+                        parameter_kinds: self.many(2, |g| KindNodeId(g.below(kinds) as u32)),
+                        body: ScopedTypeNodeId(self.below(i) as u32),
+                    },
+                };
+                ScopedTypeNodeEntry {
+                    kind: KindNodeId(self.below(kinds) as u32),
+                    form,
+                    structural_fingerprint: self.fingerprint(),
+                }
+            })
+            .collect()
+    }
+
+    /// Type nodes; lambda bodies point into a scoped table of `scoped` nodes.
+    fn types(&mut self, kinds: usize, scoped: usize) -> Box<[TypeNodeEntry]> {
+        let count = 1 + self.below(10);
+        (0..count)
+            .map(|i| {
+                let earlier = |g: &mut Self| TypeNodeId(g.below(i) as u32);
+                let form = match self.below(if i == 0 { 5 } else { 11 }) {
+                    0 => TypeNode::Never,
+                    1 => TypeNode::Unit,
+                    2 => TypeNode::Nominal {
+                        declaration: self.declaration(),
+                    },
+                    3 => TypeNode::Parameter(StableTypeParameterRef {
+                        owner: self.owner(),
+                        index: self.u32(),
+                    }),
+                    4 => TypeNode::SelfType(SelfTypeRef {
+                        owner: self.declaration(),
+                        side: self.side(),
+                        role: self.pick(&[SelfRoleRef::InstanceType, SelfRoleRef::ReceiverValue]),
+                    }),
+                    5 => TypeNode::Applied {
+                        origin: earlier(self),
+                        arguments: self.many(3, earlier),
+                    },
+                    6 => TypeNode::Union(self.many(4, earlier)),
+                    7 => TypeNode::Tuple(self.many(3, |g| TupleElementRef {
+                        label: g.maybe(Self::string),
+                        ty: earlier(g),
+                    })),
+                    8 => TypeNode::Record(self.many(3, |g| RecordFieldRef {
+                        name: g.string(),
+                        ty: earlier(g),
+                    })),
+                    9 => TypeNode::Callable(CallableTypeRef {
+                        parameters: self.many(3, |g| CallableParamRef {
+                            label: g.maybe(Self::string),
+                            ty: earlier(g),
+                            rest: g.flag(),
+                        }),
+                        return_type: earlier(self),
+                    }),
+                    _ if scoped > 0 => TypeNode::TypeLambda(TypeLambdaRef {
+                        parameter_kinds: self.many(2, |g| KindNodeId(g.below(kinds) as u32)),
+                        body: ScopedTypeNodeId(self.below(scoped) as u32),
+                    }),
+                    _ => TypeNode::Unit,
+                };
+                TypeNodeEntry {
+                    kind: KindNodeId(self.below(kinds) as u32),
+                    form,
+                    structural_fingerprint: self.fingerprint(),
+                }
+            })
+            .collect()
+    }
+
+    fn bundle(&mut self) -> SemanticMetadataBundle {
+        let kinds = self.kinds();
+        let scoped_count = self.below(8);
+        let types = self.types(kinds.len(), scoped_count);
+        let mut scoped_types = self.scoped_types(kinds.len(), types.len()).into_vec();
+        scoped_types.resize_with(scoped_count, || ScopedTypeNodeEntry {
+            kind: KindNodeId(0),
+            form: ScopedTypeNode::Bound { depth: 0, index: 0 },
+            structural_fingerprint: Fingerprint128::ZERO,
+        });
+        let (k, t) = (kinds.len(), types.len());
+        let ty = |g: &mut Self| TypeNodeId(g.below(t) as u32);
+
+        // Parameter owners must be unique per (owner, index).
+        let parameters: Box<[TypeParameterRecord]> = (0..self.below(4) as u32)
+            .map(|index| TypeParameterRecord {
+                id: StableTypeParameterRef { owner: self.owner(), index },
+                name: self.string(),
+                kind: KindNodeId(self.below(k) as u32),
+                variance: self.pick(&[VarianceRef::Covariant, VarianceRef::Contravariant, VarianceRef::Invariant]),
+                source: self.span(),
+            })
+            .collect();
+        let generic_signatures = self.many(3, |g| GenericSignatureRecord {
+            owner: g.owner(),
+            parameters: g.many(2, |g| StableTypeParameterRef {
+                owner: g.owner(),
+                index: g.u32(),
+            }),
+            constraints: g.many(2, |g| {
+                if g.flag() {
+                    GenericConstraintRef::Subtype { lower: ty(g), upper: ty(g) }
+                } else {
+                    GenericConstraintRef::Equivalent { left: ty(g), right: ty(g) }
+                }
+            }),
+        });
+        let signatures = generic_signatures.len();
+        let signature = |g: &mut Self| {
+            if signatures > 0 && g.flag() {
+                Some(GenericSignatureRecordId(g.below(signatures) as u32))
+            } else {
+                None
+            }
+        };
+
+        SemanticMetadataBundle {
+            header: SemanticMetadataHeader {
+                schema_version: TYPE_METADATA_SCHEMA_VERSION,
+                semantic_model_version: SEMANTIC_MODEL_VERSION,
+                producer: ProducerIdentity(self.string()),
+                producer_version: self.string(),
+                native_surface_schema_version: self.u32(),
+                profile: self.pick(&[
+                    MetadataProfile::RuntimeMinimal,
+                    MetadataProfile::RuntimePublic,
+                    MetadataProfile::ToolingDebug,
+                    MetadataProfile::Proof,
+                ]),
+                features: MetadataFeatures {
+                    type_lambdas: self.flag(),
+                    record_rows: self.flag(),
+                    runtime_type_constants: self.flag(),
+                    source_occurrences: self.flag(),
+                    advanced_sections: self.many(2, |g| FeatureSectionId(g.string())),
+                },
+                identity_scheme: self.pick(&[ArtifactIdentityScheme::V1Standard, ArtifactIdentityScheme::SessionLocal]),
+                source_fingerprint: self.fingerprint(),
+                interface_fingerprint: self.fingerprint(),
+            },
+            kinds,
+            parameters,
+            declarations: self.many(4, |g| DeclarationTypeRecord {
+                declaration: g.declaration(),
+                form: ty(g),
+                kind: KindNodeId(g.below(k) as u32),
+                generic_signature: signature(g),
+                superclass_template: g.maybe(ty),
+                instance_callables: g.many(2, Self::callable),
+                class_callables: g.many(2, Self::callable),
+                instance_fields: g.many(2, Self::field),
+                class_fields: g.many(2, Self::field),
+                flags: DeclarationTypeFlags {
+                    is_abstract: g.flag(),
+                    is_final: g.flag(),
+                    is_trait: g.flag(),
+                },
+                source: g.span(),
+            }),
+            aliases: self.many(2, |g| TypeAliasRecord {
+                declaration: g.declaration(),
+                generic_signature: signature(g),
+                target: ty(g),
+                source: g.span(),
+            }),
+            callables: self.many(4, |g| CallableSemanticRecord {
+                callable: g.callable(),
+                generic_signature: signature(g),
+                parameters: g.many(3, |g| CallableParameterRecord {
+                    index: g.u32(),
+                    local_name: g.string(),
+                    external_label: g.maybe(Self::string),
+                    rest: g.pick(&[RestModeRef::None, RestModeRef::Anonymous, RestModeRef::Named]),
+                    ty: g.slot(t),
+                    source: g.span(),
+                }),
+                return_type: g.slot(t),
+                source: g.span(),
+            }),
+            fields: self.many(3, |g| FieldSemanticRecord {
+                field: g.field(),
+                mutability: g.pick(&[FieldMutabilityRef::Immutable, FieldMutabilityRef::Mutable]),
+                ty: g.slot(t),
+                source: g.span(),
+            }),
+            module_roots: self.many(2, |g| ModuleMetadataRoot {
+                module: g.module(),
+                declarations: g.many(3, |g| DeclarationRecordId(g.u32())),
+                aliases: g.many(2, |g| AliasRecordId(g.u32())),
+                callables: g.many(3, |g| CallableRecordId(g.u32())),
+                fields: g.many(3, |g| FieldRecordId(g.u32())),
+                interface_fingerprint: g.fingerprint(),
+            }),
+            runtime_roots: self.many(2, |g| RuntimeTypeFormRoot {
+                module: g.module(),
+                local_key: RuntimeTypeFormKey(g.string()),
+                form: ty(g),
+            }),
+            occurrences: self.many(3, |g| TypeUseRecord {
+                role: g.pick(&[
+                    TypeUseRoleRef::Parameter,
+                    TypeUseRoleRef::Return,
+                    TypeUseRoleRef::Field,
+                    TypeUseRoleRef::Superclass,
+                    TypeUseRoleRef::TypeArgument,
+                    TypeUseRoleRef::TypeConstant,
+                ]),
+                status: match g.below(5) {
+                    0 => TypeUseStatusRef::Known(ty(g)),
+                    1 => TypeUseStatusRef::InternalClassObject(g.declaration()),
+                    2 => TypeUseStatusRef::Dynamic(DynamicReasonRef::ExplicitEscape),
+                    3 => TypeUseStatusRef::Missing,
+                    _ => TypeUseStatusRef::Unknown(UnknownReasonRef::InferenceFailed),
+                },
+                written: g.maybe(Self::string),
+                source: g.span(),
+            }),
+            extensions: self.many(2, |g| MetadataExtensionSection {
+                feature: FeatureSectionId(g.string()),
+                schema_version: g.u32(),
+                required: g.flag(),
+                semantic_fingerprint: g.fingerprint(),
+                payload: g.many(40, |g| g.next() as u8),
+            }),
+            types,
+            scoped_types: scoped_types.into(),
+            generic_signatures,
+        }
+    }
+}
+
+const CASES: u64 = 256;
+
+#[test]
+fn json_and_binary_round_trip_to_equal_bundles() {
+    let limits = ValidationLimits::default();
+    for seed in 0..CASES {
+        let bundle = Gen(seed).bundle();
+        validate_metadata_bundle(&bundle, &limits).unwrap_or_else(|e| panic!("seed {seed}: generated an invalid bundle: {e}"));
+
+        let json = encode_metadata_json(&bundle).unwrap();
+        let from_json = decode_metadata_json(&json, &limits).unwrap();
+        let binary = encode_metadata_binary(&bundle).unwrap();
+        let from_binary = decode_metadata_binary(&binary, &limits).unwrap_or_else(|e| panic!("seed {seed}: {e}"));
+        assert_eq!(from_json, bundle, "seed {seed}");
+        assert_eq!(from_binary, bundle, "seed {seed}");
+        assert_eq!(encode_metadata_binary(&from_json).unwrap(), binary, "seed {seed}");
+        assert!(binary.len() < json.len(), "seed {seed}: {} >= {}", binary.len(), json.len());
+    }
+}
+
+#[test]
+fn binary_encoding_is_deterministic() {
+    for seed in 0..CASES {
+        let first = encode_metadata_binary(&Gen(seed).bundle()).unwrap();
+        let second = encode_metadata_binary(&Gen(seed).bundle()).unwrap();
+        assert_eq!(first, second, "seed {seed}");
+    }
+}
+
+#[test]
+fn corrupt_binary_is_rejected() {
+    let limits = ValidationLimits::default();
+    let binary = encode_metadata_binary(&Gen(7).bundle()).unwrap();
+    for seed in 0..CASES {
+        let mut rng = Gen(seed);
+        let mut corrupt = binary.clone();
+        let at = rng.below(corrupt.len());
+        corrupt[at] ^= 1 << rng.below(8);
+        assert!(decode_metadata_binary(&corrupt, &limits).is_err(), "seed {seed}: flipped byte {at}");
+        let cut = rng.below(binary.len());
+        assert!(decode_metadata_binary(&binary[..cut], &limits).is_err(), "seed {seed}: truncated to {cut}");
+    }
+}
+
+#[test]
+fn binary_decoding_applies_validation_limits() {
+    let bundle = Gen(3).bundle();
+    let binary = encode_metadata_binary(&bundle).unwrap();
+
+    let limits = ValidationLimits {
+        max_total_bytes: binary.len() - 1,
+        ..ValidationLimits::default()
+    };
+    let err = decode_metadata_binary(&binary, &limits).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            MetadataDecodeError::Validation(MetadataValidationError::BudgetExceeded { resource: "bytes", .. })
+        ),
+        "{err}"
+    );
+
+    let limits = ValidationLimits {
+        max_type_nodes: bundle.types.len() - 1,
+        ..ValidationLimits::default()
+    };
+    let err = decode_metadata_binary(&binary, &limits).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            MetadataDecodeError::Validation(MetadataValidationError::BudgetExceeded { resource: "types", .. })
+        ),
+        "{err}"
+    );
+}
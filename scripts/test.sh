@@ -7,6 +7,7 @@
 #   scripts/test.sh core-integration [cargo test args...]
 #   scripts/test.sh lang [label] [-- cargo test args...]
 #   scripts/test.sh invariants [cargo test args...]
+#   scripts/test.sh gc-stress [cargo test args...]
 #   scripts/test.sh lsp [cargo test args...]
 #   scripts/test.sh repl [cargo test args...]
 #   scripts/test.sh workspace
@@ -25,6 +26,7 @@ Focused lanes:
   core-integration  phalcom-core Rust integration target
   lang [label]      language acceptance corpus, optionally one label
   invariants        object-model invariant target
+  gc-stress         invariants and lang under PHALCOM_GC_STRESS/PHALCOM_GC_SLICE
   lsp               all LSP integration stages
   repl              REPL integration target
 
@@ -61,6 +63,14 @@ case "$lane" in
   invariants)
     cargo test -p phalcom-core --test invariants "$@"
     ;;
+  gc-stress)
+    # Collect at every 64th safepoint and mark in small incremental slices, so
+    # the object-model and language suites run with collections interleaved
+    # mid-cycle. Either variable may be overridden from the environment.
+    export PHALCOM_GC_STRESS="${PHALCOM_GC_STRESS:-64}"
+    export PHALCOM_GC_SLICE="${PHALCOM_GC_SLICE:-64}"
+    cargo test -p phalcom-core --test invariants --test lang "$@"
+    ;;
   lsp)
     cargo test -p phalcom-lsp "$@"
     ;;